@@ -181,7 +181,8 @@ impl<'a> ArithmeticOnlyChecker<'a> {
             | FetchEntry | SetEntry | DeleteEntry | InsertEntry | SetVar | MintAsset
             | MintToken | TransferAsset | TransferToken | ContractCall | StxTransfer
             | StxTransferMemo | StxBurn | AtBlock | GetStxBalance | GetTokenSupply | BurnToken
-            | FromConsensusBuff | ToConsensusBuff | BurnAsset | StxGetAccount => {
+            | FromConsensusBuff | ToConsensusBuff | BurnAsset | StxGetAccount
+            | GetStackingState => {
                 return Err(Error::FunctionNotPermitted(function));
             }
             Append | Concat | AsMaxLen | ContractOf | PrincipalOf | ListCons | Print
@@ -192,7 +193,7 @@ impl<'a> ArithmeticOnlyChecker<'a> {
             BuffToIntLe | BuffToUIntLe | BuffToIntBe | BuffToUIntBe => {
                 return Err(Error::FunctionNotPermitted(function));
             }
-            IsStandard | PrincipalDestruct | PrincipalConstruct => {
+            IsStandard | PrincipalDestruct | PrincipalConstruct | PrincipalVersion => {
                 return Err(Error::FunctionNotPermitted(function));
             }
             IntToAscii | IntToUtf8 | StringToInt | StringToUInt => {
@@ -746,6 +746,18 @@ impl TypedNativeFunction {
                 )],
                 returns: TypeSignature::BoolType,
             }))),
+            PrincipalVersion => Simple(SimpleNativeFunction(FunctionType::Fixed(FixedFunction {
+                args: vec![FunctionArg::new(
+                    TypeSignature::PrincipalType,
+                    ClarityName::try_from("value".to_owned())
+                        .expect("FAIL: ClarityName failed to accept default arg name"),
+                )],
+                returns: TypeSignature::new_response(
+                    TypeSignature::UIntType,
+                    TypeSignature::UIntType,
+                )
+                .expect("FAIL: PrincipalVersion failed to initialize type signature"),
+            }))),
             BuffToIntLe | BuffToIntBe => {
                 Simple(SimpleNativeFunction(FunctionType::Fixed(FixedFunction {
                     args: vec![FunctionArg::new(
@@ -897,6 +909,29 @@ impl TypedNativeFunction {
                 .expect("FAIL: StxGetAccount failed to initialize type signature")
                 .into(),
             }))),
+            GetStackingState => Simple(SimpleNativeFunction(FunctionType::Fixed(FixedFunction {
+                args: vec![FunctionArg::new(
+                    TypeSignature::PrincipalType,
+                    ClarityName::try_from("principal".to_owned())
+                        .expect("FAIL: ClarityName failed to accept default arg name"),
+                )],
+                returns: TypeSignature::new_option(
+                    TupleTypeSignature::try_from(vec![
+                        ("stacked-amount".into(), TypeSignature::UIntType),
+                        ("reward-cycle".into(), TypeSignature::UIntType),
+                        ("lock-period".into(), TypeSignature::UIntType),
+                        (
+                            "delegated-to".into(),
+                            TypeSignature::new_option(TypeSignature::PrincipalType).expect(
+                                "FAIL: GetStackingState failed to initialize type signature",
+                            ),
+                        ),
+                    ])
+                    .expect("FAIL: GetStackingState failed to initialize type signature")
+                    .into(),
+                )
+                .expect("FAIL: GetStackingState failed to initialize type signature"),
+            }))),
             StxBurn => Simple(SimpleNativeFunction(FunctionType::Fixed(FixedFunction {
                 args: vec![
                     FunctionArg::new(
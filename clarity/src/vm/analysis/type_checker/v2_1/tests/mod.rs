@@ -1829,6 +1829,45 @@ fn test_replace_at_utf8() {
     }
 }
 
+#[test]
+fn test_slice_and_replace_at_max_size_list() {
+    // (list 65535 int) is the longest list of ints that fits in MAX_VALUE_SIZE, and its optional
+    // wrapper still fits too.
+    let good = [
+        "(slice? (unwrap-panic (as-max-len? (list 1 2 3) u65535)) u0 u2)",
+        "(slice? (unwrap-panic (as-max-len? (list 1 2 3) u65535)) u0 u0)",
+        "(slice? (unwrap-panic (as-max-len? (list 1 2 3) u65535)) u65535 u65535)",
+        "(replace-at? (unwrap-panic (as-max-len? (list 1 2 3) u65535)) u65534 4)",
+    ];
+    let expected = [
+        "(optional (list 65535 int))",
+        "(optional (list 65535 int))",
+        "(optional (list 65535 int))",
+        "(optional (list 65535 int))",
+    ];
+
+    for (good_test, expected) in good.iter().zip(expected.iter()) {
+        assert_eq!(
+            expected,
+            &format!("{}", type_check_helper(&good_test).unwrap())
+        );
+    }
+
+    // `slice?` and `replace-at?` are only available to Clarity 2 contracts
+    assert_eq!(
+        CheckErrors::UnknownFunction("slice?".to_string()),
+        type_check_helper_v1("(slice? (list 1 2 3) u0 u2)")
+            .unwrap_err()
+            .err
+    );
+    assert_eq!(
+        CheckErrors::UnknownFunction("replace-at?".to_string()),
+        type_check_helper_v1("(replace-at? (list 1 2 3) u0 4)")
+            .unwrap_err()
+            .err
+    );
+}
+
 #[test]
 fn test_native_concat() {
     let good = ["(concat (list 2 3) (list 4 5))"];
@@ -2094,6 +2133,25 @@ fn test_buffer_to_ints() {
     }
 }
 
+#[test]
+fn test_buffer_to_ints_clarity1() {
+    for function in [
+        "buff-to-int-le",
+        "buff-to-uint-le",
+        "buff-to-int-be",
+        "buff-to-uint-be",
+    ]
+    .iter()
+    {
+        assert_eq!(
+            CheckErrors::UnknownFunction(function.to_string()),
+            type_check_helper_v1(&format!("({} 0x0001)", function))
+                .unwrap_err()
+                .err
+        );
+    }
+}
+
 #[test]
 fn test_string_to_ints() {
     let good = [
@@ -2202,6 +2260,90 @@ fn test_string_to_ints() {
     }
 }
 
+#[test]
+fn test_string_to_ints_clarity1() {
+    for function in [
+        "int-to-ascii",
+        "int-to-utf8",
+        "string-to-int?",
+        "string-to-uint?",
+    ]
+    .iter()
+    {
+        let arg = if function.starts_with("int") {
+            "1"
+        } else {
+            "\"1\""
+        };
+        assert_eq!(
+            CheckErrors::UnknownFunction(function.to_string()),
+            type_check_helper_v1(&format!("({} {})", function, arg))
+                .unwrap_err()
+                .err
+        );
+    }
+}
+
+#[test]
+fn test_principal_version() {
+    assert_eq!(
+        "(response uint uint)",
+        &format!(
+            "{}",
+            type_check_helper("(principal-version 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)")
+                .unwrap()
+        )
+    );
+    assert_eq!(
+        CheckErrors::TypeError(PrincipalType, UIntType),
+        type_check_helper("(principal-version u1)").unwrap_err().err
+    );
+    assert_eq!(
+        CheckErrors::UnknownFunction("principal-version".to_string()),
+        mem_run_analysis(
+            "(principal-version tx-sender)",
+            ClarityVersion::Clarity2,
+            StacksEpochId::latest()
+        )
+        .unwrap_err()
+        .err
+    );
+}
+
+#[test]
+fn test_get_stacking_state() {
+    assert_eq!(
+        "(optional (tuple (delegated-to (optional principal)) (lock-period uint) (reward-cycle uint) (stacked-amount uint)))",
+        &format!(
+            "{}",
+            type_check_helper("(get-stacking-state 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)")
+                .unwrap()
+        )
+    );
+    assert_eq!(
+        CheckErrors::TypeError(PrincipalType, UIntType),
+        type_check_helper("(get-stacking-state u1)")
+            .unwrap_err()
+            .err
+    );
+    assert_eq!(
+        CheckErrors::UnknownFunction("get-stacking-state".to_string()),
+        type_check_helper_v1("(get-stacking-state tx-sender)")
+            .unwrap_err()
+            .err
+    );
+    assert_eq!(
+        CheckErrors::UnknownFunction("get-stacking-state".to_string()),
+        mem_run_analysis(
+            "(get-stacking-state tx-sender)",
+            ClarityVersion::Clarity2,
+            StacksEpochId::latest()
+        )
+        .unwrap_err()
+        .err
+    );
+}
+
 #[apply(test_clarity_versions_type_checker)]
 fn test_response_inference(#[case] version: ClarityVersion, #[case] epoch: StacksEpochId) {
     let good = [
@@ -3307,6 +3449,44 @@ fn test_comparison_types() {
     }
 }
 
+#[test]
+fn test_is_standard() {
+    let good = [
+        r#"(is-standard 'STB44HYPYAT2BB2QE513NSP81HTMYWBJP02HPGK6)"#,
+        r#"(is-standard 'STB44HYPYAT2BB2QE513NSP81HTMYWBJP02HPGK6.foo)"#,
+    ];
+
+    let bad = [
+        r#"(is-standard)"#,
+        r#"(is-standard 'STB44HYPYAT2BB2QE513NSP81HTMYWBJP02HPGK6 'STB44HYPYAT2BB2QE513NSP81HTMYWBJP02HPGK6)"#,
+        r#"(is-standard u10)"#,
+    ];
+    let bad_expected = [
+        CheckErrors::IncorrectArgumentCount(1, 0),
+        CheckErrors::IncorrectArgumentCount(1, 2),
+        CheckErrors::TypeError(TypeSignature::PrincipalType, UIntType),
+    ];
+
+    for good_test in good.iter() {
+        assert_eq!(
+            "bool",
+            &format!("{}", type_check_helper(&good_test).unwrap())
+        );
+    }
+
+    for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
+        assert_eq!(expected, &type_check_helper(&bad_test).unwrap_err().err);
+    }
+
+    // `is-standard` is only available to Clarity 2 contracts
+    for good_test in good.iter() {
+        assert_eq!(
+            CheckErrors::UnknownFunction("is-standard".to_string()),
+            type_check_helper_v1(&good_test).unwrap_err().err
+        );
+    }
+}
+
 #[test]
 fn test_principal_destruct() {
     let good = [
@@ -3343,6 +3523,39 @@ fn test_principal_destruct() {
     for (bad_test, expected) in bad.iter().zip(bad_expected.iter()) {
         assert_eq!(expected, &type_check_helper(&bad_test).unwrap_err().err);
     }
+
+    // `principal-destruct?` is only available to Clarity 2 contracts
+    for good_test in good.iter() {
+        assert_eq!(
+            CheckErrors::UnknownFunction("principal-destruct?".to_string()),
+            type_check_helper_v1(&good_test).unwrap_err().err
+        );
+    }
+}
+
+#[test]
+fn test_principal_destruct_fields() {
+    // The fields of a destructed principal can be used directly, e.g. to check an address hash
+    // or to rebuild the same hash under a different version byte.
+    let good = [
+        r#"(get hash-bytes (unwrap-panic (principal-destruct? tx-sender)))"#,
+        r#"(get version (unwrap-err-panic (principal-destruct? 'SP3X6QWWETNBZWGBK6DRGTR1KX50S74D3433WDGJY)))"#,
+        r#"(get name (unwrap-panic (principal-destruct? 'STB44HYPYAT2BB2QE513NSP81HTMYWBJP02HPGK6.foo)))"#,
+        r#"(principal-construct? 0x16 (get hash-bytes (unwrap-panic (principal-destruct? tx-sender))))"#,
+    ];
+    let expected = [
+        "(buff 20)",
+        "(buff 1)",
+        "(optional (string-ascii 40))",
+        "(response principal (tuple (error_code uint) (value (optional principal))))",
+    ];
+
+    for (good_test, expected) in good.iter().zip(expected.iter()) {
+        assert_eq!(
+            expected,
+            &format!("{}", type_check_helper(&good_test).unwrap())
+        );
+    }
 }
 
 #[test]
@@ -3426,6 +3639,14 @@ fn test_principal_construct() {
     for (bad_test, expected) in bad_pairs.iter() {
         assert_eq!(expected, &type_check_helper(&bad_test).unwrap_err().err);
     }
+
+    // `principal-construct?` is only available to Clarity 2 contracts
+    for (good_test, _) in good_pairs.iter() {
+        assert_eq!(
+            CheckErrors::UnknownFunction("principal-construct?".to_string()),
+            type_check_helper_v1(&good_test).unwrap_err().err
+        );
+    }
 }
 
 #[test]
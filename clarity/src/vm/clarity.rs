@@ -265,6 +265,25 @@ pub trait TransactionConnection: ClarityConnection {
         .and_then(|(value, assets, events, _)| Ok((value, assets, events)))
     }
 
+    /// Execute a batch of STX transfers in the current block.
+    /// Either every transfer happens or none do; will throw an error if the batch tries to
+    /// spend STX that the 'from' principal doesn't have.
+    fn run_stx_batch_transfer(
+        &mut self,
+        from: &PrincipalData,
+        transfers: &[(PrincipalData, u128, BuffData)],
+    ) -> Result<(Value, AssetMap, Vec<StacksTransactionEvent>), Error> {
+        self.with_abort_callback(
+            |vm_env| {
+                vm_env
+                    .stx_batch_transfer(from, transfers)
+                    .map_err(Error::from)
+            },
+            |_, _| false,
+        )
+        .and_then(|(value, assets, events, _)| Ok((value, assets, events)))
+    }
+
     /// Execute a contract call in the current block.
     ///  If an error occurs while processing the transaction, its modifications will be rolled back.
     /// abort_call_back is called with an AssetMap and a ClarityDatabase reference,
@@ -37,13 +37,13 @@ use crate::vm::errors::{
 };
 use crate::vm::events::*;
 use crate::vm::representations::{ClarityName, ContractName, SymbolicExpression};
-use crate::vm::stx_transfer_consolidated;
 use crate::vm::types::signatures::FunctionSignature;
 use crate::vm::types::{
     AssetIdentifier, BuffData, CallableData, OptionalData, PrincipalData,
     QualifiedContractIdentifier, TraitIdentifier, TypeSignature, Value,
 };
 use crate::vm::{eval, is_reserved};
+use crate::vm::{stx_batch_transfer_consolidated, stx_transfer_consolidated};
 use crate::{types::chainstate::StacksBlockId, types::StacksEpochId};
 
 use crate::vm::costs::cost_functions::ClarityCostFunction;
@@ -207,6 +207,9 @@ pub struct GlobalContext<'a, 'hooks> {
     /// This is the chain ID of the transaction
     pub chain_id: u32,
     pub eval_hooks: Option<Vec<&'hooks mut dyn EvalHook>>,
+    /// If set, the cost charged by each cost function is accumulated here (see
+    /// `enable_cost_profiling()`).
+    cost_profile: Option<HashMap<ClarityCostFunction, ExecutionCost>>,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -620,7 +623,7 @@ impl<'a, 'hooks> OwnedEnvironment<'a, 'hooks> {
         database: ClarityDatabase<'a>,
         cost_tracker: LimitedCostTracker,
         epoch_id: StacksEpochId,
-    ) -> OwnedEnvironment<'a, '_> {
+    ) -> OwnedEnvironment<'a, 'hooks> {
         OwnedEnvironment {
             context: GlobalContext::new(mainnet, chain_id, database, cost_tracker, epoch_id),
             call_stack: CallStack::new(),
@@ -771,6 +774,16 @@ impl<'a, 'hooks> OwnedEnvironment<'a, 'hooks> {
         })
     }
 
+    pub fn stx_batch_transfer(
+        &mut self,
+        from: &PrincipalData,
+        transfers: &[(PrincipalData, u128, BuffData)],
+    ) -> Result<(Value, AssetMap, Vec<StacksTransactionEvent>)> {
+        self.execute_in_env(from.clone(), None, None, |exec_env| {
+            exec_env.stx_batch_transfer(from, transfers)
+        })
+    }
+
     #[cfg(any(test, feature = "testing"))]
     pub fn stx_faucet(&mut self, recipient: &PrincipalData, amount: u128) {
         self.execute_in_env::<_, _, crate::vm::errors::Error>(
@@ -850,6 +863,14 @@ impl<'a, 'hooks> OwnedEnvironment<'a, 'hooks> {
         self.context.cost_track.get_total()
     }
 
+    pub fn enable_cost_profiling(&mut self) {
+        self.context.enable_cost_profiling()
+    }
+
+    pub fn get_cost_profile(&self) -> Option<&HashMap<ClarityCostFunction, ExecutionCost>> {
+        self.context.get_cost_profile()
+    }
+
     /// Destroys this environment, returning ownership of its database reference.
     ///  If the context wasn't top-level (i.e., it had uncommitted data), return None,
     ///   because the database is not guaranteed to be in a sane state.
@@ -873,9 +894,7 @@ impl CostTracker for Environment<'_, '_, '_> {
         cost_function: ClarityCostFunction,
         input: &[u64],
     ) -> std::result::Result<ExecutionCost, CostErrors> {
-        self.global_context
-            .cost_track
-            .compute_cost(cost_function, input)
+        self.global_context.compute_cost(cost_function, input)
     }
     fn add_cost(&mut self, cost: ExecutionCost) -> std::result::Result<(), CostErrors> {
         self.global_context.cost_track.add_cost(cost)
@@ -907,7 +926,14 @@ impl CostTracker for GlobalContext<'_, '_> {
         cost_function: ClarityCostFunction,
         input: &[u64],
     ) -> std::result::Result<ExecutionCost, CostErrors> {
-        self.cost_track.compute_cost(cost_function, input)
+        let cost = self.cost_track.compute_cost(cost_function, input)?;
+        if let Some(profile) = self.cost_profile.as_mut() {
+            profile
+                .entry(cost_function)
+                .or_insert_with(ExecutionCost::zero)
+                .add(&cost)?;
+        }
+        Ok(cost)
     }
 
     fn add_cost(&mut self, cost: ExecutionCost) -> std::result::Result<(), CostErrors> {
@@ -1394,6 +1420,33 @@ impl<'a, 'b, 'hooks> Environment<'a, 'b, 'hooks> {
         }
     }
 
+    /// Only commits if the inner stx_batch_transfer_consolidated() returns an (ok true) value,
+    /// so either every transfer in the batch happens or none of them do.
+    pub fn stx_batch_transfer(
+        &mut self,
+        from: &PrincipalData,
+        transfers: &[(PrincipalData, u128, BuffData)],
+    ) -> Result<Value> {
+        self.global_context.begin();
+        let result = stx_batch_transfer_consolidated(self, from, transfers);
+        match result {
+            Ok(value) => match value.clone().expect_result() {
+                Ok(_) => {
+                    self.global_context.commit()?;
+                    Ok(value)
+                }
+                Err(_) => {
+                    self.global_context.roll_back();
+                    Err(InterpreterError::InsufficientBalance.into())
+                }
+            },
+            Err(e) => {
+                self.global_context.roll_back();
+                Err(e)
+            }
+        }
+    }
+
     pub fn run_as_transaction<F, O, E>(&mut self, f: F) -> std::result::Result<O, E>
     where
         F: FnOnce(&mut Self) -> std::result::Result<O, E>,
@@ -1594,9 +1647,29 @@ impl<'a, 'hooks> GlobalContext<'a, 'hooks> {
             epoch_id,
             chain_id,
             eval_hooks: None,
+            cost_profile: None,
         }
     }
 
+    /// Start attributing the cost charged during execution to the cost function that charged
+    /// it.  Costs charged before this is called are not included.
+    pub fn enable_cost_profiling(&mut self) {
+        if self.cost_profile.is_none() {
+            self.cost_profile = Some(HashMap::new());
+        }
+    }
+
+    /// The cost charged by each cost function since `enable_cost_profiling()` was called, or
+    /// None if profiling is not enabled.
+    pub fn get_cost_profile(&self) -> Option<&HashMap<ClarityCostFunction, ExecutionCost>> {
+        self.cost_profile.as_ref()
+    }
+
+    /// Stop profiling, and return the cost charged by each cost function.
+    pub fn take_cost_profile(&mut self) -> Option<HashMap<ClarityCostFunction, ExecutionCost>> {
+        self.cost_profile.take()
+    }
+
     pub fn is_top_level(&self) -> bool {
         self.asset_maps.len() == 0
     }
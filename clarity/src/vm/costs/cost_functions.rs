@@ -154,5 +154,8 @@ define_named_enum!(ClarityCostFunction {
     BitwiseNot("cost_bitwise_not"),
     BitwiseLShift("cost_bitwise_left_shift"),
     BitwiseRShift("cost_bitwise_right_shift"),
+    GetStackingState("cost_get_stacking_state"),
+    StxBatchTransfer("cost_stx_batch_transfer"),
+    PrincipalVersion("cost_principal_version"),
     Unimplemented("cost_unimplemented"),
 });
@@ -49,6 +49,7 @@ pub const CLARITY_MEMORY_LIMIT: u64 = 100 * 1000 * 1000;
 pub const COSTS_1_NAME: &'static str = "costs";
 pub const COSTS_2_NAME: &'static str = "costs-2";
 pub const COSTS_3_NAME: &'static str = "costs-3";
+pub const COSTS_4_NAME: &'static str = "costs-4";
 
 lazy_static! {
     static ref COST_TUPLE_TYPE_SIGNATURE: TypeSignature = TypeSignature::TupleType(
@@ -226,6 +227,15 @@ impl CostStateSummary {
     }
 }
 
+/// The cost incurred by a single `contract-call?` frame, including the cost of any
+///  `contract-call?`s made from within that frame.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CallFrameCost {
+    pub contract_identifier: QualifiedContractIdentifier,
+    pub function_name: ClarityName,
+    pub execution_cost: ExecutionCost,
+}
+
 #[derive(Clone)]
 /// This struct holds all of the data required for non-free LimitedCostTracker instances
 pub struct TrackerData {
@@ -243,6 +253,12 @@ pub struct TrackerData {
     epoch: StacksEpochId,
     mainnet: bool,
     chain_id: u32,
+    /// the costs of the `contract-call?` frames that have returned since this was last drained,
+    ///  in the order in which they returned.
+    call_frame_costs: Vec<CallFrameCost>,
+    /// if set, the total cost charged by each Clarity cost function since this was last
+    ///  drained. This is purely informational and does not affect the tracked totals.
+    cost_function_costs: Option<HashMap<ClarityCostFunction, ExecutionCost>>,
 }
 
 #[derive(Clone)]
@@ -661,6 +677,8 @@ impl LimitedCostTracker {
             epoch,
             mainnet,
             chain_id,
+            call_frame_costs: Vec::new(),
+            cost_function_costs: None,
         };
         assert!(clarity_db.is_stack_empty());
         cost_tracker.load_costs(clarity_db, true)?;
@@ -685,6 +703,8 @@ impl LimitedCostTracker {
             epoch,
             mainnet,
             chain_id,
+            call_frame_costs: Vec::new(),
+            cost_function_costs: None,
         };
         cost_tracker.load_costs(clarity_db, false)?;
         Ok(Self::Limited(cost_tracker))
@@ -723,6 +743,7 @@ impl LimitedCostTracker {
             | StacksEpochId::Epoch22
             | StacksEpochId::Epoch23
             | StacksEpochId::Epoch24 => COSTS_3_NAME.to_string(),
+            StacksEpochId::Epoch25 => COSTS_4_NAME.to_string(),
         }
     }
 }
@@ -834,6 +855,57 @@ impl LimitedCostTracker {
             Self::Free => u64::MAX,
         }
     }
+    /// Record the cost of a `contract-call?` frame that just returned, given the total
+    ///  cost at the time the call was made. Free trackers do not record anything.
+    pub fn record_call_frame_cost(
+        &mut self,
+        contract_identifier: &QualifiedContractIdentifier,
+        function_name: &ClarityName,
+        cost_before: &ExecutionCost,
+    ) {
+        if let Self::Limited(ref mut data) = self {
+            let mut execution_cost = data.total.clone();
+            if execution_cost.sub(cost_before).is_err() {
+                // the total was reset in the middle of the call, so nothing can be attributed
+                execution_cost = ExecutionCost::zero();
+            }
+            data.call_frame_costs.push(CallFrameCost {
+                contract_identifier: contract_identifier.clone(),
+                function_name: function_name.clone(),
+                execution_cost,
+            });
+        }
+    }
+    /// Remove and return the costs of the `contract-call?` frames recorded so far.
+    pub fn take_call_frame_costs(&mut self) -> Vec<CallFrameCost> {
+        match self {
+            Self::Limited(ref mut data) => std::mem::take(&mut data.call_frame_costs),
+            Self::Free => vec![],
+        }
+    }
+    /// Start or stop recording the cost charged by each Clarity cost function.
+    ///  Free trackers do not record anything.
+    pub fn set_record_cost_functions(&mut self, record: bool) {
+        if let Self::Limited(ref mut data) = self {
+            data.cost_function_costs = if record { Some(HashMap::new()) } else { None };
+        }
+    }
+    /// Remove and return the cost charged by each Clarity cost function so far, keyed by
+    ///  cost function name. Returns None if recording is not enabled.
+    pub fn take_cost_function_costs(&mut self) -> Option<BTreeMap<String, ExecutionCost>> {
+        match self {
+            Self::Limited(TrackerData {
+                cost_function_costs: Some(ref mut costs),
+                ..
+            }) => Some(
+                costs
+                    .drain()
+                    .map(|(cost_function, cost)| (cost_function.get_name(), cost))
+                    .collect(),
+            ),
+            _ => None,
+        }
+    }
 }
 
 fn parse_cost(
@@ -986,7 +1058,17 @@ impl CostTracker for LimitedCostTracker {
                     )))?
                     .clone();
 
-                compute_cost(data, cost_function_ref, input, data.epoch)
+                let cost = compute_cost(data, cost_function_ref, input, data.epoch)?;
+                if let Some(ref mut costs) = data.cost_function_costs {
+                    let entry = costs
+                        .entry(cost_function)
+                        .or_insert_with(ExecutionCost::zero);
+                    // informational only, so saturate rather than fail the computation
+                    if entry.add(&cost).is_err() {
+                        *entry = ExecutionCost::max_value();
+                    }
+                }
+                Ok(cost)
             }
         }
     }
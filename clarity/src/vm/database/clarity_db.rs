@@ -576,7 +576,7 @@ impl<'a> ClarityDatabase<'a> {
         self.insert_metadata(contract_identifier, &key, &(contract_content.len() as u64));
 
         // insert contract-src
-        if STORE_CONTRACT_SRC_INTERFACE {
+        if STORE_CONTRACT_SRC_INTERFACE && self.store.store_contract_src() {
             let key = ClarityDatabase::make_metadata_key(StoreType::Contract, "contract-src");
             self.insert_metadata(contract_identifier, &key, &contract_content.to_string());
         }
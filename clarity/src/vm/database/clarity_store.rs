@@ -92,6 +92,12 @@ pub trait ClarityBackingStore {
         None
     }
 
+    /// Whether or not to keep a contract's source code in its metadata when it is published.
+    ///   The source is not consensus-critical, so nodes may opt out of storing it.
+    fn store_contract_src(&self) -> bool {
+        true
+    }
+
     /// The contract commitment is the hash of the contract, plus the block height in
     ///   which the contract was initialized.
     fn make_contract_commitment(&mut self, contract_hash: Sha512Trunc256Sum) -> String {
@@ -223,6 +223,10 @@ impl<'a> RollbackWrapper<'a> {
         self.store.get_cc_special_cases_handler()
     }
 
+    pub fn store_contract_src(&self) -> bool {
+        self.store.store_contract_src()
+    }
+
     pub fn nest(&mut self) {
         self.stack.push(RollbackContext {
             edits: Vec::new(),
@@ -150,7 +150,9 @@ const REGTEST_KEYWORD: SimpleKeywordAPI = SimpleKeywordAPI {
     name: "is-in-regtest",
     snippet: "is-in-regtest",
     output_type: "bool",
-    description: "Returns whether or not the code is running in a regression test",
+    description: "Returns whether or not the code is running in a regression test. Test contracts
+can use this keyword to guard test-only code paths instead of comparing against a known address.
+It is a keyword, not a function, so it is written without parentheses.",
     example:
         "(print is-in-regtest) ;; Will print 'true' if the code is running in a regression test",
 };
@@ -320,6 +322,27 @@ Note: This function is only available starting with Stacks 2.1.",
 "#,
 };
 
+const PRINCIPAL_VERSION_API: SimpleFunctionAPI = SimpleFunctionAPI {
+    name: None,
+    snippet: "principal-version ${1:principal}",
+    signature: "(principal-version standard-or-contract-principal)",
+    description: "Returns the address version byte of `standard-or-contract-principal` as a `uint`,
+wrapped in an `ok` response. For a contract principal, this is the version byte of the
+principal that deployed the contract. The version byte indicates the type of account
+and the type of network that this principal can spend tokens on (see `is-standard`).
+
+Unlike `is-standard`, this method does not depend on the network type: the
+version byte is returned regardless of whether it matches the current network.
+
+Note: This function is only available starting with Stacks 2.5.",
+    example: r#"
+(principal-version 'STB44HYPYAT2BB2QE513NSP81HTMYWBJP02HPGK6) ;; Returns (ok u26)
+(principal-version 'STB44HYPYAT2BB2QE513NSP81HTMYWBJP02HPGK6.foo) ;; Returns (ok u26)
+(principal-version 'SP3X6QWWETNBZWGBK6DRGTR1KX50S74D3433WDGJY) ;; Returns (ok u22)
+(principal-version 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR) ;; Returns (ok u31)
+"#,
+};
+
 const PRINCPIPAL_DESTRUCT_API: SimpleFunctionAPI = SimpleFunctionAPI {
     name: None,
     snippet: "principal-destruct? ${1:principal-address}",
@@ -1694,7 +1717,8 @@ not correspond to an existing block prior to the current block, the function ret
 are as follows:
 
 `burnchain-header-hash`: This property returns a `(buff 32)` value containing the header hash of the burnchain (Bitcoin) block that selected the 
-Stacks block at the given Stacks chain height.
+Stacks block at the given Stacks chain height.  This is the block's burnchain anchor, so contracts can use it to check data that was committed
+to the burnchain.
 
 `id-header-hash`: This property returns a `(buff 32)` value containing the _index block hash_ of a Stacks block.   This hash is globally unique, and is derived
 from the block hash and the history of accepted PoX operations.  This is also the block hash value you would pass into `(at-block)`.
@@ -2274,6 +2298,27 @@ unlock height for any locked STX, all denominated in microstacks.
 "#,
 };
 
+const GET_STACKING_STATE: SimpleFunctionAPI = SimpleFunctionAPI {
+    name: None,
+    snippet: "get-stacking-state ${1:principal}",
+    signature: "(get-stacking-state principal)",
+    description: "`get-stacking-state` is used to query the PoX stacking state of `principal`.
+
+If any of the principal's STX are currently locked by a PoX contract, this function returns
+`(some ...)` with a tuple describing the lock: the amount of locked STX (`stacked-amount`),
+the first reward cycle it is locked for (`reward-cycle`), the number of reward cycles it is
+locked for (`lock-period`), and the principal the stacker delegated to, if any (`delegated-to`).
+The state is read from whichever PoX contract locked the STX, so the result has the same
+type regardless of which PoX contract is active. If none of the principal's STX are locked,
+this function returns `none`.
+
+Note: This function is only available starting with Stacks 2.5.",
+    example: r#"
+(get-stacking-state 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR) ;; Returns none
+(get-stacking-state (as-contract tx-sender)) ;; Returns none
+"#,
+};
+
 const STX_TRANSFER: SpecialAPI = SpecialAPI {
     input_type: "uint, principal, principal, buff",
     snippet: "stx-transfer? ${1:amount} ${2:sender} ${3:recipient}",
@@ -2427,6 +2472,7 @@ pub fn make_api_reference(function: &NativeFunctions) -> FunctionAPI {
         BuffToIntBe => make_for_simple_native(&BUFF_TO_INT_BE_API, &function, name),
         BuffToUIntBe => make_for_simple_native(&BUFF_TO_UINT_BE_API, &function, name),
         IsStandard => make_for_simple_native(&IS_STANDARD_API, &function, name),
+        PrincipalVersion => make_for_simple_native(&PRINCIPAL_VERSION_API, &function, name),
         PrincipalDestruct => make_for_simple_native(&PRINCPIPAL_DESTRUCT_API, &function, name),
         PrincipalConstruct => make_for_special(&PRINCIPAL_CONSTRUCT_API, &function),
         StringToInt => make_for_simple_native(&STRING_TO_INT_API, &function, name),
@@ -2510,6 +2556,7 @@ pub fn make_api_reference(function: &NativeFunctions) -> FunctionAPI {
         AtBlock => make_for_special(&AT_BLOCK, function),
         GetStxBalance => make_for_simple_native(&STX_GET_BALANCE, &function, name),
         StxGetAccount => make_for_simple_native(&STX_GET_ACCOUNT, &function, name),
+        GetStackingState => make_for_simple_native(&GET_STACKING_STATE, &function, name),
         StxTransfer => make_for_special(&STX_TRANSFER, function),
         StxTransferMemo => make_for_special(&STX_TRANSFER_MEMO, function),
         StxBurn => make_for_simple_native(&STX_BURN, &function, name),
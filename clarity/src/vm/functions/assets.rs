@@ -14,6 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+use crate::boot_util::boot_code_id;
 use crate::vm::functions::tuples;
 use std::convert::{TryFrom, TryInto};
 
@@ -161,6 +162,61 @@ pub fn stx_transfer_consolidated(
     Ok(Value::okay_true())
 }
 
+/// Do a "consolidated" batch of STX transfers out of `from`.
+/// The sender is debited once for the sum of all amounts, and each recipient is credited in
+/// order.  Every transfer is validated before any balance is touched, so the caller can treat
+/// an error code as "no transfer happened" once it rolls back the enclosing context.
+pub fn stx_batch_transfer_consolidated(
+    env: &mut Environment,
+    from: &PrincipalData,
+    transfers: &[(PrincipalData, u128, BuffData)],
+) -> Result<Value> {
+    if Some(from) != env.sender.as_ref() {
+        return clarity_ecode!(StxErrorCodes::SENDER_IS_NOT_TX_SENDER);
+    }
+
+    runtime_cost(ClarityCostFunction::StxBatchTransfer, env, transfers.len())?;
+
+    let mut total: u128 = 0;
+    for (to, amount, _) in transfers.iter() {
+        if *amount == 0 {
+            return clarity_ecode!(StxErrorCodes::NON_POSITIVE_AMOUNT);
+        }
+        if from == to {
+            return clarity_ecode!(StxErrorCodes::SENDER_IS_RECIPIENT);
+        }
+        total = match total.checked_add(*amount) {
+            Some(total) => total,
+            None => return clarity_ecode!(StxErrorCodes::NOT_ENOUGH_BALANCE),
+        };
+    }
+
+    // loading the sender's principal and balance, and then each recipient's
+    env.add_memory(
+        (TypeSignature::PrincipalType.size() as u64 + STXBalance::unlocked_and_v1_size as u64)
+            .saturating_mul(transfers.len() as u64 + 1),
+    )?;
+
+    let mut sender_snapshot = env.global_context.database.get_stx_balance_snapshot(from);
+    if !sender_snapshot.can_transfer(total) {
+        return clarity_ecode!(StxErrorCodes::NOT_ENOUGH_BALANCE);
+    }
+    sender_snapshot.debit(total);
+    sender_snapshot.save();
+
+    for (to, amount, _) in transfers.iter() {
+        let mut recipient_snapshot = env.global_context.database.get_stx_balance_snapshot(to);
+        recipient_snapshot.credit(*amount);
+        recipient_snapshot.save();
+    }
+
+    env.global_context.log_stx_transfer(&from, total)?;
+    for (to, amount, memo) in transfers.iter() {
+        env.register_stx_transfer_event(from.clone(), to.clone(), *amount, memo.clone())?;
+    }
+    Ok(Value::okay_true())
+}
+
 pub fn special_stx_transfer(
     args: &[SymbolicExpression],
     env: &mut Environment,
@@ -255,6 +311,111 @@ pub fn special_stx_account(
     .map(|t| Value::Tuple(t))
 }
 
+/// Implements `(get-stacking-state principal)`: returns
+/// `(some { stacked-amount, reward-cycle, lock-period, delegated-to })` describing the
+/// principal's current lock, or `none` if none of its STX are locked.  The state is read
+/// from whichever PoX contract locked the account's STX, so callers don't need to know
+/// which PoX contract is active.
+pub fn special_get_stacking_state(
+    args: &[SymbolicExpression],
+    env: &mut Environment,
+    context: &LocalContext,
+) -> Result<Value> {
+    check_argument_count(1, args)?;
+
+    // `get-stacking-state` is a Clarity 3 built-in, which only exists from Stacks 2.5
+    if *env.epoch() < StacksEpochId::Epoch25 {
+        return Err(CheckErrors::UnknownFunction("get-stacking-state".to_string()).into());
+    }
+
+    runtime_cost(ClarityCostFunction::GetStackingState, env, 0)?;
+
+    let stacker = eval(&args[0], env, context)?;
+    let principal = if let Value::Principal(ref p) = stacker {
+        p.clone()
+    } else {
+        return Err(CheckErrors::TypeValueError(TypeSignature::PrincipalType, stacker).into());
+    };
+
+    let stx_balance = env
+        .global_context
+        .database
+        .get_stx_balance_snapshot(&principal)
+        .canonical_balance_repr();
+
+    let pox_contract_name = match stx_balance {
+        STXBalance::Unlocked { .. } => {
+            return Ok(Value::none());
+        }
+        STXBalance::LockedPoxOne { .. } => "pox",
+        STXBalance::LockedPoxTwo { .. } => "pox-2",
+        STXBalance::LockedPoxThree { .. } => "pox-3",
+    };
+    let pox_contract = boot_code_id(pox_contract_name, env.global_context.mainnet);
+    let epoch = *env.epoch();
+
+    let stacker_key = Value::Tuple(TupleData::from_data(vec![("stacker".into(), stacker)])?);
+
+    let stacking_state = match env
+        .global_context
+        .database
+        .fetch_entry_unknown_descriptor(&pox_contract, "stacking-state", &stacker_key, &epoch)?
+        .expect_optional()
+    {
+        Some(Value::Tuple(stacking_state)) => stacking_state,
+        Some(_) => {
+            return Err(InterpreterError::InterpreterError(
+                "PoX stacking-state entry is not a tuple".into(),
+            )
+            .into());
+        }
+        None => {
+            return Ok(Value::none());
+        }
+    };
+
+    // PoX-3 records the delegate in the stacking state.  Earlier PoX contracts only track it
+    // in the stacker's delegation state.
+    let delegated_to = if pox_contract_name == "pox-3" {
+        stacking_state.get("delegated-to")?.clone()
+    } else {
+        match env
+            .global_context
+            .database
+            .fetch_entry_unknown_descriptor(
+                &pox_contract,
+                "delegation-state",
+                &stacker_key,
+                &epoch,
+            )?
+            .expect_optional()
+        {
+            Some(Value::Tuple(delegation_state)) => {
+                Value::some(delegation_state.get_owned("delegated-to")?)?
+            }
+            _ => Value::none(),
+        }
+    };
+
+    let stacking_state = TupleData::from_data(vec![
+        (
+            "stacked-amount".into(),
+            Value::UInt(stx_balance.amount_locked()),
+        ),
+        (
+            "reward-cycle".into(),
+            stacking_state.get("first-reward-cycle")?.clone(),
+        ),
+        (
+            "lock-period".into(),
+            stacking_state.get("lock-period")?.clone(),
+        ),
+        ("delegated-to".into(), delegated_to),
+    ])?;
+
+    Value::some(Value::Tuple(stacking_state))
+}
+
 pub fn special_stx_burn(
     args: &[SymbolicExpression],
     env: &mut Environment,
@@ -191,6 +191,10 @@ pub fn special_contract_call(
 
     let contract_principal = env.contract_context.contract_identifier.clone().into();
 
+    // checkpoint the cost so far, so that the cost of this call frame can be
+    //   attributed to the callee once it returns.
+    let cost_before = env.global_context.cost_track.get_total();
+
     let mut nested_env = env.nest_with_caller(contract_principal);
     let result = if nested_env.short_circuit_contract_call(
         &contract_identifier,
@@ -202,7 +206,15 @@ pub fn special_contract_call(
         })
     } else {
         nested_env.execute_contract(&contract_identifier, function_name, &rest_args, false)
-    }?;
+    };
+
+    // the frame's cost is charged whether or not the call succeeded
+    env.global_context.cost_track.record_call_frame_cost(
+        &contract_identifier,
+        function_name,
+        &cost_before,
+    );
+    let result = result?;
 
     // sanitize contract-call outputs in epochs >= 2.4
     let result_type = TypeSignature::type_of(&result);
@@ -23,7 +23,9 @@ use crate::vm::errors::{
     check_argument_count, check_arguments_at_least, CheckErrors, Error,
     InterpreterResult as Result, RuntimeErrorType, ShortReturnType,
 };
-pub use crate::vm::functions::assets::stx_transfer_consolidated;
+pub use crate::vm::functions::assets::{
+    stx_batch_transfer_consolidated, stx_transfer_consolidated,
+};
 use crate::vm::is_reserved;
 use crate::vm::representations::SymbolicExpressionType::{Atom, List};
 use crate::vm::representations::{ClarityName, SymbolicExpression, SymbolicExpressionType};
@@ -62,6 +64,8 @@ macro_rules! switch_on_global_epoch {
                 StacksEpochId::Epoch23 => $Epoch205Version(args, env, context),
                 // Note: We reuse 2.05 for 2.4.
                 StacksEpochId::Epoch24 => $Epoch205Version(args, env, context),
+                // Note: We reuse 2.05 for 2.5.
+                StacksEpochId::Epoch25 => $Epoch205Version(args, env, context),
             }
         }
     };
@@ -190,6 +194,8 @@ define_versioned_named_enum!(NativeFunctions(ClarityVersion) {
     ToConsensusBuff("to-consensus-buff?", ClarityVersion::Clarity2),
     FromConsensusBuff("from-consensus-buff?", ClarityVersion::Clarity2),
     ReplaceAt("replace-at?", ClarityVersion::Clarity2),
+    GetStackingState("get-stacking-state", ClarityVersion::Clarity3),
+    PrincipalVersion("principal-version", ClarityVersion::Clarity3),
 });
 
 impl NativeFunctions {
@@ -525,6 +531,10 @@ pub fn lookup_reserved_functions(name: &str, version: &ClarityVersion) -> Option
             ),
             StxBurn => SpecialFunction("special_stx_burn", &assets::special_stx_burn),
             StxGetAccount => SpecialFunction("stx_get_account", &assets::special_stx_account),
+            GetStackingState => SpecialFunction(
+                "special_get_stacking_state",
+                &assets::special_get_stacking_state,
+            ),
             ToConsensusBuff => NativeFunction205(
                 "to_consensus_buff",
                 NativeHandle::SingleArg(&conversions::to_consensus_buff),
@@ -535,6 +545,10 @@ pub fn lookup_reserved_functions(name: &str, version: &ClarityVersion) -> Option
                 SpecialFunction("from_consensus_buff", &conversions::from_consensus_buff)
             }
             ReplaceAt => SpecialFunction("replace_at", &sequences::special_replace_at),
+            PrincipalVersion => SpecialFunction(
+                "special_principal_version",
+                &principals::special_principal_version,
+            ),
             BitwiseAnd => NativeFunction(
                 "native_bitwise_and",
                 NativeHandle::MoreArg(&arithmetic::native_bitwise_and),
@@ -13,6 +13,7 @@ use crate::vm::types::{
     SequenceSubtype, StandardPrincipalData, TupleData, TypeSignature, Value,
 };
 use crate::vm::{eval, ContractName, Environment, LocalContext};
+use stacks_common::types::StacksEpochId;
 use stacks_common::util::hash::hex_bytes;
 use std::convert::TryFrom;
 
@@ -78,6 +79,40 @@ pub fn special_is_standard(
     )))
 }
 
+/// Returns the address version byte of a standard principal, or of the issuer of a contract
+/// principal, as `(ok uint)`. Unlike `is-standard`, this does not depend on the network.
+pub fn special_principal_version(
+    args: &[SymbolicExpression],
+    env: &mut Environment,
+    context: &LocalContext,
+) -> Result<Value> {
+    check_argument_count(1, args)?;
+
+    // `principal-version` is a Clarity 3 built-in, which only exists from Stacks 2.5
+    if *env.epoch() < StacksEpochId::Epoch25 {
+        return Err(CheckErrors::UnknownFunction("principal-version".to_string()).into());
+    }
+
+    runtime_cost(ClarityCostFunction::PrincipalVersion, env, 0)?;
+    let principal = eval(&args[0], env, context)?;
+
+    let version = match principal {
+        Value::Principal(PrincipalData::Standard(StandardPrincipalData(version, _bytes))) => {
+            version
+        }
+        Value::Principal(PrincipalData::Contract(QualifiedContractIdentifier {
+            issuer,
+            name: _,
+        })) => issuer.0,
+        _ => {
+            return Err(CheckErrors::TypeValueError(TypeSignature::PrincipalType, principal).into())
+        }
+    };
+
+    Ok(Value::okay(Value::UInt(u128::from(version)))
+        .expect("FAIL: failed to build principal-version response"))
+}
+
 /// Creates a Tuple which is the result of parsing a Principal tuple into a Tuple of its `version`
 /// and `hash-bytes`.
 fn create_principal_destruct_tuple(
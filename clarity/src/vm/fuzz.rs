@@ -0,0 +1,348 @@
+// Copyright (C) 2013-2020 Blockstack PBC, a public benefit corporation
+// Copyright (C) 2020-2023 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Grammar-based generator of random, well-typed Clarity expressions.
+//!
+//! Expressions are built top-down from a target type, so every generated program passes the
+//! type checker by construction. Generation is fully determined by the seed and maximum depth,
+//! which makes it possible to reproduce any expression reported by a cost-fuzzing run.
+//! Well-typed expressions may still fail at runtime (e.g. on integer overflow).
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use stacks_common::util::hash::to_hex;
+
+use crate::vm::version::ClarityVersion;
+
+/// Deepest nesting the generator will produce. Each level can add up to two levels of
+/// parentheses (e.g. `let` bindings), so this keeps programs well below the parser's depth limit.
+pub const MAX_FUZZ_DEPTH: u32 = 10;
+
+/// Largest number of bytes or characters in a generated buffer or string literal.
+const MAX_LITERAL_LEN: usize = 8;
+
+/// Largest number of elements in a generated list literal.
+const MAX_LIST_LEN: usize = 4;
+
+/// Types of expressions the generator knows how to produce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FuzzType {
+    Int,
+    UInt,
+    Bool,
+    Buff,
+    StringAscii,
+    OptionalInt,
+    ListInt,
+}
+
+impl FuzzType {
+    pub const ALL: [FuzzType; 7] = [
+        FuzzType::Int,
+        FuzzType::UInt,
+        FuzzType::Bool,
+        FuzzType::Buff,
+        FuzzType::StringAscii,
+        FuzzType::OptionalInt,
+        FuzzType::ListInt,
+    ];
+}
+
+/// Seeded generator of random Clarity expressions of bounded depth.
+pub struct ExpressionGenerator {
+    rng: ChaCha20Rng,
+    max_depth: u32,
+    clarity_version: ClarityVersion,
+    /// Variables in scope at the current point of generation, innermost last.
+    bindings: Vec<(String, FuzzType)>,
+    next_binding: u64,
+}
+
+impl ExpressionGenerator {
+    /// Make a new generator. `max_depth` is clamped to `MAX_FUZZ_DEPTH`.
+    pub fn new(seed: u64, max_depth: u32, clarity_version: ClarityVersion) -> ExpressionGenerator {
+        ExpressionGenerator {
+            rng: ChaCha20Rng::seed_from_u64(seed),
+            max_depth: max_depth.min(MAX_FUZZ_DEPTH),
+            clarity_version,
+            bindings: vec![],
+            next_binding: 0,
+        }
+    }
+
+    /// Generate an expression of a randomly-chosen type.
+    pub fn generate(&mut self) -> (String, FuzzType) {
+        let ty = FuzzType::ALL[self.rng.gen_range(0, FuzzType::ALL.len())];
+        (self.generate_typed(ty), ty)
+    }
+
+    /// Generate an expression that evaluates to a value of type `ty`.
+    pub fn generate_typed(&mut self, ty: FuzzType) -> String {
+        self.bindings.clear();
+        let max_depth = self.max_depth;
+        self.gen_expr(ty, max_depth)
+    }
+
+    fn gen_expr(&mut self, ty: FuzzType, depth: u32) -> String {
+        if depth == 0 || self.rng.gen_ratio(1, 4) {
+            return self.gen_leaf(ty);
+        }
+        let depth = depth - 1;
+
+        // forms that can produce a value of any type
+        match self.rng.gen_range(0, 10) {
+            0 => {
+                return format!(
+                    "(if {} {} {})",
+                    self.gen_expr(FuzzType::Bool, depth),
+                    self.gen_expr(ty, depth),
+                    self.gen_expr(ty, depth)
+                )
+            }
+            1 => return self.gen_let(ty, depth),
+            2 => return self.gen_match(ty, depth),
+            _ => {}
+        }
+
+        match ty {
+            FuzzType::Int => self.gen_int(depth),
+            FuzzType::UInt => self.gen_uint(depth),
+            FuzzType::Bool => self.gen_bool(depth),
+            FuzzType::Buff => self.gen_buff(depth),
+            FuzzType::StringAscii => self.gen_string_ascii(depth),
+            FuzzType::OptionalInt => self.gen_optional_int(depth),
+            FuzzType::ListInt => self.gen_list_int(depth),
+        }
+    }
+
+    fn gen_leaf(&mut self, ty: FuzzType) -> String {
+        let in_scope: Vec<_> = self
+            .bindings
+            .iter()
+            .filter(|(_, binding_ty)| *binding_ty == ty)
+            .map(|(name, _)| name.clone())
+            .collect();
+        if !in_scope.is_empty() && self.rng.gen_bool(0.5) {
+            return in_scope[self.rng.gen_range(0, in_scope.len())].clone();
+        }
+
+        match ty {
+            FuzzType::Int => format!("{}", self.rng.gen_range(-1000i64, 1000)),
+            FuzzType::UInt => format!("u{}", self.rng.gen_range(0u64, 1000)),
+            FuzzType::Bool => format!("{}", self.rng.gen::<bool>()),
+            FuzzType::Buff => {
+                let len = self.rng.gen_range(0, MAX_LITERAL_LEN + 1);
+                let bytes: Vec<u8> = (0..len).map(|_| self.rng.gen()).collect();
+                format!("0x{}", to_hex(&bytes))
+            }
+            FuzzType::StringAscii => {
+                let len = self.rng.gen_range(0, MAX_LITERAL_LEN + 1);
+                let chars: String = (0..len)
+                    .map(|_| self.rng.gen_range(b'a', b'z' + 1) as char)
+                    .collect();
+                format!("\"{}\"", chars)
+            }
+            FuzzType::OptionalInt => format!("(some {})", self.gen_leaf(FuzzType::Int)),
+            FuzzType::ListInt => {
+                // never empty, since `(list)` has no element type
+                let len = self.rng.gen_range(1, MAX_LIST_LEN + 1);
+                let items: Vec<_> = (0..len).map(|_| self.gen_leaf(FuzzType::Int)).collect();
+                format!("(list {})", items.join(" "))
+            }
+        }
+    }
+
+    fn fresh_binding(&mut self) -> String {
+        let name = format!("fuzz-var-{}", self.next_binding);
+        self.next_binding += 1;
+        name
+    }
+
+    fn gen_let(&mut self, ty: FuzzType, depth: u32) -> String {
+        let bound_ty = FuzzType::ALL[self.rng.gen_range(0, FuzzType::ALL.len())];
+        let bound = self.gen_expr(bound_ty, depth);
+        let name = self.fresh_binding();
+        self.bindings.push((name.clone(), bound_ty));
+        let body = self.gen_expr(ty, depth);
+        self.bindings.pop();
+        format!("(let (({} {})) {})", name, bound, body)
+    }
+
+    fn gen_match(&mut self, ty: FuzzType, depth: u32) -> String {
+        let input = self.gen_expr(FuzzType::OptionalInt, depth);
+        let name = self.fresh_binding();
+        self.bindings.push((name.clone(), FuzzType::Int));
+        let some_branch = self.gen_expr(ty, depth);
+        self.bindings.pop();
+        let none_branch = self.gen_expr(ty, depth);
+        format!("(match {} {} {} {})", input, name, some_branch, none_branch)
+    }
+
+    fn gen_int(&mut self, depth: u32) -> String {
+        match self.rng.gen_range(0, 6) {
+            0 => self.gen_call("+", &[FuzzType::Int, FuzzType::Int], depth),
+            1 => self.gen_call("-", &[FuzzType::Int, FuzzType::Int], depth),
+            2 => self.gen_call("*", &[FuzzType::Int, FuzzType::Int], depth),
+            3 => self.gen_call("to-int", &[FuzzType::UInt], depth),
+            4 => self.gen_call("default-to", &[FuzzType::Int, FuzzType::OptionalInt], depth),
+            _ => format!(
+                "(fold + {} {})",
+                self.gen_expr(FuzzType::ListInt, depth),
+                self.gen_expr(FuzzType::Int, depth)
+            ),
+        }
+    }
+
+    fn gen_uint(&mut self, depth: u32) -> String {
+        match self.rng.gen_range(0, 7) {
+            0 => self.gen_call("+", &[FuzzType::UInt, FuzzType::UInt], depth),
+            1 => self.gen_call("-", &[FuzzType::UInt, FuzzType::UInt], depth),
+            2 => self.gen_call("*", &[FuzzType::UInt, FuzzType::UInt], depth),
+            3 => self.gen_call("to-uint", &[FuzzType::Int], depth),
+            4 => self.gen_call("len", &[FuzzType::Buff], depth),
+            5 => self.gen_call("len", &[FuzzType::StringAscii], depth),
+            _ => self.gen_call("len", &[FuzzType::ListInt], depth),
+        }
+    }
+
+    fn gen_bool(&mut self, depth: u32) -> String {
+        match self.rng.gen_range(0, 9) {
+            0 => self.gen_call("and", &[FuzzType::Bool, FuzzType::Bool], depth),
+            1 => self.gen_call("or", &[FuzzType::Bool, FuzzType::Bool], depth),
+            2 => self.gen_call("not", &[FuzzType::Bool], depth),
+            3 => self.gen_call("<", &[FuzzType::Int, FuzzType::Int], depth),
+            4 => self.gen_call(">=", &[FuzzType::UInt, FuzzType::UInt], depth),
+            5 => self.gen_call("is-eq", &[FuzzType::Int, FuzzType::Int], depth),
+            6 => self.gen_call("is-eq", &[FuzzType::Bool, FuzzType::Bool], depth),
+            7 => self.gen_call("is-some", &[FuzzType::OptionalInt], depth),
+            _ => self.gen_call("is-none", &[FuzzType::OptionalInt], depth),
+        }
+    }
+
+    fn gen_buff(&mut self, depth: u32) -> String {
+        match self.rng.gen_range(0, 6) {
+            0 => self.gen_call("concat", &[FuzzType::Buff, FuzzType::Buff], depth),
+            1 => self.gen_call("sha256", &[FuzzType::Buff], depth),
+            2 => self.gen_call("sha256", &[FuzzType::Int], depth),
+            3 => self.gen_call("keccak256", &[FuzzType::Buff], depth),
+            4 => self.gen_call("hash160", &[FuzzType::UInt], depth),
+            _ => self.gen_call("sha512", &[FuzzType::Buff], depth),
+        }
+    }
+
+    fn gen_string_ascii(&mut self, depth: u32) -> String {
+        if self.clarity_version == ClarityVersion::Clarity2 && self.rng.gen_bool(0.5) {
+            self.gen_call("int-to-ascii", &[FuzzType::Int], depth)
+        } else {
+            self.gen_call(
+                "concat",
+                &[FuzzType::StringAscii, FuzzType::StringAscii],
+                depth,
+            )
+        }
+    }
+
+    fn gen_optional_int(&mut self, depth: u32) -> String {
+        if self.rng.gen_bool(0.5) {
+            self.gen_call("some", &[FuzzType::Int], depth)
+        } else {
+            self.gen_call("element-at", &[FuzzType::ListInt, FuzzType::UInt], depth)
+        }
+    }
+
+    fn gen_list_int(&mut self, depth: u32) -> String {
+        match self.rng.gen_range(0, 4) {
+            0 => {
+                let len = self.rng.gen_range(1, MAX_LIST_LEN + 1);
+                let items: Vec<_> = (0..len)
+                    .map(|_| self.gen_expr(FuzzType::Int, depth))
+                    .collect();
+                format!("(list {})", items.join(" "))
+            }
+            1 => self.gen_call("concat", &[FuzzType::ListInt, FuzzType::ListInt], depth),
+            2 => self.gen_call("append", &[FuzzType::ListInt, FuzzType::Int], depth),
+            _ => format!(
+                "(map + {} {})",
+                self.gen_expr(FuzzType::ListInt, depth),
+                self.gen_expr(FuzzType::ListInt, depth)
+            ),
+        }
+    }
+
+    fn gen_call(&mut self, function: &str, arg_types: &[FuzzType], depth: u32) -> String {
+        let args: Vec<_> = arg_types
+            .iter()
+            .map(|ty| self.gen_expr(*ty, depth))
+            .collect();
+        format!("({} {})", function, args.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::vm::analysis::mem_type_check;
+    use crate::vm::types::TypeSignature;
+    use stacks_common::types::StacksEpochId;
+
+    #[test]
+    fn test_generation_is_deterministic() {
+        let mut gen_a = ExpressionGenerator::new(42, 5, ClarityVersion::Clarity2);
+        let mut gen_b = ExpressionGenerator::new(42, 5, ClarityVersion::Clarity2);
+        for _ in 0..50 {
+            assert_eq!(gen_a.generate(), gen_b.generate());
+        }
+
+        let mut gen_c = ExpressionGenerator::new(43, 5, ClarityVersion::Clarity2);
+        let exprs_a: Vec<_> = (0..50).map(|_| gen_a.generate()).collect();
+        let exprs_c: Vec<_> = (0..50).map(|_| gen_c.generate()).collect();
+        assert_ne!(exprs_a, exprs_c);
+    }
+
+    #[test]
+    fn test_generated_expressions_type_check() {
+        for version in [ClarityVersion::Clarity1, ClarityVersion::Clarity2].iter() {
+            for seed in 0..20 {
+                let mut generator = ExpressionGenerator::new(seed, 4, *version);
+                for ty in FuzzType::ALL.iter() {
+                    let expr = generator.generate_typed(*ty);
+                    let (result_type, _) = mem_type_check(&expr, *version, StacksEpochId::Epoch21)
+                        .unwrap_or_else(|e| panic!("Failed to type-check {}: {:?}", &expr, &e));
+                    let result_type = result_type.unwrap();
+                    match ty {
+                        FuzzType::Int => assert_eq!(result_type, TypeSignature::IntType),
+                        FuzzType::UInt => assert_eq!(result_type, TypeSignature::UIntType),
+                        FuzzType::Bool => assert_eq!(result_type, TypeSignature::BoolType),
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_depth_is_clamped() {
+        let generator = ExpressionGenerator::new(0, 1000, ClarityVersion::Clarity2);
+        assert_eq!(generator.max_depth, MAX_FUZZ_DEPTH);
+
+        // depth 0 only produces literals
+        let mut generator = ExpressionGenerator::new(0, 0, ClarityVersion::Clarity2);
+        for _ in 0..20 {
+            let expr = generator.generate_typed(FuzzType::Int);
+            assert!(expr.parse::<i64>().is_ok(), "{}", expr);
+        }
+    }
+}
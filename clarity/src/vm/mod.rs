@@ -43,6 +43,8 @@ pub mod coverage;
 
 pub mod events;
 
+pub mod fuzz;
+
 #[cfg(any(test, feature = "testing"))]
 pub mod tests;
 
@@ -79,7 +81,7 @@ pub use crate::vm::representations::{
 
 pub use crate::vm::contexts::MAX_CONTEXT_DEPTH;
 use crate::vm::costs::cost_functions::ClarityCostFunction;
-pub use crate::vm::functions::stx_transfer_consolidated;
+pub use crate::vm::functions::{stx_batch_transfer_consolidated, stx_transfer_consolidated};
 pub use crate::vm::version::ClarityVersion;
 use std::collections::BTreeMap;
 use std::convert::{TryFrom, TryInto};
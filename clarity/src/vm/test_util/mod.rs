@@ -49,7 +49,8 @@ pub fn generate_test_burn_state_db(epoch_id: StacksEpochId) -> UnitTestBurnState
         | StacksEpochId::Epoch21
         | StacksEpochId::Epoch22
         | StacksEpochId::Epoch23
-        | StacksEpochId::Epoch24 => UnitTestBurnStateDB {
+        | StacksEpochId::Epoch24
+        | StacksEpochId::Epoch25 => UnitTestBurnStateDB {
             epoch_id,
             ast_rules: ASTRules::PrecheckSize,
         },
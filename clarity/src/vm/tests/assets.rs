@@ -20,12 +20,13 @@ use crate::vm::contracts::Contract;
 use crate::vm::errors::{CheckErrors, Error, RuntimeErrorType};
 use crate::vm::events::StacksTransactionEvent;
 use crate::vm::execute as vm_execute;
+use crate::vm::execute_with_parameters;
 use crate::vm::representations::SymbolicExpression;
 use crate::vm::tests::{
     execute, is_committed, is_err_code, symbols_from_values, with_memory_environment,
 };
 use crate::vm::types::{
-    AssetIdentifier, PrincipalData, QualifiedContractIdentifier, ResponseData, Value,
+    AssetIdentifier, PrincipalData, QualifiedContractIdentifier, ResponseData, TypeSignature, Value,
 };
 use crate::vm::version::ClarityVersion;
 use crate::vm::ContractContext;
@@ -1386,3 +1387,61 @@ fn test_all() {
         with_memory_environment(test, StacksEpochId::latest(), true);
     }
 }
+
+#[test]
+fn test_get_stacking_state_unstacked() {
+    // no PoX contract has locked any STX in a fresh environment
+    for principal in [
+        "'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR",
+        "'STB44HYPYAT2BB2QE513NSP81HTMYWBJP02HPGK6.foo",
+        "tx-sender",
+    ]
+    .iter()
+    {
+        for mainnet in [true, false].iter() {
+            assert_eq!(
+                Value::none(),
+                execute_with_parameters(
+                    &format!("(get-stacking-state {})", principal),
+                    ClarityVersion::Clarity3,
+                    StacksEpochId::Epoch25,
+                    ASTRules::PrecheckSize,
+                    *mainnet,
+                )
+                .unwrap()
+                .unwrap()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_get_stacking_state_check_inputs() {
+    assert_eq!(
+        execute_with_parameters(
+            "(get-stacking-state u10)",
+            ClarityVersion::Clarity3,
+            StacksEpochId::Epoch25,
+            ASTRules::PrecheckSize,
+            true
+        )
+        .unwrap_err(),
+        CheckErrors::TypeValueError(TypeSignature::PrincipalType, Value::UInt(10)).into()
+    );
+}
+
+#[test]
+fn test_get_stacking_state_before_epoch_2_5() {
+    // `get-stacking-state` is a Clarity 3 built-in, and is unknown before Stacks 2.5
+    assert_eq!(
+        execute_with_parameters(
+            "(get-stacking-state tx-sender)",
+            ClarityVersion::Clarity3,
+            StacksEpochId::Epoch24,
+            ASTRules::PrecheckSize,
+            true
+        )
+        .unwrap_err(),
+        CheckErrors::UnknownFunction("get-stacking-state".to_string()).into()
+    );
+}
@@ -233,6 +233,147 @@ fn test_simple_buff_to_uint_be() {
     );
 }
 
+#[test]
+fn test_buff_to_int_empty_buffer() {
+    // An empty buffer is all padding, so every variant reads it as zero.
+    assert_eq!(
+        Value::Int(0),
+        execute_v2("(buff-to-int-le 0x)").unwrap().unwrap()
+    );
+    assert_eq!(
+        Value::UInt(0),
+        execute_v2("(buff-to-uint-le 0x)").unwrap().unwrap()
+    );
+    assert_eq!(
+        Value::Int(0),
+        execute_v2("(buff-to-int-be 0x)").unwrap().unwrap()
+    );
+    assert_eq!(
+        Value::UInt(0),
+        execute_v2("(buff-to-uint-be 0x)").unwrap().unwrap()
+    );
+}
+
+#[test]
+fn test_buff_to_int_known_vectors() {
+    // (buffer, little-endian value, big-endian value), read as unsigned integers
+    let vectors: &[(&str, u128, u128)] = &[
+        ("0x01", 0x01, 0x01),
+        ("0xff", 0xff, 0xff),
+        ("0x0102", 0x0201, 0x0102),
+        ("0x01020304", 0x04030201, 0x01020304),
+        // a Bitcoin transaction version and a block header timestamp
+        ("0x02000000", 2, 0x02000000),
+        ("0x29ab5f49", 1231006505, 0x29ab5f49),
+        (
+            "0x000102030405060708090a0b0c0d0e0f",
+            0x0f0e0d0c0b0a09080706050403020100,
+            0x000102030405060708090a0b0c0d0e0f,
+        ),
+    ];
+
+    for (buff, le, be) in vectors.iter() {
+        assert_eq!(
+            Value::UInt(*le),
+            execute_v2(&format!("(buff-to-uint-le {})", buff))
+                .unwrap()
+                .unwrap()
+        );
+        assert_eq!(
+            Value::UInt(*be),
+            execute_v2(&format!("(buff-to-uint-be {})", buff))
+                .unwrap()
+                .unwrap()
+        );
+        // none of these set the sign bit, so the signed variants agree
+        assert_eq!(
+            Value::Int(*le as i128),
+            execute_v2(&format!("(buff-to-int-le {})", buff))
+                .unwrap()
+                .unwrap()
+        );
+        assert_eq!(
+            Value::Int(*be as i128),
+            execute_v2(&format!("(buff-to-int-be {})", buff))
+                .unwrap()
+                .unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_buff_to_int_sign_bit() {
+    // The sign bit is the most significant bit of the most significant byte: the last byte
+    // for little-endian, and the first byte for big-endian.
+    assert_eq!(
+        Value::Int(i128::MIN),
+        execute_v2("(buff-to-int-le 0x00000000000000000000000000000080)")
+            .unwrap()
+            .unwrap()
+    );
+    assert_eq!(
+        Value::Int(i128::MIN),
+        execute_v2("(buff-to-int-be 0x80000000000000000000000000000000)")
+            .unwrap()
+            .unwrap()
+    );
+    assert_eq!(
+        Value::Int(i128::MAX),
+        execute_v2("(buff-to-int-le 0xffffffffffffffffffffffffffffff7f)")
+            .unwrap()
+            .unwrap()
+    );
+    assert_eq!(
+        Value::Int(i128::MAX),
+        execute_v2("(buff-to-int-be 0x7fffffffffffffffffffffffffffffff)")
+            .unwrap()
+            .unwrap()
+    );
+    assert_eq!(
+        Value::Int(-2),
+        execute_v2("(buff-to-int-be 0xfffffffffffffffffffffffffffffffe)")
+            .unwrap()
+            .unwrap()
+    );
+    assert_eq!(
+        Value::Int(-2),
+        execute_v2("(buff-to-int-le 0xfeffffffffffffffffffffffffffffff)")
+            .unwrap()
+            .unwrap()
+    );
+
+    // The unsigned variants read the same bytes as large positive integers.
+    assert_eq!(
+        Value::UInt(1 << 127),
+        execute_v2("(buff-to-uint-le 0x00000000000000000000000000000080)")
+            .unwrap()
+            .unwrap()
+    );
+    assert_eq!(
+        Value::UInt(u128::MAX - 1),
+        execute_v2("(buff-to-uint-be 0xfffffffffffffffffffffffffffffffe)")
+            .unwrap()
+            .unwrap()
+    );
+
+    // A short buffer is zero-padded on its most significant side, so its high bit is never
+    // the sign bit.
+    assert_eq!(
+        Value::Int(0x80),
+        execute_v2("(buff-to-int-le 0x80)").unwrap().unwrap()
+    );
+    assert_eq!(
+        Value::Int(0x80),
+        execute_v2("(buff-to-int-be 0x80)").unwrap().unwrap()
+    );
+    assert_eq!(
+        Value::Int((1 << 120) - 1),
+        execute_v2("(buff-to-int-be 0xffffffffffffffffffffffffffffff)")
+            .unwrap()
+            .unwrap()
+    );
+}
+
 #[test]
 fn test_simple_string_to_int() {
     let good1_test = r#"(string-to-int? "-1")"#;
@@ -363,6 +504,177 @@ fn test_simple_string_to_uint() {
     );
 }
 
+#[test]
+fn test_string_to_int_boundaries() {
+    // leading zeros are accepted
+    assert_eq!(
+        Value::some(Value::Int(-7)).unwrap(),
+        execute_v2(r#"(string-to-int? "-0007")"#).unwrap().unwrap()
+    );
+    assert_eq!(
+        Value::some(Value::UInt(7)).unwrap(),
+        execute_v2(r#"(string-to-uint? u"0007")"#).unwrap().unwrap()
+    );
+
+    // the extremes of `int` and `uint` parse, and one past them does not
+    assert_eq!(
+        Value::some(Value::Int(i128::MAX)).unwrap(),
+        execute_v2(r#"(string-to-int? "170141183460469231731687303715884105727")"#)
+            .unwrap()
+            .unwrap()
+    );
+    assert_eq!(
+        Value::none(),
+        execute_v2(r#"(string-to-int? "170141183460469231731687303715884105728")"#)
+            .unwrap()
+            .unwrap()
+    );
+    assert_eq!(
+        Value::some(Value::Int(i128::MIN)).unwrap(),
+        execute_v2(r#"(string-to-int? "-170141183460469231731687303715884105728")"#)
+            .unwrap()
+            .unwrap()
+    );
+    assert_eq!(
+        Value::none(),
+        execute_v2(r#"(string-to-int? "-170141183460469231731687303715884105729")"#)
+            .unwrap()
+            .unwrap()
+    );
+    assert_eq!(
+        Value::some(Value::UInt(u128::MAX)).unwrap(),
+        execute_v2(r#"(string-to-uint? "340282366920938463463374607431768211455")"#)
+            .unwrap()
+            .unwrap()
+    );
+    assert_eq!(
+        Value::none(),
+        execute_v2(r#"(string-to-uint? "340282366920938463463374607431768211456")"#)
+            .unwrap()
+            .unwrap()
+    );
+
+    // a `uint` cannot be negative, and a sign alone is not a number
+    assert_eq!(
+        Value::none(),
+        execute_v2(r#"(string-to-uint? "-1")"#).unwrap().unwrap()
+    );
+    assert_eq!(
+        Value::none(),
+        execute_v2(r#"(string-to-int? "-")"#).unwrap().unwrap()
+    );
+
+    // rendering the extremes round-trips
+    assert_eq!(
+        Value::some(Value::Int(i128::MIN)).unwrap(),
+        execute_v2(&format!("(string-to-int? (int-to-ascii {}))", i128::MIN))
+            .unwrap()
+            .unwrap()
+    );
+    assert_eq!(
+        Value::some(Value::UInt(u128::MAX)).unwrap(),
+        execute_v2(&format!("(string-to-uint? (int-to-utf8 u{}))", u128::MAX))
+            .unwrap()
+            .unwrap()
+    );
+}
+
+#[test]
+fn test_string_to_int_rejects_non_decimal() {
+    let not_decimal = [
+        // unicode digits outside of ASCII 0-9
+        r#"u"\u{0663}""#,
+        r#"u"\u{FF11}""#,
+        r#"u"\u{00B2}""#,
+        r#"u"1\u{0663}""#,
+        r#"u"-\u{FF11}""#,
+        // empty strings
+        r#""""#,
+        r#"u"""#,
+        // whitespace, separators, and other notations
+        r#"" 1""#,
+        r#""1 ""#,
+        r#""1_000""#,
+        r#""1,000""#,
+        r#""1.0""#,
+        r#""1e3""#,
+        r#""0x10""#,
+        r#""--1""#,
+        r#""-+1""#,
+        r#""1-""#,
+    ];
+    for string in not_decimal.iter() {
+        for function in ["string-to-int?", "string-to-uint?"].iter() {
+            let program = format!("({} {})", function, string);
+            assert_eq!(
+                Value::none(),
+                execute_v2(&program).unwrap().unwrap(),
+                "{}",
+                &program
+            );
+        }
+    }
+
+    // a lone leading `+` is accepted by both, as it always has been
+    assert_eq!(
+        Value::some(Value::Int(1)).unwrap(),
+        execute_v2(r#"(string-to-int? "+1")"#).unwrap().unwrap()
+    );
+    assert_eq!(
+        Value::some(Value::UInt(1)).unwrap(),
+        execute_v2(r#"(string-to-uint? u"+1")"#).unwrap().unwrap()
+    );
+
+    // zero, with or without a sign or leading zeros
+    for string in [r#""0""#, r#""-0""#, r#""000""#, r#"u"-000""#].iter() {
+        assert_eq!(
+            Value::some(Value::Int(0)).unwrap(),
+            execute_v2(&format!("(string-to-int? {})", string))
+                .unwrap()
+                .unwrap()
+        );
+    }
+    assert_eq!(
+        Value::none(),
+        execute_v2(r#"(string-to-uint? "-0")"#).unwrap().unwrap()
+    );
+}
+
+#[test]
+fn test_int_to_string_extremes() {
+    // the longest renderings of an `int` and a `uint` fit in the 40 characters the type checker
+    // allows for them
+    let min_int = i128::MIN.to_string();
+    assert_eq!(min_int.len(), 40);
+    assert_eq!(
+        Value::string_ascii_from_bytes(min_int.as_bytes().to_vec()).unwrap(),
+        execute_v2(&format!("(int-to-ascii {})", i128::MIN))
+            .unwrap()
+            .unwrap()
+    );
+    assert_eq!(
+        Value::string_utf8_from_bytes(min_int.as_bytes().to_vec()).unwrap(),
+        execute_v2(&format!("(int-to-utf8 {})", i128::MIN))
+            .unwrap()
+            .unwrap()
+    );
+
+    let max_uint = u128::MAX.to_string();
+    assert!(max_uint.len() <= 40);
+    assert_eq!(
+        Value::string_ascii_from_bytes(max_uint.as_bytes().to_vec()).unwrap(),
+        execute_v2(&format!("(int-to-ascii u{})", u128::MAX))
+            .unwrap()
+            .unwrap()
+    );
+
+    // zero is rendered without any padding
+    assert_eq!(
+        Value::string_ascii_from_bytes(b"0".to_vec()).unwrap(),
+        execute_v2("(int-to-ascii 0)").unwrap().unwrap()
+    );
+}
+
 #[test]
 fn test_simple_int_to_ascii() {
     let good1_test = r#"(int-to-ascii -1)"#;
@@ -1179,3 +1179,142 @@ fn test_principal_construct_response_errors() {
         .unwrap()
     );
 }
+
+#[test]
+// Test that `principal-construct?` rebuilds the principal that `principal-destruct?` decomposed,
+// for both standard and contract principals, on the matching and the other network.
+fn test_principal_destruct_construct_round_trip() {
+    let round_trip = |principal: &str, mainnet: bool| {
+        let input = format!(
+            r#"(let ((parts (match (principal-destruct? {})
+                                 ok-parts ok-parts
+                                 err-parts err-parts)))
+                 (match (get name parts)
+                     name (principal-construct? (get version parts) (get hash-bytes parts) name)
+                     (principal-construct? (get version parts) (get hash-bytes parts))))"#,
+            principal
+        );
+        execute_with_parameters(
+            &input,
+            ClarityVersion::Clarity2,
+            StacksEpochId::Epoch21,
+            ASTRules::PrecheckSize,
+            mainnet,
+        )
+        .unwrap()
+        .unwrap()
+    };
+    let principal_value = |principal: &str| {
+        execute(principal)
+            .unwrap()
+            .expect("FAIL: Failed to parse principal.")
+    };
+
+    // Principals on the network we are running against come back as `ok`.
+    for (principal, mainnet) in [
+        ("'SP3X6QWWETNBZWGBK6DRGTR1KX50S74D3433WDGJY", true),
+        ("'SM3X6QWWETNBZWGBK6DRGTR1KX50S74D341M9C5X7", true),
+        ("'SP3X6QWWETNBZWGBK6DRGTR1KX50S74D3433WDGJY.foo", true),
+        ("'ST3X6QWWETNBZWGBK6DRGTR1KX50S74D3425Q1TPK", false),
+        ("'SN3X6QWWETNBZWGBK6DRGTR1KX50S74D340JWTSC7", false),
+        ("'ST3X6QWWETNBZWGBK6DRGTR1KX50S74D3425Q1TPK.foo", false),
+    ]
+    .iter()
+    {
+        assert_eq!(
+            Value::okay(principal_value(principal)).unwrap(),
+            round_trip(principal, *mainnet)
+        );
+    }
+
+    // Principals on the other network come back inside the `VERSION_BYTE` error.
+    for (principal, mainnet) in [
+        ("'SP3X6QWWETNBZWGBK6DRGTR1KX50S74D3433WDGJY", false),
+        ("'SP3X6QWWETNBZWGBK6DRGTR1KX50S74D3433WDGJY.foo", false),
+        ("'ST3X6QWWETNBZWGBK6DRGTR1KX50S74D3425Q1TPK", true),
+        ("'ST3X6QWWETNBZWGBK6DRGTR1KX50S74D3425Q1TPK.foo", true),
+    ]
+    .iter()
+    {
+        assert_eq!(
+            Value::error(Value::Tuple(
+                TupleData::from_data(vec![
+                    (
+                        "error_code".into(),
+                        Value::UInt(PrincipalConstructErrorCode::VERSION_BYTE as u128)
+                    ),
+                    (
+                        "value".into(),
+                        Value::some(principal_value(principal)).unwrap()
+                    ),
+                ])
+                .expect("FAIL: Failed to initialize tuple."),
+            ))
+            .unwrap(),
+            round_trip(principal, *mainnet)
+        );
+    }
+}
+
+#[test]
+fn test_principal_version_check_inputs() {
+    let wrong_type_test = "(principal-version u10)";
+    assert_eq!(
+        execute_with_parameters(
+            wrong_type_test,
+            ClarityVersion::Clarity3,
+            StacksEpochId::Epoch25,
+            ASTRules::PrecheckSize,
+            true
+        )
+        .unwrap_err(),
+        CheckErrors::TypeValueError(PrincipalType, Value::UInt(10)).into()
+    );
+}
+
+#[test]
+fn test_principal_version_before_epoch_2_5() {
+    // `principal-version` is a Clarity 3 built-in, and is unknown before Stacks 2.5
+    assert_eq!(
+        execute_with_parameters(
+            "(principal-version tx-sender)",
+            ClarityVersion::Clarity3,
+            StacksEpochId::Epoch24,
+            ASTRules::PrecheckSize,
+            true
+        )
+        .unwrap_err(),
+        CheckErrors::UnknownFunction("principal-version".to_string()).into()
+    );
+}
+
+#[test]
+fn test_principal_version() {
+    for (principal, expected_version) in [
+        ("'SP3X6QWWETNBZWGBK6DRGTR1KX50S74D3433WDGJY", 22),
+        ("'SM3X6QWWETNBZWGBK6DRGTR1KX50S74D341M9C5X7", 20),
+        ("'ST3X6QWWETNBZWGBK6DRGTR1KX50S74D3425Q1TPK", 26),
+        ("'SN3X6QWWETNBZWGBK6DRGTR1KX50S74D340JWTSC7", 21),
+        ("'STB44HYPYAT2BB2QE513NSP81HTMYWBJP02HPGK6.foo", 26),
+        // matches neither network
+        ("'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR", 31),
+    ]
+    .iter()
+    {
+        // The version byte is reported regardless of the network we run against.
+        for mainnet in [true, false].iter() {
+            assert_eq!(
+                Value::okay(Value::UInt(*expected_version)).unwrap(),
+                execute_with_parameters(
+                    &format!("(principal-version {})", principal),
+                    ClarityVersion::Clarity3,
+                    StacksEpochId::Epoch25,
+                    ASTRules::PrecheckSize,
+                    *mainnet,
+                )
+                .unwrap()
+                .unwrap()
+            );
+        }
+    }
+}
@@ -567,6 +567,72 @@ fn test_slice_utf8() {
     }
 }
 
+#[test]
+fn test_slice_and_replace_at_max_size_list() {
+    // (list 65535 int) is the longest list of ints that fits in MAX_VALUE_SIZE
+    let max_len = 65535;
+    let items: Vec<_> = (0..max_len).map(|i| i.to_string()).collect();
+    let max_list = format!("(list {})", items.join(" "));
+    let all_items: Vec<_> = (0..max_len).map(|i| Value::Int(i as i128)).collect();
+
+    // the whole list
+    assert_eq!(
+        Value::some(Value::list_from(all_items.clone()).unwrap()).unwrap(),
+        execute_v2(&format!("(slice? {} u0 u{})", &max_list, max_len))
+            .unwrap()
+            .unwrap()
+    );
+
+    // the tail of the list
+    assert_eq!(
+        Value::some(Value::list_from(vec![Value::Int(65533), Value::Int(65534)]).unwrap()).unwrap(),
+        execute_v2(&format!(
+            "(slice? {} u{} u{})",
+            &max_list,
+            max_len - 2,
+            max_len
+        ))
+        .unwrap()
+        .unwrap()
+    );
+
+    // an empty slice
+    assert_eq!(
+        Value::some(Value::list_from(vec![]).unwrap()).unwrap(),
+        execute_v2(&format!("(slice? {} u100 u100)", &max_list))
+            .unwrap()
+            .unwrap()
+    );
+
+    // out of bounds, or end before start
+    for (start, end) in [(max_len, max_len), (0, max_len + 1), (100, 99)].iter() {
+        assert_eq!(
+            Value::none(),
+            execute_v2(&format!("(slice? {} u{} u{})", &max_list, start, end))
+                .unwrap()
+                .unwrap()
+        );
+    }
+
+    // replace the last item
+    let mut replaced_items = all_items;
+    replaced_items[max_len - 1] = Value::Int(-1);
+    assert_eq!(
+        Value::some(Value::list_from(replaced_items).unwrap()).unwrap(),
+        execute_v2(&format!("(replace-at? {} u{} -1)", &max_list, max_len - 1))
+            .unwrap()
+            .unwrap()
+    );
+
+    // ...but not one past it
+    assert_eq!(
+        Value::none(),
+        execute_v2(&format!("(replace-at? {} u{} -1)", &max_list, max_len))
+            .unwrap()
+            .unwrap()
+    );
+}
+
 #[test]
 fn test_simple_list_concat() {
     let tests = [
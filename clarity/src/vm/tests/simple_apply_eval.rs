@@ -46,8 +46,9 @@ use stacks_common::consts::{CHAIN_ID_MAINNET, CHAIN_ID_TESTNET};
 use stacks_common::types::chainstate::StacksAddress;
 use stacks_common::types::chainstate::StacksPrivateKey;
 use stacks_common::types::chainstate::StacksPublicKey;
+use stacks_common::types::PrivateKey;
 use stacks_common::types::StacksEpochId;
-use stacks_common::util::hash::{hex_bytes, to_hex};
+use stacks_common::util::hash::{hex_bytes, to_hex, Sha256Sum};
 
 #[template]
 #[rstest]
@@ -427,6 +428,70 @@ fn test_to_from_consensus_buff_vectors() {
     }
 }
 
+#[test]
+/// Serialize values with `to-consensus-buff?` and read them back with
+///  `from-consensus-buff?` in the same program, checking the round trip
+///  and that reading the buffer as a different type yields none
+fn test_to_from_consensus_buff_round_trip() {
+    let vectors = [
+        (
+            "{ owner: 'S08XXBDYXW8TQAZZZW8XXBDYXW8TQAZZZZ88551S, amount: u100, memo: (some 0x0102) }",
+            "{ owner: principal, amount: uint, memo: (optional (buff 2)) }",
+            "{ owner: principal, amount: int, memo: (optional (buff 2)) }",
+        ),
+        (
+            "{ inner: { flag: true, who: none }, count: -5 }",
+            "{ inner: { flag: bool, who: (optional principal) }, count: int }",
+            "{ inner: { flag: bool }, count: int }",
+        ),
+        (
+            "(some (some 'S08XXBDYXW8TQAZZZW8XXBDYXW8TQAZZZZ88551S.abcd))",
+            "(optional (optional principal))",
+            "(optional principal)",
+        ),
+        ("(some u7)", "(optional uint)", "(optional int)"),
+        (
+            "'S08XXBDYXW8TQAZZZW8XXBDYXW8TQAZZZZ88551S",
+            "principal",
+            "(buff 22)",
+        ),
+        (
+            "'S08XXBDYXW8TQAZZZW8XXBDYXW8TQAZZZZ88551S.abcd",
+            "principal",
+            "{ a: principal }",
+        ),
+    ];
+
+    for (value_repr, type_repr, other_type_repr) in vectors.iter() {
+        let program = format!(
+            "(from-consensus-buff? {} (unwrap-panic (to-consensus-buff? {})))",
+            type_repr, value_repr
+        );
+        eprintln!("{}", program);
+        let result_val = vm_execute_v2(&program)
+            .expect("round trip should succeed")
+            .expect("round trip should return")
+            .expect_optional()
+            .expect("from-consensus-buff? should return (some value)");
+        let expected_val = execute(value_repr);
+        assert_eq!(result_val, expected_val);
+
+        let program = format!(
+            "(from-consensus-buff? {} (unwrap-panic (to-consensus-buff? {})))",
+            other_type_repr, value_repr
+        );
+        eprintln!("{}", program);
+        let result_val = vm_execute_v2(&program)
+            .expect("from-consensus-buff? should succeed")
+            .expect("from-consensus-buff? should return")
+            .expect_optional();
+        assert!(
+            result_val.is_none(),
+            "from-consensus-buff? should return none"
+        );
+    }
+}
+
 #[test]
 fn test_secp256k1() {
     let secp256k1_evals = [
@@ -623,6 +688,100 @@ fn test_secp256k1_errors() {
     }
 }
 
+/// The order of the secp256k1 curve, big-endian
+const SECP256K1_ORDER: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
+    0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36, 0x41, 0x41,
+];
+
+/// Replace the s-value of an r || s (|| v) signature with n - s, which is an equally valid
+/// signature for the same message and key, but in high-s form.  The recovery ID, if present,
+/// is flipped to match.
+fn malleate_secp256k1_signature(signature: &[u8]) -> Vec<u8> {
+    let mut malleated = signature.to_vec();
+    let mut borrow = 0i16;
+    for i in (0..32).rev() {
+        let mut diff = SECP256K1_ORDER[i] as i16 - signature[32 + i] as i16 - borrow;
+        borrow = 0;
+        if diff < 0 {
+            diff += 256;
+            borrow = 1;
+        }
+        malleated[32 + i] = diff as u8;
+    }
+    if malleated.len() == 65 {
+        malleated[64] ^= 1;
+    }
+    malleated
+}
+
+#[test]
+fn test_secp256k1_stacks_private_key_signature() {
+    let privk = StacksPrivateKey::from_hex(
+        "510f96a8efd0b11e211733c1ac5e3fa6f3d3fcdd62869e376c47decb3e14fea101",
+    )
+    .unwrap();
+    let pubkey = StacksPublicKey::from_private(&privk).to_bytes_compressed();
+    let message_hash = Sha256Sum::from_data(b"a meta-transaction to authorize");
+
+    // Stacks signatures are v || r || s, but Clarity takes r || s || v
+    let stacks_signature = privk.sign(message_hash.as_bytes()).unwrap();
+    let mut signature = stacks_signature.0[1..].to_vec();
+    signature.push(stacks_signature.0[0]);
+
+    let message_hex = to_hex(message_hash.as_bytes());
+    let pubkey_hex = to_hex(&pubkey);
+    let recover = |signature: &[u8]| {
+        execute(&format!(
+            "(secp256k1-recover? 0x{} 0x{})",
+            &message_hex,
+            to_hex(signature)
+        ))
+    };
+    let verify = |signature: &[u8]| {
+        execute(&format!(
+            "(secp256k1-verify 0x{} 0x{} 0x{})",
+            &message_hex,
+            to_hex(signature),
+            &pubkey_hex
+        ))
+    };
+
+    assert_eq!(
+        recover(&signature),
+        Value::okay(Value::buff_from(pubkey.clone()).unwrap()).unwrap()
+    );
+    assert_eq!(verify(&signature), Value::Bool(true));
+    // the recovery ID is optional for verification
+    assert_eq!(verify(&signature[..64]), Value::Bool(true));
+
+    // a corrupted signature doesn't verify, and doesn't recover the signer's key
+    let mut corrupted_signature = signature.clone();
+    corrupted_signature[0] ^= 0x01;
+    assert_eq!(verify(&corrupted_signature), Value::Bool(false));
+    assert_ne!(
+        recover(&corrupted_signature),
+        Value::okay(Value::buff_from(pubkey.clone()).unwrap()).unwrap()
+    );
+
+    // the malleated signature is valid ECDSA, but only low-s signatures verify...
+    let malleated = malleate_secp256k1_signature(&signature);
+    assert_ne!(malleated[32..64], signature[32..64]);
+    assert_eq!(verify(&malleated), Value::Bool(false));
+    assert_eq!(verify(&malleated[..64]), Value::Bool(false));
+
+    // ...while recovery does not check the s-value, so the malleated signature (with its
+    // recovery ID flipped) recovers the signer's key.  Contracts must not use a recoverable
+    // signature as a unique identifier.
+    assert_eq!(
+        recover(&malleated),
+        Value::okay(Value::buff_from(pubkey.clone()).unwrap()).unwrap()
+    );
+
+    // malleating twice gives back the original signature
+    assert_eq!(malleate_secp256k1_signature(&malleated), signature);
+}
+
 #[test]
 fn test_buffer_equality() {
     let tests = [
@@ -1744,6 +1903,20 @@ fn test_is_mainnet() {
         });
 }
 
+#[test]
+fn test_is_in_regtest_guard() {
+    // `is-in-regtest` is a keyword, so test contracts can branch on it directly, but it
+    // cannot be called like a function
+    assert_eq!(
+        vm_execute("(if is-in-regtest 1 2)").unwrap(),
+        Some(Value::Int(1))
+    );
+    assert_eq!(
+        vm_execute("(is-in-regtest)").unwrap_err(),
+        CheckErrors::UndefinedFunction("is-in-regtest".to_string()).into()
+    );
+}
+
 #[test]
 fn test_chain_id() {
     let tests = ["chain-id"];
@@ -2903,6 +2903,13 @@ impl SortitionDB {
                     || version == "6"
                     || version == "7"
             }
+            StacksEpochId::Epoch25 => {
+                version == "3"
+                    || version == "4"
+                    || version == "5"
+                    || version == "6"
+                    || version == "7"
+            }
         }
     }
 
@@ -9520,6 +9527,67 @@ pub mod tests {
         assert_eq!(ancestors, vec![BurnchainHeaderHash([0xfe; 32])]);
     }
 
+    #[test]
+    fn test_burn_state_db_get_burn_header_hash() {
+        use clarity::vm::database::BurnStateDB;
+
+        let block_height = 100;
+        let first_burn_hash = BurnchainHeaderHash([0x00; 32]);
+        let mut db = SortitionDB::connect_test(block_height, &first_burn_hash).unwrap();
+        let mut snapshots = vec![];
+        for i in 1..11 {
+            snapshots.push(test_append_snapshot(
+                &mut db,
+                BurnchainHeaderHash([i as u8; 32]),
+                &vec![],
+            ));
+        }
+        let tip = SortitionDB::get_canonical_burn_chain_tip(db.conn()).unwrap();
+        assert_eq!(tip.block_height, 110);
+
+        let ic = db.index_conn();
+
+        // every burn block from the first one up to the tip is visible
+        for i in 0..11 {
+            assert_eq!(
+                BurnStateDB::get_burn_header_hash(&ic, 100 + i, &tip.sortition_id),
+                Some(BurnchainHeaderHash([i as u8; 32]))
+            );
+        }
+
+        // nothing before the first burn block
+        assert_eq!(
+            BurnStateDB::get_burn_header_hash(&ic, 99, &tip.sortition_id),
+            None
+        );
+        assert_eq!(
+            BurnStateDB::get_burn_header_hash(&ic, 0, &tip.sortition_id),
+            None
+        );
+
+        // nothing after the tip
+        assert_eq!(
+            BurnStateDB::get_burn_header_hash(&ic, 111, &tip.sortition_id),
+            None
+        );
+        assert_eq!(
+            BurnStateDB::get_burn_header_hash(&ic, u32::MAX, &tip.sortition_id),
+            None
+        );
+
+        // nothing after an earlier tip, either
+        let earlier_tip = &snapshots[4];
+        assert_eq!(earlier_tip.block_height, 105);
+        assert_eq!(
+            BurnStateDB::get_burn_header_hash(&ic, 105, &earlier_tip.sortition_id),
+            Some(BurnchainHeaderHash([0x05; 32]))
+        );
+        assert_eq!(
+            BurnStateDB::get_burn_header_hash(&ic, 106, &earlier_tip.sortition_id),
+            None
+        );
+    }
+
     #[test]
     fn test_get_set_ast_rules() {
         let block_height = 123;
@@ -334,4 +334,83 @@ mod tests {
         assert_eq!(op.transfered_ustx, u128::from_be_bytes([1; 16]));
         assert_eq!(op.memo, vec![1; 61]);
     }
+
+    fn make_transfer_stx_tx(data: Vec<u8>, input_vout: u32) -> BurnchainTransaction {
+        BurnchainTransaction::Bitcoin(BitcoinTransaction {
+            txid: Txid([0; 32]),
+            vtxindex: 0,
+            opcode: Opcodes::TransferStx as u8,
+            data,
+            data_amt: 0,
+            inputs: vec![BitcoinTxInputStructured {
+                keys: vec![],
+                num_required: 0,
+                in_type: BitcoinInputType::Standard,
+                tx_ref: (Txid([0; 32]), input_vout),
+            }
+            .into()],
+            outputs: vec![BitcoinTxOutput {
+                units: 10,
+                address: BitcoinAddress::Legacy(LegacyBitcoinAddress {
+                    addrtype: LegacyBitcoinAddressType::PublicKeyHash,
+                    network_id: BitcoinNetworkType::Mainnet,
+                    bytes: Hash160([1; 20]),
+                }),
+            }],
+        })
+    }
+
+    #[test]
+    fn test_parse_transfer_stx_payload_length() {
+        let sender = StacksAddress {
+            version: 0,
+            bytes: Hash160([0; 20]),
+        };
+
+        // no memo at all is fine
+        let op = TransferStxOp::parse_from_tx(
+            16843022,
+            &BurnchainHeaderHash([0; 32]),
+            &make_transfer_stx_tx(vec![1; 16], 1),
+            &sender,
+        )
+        .unwrap();
+        assert_eq!(op.transfered_ustx, u128::from_be_bytes([1; 16]));
+        assert!(op.memo.is_empty());
+
+        // too short to hold the uSTX amount
+        match TransferStxOp::parse_from_tx(
+            16843022,
+            &BurnchainHeaderHash([0; 32]),
+            &make_transfer_stx_tx(vec![1; 15], 1),
+            &sender,
+        ) {
+            Err(op_error::ParseError) => {}
+            res => panic!("Expected a parse error, got {:?}", res),
+        }
+
+        // memo is longer than 61 bytes
+        match TransferStxOp::parse_from_tx(
+            16843022,
+            &BurnchainHeaderHash([0; 32]),
+            &make_transfer_stx_tx(vec![1; 78], 1),
+            &sender,
+        ) {
+            Err(op_error::ParseError) => {}
+            res => panic!("Expected a parse error, got {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_transfer_stx_must_spend_pre_stx_change() {
+        // the sender is identified by the PreStxOp output at index 1
+        let tx = make_transfer_stx_tx(vec![1; 16], 1);
+        assert_eq!(TransferStxOp::get_sender_txid(&tx).unwrap(), &Txid([0; 32]));
+
+        let tx = make_transfer_stx_tx(vec![1; 16], 0);
+        match TransferStxOp::get_sender_txid(&tx) {
+            Err(op_error::InvalidInput) => {}
+            res => panic!("Expected an invalid input error, got {:?}", res),
+        }
+    }
 }
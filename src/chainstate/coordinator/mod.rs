@@ -156,6 +156,28 @@ pub trait BlockEventDispatcher {
         pox_constants: &PoxConstants,
     );
 
+    /// called after `announce_block` whenever the processed
+    ///  block is the first block of a new Stacks epoch.
+    fn announce_epoch_transition(
+        &self,
+        block_id: &StacksBlockId,
+        burn_block_height: u64,
+        old_epoch: StacksEpochId,
+        new_epoch: StacksEpochId,
+    );
+
+    /// called whenever the canonical burnchain fork changes, before
+    ///  the burn blocks of the new fork are processed for sortition.
+    ///  `orphaned_stacks_blocks` are the processed Stacks blocks that
+    ///  were mined in the `reorg_depth` abandoned burn blocks.
+    fn announce_burn_block_reorg(
+        &self,
+        old_canonical_tip: &BurnchainHeaderHash,
+        new_canonical_tip: &BurnchainHeaderHash,
+        reorg_depth: u64,
+        orphaned_stacks_blocks: &[BlockHeaderHash],
+    );
+
     /// called whenever a burn block is about to be
     ///  processed for sortition. note, in the event
     ///  of PoX forks, this will be called _multiple_
@@ -281,8 +303,8 @@ impl RewardSetProvider for OnChainRewardSetProvider {
                 info!("PoX reward cycle defaulting to burn in Epochs 2.2 and 2.3");
                 return Ok(RewardSet::empty());
             }
-            StacksEpochId::Epoch24 => {
-                // Epoch 2.4 computes reward sets, but *only* if PoX-3 is active
+            StacksEpochId::Epoch24 | StacksEpochId::Epoch25 => {
+                // Epochs 2.4 and 2.5 compute reward sets, but *only* if PoX-3 is active
                 if burnchain
                     .pox_constants
                     .active_pox_contract(current_burn_height)
@@ -2126,6 +2148,72 @@ impl<
         Ok(None)
     }
 
+    /// If the canonical burnchain fork no longer includes `old_tip`, then announce the burnchain
+    /// reorg to the dispatcher.  `fork_point` is the sortition of the highest processed ancestor
+    /// of `new_tip`.  The announcement lists the processed Stacks blocks that were mined in the
+    /// abandoned burnchain blocks, from lowest to highest.
+    fn announce_burnchain_reorg(
+        &self,
+        old_tip: &BlockSnapshot,
+        fork_point: &SortitionId,
+        new_tip: &BurnchainHeaderHash,
+    ) -> Result<(), Error> {
+        let dispatcher = match self.dispatcher {
+            Some(dispatcher) => dispatcher,
+            None => {
+                return Ok(());
+            }
+        };
+        let fork_point = SortitionDB::get_block_snapshot(self.sortition_db.conn(), fork_point)?
+            .expect(&format!(
+                "FATAL: do not have processed ancestor sortition {}",
+                fork_point
+            ));
+        if fork_point.block_height >= old_tip.block_height {
+            // the new burnchain tip descends from the old one
+            return Ok(());
+        }
+
+        let mut orphaned_stacks_blocks = vec![];
+        let mut cursor = old_tip.clone();
+        while cursor.block_height > fork_point.block_height {
+            if cursor.sortition
+                && StacksChainState::get_stacks_block_header_info_by_index_block_hash(
+                    self.chain_state_db.db(),
+                    &StacksBlockId::new(&cursor.consensus_hash, &cursor.winning_stacks_block_hash),
+                )?
+                .is_some()
+            {
+                orphaned_stacks_blocks.push(cursor.winning_stacks_block_hash.clone());
+            }
+            cursor = SortitionDB::get_block_snapshot(
+                self.sortition_db.conn(),
+                &cursor.parent_sortition_id,
+            )?
+            .expect(&format!(
+                "FATAL: do not have parent sortition of {}",
+                &cursor.sortition_id
+            ));
+        }
+        orphaned_stacks_blocks.reverse();
+
+        let reorg_depth = old_tip.block_height - fork_point.block_height;
+        info!("Burnchain reorg";
+              "old_canonical_tip" => %old_tip.burn_header_hash,
+              "new_canonical_tip" => %new_tip,
+              "fork_point_height" => fork_point.block_height,
+              "reorg_depth" => reorg_depth,
+              "orphaned_stacks_blocks" => orphaned_stacks_blocks.len());
+
+        dispatcher.announce_burn_block_reorg(
+            &old_tip.burn_header_hash,
+            new_tip,
+            reorg_depth,
+            &orphaned_stacks_blocks,
+        );
+        Ok(())
+    }
+
     /// Outermost call to process a burnchain block.
     /// Not called internally.
     pub fn handle_new_burnchain_block(&mut self) -> Result<Option<BlockHeaderHash>, Error> {
@@ -2242,6 +2330,16 @@ impl<
             burn_header_hashes.join(", ")
         );
 
+        // the burnchain reorged if the new canonical burnchain tip does not descend from our
+        // canonical sortition tip
+        if !sortitions_to_process.is_empty() {
+            self.announce_burnchain_reorg(
+                &canonical_snapshot,
+                &last_processed_ancestor,
+                &canonical_burnchain_tip.block_hash,
+            )?;
+        }
+
         // if this is set to true, the notify that a stacks block has been processed.
         // this wakes up anyone waiting for their block to have been processed.
         let mut revalidated_stacks_block = false;
@@ -3019,7 +3117,8 @@ impl<
                             StacksEpochId::Epoch21
                             | StacksEpochId::Epoch22
                             | StacksEpochId::Epoch23
-                            | StacksEpochId::Epoch24 => {
+                            | StacksEpochId::Epoch24
+                            | StacksEpochId::Epoch25 => {
                                 // 2.1 and onward behavior: the anchor block must also be the
                                 // heaviest-confirmed anchor block by BTC weight, and the highest
                                 // such anchor block if there are multiple contenders.
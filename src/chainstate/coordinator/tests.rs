@@ -436,6 +436,19 @@ impl BlockEventDispatcher for NullEventDispatcher {
         );
     }
 
+    fn announce_epoch_transition(
+        &self,
+        _block_id: &StacksBlockId,
+        _burn_block_height: u64,
+        _old_epoch: StacksEpochId,
+        _new_epoch: StacksEpochId,
+    ) {
+        assert!(
+            false,
+            "We should never try to announce to the null dispatcher"
+        );
+    }
+
     fn announce_burn_block(
         &self,
         _burn_block: &BurnchainHeaderHash,
@@ -445,6 +458,15 @@ impl BlockEventDispatcher for NullEventDispatcher {
         _slot_holders: Vec<PoxAddress>,
     ) {
     }
+
+    fn announce_burn_block_reorg(
+        &self,
+        _old_canonical_tip: &BurnchainHeaderHash,
+        _new_canonical_tip: &BurnchainHeaderHash,
+        _reorg_depth: u64,
+        _orphaned_stacks_blocks: &[BlockHeaderHash],
+    ) {
+    }
 }
 
 pub fn make_coordinator<'a>(
@@ -660,7 +682,7 @@ fn make_genesis_block_with_recipients(
         ),
         key_block_ptr: 1, // all registers happen in block height 1
         key_vtxindex: (1 + key_index) as u16,
-        memo: vec![STACKS_EPOCH_2_4_MARKER],
+        memo: vec![STACKS_EPOCH_2_5_MARKER],
         new_seed: VRFSeed::from_proof(&proof),
         commit_outs,
 
@@ -923,7 +945,7 @@ fn make_stacks_block_with_input(
         ),
         key_block_ptr: 1, // all registers happen in block height 1
         key_vtxindex: (1 + key_index) as u16,
-        memo: vec![STACKS_EPOCH_2_4_MARKER],
+        memo: vec![STACKS_EPOCH_2_5_MARKER],
         new_seed: VRFSeed::from_proof(&proof),
         commit_outs,
 
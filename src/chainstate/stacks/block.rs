@@ -592,9 +592,36 @@ impl StacksBlock {
                 }
             }
         }
+        if epoch_id < StacksEpochId::Epoch25 {
+            // * no Clarity 3 smart contract payloads
+            for tx in txs.iter() {
+                if let TransactionPayload::SmartContract(_, Some(ClarityVersion::Clarity3)) =
+                    &tx.payload
+                {
+                    // not supported
+                    error!("Clarity 3 smart contracts not supported before Stacks 2.5"; "txid" => %tx.txid());
+                    return false;
+                }
+            }
+        }
+        if !StacksBlock::batch_token_transfers_supported(epoch_id) {
+            for tx in txs.iter() {
+                if let TransactionPayload::BatchTokenTransfer(..) = &tx.payload {
+                    error!("Batched STX transfers not supported in {}", epoch_id; "txid" => %tx.txid());
+                    return false;
+                }
+            }
+        }
         return true;
     }
 
+    /// Can BatchTokenTransfer payloads be mined in the given epoch?
+    /// No epoch up to and including Stacks 2.4 accepts them, since doing so is a consensus
+    /// change.  Activating them requires a new epoch.
+    pub fn batch_token_transfers_supported(epoch_id: StacksEpochId) -> bool {
+        epoch_id > StacksEpochId::Epoch24
+    }
+
     /// static sanity checks on transactions.
     pub fn validate_transactions_static(
         &self,
@@ -1766,6 +1793,17 @@ mod test {
                 Some(ClarityVersion::Clarity1),
             ),
         );
+        let tx_clarity3_smart_contract = StacksTransaction::new(
+            TransactionVersion::Testnet,
+            origin_auth.clone(),
+            TransactionPayload::SmartContract(
+                TransactionSmartContract {
+                    name: ContractName::try_from("hello-world").unwrap(),
+                    code_body: StacksString::from_str("(print \"hello world\")").unwrap(),
+                },
+                Some(ClarityVersion::Clarity3),
+            ),
+        );
 
         let dup_txs = vec![
             tx_coinbase.clone(),
@@ -1778,6 +1816,7 @@ mod test {
         let no_coinbase = vec![tx_transfer.clone()];
         let coinbase_contract = vec![tx_coinbase_contract.clone()];
         let versioned_contract = vec![tx_versioned_smart_contract.clone()];
+        let clarity3_contract = vec![tx_clarity3_smart_contract.clone()];
 
         assert!(!StacksBlock::validate_transactions_unique(&dup_txs));
         assert!(!StacksBlock::validate_transactions_network(
@@ -1808,6 +1847,19 @@ mod test {
             &versioned_contract,
             StacksEpochId::Epoch21
         ));
+
+        assert!(!StacksBlock::validate_transactions_static_epoch(
+            &clarity3_contract,
+            StacksEpochId::Epoch2_05
+        ));
+        assert!(!StacksBlock::validate_transactions_static_epoch(
+            &clarity3_contract,
+            StacksEpochId::Epoch24
+        ));
+        assert!(StacksBlock::validate_transactions_static_epoch(
+            &clarity3_contract,
+            StacksEpochId::Epoch25
+        ));
     }
 
     // TODO:
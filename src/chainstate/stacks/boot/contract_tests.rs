@@ -1675,6 +1675,125 @@ fn simple_epoch21_test() {
     sim.execute_next_block(|_env| {});
 }
 
+fn stx_account_tuple(unlocked: u128, locked: u128, unlock_height: u128) -> Value {
+    Value::okay(Value::Tuple(
+        TupleData::from_data(vec![
+            ("unlocked".try_into().unwrap(), Value::UInt(unlocked)),
+            ("locked".try_into().unwrap(), Value::UInt(locked)),
+            (
+                "unlock-height".try_into().unwrap(),
+                Value::UInt(unlock_height),
+            ),
+        ])
+        .unwrap(),
+    ))
+    .unwrap()
+}
+
+#[test]
+fn stx_account_lock_states() {
+    let mut sim = ClarityTestSim::new();
+    sim.epoch_bounds = vec![0, 1, 3];
+
+    let contract_id = QualifiedContractIdentifier::new(
+        StandardPrincipalData::transient(),
+        "stx-account-test".into(),
+    );
+    let contract = "(define-public (get-account (who principal)) (ok (stx-account who)))";
+    let stacker: PrincipalData = (&USER_KEYS[0]).into();
+    let never_locked: PrincipalData = (&USER_KEYS[1]).into();
+    let amount_locked = USTX_PER_HOLDER / 4;
+
+    // advance to Stacks 2.1, where `stx-account` is available
+    sim.execute_next_block(|_env| {});
+    sim.execute_next_block(|_env| {});
+    sim.execute_next_block(|_env| {});
+
+    let unlock_height = sim.execute_next_block(|env| {
+        env.initialize_versioned_contract(
+            contract_id.clone(),
+            ClarityVersion::Clarity2,
+            contract,
+            None,
+            ASTRules::PrecheckSize,
+        )
+        .unwrap();
+
+        // lock until two burn blocks from now
+        env.execute_in_env(stacker.clone(), None, None, |env| -> Result<u64> {
+            let db = &mut env.global_context.database;
+            let unlock_height = db.get_current_burnchain_block_height() as u64 + 2;
+            let mut snapshot = db.get_stx_balance_snapshot(&stacker);
+            snapshot.lock_tokens_v3(amount_locked, unlock_height);
+            snapshot.save();
+            Ok(unlock_height)
+        })
+        .unwrap()
+        .0
+    });
+
+    // mid-lockup
+    sim.execute_next_block(|env| {
+        assert_eq!(
+            env.execute_transaction(
+                stacker.clone(),
+                None,
+                contract_id.clone(),
+                "get-account",
+                &symbols_from_values(vec![Value::Principal(stacker.clone())])
+            )
+            .unwrap()
+            .0,
+            stx_account_tuple(
+                USTX_PER_HOLDER - amount_locked,
+                amount_locked,
+                unlock_height as u128
+            )
+        );
+        assert_eq!(
+            env.execute_transaction(
+                stacker.clone(),
+                None,
+                contract_id.clone(),
+                "get-account",
+                &symbols_from_values(vec![Value::Principal(never_locked.clone())])
+            )
+            .unwrap()
+            .0,
+            stx_account_tuple(USTX_PER_HOLDER, 0, 0)
+        );
+    });
+
+    // the unlock height is reached, so the lock is reported as lifted even though the
+    // balance has not been written back yet
+    sim.execute_next_block(|env| {
+        assert_eq!(
+            env.execute_transaction(
+                stacker.clone(),
+                None,
+                contract_id.clone(),
+                "get-account",
+                &symbols_from_values(vec![Value::Principal(stacker.clone())])
+            )
+            .unwrap()
+            .0,
+            stx_account_tuple(USTX_PER_HOLDER, 0, 0)
+        );
+        assert_eq!(
+            env.execute_transaction(
+                stacker.clone(),
+                None,
+                contract_id.clone(),
+                "get-account",
+                &symbols_from_values(vec![Value::Principal(never_locked.clone())])
+            )
+            .unwrap()
+            .0,
+            stx_account_tuple(USTX_PER_HOLDER, 0, 0)
+        );
+    });
+}
+
 fn test_deploy_smart_contract(
     block: &mut ClarityBlockConnection,
     contract_id: &QualifiedContractIdentifier,
@@ -22,7 +22,7 @@ use crate::core::*;
 use crate::util_lib::db::{DBConn, FromRow};
 use crate::vm::events::StacksTransactionEvent;
 use clarity::types::Address;
-use clarity::vm::contexts::OwnedEnvironment;
+use clarity::vm::contexts::{ContractContext, OwnedEnvironment};
 use clarity::vm::contracts::Contract;
 use clarity::vm::costs::CostOverflowingMath;
 use clarity::vm::database::*;
@@ -38,6 +38,7 @@ use clarity::vm::types::{
     StacksAddressExtensions, StandardPrincipalData, TupleData, TupleTypeSignature, TypeSignature,
     Value, NONE,
 };
+use clarity::vm::ClarityVersion;
 use stacks_common::util::hash::hex_bytes;
 use stacks_common::util::hash::to_hex;
 use stacks_common::util::hash::{Sha256Sum, Sha512Trunc256Sum};
@@ -638,6 +639,42 @@ where
     })
 }
 
+/// Evaluate `expr` at `tip` as a transient `clarity_version` program running in `epoch`,
+///  regardless of the epoch the chain tip is actually in.
+pub fn eval_at_tip_with_version(
+    peer: &mut TestPeer,
+    tip: &StacksBlockId,
+    epoch: StacksEpochId,
+    clarity_version: ClarityVersion,
+    expr: &str,
+) -> Result<Value, Error> {
+    with_sortdb(peer, |ref mut c, ref sortdb| {
+        let headers_db = HeadersDBConn(c.state_index.sqlite_conn());
+        let burn_db = sortdb.index_conn();
+        let mut read_only_clar = c
+            .clarity_state
+            .read_only_connection(tip, &headers_db, &burn_db);
+        read_only_clar.with_clarity_db_readonly_owned(|clarity_db| {
+            let mut owned_env =
+                OwnedEnvironment::new_free(false, CHAIN_ID_TESTNET, clarity_db, epoch);
+            let contract_context =
+                ContractContext::new(QualifiedContractIdentifier::transient(), clarity_version);
+            let result = owned_env
+                .execute_in_env(
+                    QualifiedContractIdentifier::transient().issuer.into(),
+                    None,
+                    Some(contract_context),
+                    |env| env.eval_raw(expr),
+                )
+                .map(|(value, _, _)| value);
+            let (clarity_db, _) = owned_env
+                .destruct()
+                .expect("Failed to recover database reference after evaluating expression");
+            (result, clarity_db)
+        })
+    })
+}
+
 /// In this test case, two Stackers, Alice and Bob stack and interact with the
 ///  PoX v1 contract and PoX v2 contract across the epoch transition.
 ///
@@ -3502,7 +3539,7 @@ fn test_pox_2_getters() {
 
     let reject_pox = make_pox_2_contract_call(&danielle, 0, "reject-pox", vec![]);
 
-    peer.tenure_with_txs(
+    let tip_index_block = peer.tenure_with_txs(
         &[
             alice_lockup,
             bob_delegate_tx,
@@ -3649,6 +3686,73 @@ fn test_pox_2_getters() {
         .unwrap()
         .expect_u128();
     assert_eq!(rejected, 0);
+
+    // `get-stacking-state` is a Clarity 3 built-in, so it does not exist in Stacks 2.1
+    let result = eval_at_tip_with_version(
+        &mut peer,
+        &tip_index_block,
+        StacksEpochId::Epoch21,
+        ClarityVersion::Clarity2,
+        &format!("(get-stacking-state '{})", &alice_address),
+    );
+    assert!(result.is_err());
+
+    // `get-stacking-state` reports the same lock as pox-2's stacking-state map
+    for (stacker, delegated_to) in [
+        (&alice_address, None),
+        (&bob_address, Some(&charlie_address)),
+    ]
+    .iter()
+    {
+        let stacker_principal = PrincipalData::from((*stacker).clone());
+        let stacking_state =
+            get_stacking_state_pox_2(&mut peer, &tip_index_block, &stacker_principal)
+                .unwrap()
+                .expect_tuple();
+
+        let result = eval_at_tip_with_version(
+            &mut peer,
+            &tip_index_block,
+            StacksEpochId::Epoch25,
+            ClarityVersion::Clarity3,
+            &format!("(get-stacking-state '{})", stacker),
+        )
+        .unwrap()
+        .expect_optional()
+        .unwrap()
+        .expect_tuple();
+
+        assert_eq!(
+            result.get("stacked-amount").unwrap().clone().expect_u128(),
+            LOCKUP_AMT as u128
+        );
+        assert_eq!(
+            result.get("reward-cycle").unwrap(),
+            stacking_state.get("first-reward-cycle").unwrap()
+        );
+        assert_eq!(result.get("lock-period").unwrap(), &Value::UInt(4));
+        assert_eq!(
+            result
+                .get("delegated-to")
+                .unwrap()
+                .clone()
+                .expect_optional(),
+            delegated_to.map(|addr| Value::Principal(PrincipalData::from(addr.clone())))
+        );
+    }
+
+    // charlie and danielle never stacked
+    for address in [&charlie_address, &key_to_stacks_addr(&danielle)].iter() {
+        let result = eval_at_tip_with_version(
+            &mut peer,
+            &tip_index_block,
+            StacksEpochId::Epoch25,
+            ClarityVersion::Clarity3,
+            &format!("(get-stacking-state '{})", address),
+        )
+        .unwrap();
+        assert_eq!(result, Value::none());
+    }
 }
 
 #[test]
@@ -3920,6 +4024,150 @@ fn test_get_pox_addrs() {
     }
 }
 
+/// Evaluate `(get-burn-block-info? header-hash u$burn_height)` at `tip`
+fn eval_burn_block_info_header_hash(
+    peer: &mut TestPeer,
+    tip: &StacksBlockId,
+    burn_height: u64,
+) -> Value {
+    with_sortdb(peer, |ref mut chainstate, ref sortdb| {
+        chainstate
+            .maybe_read_only_clarity_tx(&sortdb.index_conn(), tip, |clarity_tx| {
+                clarity_tx
+                    .with_readonly_clarity_env(
+                        false,
+                        0x80000000,
+                        ClarityVersion::Clarity2,
+                        PrincipalData::Standard(StandardPrincipalData::transient()),
+                        None,
+                        LimitedCostTracker::new_free(),
+                        |env| {
+                            env.eval_read_only(
+                                &boot_code_id("pox-2", false),
+                                &format!("(get-burn-block-info? header-hash u{})", burn_height),
+                            )
+                        },
+                    )
+                    .unwrap()
+            })
+            .unwrap()
+            .unwrap()
+    })
+}
+
+#[test]
+fn test_get_burn_block_info_header_hash() {
+    let mut burnchain = Burnchain::default_unittest(
+        0,
+        &BurnchainHeaderHash::from_hex(BITCOIN_REGTEST_FIRST_BLOCK_HASH).unwrap(),
+    );
+    burnchain.pox_constants.reward_cycle_length = 4;
+    burnchain.pox_constants.prepare_length = 2;
+    burnchain.pox_constants.anchor_threshold = 1;
+    burnchain.pox_constants.v1_unlock_height = 4;
+
+    let epochs = StacksEpoch::all(1, 2, 3);
+
+    let (mut peer, _keys) = instantiate_pox_peer_with_epoch(
+        &burnchain,
+        "test-get-burn-block-info-header-hash",
+        6106,
+        Some(epochs.clone()),
+        None,
+    );
+    let num_blocks = 10;
+
+    let mut headers = vec![];
+    for tenure_id in 0..num_blocks {
+        let microblock_privkey = StacksPrivateKey::new();
+        let microblock_pubkeyhash =
+            Hash160::from_node_public_key(&StacksPublicKey::from_private(&microblock_privkey));
+        let tip = SortitionDB::get_canonical_burn_chain_tip(&peer.sortdb.as_ref().unwrap().conn())
+            .unwrap();
+
+        let (burn_ops, stacks_block, microblocks) = peer.make_tenure(
+            |ref mut miner,
+             ref mut sortdb,
+             ref mut chainstate,
+             vrf_proof,
+             ref parent_opt,
+             ref parent_microblock_header_opt| {
+                let parent_tip = get_parent_tip(parent_opt, chainstate, sortdb);
+                let coinbase_tx = make_coinbase(miner, tenure_id);
+
+                let block_builder = StacksBlockBuilder::make_block_builder(
+                    false,
+                    &parent_tip,
+                    vrf_proof,
+                    tip.total_burn,
+                    microblock_pubkeyhash,
+                )
+                .unwrap();
+                let (anchored_block, _size, _cost) =
+                    StacksBlockBuilder::make_anchored_block_from_txs(
+                        block_builder,
+                        chainstate,
+                        &sortdb.index_conn(),
+                        vec![coinbase_tx],
+                    )
+                    .unwrap();
+                (anchored_block, vec![])
+            },
+        );
+
+        let (_, _, consensus_hash) = peer.next_burnchain_block(burn_ops.clone());
+        peer.process_stacks_epoch_at_tip(&stacks_block, &microblocks);
+
+        let header = StacksChainState::get_anchored_block_header_info(
+            peer.chainstate().db(),
+            &consensus_hash,
+            &stacks_block.block_hash(),
+        )
+        .unwrap()
+        .unwrap();
+        headers.push(header);
+    }
+
+    let tip_header = headers.pop().unwrap();
+    let tip_index_block = tip_header.index_block_hash();
+
+    // each ancestor's burnchain header hash is visible at the tip
+    for header in headers.iter() {
+        assert_eq!(
+            eval_burn_block_info_header_hash(
+                &mut peer,
+                &tip_index_block,
+                header.burn_header_height.into()
+            ),
+            Value::some(Value::Sequence(SequenceData::Buffer(BuffData {
+                data: header.burn_header_hash.as_bytes().to_vec()
+            })))
+            .unwrap()
+        );
+    }
+
+    // the tip's own burnchain block is not visible while the tip is being evaluated, since the
+    // burnchain view is resolved from the parent's sortition
+    assert_eq!(
+        eval_burn_block_info_header_hash(
+            &mut peer,
+            &tip_index_block,
+            tip_header.burn_header_height.into()
+        ),
+        Value::none()
+    );
+
+    // nothing in the future
+    assert_eq!(
+        eval_burn_block_info_header_hash(
+            &mut peer,
+            &tip_index_block,
+            (tip_header.burn_header_height + 100).into()
+        ),
+        Value::none()
+    );
+}
+
 #[test]
 fn test_stack_with_segwit() {
     let mut burnchain = Burnchain::default_unittest(
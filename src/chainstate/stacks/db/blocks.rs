@@ -141,8 +141,15 @@ pub enum MemPoolRejection {
     DeserializationFailure(codec_error),
     FailedToValidate(Error),
     FeeTooLow(u64, u64),
+    BelowMinFeeRate(u64, u64),
     BadNonces(TransactionNonceMismatch),
     NotEnoughFunds(u128, u128),
+    /// The sponsor of a sponsored transaction cannot pay its fee out of its spendable balance.
+    InsufficientSponsorBalance {
+        sponsor: PrincipalData,
+        fee: u64,
+        balance: u128,
+    },
     NoSuchContract,
     NoSuchPublicFunction,
     BadFunctionArgument(CheckError),
@@ -153,7 +160,9 @@ pub enum MemPoolRejection {
     BadAddressVersionByte,
     NoCoinbaseViaMempool,
     NoSuchChainTip(ConsensusHash, BlockHeaderHash),
-    ConflictingNonceInMempool,
+    /// The mempool already has a transaction with this nonce (whose txid is given) that this
+    /// transaction may not replace.
+    ConflictingNonceInMempool(Txid),
     TooMuchChaining {
         max_nonce: u64,
         actual_nonce: u64,
@@ -179,6 +188,7 @@ pub struct SetupBlockResult<'a, 'b> {
     pub matured_miner_rewards_opt:
         Option<(MinerReward, Vec<MinerReward>, MinerReward, MinerRewardInfo)>,
     pub evaluated_epoch: StacksEpochId,
+    pub parent_epoch: StacksEpochId,
     pub applied_epoch_transition: bool,
     pub burn_stack_stx_ops: Vec<StackStxOp>,
     pub burn_transfer_stx_ops: Vec<TransferStxOp>,
@@ -211,6 +221,19 @@ impl BlockEventDispatcher for DummyEventDispatcher {
         );
     }
 
+    fn announce_epoch_transition(
+        &self,
+        _block_id: &StacksBlockId,
+        _burn_block_height: u64,
+        _old_epoch: StacksEpochId,
+        _new_epoch: StacksEpochId,
+    ) {
+        assert!(
+            false,
+            "We should never try to announce to the dummy dispatcher"
+        );
+    }
+
     fn announce_burn_block(
         &self,
         _burn_block: &BurnchainHeaderHash,
@@ -224,102 +247,216 @@ impl BlockEventDispatcher for DummyEventDispatcher {
             "We should never try to announce to the dummy dispatcher"
         );
     }
+
+    fn announce_burn_block_reorg(
+        &self,
+        _old_canonical_tip: &BurnchainHeaderHash,
+        _new_canonical_tip: &BurnchainHeaderHash,
+        _reorg_depth: u64,
+        _orphaned_stacks_blocks: &[BlockHeaderHash],
+    ) {
+        assert!(
+            false,
+            "We should never try to announce to the dummy dispatcher"
+        );
+    }
 }
 
 impl MemPoolRejection {
-    pub fn into_json(self, txid: &Txid) -> serde_json::Value {
+    /// A stable code for this rejection, which clients can match on.
+    pub fn error_code(&self) -> &'static str {
         use self::MemPoolRejection::*;
-        let (reason_code, reason_data) = match self {
-            SerializationFailure(e) => ("Serialization", Some(json!({"message": e.to_string()}))),
-            DeserializationFailure(e) => {
-                ("Deserialization", Some(json!({"message": e.to_string()})))
-            }
+        match self {
+            SerializationFailure(_) => "Serialization",
+            DeserializationFailure(_) => "Deserialization",
+            TooMuchChaining { .. } => "TooMuchChaining",
+            BadTransactionVersion => "BadTransactionVersion",
+            FailedToValidate(_) => "SignatureValidation",
+            FeeTooLow(..) => "FeeTooLow",
+            BelowMinFeeRate(..) => "BelowMinFeeRate",
+            TransferRecipientIsSender(_) => "TransferRecipientCannotEqualSender",
+            TransferAmountMustBePositive => "TransferAmountMustBePositive",
+            BadNonces(_) => "BadNonce",
+            NotEnoughFunds(..) => "NotEnoughFunds",
+            InsufficientSponsorBalance { .. } => "InsufficientSponsorBalance",
+            EstimatorError(_) => "EstimatorError",
+            NoSuchContract => "NoSuchContract",
+            NoSuchPublicFunction => "NoSuchPublicFunction",
+            BadFunctionArgument(_) => "BadFunctionArgument",
+            ConflictingNonceInMempool(_) => "ConflictingNonceInMempool",
+            ContractAlreadyExists(_) => "ContractAlreadyExists",
+            PoisonMicroblocksDoNotConflict => "PoisonMicroblocksDoNotConflict",
+            NoAnchorBlockWithPubkeyHash(_) => "PoisonMicroblockHasUnknownPubKeyHash",
+            InvalidMicroblocks => "PoisonMicroblockIsInvalid",
+            BadAddressVersionByte => "BadAddressVersionByte",
+            NoCoinbaseViaMempool => "NoCoinbaseViaMempool",
+            // this should never happen via the RPC interface
+            NoSuchChainTip(..) => "ServerFailureNoSuchChainTip",
+            DBError(_) => "ServerFailureDatabase",
+            TemporarilyBlacklisted => "TemporarilyBlacklisted",
+            Other(_) => "ServerFailureOther",
+        }
+    }
+
+    /// A human-readable explanation of this rejection.
+    pub fn reason(&self) -> String {
+        use self::MemPoolRejection::*;
+        match self {
+            SerializationFailure(e) => format!("Failed to serialize transaction: {}", e),
+            DeserializationFailure(e) => format!("Failed to deserialize transaction: {}", e),
             TooMuchChaining {
                 max_nonce,
                 actual_nonce,
-                principal,
-                is_origin,
                 ..
-            } => (
-                "TooMuchChaining",
-                Some(
-                    json!({"message": "Nonce would exceed chaining limit in mempool",
-                                "expected": max_nonce,
-                                "actual": actual_nonce,
-                                "principal": principal.to_string(),
-                                "is_origin": is_origin
-                    }),
-                ),
+            } => format!(
+                "Nonce {} would exceed chaining limit in mempool (at most {})",
+                actual_nonce, max_nonce
             ),
-            BadTransactionVersion => ("BadTransactionVersion", None),
-            FailedToValidate(e) => (
-                "SignatureValidation",
-                Some(json!({"message": e.to_string()})),
+            BadTransactionVersion => "Transaction version does not match this network".into(),
+            FailedToValidate(e) => format!("Failed to validate transaction signature: {}", e),
+            FeeTooLow(actual, expected) => format!(
+                "Transaction fee {} is below the minimum fee {}",
+                actual, expected
             ),
-            FeeTooLow(actual, expected) => (
-                "FeeTooLow",
-                Some(json!({
-                                                "expected": expected,
-                                                "actual": actual})),
+            BelowMinFeeRate(fee_rate, min_fee_rate) => format!(
+                "Transaction fee rate {} is below the minimum fee rate {}",
+                fee_rate, min_fee_rate
             ),
-            TransferRecipientIsSender(recipient) => (
-                "TransferRecipientCannotEqualSender",
-                Some(json!({"recipient": recipient.to_string()})),
-            ),
-            TransferAmountMustBePositive => ("TransferAmountMustBePositive", None),
+            TransferRecipientIsSender(recipient) => {
+                format!("Transfer recipient {} is the sender", recipient)
+            }
+            TransferAmountMustBePositive => "Transfer amount must be positive".into(),
             BadNonces(TransactionNonceMismatch {
                 expected,
                 actual,
                 principal,
-                is_origin,
                 ..
-            }) => (
-                "BadNonce",
-                Some(json!({
-                     "expected": expected,
-                     "actual": actual,
-                     "principal": principal.to_string(),
-                     "is_origin": is_origin})),
+            }) => format!(
+                "Nonce {} does not match the expected nonce {} of {}",
+                actual, expected, principal
             ),
-            NotEnoughFunds(expected, actual) => (
-                "NotEnoughFunds",
-                Some(json!({
-                    "expected": format!("0x{}", to_hex(&expected.to_be_bytes())),
-                    "actual": format!("0x{}", to_hex(&actual.to_be_bytes()))
-                })),
+            NotEnoughFunds(expected, actual) => format!(
+                "Balance {} is less than the {} the transaction needs",
+                actual, expected
             ),
-            EstimatorError(e) => ("EstimatorError", Some(json!({"message": e.to_string()}))),
-            NoSuchContract => ("NoSuchContract", None),
-            NoSuchPublicFunction => ("NoSuchPublicFunction", None),
-            BadFunctionArgument(e) => (
-                "BadFunctionArgument",
-                Some(json!({"message": e.to_string()})),
+            InsufficientSponsorBalance {
+                sponsor,
+                fee,
+                balance,
+            } => format!(
+                "Sponsor {} balance {} is less than the transaction fee {}",
+                sponsor, balance, fee
             ),
-            ConflictingNonceInMempool => ("ConflictingNonceInMempool", None),
-            ContractAlreadyExists(id) => (
-                "ContractAlreadyExists",
-                Some(json!({ "contract_identifier": id.to_string() })),
+            EstimatorError(e) => format!("Failed to estimate transaction cost: {}", e),
+            NoSuchContract => "Contract does not exist".into(),
+            NoSuchPublicFunction => "Contract has no such public function".into(),
+            BadFunctionArgument(e) => format!("Bad function argument: {}", e),
+            ConflictingNonceInMempool(conflicting_txid) => format!(
+                "Transaction {} in the mempool has the same nonce, and may not be replaced",
+                conflicting_txid
             ),
-            PoisonMicroblocksDoNotConflict => ("PoisonMicroblocksDoNotConflict", None),
-            NoAnchorBlockWithPubkeyHash(_h) => ("PoisonMicroblockHasUnknownPubKeyHash", None),
-            InvalidMicroblocks => ("PoisonMicroblockIsInvalid", None),
-            BadAddressVersionByte => ("BadAddressVersionByte", None),
-            NoCoinbaseViaMempool => ("NoCoinbaseViaMempool", None),
-            // this should never happen via the RPC interface
-            NoSuchChainTip(..) => ("ServerFailureNoSuchChainTip", None),
-            DBError(e) => (
-                "ServerFailureDatabase",
-                Some(json!({"message": e.to_string()})),
+            ContractAlreadyExists(id) => format!("Contract {} already exists", id),
+            PoisonMicroblocksDoNotConflict => "Poison microblocks do not conflict".into(),
+            NoAnchorBlockWithPubkeyHash(h) => format!(
+                "No anchored block has the poison microblocks' public key hash {}",
+                h.to_hex()
             ),
-            TemporarilyBlacklisted => ("TemporarilyBlacklisted", None),
-            Other(s) => ("ServerFailureOther", Some(json!({ "message": s }))),
-        };
+            InvalidMicroblocks => "Poison microblocks are invalid".into(),
+            BadAddressVersionByte => "Transaction has an address with the wrong version".into(),
+            NoCoinbaseViaMempool => "Coinbase transactions may not be submitted".into(),
+            NoSuchChainTip(consensus_hash, block_hash) => {
+                format!("No such chain tip {}/{}", consensus_hash, block_hash)
+            }
+            DBError(e) => format!("Database failure: {}", e),
+            TemporarilyBlacklisted => "Transaction is temporarily blacklisted".into(),
+            Other(s) => s.clone(),
+        }
+    }
+
+    /// The structured details of this rejection, if there are any.
+    pub fn reason_data(&self) -> Option<serde_json::Value> {
+        use self::MemPoolRejection::*;
+        match self {
+            SerializationFailure(e) => Some(json!({"message": e.to_string()})),
+            DeserializationFailure(e) => Some(json!({"message": e.to_string()})),
+            TooMuchChaining {
+                max_nonce,
+                actual_nonce,
+                principal,
+                is_origin,
+                ..
+            } => Some(
+                json!({"message": "Nonce would exceed chaining limit in mempool",
+                                 "expected": max_nonce,
+                                 "actual": actual_nonce,
+                                 "principal": principal.to_string(),
+                                 "is_origin": is_origin
+                }),
+            ),
+            FailedToValidate(e) => Some(json!({"message": e.to_string()})),
+            FeeTooLow(actual, expected) => Some(json!({
+                "expected": expected,
+                "actual": actual})),
+            BelowMinFeeRate(fee_rate, min_fee_rate) => Some(json!({
+                "fee_rate": fee_rate,
+                "min_fee_rate": min_fee_rate})),
+            TransferRecipientIsSender(recipient) => {
+                Some(json!({"recipient": recipient.to_string()}))
+            }
+            BadNonces(TransactionNonceMismatch {
+                expected,
+                actual,
+                principal,
+                is_origin,
+                ..
+            }) => Some(json!({
+                 "expected": expected,
+                 "actual": actual,
+                 "principal": principal.to_string(),
+                 "is_origin": is_origin})),
+            NotEnoughFunds(expected, actual) => Some(json!({
+                "expected": format!("0x{}", to_hex(&expected.to_be_bytes())),
+                "actual": format!("0x{}", to_hex(&actual.to_be_bytes()))
+            })),
+            InsufficientSponsorBalance {
+                sponsor,
+                fee,
+                balance,
+            } => Some(json!({
+                "sponsor": sponsor.to_string(),
+                "expected": format!("0x{}", to_hex(&(*fee as u128).to_be_bytes())),
+                "actual": format!("0x{}", to_hex(&balance.to_be_bytes()))
+            })),
+            EstimatorError(e) => Some(json!({"message": e.to_string()})),
+            BadFunctionArgument(e) => Some(json!({"message": e.to_string()})),
+            ConflictingNonceInMempool(conflicting_txid) => {
+                Some(json!({ "txid": conflicting_txid.to_hex() }))
+            }
+            ContractAlreadyExists(id) => Some(json!({ "contract_identifier": id.to_string() })),
+            DBError(e) => Some(json!({"message": e.to_string()})),
+            Other(s) => Some(json!({ "message": s })),
+            BadTransactionVersion
+            | TransferAmountMustBePositive
+            | NoSuchContract
+            | NoSuchPublicFunction
+            | PoisonMicroblocksDoNotConflict
+            | NoAnchorBlockWithPubkeyHash(_)
+            | InvalidMicroblocks
+            | BadAddressVersionByte
+            | NoCoinbaseViaMempool
+            | NoSuchChainTip(..)
+            | TemporarilyBlacklisted => None,
+        }
+    }
+
+    /// The JSON form of this rejection of transaction `txid`, as sent to RPC clients.
+    pub fn into_json(self, txid: &Txid) -> serde_json::Value {
         let mut result = json!({
             "txid": format!("{}", txid.to_hex()),
-            "error": "transaction rejected",
-            "reason": reason_code,
+            "error": self.error_code(),
+            "reason": self.reason(),
         });
-        if let Some(reason_data) = reason_data {
+        if let Some(reason_data) = self.reason_data() {
             result
                 .as_object_mut()
                 .unwrap()
@@ -492,6 +629,39 @@ impl MicroblockStreamData {
     }
 }
 
+impl BlocksStreamData {
+    /// Send whatever is left of the current block's 4-byte length prefix.
+    fn stream_block_len<W: Write>(&mut self, fd: &mut W) -> Result<u64, Error> {
+        let mut num_written = 0;
+        while self.block_len_ptr < self.block_len_buf.len() {
+            match fd.write(&self.block_len_buf[self.block_len_ptr..]) {
+                Ok(0) => {
+                    // done (disconnected)
+                    return Ok(num_written);
+                }
+                Ok(n) => {
+                    self.block_len_ptr += n;
+                    num_written += n as u64;
+                }
+                Err(e) => {
+                    if e.kind() == io::ErrorKind::Interrupted {
+                        // EINTR; try again
+                        continue;
+                    } else if e.kind() == io::ErrorKind::WouldBlock
+                        || (cfg!(windows) && e.kind() == io::ErrorKind::TimedOut)
+                    {
+                        // blocked
+                        return Ok(num_written);
+                    } else {
+                        return Err(Error::WriteError(e));
+                    }
+                }
+            }
+        }
+        Ok(num_written)
+    }
+}
+
 impl StreamCursor {
     pub fn new_block(index_block_hash: StacksBlockId) -> StreamCursor {
         StreamCursor::Block(BlockStreamData {
@@ -501,6 +671,17 @@ impl StreamCursor {
         })
     }
 
+    /// Stream a sequence of blocks from the chunk store, in the given order.  Each block is
+    /// preceded by its length as a 4-byte big-endian integer.
+    pub fn new_blocks(index_block_hashes: Vec<StacksBlockId>) -> StreamCursor {
+        StreamCursor::Blocks(BlocksStreamData {
+            index_block_hashes: index_block_hashes.into_iter().collect(),
+            block: None,
+            block_len_buf: [0u8; 4],
+            block_len_ptr: 4,
+        })
+    }
+
     pub fn new_microblock_confirmed(
         chainstate: &StacksChainState,
         tail_index_microblock_hash: StacksBlockId,
@@ -530,15 +711,19 @@ impl StreamCursor {
             parent_index_block_hash: parent_index_block_hash,
             seq: mblock_info.sequence,
             unconfirmed: false,
+            max_seq: u16::MAX,
             num_items_buf: num_items_buf,
             num_items_ptr: 0,
         }))
     }
 
+    /// Stream the unconfirmed microblocks descended from `anchored_index_block_hash`, starting
+    /// at sequence number `seq`.  If `limit` is given, at most that many microblocks are sent.
     pub fn new_microblock_unconfirmed(
         chainstate: &StacksChainState,
         anchored_index_block_hash: StacksBlockId,
         seq: u16,
+        limit: Option<u16>,
     ) -> Result<StreamCursor, Error> {
         let mblock_info = StacksChainState::load_next_descendant_microblock(
             &chainstate.db(),
@@ -556,6 +741,10 @@ impl StreamCursor {
             parent_index_block_hash: anchored_index_block_hash,
             seq: seq,
             unconfirmed: true,
+            max_seq: match limit {
+                Some(limit) => seq.saturating_add(limit.saturating_sub(1)),
+                None => u16::MAX,
+            },
             num_items_buf: [0u8; 4],
             num_items_ptr: 4, // stops us from trying to send a length prefix
         }))
@@ -641,6 +830,7 @@ impl StreamCursor {
     pub fn get_offset(&self) -> u64 {
         match self {
             StreamCursor::Block(ref stream) => stream.offset(),
+            StreamCursor::Blocks(ref stream) => stream.offset(),
             StreamCursor::Microblocks(ref stream) => stream.offset(),
             StreamCursor::Headers(ref stream) => stream.offset(),
             // no-op for mempool txs
@@ -651,6 +841,7 @@ impl StreamCursor {
     pub fn add_more_bytes(&mut self, nw: u64) {
         match self {
             StreamCursor::Block(ref mut stream) => stream.add_bytes(nw),
+            StreamCursor::Blocks(ref mut stream) => stream.add_bytes(nw),
             StreamCursor::Microblocks(ref mut stream) => stream.add_bytes(nw),
             StreamCursor::Headers(ref mut stream) => stream.add_bytes(nw),
             // no-op fo mempool txs
@@ -707,6 +898,7 @@ impl StreamCursor {
                 Ok(num_written)
             }
             StreamCursor::Block(ref mut stream) => chainstate.stream_block(fd, stream, count),
+            StreamCursor::Blocks(ref mut stream) => chainstate.stream_blocks(fd, stream, count),
         }
     }
 }
@@ -740,6 +932,17 @@ impl Streamer for BlockStreamData {
     }
 }
 
+impl Streamer for BlocksStreamData {
+    fn offset(&self) -> u64 {
+        self.block.as_ref().map(|block| block.offset()).unwrap_or(0)
+    }
+    fn add_bytes(&mut self, nw: u64) {
+        if let Some(ref mut block) = self.block {
+            block.add_bytes(nw);
+        }
+    }
+}
+
 impl Streamer for MicroblockStreamData {
     fn offset(&self) -> u64 {
         self.offset
@@ -3638,6 +3841,92 @@ impl StacksChainState {
         StacksChainState::stream_data_from_chunk_store(&self.blocks_path, fd, stream, count)
     }
 
+    /// Stream a sequence of length-prefixed blocks from the chunk store.
+    /// Returns the number of bytes written, and updates `stream` to point to the next point to
+    /// read.  Writes the bytes streamed to `fd`.  Returns 0 once every block has been sent.
+    pub fn stream_blocks<W: Write>(
+        &mut self,
+        fd: &mut W,
+        stream: &mut BlocksStreamData,
+        count: u64,
+    ) -> Result<u64, Error> {
+        let mut num_written = 0;
+        while num_written < count {
+            if stream.block.is_none() {
+                let index_block_hash = match stream.index_block_hashes.pop_front() {
+                    Some(index_block_hash) => index_block_hash,
+                    None => {
+                        // EOF on stream
+                        break;
+                    }
+                };
+                let block_path =
+                    StacksChainState::get_index_block_path(&self.blocks_path, &index_block_hash)?;
+                let block_len = fs::metadata(&block_path)
+                    .map_err(|e| {
+                        if e.kind() == io::ErrorKind::NotFound {
+                            error!("File not found: {:?}", &block_path);
+                            Error::NoSuchBlockError
+                        } else {
+                            Error::ReadError(e)
+                        }
+                    })?
+                    .len();
+
+                if block_len == 0 {
+                    // invalid block; nothing to send
+                    continue;
+                }
+                if block_len > u32::MAX as u64 {
+                    return Err(Error::InvalidStacksBlock(format!(
+                        "Block {} is too big to stream",
+                        &index_block_hash
+                    )));
+                }
+
+                test_debug!(
+                    "Switch to block {} ({} bytes)",
+                    &index_block_hash,
+                    block_len
+                );
+                stream.block_len_buf = (block_len as u32).to_be_bytes();
+                stream.block_len_ptr = 0;
+                stream.block = Some(BlockStreamData {
+                    index_block_hash,
+                    offset: 0,
+                    total_bytes: 0,
+                });
+            }
+
+            if stream.block_len_ptr < stream.block_len_buf.len() {
+                let nw = stream.stream_block_len(fd)?;
+                if nw == 0 {
+                    // blocked
+                    break;
+                }
+                num_written += nw;
+                continue;
+            }
+
+            let nw = match stream.block {
+                Some(ref mut block_stream) => StacksChainState::stream_data_from_chunk_store(
+                    &self.blocks_path,
+                    fd,
+                    block_stream,
+                    count - num_written,
+                )?,
+                None => 0,
+            };
+            if nw == 0 {
+                // EOF on this block; move to the next one
+                stream.block = None;
+                continue;
+            }
+            num_written += nw;
+        }
+        Ok(num_written)
+    }
+
     /// Stream unconfirmed microblocks from the staging DB.  Pull only from the staging DB.
     /// Returns the number of bytes written, and updates `stream` to point to the next point to
     /// read.  Wrties the bytes streamed to `fd`.
@@ -3659,6 +3948,10 @@ impl StacksChainState {
                     }
                     x => x + 1,
                 };
+                if next_seq > stream.max_seq {
+                    // sent as many microblocks as were asked for
+                    break;
+                }
                 let next_mblock_hash = match StacksChainState::load_next_descendant_microblock(
                     &chainstate.db(),
                     &stream.index_block_hash,
@@ -4907,6 +5200,15 @@ impl StacksChainState {
                             receipts.append(&mut clarity_tx.block.initialize_epoch_2_4()?);
                             applied = true;
                         }
+                        StacksEpochId::Epoch25 => {
+                            receipts.push(clarity_tx.block.initialize_epoch_2_05()?);
+                            receipts.append(&mut clarity_tx.block.initialize_epoch_2_1()?);
+                            receipts.append(&mut clarity_tx.block.initialize_epoch_2_2()?);
+                            receipts.append(&mut clarity_tx.block.initialize_epoch_2_3()?);
+                            receipts.append(&mut clarity_tx.block.initialize_epoch_2_4()?);
+                            receipts.append(&mut clarity_tx.block.initialize_epoch_2_5()?);
+                            applied = true;
+                        }
                         _ => {
                             panic!("Bad Stacks epoch transition; parent_epoch = {}, current_epoch = {}", &stacks_parent_epoch, &sortition_epoch.epoch_id);
                         }
@@ -4934,6 +5236,14 @@ impl StacksChainState {
                             receipts.append(&mut clarity_tx.block.initialize_epoch_2_4()?);
                             applied = true;
                         }
+                        StacksEpochId::Epoch25 => {
+                            receipts.append(&mut clarity_tx.block.initialize_epoch_2_1()?);
+                            receipts.append(&mut clarity_tx.block.initialize_epoch_2_2()?);
+                            receipts.append(&mut clarity_tx.block.initialize_epoch_2_3()?);
+                            receipts.append(&mut clarity_tx.block.initialize_epoch_2_4()?);
+                            receipts.append(&mut clarity_tx.block.initialize_epoch_2_5()?);
+                            applied = true;
+                        }
                         _ => {
                             panic!("Bad Stacks epoch transition; parent_epoch = {}, current_epoch = {}", &stacks_parent_epoch, &sortition_epoch.epoch_id);
                         }
@@ -4954,6 +5264,13 @@ impl StacksChainState {
                             receipts.append(&mut clarity_tx.block.initialize_epoch_2_4()?);
                             applied = true;
                         }
+                        StacksEpochId::Epoch25 => {
+                            receipts.append(&mut clarity_tx.block.initialize_epoch_2_2()?);
+                            receipts.append(&mut clarity_tx.block.initialize_epoch_2_3()?);
+                            receipts.append(&mut clarity_tx.block.initialize_epoch_2_4()?);
+                            receipts.append(&mut clarity_tx.block.initialize_epoch_2_5()?);
+                            applied = true;
+                        }
                         _ => {
                             panic!("Bad Stacks epoch transition; parent_epoch = {}, current_epoch = {}", &stacks_parent_epoch, &sortition_epoch.epoch_id);
                         }
@@ -4968,21 +5285,41 @@ impl StacksChainState {
                             receipts.append(&mut clarity_tx.block.initialize_epoch_2_4()?);
                             applied = true;
                         }
+                        StacksEpochId::Epoch25 => {
+                            receipts.append(&mut clarity_tx.block.initialize_epoch_2_3()?);
+                            receipts.append(&mut clarity_tx.block.initialize_epoch_2_4()?);
+                            receipts.append(&mut clarity_tx.block.initialize_epoch_2_5()?);
+                            applied = true;
+                        }
+                        _ => {
+                            panic!("Bad Stacks epoch transition; parent_epoch = {}, current_epoch = {}", &stacks_parent_epoch, &sortition_epoch.epoch_id);
+                        }
+                    },
+                    StacksEpochId::Epoch23 => match sortition_epoch.epoch_id {
+                        StacksEpochId::Epoch24 => {
+                            receipts.append(&mut clarity_tx.block.initialize_epoch_2_4()?);
+                            applied = true;
+                        }
+                        StacksEpochId::Epoch25 => {
+                            receipts.append(&mut clarity_tx.block.initialize_epoch_2_4()?);
+                            receipts.append(&mut clarity_tx.block.initialize_epoch_2_5()?);
+                            applied = true;
+                        }
                         _ => {
                             panic!("Bad Stacks epoch transition; parent_epoch = {}, current_epoch = {}", &stacks_parent_epoch, &sortition_epoch.epoch_id);
                         }
                     },
-                    StacksEpochId::Epoch23 => {
+                    StacksEpochId::Epoch24 => {
                         assert_eq!(
                             sortition_epoch.epoch_id,
-                            StacksEpochId::Epoch24,
-                            "Should only transition from Epoch23 to Epoch24"
+                            StacksEpochId::Epoch25,
+                            "Should only transition from Epoch24 to Epoch25"
                         );
-                        receipts.append(&mut clarity_tx.block.initialize_epoch_2_4()?);
+                        receipts.append(&mut clarity_tx.block.initialize_epoch_2_5()?);
                         applied = true;
                     }
-                    StacksEpochId::Epoch24 => {
-                        panic!("No defined transition from Epoch23 forward")
+                    StacksEpochId::Epoch25 => {
+                        panic!("No defined transition from Epoch25 forward")
                     }
                 }
             }
@@ -5060,6 +5397,8 @@ impl StacksChainState {
                             microblock_header: None,
                             tx_index: 0,
                             vm_error: None,
+                            cost_breakdown: None,
+                            cost_function_breakdown: None,
                         };
 
                         all_receipts.push(receipt);
@@ -5124,6 +5463,8 @@ impl StacksChainState {
                                     microblock_header: None,
                                     tx_index: 0,
                                     vm_error: None,
+                                    cost_breakdown: None,
+                                    cost_function_breakdown: None,
                                 })
                             }
                             Err(e) => {
@@ -5230,6 +5571,8 @@ impl StacksChainState {
                             microblock_header: None,
                             tx_index: 0,
                             vm_error: None,
+                            cost_breakdown: None,
+                            cost_function_breakdown: None,
                         };
 
                         all_receipts.push(receipt);
@@ -5574,7 +5917,8 @@ impl StacksChainState {
             StacksEpochId::Epoch21
             | StacksEpochId::Epoch22
             | StacksEpochId::Epoch23
-            | StacksEpochId::Epoch24 => {
+            | StacksEpochId::Epoch24
+            | StacksEpochId::Epoch25 => {
                 StacksChainState::get_stacking_and_transfer_and_delegate_burn_ops_v210(
                     chainstate_tx,
                     parent_index_hash,
@@ -5659,11 +6003,13 @@ impl StacksChainState {
                         pox_start_cycle_info,
                     )
                 }
-                StacksEpochId::Epoch24 => Self::handle_pox_cycle_start_pox_3(
-                    clarity_tx,
-                    pox_reward_cycle,
-                    pox_start_cycle_info,
-                ),
+                StacksEpochId::Epoch24 | StacksEpochId::Epoch25 => {
+                    Self::handle_pox_cycle_start_pox_3(
+                        clarity_tx,
+                        pox_reward_cycle,
+                        pox_start_cycle_info,
+                    )
+                }
             }
         })?;
         debug!("check_and_handle_reward_start: handled pox cycle start");
@@ -5844,6 +6190,8 @@ impl StacksChainState {
         // epoch defined by this miner.
         clarity_tx.reset_cost(ExecutionCost::zero());
 
+        let parent_epoch = clarity_tx.with_clarity_db_readonly(|db| db.get_clarity_epoch_version());
+
         // is this stacks block the first of a new epoch?
         let (applied_epoch_transition, mut tx_receipts) =
             StacksChainState::process_epoch_transition(&mut clarity_tx, burn_tip_height)?;
@@ -5927,6 +6275,7 @@ impl StacksChainState {
             microblock_txs_receipts,
             matured_miner_rewards_opt,
             evaluated_epoch,
+            parent_epoch,
             applied_epoch_transition,
             burn_stack_stx_ops: stacking_burn_ops,
             burn_transfer_stx_ops: transfer_burn_ops,
@@ -6122,6 +6471,7 @@ impl StacksChainState {
             microblock_txs_receipts,
             matured_miner_rewards_opt,
             evaluated_epoch,
+            parent_epoch,
             applied_epoch_transition,
             burn_stack_stx_ops,
             burn_transfer_stx_ops,
@@ -6431,6 +6781,7 @@ impl StacksChainState {
             parent_burn_block_height,
             parent_burn_block_timestamp,
             evaluated_epoch,
+            parent_epoch,
             epoch_transition: applied_epoch_transition,
         };
 
@@ -6893,6 +7244,14 @@ impl StacksChainState {
                 &epoch_receipt.parent_microblocks_cost,
                 &pox_constants,
             );
+            if epoch_receipt.epoch_transition {
+                dispatcher.announce_epoch_transition(
+                    &epoch_receipt.header.index_block_hash(),
+                    epoch_receipt.header.burn_header_height as u64,
+                    epoch_receipt.parent_epoch,
+                    epoch_receipt.evaluated_epoch,
+                );
+            }
         }
 
         StacksChainState::set_block_processed(
@@ -7090,6 +7449,22 @@ impl StacksChainState {
                 }
                 Ok(())
             }
+            TransactionPayload::BatchTokenTransfer(ref transfers) => {
+                let origin = PrincipalData::from(tx.origin_address());
+                for (recipient, amount, _memo) in transfers.iter() {
+                    if &origin == recipient {
+                        return Err(MemPoolRejection::TransferRecipientIsSender(origin));
+                    }
+                    if *amount == 0 {
+                        return Err(MemPoolRejection::TransferAmountMustBePositive);
+                    }
+                    if !StacksChainState::is_valid_address_version(is_mainnet, recipient.version())
+                    {
+                        return Err(MemPoolRejection::BadAddressVersionByte);
+                    }
+                }
+                Ok(())
+            }
             _ => Ok(()),
         }
     }
@@ -7279,7 +7654,8 @@ impl StacksChainState {
             v2_unlock_height,
         ) {
             match &tx.payload {
-                TransactionPayload::TokenTransfer(..) => {
+                TransactionPayload::TokenTransfer(..)
+                | TransactionPayload::BatchTokenTransfer(..) => {
                     // pass: we'll return a total_spent failure below.
                 }
                 _ => {
@@ -7343,6 +7719,66 @@ impl StacksChainState {
                     }
                 }
             }
+            TransactionPayload::BatchTokenTransfer(transfers) => {
+                if !StacksBlock::batch_token_transfers_supported(clarity_connection.get_epoch()) {
+                    return Err(MemPoolRejection::Other(
+                        "Batched STX transfer transactions are not supported in this epoch"
+                            .to_string(),
+                    ));
+                }
+
+                // version bytes match?
+                for (addr, _amount, _memo) in transfers.iter() {
+                    if !StacksChainState::is_valid_address_version(
+                        chainstate_config.mainnet,
+                        addr.version(),
+                    ) {
+                        return Err(MemPoolRejection::BadAddressVersionByte);
+                    }
+                }
+
+                // does the owner have the funds for every transfer in the batch?
+                let total_spent = transfers
+                    .iter()
+                    .fold(0u128, |total, (_addr, amount, _memo)| {
+                        total.saturating_add(*amount as u128)
+                    })
+                    .saturating_add(if origin == payer { fee as u128 } else { 0 });
+                if !origin.stx_balance.can_transfer_at_burn_block(
+                    total_spent,
+                    block_height,
+                    v1_unlock_height,
+                    v2_unlock_height,
+                ) {
+                    return Err(MemPoolRejection::NotEnoughFunds(
+                        total_spent,
+                        origin.stx_balance.get_available_balance_at_burn_block(
+                            block_height,
+                            v1_unlock_height,
+                            v2_unlock_height,
+                        ),
+                    ));
+                }
+
+                // if the payer for the tx is different from owner, check if they can afford fee
+                if origin != payer {
+                    if !payer.stx_balance.can_transfer_at_burn_block(
+                        fee as u128,
+                        block_height,
+                        v1_unlock_height,
+                        v2_unlock_height,
+                    ) {
+                        return Err(MemPoolRejection::NotEnoughFunds(
+                            fee as u128,
+                            payer.stx_balance.get_available_balance_at_burn_block(
+                                block_height,
+                                v1_unlock_height,
+                                v2_unlock_height,
+                            ),
+                        ));
+                    }
+                }
+            }
             TransactionPayload::ContractCall(TransactionContractCall {
                 address,
                 contract_name,
@@ -10512,6 +10948,26 @@ pub mod test {
         }
     }
 
+    fn stream_block_range_to_vec(
+        chainstate: &mut StacksChainState,
+        stream: &mut StreamCursor,
+        count: u64,
+    ) -> Result<Vec<u8>, chainstate_error> {
+        let mempool = MemPoolDB::open_test(
+            chainstate.mainnet,
+            chainstate.chain_id,
+            &chainstate.root_path,
+        )
+        .unwrap();
+        let mut bytes = vec![];
+        stream
+            .stream_to(&mempool, chainstate, &mut bytes, count)
+            .map(|nr| {
+                assert_eq!(bytes.len(), nr as usize);
+                bytes
+            })
+    }
+
     fn stream_headers_to_vec(
         chainstate: &mut StacksChainState,
         stream: &mut StreamCursor,
@@ -10680,6 +11136,86 @@ pub mod test {
         assert_eq!(staging_block, block);
     }
 
+    #[test]
+    fn stacks_db_stream_block_range() {
+        let mut chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
+        let privk = StacksPrivateKey::from_hex(
+            "eb05c83546fdd2c79f10f5ad5434a90dd28f7e3acb7c092157aa1bc3656b012c01",
+        )
+        .unwrap();
+
+        let mut blocks: Vec<StacksBlock> = vec![];
+        let mut blocks_index_hashes: Vec<StacksBlockId> = vec![];
+
+        for i in 0..4 {
+            let mut block = make_empty_coinbase_block(&privk);
+            if i > 0 {
+                block.header.parent_block = blocks.get(i - 1).unwrap().block_hash();
+                block.header.total_work.work =
+                    blocks.get(i - 1).unwrap().header.total_work.work + 1;
+            }
+
+            let consensus_hash = ConsensusHash([((i + 1) as u8); 20]);
+            let parent_consensus_hash = ConsensusHash([(i as u8); 20]);
+
+            store_staging_block(
+                &mut chainstate,
+                &consensus_hash,
+                &block,
+                &parent_consensus_hash,
+                i as u64,
+                i as u64,
+            );
+
+            blocks_index_hashes.push(StacksBlockHeader::make_index_block_hash(
+                &consensus_hash,
+                &block.block_hash(),
+            ));
+            blocks.push(block);
+        }
+
+        // can't stream a non-existant block
+        let mut stream = StreamCursor::new_blocks(vec![StacksBlockId([0x11; 32])]);
+        assert!(stream_block_range_to_vec(&mut chainstate, &mut stream, 123).is_err());
+
+        // nothing to stream
+        let mut stream = StreamCursor::new_blocks(vec![]);
+        assert_eq!(
+            stream_block_range_to_vec(&mut chainstate, &mut stream, 123).unwrap(),
+            Vec::<u8>::new()
+        );
+
+        // stream them all back, a little at a time
+        let mut stream = StreamCursor::new_blocks(blocks_index_hashes.clone());
+        let mut all_block_bytes = vec![];
+        loop {
+            let mut next_bytes =
+                stream_block_range_to_vec(&mut chainstate, &mut stream, 16).unwrap();
+            if next_bytes.len() == 0 {
+                break;
+            }
+            test_debug!(
+                "Got {} more bytes from chunkstore; add to {} total",
+                next_bytes.len(),
+                all_block_bytes.len()
+            );
+            all_block_bytes.append(&mut next_bytes);
+        }
+
+        // should decode back into length-prefixed blocks, in order
+        let mut block_ptr = &all_block_bytes[..];
+        let mut streamed_blocks = vec![];
+        while block_ptr.len() > 0 {
+            let mut len_buf = [0u8; 4];
+            block_ptr.read_exact(&mut len_buf).unwrap();
+            let block_len = u32::from_be_bytes(len_buf) as usize;
+            let block = StacksBlock::consensus_deserialize(&mut &block_ptr[..block_len]).unwrap();
+            block_ptr = &block_ptr[block_len..];
+            streamed_blocks.push(block);
+        }
+        assert_eq!(streamed_blocks, blocks);
+    }
+
     #[test]
     fn stacks_db_stream_headers() {
         let mut chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
@@ -10958,9 +11494,12 @@ pub mod test {
             panic!("Opened nonexistant microblock");
         }
 
-        if let Err(super::Error::NoSuchBlockError) =
-            StreamCursor::new_microblock_unconfirmed(&chainstate, index_block_header.clone(), 0)
-        {
+        if let Err(super::Error::NoSuchBlockError) = StreamCursor::new_microblock_unconfirmed(
+            &chainstate,
+            index_block_header.clone(),
+            0,
+            None,
+        ) {
         } else {
             panic!("Opened nonexistant microblock");
         }
@@ -10982,6 +11521,7 @@ pub mod test {
                     &chainstate,
                     index_block_header.clone(),
                     j as u16,
+                    None,
                 )
                 .unwrap();
                 loop {
@@ -11020,6 +11560,7 @@ pub mod test {
                     &chainstate,
                     index_block_header.clone(),
                     k as u16,
+                    None,
                 )
                 .unwrap();
                 loop {
@@ -11047,6 +11588,32 @@ pub mod test {
                     test_debug!("check {}", j);
                     assert_eq!(staging_mblocks[j], mblocks[k + j])
                 }
+
+                // a limited stream stops after at most `limit` microblocks
+                let mut staging_mblock_bytes = vec![];
+                let mut stream = StreamCursor::new_microblock_unconfirmed(
+                    &chainstate,
+                    index_block_header.clone(),
+                    k as u16,
+                    Some(2),
+                )
+                .unwrap();
+                loop {
+                    let mut next_bytes =
+                        stream_unconfirmed_microblocks_to_vec(&mut chainstate, &mut stream, 4096)
+                            .unwrap();
+                    if next_bytes.len() == 0 {
+                        break;
+                    }
+                    staging_mblock_bytes.append(&mut next_bytes);
+                }
+
+                let staging_mblocks = decode_microblock_stream(&staging_mblock_bytes);
+                let expected = &mblocks[k..cmp::min(k + 2, i + 1)];
+                assert_eq!(staging_mblocks.len(), expected.len());
+                for j in 0..staging_mblocks.len() {
+                    assert_eq!(staging_mblocks[j], expected[j])
+                }
             }
         }
     }
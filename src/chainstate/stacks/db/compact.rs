@@ -0,0 +1,270 @@
+// Copyright (C) 2013-2020 Blockstack PBC, a public benefit corporation
+// Copyright (C) 2020 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::chainstate::stacks::db::StacksChainState;
+use crate::chainstate::stacks::index::marf::{MARFOpenOpts, MarfConnection, MARF};
+use crate::chainstate::stacks::Error;
+use crate::util_lib::db::sql_vacuum;
+use crate::util_lib::db::Error as db_error;
+use stacks_common::types::chainstate::StacksBlockId;
+
+/// Delete the tries of the blocks in `db` that are below `min_finality_height` and that are not
+/// ancestors of any block at or above it, then vacuum the DB.  Such blocks are on forks that were
+/// abandoned before the finality horizon, so no block that can still be processed builds on them.
+/// Every block at or above `min_finality_height` keeps its trie -- including blocks on forks that
+/// are not (yet) canonical -- and so does each of its ancestors, since a trie's back-pointers refer
+/// to its ancestors' tries.
+///
+/// Returns the number of bytes freed.  If no block has reached `min_finality_height`, then
+/// nothing is deleted.
+///
+/// If the node crashes partway through, the MARF finishes or undoes the compaction the next time
+/// it is opened read-write.  Only run it on a stopped node.
+pub fn marf_compact(db: &mut MARF<StacksBlockId>, min_finality_height: u32) -> Result<u64, Error> {
+    let mut retained = HashSet::new();
+    let mut horizon = vec![];
+    let mut below_horizon = vec![];
+    for block_id in db.get_confirmed_block_hashes()?.into_iter() {
+        match db.get_block_height(&block_id, &block_id)? {
+            Some(height) if height < min_finality_height => {
+                below_horizon.push(block_id);
+            }
+            Some(height) => {
+                if height == min_finality_height {
+                    horizon.push(block_id.clone());
+                }
+                retained.insert(block_id);
+            }
+            None => {
+                // not a block's trie (e.g. the sentinel trie), so leave it alone
+                retained.insert(block_id);
+            }
+        }
+    }
+
+    if horizon.is_empty() {
+        info!(
+            "No block in {} has reached height {}; not compacting",
+            db.get_db_path(),
+            min_finality_height
+        );
+        return Ok(0);
+    }
+
+    // every block above the horizon descends from a block at the horizon, so walking back from
+    // those finds every ancestor that must be kept.  Once a walk reaches a block that an earlier
+    // walk already kept, the rest of its ancestors have been kept too.
+    for tip in horizon.iter() {
+        for height in (0..min_finality_height).rev() {
+            let ancestor = db.get_block_at_height(height, tip)?.ok_or_else(|| {
+                error!(
+                    "No ancestor of {} at height {} in {}",
+                    tip,
+                    height,
+                    db.get_db_path()
+                );
+                Error::DBError(db_error::Corruption)
+            })?;
+            if !retained.insert(ancestor) {
+                break;
+            }
+        }
+    }
+
+    let abandoned: Vec<_> = below_horizon
+        .into_iter()
+        .filter(|block_id| !retained.contains(block_id))
+        .collect();
+
+    if abandoned.is_empty() {
+        info!(
+            "No abandoned tries below height {} in {}",
+            min_finality_height,
+            db.get_db_path()
+        );
+        return Ok(0);
+    }
+
+    let db_path = db.get_db_path().to_string();
+    let size_before = fs::metadata(&db_path)
+        .map_err(|e| Error::DBError(db_error::IOError(e)))?
+        .len();
+
+    info!(
+        "Delete {} abandoned tries below height {} from {}",
+        abandoned.len(),
+        min_finality_height,
+        &db_path
+    );
+    let blobs_freed = db.delete_tries(&abandoned)?;
+    sql_vacuum(db.sqlite_conn())?;
+
+    let size_after = fs::metadata(&db_path)
+        .map_err(|e| Error::DBError(db_error::IOError(e)))?
+        .len();
+    let freed = blobs_freed + size_before.saturating_sub(size_after);
+
+    info!("Compacted {}: freed {} bytes", &db_path, freed);
+    Ok(freed)
+}
+
+impl StacksChainState {
+    /// Compact both the headers index MARF and the Clarity state MARF of the chainstate at
+    /// `path_str` (see `marf_compact()`).  The chainstate must not be open anywhere else.
+    /// Returns the total number of bytes freed.
+    pub fn compact_marfs(
+        path_str: &str,
+        marf_opts: Option<MARFOpenOpts>,
+        min_finality_height: u32,
+    ) -> Result<u64, Error> {
+        let path = PathBuf::from(path_str);
+
+        let header_index_root_path = StacksChainState::header_index_root_path(path.clone());
+        let header_index_root = header_index_root_path
+            .to_str()
+            .ok_or_else(|| Error::DBError(db_error::ParseError))?;
+
+        let clarity_state_index_marf_path = StacksChainState::vm_state_index_marf_path(path);
+        let clarity_state_index_marf = clarity_state_index_marf_path
+            .to_str()
+            .ok_or_else(|| Error::DBError(db_error::ParseError))?;
+
+        let mut freed = 0;
+
+        let mut header_index = StacksChainState::open_index(header_index_root)?;
+        freed += marf_compact(&mut header_index, min_finality_height)?;
+
+        let mut clarity_index = MARF::from_path(
+            clarity_state_index_marf,
+            marf_opts.unwrap_or_else(MARFOpenOpts::default),
+        )?;
+        freed += marf_compact(&mut clarity_index, min_finality_height)?;
+
+        Ok(freed)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::*;
+    use crate::chainstate::stacks::index::{ClarityMarfTrieId, MARFValue};
+
+    fn block_id(byte: u8) -> StacksBlockId {
+        StacksBlockId([byte; 32])
+    }
+
+    /// Make a MARF with the canonical fork 1 -> 2 -> 3 -> 4 -> 5, an abandoned fork 1 -> 12, and a
+    /// non-canonical fork 1 -> 2 -> 3 -> 14.  Each block stores its own byte under "key-$byte".
+    fn make_forked_marf(path: &str) -> MARF<StacksBlockId> {
+        if fs::metadata(path).is_ok() {
+            fs::remove_file(path).unwrap();
+        }
+        let blobs_path = format!("{}.blobs", path);
+        if fs::metadata(&blobs_path).is_ok() {
+            fs::remove_file(&blobs_path).unwrap();
+        }
+
+        let mut marf_opts = MARFOpenOpts::default();
+        marf_opts.external_blobs = true;
+        let mut marf = MARF::from_path(path, marf_opts).unwrap();
+
+        let blocks = [
+            (StacksBlockId::sentinel(), 1),
+            (block_id(1), 2),
+            (block_id(2), 3),
+            (block_id(3), 4),
+            (block_id(4), 5),
+            (block_id(1), 12),
+            (block_id(3), 14),
+        ];
+        for (parent, byte) in blocks.iter() {
+            marf.begin(parent, &block_id(*byte)).unwrap();
+            marf.insert(
+                &format!("key-{}", byte),
+                MARFValue::from_value(&format!("value-{}", byte)),
+            )
+            .unwrap();
+            marf.commit().unwrap();
+        }
+        marf
+    }
+
+    #[test]
+    fn marf_compact_drops_abandoned_forks() {
+        let path = "/tmp/marf_compact_drops_abandoned_forks.sqlite";
+        let mut marf = make_forked_marf(path);
+
+        // nothing has reached the horizon yet
+        assert_eq!(marf_compact(&mut marf, 10).unwrap(), 0);
+        assert!(marf
+            .get_confirmed_block_hashes()
+            .unwrap()
+            .contains(&block_id(12)));
+
+        // block 12 is at height 1, so it's abandoned once height 2 is final
+        assert!(marf_compact(&mut marf, 2).unwrap() > 0);
+
+        let remaining = marf.get_confirmed_block_hashes().unwrap();
+        assert!(!remaining.contains(&block_id(12)));
+        for byte in [1, 2, 3, 4, 5, 14].iter() {
+            assert!(remaining.contains(&block_id(*byte)));
+        }
+
+        // both remaining forks can still read everything they could read before
+        for (tip, bytes) in [(5, vec![1, 2, 3, 4, 5]), (14, vec![1, 2, 3, 14])].iter() {
+            for byte in bytes.iter() {
+                assert_eq!(
+                    marf.get(&block_id(*tip), &format!("key-{}", byte)).unwrap(),
+                    Some(MARFValue::from_value(&format!("value-{}", byte)))
+                );
+            }
+            assert_eq!(marf.get(&block_id(*tip), "key-12").unwrap(), None);
+        }
+
+        // ...including after the MARF is reopened
+        drop(marf);
+        let mut marf_opts = MARFOpenOpts::default();
+        marf_opts.external_blobs = true;
+        let mut marf = MARF::from_path(path, marf_opts).unwrap();
+        assert_eq!(
+            marf.get(&block_id(5), "key-1").unwrap(),
+            Some(MARFValue::from_value("value-1"))
+        );
+
+        // and it can still grow
+        marf.begin(&block_id(5), &block_id(6)).unwrap();
+        marf.insert("key-6", MARFValue::from_value("value-6"))
+            .unwrap();
+        marf.commit().unwrap();
+        assert_eq!(
+            marf.get(&block_id(6), "key-2").unwrap(),
+            Some(MARFValue::from_value("value-2"))
+        );
+
+        // non-canonical forks above the horizon are never dropped
+        assert_eq!(marf_compact(&mut marf, 3).unwrap(), 0);
+        assert!(marf
+            .get_confirmed_block_hashes()
+            .unwrap()
+            .contains(&block_id(14)));
+    }
+}
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::collections::{btree_map::Entry, BTreeMap, HashSet};
+use std::collections::{btree_map::Entry, BTreeMap, HashSet, VecDeque};
 use std::fmt;
 use std::fs;
 use std::io;
@@ -99,6 +99,7 @@ use stacks_common::types::chainstate::{StacksAddress, StacksBlockId, TrieHash};
 
 pub mod accounts;
 pub mod blocks;
+pub mod compact;
 pub mod contracts;
 pub mod headers;
 pub mod transactions;
@@ -199,6 +200,9 @@ pub struct StacksEpochReceipt {
     /// which is the Stacks epoch that this block's parent was elected
     /// in.
     pub evaluated_epoch: StacksEpochId,
+    /// This is the Stacks epoch that this block's parent was evaluated in.
+    /// It differs from `evaluated_epoch` only if `epoch_transition` is set.
+    pub parent_epoch: StacksEpochId,
     pub epoch_transition: bool,
 }
 
@@ -226,6 +230,7 @@ impl DBConfig {
             StacksEpochId::Epoch22 => self.version == "3" || self.version == "4",
             StacksEpochId::Epoch23 => self.version == "3" || self.version == "4",
             StacksEpochId::Epoch24 => self.version == "3" || self.version == "4",
+            StacksEpochId::Epoch25 => self.version == "3" || self.version == "4",
         }
     }
 }
@@ -534,6 +539,7 @@ pub trait Streamer {
 #[derive(Debug, PartialEq, Clone)]
 pub enum StreamCursor {
     Block(BlockStreamData),
+    Blocks(BlocksStreamData),
     Microblocks(MicroblockStreamData),
     Headers(HeaderStreamData),
     MempoolTxs(TxStreamData),
@@ -549,6 +555,18 @@ pub struct BlockStreamData {
     total_bytes: u64,
 }
 
+#[derive(Debug, PartialEq, Clone)]
+pub struct BlocksStreamData {
+    /// index block hashes of the blocks left to send, in order
+    index_block_hashes: VecDeque<StacksBlockId>,
+    /// the block currently being sent, if any
+    block: Option<BlockStreamData>,
+
+    /// length prefix of the block currently being sent
+    block_len_buf: [u8; 4],
+    block_len_ptr: usize,
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct MicroblockStreamData {
     /// index block hash of the block to download
@@ -571,6 +589,8 @@ pub struct MicroblockStreamData {
     /// unconfirmed state
     seq: u16,
     unconfirmed: bool,
+    /// highest sequence number to stream (unconfirmed only)
+    max_seq: u16,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -1630,6 +1650,86 @@ impl StacksChainState {
         StacksChainState::open_and_exec(mainnet, chain_id, path_str, None, marf_opts)
     }
 
+    /// Open an existing chainstate without write access, e.g. to inspect it with a CLI tool.
+    /// Its network and chain ID are read from its DB config.  Unlike `open()`, this will neither
+    /// instantiate nor migrate the chainstate, so it fails if the chainstate does not exist or
+    /// was written by a different version of this software.
+    pub fn open_readonly(
+        path_str: &str,
+        marf_opts: Option<MARFOpenOpts>,
+    ) -> Result<StacksChainState, Error> {
+        let path = PathBuf::from(path_str);
+        let blocks_path_root = StacksChainState::blocks_path(path.clone())
+            .to_str()
+            .ok_or_else(|| Error::DBError(db_error::ParseError))?
+            .to_string();
+
+        let clarity_state_index_root = StacksChainState::vm_state_index_root_path(path.clone())
+            .to_str()
+            .ok_or_else(|| Error::DBError(db_error::ParseError))?
+            .to_string();
+
+        let clarity_state_index_marf = StacksChainState::vm_state_index_marf_path(path.clone())
+            .to_str()
+            .ok_or_else(|| Error::DBError(db_error::ParseError))?
+            .to_string();
+
+        let header_index_root = StacksChainState::header_index_root_path(path)
+            .to_str()
+            .ok_or_else(|| Error::DBError(db_error::ParseError))?
+            .to_string();
+
+        if fs::metadata(&header_index_root).is_err()
+            || fs::metadata(&clarity_state_index_marf).is_err()
+        {
+            warn!("No chainstate at {}", path_str);
+            return Err(Error::DBError(db_error::NoDBError));
+        }
+
+        let mut open_opts = MARFOpenOpts::default();
+        open_opts.external_blobs = true;
+        let state_index = MARF::from_path_readonly(&header_index_root, open_opts)
+            .map_err(|e| db_error::IndexError(e))?;
+
+        let db_config = StacksChainState::load_db_config(state_index.sqlite_conn())?;
+        if db_config.version != CHAINSTATE_VERSION {
+            warn!(
+                "Chainstate at {} has version {}, but this software needs version {}",
+                path_str, &db_config.version, CHAINSTATE_VERSION
+            );
+            return Err(Error::DBError(db_error::Other(format!(
+                "Unsupported chainstate version {}",
+                &db_config.version
+            ))));
+        }
+
+        let vm_state = MarfedKV::open_readonly(
+            &clarity_state_index_root,
+            Some(&StacksBlockHeader::make_index_block_hash(
+                &MINER_BLOCK_CONSENSUS_HASH,
+                &MINER_BLOCK_HEADER_HASH,
+            )),
+            marf_opts.clone(),
+        )
+        .map_err(|e| Error::ClarityError(e.into()))?;
+
+        let clarity_state = ClarityInstance::new(db_config.mainnet, db_config.chain_id, vm_state);
+
+        Ok(StacksChainState {
+            mainnet: db_config.mainnet,
+            chain_id: db_config.chain_id,
+            clarity_state: clarity_state,
+            state_index: state_index,
+            blocks_path: blocks_path_root,
+            clarity_state_index_path: clarity_state_index_marf,
+            clarity_state_index_root: clarity_state_index_root,
+            root_path: path_str.to_string(),
+            unconfirmed_state: None,
+            fault_injection: StacksChainStateFaults::new(),
+            marf_opts: marf_opts,
+        })
+    }
+
     /// Re-open the chainstate -- i.e. to get a new handle to it using an existing chain state's
     /// parameters
     pub fn reopen(&self) -> Result<(StacksChainState, Vec<StacksTransactionReceipt>), Error> {
@@ -2613,6 +2713,51 @@ pub mod test {
         }
     }
 
+    #[test]
+    fn test_open_chainstate_readonly() {
+        let path = chainstate_path(function_name!());
+        let genesis_block_id = StacksBlockHeader::make_index_block_hash(
+            &FIRST_BURNCHAIN_CONSENSUS_HASH,
+            &FIRST_STACKS_BLOCK_HASH,
+        );
+        let pox_contract_id = boot_code_id("pox", false);
+        let program = "(var-get pox-reward-cycle-length)";
+
+        let expected = {
+            let mut chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
+            chainstate.clarity_eval_read_only(
+                &TEST_BURN_STATE_DB,
+                &genesis_block_id,
+                &pox_contract_id,
+                program,
+            )
+        };
+
+        let mut chainstate = StacksChainState::open_readonly(&path, None).unwrap();
+        assert!(!chainstate.mainnet);
+        assert_eq!(chainstate.chain_id, 0x80000000);
+        assert_eq!(
+            chainstate.clarity_eval_read_only(
+                &TEST_BURN_STATE_DB,
+                &genesis_block_id,
+                &pox_contract_id,
+                program,
+            ),
+            expected
+        );
+
+        // a read-only open never creates a chainstate
+        let missing_path = chainstate_path(&format!("{}-missing", function_name!()));
+        if fs::metadata(&missing_path).is_ok() {
+            fs::remove_dir_all(&missing_path).unwrap();
+        }
+        assert!(matches!(
+            StacksChainState::open_readonly(&missing_path, None),
+            Err(Error::DBError(db_error::NoDBError))
+        ));
+        assert!(fs::metadata(&missing_path).is_err());
+    }
+
     #[test]
     fn test_chainstate_sampled_genesis_consistency() {
         // Test root hash for the test chainstate data set
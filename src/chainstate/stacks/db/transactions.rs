@@ -78,6 +78,8 @@ impl StacksTransactionReceipt {
             microblock_header: None,
             tx_index: 0,
             vm_error: None,
+            cost_breakdown: None,
+            cost_function_breakdown: None,
         }
     }
 
@@ -99,6 +101,8 @@ impl StacksTransactionReceipt {
             microblock_header: None,
             tx_index: 0,
             vm_error: None,
+            cost_breakdown: None,
+            cost_function_breakdown: None,
         }
     }
 
@@ -120,6 +124,8 @@ impl StacksTransactionReceipt {
             microblock_header: None,
             tx_index: 0,
             vm_error: None,
+            cost_breakdown: None,
+            cost_function_breakdown: None,
         }
     }
 
@@ -141,6 +147,8 @@ impl StacksTransactionReceipt {
             microblock_header: None,
             tx_index: 0,
             vm_error: None,
+            cost_breakdown: None,
+            cost_function_breakdown: None,
         }
     }
 
@@ -162,6 +170,8 @@ impl StacksTransactionReceipt {
             microblock_header: None,
             tx_index: 0,
             vm_error: None,
+            cost_breakdown: None,
+            cost_function_breakdown: None,
         }
     }
 
@@ -177,6 +187,8 @@ impl StacksTransactionReceipt {
             microblock_header: None,
             tx_index: 0,
             vm_error: None,
+            cost_breakdown: None,
+            cost_function_breakdown: None,
         }
     }
 
@@ -195,6 +207,8 @@ impl StacksTransactionReceipt {
             microblock_header: None,
             tx_index: 0,
             vm_error: None,
+            cost_breakdown: None,
+            cost_function_breakdown: None,
         }
     }
 
@@ -214,6 +228,8 @@ impl StacksTransactionReceipt {
             microblock_header: None,
             tx_index: 0,
             vm_error: None,
+            cost_breakdown: None,
+            cost_function_breakdown: None,
         }
     }
 
@@ -234,6 +250,8 @@ impl StacksTransactionReceipt {
             microblock_header: None,
             tx_index: 0,
             vm_error: Some(format!("{}", &error)),
+            cost_breakdown: None,
+            cost_function_breakdown: None,
         }
     }
 
@@ -253,6 +271,8 @@ impl StacksTransactionReceipt {
             microblock_header: None,
             tx_index: 0,
             vm_error: Some(format!("{}", &error)),
+            cost_breakdown: None,
+            cost_function_breakdown: None,
         }
     }
 
@@ -894,6 +914,10 @@ impl StacksChainState {
         origin_account: &StacksAccount,
         ast_rules: ASTRules,
     ) -> Result<StacksTransactionReceipt, Error> {
+        // discard any call frames left over from earlier transactions or read-only evaluation,
+        // so that only this transaction's frames are reported
+        clarity_tx.take_call_frame_costs();
+
         match tx.payload {
             TransactionPayload::TokenTransfer(ref addr, ref amount, ref memo) => {
                 // post-conditions are not allowed for this variant, since they're non-sensical.
@@ -936,6 +960,56 @@ impl StacksChainState {
                 );
                 Ok(receipt)
             }
+            TransactionPayload::BatchTokenTransfer(ref transfers) => {
+                // same rules as TokenTransfer, applied to every transfer in the batch
+                if tx.post_conditions.len() > 0 {
+                    let msg = format!("Invalid Stacks transaction: BatchTokenTransfer transactions do not support post-conditions");
+                    warn!("{}", &msg);
+
+                    return Err(Error::InvalidStacksTransaction(msg, false));
+                }
+
+                if transfers
+                    .iter()
+                    .any(|(addr, _, _)| *addr == origin_account.principal)
+                {
+                    let msg =
+                        format!("Invalid BatchTokenTransfer: address tried to send to itself");
+                    warn!("{}", &msg);
+                    return Err(Error::InvalidStacksTransaction(msg, false));
+                }
+
+                let batch: Vec<_> = transfers
+                    .iter()
+                    .map(|(addr, amount, memo)| {
+                        (
+                            addr.clone(),
+                            *amount as u128,
+                            BuffData {
+                                data: Vec::from(memo.0.clone()),
+                            },
+                        )
+                    })
+                    .collect();
+
+                let cost_before = clarity_tx.cost_so_far();
+                let (value, _asset_map, events) = clarity_tx
+                    .run_stx_batch_transfer(&origin_account.principal, &batch)
+                    .map_err(Error::ClarityError)?;
+
+                let mut total_cost = clarity_tx.cost_so_far();
+                total_cost
+                    .sub(&cost_before)
+                    .expect("BUG: total block cost decreased");
+
+                let receipt = StacksTransactionReceipt::from_stx_transfer(
+                    tx.clone(),
+                    events,
+                    value,
+                    total_cost,
+                );
+                Ok(receipt)
+            }
             TransactionPayload::ContractCall(ref contract_call) => {
                 // if this calls a function that doesn't exist or is syntactically invalid, then the
                 // transaction is invalid (since this can be checked statically by the miner).
@@ -967,6 +1041,7 @@ impl StacksChainState {
                 total_cost
                     .sub(&cost_before)
                     .expect("BUG: total block cost decreased");
+                let cost_breakdown = clarity_tx.take_call_frame_costs();
 
                 let (result, asset_map, events) = match contract_call_resp {
                     Ok((return_value, asset_map, events)) => {
@@ -992,12 +1067,13 @@ impl StacksChainState {
                                       "contract_name" => %contract_id,
                                       "function_name" => %contract_call.function_name,
                                       "function_args" => %VecDisplay(&contract_call.function_args));
-                            let receipt = StacksTransactionReceipt::from_condition_aborted_contract_call(
+                            let mut receipt = StacksTransactionReceipt::from_condition_aborted_contract_call(
                                     tx.clone(),
                                     events,
                                     value.expect("BUG: Post condition contract call must provide would-have-been-returned value"),
                                     assets.get_stx_burned_total(),
                                     total_cost);
+                            receipt.cost_breakdown = Some(cost_breakdown);
                             return Ok(receipt);
                         }
                         ClarityRuntimeTxError::CostError(cost_after, budget) => {
@@ -1014,12 +1090,13 @@ impl StacksChainState {
                                       "function_args" => %VecDisplay(&contract_call.function_args),
                                       "error" => %check_error);
 
-                                let receipt =
+                                let mut receipt =
                                     StacksTransactionReceipt::from_runtime_failure_contract_call(
                                         tx.clone(),
                                         total_cost,
                                         check_error,
                                     );
+                                receipt.cost_breakdown = Some(cost_breakdown);
                                 return Ok(receipt);
                             } else {
                                 // prior to 2.1, this is not permitted in a block.
@@ -1044,13 +1121,14 @@ impl StacksChainState {
                     },
                 };
 
-                let receipt = StacksTransactionReceipt::from_contract_call(
+                let mut receipt = StacksTransactionReceipt::from_contract_call(
                     tx.clone(),
                     events,
                     result,
                     asset_map.get_stx_burned_total(),
                     total_cost,
                 );
+                receipt.cost_breakdown = Some(cost_breakdown);
                 Ok(receipt)
             }
             TransactionPayload::SmartContract(ref smart_contract, ref version_opt) => {
@@ -1332,11 +1410,34 @@ impl StacksChainState {
                 return Err(Error::InvalidStacksTransaction(msg, false));
             }
         }
+        if clarity_version == ClarityVersion::Clarity3 {
+            // requires 2.5 and higher
+            if clarity_block.get_epoch() < StacksEpochId::Epoch25 {
+                let msg = format!("Invalid transaction {}: asks for Clarity3, but not in Stacks epoch 2.5 or later", tx.txid());
+                warn!("{}", &msg);
+                return Err(Error::InvalidStacksTransaction(msg, false));
+            }
+        }
+
+        if let TransactionPayload::BatchTokenTransfer(..) = &tx.payload {
+            if !StacksBlock::batch_token_transfers_supported(epoch) {
+                let msg = format!(
+                    "Invalid transaction {}: batched STX transfers are not supported in {}",
+                    tx.txid(),
+                    epoch
+                );
+                warn!("{}", &msg);
+                return Err(Error::InvalidStacksTransaction(msg, false));
+            }
+        }
 
         let mut transaction = clarity_block.connection().start_transaction_processing();
 
+        // discard any cost function costs left over from earlier transactions
+        transaction.take_cost_function_costs();
+
         let fee = tx.get_tx_fee();
-        let tx_receipt = if epoch >= StacksEpochId::Epoch21 {
+        let mut tx_receipt = if epoch >= StacksEpochId::Epoch21 {
             // 2.1 and later: pay tx fee, then process transaction
             let (_origin_account, payer_account) =
                 StacksChainState::check_transaction_nonces(&mut transaction, tx, quiet)?;
@@ -1403,6 +1504,7 @@ impl StacksChainState {
 
             tx_receipt
         };
+        tx_receipt.cost_function_breakdown = transaction.take_cost_function_costs();
 
         transaction.commit();
 
@@ -1591,6 +1693,167 @@ pub mod test {
         }
     }
 
+    #[test]
+    fn process_batch_token_transfer_stx_transaction() {
+        let mut chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
+
+        let privk = StacksPrivateKey::from_hex(
+            "6d430bb91222408e7706c9001cfaeb91b08c2be6d5ac95779ab52c6b431950e001",
+        )
+        .unwrap();
+        let auth = TransactionAuth::from_p2pkh(&privk).unwrap();
+        let addr = auth.origin().address_testnet();
+        let recv_addr_1 = StacksAddress {
+            version: 1,
+            bytes: Hash160([0xff; 20]),
+        };
+        let recv_addr_2 = PrincipalData::from(QualifiedContractIdentifier {
+            issuer: StacksAddress {
+                version: 1,
+                bytes: Hash160([0xfe; 20]),
+            }
+            .into(),
+            name: "contract-hellow".into(),
+        });
+
+        let make_batch_tx = |transfers: Vec<(PrincipalData, u64)>, nonce: u64| {
+            let mut tx_batch = StacksTransaction::new(
+                TransactionVersion::Testnet,
+                auth.clone(),
+                TransactionPayload::BatchTokenTransfer(
+                    transfers
+                        .into_iter()
+                        .map(|(recv, amount)| (recv, amount, TokenTransferMemo([0u8; 34])))
+                        .collect(),
+                ),
+            );
+            tx_batch.chain_id = 0x80000000;
+            tx_batch.post_condition_mode = TransactionPostConditionMode::Allow;
+            tx_batch.set_tx_fee(0);
+            tx_batch.set_origin_nonce(nonce);
+
+            let mut signer = StacksTransactionSigner::new(&tx_batch);
+            signer.sign_origin(&privk).unwrap();
+            signer.get_tx().unwrap()
+        };
+
+        for (dbi, burn_db) in ALL_BURN_DBS.iter().enumerate() {
+            let mut conn = chainstate.block_begin(
+                burn_db,
+                &FIRST_BURNCHAIN_CONSENSUS_HASH,
+                &FIRST_STACKS_BLOCK_HASH,
+                &ConsensusHash([(dbi + 1) as u8; 20]),
+                &BlockHeaderHash([(dbi + 1) as u8; 32]),
+            );
+
+            conn.connection().as_transaction(|tx| {
+                StacksChainState::account_credit(tx, &addr.to_account_principal(), 1000)
+            });
+
+            // no Stacks epoch supports batched transfers yet
+            let signed_tx = make_batch_tx(
+                vec![
+                    (recv_addr_1.to_account_principal(), 123),
+                    (recv_addr_2.clone(), 456),
+                ],
+                0,
+            );
+            assert!(StacksChainState::process_transaction(
+                &mut conn,
+                &signed_tx,
+                false,
+                ASTRules::PrecheckSize
+            )
+            .is_err());
+
+            // but the payload itself debits the sender once and credits each recipient
+            let origin_account =
+                StacksChainState::get_account(&mut conn, &addr.to_account_principal());
+            let receipt = conn
+                .connection()
+                .as_transaction(|tx| {
+                    StacksChainState::process_transaction_payload(
+                        tx,
+                        &signed_tx,
+                        &origin_account,
+                        ASTRules::PrecheckSize,
+                    )
+                })
+                .unwrap();
+            assert_eq!(receipt.events.len(), 2);
+            assert!(receipt.execution_cost.runtime > 0);
+
+            let account_after =
+                StacksChainState::get_account(&mut conn, &addr.to_account_principal());
+            assert_eq!(
+                account_after.stx_balance.amount_unlocked(),
+                1000 - 123 - 456
+            );
+
+            let recv_account_1 =
+                StacksChainState::get_account(&mut conn, &recv_addr_1.to_account_principal());
+            assert_eq!(recv_account_1.stx_balance.amount_unlocked(), 123);
+
+            let recv_account_2 = StacksChainState::get_account(&mut conn, &recv_addr_2);
+            assert_eq!(recv_account_2.stx_balance.amount_unlocked(), 456);
+
+            // a batch the sender cannot fully cover moves no STX at all
+            let signed_tx = make_batch_tx(
+                vec![
+                    (recv_addr_1.to_account_principal(), 100),
+                    (recv_addr_2.clone(), 1000),
+                ],
+                1,
+            );
+            let origin_account =
+                StacksChainState::get_account(&mut conn, &addr.to_account_principal());
+            assert!(conn
+                .connection()
+                .as_transaction(|tx| {
+                    StacksChainState::process_transaction_payload(
+                        tx,
+                        &signed_tx,
+                        &origin_account,
+                        ASTRules::PrecheckSize,
+                    )
+                })
+                .is_err());
+
+            let account_after =
+                StacksChainState::get_account(&mut conn, &addr.to_account_principal());
+            assert_eq!(
+                account_after.stx_balance.amount_unlocked(),
+                1000 - 123 - 456
+            );
+
+            let recv_account_1 =
+                StacksChainState::get_account(&mut conn, &recv_addr_1.to_account_principal());
+            assert_eq!(recv_account_1.stx_balance.amount_unlocked(), 123);
+
+            // sending to oneself anywhere in the batch is invalid
+            let signed_tx = make_batch_tx(
+                vec![
+                    (recv_addr_1.to_account_principal(), 1),
+                    (addr.to_account_principal(), 1),
+                ],
+                1,
+            );
+            assert!(conn
+                .connection()
+                .as_transaction(|tx| {
+                    StacksChainState::process_transaction_payload(
+                        tx,
+                        &signed_tx,
+                        &origin_account,
+                        ASTRules::PrecheckSize,
+                    )
+                })
+                .is_err());
+
+            conn.commit_block();
+        }
+    }
+
     #[test]
     fn process_token_transfer_stx_transaction_invalid() {
         let mut chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
@@ -2401,6 +2664,354 @@ pub mod test {
         }
     }
 
+    #[test]
+    fn process_contract_call_cost_breakdown() {
+        let contracts = [
+            ("callee", "(define-public (inner) (ok u1))"),
+            (
+                "middle",
+                "(define-public (middle) (contract-call? .callee inner))",
+            ),
+            (
+                "outer",
+                "(define-public (outer) (contract-call? .middle middle))",
+            ),
+        ];
+
+        let mut chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
+
+        let privk = StacksPrivateKey::from_hex(
+            "6d430bb91222408e7706c9001cfaeb91b08c2be6d5ac95779ab52c6b431950e001",
+        )
+        .unwrap();
+        let auth = TransactionAuth::from_p2pkh(&privk).unwrap();
+        let addr = auth.origin().address_testnet();
+
+        let mut signed_contract_txs = vec![];
+        for (nonce, (name, code)) in contracts.iter().enumerate() {
+            let mut tx_contract = StacksTransaction::new(
+                TransactionVersion::Testnet,
+                auth.clone(),
+                TransactionPayload::new_smart_contract(&name.to_string(), &code.to_string(), None)
+                    .unwrap(),
+            );
+
+            tx_contract.chain_id = 0x80000000;
+            tx_contract.set_tx_fee(0);
+            tx_contract.set_origin_nonce(nonce as u64);
+
+            let mut signer = StacksTransactionSigner::new(&tx_contract);
+            signer.sign_origin(&privk).unwrap();
+            signed_contract_txs.push(signer.get_tx().unwrap());
+        }
+
+        let privk_2 = StacksPrivateKey::from_hex(
+            "d2c340ebcc0794b6fabdd8ac8b1c983e363b05dc8adcdf7e30db205a3fa54c1601",
+        )
+        .unwrap();
+        let auth_2 = TransactionAuth::from_p2pkh(&privk_2).unwrap();
+
+        let mut tx_contract_call = StacksTransaction::new(
+            TransactionVersion::Testnet,
+            auth_2.clone(),
+            TransactionPayload::new_contract_call(addr.clone(), "outer", "outer", vec![]).unwrap(),
+        );
+
+        tx_contract_call.chain_id = 0x80000000;
+        tx_contract_call.set_tx_fee(0);
+
+        let mut signer_2 = StacksTransactionSigner::new(&tx_contract_call);
+        signer_2.sign_origin(&privk_2).unwrap();
+
+        let signed_tx_2 = signer_2.get_tx().unwrap();
+
+        let callee_id = QualifiedContractIdentifier::new(
+            StandardPrincipalData::from(addr.clone()),
+            ContractName::from("callee"),
+        );
+        let middle_id = QualifiedContractIdentifier::new(
+            StandardPrincipalData::from(addr.clone()),
+            ContractName::from("middle"),
+        );
+
+        for (dbi, burn_db) in ALL_BURN_DBS.iter().enumerate() {
+            let mut conn = chainstate.block_begin(
+                burn_db,
+                &FIRST_BURNCHAIN_CONSENSUS_HASH,
+                &FIRST_STACKS_BLOCK_HASH,
+                &ConsensusHash([(dbi + 1) as u8; 20]),
+                &BlockHeaderHash([(dbi + 1) as u8; 32]),
+            );
+
+            for signed_tx in signed_contract_txs.iter() {
+                let (_, receipt) = StacksChainState::process_transaction(
+                    &mut conn,
+                    signed_tx,
+                    false,
+                    ASTRules::PrecheckSize,
+                )
+                .unwrap();
+                assert_eq!(receipt.cost_breakdown, None);
+            }
+
+            let (_, receipt) = StacksChainState::process_transaction(
+                &mut conn,
+                &signed_tx_2,
+                false,
+                ASTRules::PrecheckSize,
+            )
+            .unwrap();
+
+            conn.commit_block();
+
+            assert_eq!(receipt.result, Value::okay(Value::UInt(1)).unwrap());
+
+            // only `contract-call?` frames are recorded, innermost first: the transaction's
+            //  own call into `outer` is accounted for by `execution_cost`.
+            let cost_breakdown = receipt.cost_breakdown.unwrap();
+            assert_eq!(cost_breakdown.len(), 2);
+            assert_eq!(cost_breakdown[0].contract_identifier, callee_id);
+            assert_eq!(cost_breakdown[0].function_name.as_str(), "inner");
+            assert_eq!(cost_breakdown[1].contract_identifier, middle_id);
+            assert_eq!(cost_breakdown[1].function_name.as_str(), "middle");
+
+            // each frame's cost includes the cost of the frames it called
+            assert!(cost_breakdown[0].execution_cost.runtime > 0);
+            assert!(
+                cost_breakdown[1].execution_cost.runtime > cost_breakdown[0].execution_cost.runtime
+            );
+            assert!(!cost_breakdown[1]
+                .execution_cost
+                .exceeds(&receipt.execution_cost));
+        }
+    }
+
+    #[test]
+    fn process_contract_call_cost_breakdown_after_deploy() {
+        let contracts = [
+            ("callee", "(define-public (inner) (ok u1))"),
+            (
+                "middle",
+                "(define-public (middle) (contract-call? .callee inner))",
+            ),
+            // calls into `callee` while it is being deployed
+            (
+                "deployer",
+                "(define-data-var x uint u0)
+                 (begin
+                    (unwrap-panic (contract-call? .callee inner))
+                    (unwrap-panic (contract-call? .middle middle))
+                    (var-set x u1))",
+            ),
+        ];
+
+        let mut chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
+
+        let privk = StacksPrivateKey::from_hex(
+            "6d430bb91222408e7706c9001cfaeb91b08c2be6d5ac95779ab52c6b431950e001",
+        )
+        .unwrap();
+        let auth = TransactionAuth::from_p2pkh(&privk).unwrap();
+        let addr = auth.origin().address_testnet();
+
+        let mut signed_contract_txs = vec![];
+        for (nonce, (name, code)) in contracts.iter().enumerate() {
+            let mut tx_contract = StacksTransaction::new(
+                TransactionVersion::Testnet,
+                auth.clone(),
+                TransactionPayload::new_smart_contract(&name.to_string(), &code.to_string(), None)
+                    .unwrap(),
+            );
+
+            tx_contract.chain_id = 0x80000000;
+            tx_contract.set_tx_fee(0);
+            tx_contract.set_origin_nonce(nonce as u64);
+
+            let mut signer = StacksTransactionSigner::new(&tx_contract);
+            signer.sign_origin(&privk).unwrap();
+            signed_contract_txs.push(signer.get_tx().unwrap());
+        }
+
+        let privk_2 = StacksPrivateKey::from_hex(
+            "d2c340ebcc0794b6fabdd8ac8b1c983e363b05dc8adcdf7e30db205a3fa54c1601",
+        )
+        .unwrap();
+        let auth_2 = TransactionAuth::from_p2pkh(&privk_2).unwrap();
+
+        let mut tx_contract_call = StacksTransaction::new(
+            TransactionVersion::Testnet,
+            auth_2.clone(),
+            TransactionPayload::new_contract_call(addr.clone(), "middle", "middle", vec![])
+                .unwrap(),
+        );
+
+        tx_contract_call.chain_id = 0x80000000;
+        tx_contract_call.set_tx_fee(0);
+
+        let mut signer_2 = StacksTransactionSigner::new(&tx_contract_call);
+        signer_2.sign_origin(&privk_2).unwrap();
+
+        let signed_tx_2 = signer_2.get_tx().unwrap();
+
+        let callee_id = QualifiedContractIdentifier::new(
+            StandardPrincipalData::from(addr.clone()),
+            ContractName::from("callee"),
+        );
+
+        for (dbi, burn_db) in ALL_BURN_DBS.iter().enumerate() {
+            let mut conn = chainstate.block_begin(
+                burn_db,
+                &FIRST_BURNCHAIN_CONSENSUS_HASH,
+                &FIRST_STACKS_BLOCK_HASH,
+                &ConsensusHash([(dbi + 1) as u8; 20]),
+                &BlockHeaderHash([(dbi + 1) as u8; 32]),
+            );
+
+            for signed_tx in signed_contract_txs.iter() {
+                let (_, receipt) = StacksChainState::process_transaction(
+                    &mut conn,
+                    signed_tx,
+                    false,
+                    ASTRules::PrecheckSize,
+                )
+                .unwrap();
+                assert_eq!(receipt.cost_breakdown, None);
+                assert!(receipt.vm_error.is_none());
+            }
+
+            let (_, receipt) = StacksChainState::process_transaction(
+                &mut conn,
+                &signed_tx_2,
+                false,
+                ASTRules::PrecheckSize,
+            )
+            .unwrap();
+
+            conn.commit_block();
+
+            assert_eq!(receipt.result, Value::okay(Value::UInt(1)).unwrap());
+
+            // none of the frames evaluated while deploying `deployer` show up here
+            let cost_breakdown = receipt.cost_breakdown.unwrap();
+            assert_eq!(cost_breakdown.len(), 1);
+            assert_eq!(cost_breakdown[0].contract_identifier, callee_id);
+            assert_eq!(cost_breakdown[0].function_name.as_str(), "inner");
+        }
+    }
+
+    #[test]
+    fn process_contract_call_cost_function_breakdown() {
+        let contract = "
+        (define-map counts principal uint)
+        (define-public (bump (n uint))
+            (let ((current (default-to u0 (map-get? counts tx-sender))))
+                (if (> n u0)
+                    (ok (map-set counts tx-sender (+ current n)))
+                    (err u1))))";
+
+        let mut chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
+
+        let privk = StacksPrivateKey::from_hex(
+            "6d430bb91222408e7706c9001cfaeb91b08c2be6d5ac95779ab52c6b431950e001",
+        )
+        .unwrap();
+        let auth = TransactionAuth::from_p2pkh(&privk).unwrap();
+        let addr = auth.origin().address_testnet();
+
+        let mut tx_contract = StacksTransaction::new(
+            TransactionVersion::Testnet,
+            auth.clone(),
+            TransactionPayload::new_smart_contract(
+                &"hello-world".to_string(),
+                &contract.to_string(),
+                None,
+            )
+            .unwrap(),
+        );
+
+        tx_contract.chain_id = 0x80000000;
+        tx_contract.set_tx_fee(0);
+
+        let mut signer = StacksTransactionSigner::new(&tx_contract);
+        signer.sign_origin(&privk).unwrap();
+
+        let signed_tx = signer.get_tx().unwrap();
+
+        let mut tx_contract_call = StacksTransaction::new(
+            TransactionVersion::Testnet,
+            auth.clone(),
+            TransactionPayload::new_contract_call(
+                addr.clone(),
+                "hello-world",
+                "bump",
+                vec![Value::UInt(3)],
+            )
+            .unwrap(),
+        );
+
+        tx_contract_call.chain_id = 0x80000000;
+        tx_contract_call.set_tx_fee(0);
+        tx_contract_call.set_origin_nonce(1);
+
+        let mut signer_2 = StacksTransactionSigner::new(&tx_contract_call);
+        signer_2.sign_origin(&privk).unwrap();
+
+        let signed_tx_2 = signer_2.get_tx().unwrap();
+
+        for (dbi, burn_db) in ALL_BURN_DBS.iter().enumerate() {
+            for record in [false, true] {
+                chainstate.clarity_state.set_record_cost_functions(record);
+                let block_byte = (dbi + 1 + if record { 0x10 } else { 0 }) as u8;
+                let mut conn = chainstate.block_begin(
+                    burn_db,
+                    &FIRST_BURNCHAIN_CONSENSUS_HASH,
+                    &FIRST_STACKS_BLOCK_HASH,
+                    &ConsensusHash([block_byte; 20]),
+                    &BlockHeaderHash([block_byte; 32]),
+                );
+
+                let (_, receipt) = StacksChainState::process_transaction(
+                    &mut conn,
+                    &signed_tx,
+                    false,
+                    ASTRules::PrecheckSize,
+                )
+                .unwrap();
+                assert_eq!(receipt.cost_function_breakdown.is_some(), record);
+
+                let (_, receipt) = StacksChainState::process_transaction(
+                    &mut conn,
+                    &signed_tx_2,
+                    false,
+                    ASTRules::PrecheckSize,
+                )
+                .unwrap();
+
+                conn.commit_block();
+
+                assert_eq!(receipt.result, Value::okay(Value::Bool(true)).unwrap());
+                if !record {
+                    assert_eq!(receipt.cost_function_breakdown, None);
+                    continue;
+                }
+
+                // only the cost functions this call charged are listed, and their runtimes add
+                //  up to the runtime charged for the whole transaction
+                let breakdown = receipt.cost_function_breakdown.unwrap();
+                for name in ["cost_user_function_application", "cost_let", "cost_if"] {
+                    assert!(breakdown[name].runtime > 0, "no runtime for {}", name);
+                }
+                assert!(!breakdown.contains_key("cost_ast_parse"));
+
+                let total_runtime = breakdown
+                    .values()
+                    .fold(0u64, |total, cost| total + cost.runtime);
+                assert!(receipt.execution_cost.runtime > 0);
+                assert_eq!(total_runtime, receipt.execution_cost.runtime);
+            }
+        }
+    }
+
     // Verify that a contract call transaction which passes a long contract
     // name (> 40 chars and < 128) is processed successfully.
     #[test]
@@ -8370,6 +8981,7 @@ pub mod test {
                     StacksEpochId::Epoch22 => self.get_stacks_epoch(3),
                     StacksEpochId::Epoch23 => self.get_stacks_epoch(4),
                     StacksEpochId::Epoch24 => self.get_stacks_epoch(5),
+                    StacksEpochId::Epoch25 => self.get_stacks_epoch(6),
                 }
             }
             fn get_pox_payout_addrs(
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use crate::burnchains::Txid;
 use crate::chainstate::stacks::StacksMicroblockHeader;
 use crate::chainstate::stacks::StacksTransaction;
@@ -5,7 +7,7 @@ use crate::codec::StacksMessageCodec;
 use crate::types::chainstate::StacksAddress;
 use clarity::util::hash::to_hex;
 use clarity::vm::analysis::ContractAnalysis;
-use clarity::vm::costs::ExecutionCost;
+use clarity::vm::costs::{CallFrameCost, ExecutionCost};
 use clarity::vm::types::{
     AssetIdentifier, PrincipalData, QualifiedContractIdentifier, StandardPrincipalData, Value,
 };
@@ -55,4 +57,10 @@ pub struct StacksTransactionReceipt {
     pub tx_index: u32,
     /// This is really a string-formatted CheckError (which can't be clone()'ed)
     pub vm_error: Option<String>,
+    /// For contract-calls, the cost of each `contract-call?` frame, in the order in which
+    ///  the frames returned.
+    pub cost_breakdown: Option<Vec<CallFrameCost>>,
+    /// The cost charged by each Clarity cost function, keyed by cost function name. Only
+    ///  recorded if the node opts in, since it is not needed for consensus.
+    pub cost_function_breakdown: Option<BTreeMap<String, ExecutionCost>>,
 }
@@ -335,6 +335,124 @@ impl TrieFile {
         trie_sql::set_migrated(db).expect("FATAL: failed to mark DB as migrated");
         Ok(())
     }
+
+    /// Get the path to the compacted copy of a disk-backed TrieFile at `path`
+    fn compact_path(path: &str) -> String {
+        format!("{}.compact", path)
+    }
+
+    /// Write a compacted copy of this TrieFile that only holds the trie blobs that the given DB
+    /// refers to, packed together in the order in which they were stored.  The blobs' new
+    /// offsets are written to `db`, along with a marker that a compaction is pending.  `db`
+    /// should be a transaction; once the caller commits it, it must pass the returned TrieFile
+    /// to `finish_compaction()`.  This TrieFile is left untouched, so if the transaction is
+    /// rolled back instead, the old offsets remain valid.
+    /// Returns the compacted TrieFile and the number of bytes it frees.
+    /// NOTE: this is *not* thread-safe.  Do not call while the DB is being used by another thread.
+    pub fn compact_trie_blobs(&mut self, db: &Connection) -> Result<(TrieFile, u64), Error> {
+        let size_before = self.seek(SeekFrom::End(0))?;
+        let mut compacted = match self {
+            TrieFile::RAM(ref ram) => TrieFile::new_ram(ram.readonly),
+            TrieFile::Disk(ref disk) => {
+                let compact_path = TrieFile::compact_path(&disk.path);
+                if let Err(e) = fs::remove_file(&compact_path) {
+                    if e.kind() != io::ErrorKind::NotFound {
+                        return Err(e.into());
+                    }
+                }
+                TrieFile::new_disk(&compact_path, false)?
+            }
+        };
+
+        let mut offset = 0;
+        for (block_id, old_offset, length) in trie_sql::get_external_trie_offsets_lengths(db)? {
+            let mut trie_blob = vec![0u8; length as usize];
+            self.seek(SeekFrom::Start(old_offset))?;
+            self.read_exact(&mut trie_blob)?;
+
+            compacted.write_all(&trie_blob)?;
+            trie_sql::set_external_trie_offset(db, block_id, offset)?;
+            offset += length;
+        }
+        compacted.flush()?;
+        if let TrieFile::Disk(ref compacted_disk) = compacted {
+            // must be durable before the new offsets are
+            compacted_disk.fd.sync_all()?;
+        }
+        trie_sql::set_pending_compaction(db, offset)?;
+
+        debug!(
+            "Compacted trie blobs in {} from {} to {} bytes",
+            &self.get_path(),
+            size_before,
+            offset
+        );
+        Ok((compacted, size_before.saturating_sub(offset)))
+    }
+
+    /// Replace this TrieFile with the compacted TrieFile from `compact_trie_blobs()`, once the
+    /// transaction that stored its offsets has been committed, and clear the pending compaction
+    /// marker from `db`.
+    pub fn finish_compaction(&mut self, db: &Connection, compacted: TrieFile) -> Result<(), Error> {
+        match compacted {
+            TrieFile::RAM(..) => {
+                *self = compacted;
+            }
+            TrieFile::Disk(compacted_disk) => {
+                let path = self.get_path();
+                fs::rename(&compacted_disk.path, &path)?;
+                *self = TrieFile::new_disk(&path, false)?;
+            }
+        }
+        trie_sql::clear_pending_compaction(db)
+    }
+
+    /// Finish or undo a compaction that was interrupted by a crash.  If the DB has the pending
+    /// compaction marker, then the compacted file's offsets were committed, so the compacted file
+    /// replaces this one (if that did not already happen).  Otherwise, any compacted file is
+    /// left over from a compaction that was never committed, and is deleted.
+    pub fn recover_compaction(&mut self, db: &Connection) -> Result<(), Error> {
+        let path = match self {
+            TrieFile::RAM(_) => {
+                return Ok(());
+            }
+            TrieFile::Disk(ref disk) => disk.path.clone(),
+        };
+        let compact_path = TrieFile::compact_path(&path);
+        let compact_len_opt = match fs::metadata(&compact_path) {
+            Ok(md) => Some(md.len()),
+            Err(e) => {
+                if e.kind() != io::ErrorKind::NotFound {
+                    return Err(e.into());
+                }
+                None
+            }
+        };
+
+        match (trie_sql::get_pending_compaction(db)?, compact_len_opt) {
+            (Some(blobs_length), Some(compact_len)) => {
+                if blobs_length != compact_len {
+                    return Err(Error::CorruptionError(format!(
+                        "Compacted trie blobs file {} has {} bytes, but expected {}",
+                        &compact_path, compact_len, blobs_length
+                    )));
+                }
+                info!("Finish interrupted compaction of trie blobs in {}", &path);
+                let compacted = TrieFile::new_disk(&compact_path, false)?;
+                self.finish_compaction(db, compacted)?;
+            }
+            (Some(_), None) => {
+                // already swapped in
+                trie_sql::clear_pending_compaction(db)?;
+            }
+            (None, Some(_)) => {
+                info!("Discard uncommitted compaction of trie blobs in {}", &path);
+                fs::remove_file(&compact_path)?;
+            }
+            (None, None) => {}
+        }
+        Ok(())
+    }
 }
 
 /// NodeHashReader for TrieFile
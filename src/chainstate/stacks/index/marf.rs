@@ -34,6 +34,7 @@ use crate::chainstate::stacks::index::storage::{
     TrieFileStorage, TrieHashCalculationMode, TrieStorageConnection, TrieStorageTransaction,
 };
 use crate::chainstate::stacks::index::trie::Trie;
+use crate::chainstate::stacks::index::trie_sql;
 use crate::chainstate::stacks::index::Error;
 use crate::chainstate::stacks::index::MARFValue;
 use crate::chainstate::stacks::index::MarfTrieId;
@@ -1121,6 +1122,13 @@ impl<T: MarfTrieId> MARF<T> {
         Ok(MARF::from_storage(file_storage))
     }
 
+    /// Instantiate a read-only MARF using a TrieFileStorage instance, from the given path on disk.
+    /// The MARF must already exist.
+    pub fn from_path_readonly(path: &str, open_opts: MARFOpenOpts) -> Result<MARF<T>, Error> {
+        let file_storage = TrieFileStorage::open_readonly(path, open_opts)?;
+        Ok(MARF::from_storage(file_storage))
+    }
+
     /// Instantiate an unconfirmed MARF using a TrieFileStorage instance, from the given path on disk.
     /// This will have the side-effect of instantiating a new fork table from the tries encoded on
     /// disk. Performant code should call this method sparingly.
@@ -1510,6 +1518,29 @@ impl<T: MarfTrieId> MARF<T> {
         self.storage.transaction().unwrap()
     }
 
+    /// Get the path to the DB that backs this MARF
+    pub fn get_db_path(&self) -> &str {
+        &self.storage.db_path
+    }
+
+    /// Get the hashes of all confirmed blocks that have tries in this MARF
+    pub fn get_confirmed_block_hashes(&self) -> Result<Vec<T>, Error> {
+        trie_sql::get_confirmed_block_hashes(self.sqlite_conn())
+    }
+
+    /// Delete the tries of the given confirmed blocks, and reclaim the space they used in the
+    /// external trie blobs file.  Returns the number of bytes freed in that file.  The caller
+    /// must ensure that no remaining block descends from a deleted block.
+    pub fn delete_tries(&mut self, block_hashes: &[T]) -> Result<u64, Error> {
+        if self.storage.readonly() {
+            return Err(Error::ReadOnlyError);
+        }
+        if self.open_chain_tip.is_some() {
+            return Err(Error::InProgressError);
+        }
+        self.storage.delete_tries(block_hashes)
+    }
+
     /// Make a raw transaction to the underlying storage
     pub fn storage_tx<'a>(&'a mut self) -> Result<Transaction<'a>, db_error> {
         self.storage.sqlite_tx()
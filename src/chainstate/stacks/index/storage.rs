@@ -1434,6 +1434,19 @@ impl<T: MarfTrieId> TrieFileStorage<T> {
             None
         };
 
+        if let Some(blobs) = blobs.as_mut() {
+            if readonly {
+                if trie_sql::get_pending_compaction(&db)?.is_some() {
+                    return Err(Error::CorruptionError(format!(
+                        "Trie blobs compaction of {} is unfinished; open it read-write to finish it",
+                        &db_path
+                    )));
+                }
+            } else {
+                blobs.recover_compaction(&db)?;
+            }
+        }
+
         let prev_schema_version = trie_sql::migrate_tables_if_needed::<T>(&mut db)?;
         if prev_schema_version != trie_sql::SQL_MARF_SCHEMA_VERSION || marf_opts.force_db_migrate {
             if let Some(blobs) = blobs.as_mut() {
@@ -1592,6 +1605,44 @@ impl<T: MarfTrieId> TrieFileStorage<T> {
     pub fn reset_benchmarks(&mut self) {
         self.bench.reset();
     }
+
+    /// Delete the tries of the given confirmed blocks, and rewrite the external trie blobs file
+    /// (if there is one) without them.  Returns the number of bytes freed in the blobs file.
+    /// The caller must ensure that no remaining trie has a back-pointer into a deleted trie.
+    /// The old blobs file is only replaced once the new offsets are committed; if the node crashes
+    /// in between, the compaction is finished or undone when the MARF is next opened.
+    /// NOTE: do not call while the MARF is used by another process.
+    pub fn delete_tries(&mut self, block_hashes: &[T]) -> Result<u64, Error> {
+        if self.data.readonly {
+            return Err(Error::ReadOnlyError);
+        }
+        if self.data.uncommitted_writes.is_some() {
+            return Err(Error::InProgressError);
+        }
+
+        let tx = tx_begin_immediate(&mut self.db)?;
+        for bhh in block_hashes.iter() {
+            trie_sql::drop_confirmed_trie(&tx, bhh)?;
+        }
+        let compaction = match self.blobs.as_mut() {
+            Some(blobs) => Some(blobs.compact_trie_blobs(&tx)?),
+            None => None,
+        };
+        tx.commit()?;
+
+        let freed = match (self.blobs.as_mut(), compaction) {
+            (Some(blobs), Some((compacted, freed))) => {
+                blobs.finish_compaction(&self.db, compacted)?;
+                freed
+            }
+            _ => 0,
+        };
+
+        // cached block IDs may refer to deleted tries
+        self.cache = TrieCache::default();
+        self.data.set_block(T::sentinel(), None);
+        Ok(freed)
+    }
 }
 
 impl<'a, T: MarfTrieId> TrieStorageTransaction<'a, T> {
@@ -162,3 +162,101 @@ fn test_migrate_existing_trie_blobs() {
         }
     }
 }
+
+/// Store three trie blobs, and start compacting away the second one
+fn setup_compaction(test_name: &str) -> (Connection, TrieFile) {
+    let path = db_path(test_name);
+    for suffix in [".blobs", ".blobs.compact"].iter() {
+        let file_path = format!("{}{}", &path, suffix);
+        if fs::metadata(&file_path).is_ok() {
+            fs::remove_file(&file_path).unwrap();
+        }
+    }
+    let mut db = setup_db(test_name);
+    let mut blobs = TrieFile::from_db_path(&path, false).unwrap();
+    trie_sql::migrate_tables_if_needed::<BlockHeaderHash>(&mut db).unwrap();
+
+    for i in 1..4u8 {
+        blobs
+            .store_trie_blob::<BlockHeaderHash>(&db, &BlockHeaderHash([i; 32]), &[i; 5])
+            .unwrap();
+    }
+    (db, blobs)
+}
+
+fn check_trie_blob(db: &Connection, blobs: &mut TrieFile, i: u8) {
+    let block_id = trie_sql::get_block_identifier(db, &BlockHeaderHash([i; 32])).unwrap();
+    assert_eq!(blobs.read_trie_blob(db, block_id).unwrap(), vec![i; 5]);
+}
+
+#[test]
+fn test_compact_trie_blobs() {
+    let test_name = "test_compact_trie_blobs";
+    let (mut db, mut blobs) = setup_compaction(test_name);
+
+    let tx = tx_begin_immediate(&mut db).unwrap();
+    trie_sql::drop_confirmed_trie(&tx, &BlockHeaderHash([2; 32])).unwrap();
+    let (compacted, freed) = blobs.compact_trie_blobs(&tx).unwrap();
+    assert_eq!(freed, 5);
+    tx.commit().unwrap();
+
+    blobs.finish_compaction(&db, compacted).unwrap();
+    assert_eq!(trie_sql::get_pending_compaction(&db).unwrap(), None);
+    assert!(fs::metadata(&format!("{}.blobs.compact", &db_path(test_name))).is_err());
+    check_trie_blob(&db, &mut blobs, 1);
+    check_trie_blob(&db, &mut blobs, 3);
+    assert_eq!(
+        fs::metadata(&format!("{}.blobs", &db_path(test_name)))
+            .unwrap()
+            .len(),
+        10
+    );
+}
+
+#[test]
+fn test_compact_trie_blobs_crash_before_commit() {
+    let test_name = "test_compact_trie_blobs_crash_before_commit";
+    let (mut db, mut blobs) = setup_compaction(test_name);
+
+    // crash before the new offsets are committed
+    let tx = tx_begin_immediate(&mut db).unwrap();
+    trie_sql::drop_confirmed_trie(&tx, &BlockHeaderHash([2; 32])).unwrap();
+    blobs.compact_trie_blobs(&tx).unwrap();
+    drop(tx);
+    drop(blobs);
+
+    let compact_path = format!("{}.blobs.compact", &db_path(test_name));
+    assert!(fs::metadata(&compact_path).is_ok());
+
+    // the compacted file is discarded, and the old blobs are still readable
+    let mut blobs = TrieFile::from_db_path(&db_path(test_name), false).unwrap();
+    blobs.recover_compaction(&db).unwrap();
+    assert!(fs::metadata(&compact_path).is_err());
+    for i in 1..4u8 {
+        check_trie_blob(&db, &mut blobs, i);
+    }
+}
+
+#[test]
+fn test_compact_trie_blobs_crash_after_commit() {
+    let test_name = "test_compact_trie_blobs_crash_after_commit";
+    let (mut db, mut blobs) = setup_compaction(test_name);
+
+    // crash after the new offsets are committed, but before the compacted file is swapped in
+    let tx = tx_begin_immediate(&mut db).unwrap();
+    trie_sql::drop_confirmed_trie(&tx, &BlockHeaderHash([2; 32])).unwrap();
+    let (compacted, _) = blobs.compact_trie_blobs(&tx).unwrap();
+    tx.commit().unwrap();
+    drop(compacted);
+    drop(blobs);
+
+    assert_eq!(trie_sql::get_pending_compaction(&db).unwrap(), Some(10));
+
+    // the compacted file is swapped in, and the remaining blobs are readable at their new offsets
+    let mut blobs = TrieFile::from_db_path(&db_path(test_name), false).unwrap();
+    blobs.recover_compaction(&db).unwrap();
+    assert_eq!(trie_sql::get_pending_compaction(&db).unwrap(), None);
+    assert!(fs::metadata(&format!("{}.blobs.compact", &db_path(test_name))).is_err());
+    check_trie_blob(&db, &mut blobs, 1);
+    check_trie_blob(&db, &mut blobs, 3);
+}
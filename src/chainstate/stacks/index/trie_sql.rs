@@ -56,6 +56,7 @@ use crate::util_lib::db::query_count;
 use crate::util_lib::db::query_row;
 use crate::util_lib::db::query_rows;
 use crate::util_lib::db::sql_pragma;
+use crate::util_lib::db::table_exists;
 use crate::util_lib::db::tx_begin_immediate;
 use crate::util_lib::db::u64_to_sql;
 use stacks_common::util::log;
@@ -111,6 +112,13 @@ INSERT OR REPLACE INTO migrated_version (version) VALUES (1);
 
 pub static SQL_MARF_SCHEMA_VERSION: u64 = 2;
 
+/// Only exists between committing a compaction of the trie blobs file and swapping in the
+/// compacted file, so it is not part of any schema version.  Its presence means that the offsets
+/// in `marf_data` refer to the compacted file.
+static SQL_PENDING_COMPACTION_TABLE: &str = "
+CREATE TABLE pending_compaction (blobs_length INTEGER NOT NULL);
+";
+
 pub fn create_tables_if_needed(conn: &mut Connection) -> Result<(), Error> {
     let tx = tx_begin_immediate(conn)?;
 
@@ -712,6 +720,87 @@ pub fn drop_unconfirmed_trie<T: MarfTrieId>(conn: &Connection, bhh: &T) -> Resul
     Ok(())
 }
 
+/// Get the hashes of all confirmed blocks that have tries.
+pub fn get_confirmed_block_hashes<T: MarfTrieId>(conn: &Connection) -> Result<Vec<T>, Error> {
+    let mut s =
+        conn.prepare("SELECT block_hash FROM marf_data WHERE unconfirmed = 0 ORDER BY block_id")?;
+    let rows = s.query_and_then(NO_PARAMS, |row| {
+        let block_hash: T = row.get_unwrap("block_hash");
+        Ok(block_hash)
+    })?;
+    rows.collect()
+}
+
+/// Delete a confirmed block's trie.  Does nothing if there is no such trie.
+pub fn drop_confirmed_trie<T: MarfTrieId>(conn: &Connection, bhh: &T) -> Result<(), Error> {
+    debug!("Drop confirmed trie {}", bhh);
+    conn.execute(
+        "DELETE FROM marf_data WHERE block_hash = ? AND unconfirmed = 0",
+        &[bhh],
+    )?;
+    Ok(())
+}
+
+/// Get the block ID, offset, and length of each trie blob in the trie blobs file, in the order in
+/// which they are stored.
+pub fn get_external_trie_offsets_lengths(conn: &Connection) -> Result<Vec<(u32, u64, u64)>, Error> {
+    let mut s = conn.prepare(
+        "SELECT block_id, external_offset, external_length FROM marf_data WHERE external_length > 0 ORDER BY external_offset",
+    )?;
+    let rows = s.query_and_then(NO_PARAMS, |row| {
+        let block_id: u32 = row.get_unwrap("block_id");
+        let offset_i64: i64 = row.get_unwrap("external_offset");
+        let length_i64: i64 = row.get_unwrap("external_length");
+        Ok((block_id, offset_i64 as u64, length_i64 as u64))
+    })?;
+    rows.collect()
+}
+
+/// Move a trie blob to a new offset in the trie blobs file.
+pub fn set_external_trie_offset(
+    conn: &Connection,
+    block_id: u32,
+    offset: u64,
+) -> Result<(), Error> {
+    let args: &[&dyn ToSql] = &[&u64_to_sql(offset)?, &block_id];
+    conn.execute(
+        "UPDATE marf_data SET external_offset = ?1 WHERE block_id = ?2",
+        args,
+    )?;
+    Ok(())
+}
+
+/// Record that the trie blobs file is being replaced by a compacted file of the given length.
+/// This must be written in the same transaction as the compacted file's offsets.
+pub fn set_pending_compaction(conn: &Connection, blobs_length: u64) -> Result<(), Error> {
+    conn.execute_batch(SQL_PENDING_COMPACTION_TABLE)?;
+    conn.execute(
+        "INSERT INTO pending_compaction (blobs_length) VALUES (?1)",
+        &[&u64_to_sql(blobs_length)?],
+    )?;
+    Ok(())
+}
+
+/// Get the length of the compacted trie blobs file that has yet to replace the trie blobs file,
+/// if there is one.
+pub fn get_pending_compaction(conn: &Connection) -> Result<Option<u64>, Error> {
+    if !table_exists(conn, "pending_compaction")? {
+        return Ok(None);
+    }
+    let blobs_length = query_row(
+        conn,
+        "SELECT blobs_length FROM pending_compaction",
+        NO_PARAMS,
+    )?;
+    Ok(blobs_length)
+}
+
+/// Forget about a compaction once the compacted trie blobs file has replaced the old one.
+pub fn clear_pending_compaction(conn: &Connection) -> Result<(), Error> {
+    conn.execute("DROP TABLE IF EXISTS pending_compaction", NO_PARAMS)?;
+    Ok(())
+}
+
 pub fn clear_lock_data(conn: &Connection) -> Result<(), Error> {
     conn.execute("DELETE FROM block_extension_locks", NO_PARAMS)?;
     Ok(())
@@ -192,6 +192,84 @@ impl BlockBuilderSettings {
     }
 }
 
+/// How far an anchored block assembly got, so that a later attempt to mine off of the same parent
+/// can carry over its transactions and resume the mempool walk instead of starting over.
+#[derive(Debug, Clone)]
+pub struct BlockAssemblyCursor {
+    /// Index block hash of the parent the block was built off of
+    pub parent_block_id: StacksBlockId,
+    /// Hash of the parent microblock tail the block was built off of
+    pub parent_microblock: BlockHeaderHash,
+    /// Mempool transactions mined into the block, in order
+    pub mined_txs: Vec<StacksTransaction>,
+    /// Where the mempool walk stopped
+    pub mempool_cursor: MemPoolWalkCursor,
+}
+
+/// Counters describing a single anchored block assembly
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MinerMetrics {
+    /// Number of mempool transactions tried against the block
+    pub txs_considered: u64,
+    /// Number of mempool transactions in the block, including those carried over
+    pub txs_included: u64,
+    /// Number of transactions carried over from a previous attempt
+    pub txs_resumed: u64,
+    /// Whether transaction selection ran out of time
+    pub deadline_reached: bool,
+    /// Time spent assembling the block, in milliseconds
+    pub assembly_time_ms: u64,
+}
+
+/// The outcome of re-mining a proposed anchored block with
+/// `StacksBlockBuilder::validate_anchored_block_proposal`
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockProposalValidation {
+    /// Transactions in the proposal that could not be mined
+    pub invalid_txids: Vec<Txid>,
+    /// Execution cost of the proposal's transactions, as far as they could be mined
+    pub execution_cost: ExecutionCost,
+    /// Why the proposal is invalid, or None if it is valid
+    pub reason: Option<String>,
+}
+
+impl BlockProposalValidation {
+    fn invalid(reason: String) -> BlockProposalValidation {
+        BlockProposalValidation {
+            invalid_txids: vec![],
+            execution_cost: ExecutionCost::zero(),
+            reason: Some(reason),
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.reason.is_none()
+    }
+}
+
+/// The outcome of re-mining a microblock stream with
+/// `StacksMicroblockBuilder::validate_microblock_stream`
+#[derive(Debug, Clone, PartialEq)]
+pub struct MicroblockStreamValidation {
+    /// Sequence number of the first invalid microblock, or None if the stream is valid
+    pub first_invalid_seq: Option<u16>,
+    /// Why the microblock at `first_invalid_seq` is invalid
+    pub errors: Vec<String>,
+}
+
+impl MicroblockStreamValidation {
+    fn invalid(seq: u16, errors: Vec<String>) -> MicroblockStreamValidation {
+        MicroblockStreamValidation {
+            first_invalid_seq: Some(seq),
+            errors,
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.first_invalid_seq.is_none()
+    }
+}
+
 #[derive(Clone)]
 struct MicroblockMinerRuntime {
     bytes_so_far: u64,
@@ -407,6 +485,36 @@ impl TransactionResult {
         )
     }
 
+    /// Logs a queryable debug message for a mempool transaction that the miner tried to include in
+    /// a block, with its fee, origin, the execution cost it consumed (if it was mined), and how
+    /// long it took to process.
+    pub fn log_transaction_considered(&self, processing_time_ms: u128) {
+        let (tx, event_type, execution_cost) = match self {
+            TransactionResult::Success(TransactionSuccess { tx, receipt, .. }) => {
+                (tx, "success", Some(&receipt.execution_cost))
+            }
+            TransactionResult::ProcessingError(TransactionError { tx, .. }) => (tx, "error", None),
+            TransactionResult::Skipped(TransactionSkipped { tx, .. }) => (tx, "skip", None),
+            TransactionResult::Problematic(TransactionProblematic { tx, .. }) => {
+                (tx, "problematic", None)
+            }
+        };
+        debug!(
+            "Tx considered for block";
+            "event_name" => "transaction_considered",
+            "tx_id" => %tx.txid(),
+            "event_type" => event_type,
+            "fee" => tx.get_tx_fee(),
+            "origin" => %tx.origin_address(),
+            "runtime" => execution_cost.map(|cost| cost.runtime),
+            "read_count" => execution_cost.map(|cost| cost.read_count),
+            "read_length" => execution_cost.map(|cost| cost.read_length),
+            "write_count" => execution_cost.map(|cost| cost.write_count),
+            "write_length" => execution_cost.map(|cost| cost.write_length),
+            "processing_time_ms" => processing_time_ms,
+        );
+    }
+
     /// Creates a `TransactionResult` backed by `TransactionSuccess`.
     /// This method logs "transaction success" as a side effect.
     pub fn success(
@@ -994,6 +1102,217 @@ impl<'a> StacksMicroblockBuilder<'a> {
         }
     }
 
+    /// Apply each of a microblock's transactions with `mine_next_transaction`, and return why
+    /// each one that could not be mined was rejected.
+    fn apply_microblock_txs(
+        clarity_tx: &mut ClarityTx,
+        microblock: &StacksMicroblock,
+        bytes_so_far: &mut u64,
+        ast_rules: ASTRules,
+    ) -> Result<Vec<String>, Error> {
+        let mut errors = vec![];
+        for tx in microblock.txs.iter() {
+            let tx_len = tx.tx_len();
+            let error = match StacksMicroblockBuilder::mine_next_transaction(
+                clarity_tx,
+                tx.clone(),
+                tx_len,
+                *bytes_so_far,
+                &BlockLimitFunction::NO_LIMIT_HIT,
+                ast_rules,
+            )? {
+                TransactionResult::Success(..) => {
+                    *bytes_so_far += tx_len;
+                    continue;
+                }
+                TransactionResult::ProcessingError(TransactionError { error, .. })
+                | TransactionResult::Skipped(TransactionSkipped { error, .. })
+                | TransactionResult::Problematic(TransactionProblematic { error, .. }) => error,
+            };
+            errors.push(format!("Transaction {} is invalid: {}", &tx.txid(), &error));
+        }
+        Ok(errors)
+    }
+
+    /// Check a microblock stream off of the anchored block `parent_stacks_header` by applying
+    /// its transactions, in order, the same way the miner would.  The stream may either start a
+    /// new stream, or continue a stored one, in which case the stored microblocks it builds on
+    /// are applied first.  Checking stops at the first invalid microblock.  The Clarity state is
+    /// rolled back afterwards, and nothing is stored.
+    pub fn validate_microblock_stream(
+        chainstate_handle: &StacksChainState, // not directly used; used as a handle to open other chainstates
+        burn_dbconn: &dyn BurnStateDB,
+        parent_stacks_header: &StacksHeaderInfo, // anchored block the stream must build off of
+        microblocks: &[StacksMicroblock],
+    ) -> Result<MicroblockStreamValidation, Error> {
+        let parent_consensus_hash = parent_stacks_header.consensus_hash.clone();
+        let parent_block_hash = parent_stacks_header.anchored_header.block_hash();
+        let pubkey_hash = parent_stacks_header
+            .anchored_header
+            .microblock_pubkey_hash
+            .clone();
+
+        let first_microblock = match microblocks.first() {
+            Some(mblock) => mblock,
+            None => {
+                return Ok(MicroblockStreamValidation {
+                    first_invalid_seq: None,
+                    errors: vec![],
+                });
+            }
+        };
+
+        // the stored microblocks that this stream continues, if any
+        let parent_stream = if first_microblock.header.prev_block == parent_block_hash {
+            vec![]
+        } else {
+            match StacksChainState::load_microblock_stream_fork(
+                chainstate_handle.db(),
+                &parent_consensus_hash,
+                &parent_block_hash,
+                &first_microblock.header.prev_block,
+            )? {
+                Some(stream) => stream,
+                None => {
+                    return Ok(MicroblockStreamValidation::invalid(
+                        first_microblock.header.sequence,
+                        vec![format!(
+                            "Parent microblock {} does not descend from Stacks tip {}/{}",
+                            &first_microblock.header.prev_block,
+                            &parent_consensus_hash,
+                            &parent_block_hash
+                        )],
+                    ));
+                }
+            }
+        };
+
+        let (mut chainstate, _) = chainstate_handle.reopen()?;
+        let mainnet = chainstate.mainnet;
+        let chain_id = chainstate.chain_id;
+        let ast_rules = burn_dbconn.get_ast_rules(parent_stacks_header.burn_header_height);
+
+        // microblocks share the execution budget of their parent anchored block
+        let parent_index_hash =
+            StacksBlockHeader::make_index_block_hash(&parent_consensus_hash, &parent_block_hash);
+        let cost_so_far =
+            StacksChainState::get_stacks_block_anchored_cost(chainstate.db(), &parent_index_hash)?
+                .ok_or(Error::NoSuchBlockError)?;
+
+        let mut clarity_tx = chainstate.block_begin(
+            burn_dbconn,
+            &parent_consensus_hash,
+            &parent_block_hash,
+            &MINER_BLOCK_CONSENSUS_HASH,
+            &MINER_BLOCK_HEADER_HASH,
+        );
+        clarity_tx.reset_cost(cost_so_far);
+
+        let mut bytes_so_far = 0;
+        let mut expected_sequence = 0u32;
+        let mut expected_parent = parent_block_hash.clone();
+        for microblock in parent_stream.iter() {
+            match StacksMicroblockBuilder::apply_microblock_txs(
+                &mut clarity_tx,
+                microblock,
+                &mut bytes_so_far,
+                ast_rules,
+            ) {
+                Ok(errors) if errors.is_empty() => {}
+                Ok(_) => {
+                    clarity_tx.rollback_block();
+                    return Ok(MicroblockStreamValidation::invalid(
+                        first_microblock.header.sequence,
+                        vec![format!(
+                            "Stored parent microblock {} is invalid",
+                            &microblock.block_hash()
+                        )],
+                    ));
+                }
+                Err(e) => {
+                    clarity_tx.rollback_block();
+                    return Err(e);
+                }
+            }
+            expected_sequence = (microblock.header.sequence as u32) + 1;
+            expected_parent = microblock.block_hash();
+        }
+
+        let mut validation = MicroblockStreamValidation {
+            first_invalid_seq: None,
+            errors: vec![],
+        };
+        for microblock in microblocks.iter() {
+            let sequence = microblock.header.sequence;
+            let mut errors = vec![];
+            // a u16 sequence number never matches once the stream has run out of them
+            if (sequence as u32) != expected_sequence {
+                errors.push(format!(
+                    "Microblock {} has sequence {}, but expected {}",
+                    &microblock.block_hash(),
+                    sequence,
+                    expected_sequence
+                ));
+            }
+            if microblock.header.prev_block != expected_parent {
+                errors.push(format!(
+                    "Microblock {} has parent {}, but expected {}",
+                    &microblock.block_hash(),
+                    &microblock.header.prev_block,
+                    &expected_parent
+                ));
+            }
+            if let Err(e) = microblock.clone().verify(&pubkey_hash) {
+                errors.push(format!(
+                    "Microblock {} failed to verify signature with {}: {:?}",
+                    &microblock.block_hash(),
+                    &pubkey_hash,
+                    &e
+                ));
+            }
+            if !microblock.validate_transactions_static(mainnet, chain_id) {
+                errors.push(format!(
+                    "Microblock {} has one or more transactions that failed static checks",
+                    &microblock.block_hash()
+                ));
+            }
+            if errors.is_empty() {
+                match StacksMicroblockBuilder::apply_microblock_txs(
+                    &mut clarity_tx,
+                    microblock,
+                    &mut bytes_so_far,
+                    ast_rules,
+                ) {
+                    Ok(tx_errors) => {
+                        errors = tx_errors;
+                    }
+                    Err(e) => {
+                        clarity_tx.rollback_block();
+                        return Err(e);
+                    }
+                }
+            }
+            if !errors.is_empty() {
+                debug!(
+                    "Microblock {} (seq {}) off of {}/{} is invalid: {:?}",
+                    &microblock.block_hash(),
+                    sequence,
+                    &parent_consensus_hash,
+                    &parent_block_hash,
+                    &errors
+                );
+                validation = MicroblockStreamValidation::invalid(sequence, errors);
+                break;
+            }
+            expected_sequence = (sequence as u32) + 1;
+            expected_parent = microblock.block_hash();
+        }
+
+        // don't store the stream
+        clarity_tx.rollback_block();
+        Ok(validation)
+    }
+
     /// NOTE: this is only used in integration tests.
     #[cfg(any(test, feature = "testing"))]
     pub fn mine_next_microblock_from_txs(
@@ -1273,6 +1592,12 @@ impl<'a> StacksMicroblockBuilder<'a> {
                     },
                 );
 
+                // later passes only revisit transactions the first pass put aside, so the first
+                // pass's cursor is how far into the mempool this attempt got
+                if mempool_cursor.is_none() {
+                    mempool_cursor = Some(pass_cursor);
+                }
+
                 if to_drop_and_blacklist.len() > 0 {
                     debug!(
                         "Dropping and blacklisting {} problematic transaction(s)",
@@ -1313,6 +1638,7 @@ impl<'a> StacksMicroblockBuilder<'a> {
         self.runtime.num_mined = num_txs;
 
         mem_pool.drop_txs(&invalidated_txs)?;
+        MemPoolStats::record_too_expensive(invalidated_txs.len());
         event_dispatcher.mempool_txs_dropped(invalidated_txs, MemPoolDropReason::TOO_EXPENSIVE);
         event_dispatcher.mempool_txs_dropped(to_drop_and_blacklist, MemPoolDropReason::PROBLEMATIC);
 
@@ -2336,6 +2662,119 @@ impl StacksBlockBuilder {
         Ok(builder)
     }
 
+    /// Check a proposed anchored block by re-mining its transactions off of
+    /// `parent_stacks_header` and comparing the resulting block against the proposal.  The
+    /// transactions are applied the same way block processing applies them, but the block's
+    /// Clarity state is rolled back afterwards, so nothing is written to the MARF or the headers
+    /// DB.
+    pub fn validate_anchored_block_proposal(
+        chainstate_handle: &StacksChainState, // not directly used; used as a handle to open other chainstates
+        burn_dbconn: &SortitionDBConn,
+        parent_stacks_header: &StacksHeaderInfo, // Stacks header the proposal must build off of
+        block: &StacksBlock,
+    ) -> Result<BlockProposalValidation, Error> {
+        let parent_block_hash = parent_stacks_header.anchored_header.block_hash();
+        if block.header.parent_block != parent_block_hash {
+            return Ok(BlockProposalValidation::invalid(format!(
+                "Block does not build off of Stacks tip {}/{}",
+                &parent_stacks_header.consensus_hash, &parent_block_hash
+            )));
+        }
+
+        let (mut chainstate, _) = chainstate_handle.reopen()?;
+        let mainnet = chainstate.mainnet;
+        let chain_id = chainstate.chain_id;
+
+        let mut builder = StacksBlockBuilder::make_block_builder(
+            mainnet,
+            parent_stacks_header,
+            block.header.proof.clone(),
+            block.header.total_work.burn,
+            block.header.microblock_pubkey_hash.clone(),
+        )?;
+        builder.header.version = block.header.version;
+        if block.has_microblock_parent() {
+            builder.parent_microblock_hash = Some(block.header.parent_microblock.clone());
+        }
+
+        let mut miner_epoch_info = builder.pre_epoch_begin(&mut chainstate, burn_dbconn)?;
+        if !block.has_microblock_parent() {
+            // the proposal confirms none of the parent's microblocks
+            miner_epoch_info.parent_microblocks.clear();
+            builder.set_parent_microblock(&EMPTY_MICROBLOCK_PARENT_HASH, 0);
+        }
+        if builder.header.parent_microblock != block.header.parent_microblock
+            || builder.header.parent_microblock_sequence != block.header.parent_microblock_sequence
+        {
+            return Ok(BlockProposalValidation::invalid(format!(
+                "Parent microblock {} (seq {}) is not available",
+                &block.header.parent_microblock, block.header.parent_microblock_sequence
+            )));
+        }
+
+        let ast_rules = miner_epoch_info.ast_rules;
+        let (mut epoch_tx, _) = builder.epoch_begin(burn_dbconn, &mut miner_epoch_info)?;
+
+        if !block.validate_transactions_static(mainnet, chain_id, epoch_tx.get_epoch()) {
+            epoch_tx.rollback_block();
+            return Ok(BlockProposalValidation::invalid(
+                "Block failed static transaction checks".to_string(),
+            ));
+        }
+
+        let mut invalid_txids = vec![];
+        for tx in block.txs.iter() {
+            match builder.try_mine_tx_with_len(
+                &mut epoch_tx,
+                tx,
+                tx.tx_len(),
+                &BlockLimitFunction::NO_LIMIT_HIT,
+                ast_rules,
+            ) {
+                TransactionResult::Success(..) => {}
+                result => {
+                    debug!(
+                        "Block proposal transaction {} is invalid: {:?}",
+                        &tx.txid(),
+                        &result
+                    );
+                    invalid_txids.push(tx.txid());
+                }
+            }
+        }
+        let execution_cost = epoch_tx.cost_so_far();
+
+        let reason = if invalid_txids.len() > 0 {
+            Some(format!(
+                "{} transaction(s) could not be mined",
+                invalid_txids.len()
+            ))
+        } else {
+            let mined_block = builder.mine_anchored_block(&mut epoch_tx);
+            if mined_block.header.tx_merkle_root != block.header.tx_merkle_root {
+                Some("Transaction merkle root does not match".to_string())
+            } else if mined_block.header.state_index_root != block.header.state_index_root {
+                Some(format!(
+                    "State index root does not match: expected {}",
+                    &mined_block.header.state_index_root
+                ))
+            } else if mined_block.block_hash() != block.block_hash() {
+                Some("Block header does not match".to_string())
+            } else {
+                None
+            }
+        };
+
+        // don't store the block
+        epoch_tx.rollback_block();
+
+        Ok(BlockProposalValidation {
+            invalid_txids,
+            execution_cost,
+            reason,
+        })
+    }
+
     /// Given access to the mempool, mine an anchored block with no more than the given execution cost.
     ///   returns the assembled block, and the consumed execution budget.
     pub fn build_anchored_block(
@@ -2350,6 +2789,49 @@ impl StacksBlockBuilder {
         settings: BlockBuilderSettings,
         event_observer: Option<&dyn MemPoolEventDispatcher>,
     ) -> Result<(StacksBlock, ExecutionCost, u64), Error> {
+        let (block, consumed, size, _, _) = StacksBlockBuilder::build_anchored_block_resumable(
+            chainstate_handle,
+            burn_dbconn,
+            mempool,
+            parent_stacks_header,
+            total_burn,
+            proof,
+            pubkey_hash,
+            coinbase_tx,
+            settings,
+            event_observer,
+            None,
+        )?;
+        Ok((block, consumed, size))
+    }
+
+    /// Like `build_anchored_block`, but if `resume_from` was produced by an earlier attempt off of
+    /// the same parent block and microblock tail, then its transactions are carried over and the
+    /// mempool walk picks up where that attempt's walk stopped.
+    ///   returns the assembled block, the consumed execution budget, the block size, the cursor
+    ///   for the next attempt to resume from, and metrics on how the assembly went.
+    pub fn build_anchored_block_resumable(
+        chainstate_handle: &StacksChainState, // not directly used; used as a handle to open other chainstates
+        burn_dbconn: &SortitionDBConn,
+        mempool: &mut MemPoolDB,
+        parent_stacks_header: &StacksHeaderInfo, // Stacks header we're building off of
+        total_burn: u64, // the burn so far on the burnchain (i.e. from the last burnchain block)
+        proof: VRFProof, // proof over the burnchain's last seed
+        pubkey_hash: Hash160,
+        coinbase_tx: &StacksTransaction,
+        settings: BlockBuilderSettings,
+        event_observer: Option<&dyn MemPoolEventDispatcher>,
+        resume_from: Option<&BlockAssemblyCursor>,
+    ) -> Result<
+        (
+            StacksBlock,
+            ExecutionCost,
+            u64,
+            BlockAssemblyCursor,
+            MinerMetrics,
+        ),
+        Error,
+    > {
         let mempool_settings = settings.mempool_settings;
         let max_miner_time_ms = settings.max_miner_time_ms;
 
@@ -2422,6 +2904,49 @@ impl StacksBlockBuilder {
         let mut num_txs = 0;
         let mut blocked = false;
 
+        let parent_block_id = parent_stacks_header.index_block_hash();
+        let parent_microblock = builder.header.parent_microblock.clone();
+        let mut metrics = MinerMetrics::default();
+        let mut mined_txs = vec![];
+        let mut start_cursor = MemPoolWalkCursor::new();
+
+        if let Some(resume_from) = resume_from.filter(|cursor| {
+            cursor.parent_block_id == parent_block_id
+                && cursor.parent_microblock == parent_microblock
+        }) {
+            debug!(
+                "Anchored block assembly resumes from a previous attempt with {} transactions (child of {})",
+                resume_from.mined_txs.len(),
+                &parent_stacks_header.anchored_header.block_hash()
+            );
+            for tx in resume_from.mined_txs.iter() {
+                considered.insert(tx.txid());
+                let tx_result = builder.try_mine_tx_with_len(
+                    &mut epoch_tx,
+                    tx,
+                    tx.tx_len(),
+                    &block_limit_hit,
+                    ast_rules,
+                );
+                if let TransactionResult::Success(..) = tx_result {
+                    num_txs += 1;
+                    metrics.txs_resumed += 1;
+                    mined_origin_nonces.insert(tx.origin_address(), tx.get_origin_nonce());
+                    if let (Some(sponsor_addr), Some(sponsor_nonce)) =
+                        (tx.sponsor_address(), tx.get_sponsor_nonce())
+                    {
+                        mined_sponsor_nonces.insert(sponsor_addr, sponsor_nonce);
+                    }
+                    mined_txs.push(tx.clone());
+                } else {
+                    debug!("Could not carry over transaction {}", &tx.txid());
+                }
+                tx_events.push(tx_result.convert_to_event());
+            }
+            start_cursor = resume_from.mempool_cursor.clone();
+        }
+        let mut mempool_cursor = None;
+
         debug!(
             "Anchored block transaction selection begins (child of {})",
             &parent_stacks_header.anchored_header.block_hash()
@@ -2430,11 +2955,13 @@ impl StacksBlockBuilder {
             let mut intermediate_result = Ok(0);
             while block_limit_hit != BlockLimitFunction::LIMIT_REACHED {
                 let mut num_considered = 0;
-                intermediate_result = mempool.iterate_candidates(
+                let mut pass_cursor = start_cursor.clone();
+                intermediate_result = mempool.iterate_candidates_from(
                     &mut epoch_tx,
                     &mut tx_events,
                     tip_height,
                     mempool_settings.clone(),
+                    &mut pass_cursor,
                     |epoch_tx, to_consider, estimator| {
                         // first, have we been preempted?
                         blocked = (*settings.miner_status.lock().expect("FATAL: mutex poisoned"))
@@ -2502,7 +3029,9 @@ impl StacksBlockBuilder {
 
                         considered.insert(txinfo.tx.txid());
                         num_considered += 1;
+                        metrics.txs_considered += 1;
 
+                        let tx_start = get_epoch_time_ms();
                         let tx_result = builder.try_mine_tx_with_len(
                             epoch_tx,
                             &txinfo.tx,
@@ -2510,11 +3039,15 @@ impl StacksBlockBuilder {
                             &block_limit_hit,
                             ast_rules,
                         );
+                        tx_result.log_transaction_considered(
+                            get_epoch_time_ms().saturating_sub(tx_start),
+                        );
 
                         let result_event = tx_result.convert_to_event();
                         match tx_result {
                             TransactionResult::Success(TransactionSuccess { receipt, .. }) => {
                                 num_txs += 1;
+                                mined_txs.push(txinfo.tx.clone());
                                 if update_estimator {
                                     if let Err(e) = estimator.notify_event(
                                         &txinfo.tx.payload,
@@ -2586,6 +3119,12 @@ impl StacksBlockBuilder {
                     },
                 );
 
+                // later passes only revisit transactions the first pass put aside, so the first
+                // pass's cursor is how far into the mempool this attempt got
+                if mempool_cursor.is_none() {
+                    mempool_cursor = Some(pass_cursor);
+                }
+
                 if to_drop_and_blacklist.len() > 0 {
                     let _ = mempool.drop_and_blacklist_txs(&to_drop_and_blacklist);
                 }
@@ -2598,11 +3137,16 @@ impl StacksBlockBuilder {
                     break;
                 }
             }
-            debug!("Anchored block transaction selection finished (child of {}): {} transactions selected ({} considered)", &parent_stacks_header.anchored_header.block_hash(), num_txs, considered.len());
+            metrics.deadline_reached = get_epoch_time_ms() >= deadline;
+            info!("Anchored block transaction selection finished (child of {}): {} transactions selected ({} considered)", &parent_stacks_header.anchored_header.block_hash(), num_txs, considered.len();
+                   "selection_time_ms" => get_epoch_time_ms().saturating_sub(ts_start),
+                   "deadline_reached" => metrics.deadline_reached);
             intermediate_result
         };
+        metrics.txs_included = num_txs;
 
         mempool.drop_txs(&invalidated_txs)?;
+        MemPoolStats::record_too_expensive(invalidated_txs.len());
 
         if let Some(observer) = event_observer {
             observer.mempool_txs_dropped(invalidated_txs, MemPoolDropReason::TOO_EXPENSIVE);
@@ -2635,6 +3179,7 @@ impl StacksBlockBuilder {
         let consumed = builder.epoch_finish(epoch_tx);
 
         let ts_end = get_epoch_time_ms();
+        metrics.assembly_time_ms = ts_end.saturating_sub(ts_start) as u64;
 
         if let Some(observer) = event_observer {
             observer.mined_block_event(
@@ -2658,12 +3203,22 @@ impl StacksBlockBuilder {
             "block_size" => size,
             "execution_consumed" => %consumed,
             "%-full" => block_limit.proportion_largest_dimension(&consumed),
-            "assembly_time_ms" => ts_end.saturating_sub(ts_start),
+            "assembly_time_ms" => metrics.assembly_time_ms,
+            "txs_considered" => metrics.txs_considered,
+            "txs_included" => metrics.txs_included,
+            "txs_resumed" => metrics.txs_resumed,
+            "deadline_reached" => metrics.deadline_reached,
             "tx_fees_microstacks" => block.txs.iter().fold(0, |agg: u64, tx| {
                 agg.saturating_add(tx.get_tx_fee())
             })
         );
 
-        Ok((block, consumed, size))
+        let cursor = BlockAssemblyCursor {
+            parent_block_id,
+            parent_microblock,
+            mined_txs,
+            mempool_cursor: mempool_cursor.unwrap_or(start_cursor),
+        };
+        Ok((block, consumed, size, cursor, metrics))
     }
 }
@@ -90,6 +90,9 @@ pub const STACKS_BLOCK_VERSION_AST_PRECHECK_SIZE: u8 = 1;
 pub const MAX_BLOCK_LEN: u32 = 2 * 1024 * 1024;
 pub const MAX_TRANSACTION_LEN: u32 = MAX_BLOCK_LEN;
 
+/// Maximum number of STX transfers a single BatchTokenTransfer payload may carry
+pub const MAX_BATCH_TOKEN_TRANSFERS: u32 = 256;
+
 #[derive(Debug)]
 pub enum Error {
     InvalidFee,
@@ -621,6 +624,8 @@ pub enum TransactionPayload {
     SmartContract(TransactionSmartContract, Option<ClarityVersion>),
     PoisonMicroblock(StacksMicroblockHeader, StacksMicroblockHeader), // the previous epoch leader sent two microblocks with the same sequence, and this is proof
     Coinbase(CoinbasePayload, Option<PrincipalData>),
+    /// N STX transfers from the origin account, executed atomically
+    BatchTokenTransfer(Vec<(PrincipalData, u64, TokenTransferMemo)>),
 }
 
 impl TransactionPayload {
@@ -631,6 +636,7 @@ impl TransactionPayload {
             TransactionPayload::SmartContract(..) => "SmartContract",
             TransactionPayload::PoisonMicroblock(..) => "PoisonMicroblock",
             TransactionPayload::Coinbase(..) => "Coinbase",
+            TransactionPayload::BatchTokenTransfer(..) => "BatchTokenTransfer",
         }
     }
 }
@@ -645,6 +651,7 @@ pub enum TransactionPayloadID {
     Coinbase = 4,
     CoinbaseToAltRecipient = 5,
     VersionedSmartContract = 6,
+    BatchTokenTransfer = 7,
 }
 
 /// Encoding of an asset type identifier
@@ -297,6 +297,171 @@ fn test_build_anchored_blocks_stx_transfers_single() {
     }
 }
 
+#[test]
+fn test_validate_anchored_block_proposal() {
+    let privk = StacksPrivateKey::from_hex(
+        "42faca653724860da7a41bfcef7e6ba78db55146f6900de8cb2a9f760ffac70c01",
+    )
+    .unwrap();
+    let addr = StacksAddress::from_public_keys(
+        C32_ADDRESS_VERSION_TESTNET_SINGLESIG,
+        &AddressHashMode::SerializeP2PKH,
+        1,
+        &vec![StacksPublicKey::from_private(&privk)],
+    )
+    .unwrap();
+
+    let mut peer_config = TestPeerConfig::new(function_name!(), 2032, 2033);
+    peer_config.initial_balances = vec![(addr.to_account_principal(), 1000000000)];
+
+    let mut peer = TestPeer::new(peer_config);
+
+    let chainstate_path = peer.chainstate_path.clone();
+
+    let num_blocks = 5;
+    let recipient_addr_str = "ST1RFD5Q2QPK3E0F08HG9XDX7SSC7CNRS0QR0SGEV";
+    let recipient = StacksAddress::from_string(recipient_addr_str).unwrap();
+    let mut sender_nonce = 0;
+
+    for tenure_id in 0..num_blocks {
+        let tip = SortitionDB::get_canonical_burn_chain_tip(&peer.sortdb.as_ref().unwrap().conn())
+            .unwrap();
+
+        let (burn_ops, stacks_block, microblocks) = peer.make_tenure(
+            |ref mut miner,
+             ref mut sortdb,
+             ref mut chainstate,
+             vrf_proof,
+             ref parent_opt,
+             ref parent_microblock_header_opt| {
+                let parent_tip = match parent_opt {
+                    None => StacksChainState::get_genesis_header_info(chainstate.db()).unwrap(),
+                    Some(block) => {
+                        let ic = sortdb.index_conn();
+                        let snapshot = SortitionDB::get_block_snapshot_for_winning_stacks_block(
+                            &ic,
+                            &tip.sortition_id,
+                            &block.block_hash(),
+                        )
+                        .unwrap()
+                        .unwrap(); // succeeds because we don't fork
+                        StacksChainState::get_anchored_block_header_info(
+                            chainstate.db(),
+                            &snapshot.consensus_hash,
+                            &snapshot.winning_stacks_block_hash,
+                        )
+                        .unwrap()
+                        .unwrap()
+                    }
+                };
+
+                let mut mempool =
+                    MemPoolDB::open_test(false, 0x80000000, &chainstate_path).unwrap();
+
+                let coinbase_tx = make_coinbase(miner, tenure_id);
+
+                let stx_transfer = make_user_stacks_transfer(
+                    &privk,
+                    sender_nonce,
+                    200,
+                    &recipient.to_account_principal(),
+                    1,
+                );
+                sender_nonce += 1;
+
+                mempool
+                    .submit(
+                        chainstate,
+                        sortdb,
+                        &parent_tip.consensus_hash,
+                        &parent_tip.anchored_header.block_hash(),
+                        &stx_transfer,
+                        None,
+                        &ExecutionCost::max_value(),
+                        &StacksEpochId::Epoch20,
+                    )
+                    .unwrap();
+
+                let (anchored_block, anchored_cost, _) = StacksBlockBuilder::build_anchored_block(
+                    chainstate,
+                    &sortdb.index_conn(),
+                    &mut mempool,
+                    &parent_tip,
+                    tip.total_burn,
+                    vrf_proof,
+                    Hash160([tenure_id as u8; 20]),
+                    &coinbase_tx,
+                    BlockBuilderSettings::max_value(),
+                    None,
+                )
+                .unwrap();
+                assert_eq!(anchored_block.txs.len(), 2);
+
+                // the block the miner built is valid, and validating it twice gives the same
+                // answer since nothing was stored the first time
+                for _ in 0..2 {
+                    let validation = StacksBlockBuilder::validate_anchored_block_proposal(
+                        chainstate,
+                        &sortdb.index_conn(),
+                        &parent_tip,
+                        &anchored_block,
+                    )
+                    .unwrap();
+                    assert!(validation.is_valid(), "{:?}", &validation.reason);
+                    assert!(validation.invalid_txids.is_empty());
+                    assert_eq!(validation.execution_cost, anchored_cost);
+                }
+
+                // a block whose state root was not computed from its transactions is invalid
+                let mut bad_root_block = anchored_block.clone();
+                bad_root_block.header.state_index_root = TrieHash([0x11; 32]);
+                let validation = StacksBlockBuilder::validate_anchored_block_proposal(
+                    chainstate,
+                    &sortdb.index_conn(),
+                    &parent_tip,
+                    &bad_root_block,
+                )
+                .unwrap();
+                assert!(!validation.is_valid());
+                assert!(validation.invalid_txids.is_empty());
+
+                // a block with a transaction that can't be mined names that transaction
+                let bad_nonce_tx = make_user_stacks_transfer(
+                    &privk,
+                    sender_nonce + 10,
+                    200,
+                    &recipient.to_account_principal(),
+                    1,
+                );
+                let mut bad_tx_block = anchored_block.clone();
+                bad_tx_block.txs[1] = bad_nonce_tx.clone();
+                let txid_vecs = bad_tx_block
+                    .txs
+                    .iter()
+                    .map(|tx| tx.txid().as_bytes().to_vec())
+                    .collect();
+                bad_tx_block.header.tx_merkle_root =
+                    MerkleTree::<Sha512Trunc256Sum>::new(&txid_vecs).root();
+                let validation = StacksBlockBuilder::validate_anchored_block_proposal(
+                    chainstate,
+                    &sortdb.index_conn(),
+                    &parent_tip,
+                    &bad_tx_block,
+                )
+                .unwrap();
+                assert!(!validation.is_valid());
+                assert_eq!(validation.invalid_txids, vec![bad_nonce_tx.txid()]);
+
+                (anchored_block, vec![])
+            },
+        );
+
+        // the validated block can still be processed
+        peer.next_burnchain_block(burn_ops.clone());
+        peer.process_stacks_epoch_at_tip(&stacks_block, &microblocks);
+    }
+}
+
 #[test]
 fn test_build_anchored_blocks_empty_with_builder_timeout() {
     let privk = StacksPrivateKey::from_hex(
@@ -120,6 +120,7 @@ fn ClarityVersion_consensus_serialize<W: Write>(
     match *version {
         ClarityVersion::Clarity1 => write_next(fd, &1u8)?,
         ClarityVersion::Clarity2 => write_next(fd, &2u8)?,
+        ClarityVersion::Clarity3 => write_next(fd, &3u8)?,
     }
     Ok(())
 }
@@ -131,6 +132,7 @@ fn ClarityVersion_consensus_deserialize<R: Read>(
     match version_byte {
         1u8 => Ok(ClarityVersion::Clarity1),
         2u8 => Ok(ClarityVersion::Clarity2),
+        3u8 => Ok(ClarityVersion::Clarity3),
         _ => Err(codec_error::DeserializeError(format!(
             "Unrecognized ClarityVersion byte {}",
             &version_byte
@@ -182,6 +184,22 @@ impl StacksMessageCodec for TransactionPayload {
                     }
                 }
             }
+            TransactionPayload::BatchTokenTransfer(ref transfers) => {
+                if transfers.is_empty() || transfers.len() > MAX_BATCH_TOKEN_TRANSFERS as usize {
+                    return Err(codec_error::SerializeError(format!(
+                        "Failed to serialize transaction -- batch of {} STX transfers is not between 1 and {}",
+                        transfers.len(),
+                        MAX_BATCH_TOKEN_TRANSFERS
+                    )));
+                }
+                write_next(fd, &(TransactionPayloadID::BatchTokenTransfer as u8))?;
+                write_next(fd, &(transfers.len() as u32))?;
+                for (address, amount, memo) in transfers.iter() {
+                    write_next(fd, address)?;
+                    write_next(fd, amount)?;
+                    write_next(fd, memo)?;
+                }
+            }
         }
         Ok(())
     }
@@ -245,6 +263,23 @@ impl StacksMessageCodec for TransactionPayload {
 
                 TransactionPayload::Coinbase(payload, Some(recipient))
             }
+            x if x == TransactionPayloadID::BatchTokenTransfer as u8 => {
+                let len: u32 = read_next(fd)?;
+                if len == 0 || len > MAX_BATCH_TOKEN_TRANSFERS {
+                    return Err(codec_error::DeserializeError(format!(
+                        "Failed to parse transaction -- batch of {} STX transfers is not between 1 and {}",
+                        len, MAX_BATCH_TOKEN_TRANSFERS
+                    )));
+                }
+                let mut transfers = Vec::with_capacity(len as usize);
+                for _ in 0..len {
+                    let principal = read_next(fd)?;
+                    let amount = read_next(fd)?;
+                    let memo = read_next(fd)?;
+                    transfers.push((principal, amount, memo));
+                }
+                TransactionPayload::BatchTokenTransfer(transfers)
+            }
             _ => {
                 return Err(codec_error::DeserializeError(format!(
                     "Failed to parse transaction -- unknown payload ID {}",
@@ -1542,6 +1577,11 @@ mod test {
                 let corrupt_buf = CoinbasePayload(corrupt_buf_bytes);
                 TransactionPayload::Coinbase(corrupt_buf, recipient_opt.clone())
             }
+            TransactionPayload::BatchTokenTransfer(ref transfers) => {
+                let mut corrupt_transfers = transfers.clone();
+                corrupt_transfers[0].1 += 1;
+                TransactionPayload::BatchTokenTransfer(corrupt_transfers)
+            }
         };
         assert!(corrupt_tx_payload.txid() != signed_tx.txid());
 
@@ -1654,6 +1694,78 @@ mod test {
         check_codec_and_corruption::<TransactionPayload>(&tt_stx, &tt_stx_bytes);
     }
 
+    #[test]
+    fn tx_stacks_transaction_payload_batch_tokens() {
+        let addr_1 = PrincipalData::from(StacksAddress {
+            version: 1,
+            bytes: Hash160([0xff; 20]),
+        });
+        let addr_2 = PrincipalData::from(QualifiedContractIdentifier {
+            issuer: StacksAddress {
+                version: 1,
+                bytes: Hash160([0xfe; 20]),
+            }
+            .into(),
+            name: "foo-contract".into(),
+        });
+
+        let batch_stx = TransactionPayload::BatchTokenTransfer(vec![
+            (addr_1.clone(), 123, TokenTransferMemo([1u8; 34])),
+            (addr_2.clone(), 456, TokenTransferMemo([2u8; 34])),
+        ]);
+
+        // wire encodings of the same
+        let mut batch_stx_bytes = vec![];
+        batch_stx_bytes.push(TransactionPayloadID::BatchTokenTransfer as u8);
+        batch_stx_bytes.append(&mut vec![0x00, 0x00, 0x00, 0x02]);
+        addr_1.consensus_serialize(&mut batch_stx_bytes).unwrap();
+        batch_stx_bytes.append(&mut vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 123]);
+        batch_stx_bytes.append(&mut vec![1u8; 34]);
+        addr_2.consensus_serialize(&mut batch_stx_bytes).unwrap();
+        batch_stx_bytes.append(&mut vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0xc8]);
+        batch_stx_bytes.append(&mut vec![2u8; 34]);
+
+        check_codec_and_corruption::<TransactionPayload>(&batch_stx, &batch_stx_bytes);
+
+        // empty batches are not allowed
+        let empty_batch_bytes = vec![
+            TransactionPayloadID::BatchTokenTransfer as u8,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+        ];
+        assert!(TransactionPayload::consensus_deserialize(&mut &empty_batch_bytes[..]).is_err());
+
+        // neither are oversized batches
+        let mut oversized_batch_bytes = vec![TransactionPayloadID::BatchTokenTransfer as u8];
+        oversized_batch_bytes.append(&mut (MAX_BATCH_TOKEN_TRANSFERS + 1).to_be_bytes().to_vec());
+        for _ in 0..(MAX_BATCH_TOKEN_TRANSFERS + 1) {
+            addr_1
+                .consensus_serialize(&mut oversized_batch_bytes)
+                .unwrap();
+            oversized_batch_bytes.append(&mut vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 1]);
+            oversized_batch_bytes.append(&mut vec![1u8; 34]);
+        }
+        assert!(
+            TransactionPayload::consensus_deserialize(&mut &oversized_batch_bytes[..]).is_err()
+        );
+
+        // and neither can be serialized
+        let mut bytes = vec![];
+        assert!(TransactionPayload::BatchTokenTransfer(vec![])
+            .consensus_serialize(&mut bytes)
+            .is_err());
+        let transfer = (addr_1.clone(), 1, TokenTransferMemo([1u8; 34]));
+        let oversized_batch =
+            TransactionPayload::BatchTokenTransfer(vec![
+                transfer;
+                MAX_BATCH_TOKEN_TRANSFERS as usize + 1
+            ]);
+        assert!(oversized_batch.consensus_serialize(&mut bytes).is_err());
+        assert!(bytes.is_empty());
+    }
+
     #[test]
     fn tx_stacks_transaction_payload_contracts() {
         let hello_contract_call = "hello-contract-call";
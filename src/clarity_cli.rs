@@ -54,6 +54,7 @@ use crate::clarity::{
     },
     vm::errors::{Error, InterpreterResult, RuntimeErrorType},
     vm::eval_all,
+    vm::fuzz::ExpressionGenerator,
     vm::types::{OptionalData, PrincipalData, QualifiedContractIdentifier},
     vm::ClarityVersion,
     vm::ContractContext,
@@ -1928,6 +1929,134 @@ pub fn invoke_command(invoked_by: &str, args: &[String]) -> (i32, Option<serde_j
     }
 }
 
+#[cfg_attr(tarpaulin, skip)]
+fn print_fuzzer_usage(invoked_by: &str) {
+    eprintln!(
+        "Usage: {} [--testnet] [--seed SEED] [--depth DEPTH] [--count COUNT] [--output FILE]
+
+Generates COUNT random, well-typed Clarity expressions of at most DEPTH nested calls from
+the given SEED, evaluates each one against a scratch VM state with the cost contracts installed,
+and writes a CSV row of the execution cost of each expression to FILE (or stdout).
+",
+        invoked_by
+    );
+    panic_test!()
+}
+
+fn consume_numeric_arg(args: &mut Vec<String>, argname: &str) -> Option<u64> {
+    friendly_expect(
+        consume_arg(args, &[argname], true),
+        &format!("Expected a value for {}", argname),
+    )
+    .map(|value| {
+        friendly_expect(
+            value.parse::<u64>(),
+            &format!("Failed to parse value for {}", argname),
+        )
+    })
+}
+
+/// Entry point for the `clarity-fuzzer` binary.  Evaluates randomly-generated Clarity
+/// expressions with cost tracking enabled, and emits their execution costs as CSV.
+pub fn invoke_fuzzer(invoked_by: &str, args: &[String]) -> i32 {
+    let mut argv: Vec<String> = args.into_iter().map(|x| x.clone()).collect();
+
+    let mainnet = if let Ok(Some(_)) = consume_arg(&mut argv, &["--testnet"], false) {
+        false
+    } else {
+        true
+    };
+    let seed = consume_numeric_arg(&mut argv, "--seed").unwrap_or_else(|| rand::thread_rng().gen());
+    let depth = consume_numeric_arg(&mut argv, "--depth").unwrap_or(4);
+    let count = consume_numeric_arg(&mut argv, "--count").unwrap_or(100);
+    let output_path = friendly_expect(
+        consume_arg(&mut argv, &["--output"], true),
+        "Expected a value for --output",
+    );
+
+    if argv.len() > 0 {
+        print_fuzzer_usage(invoked_by);
+        return 1;
+    }
+
+    let mut output: Box<dyn Write> = match output_path {
+        Some(path) => Box::new(friendly_expect(
+            fs::File::create(&path),
+            &format!("Failed to create {}", &path),
+        )),
+        None => Box::new(io::stdout()),
+    };
+
+    // report the seed so that any run can be reproduced
+    eprintln!("Fuzzing {} expressions with seed {}", count, seed);
+
+    let header_db = CLIHeadersDB::new_memory(mainnet);
+    let marf_kv = MarfedKV::temporary();
+    let (header_db, marf_kv, _) = in_block(header_db, marf_kv, |header_db, mut marf| {
+        install_boot_code(&header_db, &mut marf);
+        (header_db, marf, ())
+    });
+
+    let clarity_version = ClarityVersion::default_for_epoch(DEFAULT_CLI_EPOCH);
+    let mut generator = ExpressionGenerator::new(
+        seed,
+        u32::try_from(depth).unwrap_or(u32::MAX),
+        clarity_version,
+    );
+    let mut placeholder_context =
+        ContractContext::new(QualifiedContractIdentifier::transient(), clarity_version);
+
+    friendly_expect(
+        writeln!(
+            output,
+            "expression_hash,runtime,read_count,write_count,read_len,write_len"
+        ),
+        "Failed to write CSV header",
+    );
+
+    let (_, _, num_failed) = in_block(header_db, marf_kv, |header_db, mut marf| {
+        let mut num_failed = 0;
+        for _ in 0..count {
+            let (expr, _) = generator.generate();
+            let expr_hash = Sha512Trunc256Sum::from_data(expr.as_bytes()).to_hex();
+            let (result, cost) = with_env_costs(mainnet, &header_db, &mut marf, None, |vm_env| {
+                vm_env
+                    .get_exec_environment(None, None, &mut placeholder_context)
+                    .eval_raw_with_rules(&expr, ASTRules::PrecheckSize)
+            });
+            match result {
+                Ok(value) => debug!("{}: {} => {}", &expr_hash, &expr, &value),
+                Err(e) => {
+                    // well-typed expressions can still fail at runtime (e.g. on overflow), but
+                    // the cost incurred up to the failure is still worth recording.
+                    debug!("{}: {} => {:?}", &expr_hash, &expr, &e);
+                    num_failed += 1;
+                }
+            }
+            friendly_expect(
+                writeln!(
+                    output,
+                    "{},{},{},{},{},{}",
+                    &expr_hash,
+                    cost.runtime,
+                    cost.read_count,
+                    cost.write_count,
+                    cost.read_len,
+                    cost.write_len
+                ),
+                "Failed to write CSV row",
+            );
+        }
+        (header_db, marf, num_failed)
+    });
+
+    eprintln!(
+        "Evaluated {} expressions ({} failed at runtime)",
+        count, num_failed
+    );
+    0
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -2389,4 +2518,36 @@ mod test {
                 })
         );
     }
+
+    #[test]
+    fn test_fuzzer_costs_csv() {
+        let csv_name = format!("/tmp/fuzz-costs_{}.csv", rand::thread_rng().gen::<i32>());
+        let args: Vec<String> = ["--seed", "7", "--depth", "3", "--count", "10", "--output"]
+            .iter()
+            .map(|s| s.to_string())
+            .chain(std::iter::once(csv_name.clone()))
+            .collect();
+
+        assert_eq!(invoke_fuzzer("test", &args), 0);
+        let first_run = fs::read_to_string(&csv_name).unwrap();
+
+        let mut lines = first_run.lines();
+        assert_eq!(
+            lines.next().unwrap(),
+            "expression_hash,runtime,read_count,write_count,read_len,write_len"
+        );
+        let rows: Vec<_> = lines.collect();
+        assert_eq!(rows.len(), 10);
+        for row in rows.iter() {
+            let fields: Vec<_> = row.split(',').collect();
+            assert_eq!(fields.len(), 6);
+            assert_eq!(fields[0].len(), 64);
+            // every expression does at least some work
+            assert!(fields[1].parse::<u64>().unwrap() > 0);
+        }
+
+        // the same seed reproduces the same expressions and costs
+        assert_eq!(invoke_fuzzer("test", &args), 0);
+        assert_eq!(fs::read_to_string(&csv_name).unwrap(), first_run);
+    }
 }
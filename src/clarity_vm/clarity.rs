@@ -14,6 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::collections::BTreeMap;
 use std::convert::TryFrom;
 use std::error;
 use std::fmt;
@@ -25,9 +26,9 @@ use crate::chainstate::stacks::boot::POX_2_TESTNET_CODE;
 use crate::chainstate::stacks::boot::POX_3_MAINNET_CODE;
 use crate::chainstate::stacks::boot::POX_3_TESTNET_CODE;
 use crate::chainstate::stacks::boot::{
-    BOOT_CODE_COSTS, BOOT_CODE_COSTS_2, BOOT_CODE_COSTS_3,
+    BOOT_CODE_COSTS, BOOT_CODE_COSTS_2, BOOT_CODE_COSTS_3, BOOT_CODE_COSTS_4,
     BOOT_CODE_COST_VOTING_TESTNET as BOOT_CODE_COST_VOTING, BOOT_CODE_POX_TESTNET, COSTS_2_NAME,
-    COSTS_3_NAME, POX_2_NAME, POX_3_NAME,
+    COSTS_3_NAME, COSTS_4_NAME, POX_2_NAME, POX_3_NAME,
 };
 use crate::chainstate::stacks::db::StacksAccount;
 use crate::chainstate::stacks::db::StacksChainState;
@@ -65,7 +66,7 @@ use clarity::vm::analysis::{errors::CheckError, errors::CheckErrors, ContractAna
 use clarity::vm::ast;
 use clarity::vm::ast::{errors::ParseError, errors::ParseErrors, ASTRules, ContractAST};
 use clarity::vm::contexts::{AssetMap, Environment, OwnedEnvironment};
-use clarity::vm::costs::{CostTracker, ExecutionCost, LimitedCostTracker};
+use clarity::vm::costs::{CallFrameCost, CostTracker, ExecutionCost, LimitedCostTracker};
 use clarity::vm::database::{
     BurnStateDB, ClarityDatabase, HeadersDB, RollbackWrapper, RollbackWrapperPersistedLog,
     STXBalance, SqliteConnection, NULL_BURN_STATE_DB, NULL_HEADER_DB,
@@ -113,6 +114,9 @@ pub struct ClarityInstance {
     datastore: MarfedKV,
     mainnet: bool,
     chain_id: u32,
+    /// if true, block and unconfirmed-state cost trackers record the cost charged by each
+    ///  Clarity cost function
+    record_cost_functions: bool,
 }
 
 ///
@@ -253,9 +257,22 @@ impl ClarityInstance {
             datastore,
             mainnet,
             chain_id,
+            record_cost_functions: false,
         }
     }
 
+    /// Make the cost trackers of subsequently-opened blocks record the cost charged by each
+    ///  Clarity cost function. This does not affect the costs that are charged.
+    pub fn set_record_cost_functions(&mut self, record: bool) {
+        self.record_cost_functions = record;
+    }
+
+    /// Set whether or not contracts published in subsequently-opened blocks keep their source
+    ///  code in the Clarity metadata store. The source is not consensus-critical.
+    pub fn set_store_contract_src(&mut self, store_contract_src: bool) {
+        self.datastore.set_store_contract_src(store_contract_src);
+    }
+
     pub fn with_marf<F, R>(&mut self, f: F) -> R
     where
         F: FnOnce(&mut MARF<StacksBlockId>) -> R,
@@ -303,7 +320,7 @@ impl ClarityInstance {
         let mut datastore = self.datastore.begin(current, next);
 
         let epoch = Self::get_epoch_of(current, header_db, burn_state_db);
-        let cost_track = {
+        let mut cost_track = {
             let mut clarity_db = datastore.as_clarity_db(&NULL_HEADER_DB, &NULL_BURN_STATE_DB);
             Some(
                 LimitedCostTracker::new(
@@ -316,6 +333,9 @@ impl ClarityInstance {
                 .expect("FAIL: problem instantiating cost tracking"),
             )
         };
+        if let Some(ref mut track) = cost_track {
+            track.set_record_cost_functions(self.record_cost_functions);
+        }
 
         ClarityBlockConnection {
             datastore,
@@ -556,7 +576,7 @@ impl ClarityInstance {
 
         let epoch = Self::get_epoch_of(current, header_db, burn_state_db);
 
-        let cost_track = {
+        let mut cost_track = {
             let mut clarity_db = datastore.as_clarity_db(&NULL_HEADER_DB, &NULL_BURN_STATE_DB);
             Some(
                 LimitedCostTracker::new(
@@ -569,6 +589,9 @@ impl ClarityInstance {
                 .expect("FAIL: problem instantiating cost tracking"),
             )
         };
+        if let Some(ref mut track) = cost_track {
+            track.set_record_cost_functions(self.record_cost_functions);
+        }
 
         ClarityBlockConnection {
             datastore,
@@ -1277,6 +1300,102 @@ impl<'a, 'b> ClarityBlockConnection<'a, 'b> {
         })
     }
 
+    pub fn initialize_epoch_2_5(&mut self) -> Result<Vec<StacksTransactionReceipt>, Error> {
+        // use the `using!` statement to ensure that the old cost_tracker is placed
+        //  back in all branches after initialization
+        using!(self.cost_track, "cost tracker", |old_cost_tracker| {
+            // epoch initialization is *free*.
+            // NOTE: this also means that cost functions won't be evaluated.
+            self.cost_track.replace(LimitedCostTracker::new_free());
+            self.epoch = StacksEpochId::Epoch25;
+            self.as_transaction(|tx_conn| {
+                // bump the epoch in the Clarity DB
+                tx_conn
+                    .with_clarity_db(|db| {
+                        db.set_clarity_epoch_version(StacksEpochId::Epoch25);
+                        Ok(())
+                    })
+                    .unwrap();
+
+                // require 2.5 rules henceforth in this connection as well
+                tx_conn.epoch = StacksEpochId::Epoch25;
+            });
+
+            /////////////////// .costs-4 ////////////////////////
+            let mainnet = self.mainnet;
+
+            // get tx_version & boot code account information for costs-4 contract init
+            let tx_version = if mainnet {
+                TransactionVersion::Mainnet
+            } else {
+                TransactionVersion::Testnet
+            };
+
+            let boot_code_address = boot_code_addr(mainnet);
+
+            let boot_code_auth = TransactionAuth::Standard(
+                TransactionSpendingCondition::Singlesig(SinglesigSpendingCondition {
+                    signer: boot_code_address.bytes.clone(),
+                    hash_mode: SinglesigHashMode::P2PKH,
+                    key_encoding: TransactionPublicKeyEncoding::Uncompressed,
+                    nonce: 0,
+                    tx_fee: 0,
+                    signature: MessageSignature::empty(),
+                }),
+            );
+
+            let boot_code_nonce = self.with_clarity_db_readonly(|db| {
+                db.get_account_nonce(&boot_code_address.clone().into())
+            });
+
+            let boot_code_account = StacksAccount {
+                principal: PrincipalData::Standard(boot_code_address.into()),
+                nonce: boot_code_nonce,
+                stx_balance: STXBalance::zero(),
+            };
+
+            let payload = TransactionPayload::SmartContract(
+                TransactionSmartContract {
+                    name: ContractName::try_from(COSTS_4_NAME)
+                        .expect("FATAL: invalid boot-code contract name"),
+                    code_body: StacksString::from_str(BOOT_CODE_COSTS_4)
+                        .expect("FATAL: invalid boot code body"),
+                },
+                Some(ClarityVersion::Clarity2),
+            );
+
+            let costs_4_contract_tx =
+                StacksTransaction::new(tx_version.clone(), boot_code_auth.clone(), payload);
+
+            let costs_4_initialization_receipt = self.as_transaction(|tx_conn| {
+                // initialize with a synthetic transaction
+                debug!("Instantiate .costs-4 contract");
+                let receipt = StacksChainState::process_transaction_payload(
+                    tx_conn,
+                    &costs_4_contract_tx,
+                    &boot_code_account,
+                    ASTRules::PrecheckSize,
+                )
+                .expect("FATAL: Failed to process costs-4 contract initialization");
+
+                receipt
+            });
+
+            if costs_4_initialization_receipt.result != Value::okay_true()
+                || costs_4_initialization_receipt.post_condition_aborted
+            {
+                panic!(
+                    "FATAL: Failure processing Costs 4 contract initialization: {:#?}",
+                    &costs_4_initialization_receipt
+                );
+            }
+
+            debug!("Epoch 2.5 initialized");
+
+            (old_cost_tracker, Ok(vec![costs_4_initialization_receipt]))
+        })
+    }
+
     pub fn start_transaction_processing<'c>(&'c mut self) -> ClarityTransactionConnection<'c, 'a> {
         let store = &mut self.datastore;
         let cost_track = &mut self.cost_track;
@@ -1510,6 +1629,23 @@ impl<'a, 'b> ClarityTransactionConnection<'a, 'b> {
         }
     }
 
+    /// Remove and return the costs of the `contract-call?` frames evaluated so far.
+    pub fn take_call_frame_costs(&mut self) -> Vec<CallFrameCost> {
+        match self.cost_track {
+            Some(ref mut track) => track.take_call_frame_costs(),
+            None => vec![],
+        }
+    }
+
+    /// Remove and return the cost charged by each Clarity cost function so far, if the cost
+    ///  tracker is recording them.
+    pub fn take_cost_function_costs(&mut self) -> Option<BTreeMap<String, ExecutionCost>> {
+        match self.cost_track {
+            Some(ref mut track) => track.take_cost_function_costs(),
+            None => None,
+        }
+    }
+
     /// Evaluate a poison-microblock transaction
     pub fn run_poison_microblock(
         &mut self,
@@ -2112,6 +2248,108 @@ mod tests {
         );
     }
 
+    #[test]
+    pub fn test_store_contract_src() {
+        let marf = MarfedKV::temporary();
+        let mut clarity_instance = ClarityInstance::new(false, CHAIN_ID_TESTNET, marf);
+        let contract = "(define-public (foo (x int) (y int)) (ok (+ x y)))";
+
+        clarity_instance
+            .begin_test_genesis_block(
+                &StacksBlockId::sentinel(),
+                &StacksBlockId([0 as u8; 32]),
+                &TEST_HEADER_DB,
+                &TEST_BURN_STATE_DB,
+            )
+            .commit_block();
+
+        // contracts published while the source is not stored are still published
+        clarity_instance.set_store_contract_src(false);
+        let dropped_identifier = QualifiedContractIdentifier::local("dropped").unwrap();
+        {
+            let mut conn = clarity_instance.begin_block(
+                &StacksBlockId([0 as u8; 32]),
+                &StacksBlockId([1 as u8; 32]),
+                &TEST_HEADER_DB,
+                &TEST_BURN_STATE_DB,
+            );
+
+            conn.as_transaction(|conn| {
+                let (ct_ast, ct_analysis) = conn
+                    .analyze_smart_contract(
+                        &dropped_identifier,
+                        ClarityVersion::Clarity1,
+                        &contract,
+                        ASTRules::PrecheckSize,
+                    )
+                    .unwrap();
+                conn.initialize_smart_contract(
+                    &dropped_identifier,
+                    ClarityVersion::Clarity1,
+                    &ct_ast,
+                    &contract,
+                    None,
+                    |_, _| false,
+                )
+                .unwrap();
+                conn.save_analysis(&dropped_identifier, &ct_analysis)
+                    .unwrap();
+            });
+
+            conn.as_transaction(|conn| {
+                conn.with_clarity_db_readonly(|ref mut db| {
+                    assert!(db.has_contract(&dropped_identifier));
+                    assert!(db.get_contract_src(&dropped_identifier).is_none());
+                });
+            });
+
+            conn.commit_block();
+        }
+
+        // turning it back on only affects contracts published afterwards
+        clarity_instance.set_store_contract_src(true);
+        let stored_identifier = QualifiedContractIdentifier::local("stored").unwrap();
+        {
+            let mut conn = clarity_instance.begin_block(
+                &StacksBlockId([1 as u8; 32]),
+                &StacksBlockId([2 as u8; 32]),
+                &TEST_HEADER_DB,
+                &TEST_BURN_STATE_DB,
+            );
+
+            conn.as_transaction(|conn| {
+                let (ct_ast, ct_analysis) = conn
+                    .analyze_smart_contract(
+                        &stored_identifier,
+                        ClarityVersion::Clarity1,
+                        &contract,
+                        ASTRules::PrecheckSize,
+                    )
+                    .unwrap();
+                conn.initialize_smart_contract(
+                    &stored_identifier,
+                    ClarityVersion::Clarity1,
+                    &ct_ast,
+                    &contract,
+                    None,
+                    |_, _| false,
+                )
+                .unwrap();
+                conn.save_analysis(&stored_identifier, &ct_analysis)
+                    .unwrap();
+            });
+
+            conn.as_transaction(|conn| {
+                conn.with_clarity_db_readonly(|ref mut db| {
+                    assert!(db.get_contract_src(&dropped_identifier).is_none());
+                    assert_eq!(db.get_contract_src(&stored_identifier).unwrap(), contract);
+                });
+            });
+
+            conn.commit_block();
+        }
+    }
+
     #[test]
     pub fn test_tx_roll_backs() {
         let marf = MarfedKV::temporary();
@@ -34,6 +34,8 @@ use crate::util_lib::db::Error as DatabaseError;
 pub struct MarfedKV {
     chain_tip: StacksBlockId,
     marf: MARF<StacksBlockId>,
+    /// whether or not published contracts' source code is kept in their metadata
+    store_contract_src: bool,
 }
 
 impl MarfedKV {
@@ -91,7 +93,44 @@ impl MarfedKV {
             None => StacksBlockId::sentinel(),
         };
 
-        Ok(MarfedKV { marf, chain_tip })
+        Ok(MarfedKV {
+            marf,
+            chain_tip,
+            store_contract_src: true,
+        })
+    }
+
+    /// Open an existing Clarity MARF without write access.  Unlike `open()`, this neither creates
+    /// the MARF nor initializes its schema.
+    pub fn open_readonly(
+        path_str: &str,
+        miner_tip: Option<&StacksBlockId>,
+        marf_opts: Option<MARFOpenOpts>,
+    ) -> InterpreterResult<MarfedKV> {
+        let mut path = PathBuf::from(path_str);
+        path.push("marf.sqlite");
+        let marf_path = path
+            .to_str()
+            .ok_or_else(|| InterpreterError::BadFileName)?
+            .to_string();
+
+        let mut marf_opts = marf_opts.unwrap_or(MARFOpenOpts::default());
+        marf_opts.external_blobs = true;
+
+        let marf: MARF<StacksBlockId> = MARF::from_path_readonly(&marf_path, marf_opts)
+            .map_err(|err| InterpreterError::MarfFailure(err.to_string()))?;
+        SqliteConnection::check_schema(&marf.sqlite_conn())?;
+
+        let chain_tip = match miner_tip {
+            Some(ref miner_tip) => *miner_tip.clone(),
+            None => StacksBlockId::sentinel(),
+        };
+
+        Ok(MarfedKV {
+            marf,
+            chain_tip,
+            store_contract_src: true,
+        })
     }
 
     pub fn open_unconfirmed(
@@ -105,7 +144,11 @@ impl MarfedKV {
             None => StacksBlockId::sentinel(),
         };
 
-        Ok(MarfedKV { marf, chain_tip })
+        Ok(MarfedKV {
+            marf,
+            chain_tip,
+            store_contract_src: true,
+        })
     }
 
     // used by benchmarks
@@ -135,7 +178,11 @@ impl MarfedKV {
 
         let chain_tip = StacksBlockId::sentinel();
 
-        MarfedKV { marf, chain_tip }
+        MarfedKV {
+            marf,
+            chain_tip,
+            store_contract_src: true,
+        }
     }
 
     pub fn begin_read_only<'a>(
@@ -213,6 +260,7 @@ impl MarfedKV {
         WritableMarfStore {
             chain_tip,
             marf: tx,
+            store_contract_src: self.store_contract_src,
         }
     }
 
@@ -234,6 +282,7 @@ impl MarfedKV {
         WritableMarfStore {
             chain_tip,
             marf: tx,
+            store_contract_src: self.store_contract_src,
         }
     }
 
@@ -241,6 +290,11 @@ impl MarfedKV {
         &self.chain_tip
     }
 
+    /// Set whether or not contracts published in blocks begun from here keep their source code
+    pub fn set_store_contract_src(&mut self, store_contract_src: bool) {
+        self.store_contract_src = store_contract_src;
+    }
+
     pub fn get_marf(&mut self) -> &mut MARF<StacksBlockId> {
         &mut self.marf
     }
@@ -261,6 +315,7 @@ impl MarfedKV {
 pub struct WritableMarfStore<'a> {
     chain_tip: StacksBlockId,
     marf: MarfTransaction<'a, StacksBlockId>,
+    store_contract_src: bool,
 }
 
 pub struct ReadOnlyMarfStore<'a> {
@@ -544,6 +599,10 @@ impl<'a> ClarityBackingStore for WritableMarfStore<'a> {
         Some(&handle_contract_call_special_cases)
     }
 
+    fn store_contract_src(&self) -> bool {
+        self.store_contract_src
+    }
+
     fn get(&mut self, key: &str) -> Option<String> {
         trace!("MarfedKV get: {:?} tip={}", key, &self.chain_tip);
         self.marf
@@ -25,6 +25,7 @@ use rstest::rstest;
 use rstest_reuse::{self, *};
 
 use crate::chainstate::burn::BlockSnapshot;
+use crate::chainstate::stacks::address::PoxAddress;
 use crate::clarity_vm::clarity::ClarityBlockConnection;
 use clarity::vm::ast;
 use clarity::vm::ast::errors::ParseErrors;
@@ -185,6 +186,79 @@ fn test_get_burn_block_info_eval() {
     });
 }
 
+#[test]
+fn test_get_burn_block_info_pox_addrs_eval() {
+    let mut sim = ClarityTestSim::new();
+    sim.epoch_bounds = vec![0, 2, 4];
+
+    // Advance to Stacks 2.1, where 'get-burn-block-info?' is available.
+    sim.execute_next_block(|_env| {});
+    sim.execute_next_block(|_env| {});
+    sim.execute_next_block(|_env| {});
+    sim.execute_next_block_as_conn(|conn| {
+        let contract_identifier = QualifiedContractIdentifier::local("test-contract").unwrap();
+        let contract =
+            "(define-private (test-func (height uint)) (get-burn-block-info? pox-addrs height))";
+        let epoch = conn.get_epoch();
+        conn.as_transaction(|clarity_db| {
+            let clarity_version = ClarityVersion::default_for_epoch(epoch);
+            let (ast, analysis) = clarity_db
+                .analyze_smart_contract(
+                    &contract_identifier,
+                    clarity_version,
+                    contract,
+                    ASTRules::PrecheckSize,
+                )
+                .unwrap();
+            clarity_db
+                .initialize_smart_contract(
+                    &contract_identifier,
+                    clarity_version,
+                    &ast,
+                    contract,
+                    None,
+                    |_, _| false,
+                )
+                .unwrap();
+        });
+        // This relies on `TestSimBurnStateDB::get_pox_payout_addrs`, which pays 123 to one or
+        // two burn addresses at each burnchain height it knows a header hash for.
+        let mut tx = conn.start_transaction_processing();
+        assert_eq!(
+            Value::UInt(123),
+            tx.eval_read_only(
+                &contract_identifier,
+                "(get payout (unwrap-panic (test-func u0)))"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            Value::Tuple(
+                PoxAddress::standard_burn_address(false)
+                    .as_clarity_tuple()
+                    .unwrap()
+            ),
+            tx.eval_read_only(
+                &contract_identifier,
+                "(unwrap-panic (element-at (get addrs (unwrap-panic (test-func u1))) u0))"
+            )
+            .unwrap()
+        );
+        // burnchain is 100 blocks ahead of stacks chain in this sim
+        assert_eq!(
+            Value::none(),
+            tx.eval_read_only(&contract_identifier, "(test-func u103)")
+                .unwrap()
+        );
+        // heights that do not fit in a u32 are never reached
+        assert_eq!(
+            Value::none(),
+            tx.eval_read_only(&contract_identifier, "(test-func u4294967296)")
+                .unwrap()
+        );
+    });
+}
+
 #[test]
 fn test_get_block_info_eval_v210() {
     let mut sim = ClarityTestSim::new();
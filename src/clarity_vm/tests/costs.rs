@@ -169,6 +169,8 @@ pub fn get_simple_test(function: &NativeFunctions) -> &'static str {
         ToConsensusBuff => "(to-consensus-buff? u1)",
         FromConsensusBuff => "(from-consensus-buff? bool 0x03)",
         ReplaceAt => "(replace-at? list-bar u0 5)",
+        GetStackingState => "(get-stacking-state 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
+        PrincipalVersion => "(principal-version 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
     }
 }
 
@@ -220,9 +222,18 @@ where
         tip = next_block.clone();
     }
 
+    if epoch >= StacksEpochId::Epoch25 {
+        let next_block = StacksBlockId([3 as u8; 32]);
+        let mut clarity_conn =
+            clarity_instance.begin_block(&tip, &next_block, &TEST_HEADER_DB, &TEST_BURN_STATE_DB);
+        clarity_conn.initialize_epoch_2_5().unwrap();
+        clarity_conn.commit_block();
+        tip = next_block.clone();
+    }
+
     let mut marf_kv = clarity_instance.destroy();
 
-    let mut store = marf_kv.begin(&tip, &StacksBlockId([3 as u8; 32]));
+    let mut store = marf_kv.begin(&tip, &StacksBlockId([4 as u8; 32]));
 
     to_do(OwnedEnvironment::new_max_limit(
         store.as_clarity_db(&TEST_HEADER_DB, &TEST_BURN_STATE_DB),
@@ -999,25 +1010,352 @@ fn epoch_21_test_all(use_mainnet: bool) {
 
     for f in NativeFunctions::ALL.iter() {
         // Note: Include Clarity2 functions for Epoch21.
+        if f.get_version() <= ClarityVersion::Clarity2 {
+            let test = get_simple_test(f);
+            let cost = test_tracked_costs(
+                test,
+                use_mainnet,
+                StacksEpochId::Epoch21,
+                ClarityVersion::Clarity2,
+            );
+            assert!(cost.exceeds(&baseline));
+        }
+    }
+}
+
+#[test]
+fn epoch_21_test_all_mainnet() {
+    epoch_21_test_all(true)
+}
+
+#[test]
+fn epoch_21_test_all_testnet() {
+    epoch_21_test_all(false)
+}
+
+// test each individual cost function can be correctly invoked as
+//  Clarity code executes in Epoch 2.5
+fn epoch_25_test_all(use_mainnet: bool) {
+    let baseline = test_tracked_costs(
+        "1",
+        use_mainnet,
+        StacksEpochId::Epoch25,
+        ClarityVersion::Clarity3,
+    );
+
+    for f in NativeFunctions::ALL.iter() {
+        // Note: Include Clarity3 functions for Epoch25.
         let test = get_simple_test(f);
         let cost = test_tracked_costs(
             test,
             use_mainnet,
-            StacksEpochId::Epoch21,
-            ClarityVersion::Clarity2,
+            StacksEpochId::Epoch25,
+            ClarityVersion::Clarity3,
         );
         assert!(cost.exceeds(&baseline));
     }
 }
 
 #[test]
-fn epoch_21_test_all_mainnet() {
-    epoch_21_test_all(true)
+fn epoch_25_test_all_mainnet() {
+    epoch_25_test_all(true)
 }
 
 #[test]
-fn epoch_21_test_all_testnet() {
-    epoch_21_test_all(false)
+fn epoch_25_test_all_testnet() {
+    epoch_25_test_all(false)
+}
+
+/// Measure the runtime cost charged for evaluating `expression` in a Clarity2 contract
+///  in epoch 2.1, net of the cost of calling a public function at all.
+fn epoch21_expression_runtime(use_mainnet: bool, expression: &str) -> u64 {
+    expression_runtime(StacksEpochId::Epoch21, use_mainnet, expression)
+}
+
+/// Runtime cost charged for evaluating `expression` in `epoch`, over and above the cost of
+///  calling a public function that does nothing.
+fn expression_runtime(epoch: StacksEpochId, use_mainnet: bool, expression: &str) -> u64 {
+    let p1 = execute("'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR");
+    let p1_principal = match p1 {
+        Value::Principal(PrincipalData::Standard(ref data)) => data.clone(),
+        _ => panic!(),
+    };
+    let contract_id = QualifiedContractIdentifier::new(p1_principal.clone(), "self".into());
+
+    // both functions live in the same contract, so that they share the same load cost
+    let contract = format!(
+        "(define-public (execute) (ok {}))
+         (define-public (baseline) (ok true))",
+        expression
+    );
+    with_owned_env(epoch, use_mainnet, |mut owned_env| {
+        owned_env
+            .initialize_versioned_contract(
+                contract_id.clone(),
+                ClarityVersion::default_for_epoch(epoch),
+                &contract,
+                None,
+                ASTRules::PrecheckSize,
+            )
+            .unwrap();
+
+        let mut runtimes = vec![];
+        for function in ["baseline", "execute"].iter() {
+            let cost_before = owned_env.get_cost_total();
+            execute_transaction(
+                &mut owned_env,
+                p1_principal.clone().into(),
+                &contract_id,
+                function,
+                &[],
+            )
+            .unwrap();
+            let mut cost_after = owned_env.get_cost_total();
+            cost_after.sub(&cost_before).unwrap();
+            runtimes.push(cost_after.runtime);
+        }
+        runtimes[1] - runtimes[0]
+    })
+}
+
+/// `is-standard` should be charged the constant `cost_is_standard` runtime
+///  from the epoch 2.1 cost contract, regardless of network or input principal.
+fn epoch21_is_standard_cost(use_mainnet: bool) {
+    // `cost_is_standard` and `cost_lookup_function` in costs-3.clar
+    let is_standard_runtime = 127;
+    let lookup_function_runtime = 16;
+
+    for principal in [
+        "'SP3X6QWWETNBZWGBK6DRGTR1KX50S74D3433WDGJY",
+        "'STB44HYPYAT2BB2QE513NSP81HTMYWBJP02HPGK6",
+        "'STB44HYPYAT2BB2QE513NSP81HTMYWBJP02HPGK6.tokens",
+    ]
+    .iter()
+    {
+        let runtime =
+            epoch21_expression_runtime(use_mainnet, &format!("(is-standard {})", principal));
+        assert_eq!(runtime, is_standard_runtime + lookup_function_runtime);
+    }
+}
+
+#[test]
+fn epoch21_is_standard_cost_mainnet() {
+    epoch21_is_standard_cost(true)
+}
+
+#[test]
+fn epoch21_is_standard_cost_testnet() {
+    epoch21_is_standard_cost(false)
+}
+
+/// `get-stacking-state` should be charged the constant `cost_get_stacking_state`
+///  runtime from the epoch 2.5 cost contract, even if the principal has not stacked.
+fn epoch25_get_stacking_state_cost(use_mainnet: bool) {
+    // `cost_get_stacking_state` and `cost_lookup_function` in costs-4.clar
+    let get_stacking_state_runtime = 5774;
+    let lookup_function_runtime = 16;
+
+    let runtime = expression_runtime(
+        StacksEpochId::Epoch25,
+        use_mainnet,
+        "(get-stacking-state 'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR)",
+    );
+    assert_eq!(
+        runtime,
+        get_stacking_state_runtime + lookup_function_runtime
+    );
+}
+
+#[test]
+fn epoch25_get_stacking_state_cost_mainnet() {
+    epoch25_get_stacking_state_cost(true)
+}
+
+#[test]
+fn epoch25_get_stacking_state_cost_testnet() {
+    epoch25_get_stacking_state_cost(false)
+}
+
+/// `principal-version` should be charged the constant `cost_principal_version`
+///  runtime from the epoch 2.5 cost contract, regardless of network or input principal.
+fn epoch25_principal_version_cost(use_mainnet: bool) {
+    // `cost_principal_version` and `cost_lookup_function` in costs-4.clar
+    let principal_version_runtime = 127;
+    let lookup_function_runtime = 16;
+
+    for principal in [
+        "'SP3X6QWWETNBZWGBK6DRGTR1KX50S74D3433WDGJY",
+        "'STB44HYPYAT2BB2QE513NSP81HTMYWBJP02HPGK6",
+        "'STB44HYPYAT2BB2QE513NSP81HTMYWBJP02HPGK6.tokens",
+    ]
+    .iter()
+    {
+        let runtime = expression_runtime(
+            StacksEpochId::Epoch25,
+            use_mainnet,
+            &format!("(principal-version {})", principal),
+        );
+        assert_eq!(runtime, principal_version_runtime + lookup_function_runtime);
+    }
+}
+
+#[test]
+fn epoch25_principal_version_cost_mainnet() {
+    epoch25_principal_version_cost(true)
+}
+
+#[test]
+fn epoch25_principal_version_cost_testnet() {
+    epoch25_principal_version_cost(false)
+}
+
+/// `principal-construct?` should be charged the constant `cost_principal_construct`
+///  runtime from the epoch 2.1 cost contract, whether it constructs a principal or
+///  returns one of its error responses.
+fn epoch21_principal_construct_cost(use_mainnet: bool) {
+    // `cost_principal_construct` and `cost_lookup_function` in costs-3.clar
+    let principal_construct_runtime = 398;
+    let lookup_function_runtime = 16;
+
+    for expression in [
+        // standard principals on either network
+        "(principal-construct? 0x16 0xfa6bf38ed557fe417333710d6033e9419391a320)",
+        "(principal-construct? 0x1a 0xfa6bf38ed557fe417333710d6033e9419391a320)",
+        // contract principal
+        "(principal-construct? 0x1a 0xfa6bf38ed557fe417333710d6033e9419391a320 \"foo\")",
+        // version byte out of range
+        "(principal-construct? 0x20 0xfa6bf38ed557fe417333710d6033e9419391a320)",
+        // hash bytes too short
+        "(principal-construct? 0x1a 0xfa6bf38ed557fe417333710d6033e9419391a3)",
+        // invalid contract name
+        "(principal-construct? 0x1a 0xfa6bf38ed557fe417333710d6033e9419391a320 \"foo[\")",
+    ]
+    .iter()
+    {
+        let runtime = epoch21_expression_runtime(use_mainnet, expression);
+        assert_eq!(
+            runtime,
+            principal_construct_runtime + lookup_function_runtime,
+            "Unexpected runtime cost for {}",
+            expression
+        );
+    }
+}
+
+#[test]
+fn epoch21_principal_construct_cost_mainnet() {
+    epoch21_principal_construct_cost(true)
+}
+
+#[test]
+fn epoch21_principal_construct_cost_testnet() {
+    epoch21_principal_construct_cost(false)
+}
+
+/// `string-to-int?` and `string-to-uint?` should be charged the constant runtime from
+///  the epoch 2.1 cost contract, however long the string they parse.
+fn epoch21_string_to_int_cost(use_mainnet: bool) {
+    // `cost_string_to_int`, `cost_string_to_uint` and `cost_lookup_function` in costs-3.clar
+    let string_to_int_runtime = 168;
+    let lookup_function_runtime = 16;
+
+    for expression in [
+        r#"(string-to-int? "1")"#,
+        r#"(string-to-int? "-0000000000000000000001")"#,
+        r#"(string-to-int? "not a number")"#,
+        r#"(string-to-uint? u"1")"#,
+        r#"(string-to-uint? u"00000000000000000000001")"#,
+    ]
+    .iter()
+    {
+        let runtime = epoch21_expression_runtime(use_mainnet, expression);
+        assert_eq!(
+            runtime,
+            string_to_int_runtime + lookup_function_runtime,
+            "Unexpected runtime cost for {}",
+            expression
+        );
+    }
+}
+
+#[test]
+fn epoch21_string_to_int_cost_mainnet() {
+    epoch21_string_to_int_cost(true)
+}
+
+#[test]
+fn epoch21_string_to_int_cost_testnet() {
+    epoch21_string_to_int_cost(false)
+}
+
+/// With cost profiling enabled, the cost of a contract call should be attributed to the
+///  cost functions of the natives it evaluated, and add up to the total cost charged.
+fn epoch21_cost_profile(use_mainnet: bool) {
+    let contract = "(define-data-var counter int 1)
+                    (define-public (execute) (ok (+ (var-get counter) 2)))";
+    let p1 = execute("'SZ2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQ9H6DPR");
+    let p1_principal = match p1 {
+        Value::Principal(PrincipalData::Standard(ref data)) => data.clone(),
+        _ => panic!(),
+    };
+    let contract_id = QualifiedContractIdentifier::new(p1_principal.clone(), "self".into());
+
+    with_owned_env(StacksEpochId::Epoch21, use_mainnet, |mut owned_env| {
+        owned_env
+            .initialize_contract(contract_id.clone(), contract, None, ASTRules::PrecheckSize)
+            .unwrap();
+
+        // profiling is off unless asked for
+        assert!(owned_env.get_cost_profile().is_none());
+
+        let cost_before = owned_env.get_cost_total();
+        owned_env.enable_cost_profiling();
+
+        let (result, ..) = execute_transaction(
+            &mut owned_env,
+            p1_principal.clone().into(),
+            &contract_id,
+            "execute",
+            &[],
+        )
+        .unwrap();
+        assert_eq!(result, Value::okay(Value::Int(3)).unwrap());
+
+        let mut cost = owned_env.get_cost_total();
+        cost.sub(&cost_before).unwrap();
+
+        let profile = owned_env.get_cost_profile().unwrap().clone();
+
+        let var_get = profile.get(&ClarityCostFunction::FetchVar).unwrap();
+        assert!(var_get.runtime > 0);
+        assert_eq!(var_get.read_count, 1);
+        assert!(var_get.read_length > 0);
+
+        let add = profile.get(&ClarityCostFunction::Add).unwrap();
+        assert!(add.runtime > 0);
+        assert_eq!(add.read_count, 0);
+        assert_eq!(add.read_length, 0);
+
+        // natives that were not evaluated are not in the profile
+        assert!(!profile.contains_key(&ClarityCostFunction::SetVar));
+        assert!(!profile.contains_key(&ClarityCostFunction::Sub));
+
+        let mut profiled_cost = ExecutionCost::zero();
+        for function_cost in profile.values() {
+            profiled_cost.add(function_cost).unwrap();
+        }
+        assert_eq!(profiled_cost, cost);
+    })
+}
+
+#[test]
+fn epoch21_cost_profile_mainnet() {
+    epoch21_cost_profile(true)
+}
+
+#[test]
+fn epoch21_cost_profile_testnet() {
+    epoch21_cost_profile(false)
 }
 
 fn test_cost_contract_short_circuits(use_mainnet: bool, clarity_version: ClarityVersion) {
@@ -22,6 +22,8 @@ use std::io::{Read, Write};
 use std::ops::Deref;
 use std::ops::DerefMut;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
+use std::sync::{Arc, Mutex};
 
 use rand::distributions::Uniform;
 use rand::prelude::Distribution;
@@ -37,6 +39,7 @@ use rusqlite::NO_PARAMS;
 
 use siphasher::sip::SipHasher; // this is SipHash-2-4
 
+use crate::burnchains::Address;
 use crate::burnchains::Txid;
 use crate::chainstate::burn::db::sortdb::SortitionDB;
 use crate::chainstate::burn::ConsensusHash;
@@ -89,7 +92,8 @@ use crate::monitoring;
 use crate::types::chainstate::{BlockHeaderHash, StacksAddress, StacksBlockId};
 use crate::util_lib::db::table_exists;
 
-// maximum number of confirmations a transaction can have before it's garbage-collected
+// maximum number of burnchain blocks a transaction can be in the mempool before it's
+// garbage-collected
 pub const MEMPOOL_MAX_TRANSACTION_AGE: u64 = 256;
 pub const MAXIMUM_MEMPOOL_TX_CHAINING: u64 = 25;
 
@@ -110,6 +114,14 @@ pub const BLOOM_COUNTER_DEPTH: usize = 2;
 pub const DEFAULT_BLACKLIST_TIMEOUT: u64 = 24 * 60 * 60 * 2;
 pub const DEFAULT_BLACKLIST_MAX_SIZE: u64 = 134217728; // 2**27 -- the blacklist table can reach at most 4GB at 128 bytes per record
 
+// a replace-by-fee transaction must pay at least this many percent more than the transaction it
+// replaces, no matter how the node is configured.
+pub const MIN_RBF_FEE_INCREASE_PCT: u64 = 25;
+
+// by default, a replace-by-fee transaction must pay at least this many percent more than the
+// transaction it replaces.
+pub const DEFAULT_RBF_FEE_INCREASE_PCT: u64 = MIN_RBF_FEE_INCREASE_PCT;
+
 // maximum many tx tags we'll send before sending a bloom filter instead.
 // The parameter choice here is due to performance -- calculating a tag set can be slower than just
 // loading the bloom filter, even though the bloom filter is larger.
@@ -195,6 +207,9 @@ pub enum MemPoolDropReason {
     STALE_COLLECT,
     TOO_EXPENSIVE,
     PROBLEMATIC,
+    STALE_NONCE,
+    MAX_TX_COUNT,
+    MAX_TOTAL_BYTES,
 }
 
 pub struct ConsiderTransaction {
@@ -220,12 +235,222 @@ impl std::fmt::Display for MemPoolDropReason {
             MemPoolDropReason::REPLACE_ACROSS_FORK => write!(f, "ReplaceAcrossFork"),
             MemPoolDropReason::REPLACE_BY_FEE => write!(f, "ReplaceByFee"),
             MemPoolDropReason::PROBLEMATIC => write!(f, "Problematic"),
+            MemPoolDropReason::STALE_NONCE => write!(f, "StaleNonce"),
+            MemPoolDropReason::MAX_TX_COUNT => write!(f, "MaxTxCount"),
+            MemPoolDropReason::MAX_TOTAL_BYTES => write!(f, "MaxTotalBytes"),
         }
     }
 }
 
+/// How many transactions the mempool rejected, by the check that failed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemPoolRejectionCounts {
+    /// the origin or sponsor nonce was stale, too far ahead of the account's nonce, or
+    /// already used by a pending transaction that this one could not replace
+    pub bad_nonce: u64,
+    /// the fee was below the minimum fee or below the mempool's minimum fee rate
+    pub fee_too_low: u64,
+    /// the miner evicted the transaction because it did not fit in a block's execution budget
+    pub too_expensive: u64,
+    /// every other admission check
+    pub other: u64,
+}
+
+/// Transaction admission counts for this process's mempool, as reported by `/v2/mempool/stats`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemPoolStats {
+    /// how many transactions `MemPoolDB::submit()` accepted since the node started
+    pub admitted: u64,
+    /// how many transactions were rejected since the node started
+    pub rejected: MemPoolRejectionCounts,
+    /// how many transactions are in the mempool now
+    pub pending: u64,
+    /// how many transactions were evicted since the node started for having been in the
+    /// mempool for too many burnchain blocks
+    #[serde(default)]
+    pub expired: u64,
+}
+
+/// Admission counters, shared by every `MemPoolDB` in the process (the RPC server, the relayer,
+/// and the miner each open their own).
+static MEMPOOL_ADMITTED: AtomicU64 = AtomicU64::new(0);
+static MEMPOOL_REJECTED_BAD_NONCE: AtomicU64 = AtomicU64::new(0);
+static MEMPOOL_REJECTED_FEE_TOO_LOW: AtomicU64 = AtomicU64::new(0);
+static MEMPOOL_REJECTED_TOO_EXPENSIVE: AtomicU64 = AtomicU64::new(0);
+static MEMPOOL_REJECTED_OTHER: AtomicU64 = AtomicU64::new(0);
+static MEMPOOL_EXPIRED: AtomicU64 = AtomicU64::new(0);
+
+impl MemPoolStats {
+    /// Count the outcome of a `MemPoolDB::submit()` or `MemPoolDB::check_conflicting_nonce()`
+    /// call.
+    fn record_submit(result: &Result<(), MemPoolRejection>) {
+        let counter = match result {
+            Ok(()) => &MEMPOOL_ADMITTED,
+            Err(MemPoolRejection::BadNonces(_))
+            | Err(MemPoolRejection::TooMuchChaining { .. })
+            | Err(MemPoolRejection::ConflictingNonceInMempool(..)) => &MEMPOOL_REJECTED_BAD_NONCE,
+            Err(MemPoolRejection::FeeTooLow(..)) | Err(MemPoolRejection::BelowMinFeeRate(..)) => {
+                &MEMPOOL_REJECTED_FEE_TOO_LOW
+            }
+            Err(_) => &MEMPOOL_REJECTED_OTHER,
+        };
+        counter.fetch_add(1, AtomicOrdering::SeqCst);
+    }
+
+    /// Count transactions that the miner evicted from the mempool because they were too
+    /// expensive to fit in a block (see `MemPoolDropReason::TOO_EXPENSIVE`).
+    pub fn record_too_expensive(num_txs: usize) {
+        MEMPOOL_REJECTED_TOO_EXPENSIVE.fetch_add(num_txs as u64, AtomicOrdering::SeqCst);
+    }
+
+    /// Count transactions that were evicted from the mempool for being too many burnchain blocks
+    /// old (see `MemPoolDB::garbage_collect_by_burn_age()`).
+    fn record_expired(num_txs: usize) {
+        MEMPOOL_EXPIRED.fetch_add(num_txs as u64, AtomicOrdering::SeqCst);
+    }
+
+    /// Read the admission counters, along with the number of transactions in `mempool`.
+    pub fn load(mempool: &MemPoolDB) -> Result<MemPoolStats, db_error> {
+        Ok(MemPoolStats {
+            admitted: MEMPOOL_ADMITTED.load(AtomicOrdering::SeqCst),
+            rejected: MemPoolRejectionCounts {
+                bad_nonce: MEMPOOL_REJECTED_BAD_NONCE.load(AtomicOrdering::SeqCst),
+                fee_too_low: MEMPOOL_REJECTED_FEE_TOO_LOW.load(AtomicOrdering::SeqCst),
+                too_expensive: MEMPOOL_REJECTED_TOO_EXPENSIVE.load(AtomicOrdering::SeqCst),
+                other: MEMPOOL_REJECTED_OTHER.load(AtomicOrdering::SeqCst),
+            },
+            pending: MemPoolDB::get_num_txs(mempool.conn())?,
+            expired: MEMPOOL_EXPIRED.load(AtomicOrdering::SeqCst),
+        })
+    }
+}
+
+/// The transaction in a mempool that holds an origin account's nonce.
+#[derive(Debug, Clone, PartialEq)]
+struct MemPoolConflictEntry {
+    txid: Txid,
+    origin_address: StacksAddress,
+    origin_nonce: u64,
+    tx_fee: u64,
+    consensus_hash: ConsensusHash,
+    block_header_hash: BlockHeaderHash,
+}
+
+impl FromRow<MemPoolConflictEntry> for MemPoolConflictEntry {
+    fn from_row<'a>(row: &'a Row) -> Result<MemPoolConflictEntry, db_error> {
+        Ok(MemPoolConflictEntry {
+            txid: Txid::from_column(row, "txid")?,
+            origin_address: StacksAddress::from_column(row, "origin_address")?,
+            origin_nonce: u64::from_column(row, "origin_nonce")?,
+            tx_fee: u64::from_column(row, "tx_fee")?,
+            consensus_hash: ConsensusHash::from_column(row, "consensus_hash")?,
+            block_header_hash: BlockHeaderHash::from_column(row, "block_header_hash")?,
+        })
+    }
+}
+
+enum MemPoolConflictUpdate {
+    Insert(MemPoolConflictEntry),
+    Remove(Txid),
+}
+
+/// In-memory index of the transactions in a mempool DB by origin address and nonce, so that a
+/// transaction which conflicts with one already in the mempool can be turned away without
+/// opening a DB transaction (see `MemPoolDB::check_conflicting_nonce()`).
+/// Every `MemPoolDB` in the process with the same DB open shares one cache, since the RPC server,
+/// the relayer, and the miner each open their own.  It is loaded from the DB once, by the first
+/// `MemPoolDB` to open it, and from then on kept up to date by each `MemPoolTx`, which applies its
+/// inserts and removals to it as it commits.
+#[derive(Debug, Default)]
+pub struct MemPoolConflictCache {
+    by_origin: HashMap<(StacksAddress, u64), MemPoolConflictEntry>,
+    origins: HashMap<Txid, (StacksAddress, u64)>,
+    /// Whether or not the cache has been loaded from the DB
+    loaded: bool,
+}
+
+lazy_static! {
+    /// The conflict cache of each mempool DB opened by this process, by canonical DB path.
+    static ref MEMPOOL_CONFLICT_CACHES: Mutex<HashMap<PathBuf, Arc<Mutex<MemPoolConflictCache>>>> =
+        Mutex::new(HashMap::new());
+}
+
+impl MemPoolConflictCache {
+    /// Get the conflict cache of the mempool DB at `db_path`, loading it from `conn` if no other
+    /// `MemPoolDB` in this process has done so yet, or if the DB was just `created`.
+    fn open(
+        db_path: &str,
+        conn: &DBConn,
+        created: bool,
+    ) -> Result<Arc<Mutex<MemPoolConflictCache>>, db_error> {
+        let key = fs::canonicalize(db_path).unwrap_or_else(|_| PathBuf::from(db_path));
+        let cache = MEMPOOL_CONFLICT_CACHES
+            .lock()
+            .expect("FATAL: mempool conflict cache registry lock is poisoned")
+            .entry(key)
+            .or_insert_with(|| Arc::new(Mutex::new(MemPoolConflictCache::default())))
+            .clone();
+
+        // Whoever locks the cache first loads it.  No other `MemPoolDB` can commit to the DB
+        // until then, since none has been opened on it yet.
+        let mut cache_guard = cache
+            .lock()
+            .expect("FATAL: mempool conflict cache lock is poisoned");
+        if !cache_guard.loaded || created {
+            cache_guard.load(conn)?;
+        }
+        drop(cache_guard);
+        Ok(cache)
+    }
+
+    fn load(&mut self, conn: &DBConn) -> Result<(), db_error> {
+        let sql = "SELECT txid, origin_address, origin_nonce, tx_fee, consensus_hash, block_header_hash FROM mempool";
+        let entries: Vec<MemPoolConflictEntry> = query_rows(conn, sql, NO_PARAMS)?;
+        self.by_origin.clear();
+        self.origins.clear();
+        for entry in entries.into_iter() {
+            self.insert(entry);
+        }
+        self.loaded = true;
+        Ok(())
+    }
+
+    fn insert(&mut self, entry: MemPoolConflictEntry) {
+        let key = (entry.origin_address.clone(), entry.origin_nonce);
+        let txid = entry.txid.clone();
+        if let Some(prior) = self.by_origin.insert(key.clone(), entry) {
+            self.origins.remove(&prior.txid);
+        }
+        self.origins.insert(txid, key);
+    }
+
+    fn remove(&mut self, txid: &Txid) {
+        if let Some(key) = self.origins.remove(txid) {
+            self.by_origin.remove(&key);
+        }
+    }
+
+    fn apply(&mut self, updates: Vec<MemPoolConflictUpdate>) {
+        for update in updates.into_iter() {
+            match update {
+                MemPoolConflictUpdate::Insert(entry) => self.insert(entry),
+                MemPoolConflictUpdate::Remove(txid) => self.remove(&txid),
+            }
+        }
+    }
+
+    fn get(
+        &self,
+        origin_address: &StacksAddress,
+        origin_nonce: u64,
+    ) -> Option<&MemPoolConflictEntry> {
+        self.by_origin.get(&(origin_address.clone(), origin_nonce))
+    }
+}
+
 pub trait MemPoolEventDispatcher {
     fn mempool_txs_dropped(&self, txids: Vec<Txid>, reason: MemPoolDropReason);
+    fn mempool_tx_evicted(&self, txid: Txid, reason: MemPoolDropReason);
     fn mined_block_event(
         &self,
         target_burn_height: u64,
@@ -262,6 +487,13 @@ pub struct MemPoolTxInfoPartial {
     pub sponsor_nonce: u64,
 }
 
+/// A mempool transaction together with its estimated fee rate, if one has been computed.
+#[derive(Debug, Clone)]
+pub struct MemPoolTxInfoWithRate {
+    pub tx: MemPoolTxInfo,
+    pub fee_rate: Option<f64>,
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct MemPoolTxMetadata {
     pub txid: Txid,
@@ -331,6 +563,35 @@ impl MemPoolWalkSettings {
     }
 }
 
+/// Where a mempool walk left off, so that a later walk can resume from it instead of starting
+/// over from the highest-fee-rate candidate.  Candidates are walked in descending fee rate order
+/// (ties broken by txid), and those without a fee rate estimate in txid order; the cursor holds
+/// the last candidate handled in each order.
+///
+/// A resumed walk does not revisit candidates that were put aside for their nonces, nor
+/// candidates whose fee rate estimate changed to one above the cursor.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MemPoolWalkCursor {
+    /// Fee rate and txid of the last candidate handled that had a fee rate estimate
+    pub last_fee_rate: Option<(f64, Txid)>,
+    /// Txid of the last candidate handled that had no fee rate estimate
+    pub last_no_estimate: Option<Txid>,
+}
+
+impl MemPoolWalkCursor {
+    pub fn new() -> MemPoolWalkCursor {
+        MemPoolWalkCursor::default()
+    }
+
+    /// Move the cursor past `candidate`.
+    fn advance(&mut self, candidate: &MemPoolTxInfoPartial) {
+        match candidate.fee_rate {
+            Some(fee_rate) => self.last_fee_rate = Some((fee_rate, candidate.txid.clone())),
+            None => self.last_no_estimate = Some(candidate.txid.clone()),
+        }
+    }
+}
+
 impl FromRow<Txid> for Txid {
     fn from_row<'a>(row: &'a Row) -> Result<Txid, db_error> {
         row.get(0).map_err(db_error::SqliteError)
@@ -389,6 +650,18 @@ impl FromRow<MemPoolTxInfo> for MemPoolTxInfo {
     }
 }
 
+impl FromRow<MemPoolTxInfoWithRate> for MemPoolTxInfoWithRate {
+    fn from_row<'a>(row: &'a Row) -> Result<MemPoolTxInfoWithRate, db_error> {
+        let tx = MemPoolTxInfo::from_row(row)?;
+        let fee_rate: Option<f64> = match row.get("fee_rate") {
+            Ok(rate) => Some(rate),
+            Err(_) => None,
+        };
+
+        Ok(MemPoolTxInfoWithRate { tx, fee_rate })
+    }
+}
+
 impl FromRow<MemPoolTxInfoPartial> for MemPoolTxInfoPartial {
     fn from_row<'a>(row: &'a Row) -> Result<MemPoolTxInfoPartial, db_error> {
         let txid = Txid::from_column(row, "txid")?;
@@ -556,6 +829,17 @@ const MEMPOOL_SCHEMA_6_NONCES: &'static [&'static str] = &[
     "#,
 ];
 
+const MEMPOOL_SCHEMA_7_BURN_AGE: &'static [&'static str] = &[
+    r#"
+    -- burnchain height at which the expiry sweep first saw the transaction.
+    -- NULL until the next sweep after the transaction is accepted.
+    ALTER TABLE mempool ADD COLUMN accept_burn_height INTEGER;
+    "#,
+    r#"
+    INSERT INTO schema_version (version) VALUES (7)
+    "#,
+];
+
 const MEMPOOL_INDEXES: &'static [&'static str] = &[
     "CREATE INDEX IF NOT EXISTS by_txid ON mempool(txid);",
     "CREATE INDEX IF NOT EXISTS by_height ON mempool(height);",
@@ -568,6 +852,7 @@ const MEMPOOL_INDEXES: &'static [&'static str] = &[
     "CREATE INDEX IF NOT EXISTS by_ordered_hashed_txid ON randomized_txids(hashed_txid ASC);",
     "CREATE INDEX IF NOT EXISTS by_hashed_txid ON randomized_txids(txid,hashed_txid);",
     "CREATE INDEX IF NOT EXISTS by_arrival_time_desc ON tx_blacklist(arrival_time DESC);",
+    "CREATE INDEX IF NOT EXISTS by_accept_burn_height ON mempool(accept_burn_height);",
 ];
 
 pub struct MemPoolDB {
@@ -580,12 +865,45 @@ pub struct MemPoolDB {
     metric: Box<dyn CostMetric>,
     pub blacklist_timeout: u64,
     pub blacklist_max_size: u64,
+    /// Whether or not a transaction may replace one with the same origin or sponsor nonce by
+    /// paying a sufficiently higher fee.
+    pub enable_rbf: bool,
+    /// How many percent more than the transaction it replaces a replace-by-fee transaction must
+    /// pay.  Values below `MIN_RBF_FEE_INCREASE_PCT` are treated as `MIN_RBF_FEE_INCREASE_PCT`.
+    pub rbf_fee_increase_pct: u64,
+    /// The lowest fee rate, in microSTX per byte, at which a transaction is accepted from a
+    /// peer or client.  0 accepts every fee rate.
+    pub min_fee_rate: u64,
+    /// Whether or not to reject a sponsored transaction whose sponsor cannot pay its fee at the
+    /// chain tip it is submitted against.  This costs a chainstate read per submission.
+    pub validate_sponsor_balance: bool,
+    /// Transactions that have been in the mempool for at least this many burnchain blocks are
+    /// garbage-collected.
+    pub max_tx_age: u64,
+    /// If set, the lowest-fee-rate transactions are evicted as soon as accepting a transaction
+    /// leaves more than this many transactions in the mempool.
+    pub max_tx_count: Option<u64>,
+    /// If set, the lowest-fee-rate transactions are evicted as soon as accepting a transaction
+    /// leaves the mempool's transactions taking up more than this many bytes.
+    pub max_total_bytes: Option<u64>,
+    /// Chain tip and burnchain height at which the mempool was last garbage-collected
+    last_gc_tip: Option<(StacksBlockId, u64)>,
+    /// The mempool's transactions by origin address and nonce, shared with every other
+    /// `MemPoolDB` on this DB
+    conflict_cache: Arc<Mutex<MemPoolConflictCache>>,
 }
 
 pub struct MemPoolTx<'a> {
     tx: DBTx<'a>,
     admitter: &'a mut MemPoolAdmitter,
     bloom_counter: Option<&'a mut BloomCounter<BloomNodeHasher>>,
+    enable_rbf: bool,
+    rbf_fee_increase_pct: u64,
+    max_tx_count: Option<u64>,
+    max_total_bytes: Option<u64>,
+    /// If set, the conflict cache to apply `conflict_updates` to on commit
+    conflict_cache: Option<Arc<Mutex<MemPoolConflictCache>>>,
+    conflict_updates: Vec<MemPoolConflictUpdate>,
 }
 
 impl<'a> Deref for MemPoolTx<'a> {
@@ -611,9 +929,43 @@ impl<'a> MemPoolTx<'a> {
             tx,
             admitter,
             bloom_counter: Some(bloom_counter),
+            enable_rbf: false,
+            rbf_fee_increase_pct: DEFAULT_RBF_FEE_INCREASE_PCT,
+            max_tx_count: None,
+            max_total_bytes: None,
+            conflict_cache: None,
+            conflict_updates: vec![],
         }
     }
 
+    /// Keep `conflict_cache` in sync with the transactions added and removed in this transaction.
+    pub fn set_conflict_cache(&mut self, conflict_cache: Arc<Mutex<MemPoolConflictCache>>) {
+        self.conflict_cache = Some(conflict_cache);
+    }
+
+    fn record_conflict_update(&mut self, update: MemPoolConflictUpdate) {
+        if self.conflict_cache.is_some() {
+            self.conflict_updates.push(update);
+        }
+    }
+
+    /// Set whether or not transactions added in this transaction may replace-by-fee.
+    pub fn set_enable_rbf(&mut self, enable_rbf: bool) {
+        self.enable_rbf = enable_rbf;
+    }
+
+    /// Set how many percent more a replace-by-fee transaction added in this transaction must pay.
+    pub fn set_rbf_fee_increase_pct(&mut self, rbf_fee_increase_pct: u64) {
+        self.rbf_fee_increase_pct = rbf_fee_increase_pct;
+    }
+
+    /// Set how many transactions, and how many bytes of them, the mempool may hold once a
+    /// transaction is added in this transaction.
+    pub fn set_size_limits(&mut self, max_tx_count: Option<u64>, max_total_bytes: Option<u64>) {
+        self.max_tx_count = max_tx_count;
+        self.max_total_bytes = max_total_bytes;
+    }
+
     pub fn with_bloom_state<F, R>(tx: &mut MemPoolTx<'a>, f: F) -> R
     where
         F: FnOnce(&mut DBTx<'a>, &mut BloomCounter<BloomNodeHasher>) -> R,
@@ -628,7 +980,18 @@ impl<'a> MemPoolTx<'a> {
     }
 
     pub fn commit(self) -> Result<(), db_error> {
-        self.tx.commit().map_err(db_error::SqliteError)
+        let conflict_cache = match self.conflict_cache {
+            Some(conflict_cache) => conflict_cache,
+            None => return self.tx.commit().map_err(db_error::SqliteError),
+        };
+        // hold the cache lock across the commit, so that commits from different `MemPoolDB`s
+        // are applied to the cache in the same order as to the DB
+        let mut conflict_cache = conflict_cache
+            .lock()
+            .expect("FATAL: mempool conflict cache lock is poisoned");
+        self.tx.commit().map_err(db_error::SqliteError)?;
+        conflict_cache.apply(self.conflict_updates);
+        Ok(())
     }
 
     /// Remove all txids at the given height from the bloom counter.
@@ -1076,6 +1439,9 @@ impl MemPoolDB {
                     MemPoolDB::instantiate_nonces(tx)?;
                 }
                 6 => {
+                    MemPoolDB::instantiate_burn_age(tx)?;
+                }
+                7 => {
                     break;
                 }
                 _ => {
@@ -1147,6 +1513,15 @@ impl MemPoolDB {
         Ok(())
     }
 
+    /// Add the burnchain height at which each transaction was first seen
+    fn instantiate_burn_age(tx: &DBTx) -> Result<(), db_error> {
+        for sql_exec in MEMPOOL_SCHEMA_7_BURN_AGE {
+            tx.execute_batch(sql_exec)?;
+        }
+
+        Ok(())
+    }
+
     pub fn db_path(chainstate_root_path: &str) -> Result<String, db_error> {
         let mut path = PathBuf::from(chainstate_root_path);
 
@@ -1198,6 +1573,8 @@ impl MemPoolDB {
         let bloom_counter = BloomCounter::<BloomNodeHasher>::try_load(&conn, BLOOM_COUNTER_TABLE)?
             .ok_or(db_error::Other(format!("Failed to load bloom counter")))?;
 
+        let conflict_cache = MemPoolConflictCache::open(db_path, &conn, create_flag)?;
+
         Ok(MemPoolDB {
             db: conn,
             path: db_path.to_owned(),
@@ -1208,6 +1585,15 @@ impl MemPoolDB {
             metric,
             blacklist_timeout: DEFAULT_BLACKLIST_TIMEOUT,
             blacklist_max_size: DEFAULT_BLACKLIST_MAX_SIZE,
+            enable_rbf: false,
+            rbf_fee_increase_pct: DEFAULT_RBF_FEE_INCREASE_PCT,
+            min_fee_rate: 0,
+            validate_sponsor_balance: false,
+            max_tx_age: MEMPOOL_MAX_TRANSACTION_AGE,
+            max_tx_count: None,
+            max_total_bytes: None,
+            last_gc_tip: None,
+            conflict_cache,
         })
     }
 
@@ -1362,11 +1748,42 @@ impl MemPoolDB {
     /// transaction events (success and error events, but not skipped) output
     /// by `todo`.
     pub fn iterate_candidates<F, E, C>(
+        &mut self,
+        clarity_tx: &mut C,
+        output_events: &mut Vec<TransactionEvent>,
+        tip_height: u64,
+        settings: MemPoolWalkSettings,
+        todo: F,
+    ) -> Result<u64, E>
+    where
+        C: ClarityConnection,
+        F: FnMut(
+            &mut C,
+            &ConsiderTransaction,
+            &mut dyn CostEstimator,
+        ) -> Result<Option<TransactionEvent>, E>,
+        E: From<db_error> + From<ChainstateError>,
+    {
+        self.iterate_candidates_from(
+            clarity_tx,
+            output_events,
+            tip_height,
+            settings,
+            &mut MemPoolWalkCursor::new(),
+            todo,
+        )
+    }
+
+    /// Like `iterate_candidates`, but only walks the candidates past `cursor`, and advances
+    /// `cursor` past each candidate it handles.  A candidate for which `todo` returns None is
+    /// not handled, so a walk resumed from `cursor` starts with it.
+    pub fn iterate_candidates_from<F, E, C>(
         &mut self,
         clarity_tx: &mut C,
         output_events: &mut Vec<TransactionEvent>,
         _tip_height: u64,
         settings: MemPoolWalkSettings,
+        cursor: &mut MemPoolWalkCursor,
         mut todo: F,
     ) -> Result<u64, E>
     where
@@ -1395,28 +1812,34 @@ impl MemPoolDB {
         let sql = "
              SELECT txid, origin_nonce, origin_address, sponsor_nonce, sponsor_address, fee_rate
              FROM mempool
-             WHERE fee_rate IS NULL
+             WHERE fee_rate IS NULL AND (?1 IS NULL OR txid > ?1)
+             ORDER BY txid ASC
              ";
         let mut query_stmt_null = self
             .db
             .prepare(&sql)
             .map_err(|err| Error::SqliteError(err))?;
         let mut null_iterator = query_stmt_null
-            .query(NO_PARAMS)
+            .query(rusqlite::params![cursor.last_no_estimate])
             .map_err(|err| Error::SqliteError(err))?;
 
+        let (last_fee_rate, last_fee_rate_txid) = match cursor.last_fee_rate.clone() {
+            Some((fee_rate, txid)) => (Some(fee_rate), Some(txid)),
+            None => (None, None),
+        };
         let sql = "
             SELECT txid, origin_nonce, origin_address, sponsor_nonce, sponsor_address, fee_rate
             FROM mempool
             WHERE fee_rate IS NOT NULL
-            ORDER BY fee_rate DESC
+                AND (?1 IS NULL OR fee_rate < ?1 OR (fee_rate = ?1 AND txid > ?2))
+            ORDER BY fee_rate DESC, txid ASC
             ";
         let mut query_stmt_fee = self
             .db
             .prepare(&sql)
             .map_err(|err| Error::SqliteError(err))?;
         let mut fee_iterator = query_stmt_fee
-            .query(NO_PARAMS)
+            .query(rusqlite::params![last_fee_rate, last_fee_rate_txid])
             .map_err(|err| Error::SqliteError(err))?;
 
         loop {
@@ -1430,10 +1853,10 @@ impl MemPoolDB {
                 tx_consideration_sampler.sample(&mut rng) < settings.consider_no_estimate_tx_prob;
 
             // First, try to read from the retry list
-            let (candidate, update_estimate) = match candidate_cache.next() {
+            let (candidate, update_estimate, from_db) = match candidate_cache.next() {
                 Some(tx) => {
                     let update_estimate = tx.fee_rate.is_none();
-                    (tx, update_estimate)
+                    (tx, update_estimate, false)
                 }
                 None => {
                     // When the retry list is empty, read from the mempool db,
@@ -1447,7 +1870,11 @@ impl MemPoolDB {
                         fee_iterator.next().map_err(|err| Error::SqliteError(err))?
                     };
                     match opt_tx {
-                        Some(row) => (MemPoolTxInfoPartial::from_row(row)?, start_with_no_estimate),
+                        Some(row) => (
+                            MemPoolTxInfoPartial::from_row(row)?,
+                            start_with_no_estimate,
+                            true,
+                        ),
                         None => {
                             // If the selected iterator is empty, check the other
                             match if start_with_no_estimate {
@@ -1460,6 +1887,7 @@ impl MemPoolDB {
                                 Some(row) => (
                                     MemPoolTxInfoPartial::from_row(row)?,
                                     !start_with_no_estimate,
+                                    true,
                                 ),
                                 None => {
                                     debug!("No more transactions to consider in mempool");
@@ -1510,6 +1938,9 @@ impl MemPoolDB {
                         candidate.fee_rate.unwrap_or_default()
                     );
                     // This transaction cannot execute in this pass, just drop it
+                    if from_db {
+                        cursor.advance(&candidate);
+                    }
                     continue;
                 }
                 Ordering::Greater => {
@@ -1520,6 +1951,9 @@ impl MemPoolDB {
                         candidate.fee_rate.unwrap_or_default()
                     );
                     // This transaction could become runnable in this pass, save it for later
+                    if from_db {
+                        cursor.advance(&candidate);
+                    }
                     candidate_cache.push(candidate);
                     continue;
                 }
@@ -1535,6 +1969,9 @@ impl MemPoolDB {
                 None => {
                     // Note: Don't panic here because maybe the state has changed from garbage collection.
                     warn!("Miner: could not find a tx for id {:?}", &candidate.txid);
+                    if from_db {
+                        cursor.advance(&candidate);
+                    }
                     continue;
                 }
             };
@@ -1558,6 +1995,9 @@ impl MemPoolDB {
             // Run `todo` on the transaction.
             match todo(clarity_tx, &consider, self.cost_estimator.as_mut())? {
                 Some(tx_event) => {
+                    if from_db {
+                        cursor.advance(&candidate);
+                    }
                     match tx_event {
                         TransactionEvent::Success(_) => {
                             // Bump nonces in the cache for the executed transaction
@@ -1644,11 +2084,12 @@ impl MemPoolDB {
 
     pub fn tx_begin<'a>(&'a mut self) -> Result<MemPoolTx<'a>, db_error> {
         let tx = tx_begin_immediate(&mut self.db)?;
-        Ok(MemPoolTx::new(
-            tx,
-            &mut self.admitter,
-            &mut self.bloom_counter,
-        ))
+        let mut mempool_tx = MemPoolTx::new(tx, &mut self.admitter, &mut self.bloom_counter);
+        mempool_tx.set_enable_rbf(self.enable_rbf);
+        mempool_tx.set_rbf_fee_increase_pct(self.rbf_fee_increase_pct);
+        mempool_tx.set_size_limits(self.max_tx_count, self.max_total_bytes);
+        mempool_tx.set_conflict_cache(self.conflict_cache.clone());
+        Ok(mempool_tx)
     }
 
     pub fn db_has_tx(conn: &DBConn, txid: &Txid) -> Result<bool, db_error> {
@@ -1729,6 +2170,45 @@ impl MemPoolDB {
         Ok(rows)
     }
 
+    /// Get all transactions in the mempool that `addr` either originates or sponsors, across all
+    /// tips, along with their fee-rate estimates.  They are ordered by the nonce `addr` uses in
+    /// each transaction (i.e. its origin nonce if it is the origin, and its sponsor nonce
+    /// otherwise).
+    pub fn get_txs_by_address(
+        conn: &DBConn,
+        addr: &StacksAddress,
+    ) -> Result<Vec<MemPoolTxInfoWithRate>, db_error> {
+        let sql = "SELECT * FROM mempool WHERE origin_address = ?1 OR sponsor_address = ?1
+                   ORDER BY CASE WHEN origin_address = ?1 THEN origin_nonce ELSE sponsor_nonce END ASC";
+        let args: &[&dyn ToSql] = &[&addr.to_string()];
+        query_rows(conn, sql, args)
+    }
+
+    /// Get the smallest nonce at or above `start_nonce` that `addr` does not use in any mempool
+    /// transaction, either as an origin or as a sponsor.  If the mempool's nonces for `addr` have
+    /// a gap, then the first missing nonce is returned instead of the nonce after the highest one.
+    pub fn get_possible_next_nonce(
+        conn: &DBConn,
+        addr: &StacksAddress,
+        start_nonce: u64,
+    ) -> Result<u64, db_error> {
+        let sql = "SELECT origin_nonce AS nonce FROM mempool WHERE origin_address = ?1 AND origin_nonce >= ?2
+                   UNION
+                   SELECT sponsor_nonce AS nonce FROM mempool WHERE sponsor_address = ?1 AND sponsor_nonce >= ?2
+                   ORDER BY nonce ASC";
+        let args: &[&dyn ToSql] = &[&addr.to_string(), &u64_to_sql(start_nonce)?];
+        let nonces: Vec<u64> = query_rows(conn, sql, args)?;
+
+        let mut next_nonce = start_nonce;
+        for nonce in nonces.into_iter() {
+            if nonce != next_nonce {
+                break;
+            }
+            next_nonce += 1;
+        }
+        Ok(next_nonce)
+    }
+
     /// Get a transaction's metadata, given address and nonce, and whether the address is used as a sponsor or an origin.
     /// Faster than getting the MemPoolTxInfo, since no deserialization will be needed.
     /// Used to see if there exists a transaction with this info, so as to implement replace-by-fee
@@ -1791,8 +2271,18 @@ impl MemPoolDB {
         }
     }
 
-    /// Add a transaction to the mempool.  If it already exists, then replace it if the given fee
-    /// is higher than the one that's already there.
+    /// Can a transaction paying `new_fee` replace one paying `prior_fee` by fee?  It must pay at
+    /// least `fee_increase_pct` percent more, and never less than `MIN_RBF_FEE_INCREASE_PCT`
+    /// percent more.
+    pub fn is_rbf_fee_sufficient(prior_fee: u64, new_fee: u64, fee_increase_pct: u64) -> bool {
+        let fee_increase_pct = cmp::max(fee_increase_pct, MIN_RBF_FEE_INCREASE_PCT);
+        new_fee > prior_fee
+            && (new_fee as u128) * 100 >= (prior_fee as u128) * (100 + fee_increase_pct as u128)
+    }
+
+    /// Add a transaction to the mempool.  If it already exists, then replace it if replace-by-fee
+    /// is enabled and the given fee is at least `rbf_fee_increase_pct` percent higher than the one
+    /// that's already there.
     /// Carry out the mempool admission test before adding.
     /// Don't call directly; use submit().
     /// This is `pub` only for testing.
@@ -1829,15 +2319,23 @@ impl MemPoolDB {
         let mut replace_reason = MemPoolDropReason::REPLACE_BY_FEE;
 
         // if so, is this a replace-by-fee? or a replace-in-chain-tip?
-        let add_tx = if let Some(ref prior_tx) = prior_tx {
-            if tx_fee > prior_tx.tx_fee {
+        if let Some(ref prior_tx) = prior_tx {
+            if tx.enable_rbf
+                && MemPoolDB::is_rbf_fee_sufficient(
+                    prior_tx.tx_fee,
+                    tx_fee,
+                    tx.rbf_fee_increase_pct,
+                )
+            {
                 // is this a replace-by-fee ?
-                debug!(
-                    "Can replace {} with {} for {},{} by fee ({} < {})",
-                    &prior_tx.txid, &txid, origin_address, origin_nonce, &prior_tx.tx_fee, &tx_fee
-                );
+                info!("Replace-by-fee: evicting mempool transaction";
+                      "new_txid" => %txid,
+                      "old_txid" => %prior_tx.txid,
+                      "origin_addr" => %origin_address,
+                      "origin_nonce" => origin_nonce,
+                      "new_fee" => tx_fee,
+                      "old_fee" => prior_tx.tx_fee);
                 replace_reason = MemPoolDropReason::REPLACE_BY_FEE;
-                true
             } else if !MemPoolDB::are_blocks_in_same_fork(
                 chainstate,
                 &prior_tx.consensus_hash,
@@ -1851,10 +2349,10 @@ impl MemPoolDB {
                     &prior_tx.txid, &txid, origin_address, origin_nonce
                 );
                 replace_reason = MemPoolDropReason::REPLACE_ACROSS_FORK;
-                true
             } else {
-                // there's a >= fee tx in this fork, cannot add
-                info!("TX conflicts with sponsor/origin nonce in same fork with >= fee";
+                // there's a conflicting tx in this fork that we may not replace, cannot add
+                info!("TX conflicts with sponsor/origin nonce in same fork";
+                      "rbf_enabled" => tx.enable_rbf,
                       "new_txid" => %txid,
                       "old_txid" => %prior_tx.txid,
                       "origin_addr" => %origin_address,
@@ -1863,17 +2361,21 @@ impl MemPoolDB {
                       "sponsor_nonce" => sponsor_nonce,
                       "new_fee" => tx_fee,
                       "old_fee" => prior_tx.tx_fee);
-                false
+                return Err(MemPoolRejection::ConflictingNonceInMempool(
+                    prior_tx.txid.clone(),
+                ));
             }
+        }
+
+        // the insert below evicts both the transaction with this origin nonce and the one with
+        // this sponsor nonce, if they differ
+        let sponsor_prior_txid = if prior_tx.is_some() {
+            MemPoolDB::get_tx_metadata_by_address(tx, false, sponsor_address, sponsor_nonce)?
+                .map(|tx| tx.txid)
         } else {
-            // no conflicting TX with this origin/sponsor, go ahead and add
-            true
+            None
         };
 
-        if !add_tx {
-            return Err(MemPoolRejection::ConflictingNonceInMempool);
-        }
-
         tx.update_bloom_counter(height, &txid, prior_tx.as_ref().map(|tx| tx.txid.clone()))?;
 
         let sql = "INSERT OR REPLACE INTO mempool (
@@ -1911,11 +2413,96 @@ impl MemPoolDB {
 
         tx.update_mempool_pager(&txid)?;
 
+        for evicted_txid in prior_tx
+            .iter()
+            .map(|prior_tx| &prior_tx.txid)
+            .chain(sponsor_prior_txid.iter())
+        {
+            tx.record_conflict_update(MemPoolConflictUpdate::Remove(evicted_txid.clone()));
+        }
+        tx.record_conflict_update(MemPoolConflictUpdate::Insert(MemPoolConflictEntry {
+            txid: txid.clone(),
+            origin_address: origin_address.clone(),
+            origin_nonce,
+            tx_fee,
+            consensus_hash: consensus_hash.clone(),
+            block_header_hash: block_header_hash.clone(),
+        }));
+
         // broadcast drop event if a tx is being replaced
         if let (Some(prior_tx), Some(event_observer)) = (prior_tx, event_observer) {
             event_observer.mempool_txs_dropped(vec![prior_tx.txid], replace_reason);
         };
 
+        MemPoolDB::evict_to_limits(tx, event_observer)?;
+
+        Ok(())
+    }
+
+    /// Evict the transactions with the lowest fee rate, in microSTX per byte, until the mempool
+    /// holds at most `max_tx_count` transactions taking up at most `max_total_bytes` (as set on
+    /// `tx`).  Among equal fee rates, the oldest transactions are evicted first.  `try_add_tx()`
+    /// runs this in the same DB transaction as the insert, so the mempool never exceeds either
+    /// limit, and `garbage_collect_at_tip()` runs it in case the limits have been lowered.
+    pub fn evict_to_limits(
+        tx: &mut MemPoolTx,
+        event_observer: Option<&dyn MemPoolEventDispatcher>,
+    ) -> Result<(), db_error> {
+        let (max_tx_count, max_total_bytes) = (tx.max_tx_count, tx.max_total_bytes);
+        if max_tx_count.is_none() && max_total_bytes.is_none() {
+            return Ok(());
+        }
+
+        let mut tx_count: u64 =
+            query_row(tx, "SELECT COUNT(*) FROM mempool", NO_PARAMS)?.unwrap_or(0);
+        let mut total_bytes: u64 =
+            query_row(tx, "SELECT IFNULL(SUM(length), 0) FROM mempool", NO_PARAMS)?.unwrap_or(0);
+
+        let mut evicted = vec![];
+        {
+            let sql = "SELECT txid, length FROM mempool ORDER BY (tx_fee * 1.0) / length ASC, accept_time ASC";
+            let mut stmt = tx.prepare(sql)?;
+            let mut rows = stmt.query(NO_PARAMS)?;
+            loop {
+                let reason = if max_tx_count.map(|max| tx_count > max).unwrap_or(false) {
+                    MemPoolDropReason::MAX_TX_COUNT
+                } else if max_total_bytes
+                    .map(|max| total_bytes > max)
+                    .unwrap_or(false)
+                {
+                    MemPoolDropReason::MAX_TOTAL_BYTES
+                } else {
+                    break;
+                };
+                let row = match rows.next()? {
+                    Some(row) => row,
+                    None => break,
+                };
+                let txid = Txid::from_column(row, "txid")?;
+                let length = u64::from_column(row, "length")?;
+                evicted.push((txid, reason));
+                tx_count = tx_count.saturating_sub(1);
+                total_bytes = total_bytes.saturating_sub(length);
+            }
+        }
+
+        if evicted.len() == 0 {
+            return Ok(());
+        }
+
+        debug!(
+            "Evict {} transaction(s) to keep the mempool within {:?} transactions and {:?} bytes",
+            evicted.len(),
+            max_tx_count,
+            max_total_bytes
+        );
+        let evicted_txids: Vec<_> = evicted.iter().map(|(txid, _)| txid.clone()).collect();
+        MemPoolDB::inner_drop_txs(tx, &evicted_txids)?;
+        if let Some(event_observer) = event_observer {
+            for (txid, reason) in evicted.into_iter() {
+                event_observer.mempool_tx_evicted(txid, reason);
+            }
+        }
         Ok(())
     }
 
@@ -1928,9 +2515,12 @@ impl MemPoolDB {
     ) -> Result<(), db_error> {
         let args: &[&dyn ToSql] = &[&u64_to_sql(min_height)?];
 
+        let sql = "SELECT txid FROM mempool WHERE height < ?1";
+        let txids: Vec<Txid> = query_rows(tx, sql, args)?;
+        for txid in txids.iter() {
+            tx.record_conflict_update(MemPoolConflictUpdate::Remove(txid.clone()));
+        }
         if let Some(event_observer) = event_observer {
-            let sql = "SELECT txid FROM mempool WHERE height < ?1";
-            let txids = query_rows(tx, sql, args)?;
             event_observer.mempool_txs_dropped(txids, MemPoolDropReason::STALE_COLLECT);
         }
 
@@ -1941,6 +2531,142 @@ impl MemPoolDB {
         Ok(())
     }
 
+    /// Garbage-collect the mempool.  Remove transactions whose origin nonce is below the nonce
+    /// that `get_nonce` reports for their origin account, since they can never be mined on the
+    /// fork `get_nonce` reads from.
+    pub fn garbage_collect_stale_nonces<F>(
+        tx: &mut MemPoolTx,
+        mut get_nonce: F,
+        event_observer: Option<&dyn MemPoolEventDispatcher>,
+    ) -> Result<(), db_error>
+    where
+        F: FnMut(&StacksAddress) -> u64,
+    {
+        let sql = "SELECT DISTINCT origin_address FROM mempool";
+        let origin_addresses: Vec<String> = query_rows(tx, sql, NO_PARAMS)?;
+
+        let mut stale_txids = vec![];
+        for addr_str in origin_addresses.iter() {
+            let address = match StacksAddress::from_string(addr_str) {
+                Some(address) => address,
+                None => {
+                    warn!("Unparseable origin address in mempool: {}", addr_str);
+                    continue;
+                }
+            };
+            let nonce = get_nonce(&address);
+
+            let sql = "SELECT txid FROM mempool WHERE origin_address = ?1 AND origin_nonce < ?2";
+            let args: &[&dyn ToSql] = &[addr_str, &u64_to_sql(nonce)?];
+            let mut txids: Vec<Txid> = query_rows(tx, sql, args)?;
+            stale_txids.append(&mut txids);
+        }
+
+        if stale_txids.len() == 0 {
+            return Ok(());
+        }
+
+        debug!(
+            "Remove {} transaction(s) with stale nonces",
+            stale_txids.len()
+        );
+        MemPoolDB::inner_drop_txs(tx, &stale_txids)?;
+        if let Some(event_observer) = event_observer {
+            event_observer.mempool_txs_dropped(stale_txids, MemPoolDropReason::STALE_NONCE);
+        }
+        increment_stx_mempool_gc();
+        Ok(())
+    }
+
+    /// Garbage-collect the mempool once a new chain tip or burnchain block has been processed,
+    /// per the configured policy:
+    /// * transactions that have been in the mempool for `max_tx_age` or more burnchain blocks,
+    ///   as of the burnchain block at `burn_height`, are removed (see
+    ///   `garbage_collect_by_burn_age()`);
+    /// * transactions whose origin nonce is below their origin account's nonce at the chain tip
+    ///   are removed;
+    /// * if `max_tx_count` or `max_total_bytes` is set, transactions are evicted per
+    ///   `evict_to_limits()`, in case the limits were lowered since they were accepted.
+    /// This is a no-op if the mempool was already garbage-collected at `chain_tip` and
+    /// `burn_height`.
+    pub fn garbage_collect_at_tip<C>(
+        &mut self,
+        chain_tip: &StacksBlockId,
+        burn_height: u64,
+        clarity_tx: &mut C,
+        event_observer: Option<&dyn MemPoolEventDispatcher>,
+    ) -> Result<(), db_error>
+    where
+        C: ClarityConnection,
+    {
+        let gc_tip = (chain_tip.clone(), burn_height);
+        if self.last_gc_tip.as_ref() == Some(&gc_tip) {
+            return Ok(());
+        }
+
+        let max_tx_age = self.max_tx_age;
+        let mut mempool_tx = self.tx_begin()?;
+
+        MemPoolDB::garbage_collect_by_burn_age(
+            &mut mempool_tx,
+            burn_height,
+            max_tx_age,
+            event_observer,
+        )?;
+
+        MemPoolDB::garbage_collect_stale_nonces(
+            &mut mempool_tx,
+            |address| StacksChainState::get_nonce(clarity_tx, &address.clone().into()),
+            event_observer,
+        )?;
+
+        MemPoolDB::evict_to_limits(&mut mempool_tx, event_observer)?;
+
+        mempool_tx.commit()?;
+        self.last_gc_tip = Some(gc_tip);
+        Ok(())
+    }
+
+    /// Garbage-collect the mempool.  Remove transactions that were first seen at least
+    /// `max_burn_age` burnchain blocks before `burn_height`.  Transactions that have not been
+    /// seen by a garbage collection yet are stamped with `burn_height` first, so a transaction's
+    /// age is counted from the first garbage collection after it was accepted.
+    /// Returns the number of transactions removed.
+    pub fn garbage_collect_by_burn_age(
+        tx: &mut MemPoolTx,
+        burn_height: u64,
+        max_burn_age: u64,
+        event_observer: Option<&dyn MemPoolEventDispatcher>,
+    ) -> Result<usize, db_error> {
+        let sql = "UPDATE mempool SET accept_burn_height = ?1 WHERE accept_burn_height IS NULL";
+        tx.execute(sql, &[&u64_to_sql(burn_height)?])?;
+
+        if burn_height < max_burn_age {
+            return Ok(0);
+        }
+        let max_accept_burn_height = burn_height - max_burn_age;
+        let sql = "SELECT txid FROM mempool WHERE accept_burn_height <= ?1";
+        let expired_txids: Vec<Txid> =
+            query_rows(tx, sql, &[&u64_to_sql(max_accept_burn_height)?])?;
+        if expired_txids.len() == 0 {
+            return Ok(0);
+        }
+
+        debug!(
+            "Remove {} transaction(s) first seen at or before burnchain height {}",
+            expired_txids.len(),
+            max_accept_burn_height
+        );
+        let num_expired = expired_txids.len();
+        MemPoolDB::inner_drop_txs(tx, &expired_txids)?;
+        if let Some(event_observer) = event_observer {
+            event_observer.mempool_txs_dropped(expired_txids, MemPoolDropReason::STALE_COLLECT);
+        }
+        MemPoolStats::record_expired(num_expired);
+        increment_stx_mempool_gc();
+        Ok(num_expired)
+    }
+
     #[cfg(test)]
     pub fn clear_before_height(&mut self, min_height: u64) -> Result<(), db_error> {
         let mut tx = self.tx_begin()?;
@@ -2067,11 +2793,113 @@ impl MemPoolDB {
         if let Err(e) = monitoring::mempool_accepted(&txid, &chainstate.root_path) {
             warn!("Failed to monitor TX receive: {:?}", e; "txid" => %txid);
         }
+        monitoring::observe_mempool_tx_size(tx, len);
+
+        Ok(())
+    }
+
+    /// Check that a transaction pays at least this mempool's minimum fee rate.
+    /// Coinbases never pay a fee, so they are exempt.
+    pub fn check_min_fee_rate(&self, tx: &StacksTransaction) -> Result<(), MemPoolRejection> {
+        if self.min_fee_rate == 0 {
+            return Ok(());
+        }
+        if let TransactionPayload::Coinbase(..) = tx.payload {
+            return Ok(());
+        }
+        let fee_rate = tx.get_tx_fee() / tx.tx_len();
+        if fee_rate < self.min_fee_rate {
+            return Err(MemPoolRejection::BelowMinFeeRate(
+                fee_rate,
+                self.min_fee_rate,
+            ));
+        }
+        Ok(())
+    }
 
+    /// Check that the sponsor of a sponsored transaction can pay its fee out of its spendable
+    /// balance in the chain state `clarity_conn` reads.  Non-sponsored transactions always pass.
+    pub fn check_sponsor_balance<T: ClarityConnection>(
+        clarity_conn: &mut T,
+        tx: &StacksTransaction,
+    ) -> Result<(), MemPoolRejection> {
+        let sponsor: PrincipalData = match tx.sponsor_address() {
+            Some(sponsor_addr) => sponsor_addr.into(),
+            None => return Ok(()),
+        };
+        let fee = tx.get_tx_fee();
+        let balance = clarity_conn.with_clarity_db_readonly(|db| {
+            let burn_block_height = db.get_current_burnchain_block_height() as u64;
+            let v1_unlock_height = db.get_v1_unlock_height();
+            let v2_unlock_height = db.get_v2_unlock_height();
+            db.get_account_stx_balance(&sponsor)
+                .get_available_balance_at_burn_block(
+                    burn_block_height,
+                    v1_unlock_height,
+                    v2_unlock_height,
+                )
+        });
+        if balance < fee as u128 {
+            return Err(MemPoolRejection::InsufficientSponsorBalance {
+                sponsor,
+                fee,
+                balance,
+            });
+        }
         Ok(())
     }
 
-    /// One-shot submit
+    /// Check, without opening a DB transaction, whether `tx` would be rejected by `submit()` at
+    /// the given chain tip for conflicting with the transaction that already has its origin nonce:
+    /// that transaction was accepted at the same chain tip, and `tx` may not replace it by fee.
+    /// If the conflict is less certain (e.g. it may be a replace-across-fork), `submit()` decides.
+    /// A rejection is counted in the `MemPoolStats`.
+    pub fn check_conflicting_nonce(
+        &self,
+        consensus_hash: &ConsensusHash,
+        block_hash: &BlockHeaderHash,
+        tx: &StacksTransaction,
+    ) -> Result<(), MemPoolRejection> {
+        let txid = tx.txid();
+        let conflicting_txid = {
+            let conflict_cache = self
+                .conflict_cache
+                .lock()
+                .expect("FATAL: mempool conflict cache lock is poisoned");
+            let prior = match conflict_cache.get(&tx.origin_address(), tx.get_origin_nonce()) {
+                Some(prior) => prior,
+                None => {
+                    return Ok(());
+                }
+            };
+            if prior.txid == txid
+                || prior.consensus_hash != *consensus_hash
+                || prior.block_header_hash != *block_hash
+                || (self.enable_rbf
+                    && MemPoolDB::is_rbf_fee_sufficient(
+                        prior.tx_fee,
+                        tx.get_tx_fee(),
+                        self.rbf_fee_increase_pct,
+                    ))
+            {
+                return Ok(());
+            }
+            prior.txid.clone()
+        };
+
+        debug!("TX conflicts with origin nonce at the same chain tip";
+               "new_txid" => %txid,
+               "old_txid" => %conflicting_txid,
+               "origin_addr" => %tx.origin_address(),
+               "origin_nonce" => tx.get_origin_nonce());
+        let result = Err(MemPoolRejection::ConflictingNonceInMempool(
+            conflicting_txid,
+        ));
+        MemPoolStats::record_submit(&result);
+        result
+    }
+
+    /// One-shot submit.  The outcome is counted in the `MemPoolStats`.
     pub fn submit(
         &mut self,
         chainstate: &mut StacksChainState,
@@ -2082,6 +2910,31 @@ impl MemPoolDB {
         event_observer: Option<&dyn MemPoolEventDispatcher>,
         block_limit: &ExecutionCost,
         stacks_epoch_id: &StacksEpochId,
+    ) -> Result<(), MemPoolRejection> {
+        let result = self.inner_submit(
+            chainstate,
+            sortdb,
+            consensus_hash,
+            block_hash,
+            tx,
+            event_observer,
+            block_limit,
+            stacks_epoch_id,
+        );
+        MemPoolStats::record_submit(&result);
+        result
+    }
+
+    fn inner_submit(
+        &mut self,
+        chainstate: &mut StacksChainState,
+        sortdb: &SortitionDB,
+        consensus_hash: &ConsensusHash,
+        block_hash: &BlockHeaderHash,
+        tx: &StacksTransaction,
+        event_observer: Option<&dyn MemPoolEventDispatcher>,
+        block_limit: &ExecutionCost,
+        stacks_epoch_id: &StacksEpochId,
     ) -> Result<(), MemPoolRejection> {
         if self.is_tx_blacklisted(&tx.txid())? {
             // don't re-store this transaction
@@ -2089,6 +2942,19 @@ impl MemPoolDB {
             return Err(MemPoolRejection::TemporarilyBlacklisted);
         }
 
+        self.check_min_fee_rate(tx)?;
+
+        if self.validate_sponsor_balance && tx.sponsor_address().is_some() {
+            let tip = StacksBlockId::new(consensus_hash, block_hash);
+            chainstate
+                .with_read_only_clarity_tx(&sortdb.index_conn(), &tip, |clarity_conn| {
+                    MemPoolDB::check_sponsor_balance(clarity_conn, tx)
+                })
+                .ok_or_else(|| {
+                    MemPoolRejection::NoSuchChainTip(consensus_hash.clone(), block_hash.clone())
+                })??;
+        }
+
         let estimator_result = cost_estimates::estimate_fee_rate(
             tx,
             self.cost_estimator.as_ref(),
@@ -2297,10 +3163,11 @@ impl MemPoolDB {
 
     /// Inner code body for dropping transactions.
     /// Note that the bloom filter will *NOT* be updated.  That's the caller's job, if desired.
-    fn inner_drop_txs<'a>(tx: &DBTx<'a>, txids: &[Txid]) -> Result<(), db_error> {
+    fn inner_drop_txs(tx: &mut MemPoolTx, txids: &[Txid]) -> Result<(), db_error> {
         let sql = "DELETE FROM mempool WHERE txid = ?";
         for txid in txids.iter() {
             tx.execute(sql, &[txid])?;
+            tx.record_conflict_update(MemPoolConflictUpdate::Remove(txid.clone()));
         }
         Ok(())
     }
@@ -2308,8 +3175,8 @@ impl MemPoolDB {
     /// Drop transactions from the mempool.  Does not update the bloom filter, thereby ensuring that
     /// these transactions will still show up as present to the mempool sync logic.
     pub fn drop_txs(&mut self, txids: &[Txid]) -> Result<(), db_error> {
-        let mempool_tx = self.tx_begin()?;
-        MemPoolDB::inner_drop_txs(&mempool_tx, txids)?;
+        let mut mempool_tx = self.tx_begin()?;
+        MemPoolDB::inner_drop_txs(&mut mempool_tx, txids)?;
         mempool_tx.commit()?;
         Ok(())
     }
@@ -2324,8 +3191,8 @@ impl MemPoolDB {
         let blacklist_timeout = self.blacklist_timeout;
         let blacklist_max_size = self.blacklist_max_size;
 
-        let mempool_tx = self.tx_begin()?;
-        MemPoolDB::inner_drop_txs(&mempool_tx, txids)?;
+        let mut mempool_tx = self.tx_begin()?;
+        MemPoolDB::inner_drop_txs(&mut mempool_tx, txids)?;
         MemPoolDB::inner_blacklist_txs(&mempool_tx, txids, now)?;
         MemPoolDB::garbage_collect_tx_blacklist(
             &mempool_tx,
@@ -2367,6 +3234,18 @@ impl MemPoolDB {
         self.bloom_counter.to_bloom_filter(&self.conn())
     }
 
+    /// How many transactions are in the mempool, across all forks?
+    pub fn get_num_txs(conn: &DBConn) -> Result<u64, db_error> {
+        let sql = "SELECT COUNT(txid) FROM mempool";
+        query_int(conn, sql, NO_PARAMS).map(|cnt| cnt as u64)
+    }
+
+    /// Get the total size, in bytes, of the transactions in the mempool
+    pub fn get_total_bytes(conn: &DBConn) -> Result<u64, db_error> {
+        let sql = "SELECT IFNULL(SUM(length), 0) FROM mempool";
+        query_int(conn, sql, NO_PARAMS).map(|total| total as u64)
+    }
+
     /// Find maximum height represented in the mempool
     pub fn get_max_height(conn: &DBConn) -> Result<Option<u64>, db_error> {
         let sql = "SELECT 1 FROM mempool WHERE height >= 0";
@@ -58,10 +58,11 @@ pub const PEER_VERSION_EPOCH_2_1: u8 = 0x06;
 pub const PEER_VERSION_EPOCH_2_2: u8 = 0x07;
 pub const PEER_VERSION_EPOCH_2_3: u8 = 0x08;
 pub const PEER_VERSION_EPOCH_2_4: u8 = 0x09;
+pub const PEER_VERSION_EPOCH_2_5: u8 = 0x0a;
 
 // this should be updated to the latest network epoch version supported by
 //  this node. this will be checked by the `validate_epochs()` method.
-pub const PEER_NETWORK_EPOCH: u32 = PEER_VERSION_EPOCH_2_4 as u32;
+pub const PEER_NETWORK_EPOCH: u32 = PEER_VERSION_EPOCH_2_5 as u32;
 
 // set the fourth byte of the peer version
 pub const PEER_VERSION_MAINNET: u32 = PEER_VERSION_MAINNET_MAJOR | PEER_NETWORK_EPOCH;
@@ -120,6 +121,8 @@ pub const BITCOIN_MAINNET_STACKS_22_BURN_HEIGHT: u64 = 787_651;
 pub const BITCOIN_MAINNET_STACKS_23_BURN_HEIGHT: u64 = 788_240;
 /// This is Epoch-2.3, now Epoch-2.4, activation height proposed in SIP-024
 pub const BITCOIN_MAINNET_STACKS_24_BURN_HEIGHT: u64 = 791_551;
+/// This is Epoch-2.5 activation height, which brings in costs-4 and Clarity 3
+pub const BITCOIN_MAINNET_STACKS_25_BURN_HEIGHT: u64 = 840_360;
 
 pub const BITCOIN_TESTNET_FIRST_BLOCK_HEIGHT: u64 = 2000000;
 pub const BITCOIN_TESTNET_FIRST_BLOCK_TIMESTAMP: u32 = 1622691840;
@@ -130,6 +133,7 @@ pub const BITCOIN_TESTNET_STACKS_21_BURN_HEIGHT: u64 = 2_422_101;
 pub const BITCOIN_TESTNET_STACKS_22_BURN_HEIGHT: u64 = 2_431_300;
 pub const BITCOIN_TESTNET_STACKS_23_BURN_HEIGHT: u64 = 2_431_633;
 pub const BITCOIN_TESTNET_STACKS_24_BURN_HEIGHT: u64 = 2_432_545;
+pub const BITCOIN_TESTNET_STACKS_25_BURN_HEIGHT: u64 = 2_583_893;
 
 pub const BITCOIN_REGTEST_FIRST_BLOCK_HEIGHT: u64 = 0;
 pub const BITCOIN_REGTEST_FIRST_BLOCK_TIMESTAMP: u32 = 0;
@@ -243,7 +247,7 @@ pub fn check_fault_injection(fault_name: &str) -> bool {
 }
 
 lazy_static! {
-    pub static ref STACKS_EPOCHS_MAINNET: [StacksEpoch; 7] = [
+    pub static ref STACKS_EPOCHS_MAINNET: [StacksEpoch; 8] = [
         StacksEpoch {
             epoch_id: StacksEpochId::Epoch10,
             start_height: 0,
@@ -289,15 +293,22 @@ lazy_static! {
         StacksEpoch {
             epoch_id: StacksEpochId::Epoch24,
             start_height: BITCOIN_MAINNET_STACKS_24_BURN_HEIGHT,
-            end_height: STACKS_EPOCH_MAX,
+            end_height: BITCOIN_MAINNET_STACKS_25_BURN_HEIGHT,
             block_limit: BLOCK_LIMIT_MAINNET_21.clone(),
             network_epoch: PEER_VERSION_EPOCH_2_4
         },
+        StacksEpoch {
+            epoch_id: StacksEpochId::Epoch25,
+            start_height: BITCOIN_MAINNET_STACKS_25_BURN_HEIGHT,
+            end_height: STACKS_EPOCH_MAX,
+            block_limit: BLOCK_LIMIT_MAINNET_21.clone(),
+            network_epoch: PEER_VERSION_EPOCH_2_5
+        },
     ];
 }
 
 lazy_static! {
-    pub static ref STACKS_EPOCHS_TESTNET: [StacksEpoch; 7] = [
+    pub static ref STACKS_EPOCHS_TESTNET: [StacksEpoch; 8] = [
         StacksEpoch {
             epoch_id: StacksEpochId::Epoch10,
             start_height: 0,
@@ -343,15 +354,22 @@ lazy_static! {
         StacksEpoch {
             epoch_id: StacksEpochId::Epoch24,
             start_height: BITCOIN_TESTNET_STACKS_24_BURN_HEIGHT,
-            end_height: STACKS_EPOCH_MAX,
+            end_height: BITCOIN_TESTNET_STACKS_25_BURN_HEIGHT,
             block_limit: BLOCK_LIMIT_MAINNET_21.clone(),
             network_epoch: PEER_VERSION_EPOCH_2_4
         },
+        StacksEpoch {
+            epoch_id: StacksEpochId::Epoch25,
+            start_height: BITCOIN_TESTNET_STACKS_25_BURN_HEIGHT,
+            end_height: STACKS_EPOCH_MAX,
+            block_limit: BLOCK_LIMIT_MAINNET_21.clone(),
+            network_epoch: PEER_VERSION_EPOCH_2_5
+        },
     ];
 }
 
 lazy_static! {
-    pub static ref STACKS_EPOCHS_REGTEST: [StacksEpoch; 7] = [
+    pub static ref STACKS_EPOCHS_REGTEST: [StacksEpoch; 8] = [
         StacksEpoch {
             epoch_id: StacksEpochId::Epoch10,
             start_height: 0,
@@ -397,10 +415,17 @@ lazy_static! {
         StacksEpoch {
             epoch_id: StacksEpochId::Epoch24,
             start_height: 5000,
-            end_height: STACKS_EPOCH_MAX,
+            end_height: 6000,
             block_limit: HELIUM_BLOCK_LIMIT_20.clone(),
             network_epoch: PEER_VERSION_EPOCH_2_4
         },
+        StacksEpoch {
+            epoch_id: StacksEpochId::Epoch25,
+            start_height: 6000,
+            end_height: STACKS_EPOCH_MAX,
+            block_limit: HELIUM_BLOCK_LIMIT_20.clone(),
+            network_epoch: PEER_VERSION_EPOCH_2_5
+        },
     ];
 }
 
@@ -424,6 +449,10 @@ pub static STACKS_EPOCH_2_3_MARKER: u8 = 0x08;
 /// *or greater*.
 pub static STACKS_EPOCH_2_4_MARKER: u8 = 0x09;
 
+/// Stacks 2.5 epoch marker.  All block-commits in 2.5 must have a memo bitfield with this value
+/// *or greater*.
+pub static STACKS_EPOCH_2_5_MARKER: u8 = 0x0a;
+
 #[test]
 fn test_ord_for_stacks_epoch() {
     let epochs = STACKS_EPOCHS_MAINNET.clone();
@@ -447,6 +476,28 @@ fn test_ord_for_stacks_epoch() {
     assert_eq!(epochs[4].cmp(&epochs[3]), Ordering::Greater);
 }
 
+#[test]
+fn test_epoch_2_5_activation_heights() {
+    for (epochs, activation_height) in [
+        (
+            STACKS_EPOCHS_MAINNET.to_vec(),
+            BITCOIN_MAINNET_STACKS_25_BURN_HEIGHT,
+        ),
+        (
+            STACKS_EPOCHS_TESTNET.to_vec(),
+            BITCOIN_TESTNET_STACKS_25_BURN_HEIGHT,
+        ),
+    ] {
+        let epochs = StacksEpoch::validate_epochs(&epochs);
+        let epoch_2_4 = &epochs[StacksEpoch::find_epoch(&epochs, activation_height - 1).unwrap()];
+        let epoch_2_5 = &epochs[StacksEpoch::find_epoch(&epochs, activation_height).unwrap()];
+        assert_eq!(epoch_2_4.epoch_id, StacksEpochId::Epoch24);
+        assert_eq!(epoch_2_5.epoch_id, StacksEpochId::Epoch25);
+        assert_eq!(epoch_2_5.network_epoch, PEER_VERSION_EPOCH_2_5);
+        assert_eq!(epoch_2_5.end_height, STACKS_EPOCH_MAX);
+    }
+}
+
 #[test]
 fn test_ord_for_stacks_epoch_id() {
     assert_eq!(
@@ -504,6 +555,8 @@ pub trait StacksEpochExtension {
     #[cfg(test)]
     fn unit_test_2_4(epoch_2_0_block_height: u64) -> Vec<StacksEpoch>;
     #[cfg(test)]
+    fn unit_test_2_5(epoch_2_0_block_height: u64) -> Vec<StacksEpoch>;
+    #[cfg(test)]
     fn unit_test_2_1_only(epoch_2_0_block_height: u64) -> Vec<StacksEpoch>;
     fn all(
         epoch_2_0_block_height: u64,
@@ -897,6 +950,109 @@ impl StacksEpochExtension for StacksEpoch {
         ]
     }
 
+    #[cfg(test)]
+    fn unit_test_2_5(first_burnchain_height: u64) -> Vec<StacksEpoch> {
+        info!(
+            "StacksEpoch unit_test_2_5 first_burn_height = {}",
+            first_burnchain_height
+        );
+
+        vec![
+            StacksEpoch {
+                epoch_id: StacksEpochId::Epoch10,
+                start_height: 0,
+                end_height: first_burnchain_height,
+                block_limit: ExecutionCost::max_value(),
+                network_epoch: PEER_VERSION_EPOCH_1_0,
+            },
+            StacksEpoch {
+                epoch_id: StacksEpochId::Epoch20,
+                start_height: first_burnchain_height,
+                end_height: first_burnchain_height + 4,
+                block_limit: ExecutionCost::max_value(),
+                network_epoch: PEER_VERSION_EPOCH_2_0,
+            },
+            StacksEpoch {
+                epoch_id: StacksEpochId::Epoch2_05,
+                start_height: first_burnchain_height + 4,
+                end_height: first_burnchain_height + 8,
+                block_limit: ExecutionCost {
+                    write_length: 205205,
+                    write_count: 205205,
+                    read_length: 205205,
+                    read_count: 205205,
+                    runtime: 205205,
+                },
+                network_epoch: PEER_VERSION_EPOCH_2_05,
+            },
+            StacksEpoch {
+                epoch_id: StacksEpochId::Epoch21,
+                start_height: first_burnchain_height + 8,
+                end_height: first_burnchain_height + 12,
+                block_limit: ExecutionCost {
+                    write_length: 210210,
+                    write_count: 210210,
+                    read_length: 210210,
+                    read_count: 210210,
+                    runtime: 210210,
+                },
+                network_epoch: PEER_VERSION_EPOCH_2_1,
+            },
+            StacksEpoch {
+                epoch_id: StacksEpochId::Epoch22,
+                start_height: first_burnchain_height + 12,
+                end_height: first_burnchain_height + 16,
+                block_limit: ExecutionCost {
+                    write_length: 210210,
+                    write_count: 210210,
+                    read_length: 210210,
+                    read_count: 210210,
+                    runtime: 210210,
+                },
+                network_epoch: PEER_VERSION_EPOCH_2_2,
+            },
+            StacksEpoch {
+                epoch_id: StacksEpochId::Epoch23,
+                start_height: first_burnchain_height + 16,
+                end_height: first_burnchain_height + 20,
+                block_limit: ExecutionCost {
+                    write_length: 210210,
+                    write_count: 210210,
+                    read_length: 210210,
+                    read_count: 210210,
+                    runtime: 210210,
+                },
+                network_epoch: PEER_VERSION_EPOCH_2_3,
+            },
+            StacksEpoch {
+                epoch_id: StacksEpochId::Epoch24,
+                start_height: first_burnchain_height + 20,
+                end_height: first_burnchain_height + 24,
+                block_limit: ExecutionCost {
+                    write_length: 210210,
+                    write_count: 210210,
+                    read_length: 210210,
+                    read_count: 210210,
+                    runtime: 210210,
+                },
+                network_epoch: PEER_VERSION_EPOCH_2_4,
+            },
+            StacksEpoch {
+                epoch_id: StacksEpochId::Epoch25,
+                start_height: first_burnchain_height + 24,
+                end_height: STACKS_EPOCH_MAX,
+                block_limit: ExecutionCost {
+                    write_length: 210210,
+                    write_count: 210210,
+                    read_length: 210210,
+                    read_count: 210210,
+                    runtime: 210210,
+                },
+                network_epoch: PEER_VERSION_EPOCH_2_5,
+            },
+        ]
+    }
+
     #[cfg(test)]
     fn unit_test_2_1_only(first_burnchain_height: u64) -> Vec<StacksEpoch> {
         info!(
@@ -959,6 +1115,7 @@ impl StacksEpochExtension for StacksEpoch {
             StacksEpochId::Epoch22 => StacksEpoch::unit_test_2_2(first_burnchain_height),
             StacksEpochId::Epoch23 => StacksEpoch::unit_test_2_3(first_burnchain_height),
             StacksEpochId::Epoch24 => StacksEpoch::unit_test_2_4(first_burnchain_height),
+            StacksEpochId::Epoch25 => StacksEpoch::unit_test_2_5(first_burnchain_height),
         }
     }
 
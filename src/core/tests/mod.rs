@@ -14,6 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::cell::RefCell;
 use std::cmp;
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -27,13 +28,15 @@ use crate::chainstate::stacks::db::test::instantiate_chainstate;
 use crate::chainstate::stacks::db::test::instantiate_chainstate_with_balances;
 use crate::chainstate::stacks::db::StreamCursor;
 use crate::chainstate::stacks::events::StacksTransactionReceipt;
+use crate::chainstate::stacks::miner::TransactionEvent;
 use crate::chainstate::stacks::miner::TransactionResult;
 use crate::chainstate::stacks::test::codec_all_transactions;
 use crate::chainstate::stacks::{
     db::blocks::MemPoolRejection, db::StacksChainState, index::MarfTrieId, CoinbasePayload,
-    Error as ChainstateError, SinglesigHashMode, SinglesigSpendingCondition, StacksPrivateKey,
-    StacksPublicKey, StacksTransaction, StacksTransactionSigner, TokenTransferMemo,
-    TransactionAnchorMode, TransactionAuth, TransactionContractCall, TransactionPayload,
+    Error as ChainstateError, FungibleConditionCode, PostConditionPrincipal, SinglesigHashMode,
+    SinglesigSpendingCondition, StacksPrivateKey, StacksPublicKey, StacksTransaction,
+    StacksTransactionSigner, TokenTransferMemo, TransactionAnchorMode, TransactionAuth,
+    TransactionContractCall, TransactionPayload, TransactionPostCondition,
     TransactionPostConditionMode, TransactionPublicKeyEncoding, TransactionSmartContract,
     TransactionSpendingCondition, TransactionVersion,
 };
@@ -41,8 +44,12 @@ use crate::chainstate::stacks::{
     C32_ADDRESS_VERSION_MAINNET_SINGLESIG, C32_ADDRESS_VERSION_TESTNET_SINGLESIG,
 };
 use crate::core::mempool::db_get_all_nonces;
+use crate::core::mempool::MemPoolStats;
+use crate::core::mempool::MemPoolWalkCursor;
 use crate::core::mempool::MemPoolWalkSettings;
 use crate::core::mempool::TxTag;
+use crate::core::mempool::DEFAULT_RBF_FEE_INCREASE_PCT;
+use crate::core::mempool::{MemPoolDropReason, MemPoolEventDispatcher};
 use crate::core::mempool::{BLOOM_COUNTER_DEPTH, BLOOM_COUNTER_ERROR_RATE, MAX_BLOOM_COUNTER_TXS};
 use crate::core::FIRST_BURNCHAIN_CONSENSUS_HASH;
 use crate::core::FIRST_STACKS_BLOCK_HASH;
@@ -70,7 +77,9 @@ use stacks_common::util::{get_epoch_time_ms, get_epoch_time_secs};
 use stacks_common::util::{hash::hex_bytes, hash::to_hex, hash::*, log, secp256k1::*};
 
 use crate::chainstate::stacks::index::TrieHashExtension;
-use crate::chainstate::stacks::{StacksBlockHeader, StacksMicroblockHeader};
+use crate::chainstate::stacks::{
+    StacksBlock, StacksBlockHeader, StacksMicroblock, StacksMicroblockHeader,
+};
 use crate::codec::StacksMessageCodec;
 use crate::types::chainstate::{BlockHeaderHash, BurnchainHeaderHash};
 use crate::types::chainstate::{StacksAddress, StacksBlockId, StacksWorkScore, VRFSeed};
@@ -789,6 +798,295 @@ fn test_iterate_candidates_consider_no_estimate_tx_prob() {
     );
 }
 
+#[test]
+/// This test verifies that candidates are visited in descending fee-rate order, that a
+/// higher fee-rate transaction waits for its origin's lower-nonce transaction, and that
+/// transactions without a fee-rate estimate are visited last when
+/// `consider_no_estimate_tx_prob` is 0.
+fn test_iterate_candidates_fee_rate_order() {
+    let mut chainstate =
+        instantiate_chainstate_with_balances(false, 0x80000000, function_name!(), vec![]);
+    let chainstate_path = chainstate_path(function_name!());
+    let mut mempool = MemPoolDB::open_test(false, 0x80000000, &chainstate_path).unwrap();
+    let b_1 = make_block(
+        &mut chainstate,
+        ConsensusHash([0x1; 20]),
+        &(
+            FIRST_BURNCHAIN_CONSENSUS_HASH.clone(),
+            FIRST_STACKS_BLOCK_HASH.clone(),
+        ),
+        1,
+        1,
+    );
+    let b_2 = make_block(&mut chainstate, ConsensusHash([0x2; 20]), &b_1, 2, 2);
+
+    let mut mempool_settings = MemPoolWalkSettings::default();
+    mempool_settings.min_tx_fee = 10;
+    mempool_settings.consider_no_estimate_tx_prob = 0;
+    let mut tx_events = Vec::new();
+
+    let mut txs = codec_all_transactions(
+        &TransactionVersion::Testnet,
+        0x80000000,
+        &TransactionAnchorMode::Any,
+        &TransactionPostConditionMode::Allow,
+    );
+
+    // (origin address byte, origin nonce, fee rate)
+    let candidates = [
+        (0x01, 0, Some(10.0)),
+        (0x02, 0, Some(50.0)),
+        (0x03, 0, Some(30.0)),
+        (0x04, 0, Some(40.0)),
+        (0x05, 0, None),
+        (0x06, 0, Some(20.0)),
+        (0x06, 1, Some(100.0)),
+    ];
+    let mut txids = vec![];
+    for (addr_byte, nonce, fee_rate) in candidates.iter() {
+        let mut tx = txs.pop().unwrap();
+        let mut mempool_tx = mempool.tx_begin().unwrap();
+
+        let address = StacksAddress {
+            version: C32_ADDRESS_VERSION_TESTNET_SINGLESIG,
+            bytes: Hash160([*addr_byte; 20]),
+        };
+
+        tx.set_tx_fee(100);
+        let txid = tx.txid();
+        let tx_bytes = tx.serialize_to_vec();
+        let tx_fee = tx.get_tx_fee();
+        let height = 100;
+
+        MemPoolDB::try_add_tx(
+            &mut mempool_tx,
+            &mut chainstate,
+            &b_1.0,
+            &b_1.1,
+            txid,
+            tx_bytes,
+            tx_fee,
+            height,
+            &address,
+            *nonce,
+            &address,
+            *nonce,
+            None,
+        )
+        .unwrap();
+
+        mempool_tx
+            .execute(
+                "UPDATE mempool SET fee_rate = ? WHERE txid = ?",
+                rusqlite::params![fee_rate, &txid],
+            )
+            .unwrap();
+
+        mempool_tx.commit().unwrap();
+        txids.push(txid);
+    }
+
+    // Highest fee-rate first, except that 0x06's nonce 1 (rate 100) can only follow its
+    // nonce 0 (rate 20). The transaction without an estimate comes last.
+    let expected_order: Vec<Txid> = [1, 3, 2, 5, 6, 0, 4]
+        .iter()
+        .map(|i| txids[*i].clone())
+        .collect();
+
+    chainstate.with_read_only_clarity_tx(
+        &TEST_BURN_STATE_DB,
+        &StacksBlockHeader::make_index_block_hash(&b_2.0, &b_2.1),
+        |clarity_conn| {
+            let mut visited = vec![];
+            mempool
+                .iterate_candidates::<_, ChainstateError, _>(
+                    clarity_conn,
+                    &mut tx_events,
+                    2,
+                    mempool_settings.clone(),
+                    |_, available_tx, _| {
+                        visited.push(available_tx.tx.tx.txid());
+                        Ok(Some(
+                            // Generate any success result
+                            TransactionResult::success(
+                                &available_tx.tx.tx,
+                                available_tx.tx.metadata.tx_fee,
+                                StacksTransactionReceipt::from_stx_transfer(
+                                    available_tx.tx.tx.clone(),
+                                    vec![],
+                                    Value::okay(Value::Bool(true)).unwrap(),
+                                    ExecutionCost::zero(),
+                                ),
+                            )
+                            .convert_to_event(),
+                        ))
+                    },
+                )
+                .unwrap();
+            assert_eq!(visited, expected_order);
+        },
+    );
+}
+
+#[test]
+/// This test verifies that a walk which stops early leaves its cursor at the last candidate it
+/// finished with, so that a walk resumed from that cursor visits only the candidates after it.
+fn test_iterate_candidates_resume_from_cursor() {
+    let mut chainstate =
+        instantiate_chainstate_with_balances(false, 0x80000000, function_name!(), vec![]);
+    let chainstate_path = chainstate_path(function_name!());
+    let mut mempool = MemPoolDB::open_test(false, 0x80000000, &chainstate_path).unwrap();
+    let b_1 = make_block(
+        &mut chainstate,
+        ConsensusHash([0x1; 20]),
+        &(
+            FIRST_BURNCHAIN_CONSENSUS_HASH.clone(),
+            FIRST_STACKS_BLOCK_HASH.clone(),
+        ),
+        1,
+        1,
+    );
+    let b_2 = make_block(&mut chainstate, ConsensusHash([0x2; 20]), &b_1, 2, 2);
+
+    let mut mempool_settings = MemPoolWalkSettings::default();
+    mempool_settings.min_tx_fee = 10;
+    mempool_settings.consider_no_estimate_tx_prob = 0;
+    let mut tx_events = Vec::new();
+
+    let mut txs = codec_all_transactions(
+        &TransactionVersion::Testnet,
+        0x80000000,
+        &TransactionAnchorMode::Any,
+        &TransactionPostConditionMode::Allow,
+    );
+
+    // (origin address byte, fee rate)
+    let candidates = [
+        (0x01, Some(50.0)),
+        (0x02, Some(40.0)),
+        (0x03, Some(30.0)),
+        (0x04, Some(20.0)),
+        (0x05, None),
+    ];
+    let mut txids = vec![];
+    for (addr_byte, fee_rate) in candidates.iter() {
+        let mut tx = txs.pop().unwrap();
+        let mut mempool_tx = mempool.tx_begin().unwrap();
+
+        let address = StacksAddress {
+            version: C32_ADDRESS_VERSION_TESTNET_SINGLESIG,
+            bytes: Hash160([*addr_byte; 20]),
+        };
+
+        tx.set_tx_fee(100);
+        let txid = tx.txid();
+        let tx_bytes = tx.serialize_to_vec();
+        let tx_fee = tx.get_tx_fee();
+        let height = 100;
+
+        MemPoolDB::try_add_tx(
+            &mut mempool_tx,
+            &mut chainstate,
+            &b_1.0,
+            &b_1.1,
+            txid,
+            tx_bytes,
+            tx_fee,
+            height,
+            &address,
+            0,
+            &address,
+            0,
+            None,
+        )
+        .unwrap();
+
+        mempool_tx
+            .execute(
+                "UPDATE mempool SET fee_rate = ? WHERE txid = ?",
+                rusqlite::params![fee_rate, &txid],
+            )
+            .unwrap();
+
+        mempool_tx.commit().unwrap();
+        txids.push(txid);
+    }
+
+    chainstate.with_read_only_clarity_tx(
+        &TEST_BURN_STATE_DB,
+        &StacksBlockHeader::make_index_block_hash(&b_2.0, &b_2.1),
+        |clarity_conn| {
+            let mut cursor = MemPoolWalkCursor::new();
+
+            // stop on the third candidate, as if the miner ran out of time
+            let mut visited = vec![];
+            mempool
+                .iterate_candidates_from::<_, ChainstateError, _>(
+                    clarity_conn,
+                    &mut tx_events,
+                    2,
+                    mempool_settings.clone(),
+                    &mut cursor,
+                    |_, available_tx, _| {
+                        visited.push(available_tx.tx.tx.txid());
+                        if visited.len() > 2 {
+                            return Ok(None);
+                        }
+                        Ok(Some(
+                            // Generate any success result
+                            TransactionResult::success(
+                                &available_tx.tx.tx,
+                                available_tx.tx.metadata.tx_fee,
+                                StacksTransactionReceipt::from_stx_transfer(
+                                    available_tx.tx.tx.clone(),
+                                    vec![],
+                                    Value::okay(Value::Bool(true)).unwrap(),
+                                    ExecutionCost::zero(),
+                                ),
+                            )
+                            .convert_to_event(),
+                        ))
+                    },
+                )
+                .unwrap();
+            assert_eq!(visited, txids[0..3].to_vec());
+            assert_eq!(cursor.last_fee_rate, Some((40.0, txids[1].clone())));
+            assert_eq!(cursor.last_no_estimate, None);
+
+            // the resumed walk starts with the candidate the first one stopped on
+            let mut visited = vec![];
+            mempool
+                .iterate_candidates_from::<_, ChainstateError, _>(
+                    clarity_conn,
+                    &mut tx_events,
+                    2,
+                    mempool_settings.clone(),
+                    &mut cursor,
+                    |_, available_tx, _| {
+                        visited.push(available_tx.tx.tx.txid());
+                        Ok(Some(
+                            // Generate any success result
+                            TransactionResult::success(
+                                &available_tx.tx.tx,
+                                available_tx.tx.metadata.tx_fee,
+                                StacksTransactionReceipt::from_stx_transfer(
+                                    available_tx.tx.tx.clone(),
+                                    vec![],
+                                    Value::okay(Value::Bool(true)).unwrap(),
+                                    ExecutionCost::zero(),
+                                ),
+                            )
+                            .convert_to_event(),
+                        ))
+                    },
+                )
+                .unwrap();
+            assert_eq!(visited, txids[2..5].to_vec());
+            assert_eq!(cursor.last_no_estimate, Some(txids[4].clone()));
+        },
+    );
+}
+
 #[test]
 /// This test verifies that when a transaction is skipped, other transactions
 /// from the same address with higher nonces are not considered for inclusion in a block.
@@ -1396,7 +1694,7 @@ fn mempool_do_not_replace_tx() {
     )
     .unwrap_err();
     assert!(match err_resp {
-        MemPoolRejection::ConflictingNonceInMempool => true,
+        MemPoolRejection::ConflictingNonceInMempool(..) => true,
         _ => false,
     });
 
@@ -1405,8 +1703,20 @@ fn mempool_do_not_replace_tx() {
 }
 
 #[test]
-fn mempool_db_load_store_replace_tx() {
+fn mempool_conflict_cache() {
     let mut chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
+    let b_1 = make_block(
+        &mut chainstate,
+        ConsensusHash([0x1; 20]),
+        &(
+            FIRST_BURNCHAIN_CONSENSUS_HASH.clone(),
+            FIRST_STACKS_BLOCK_HASH.clone(),
+        ),
+        1,
+        1,
+    );
+    let b_2 = make_block(&mut chainstate, ConsensusHash([0x2; 20]), &b_1, 2, 2);
+
     let chainstate_path = chainstate_path(function_name!());
     let mut mempool = MemPoolDB::open_test(false, 0x80000000, &chainstate_path).unwrap();
 
@@ -1416,28 +1726,144 @@ fn mempool_db_load_store_replace_tx() {
         &TransactionAnchorMode::Any,
         &TransactionPostConditionMode::Allow,
     );
-    let num_txs = txs.len() as u64;
-
-    let mut mempool_tx = mempool.tx_begin().unwrap();
-
-    eprintln!("add all txs");
-    for (i, mut tx) in txs.drain(..).enumerate() {
-        // make sure each address is unique per tx (not the case in codec_all_transactions)
-        let origin_address = StacksAddress {
-            version: 22,
-            bytes: Hash160::from_data(&i.to_be_bytes()),
-        };
-        let sponsor_address = StacksAddress {
-            version: 22,
-            bytes: Hash160::from_data(&(i + 1).to_be_bytes()),
-        };
-
-        tx.set_tx_fee(123);
+    let mut tx = txs.pop().unwrap();
+    tx.set_tx_fee(123);
 
-        // test insert
+    let txid = tx.txid();
+    let origin_address = tx.origin_address();
+    let origin_nonce = tx.get_origin_nonce();
+    let (sponsor_address, sponsor_nonce) = match (tx.sponsor_address(), tx.get_sponsor_nonce()) {
+        (Some(addr), Some(nonce)) => (addr, nonce),
+        _ => (origin_address.clone(), origin_nonce),
+    };
 
-        let txid = tx.txid();
-        let mut tx_bytes = vec![];
+    let add_tx = |mempool: &mut MemPoolDB, chainstate: &mut StacksChainState, commit: bool| {
+        let mut mempool_tx = mempool.tx_begin().unwrap();
+        MemPoolDB::try_add_tx(
+            &mut mempool_tx,
+            chainstate,
+            &b_1.0,
+            &b_1.1,
+            txid.clone(),
+            tx.serialize_to_vec(),
+            tx.get_tx_fee(),
+            100,
+            &origin_address,
+            origin_nonce,
+            &sponsor_address,
+            sponsor_nonce,
+            None,
+        )
+        .unwrap();
+        if commit {
+            mempool_tx.commit().unwrap();
+        }
+    };
+
+    let mut conflicting_tx = tx.clone();
+    conflicting_tx.set_tx_fee(100);
+    let mut rbf_tx = tx.clone();
+    rbf_tx.set_tx_fee(1000);
+
+    // a transaction that is rolled back never makes it into the cache
+    add_tx(&mut mempool, &mut chainstate, false);
+    assert!(mempool
+        .check_conflicting_nonce(&b_1.0, &b_1.1, &conflicting_tx)
+        .is_ok());
+
+    add_tx(&mut mempool, &mut chainstate, true);
+
+    // a conflicting transaction at the same chain tip is rejected, and told what it conflicts
+    // with
+    match mempool
+        .check_conflicting_nonce(&b_1.0, &b_1.1, &conflicting_tx)
+        .unwrap_err()
+    {
+        MemPoolRejection::ConflictingNonceInMempool(conflicting_txid) => {
+            assert_eq!(conflicting_txid, txid)
+        }
+        e => panic!("Unexpected rejection: {:?}", &e),
+    }
+
+    // the transaction itself doesn't conflict
+    assert!(mempool.check_conflicting_nonce(&b_1.0, &b_1.1, &tx).is_ok());
+
+    // submit() decides whether a conflicting transaction may replace across forks...
+    assert!(mempool
+        .check_conflicting_nonce(&b_2.0, &b_2.1, &conflicting_tx)
+        .is_ok());
+
+    // ...or by fee
+    assert!(mempool
+        .check_conflicting_nonce(&b_1.0, &b_1.1, &rbf_tx)
+        .is_err());
+    mempool.enable_rbf = true;
+    assert!(mempool
+        .check_conflicting_nonce(&b_1.0, &b_1.1, &rbf_tx)
+        .is_ok());
+    assert!(mempool
+        .check_conflicting_nonce(&b_1.0, &b_1.1, &conflicting_tx)
+        .is_err());
+    mempool.enable_rbf = false;
+
+    // another mempool handle on the same DB shares the cache, so a transaction it drops no longer
+    // conflicts
+    let mut other_mempool = MemPoolDB::open_test(false, 0x80000000, &chainstate_path).unwrap();
+    assert!(other_mempool
+        .check_conflicting_nonce(&b_1.0, &b_1.1, &conflicting_tx)
+        .is_err());
+    other_mempool.drop_txs(&[txid.clone()]).unwrap();
+    assert!(mempool
+        .check_conflicting_nonce(&b_1.0, &b_1.1, &conflicting_tx)
+        .is_ok());
+
+    // garbage-collected transactions no longer conflict either
+    add_tx(&mut mempool, &mut chainstate, true);
+    assert!(other_mempool
+        .check_conflicting_nonce(&b_1.0, &b_1.1, &conflicting_tx)
+        .is_err());
+    mempool.clear_before_height(101).unwrap();
+    assert!(other_mempool
+        .check_conflicting_nonce(&b_1.0, &b_1.1, &conflicting_tx)
+        .is_ok());
+}
+
+#[test]
+fn mempool_db_load_store_replace_tx() {
+    let mut chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
+    let chainstate_path = chainstate_path(function_name!());
+    let mut mempool = MemPoolDB::open_test(false, 0x80000000, &chainstate_path).unwrap();
+    mempool.enable_rbf = true;
+    mempool.rbf_fee_increase_pct = 25;
+
+    let mut txs = codec_all_transactions(
+        &TransactionVersion::Testnet,
+        0x80000000,
+        &TransactionAnchorMode::Any,
+        &TransactionPostConditionMode::Allow,
+    );
+    let num_txs = txs.len() as u64;
+
+    let mut mempool_tx = mempool.tx_begin().unwrap();
+
+    eprintln!("add all txs");
+    for (i, mut tx) in txs.drain(..).enumerate() {
+        // make sure each address is unique per tx (not the case in codec_all_transactions)
+        let origin_address = StacksAddress {
+            version: 22,
+            bytes: Hash160::from_data(&i.to_be_bytes()),
+        };
+        let sponsor_address = StacksAddress {
+            version: 22,
+            bytes: Hash160::from_data(&(i + 1).to_be_bytes()),
+        };
+
+        tx.set_tx_fee(123);
+
+        // test insert
+
+        let txid = tx.txid();
+        let mut tx_bytes = vec![];
         tx.consensus_serialize(&mut tx_bytes).unwrap();
         let expected_tx = tx.clone();
 
@@ -1489,10 +1915,37 @@ fn mempool_db_load_store_replace_tx() {
         );
         assert_eq!(tx_info.metadata.block_height, height);
 
-        // test replace-by-fee with a higher fee
+        // test replace-by-fee with a higher fee, but less than 1.25x the fee
+        tx.set_tx_fee(153);
+        let mut tx_bytes = vec![];
+        tx.consensus_serialize(&mut tx_bytes).unwrap();
+
+        assert!(match MemPoolDB::try_add_tx(
+            &mut mempool_tx,
+            &mut chainstate,
+            &ConsensusHash([0x1; 20]),
+            &BlockHeaderHash([0x2; 32]),
+            tx.txid(),
+            tx_bytes,
+            tx.get_tx_fee(),
+            height,
+            &origin_address,
+            origin_nonce,
+            &sponsor_address,
+            sponsor_nonce,
+            None,
+        )
+        .unwrap_err()
+        {
+            MemPoolRejection::ConflictingNonceInMempool(..) => true,
+            _ => false,
+        });
+        assert!(MemPoolDB::db_has_tx(&mempool_tx, &txid).unwrap());
+
+        // test replace-by-fee with a fee that is at least 1.25x higher
         let old_txid = txid;
 
-        tx.set_tx_fee(124);
+        tx.set_tx_fee(154);
         assert!(txid != tx.txid());
 
         let txid = tx.txid();
@@ -1545,7 +1998,7 @@ fn mempool_db_load_store_replace_tx() {
 
         assert_eq!(tx_info.tx, expected_tx);
         assert_eq!(tx_info.metadata.len, len);
-        assert_eq!(tx_info.metadata.tx_fee, 124);
+        assert_eq!(tx_info.metadata.tx_fee, 154);
         assert_eq!(tx_info.metadata.origin_address, origin_address);
         assert_eq!(tx_info.metadata.origin_nonce, origin_nonce);
         assert_eq!(tx_info.metadata.sponsor_address, sponsor_address);
@@ -1560,7 +2013,7 @@ fn mempool_db_load_store_replace_tx() {
         // test replace-by-fee with a lower fee
         let old_txid = txid;
 
-        tx.set_tx_fee(122);
+        tx.set_tx_fee(153);
         assert!(txid != tx.txid());
 
         let txid = tx.txid();
@@ -1586,7 +2039,7 @@ fn mempool_db_load_store_replace_tx() {
         )
         .unwrap_err()
         {
-            MemPoolRejection::ConflictingNonceInMempool => true,
+            MemPoolRejection::ConflictingNonceInMempool(..) => true,
             _ => false,
         });
 
@@ -1650,6 +2103,7 @@ fn mempool_db_test_rbf() {
     let mut chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
     let chainstate_path = chainstate_path(function_name!());
     let mut mempool = MemPoolDB::open_test(false, 0x80000000, &chainstate_path).unwrap();
+    mempool.enable_rbf = true;
 
     // create initial transaction
     let mut mempool_tx = mempool.tx_begin().unwrap();
@@ -1679,7 +2133,14 @@ fn mempool_db_test_rbf() {
         auth: TransactionAuth::Standard(spending_condition.clone()),
         anchor_mode: TransactionAnchorMode::Any,
         post_condition_mode: TransactionPostConditionMode::Allow,
-        post_conditions: Vec::new(),
+        post_conditions: vec![
+            TransactionPostCondition::STX(
+                PostConditionPrincipal::Origin,
+                FungibleConditionCode::SentLe,
+                123,
+            );
+            6
+        ],
         payload,
     };
 
@@ -1730,11 +2191,12 @@ fn mempool_db_test_rbf() {
     let tx_info_opt = MemPoolDB::get_tx(&mempool_tx, &txid).unwrap();
     let tx_info = tx_info_opt.unwrap();
 
-    // test replace-by-fee with a higher fee, where the payload is smaller
+    // test replace-by-fee with a higher fee, where the transaction is smaller
     let old_txid = txid;
     let old_tx_fee = tx_fee;
 
-    tx.set_tx_fee(124);
+    tx.set_tx_fee(154);
+    tx.post_conditions = vec![];
     tx.payload =
         TransactionPayload::TokenTransfer(stx_address.into(), 123, TokenTransferMemo([0u8; 34]));
     assert!(txid != tx.txid());
@@ -1789,7 +2251,403 @@ fn mempool_db_test_rbf() {
     let tx_info = tx_info_opt.unwrap();
     assert_eq!(tx_info.metadata, tx_info_after);
     assert_eq!(tx_info.metadata.len, second_len);
-    assert_eq!(tx_info.metadata.tx_fee, 124);
+    assert_eq!(tx_info.metadata.tx_fee, 154);
+}
+
+/// Replace-by-fee is disabled by default, in which case a higher fee does not replace a
+/// transaction in the same fork.
+#[test]
+fn mempool_db_test_rbf_disabled() {
+    let mut chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
+    let chainstate_path = chainstate_path(function_name!());
+    let mut mempool = MemPoolDB::open_test(false, 0x80000000, &chainstate_path).unwrap();
+    assert!(!mempool.enable_rbf);
+
+    let mut tx = codec_all_transactions(
+        &TransactionVersion::Testnet,
+        0x80000000,
+        &TransactionAnchorMode::Any,
+        &TransactionPostConditionMode::Allow,
+    )
+    .remove(0);
+    let origin_address = tx.origin_address();
+    let origin_nonce = tx.get_origin_nonce();
+
+    let mut mempool_tx = mempool.tx_begin().unwrap();
+    let mut txids = vec![];
+    for fee in [100, 1000] {
+        tx.set_tx_fee(fee);
+        let txid = tx.txid();
+        let res = MemPoolDB::try_add_tx(
+            &mut mempool_tx,
+            &mut chainstate,
+            &ConsensusHash([0x1; 20]),
+            &BlockHeaderHash([0x2; 32]),
+            txid.clone(),
+            tx.serialize_to_vec(),
+            fee,
+            100,
+            &origin_address,
+            origin_nonce,
+            &origin_address,
+            origin_nonce,
+            None,
+        );
+        if txids.is_empty() {
+            res.unwrap();
+        } else {
+            assert!(match res.unwrap_err() {
+                MemPoolRejection::ConflictingNonceInMempool(..) => true,
+                _ => false,
+            });
+        }
+        txids.push(txid);
+    }
+
+    assert!(MemPoolDB::db_has_tx(&mempool_tx, &txids[0]).unwrap());
+    assert!(!MemPoolDB::db_has_tx(&mempool_tx, &txids[1]).unwrap());
+
+    // the same transaction is accepted once replace-by-fee is enabled
+    mempool_tx.set_enable_rbf(true);
+    MemPoolDB::try_add_tx(
+        &mut mempool_tx,
+        &mut chainstate,
+        &ConsensusHash([0x1; 20]),
+        &BlockHeaderHash([0x2; 32]),
+        txids[1].clone(),
+        tx.serialize_to_vec(),
+        1000,
+        100,
+        &origin_address,
+        origin_nonce,
+        &origin_address,
+        origin_nonce,
+        None,
+    )
+    .unwrap();
+    assert!(!MemPoolDB::db_has_tx(&mempool_tx, &txids[0]).unwrap());
+    assert!(MemPoolDB::db_has_tx(&mempool_tx, &txids[1]).unwrap());
+}
+
+#[test]
+fn mempool_min_fee_rate() {
+    let _chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
+    let chainstate_path = chainstate_path(function_name!());
+    let mut mempool = MemPoolDB::open_test(false, 0x80000000, &chainstate_path).unwrap();
+    assert_eq!(mempool.min_fee_rate, 0);
+
+    let txs = codec_all_transactions(
+        &TransactionVersion::Testnet,
+        0x80000000,
+        &TransactionAnchorMode::Any,
+        &TransactionPostConditionMode::Allow,
+    );
+    let mut transfer = txs
+        .iter()
+        .find(|tx| match tx.payload {
+            TransactionPayload::TokenTransfer(..) => true,
+            _ => false,
+        })
+        .unwrap()
+        .clone();
+    let mut coinbase = txs
+        .iter()
+        .find(|tx| match tx.payload {
+            TransactionPayload::Coinbase(..) => true,
+            _ => false,
+        })
+        .unwrap()
+        .clone();
+    transfer.set_tx_fee(0);
+    coinbase.set_tx_fee(0);
+
+    // no floor by default
+    mempool.check_min_fee_rate(&transfer).unwrap();
+
+    mempool.min_fee_rate = 1;
+    match mempool.check_min_fee_rate(&transfer).unwrap_err() {
+        MemPoolRejection::BelowMinFeeRate(fee_rate, min_fee_rate) => {
+            assert_eq!(fee_rate, 0);
+            assert_eq!(min_fee_rate, 1);
+        }
+        e => panic!("Unexpected rejection: {:?}", &e),
+    }
+
+    // coinbases are exempt
+    mempool.check_min_fee_rate(&coinbase).unwrap();
+
+    transfer.set_tx_fee(transfer.tx_len());
+    mempool.check_min_fee_rate(&transfer).unwrap();
+}
+
+#[test]
+fn mempool_check_sponsor_balance() {
+    let txs = codec_all_transactions(
+        &TransactionVersion::Testnet,
+        0x80000000,
+        &TransactionAnchorMode::Any,
+        &TransactionPostConditionMode::Allow,
+    );
+    let mut sponsored = txs
+        .iter()
+        .find(|tx| match (&tx.payload, &tx.auth) {
+            (TransactionPayload::TokenTransfer(..), TransactionAuth::Sponsored(..)) => true,
+            _ => false,
+        })
+        .unwrap()
+        .clone();
+    let mut standard = txs
+        .iter()
+        .find(|tx| match (&tx.payload, &tx.auth) {
+            (TransactionPayload::TokenTransfer(..), TransactionAuth::Standard(..)) => true,
+            _ => false,
+        })
+        .unwrap()
+        .clone();
+    let sponsor = sponsored.sponsor_address().unwrap();
+
+    let mut chainstate = instantiate_chainstate_with_balances(
+        false,
+        0x80000000,
+        function_name!(),
+        vec![(sponsor.clone(), 1000)],
+    );
+    let chain_tip = StacksBlockHeader::make_index_block_hash(
+        &FIRST_BURNCHAIN_CONSENSUS_HASH,
+        &FIRST_STACKS_BLOCK_HASH,
+    );
+    let mut check = |tx: &StacksTransaction| {
+        chainstate
+            .with_read_only_clarity_tx(&TEST_BURN_STATE_DB, &chain_tip, |clarity_conn| {
+                MemPoolDB::check_sponsor_balance(clarity_conn, tx)
+            })
+            .unwrap()
+    };
+
+    // the sponsor can pay exactly its balance
+    sponsored.set_tx_fee(1000);
+    check(&sponsored).unwrap();
+
+    sponsored.set_tx_fee(1001);
+    match check(&sponsored).unwrap_err() {
+        MemPoolRejection::InsufficientSponsorBalance {
+            sponsor: rejected_sponsor,
+            fee,
+            balance,
+        } => {
+            assert_eq!(rejected_sponsor, PrincipalData::from(sponsor));
+            assert_eq!(fee, 1001);
+            assert_eq!(balance, 1000);
+        }
+        e => panic!("Unexpected rejection: {:?}", &e),
+    }
+
+    // transactions without a sponsor are not checked
+    standard.set_tx_fee(u64::MAX);
+    check(&standard).unwrap();
+}
+
+#[test]
+fn test_rbf_fee_ratio() {
+    assert!(!MemPoolDB::is_rbf_fee_sufficient(100, 100, 25));
+    assert!(!MemPoolDB::is_rbf_fee_sufficient(100, 124, 25));
+    assert!(MemPoolDB::is_rbf_fee_sufficient(100, 125, 25));
+    assert!(MemPoolDB::is_rbf_fee_sufficient(100, 1000, 25));
+    assert!(!MemPoolDB::is_rbf_fee_sufficient(100, 99, 25));
+    assert!(!MemPoolDB::is_rbf_fee_sufficient(0, 0, 25));
+    assert!(MemPoolDB::is_rbf_fee_sufficient(0, 1, 25));
+    assert!(MemPoolDB::is_rbf_fee_sufficient(u64::MAX / 2, u64::MAX, 25));
+    assert!(!MemPoolDB::is_rbf_fee_sufficient(
+        u64::MAX - 1,
+        u64::MAX,
+        25
+    ));
+
+    // the default bump
+    assert!(!MemPoolDB::is_rbf_fee_sufficient(
+        100,
+        124,
+        DEFAULT_RBF_FEE_INCREASE_PCT
+    ));
+    assert!(MemPoolDB::is_rbf_fee_sufficient(
+        100,
+        125,
+        DEFAULT_RBF_FEE_INCREASE_PCT
+    ));
+
+    // a bump below the minimum is raised to it
+    assert!(!MemPoolDB::is_rbf_fee_sufficient(100, 110, 10));
+    assert!(!MemPoolDB::is_rbf_fee_sufficient(100, 124, 0));
+    assert!(MemPoolDB::is_rbf_fee_sufficient(100, 125, 0));
+
+    // a larger bump is honored
+    assert!(!MemPoolDB::is_rbf_fee_sufficient(100, 149, 50));
+    assert!(MemPoolDB::is_rbf_fee_sufficient(100, 150, 50));
+}
+
+#[test]
+fn test_get_txs_by_address() {
+    let mut chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
+    let chainstate_path = chainstate_path(function_name!());
+    let mut mempool = MemPoolDB::open_test(false, 0x80000000, &chainstate_path).unwrap();
+
+    let mut txs = codec_all_transactions(
+        &TransactionVersion::Testnet,
+        0x80000000,
+        &TransactionAnchorMode::Any,
+        &TransactionPostConditionMode::Allow,
+    );
+
+    let addr = |b: u8| StacksAddress {
+        version: C32_ADDRESS_VERSION_TESTNET_SINGLESIG,
+        bytes: Hash160([b; 20]),
+    };
+
+    // (origin, origin nonce, sponsor, sponsor nonce, fee rate)
+    let entries = [
+        (addr(0x01), 2, addr(0x01), 2, Some(5.0)),
+        (addr(0x01), 0, addr(0x01), 0, None),
+        (addr(0x02), 0, addr(0x01), 1, Some(2.0)),
+        (addr(0x03), 0, addr(0x03), 0, Some(1.0)),
+    ];
+    let mut txids = vec![];
+    let mut mempool_tx = mempool.tx_begin().unwrap();
+    for (origin, origin_nonce, sponsor, sponsor_nonce, fee_rate) in entries.iter() {
+        let mut tx = txs.pop().unwrap();
+        tx.set_tx_fee(123);
+        let txid = tx.txid();
+        let tx_bytes = tx.serialize_to_vec();
+        let tx_fee = tx.get_tx_fee();
+
+        MemPoolDB::try_add_tx(
+            &mut mempool_tx,
+            &mut chainstate,
+            &ConsensusHash([0x1; 20]),
+            &BlockHeaderHash([0x2; 32]),
+            txid,
+            tx_bytes,
+            tx_fee,
+            100,
+            origin,
+            *origin_nonce,
+            sponsor,
+            *sponsor_nonce,
+            None,
+        )
+        .unwrap();
+
+        mempool_tx
+            .execute(
+                "UPDATE mempool SET fee_rate = ? WHERE txid = ?",
+                rusqlite::params![fee_rate, &txid],
+            )
+            .unwrap();
+        txids.push(txid);
+    }
+    mempool_tx.commit().unwrap();
+
+    // 0x01 originates two transactions and sponsors a third; they come back ordered by the
+    // nonce 0x01 uses in each.
+    let found = MemPoolDB::get_txs_by_address(mempool.conn(), &addr(0x01)).unwrap();
+    let found_txids: Vec<_> = found.iter().map(|t| t.tx.metadata.txid).collect();
+    assert_eq!(found_txids, vec![txids[1], txids[2], txids[0]]);
+    let found_rates: Vec<_> = found.iter().map(|t| t.fee_rate).collect();
+    assert_eq!(found_rates, vec![None, Some(2.0), Some(5.0)]);
+
+    // 0x02 only originates the sponsored transaction
+    let found = MemPoolDB::get_txs_by_address(mempool.conn(), &addr(0x02)).unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].tx.metadata.txid, txids[2]);
+
+    // nothing for an unknown address
+    assert!(MemPoolDB::get_txs_by_address(mempool.conn(), &addr(0x04))
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_get_possible_next_nonce() {
+    let mut chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
+    let chainstate_path = chainstate_path(function_name!());
+    let mut mempool = MemPoolDB::open_test(false, 0x80000000, &chainstate_path).unwrap();
+
+    let mut txs = codec_all_transactions(
+        &TransactionVersion::Testnet,
+        0x80000000,
+        &TransactionAnchorMode::Any,
+        &TransactionPostConditionMode::Allow,
+    );
+
+    let addr = |b: u8| StacksAddress {
+        version: C32_ADDRESS_VERSION_TESTNET_SINGLESIG,
+        bytes: Hash160([b; 20]),
+    };
+
+    // (origin, origin nonce, sponsor, sponsor nonce)
+    // 0x01 uses nonces 0, 1 (as a sponsor), 3 and 4, leaving a gap at 2
+    let entries = [
+        (addr(0x01), 0, addr(0x01), 0),
+        (addr(0x02), 0, addr(0x01), 1),
+        (addr(0x01), 3, addr(0x01), 3),
+        (addr(0x01), 4, addr(0x01), 4),
+    ];
+    let mut mempool_tx = mempool.tx_begin().unwrap();
+    for (origin, origin_nonce, sponsor, sponsor_nonce) in entries.iter() {
+        let mut tx = txs.pop().unwrap();
+        tx.set_tx_fee(123);
+        let txid = tx.txid();
+        let tx_bytes = tx.serialize_to_vec();
+        let tx_fee = tx.get_tx_fee();
+
+        MemPoolDB::try_add_tx(
+            &mut mempool_tx,
+            &mut chainstate,
+            &ConsensusHash([0x1; 20]),
+            &BlockHeaderHash([0x2; 32]),
+            txid,
+            tx_bytes,
+            tx_fee,
+            100,
+            origin,
+            *origin_nonce,
+            sponsor,
+            *sponsor_nonce,
+            None,
+        )
+        .unwrap();
+    }
+    mempool_tx.commit().unwrap();
+
+    // the gap is returned, not the nonce after the highest one
+    assert_eq!(
+        MemPoolDB::get_possible_next_nonce(mempool.conn(), &addr(0x01), 0).unwrap(),
+        2
+    );
+    assert_eq!(
+        MemPoolDB::get_possible_next_nonce(mempool.conn(), &addr(0x01), 2).unwrap(),
+        2
+    );
+    // nonces below the starting nonce are ignored
+    assert_eq!(
+        MemPoolDB::get_possible_next_nonce(mempool.conn(), &addr(0x01), 3).unwrap(),
+        5
+    );
+    assert_eq!(
+        MemPoolDB::get_possible_next_nonce(mempool.conn(), &addr(0x01), 7).unwrap(),
+        7
+    );
+
+    // 0x02's only transaction is sponsored, but it still uses its origin nonce
+    assert_eq!(
+        MemPoolDB::get_possible_next_nonce(mempool.conn(), &addr(0x02), 0).unwrap(),
+        1
+    );
+
+    // nothing in the mempool for an unknown address
+    assert_eq!(
+        MemPoolDB::get_possible_next_nonce(mempool.conn(), &addr(0x04), 3).unwrap(),
+        3
+    );
 }
 
 #[test]
@@ -3013,3 +3871,478 @@ fn test_drop_and_blacklist_txs_by_size() {
 
     assert_eq!(num_blacklisted, 5);
 }
+
+/// Records the transactions dropped and evicted from the mempool, and why.
+struct DroppedTxRecorder {
+    dropped: RefCell<Vec<(Vec<Txid>, String)>>,
+    evicted: RefCell<Vec<(Txid, String)>>,
+}
+
+impl DroppedTxRecorder {
+    fn new() -> DroppedTxRecorder {
+        DroppedTxRecorder {
+            dropped: RefCell::new(vec![]),
+            evicted: RefCell::new(vec![]),
+        }
+    }
+
+    fn dropped_for(&self, reason: &str) -> Vec<Txid> {
+        self.dropped
+            .borrow()
+            .iter()
+            .filter(|(_, dropped_reason)| dropped_reason == reason)
+            .map(|(txids, _)| txids.clone())
+            .flatten()
+            .collect()
+    }
+}
+
+impl MemPoolEventDispatcher for DroppedTxRecorder {
+    fn mempool_txs_dropped(&self, txids: Vec<Txid>, reason: MemPoolDropReason) {
+        self.dropped.borrow_mut().push((txids, reason.to_string()));
+    }
+
+    fn mempool_tx_evicted(&self, txid: Txid, reason: MemPoolDropReason) {
+        self.evicted.borrow_mut().push((txid, reason.to_string()));
+    }
+
+    fn mined_block_event(
+        &self,
+        _target_burn_height: u64,
+        _block: &StacksBlock,
+        _block_size_bytes: u64,
+        _consumed: &ExecutionCost,
+        _confirmed_microblock_cost: &ExecutionCost,
+        _tx_results: Vec<TransactionEvent>,
+    ) {
+    }
+
+    fn mined_microblock_event(
+        &self,
+        _microblock: &StacksMicroblock,
+        _tx_results: Vec<TransactionEvent>,
+        _anchor_block_consensus_hash: ConsensusHash,
+        _anchor_block: BlockHeaderHash,
+    ) {
+    }
+}
+
+/// Store a token transfer from `origin_address` to the mempool, and return its txid.
+fn add_gc_test_tx(
+    mempool: &mut MemPoolDB,
+    chainstate: &mut StacksChainState,
+    origin_address: &StacksAddress,
+    origin_nonce: u64,
+    tx_fee: u64,
+    height: u64,
+) -> Txid {
+    add_gc_test_tx_with_observer(
+        mempool,
+        chainstate,
+        origin_address,
+        origin_nonce,
+        tx_fee,
+        height,
+        None,
+    )
+}
+
+fn add_gc_test_tx_with_observer(
+    mempool: &mut MemPoolDB,
+    chainstate: &mut StacksChainState,
+    origin_address: &StacksAddress,
+    origin_nonce: u64,
+    tx_fee: u64,
+    height: u64,
+    event_observer: Option<&dyn MemPoolEventDispatcher>,
+) -> Txid {
+    let mut tx = codec_all_transactions(
+        &TransactionVersion::Testnet,
+        0x80000000,
+        &TransactionAnchorMode::Any,
+        &TransactionPostConditionMode::Allow,
+    )
+    .into_iter()
+    .find(|tx| match tx.payload {
+        TransactionPayload::TokenTransfer(..) => true,
+        _ => false,
+    })
+    .unwrap();
+
+    // make the txid unique per origin address
+    if let TransactionPayload::TokenTransfer(_, _, ref mut memo) = tx.payload {
+        memo.0[..20].copy_from_slice(&origin_address.bytes.0);
+    }
+    tx.set_origin_nonce(origin_nonce);
+    tx.set_tx_fee(tx_fee);
+
+    let txid = tx.txid();
+    let mut mempool_tx = mempool.tx_begin().unwrap();
+    MemPoolDB::try_add_tx(
+        &mut mempool_tx,
+        chainstate,
+        &ConsensusHash([0x1; 20]),
+        &BlockHeaderHash([0x2; 32]),
+        txid.clone(),
+        tx.serialize_to_vec(),
+        tx_fee,
+        height,
+        origin_address,
+        origin_nonce,
+        origin_address,
+        origin_nonce,
+        event_observer,
+    )
+    .unwrap();
+    mempool_tx.commit().unwrap();
+    txid
+}
+
+#[test]
+fn mempool_garbage_collect_stale_nonces() {
+    let mut chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
+    let chainstate_path = chainstate_path(function_name!());
+    let mut mempool = MemPoolDB::open_test(false, 0x80000000, &chainstate_path).unwrap();
+
+    let addr_a = StacksAddress {
+        version: 22,
+        bytes: Hash160([0xaa; 20]),
+    };
+    let addr_b = StacksAddress {
+        version: 22,
+        bytes: Hash160([0xbb; 20]),
+    };
+
+    let txids_a: Vec<_> = (0..5)
+        .map(|nonce| add_gc_test_tx(&mut mempool, &mut chainstate, &addr_a, nonce, 1000, 100))
+        .collect();
+    let txids_b: Vec<_> = (0..3)
+        .map(|nonce| add_gc_test_tx(&mut mempool, &mut chainstate, &addr_b, nonce, 1000, 100))
+        .collect();
+
+    // addr_a has since had 3 transactions mined; addr_b has had none
+    let recorder = DroppedTxRecorder::new();
+    let mut mempool_tx = mempool.tx_begin().unwrap();
+    MemPoolDB::garbage_collect_stale_nonces(
+        &mut mempool_tx,
+        |address| if *address == addr_a { 3 } else { 0 },
+        Some(&recorder),
+    )
+    .unwrap();
+    mempool_tx.commit().unwrap();
+
+    for txid in txids_a[..3].iter() {
+        assert!(!mempool.has_tx(txid));
+    }
+    for txid in txids_a[3..].iter().chain(txids_b.iter()) {
+        assert!(mempool.has_tx(txid));
+    }
+
+    let mut dropped = recorder.dropped_for("StaleNonce");
+    dropped.sort();
+    let mut expected = txids_a[..3].to_vec();
+    expected.sort();
+    assert_eq!(dropped, expected);
+}
+
+#[test]
+fn mempool_evict_to_lowered_limits() {
+    let mut chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
+    let chainstate_path = chainstate_path(function_name!());
+    let mut mempool = MemPoolDB::open_test(false, 0x80000000, &chainstate_path).unwrap();
+
+    let origin_address = StacksAddress {
+        version: 22,
+        bytes: Hash160([0xcc; 20]),
+    };
+
+    // all transactions are the same size, so the fee determines the fee rate
+    let txids: Vec<_> = (0..5)
+        .map(|nonce| {
+            add_gc_test_tx(
+                &mut mempool,
+                &mut chainstate,
+                &origin_address,
+                nonce,
+                100 * (nonce + 1),
+                100,
+            )
+        })
+        .collect();
+    let tx_len = MemPoolDB::get_tx(mempool.conn(), &txids[0])
+        .unwrap()
+        .unwrap()
+        .metadata
+        .len;
+
+    // within the limits, so nothing happens
+    let recorder = DroppedTxRecorder::new();
+    mempool.max_total_bytes = Some(5 * tx_len);
+    let mut mempool_tx = mempool.tx_begin().unwrap();
+    MemPoolDB::evict_to_limits(&mut mempool_tx, Some(&recorder)).unwrap();
+    mempool_tx.commit().unwrap();
+    for txid in txids.iter() {
+        assert!(mempool.has_tx(txid));
+    }
+    assert_eq!(recorder.evicted.borrow().len(), 0);
+
+    // the limit was lowered after the transactions were accepted, so the two cheapest go
+    mempool.max_total_bytes = Some(3 * tx_len + 1);
+    let mut mempool_tx = mempool.tx_begin().unwrap();
+    MemPoolDB::evict_to_limits(&mut mempool_tx, Some(&recorder)).unwrap();
+    mempool_tx.commit().unwrap();
+    for txid in txids[..2].iter() {
+        assert!(!mempool.has_tx(txid));
+    }
+    for txid in txids[2..].iter() {
+        assert!(mempool.has_tx(txid));
+    }
+    assert_eq!(
+        *recorder.evicted.borrow(),
+        vec![
+            (txids[0].clone(), "MaxTotalBytes".to_string()),
+            (txids[1].clone(), "MaxTotalBytes".to_string())
+        ]
+    );
+    assert_eq!(recorder.dropped.borrow().len(), 0);
+}
+
+/// Garbage-collect transactions that have been in the mempool for at least 3 burnchain blocks,
+/// as of `burn_height`, and return how many were removed.
+fn garbage_collect_by_test_burn_age(
+    mempool: &mut MemPoolDB,
+    burn_height: u64,
+    recorder: &DroppedTxRecorder,
+) -> usize {
+    let mut mempool_tx = mempool.tx_begin().unwrap();
+    let num_removed =
+        MemPoolDB::garbage_collect_by_burn_age(&mut mempool_tx, burn_height, 3, Some(recorder))
+            .unwrap();
+    mempool_tx.commit().unwrap();
+    num_removed
+}
+
+#[test]
+fn mempool_garbage_collect_by_burn_age() {
+    let mut chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
+    let chainstate_path = chainstate_path(function_name!());
+    let mut mempool = MemPoolDB::open_test(false, 0x80000000, &chainstate_path).unwrap();
+
+    let origin_address = StacksAddress {
+        version: 22,
+        bytes: Hash160([0xcc; 20]),
+    };
+    let recorder = DroppedTxRecorder::new();
+    let stats_before = MemPoolStats::load(&mempool).unwrap();
+
+    // the first garbage collection stamps the transaction with the burnchain height it saw it at
+    let old_txid = add_gc_test_tx(&mut mempool, &mut chainstate, &origin_address, 0, 100, 100);
+    assert_eq!(
+        garbage_collect_by_test_burn_age(&mut mempool, 200, &recorder),
+        0
+    );
+    let new_txid = add_gc_test_tx(&mut mempool, &mut chainstate, &origin_address, 1, 100, 100);
+    for burn_height in 201..203 {
+        assert_eq!(
+            garbage_collect_by_test_burn_age(&mut mempool, burn_height, &recorder),
+            0
+        );
+        assert!(mempool.has_tx(&old_txid));
+        assert!(mempool.has_tx(&new_txid));
+    }
+
+    // the older transaction is removed three burnchain blocks after it was first seen, and the
+    // newer one a block later
+    assert_eq!(
+        garbage_collect_by_test_burn_age(&mut mempool, 203, &recorder),
+        1
+    );
+    assert!(!mempool.has_tx(&old_txid));
+    assert!(mempool.has_tx(&new_txid));
+
+    assert_eq!(
+        garbage_collect_by_test_burn_age(&mut mempool, 204, &recorder),
+        1
+    );
+    assert!(!mempool.has_tx(&new_txid));
+    assert_eq!(
+        recorder.dropped_for("StaleGarbageCollect"),
+        vec![old_txid, new_txid]
+    );
+
+    // the counters are shared with any other test in this process
+    let stats = MemPoolStats::load(&mempool).unwrap();
+    assert!(stats.expired >= stats_before.expired + 2);
+}
+
+/// Garbage-collect the mempool at the test chain tip and the given burnchain height
+fn garbage_collect_at_test_tip(
+    mempool: &mut MemPoolDB,
+    chainstate: &mut StacksChainState,
+    burn_height: u64,
+    recorder: &DroppedTxRecorder,
+) {
+    let chain_tip = StacksBlockHeader::make_index_block_hash(
+        &FIRST_BURNCHAIN_CONSENSUS_HASH,
+        &FIRST_STACKS_BLOCK_HASH,
+    );
+    chainstate
+        .with_read_only_clarity_tx(&TEST_BURN_STATE_DB, &chain_tip, |clarity_conn| {
+            mempool
+                .garbage_collect_at_tip(&chain_tip, burn_height, clarity_conn, Some(recorder))
+                .unwrap();
+        })
+        .unwrap();
+}
+
+#[test]
+fn mempool_garbage_collect_at_tip() {
+    let mut chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
+    let chainstate_path = chainstate_path(function_name!());
+    let mut mempool = MemPoolDB::open_test(false, 0x80000000, &chainstate_path).unwrap();
+    mempool.max_tx_age = 50;
+
+    let origin_address = StacksAddress {
+        version: 22,
+        bytes: Hash160([0xdd; 20]),
+    };
+    let recorder = DroppedTxRecorder::new();
+
+    // age is counted in burnchain blocks, from the first garbage collection that sees a
+    // transaction, regardless of the Stacks height it was accepted at
+    let old_txid = add_gc_test_tx(&mut mempool, &mut chainstate, &origin_address, 0, 1000, 100);
+    garbage_collect_at_test_tip(&mut mempool, &mut chainstate, 100, &recorder);
+    let fresh_txid = add_gc_test_tx(&mut mempool, &mut chainstate, &origin_address, 1, 1000, 10);
+    garbage_collect_at_test_tip(&mut mempool, &mut chainstate, 149, &recorder);
+    assert!(mempool.has_tx(&old_txid));
+    assert!(mempool.has_tx(&fresh_txid));
+
+    // the account nonce is still 0, so only the old transaction is dropped
+    garbage_collect_at_test_tip(&mut mempool, &mut chainstate, 150, &recorder);
+    assert!(!mempool.has_tx(&old_txid));
+    assert!(mempool.has_tx(&fresh_txid));
+    assert_eq!(recorder.dropped_for("StaleGarbageCollect"), vec![old_txid]);
+    assert_eq!(recorder.dropped_for("StaleNonce").len(), 0);
+
+    // already garbage-collected at this tip and burnchain height, so this is a no-op
+    let other_old_txid =
+        add_gc_test_tx(&mut mempool, &mut chainstate, &origin_address, 2, 1000, 10);
+    mempool
+        .db
+        .execute(
+            "UPDATE mempool SET accept_burn_height = 1 WHERE txid = ?1",
+            rusqlite::params![&other_old_txid],
+        )
+        .unwrap();
+    garbage_collect_at_test_tip(&mut mempool, &mut chainstate, 150, &recorder);
+    assert!(mempool.has_tx(&other_old_txid));
+
+    // but a new burnchain block triggers another garbage collection
+    garbage_collect_at_test_tip(&mut mempool, &mut chainstate, 151, &recorder);
+    assert!(!mempool.has_tx(&other_old_txid));
+    assert!(mempool.has_tx(&fresh_txid));
+}
+
+#[test]
+fn mempool_evict_to_limits() {
+    let mut chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
+    let chainstate_path = chainstate_path(function_name!());
+    let mut mempool = MemPoolDB::open_test(false, 0x80000000, &chainstate_path).unwrap();
+    mempool.max_tx_count = Some(3);
+
+    let origin_address = StacksAddress {
+        version: 22,
+        bytes: Hash160([0xee; 20]),
+    };
+    let recorder = DroppedTxRecorder::new();
+
+    // all transactions are the same size, so the fee determines the fee rate
+    let txids: Vec<_> = [300, 100, 200]
+        .iter()
+        .enumerate()
+        .map(|(nonce, tx_fee)| {
+            add_gc_test_tx_with_observer(
+                &mut mempool,
+                &mut chainstate,
+                &origin_address,
+                nonce as u64,
+                *tx_fee,
+                100,
+                Some(&recorder),
+            )
+        })
+        .collect();
+    for txid in txids.iter() {
+        assert!(mempool.has_tx(txid));
+    }
+    assert_eq!(recorder.evicted.borrow().len(), 0);
+
+    // a fourth transaction pushes out the cheapest one
+    let txid_400 = add_gc_test_tx_with_observer(
+        &mut mempool,
+        &mut chainstate,
+        &origin_address,
+        3,
+        400,
+        100,
+        Some(&recorder),
+    );
+    assert!(mempool.has_tx(&txid_400));
+    assert!(!mempool.has_tx(&txids[1]));
+    assert_eq!(
+        *recorder.evicted.borrow(),
+        vec![(txids[1].clone(), "MaxTxCount".to_string())]
+    );
+
+    // a transaction cheaper than all the others is evicted as soon as it is accepted
+    let txid_50 = add_gc_test_tx_with_observer(
+        &mut mempool,
+        &mut chainstate,
+        &origin_address,
+        4,
+        50,
+        100,
+        Some(&recorder),
+    );
+    assert!(!mempool.has_tx(&txid_50));
+    assert_eq!(
+        recorder.evicted.borrow().last(),
+        Some(&(txid_50, "MaxTxCount".to_string()))
+    );
+
+    // the byte limit applies too, and among equal fee rates the oldest transaction goes first
+    let tx_len = MemPoolDB::get_tx(mempool.conn(), &txids[0])
+        .unwrap()
+        .unwrap()
+        .metadata
+        .len;
+    mempool.max_tx_count = None;
+    mempool.max_total_bytes = Some(3 * tx_len);
+    mempool
+        .db
+        .execute(
+            "UPDATE mempool SET accept_time = 1 WHERE txid = ?1",
+            rusqlite::params![&txids[2]],
+        )
+        .unwrap();
+    let txid_200 = add_gc_test_tx_with_observer(
+        &mut mempool,
+        &mut chainstate,
+        &origin_address,
+        5,
+        200,
+        100,
+        Some(&recorder),
+    );
+    assert!(mempool.has_tx(&txid_200));
+    assert!(!mempool.has_tx(&txids[2]));
+    assert!(mempool.has_tx(&txids[0]));
+    assert!(mempool.has_tx(&txid_400));
+    assert_eq!(
+        recorder.evicted.borrow().last(),
+        Some(&(txids[2].clone(), "MaxTotalBytes".to_string()))
+    );
+
+    // evictions are not reported as drops
+    assert_eq!(recorder.dropped.borrow().len(), 0);
+}
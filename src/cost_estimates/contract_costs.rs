@@ -0,0 +1,260 @@
+use std::path::Path;
+
+use rusqlite::Transaction as SqliteTransaction;
+use rusqlite::{Connection, Error as SqliteError};
+
+use crate::chainstate::stacks::TransactionPayload;
+use crate::core::StacksEpochId;
+use crate::util_lib::db::{sqlite_open, table_exists, tx_begin_immediate_sqlite};
+use clarity::vm::costs::ExecutionCost;
+use clarity::vm::types::{QualifiedContractIdentifier, StandardPrincipalData};
+use clarity::vm::ClarityName;
+
+use super::pessimistic::{contract_call_epoch_marker, CostField};
+use super::{CostEstimator, EstimatorError};
+
+/// Width of a histogram bucket: bucket `k > 0` holds the values in `[r^(k-1), r^k)`, where `r` is
+/// this ratio. Reporting a bucket's geometric midpoint is therefore off by at most ~2.5%.
+const BUCKET_RATIO: f64 = 1.05;
+/// Every new observation of a function scales the weight of its earlier observations by this
+/// factor, so an observation counts half as much after ~34 newer ones.
+const DECAY_RATE: f64 = 0.98;
+/// Buckets whose weight decays below this are dropped.
+const MIN_BUCKET_WEIGHT: f64 = 0.01;
+
+const CREATE_TABLE: &'static str = "
+CREATE TABLE contract_function_costs (
+    epoch TEXT NOT NULL,
+    contract_id TEXT NOT NULL,
+    function_name TEXT NOT NULL,
+    cost_field TEXT NOT NULL,
+    bucket INTEGER NOT NULL,
+    weight REAL NOT NULL,
+    PRIMARY KEY (epoch, contract_id, function_name, cost_field, bucket)
+)";
+
+/// CostEstimator which estimates the cost of a contract call from the costs previously observed
+/// for calls to the same contract function.
+///
+/// For each `(contract, function)` pair and each dimension of `ExecutionCost`, the estimator
+/// keeps a histogram of the observed values with logarithmically sized buckets, so the
+/// estimate follows the typical cost of *that* function instead of being skewed by other
+/// functions or by a handful of unusually expensive calls. The estimate is the median of each
+/// dimension's histogram.
+///
+/// Like the `PessimisticEstimator`, histograms are kept separately for each cost contract
+/// (see `contract_call_epoch_marker`). Older observations decay by `DECAY_RATE` with every new
+/// one, so the estimate follows a function whose cost changes over time.
+///
+/// Only contract calls are tracked: other payloads never produce an estimate.
+pub struct ContractFunctionCostEstimator {
+    db: Connection,
+}
+
+impl ContractFunctionCostEstimator {
+    /// Open a cost estimator at the given db path. Creates if not existent.
+    pub fn open(p: &Path) -> Result<ContractFunctionCostEstimator, SqliteError> {
+        let mut db = sqlite_open(
+            p,
+            rusqlite::OpenFlags::SQLITE_OPEN_CREATE | rusqlite::OpenFlags::SQLITE_OPEN_READ_WRITE,
+            false,
+        )?;
+
+        // check if the db needs to be instantiated regardless of whether or not
+        //  it was newly created: the db itself may be shared with other estimators,
+        //  which would not have created the necessary table for this estimator.
+        let tx = tx_begin_immediate_sqlite(&mut db)?;
+        Self::instantiate_db(&tx)?;
+        tx.commit()?;
+
+        Ok(ContractFunctionCostEstimator { db })
+    }
+
+    /// Check if the SQL database was already created. Necessary to avoid races if
+    ///  different threads open an estimator at the same time.
+    fn db_already_instantiated(tx: &SqliteTransaction) -> Result<bool, SqliteError> {
+        table_exists(tx, "contract_function_costs")
+    }
+
+    fn instantiate_db(tx: &SqliteTransaction) -> Result<(), SqliteError> {
+        if !Self::db_already_instantiated(tx)? {
+            tx.execute(CREATE_TABLE, rusqlite::NO_PARAMS)?;
+        }
+
+        Ok(())
+    }
+
+    /// Estimate the cost of calling `function` in `contract` in `evaluated_epoch`: the weighted
+    /// median of each dimension of the costs observed for that function so far.
+    pub fn estimate_function_cost(
+        &self,
+        contract: &QualifiedContractIdentifier,
+        function: &ClarityName,
+        evaluated_epoch: &StacksEpochId,
+    ) -> Result<ExecutionCost, EstimatorError> {
+        let epoch = contract_call_epoch_marker(evaluated_epoch);
+        let contract_id = contract.to_string();
+        let median = |field: &CostField| {
+            Self::get_median_sqlite(&self.db, epoch, &contract_id, function.as_str(), field)
+        };
+
+        Ok(ExecutionCost {
+            runtime: median(&CostField::RuntimeCost)?,
+            read_count: median(&CostField::ReadCount)?,
+            read_length: median(&CostField::ReadLength)?,
+            write_count: median(&CostField::WriteCount)?,
+            write_length: median(&CostField::WriteLength)?,
+        })
+    }
+
+    /// Add one observation of `function` in `contract` having cost `actual_cost` in
+    /// `evaluated_epoch`, after decaying the weight of the earlier ones.
+    fn record_function_cost(
+        &mut self,
+        contract: &QualifiedContractIdentifier,
+        function: &ClarityName,
+        actual_cost: &ExecutionCost,
+        evaluated_epoch: &StacksEpochId,
+    ) -> Result<(), EstimatorError> {
+        let epoch = contract_call_epoch_marker(evaluated_epoch);
+        let contract_id = contract.to_string();
+        let decay_sql = "UPDATE contract_function_costs SET weight = weight * ?
+                         WHERE epoch = ? AND contract_id = ? AND function_name = ?";
+        let prune_sql = "DELETE FROM contract_function_costs
+                         WHERE epoch = ? AND contract_id = ? AND function_name = ? AND weight < ?";
+        let insert_sql = "INSERT INTO contract_function_costs
+                     (epoch, contract_id, function_name, cost_field, bucket, weight)
+                     VALUES (?, ?, ?, ?, ?, 1.0)
+                   ON CONFLICT (epoch, contract_id, function_name, cost_field, bucket)
+                     DO UPDATE SET weight = weight + 1.0";
+
+        let tx = tx_begin_immediate_sqlite(&mut self.db)?;
+        tx.execute(
+            decay_sql,
+            rusqlite::params![DECAY_RATE, epoch, contract_id, function.as_str()],
+        )?;
+        tx.execute(
+            prune_sql,
+            rusqlite::params![epoch, contract_id, function.as_str(), MIN_BUCKET_WEIGHT],
+        )?;
+        for field in CostField::ALL.iter() {
+            let bucket = bucket_of(field.select_key(actual_cost));
+            tx.execute(
+                insert_sql,
+                rusqlite::params![
+                    epoch,
+                    contract_id,
+                    function.as_str(),
+                    field.to_string(),
+                    bucket
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn get_median_sqlite(
+        conn: &Connection,
+        epoch: &str,
+        contract_id: &str,
+        function_name: &str,
+        field: &CostField,
+    ) -> Result<u64, EstimatorError> {
+        let sql = "SELECT bucket, weight FROM contract_function_costs
+                   WHERE epoch = ? AND contract_id = ? AND function_name = ? AND cost_field = ?
+                   ORDER BY bucket ASC";
+        let mut stmt = conn.prepare(sql)?;
+        let histogram = stmt
+            .query_and_then::<_, SqliteError, _, _>(
+                rusqlite::params![epoch, contract_id, function_name, field.to_string()],
+                |row| {
+                    let bucket: i64 = row.get("bucket")?;
+                    let weight: f64 = row.get("weight")?;
+                    Ok((bucket, weight))
+                },
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let total: f64 = histogram.iter().map(|(_, weight)| weight).sum();
+        // the weighted median is the value with half of the total weight below it
+        let mut below = 0.0;
+        for (bucket, weight) in histogram.into_iter() {
+            below += weight;
+            if below > total / 2.0 {
+                return Ok(bucket_value(bucket));
+            }
+        }
+        Err(EstimatorError::NoEstimateAvailable)
+    }
+}
+
+/// The histogram bucket `value` falls in. Zero gets a bucket of its own.
+fn bucket_of(value: u64) -> i64 {
+    if value == 0 {
+        0
+    } else {
+        1 + ((value as f64).ln() / BUCKET_RATIO.ln()).floor() as i64
+    }
+}
+
+/// The value reported for a histogram bucket: the geometric midpoint of its range.
+fn bucket_value(bucket: i64) -> u64 {
+    if bucket <= 0 {
+        0
+    } else {
+        BUCKET_RATIO.powf(bucket as f64 - 0.5).round() as u64
+    }
+}
+
+impl CostEstimator for ContractFunctionCostEstimator {
+    fn notify_event(
+        &mut self,
+        tx: &TransactionPayload,
+        actual_cost: &ExecutionCost,
+        _block_limit: &ExecutionCost,
+        evaluated_epoch: &StacksEpochId,
+    ) -> Result<(), EstimatorError> {
+        match tx {
+            TransactionPayload::ContractCall(cc) => {
+                let contract = QualifiedContractIdentifier::new(
+                    StandardPrincipalData::from(cc.address.clone()),
+                    cc.contract_name.clone(),
+                );
+                self.record_function_cost(
+                    &contract,
+                    &cc.function_name,
+                    actual_cost,
+                    evaluated_epoch,
+                )
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn estimate_cost(
+        &self,
+        tx: &TransactionPayload,
+        evaluated_epoch: &StacksEpochId,
+    ) -> Result<ExecutionCost, EstimatorError> {
+        match tx {
+            TransactionPayload::ContractCall(cc) => {
+                let contract = QualifiedContractIdentifier::new(
+                    StandardPrincipalData::from(cc.address.clone()),
+                    cc.contract_name.clone(),
+                );
+                self.estimate_function_cost(&contract, &cc.function_name, evaluated_epoch)
+            }
+            _ => Err(EstimatorError::NoEstimateAvailable),
+        }
+    }
+
+    fn estimate_for_contract_call(
+        &self,
+        contract: &QualifiedContractIdentifier,
+        function: &ClarityName,
+        evaluated_epoch: &StacksEpochId,
+    ) -> Result<ExecutionCost, EstimatorError> {
+        self.estimate_function_cost(contract, function, evaluated_epoch)
+    }
+}
@@ -0,0 +1,185 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+use clarity::vm::costs::ExecutionCost;
+use url::Url;
+
+use super::FeeRateEstimate;
+use super::{EstimatorError, FeeEstimator};
+use crate::chainstate::stacks::db::StacksEpochReceipt;
+use crate::deps::httparse;
+
+/// The path, relative to the oracle's base URL, from which fee rate estimates are fetched.
+pub const HTTP_FEE_RATE_ESTIMATES_PATH: &str = "fee_rate_estimates";
+
+/// The longest oracle response we will read, in bytes.
+const MAX_HTTP_FEE_RATE_RESPONSE_LEN: u64 = 65536;
+
+/// The HttpFeeRateEstimator delegates fee rate estimation to an external oracle, such as a
+/// centralized service or an aggregator over many nodes' estimates. On `get_rate_estimates`
+/// calls, it issues `GET {base_url}/fee_rate_estimates` and expects a JSON-encoded
+/// `FeeRateEstimate` back. It does not look at blocks itself, so `notify_block` is a no-op.
+///
+/// The last estimate fetched from the oracle is cached, and is returned if a later request fails
+/// or times out. Only plain `http://` oracles are supported.
+pub struct HttpFeeRateEstimator {
+    /// The URL to fetch fee rate estimates from.
+    url: Url,
+    /// How long to wait to connect to the oracle, and for each read from or write to it.
+    timeout: Duration,
+    /// The last estimate the oracle gave us, if any.
+    last_estimate: RefCell<Option<FeeRateEstimate>>,
+}
+
+impl HttpFeeRateEstimator {
+    /// Make an estimator that asks the oracle at `base_url`. Fails if `base_url` is not an
+    /// `http://` URL with a host.
+    pub fn new(base_url: &str, timeout: Duration) -> Result<HttpFeeRateEstimator, String> {
+        let mut url = Url::parse(base_url)
+            .map_err(|e| format!("Invalid fee rate oracle URL '{}': {}", base_url, e))?;
+        if url.scheme() != "http" {
+            return Err(format!(
+                "Unsupported fee rate oracle URL '{}': only http:// is supported",
+                base_url
+            ));
+        }
+        if url.host_str().is_none() {
+            return Err(format!("Fee rate oracle URL '{}' has no host", base_url));
+        }
+        url.path_segments_mut()
+            .map_err(|_| format!("Fee rate oracle URL '{}' cannot have a path", base_url))?
+            .pop_if_empty()
+            .push(HTTP_FEE_RATE_ESTIMATES_PATH);
+
+        Ok(HttpFeeRateEstimator {
+            url,
+            timeout,
+            last_estimate: RefCell::new(None),
+        })
+    }
+
+    /// The URL that fee rate estimates are fetched from.
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Ask the oracle for its current estimate.
+    fn fetch_estimate(&self) -> Result<FeeRateEstimate, String> {
+        let host = self
+            .url
+            .host_str()
+            .ok_or_else(|| "No host in fee rate oracle URL".to_string())?;
+        let port = self.url.port_or_known_default().unwrap_or(80);
+
+        let mut last_error = None;
+        let mut stream = None;
+        for addr in (host, port)
+            .to_socket_addrs()
+            .map_err(|e| format!("Failed to resolve {}: {}", host, e))?
+        {
+            match TcpStream::connect_timeout(&addr, self.timeout) {
+                Ok(s) => {
+                    stream = Some(s);
+                    break;
+                }
+                Err(e) => {
+                    last_error = Some(e);
+                }
+            }
+        }
+        let mut stream = stream
+            .ok_or_else(|| format!("Failed to connect to {}:{}: {:?}", host, port, &last_error))?;
+        stream
+            .set_read_timeout(Some(self.timeout))
+            .and_then(|_| stream.set_write_timeout(Some(self.timeout)))
+            .map_err(|e| format!("Failed to set socket timeouts: {}", e))?;
+
+        // HTTP/1.0 so that the reply is neither chunked nor kept alive
+        let path = match self.url.query() {
+            Some(query) => format!("{}?{}", self.url.path(), query),
+            None => self.url.path().to_string(),
+        };
+        let request = format!(
+            "GET {} HTTP/1.0\r\nHost: {}:{}\r\nAccept: application/json\r\nConnection: close\r\n\r\n",
+            path, host, port
+        );
+        stream
+            .write_all(request.as_bytes())
+            .map_err(|e| format!("Failed to send request: {}", e))?;
+
+        let mut response = vec![];
+        stream
+            .take(MAX_HTTP_FEE_RATE_RESPONSE_LEN + 1)
+            .read_to_end(&mut response)
+            .map_err(|e| format!("Failed to read response: {}", e))?;
+        if (response.len() as u64) > MAX_HTTP_FEE_RATE_RESPONSE_LEN {
+            return Err("Response is too long".to_string());
+        }
+
+        let mut headers = [httparse::EMPTY_HEADER; 32];
+        let mut parsed = httparse::Response::new(&mut headers);
+        let body_offset = match parsed.parse(&response) {
+            Ok(httparse::Status::Complete(offset)) => offset,
+            Ok(httparse::Status::Partial) => {
+                return Err("Incomplete response".to_string());
+            }
+            Err(e) => {
+                return Err(format!("Failed to parse response: {:?}", &e));
+            }
+        };
+        if parsed.code != Some(200) {
+            return Err(format!("Unexpected response status {:?}", parsed.code));
+        }
+
+        let estimate: FeeRateEstimate = serde_json::from_slice(&response[body_offset..])
+            .map_err(|e| format!("Failed to decode fee rate estimate: {}", e))?;
+        for rate in [estimate.high, estimate.middle, estimate.low] {
+            if !rate.is_finite() || rate < 0f64 {
+                return Err(format!("Invalid fee rate estimate {:?}", &estimate));
+            }
+        }
+        Ok(estimate)
+    }
+
+    /// Ask the oracle for its estimate, falling back to the last one it gave us.
+    fn fetch_or_last_estimate(&self) -> Result<FeeRateEstimate, EstimatorError> {
+        match self.fetch_estimate() {
+            Ok(estimate) => {
+                self.last_estimate.replace(Some(estimate.clone()));
+                Ok(estimate)
+            }
+            Err(msg) => {
+                let last_estimate = self.last_estimate.borrow().clone();
+                warn!("Failed to fetch fee rate estimates from oracle";
+                      "url" => %self.url,
+                      "err" => msg,
+                      "using_last_estimate" => last_estimate.is_some());
+                last_estimate.ok_or(EstimatorError::NoEstimateAvailable)
+            }
+        }
+    }
+}
+
+impl FeeEstimator for HttpFeeRateEstimator {
+    /// The oracle tracks fees itself, so there is nothing to do.
+    fn notify_block(
+        &mut self,
+        _receipt: &StacksEpochReceipt,
+        _block_limit: &ExecutionCost,
+    ) -> Result<(), EstimatorError> {
+        Ok(())
+    }
+
+    /// Ask the oracle for its low, middle and high estimates, falling back to the last ones it
+    /// gave us. No other percentile is available.
+    fn get_rate_estimates_at(
+        &self,
+        percentiles: &[u8],
+    ) -> Result<BTreeMap<u8, f64>, EstimatorError> {
+        self.fetch_or_last_estimate()?
+            .at_percentiles(self.rate_estimate_percentiles(), percentiles)
+    }
+}
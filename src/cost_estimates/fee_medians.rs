@@ -1,5 +1,6 @@
 use std::cmp;
 use std::cmp::Ordering;
+use std::collections::BTreeMap;
 use std::convert::TryFrom;
 use std::{iter::FromIterator, path::Path};
 
@@ -223,8 +224,13 @@ impl<M: CostMetric> FeeEstimator for WeightedMedianFeeRateEstimator<M> {
         Ok(())
     }
 
-    fn get_rate_estimates(&self) -> Result<FeeRateEstimate, EstimatorError> {
-        Self::get_rate_estimates_from_sql(&self.db, self.window_size)
+    /// Only the 5th, 50th and 95th percentiles are tracked, so no other percentile is available.
+    fn get_rate_estimates_at(
+        &self,
+        percentiles: &[u8],
+    ) -> Result<BTreeMap<u8, f64>, EstimatorError> {
+        Self::get_rate_estimates_from_sql(&self.db, self.window_size)?
+            .at_percentiles(self.rate_estimate_percentiles(), percentiles)
     }
 }
 
@@ -310,7 +316,7 @@ fn maybe_add_minimum_fee_rate(working_rates: &mut Vec<FeeRateAndWeight>, full_bl
 /// Returns None if:
 ///   1) There is no fee rate for the tx.
 ///   2) Cacluated fee rate is infinite.
-fn fee_rate_and_weight_from_receipt(
+pub fn fee_rate_and_weight_from_receipt(
     metric: &dyn CostMetric,
     tx_receipt: &StacksTransactionReceipt,
     block_limit: &ExecutionCost,
@@ -330,10 +336,16 @@ fn fee_rate_and_weight_from_receipt(
         }
         TransactionPayload::PoisonMicroblock(_, _)
         | TransactionPayload::ContractCall(_)
-        | TransactionPayload::SmartContract(..) => {
+        | TransactionPayload::SmartContract(..)
+        | TransactionPayload::BatchTokenTransfer(..) => {
             // These transaction payload types all "work" the same: they have associated ExecutionCosts
             // and contibute to the block length limit with their tx_len
-            metric.from_cost_and_len(&tx_receipt.execution_cost, &block_limit, tx_size)
+            metric.from_payload_cost_and_len(
+                payload,
+                &tx_receipt.execution_cost,
+                &block_limit,
+                tx_size,
+            )
         }
     };
     let denominator = cmp::max(scalar_cost, 1) as f64;
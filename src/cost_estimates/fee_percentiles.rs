@@ -0,0 +1,212 @@
+use std::cmp;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use rusqlite::Transaction as SqlTransaction;
+use rusqlite::{Connection, Error as SqliteError};
+
+use crate::util_lib::db::{sqlite_open, table_exists, tx_begin_immediate_sqlite};
+
+use clarity::vm::costs::ExecutionCost;
+
+use crate::chainstate::stacks::db::StacksEpochReceipt;
+
+use super::fee_medians::fee_rate_and_weight_from_receipt;
+use super::metrics::CostMetric;
+use super::FeeRateEstimate;
+use super::{EstimatorError, FeeEstimator};
+
+const CREATE_TABLE: &'static str = "
+CREATE TABLE percentile_fee_estimator (
+    block_key INTEGER NOT NULL,
+    fee_rate NUMBER NOT NULL
+)";
+
+/// FeeRateEstimator with the following properties:
+///
+/// 1) Every transaction fee rate observed in the last `window_size` blocks is retained, and the
+///    estimates are the actual 10th, 50th and 90th percentile values of those fee rates (used as
+///    the low, middle and high estimates respectively).
+/// 2) Each transaction counts once, regardless of how much of the block it consumed. This makes
+///    the estimate react quickly to congestion, and limits the influence a single large
+///    transaction can have.
+/// 3) Blocks which contain no fee-paying transactions (e.g., only a coinbase) do not count toward
+///    the window.
+pub struct PercentileFeeRateEstimator<M: CostMetric> {
+    db: Connection,
+    /// We only look back `window_size` qualifying blocks when computing the percentiles.
+    window_size: u32,
+    /// Use this cost metric in fee rate calculations.
+    metric: M,
+}
+
+impl<M: CostMetric> PercentileFeeRateEstimator<M> {
+    /// Open a fee rate estimator at the given db path. Creates if not existent.
+    pub fn open(p: &Path, metric: M, window_size: u32) -> Result<Self, SqliteError> {
+        let mut db = sqlite_open(
+            p,
+            rusqlite::OpenFlags::SQLITE_OPEN_CREATE | rusqlite::OpenFlags::SQLITE_OPEN_READ_WRITE,
+            false,
+        )?;
+
+        // check if the db needs to be instantiated regardless of whether or not
+        //  it was newly created: the db itself may be shared with other fee estimators,
+        //  which would not have created the necessary table for this estimator.
+        let tx = tx_begin_immediate_sqlite(&mut db)?;
+        Self::instantiate_db(&tx)?;
+        tx.commit()?;
+
+        Ok(Self {
+            db,
+            metric,
+            window_size,
+        })
+    }
+
+    /// Check if the SQL database was already created. Necessary to avoid races if
+    ///  different threads open an estimator at the same time.
+    fn db_already_instantiated(tx: &SqlTransaction) -> Result<bool, SqliteError> {
+        table_exists(tx, "percentile_fee_estimator")
+    }
+
+    fn instantiate_db(tx: &SqlTransaction) -> Result<(), SqliteError> {
+        if !Self::db_already_instantiated(tx)? {
+            tx.execute(CREATE_TABLE, rusqlite::NO_PARAMS)?;
+        }
+
+        Ok(())
+    }
+
+    /// Load every fee rate in the window, in ascending order.
+    fn get_sorted_fee_rates_from_sql(conn: &Connection) -> Result<Vec<f64>, EstimatorError> {
+        let sql = "SELECT fee_rate FROM percentile_fee_estimator";
+        let mut stmt = conn.prepare(sql).expect("SQLite failure");
+        let mut fee_rates = stmt
+            .query_and_then::<_, SqliteError, _, _>(rusqlite::NO_PARAMS, |row| {
+                let fee_rate: f64 = row.get("fee_rate")?;
+                Ok(fee_rate)
+            })
+            .expect("SQLite failure")
+            .collect::<Result<Vec<f64>, _>>()
+            .expect("SQLite failure");
+
+        if fee_rates.is_empty() {
+            return Err(EstimatorError::NoEstimateAvailable);
+        }
+
+        // Sort our float array. For float values that do not compare easily,
+        // treat them as equals.
+        fee_rates.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        Ok(fee_rates)
+    }
+
+    fn get_rate_estimates_from_sql(conn: &Connection) -> Result<FeeRateEstimate, EstimatorError> {
+        let fee_rates = Self::get_sorted_fee_rates_from_sql(conn)?;
+        Ok(fee_rate_estimate_from_sorted_fees(&fee_rates))
+    }
+
+    fn update_estimate(&mut self, block_fee_rates: &[f64]) {
+        let tx = tx_begin_immediate_sqlite(&mut self.db).expect("SQLite failure");
+        let next_block_key: i64 = tx
+            .query_row(
+                "SELECT IFNULL(MAX(block_key), 0) + 1 FROM percentile_fee_estimator",
+                rusqlite::NO_PARAMS,
+                |row| row.get(0),
+            )
+            .expect("SQLite failure");
+        let insert_sql = "INSERT INTO percentile_fee_estimator
+                          (block_key, fee_rate) VALUES (?, ?)";
+        let deletion_sql = "DELETE FROM percentile_fee_estimator
+                            WHERE block_key <= ?";
+        for fee_rate in block_fee_rates.iter() {
+            tx.execute(insert_sql, rusqlite::params![next_block_key, fee_rate])
+                .expect("SQLite failure");
+        }
+        tx.execute(
+            deletion_sql,
+            rusqlite::params![next_block_key - i64::from(self.window_size)],
+        )
+        .expect("SQLite failure");
+
+        let estimate = Self::get_rate_estimates_from_sql(&tx);
+        tx.commit().expect("SQLite failure");
+        if let Ok(next_estimate) = estimate {
+            debug!("Updating percentile fee rate estimate for new block";
+                   "block_tx_count" => block_fee_rates.len(),
+                   "new_estimate_high" => next_estimate.high,
+                   "new_estimate_middle" => next_estimate.middle,
+                   "new_estimate_low" => next_estimate.low);
+        }
+    }
+}
+
+impl<M: CostMetric> FeeEstimator for PercentileFeeRateEstimator<M> {
+    fn notify_block(
+        &mut self,
+        receipt: &StacksEpochReceipt,
+        block_limit: &ExecutionCost,
+    ) -> Result<(), EstimatorError> {
+        let block_fee_rates: Vec<f64> = receipt
+            .tx_receipts
+            .iter()
+            .filter_map(|tx_receipt| {
+                fee_rate_and_weight_from_receipt(&self.metric, &tx_receipt, block_limit)
+            })
+            .map(|rate_and_weight| rate_and_weight.fee_rate)
+            .collect();
+
+        // Blocks without any fee-paying transactions do not count toward the window.
+        if !block_fee_rates.is_empty() {
+            self.update_estimate(&block_fee_rates);
+        }
+
+        Ok(())
+    }
+
+    fn get_rate_estimates_at(
+        &self,
+        percentiles: &[u8],
+    ) -> Result<BTreeMap<u8, f64>, EstimatorError> {
+        let fee_rates = Self::get_sorted_fee_rates_from_sql(&self.db)?;
+        Ok(percentiles
+            .iter()
+            .map(|percentile| {
+                let fraction = f64::from(cmp::min(*percentile, 100)) / 100f64;
+                (*percentile, percentile_of_sorted(&fee_rates, fraction))
+            })
+            .collect())
+    }
+
+    /// The low, middle and high estimates are the 10th, 50th and 90th percentiles.
+    fn rate_estimate_percentiles(&self) -> [u8; 3] {
+        [10, 50, 90]
+    }
+}
+
+/// Computes the value at `percentile` (in [0, 1]) of `sorted_fee_rates`, linearly interpolating
+/// between the two closest ranks.
+///
+/// `sorted_fee_rates` must be non-empty.
+fn percentile_of_sorted(sorted_fee_rates: &[f64], percentile: f64) -> f64 {
+    assert!(!sorted_fee_rates.is_empty());
+
+    let rank = percentile * (sorted_fee_rates.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    let lower_value = sorted_fee_rates[lower];
+    let upper_value = sorted_fee_rates[upper];
+    lower_value + (rank - lower as f64) * (upper_value - lower_value)
+}
+
+/// Computes a `FeeRateEstimate` from the 10th, 50th and 90th percentiles of
+/// `sorted_fee_rates`.
+///
+/// `sorted_fee_rates` must be non-empty.
+pub fn fee_rate_estimate_from_sorted_fees(sorted_fee_rates: &[f64]) -> FeeRateEstimate {
+    FeeRateEstimate {
+        high: percentile_of_sorted(sorted_fee_rates, 0.9),
+        middle: percentile_of_sorted(sorted_fee_rates, 0.5),
+        low: percentile_of_sorted(sorted_fee_rates, 0.1),
+    }
+}
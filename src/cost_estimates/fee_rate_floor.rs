@@ -0,0 +1,81 @@
+use std::cmp;
+use std::collections::BTreeMap;
+
+use clarity::vm::costs::ExecutionCost;
+
+use super::FeeRateEstimate;
+use super::{EstimatorError, FeeEstimator};
+use crate::chainstate::stacks::db::StacksEpochReceipt;
+
+/// The FeeRateFloor wraps an underlying FeeEstimator. It passes `notify_block` calls to the
+/// underlying estimator. On `get_rate_estimates_at` calls, it raises each estimate coming back from
+/// the underlying estimator to at least the floor of the corresponding high, middle or low bucket.
+///
+/// This keeps estimators that only look at recently-paid fees (like `ScalarFeeRateEstimator`)
+/// from reporting rates below what miners will accept when the chain is quiet.
+pub struct FeeRateFloor<UnderlyingEstimator: FeeEstimator> {
+    /// The estimates given by this will be clamped to `floor`.
+    underlying: UnderlyingEstimator,
+    /// The least fee rate to report for each of the high, middle and low estimates.
+    floor: FeeRateEstimate,
+}
+
+impl<UnderlyingEstimator: FeeEstimator> FeeRateFloor<UnderlyingEstimator> {
+    pub fn new(
+        underlying: UnderlyingEstimator,
+        floor: FeeRateEstimate,
+    ) -> FeeRateFloor<UnderlyingEstimator> {
+        Self { underlying, floor }
+    }
+
+    /// Raise each of the per-percentile estimates in `input` to at least the floor of its
+    /// bucket. A percentile at or above the underlying estimator's high (middle) percentile is
+    /// held to the high (middle) floor, and any lower percentile is held to the low floor.
+    fn clamp_percentile_estimates(&self, input: BTreeMap<u8, f64>) -> BTreeMap<u8, f64> {
+        let [_, middle, high] = self.underlying.rate_estimate_percentiles();
+        input
+            .into_iter()
+            .map(|(percentile, fee_rate)| {
+                let floor = match cmp::min(percentile, 100) {
+                    p if p >= high => self.floor.high,
+                    p if p >= middle => self.floor.middle,
+                    _ => self.floor.low,
+                };
+                (percentile, fee_rate.max(floor))
+            })
+            .collect()
+    }
+}
+
+impl<T: FeeEstimator> FeeEstimator for FeeRateFloor<T> {
+    /// Just passes the information straight to `underlying`.
+    fn notify_block(
+        &mut self,
+        receipt: &StacksEpochReceipt,
+        block_limit: &ExecutionCost,
+    ) -> Result<(), EstimatorError> {
+        self.underlying.notify_block(receipt, block_limit)
+    }
+
+    /// Just passes the information straight to `underlying`.
+    fn notify_block_batch(
+        &mut self,
+        receipts: &[StacksEpochReceipt],
+        block_limit: &ExecutionCost,
+    ) -> Result<(), EstimatorError> {
+        self.underlying.notify_block_batch(receipts, block_limit)
+    }
+
+    /// Call underlying estimator and clamp the result to the floor.
+    fn get_rate_estimates_at(
+        &self,
+        percentiles: &[u8],
+    ) -> Result<BTreeMap<u8, f64>, EstimatorError> {
+        let underlying_estimates = self.underlying.get_rate_estimates_at(percentiles)?;
+        Ok(self.clamp_percentile_estimates(underlying_estimates))
+    }
+
+    fn rate_estimate_percentiles(&self) -> [u8; 3] {
+        self.underlying.rate_estimate_percentiles()
+    }
+}
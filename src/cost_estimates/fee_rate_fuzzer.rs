@@ -1,6 +1,7 @@
+use std::collections::BTreeMap;
+
 use clarity::vm::costs::ExecutionCost;
 
-use super::FeeRateEstimate;
 use super::{EstimatorError, FeeEstimator};
 use crate::chainstate::stacks::db::StacksEpochReceipt;
 use rand::distributions::{Distribution, Uniform};
@@ -10,8 +11,9 @@ use rand::RngCore;
 use rand::SeedableRng;
 
 /// The FeeRateFuzzer wraps an underlying FeeEstimator. It passes `notify_block` calls to the
-/// underlying estimator. On `get_rate_estimates` calls, it adds a random fuzz to the result coming
-/// back from the underlying estimator. The fuzz applied is as a random fraction of the base value.
+/// underlying estimator. On `get_rate_estimates_at` calls, it adds a random fuzz to the results
+/// coming back from the underlying estimator. The fuzz applied is as a random fraction of the base
+/// value.
 ///
 /// Note: We currently use "uniform" random noise instead of "normal" distributed noise to avoid
 /// importing a new crate just for this.
@@ -61,13 +63,17 @@ impl<UnderlyingEstimator: FeeEstimator> FeeRateFuzzer<UnderlyingEstimator> {
         }
     }
 
-    /// Add a uniform fuzz to input. Each element is multiplied by the same random factor.
-    fn fuzz_estimate(&self, input: FeeRateEstimate) -> FeeRateEstimate {
+    /// Add a uniform fuzz to each of the per-percentile estimates in `input`. Each element is
+    /// multiplied by the same random factor.
+    fn fuzz_percentile_estimates(&self, input: BTreeMap<u8, f64>) -> BTreeMap<u8, f64> {
         if self.uniform_fuzz_fraction > 0f64 {
             let mut rng = (self.rng_creator)();
             let uniform = Uniform::new(-self.uniform_fuzz_fraction, self.uniform_fuzz_fraction);
             let fuzz_scale = 1f64 + uniform.sample(&mut rng);
-            input * fuzz_scale
+            input
+                .into_iter()
+                .map(|(percentile, fee_rate)| (percentile, fee_rate * fuzz_scale))
+                .collect()
         } else {
             input
         }
@@ -84,9 +90,25 @@ impl<T: FeeEstimator> FeeEstimator for FeeRateFuzzer<T> {
         self.underlying.notify_block(receipt, block_limit)
     }
 
+    /// Just passes the information straight to `underlying`.
+    fn notify_block_batch(
+        &mut self,
+        receipts: &[StacksEpochReceipt],
+        block_limit: &ExecutionCost,
+    ) -> Result<(), EstimatorError> {
+        self.underlying.notify_block_batch(receipts, block_limit)
+    }
+
     /// Call underlying estimator and add some fuzz.
-    fn get_rate_estimates(&self) -> Result<FeeRateEstimate, EstimatorError> {
-        let underlying_estimate = self.underlying.get_rate_estimates()?;
-        Ok(self.fuzz_estimate(underlying_estimate))
+    fn get_rate_estimates_at(
+        &self,
+        percentiles: &[u8],
+    ) -> Result<BTreeMap<u8, f64>, EstimatorError> {
+        let underlying_estimates = self.underlying.get_rate_estimates_at(percentiles)?;
+        Ok(self.fuzz_percentile_estimates(underlying_estimates))
+    }
+
+    fn rate_estimate_percentiles(&self) -> [u8; 3] {
+        self.underlying.rate_estimate_percentiles()
     }
 }
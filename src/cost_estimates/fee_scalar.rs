@@ -1,11 +1,11 @@
 use std::cmp;
+use std::collections::BTreeMap;
 use std::convert::TryFrom;
 use std::{iter::FromIterator, path::Path};
 
-use rusqlite::Transaction as SqlTransaction;
 use rusqlite::{
     types::{FromSql, FromSqlError},
-    Connection, Error as SqliteError, OptionalExtension, ToSql,
+    Connection, OptionalExtension, ToSql,
 };
 use serde_json::Value as JsonValue;
 
@@ -27,19 +27,53 @@ use super::FeeRateEstimate;
 use super::{EstimatorError, FeeEstimator};
 
 const SINGLETON_ROW_ID: i64 = 1;
+// The estimate columns have NUMBER affinity, so rows written as integers
+//  by older versions of this estimator are still read back as `f64`.
 const CREATE_TABLE: &'static str = "
-CREATE TABLE scalar_fee_estimator (
+CREATE TABLE IF NOT EXISTS scalar_fee_estimator (
     estimate_key NUMBER PRIMARY KEY,
     high NUMBER NOT NULL,
     middle NUMBER NOT NULL,
     low NUMBER NOT NULL
 )";
+const CREATE_PERCENTILES_TABLE: &'static str = "
+CREATE TABLE IF NOT EXISTS scalar_fee_estimator_percentiles (
+    percentile INTEGER PRIMARY KEY,
+    fee_rate NUMBER NOT NULL
+)";
+// The db may be shared with other fee estimators, so this estimator's
+//  schema version is tracked in its own table.
+const CREATE_SCHEMA_VERSION_TABLE: &'static str = "
+CREATE TABLE IF NOT EXISTS scalar_fee_estimator_schema_version (
+    version INTEGER PRIMARY KEY
+)";
+
+/// The schema version that `run_migrations` brings the db up to.
+pub const SCALAR_FEE_ESTIMATOR_SCHEMA_VERSION: i64 = 2;
+
+/// The DDL statements for each schema migration. The statements at index `i`
+///  migrate the db from version `i` to version `i + 1`. Databases created before
+///  the schema was versioned are at version 0, even if they already hold some of
+///  these tables, so every statement must be safe to re-run.
+const SCALAR_FEE_ESTIMATOR_MIGRATIONS: &'static [&'static [&'static str]] = &[
+    // version 1: the low/middle/high estimates
+    &[CREATE_TABLE],
+    // version 2: the per-percentile estimates
+    &[CREATE_PERCENTILES_TABLE],
+];
 
 /// This struct estimates fee rates by translating a transaction's `ExecutionCost`
 /// into a scalar using `ExecutionCost::proportion_dot_product` and computing
 /// the subsequent fee rate using the actual paid fee. The 5th, 50th and 95th
 /// percentile fee rates for each block are used as the low, middle, and high
 /// estimates. Estimates are updated via exponential decay windowing.
+///
+/// The estimator additionally tracks a decayed estimate for every integer percentile
+/// in [0, 100], so that `get_rate_estimates_at` can serve arbitrary percentiles.
+///
+/// Optionally, fee rates which exceed their block's median by more than a given number
+/// of median absolute deviations (MADs) are dropped before the block's percentiles are
+/// measured, so that a single outlier does not drag the high estimate upward.
 pub struct ScalarFeeRateEstimator<M: CostMetric> {
     db: Connection,
     /// how quickly does the current estimate decay
@@ -47,116 +81,66 @@ pub struct ScalarFeeRateEstimator<M: CostMetric> {
     ///      new_estimate := (decay_rate) * old_estimate + (1 - decay_rate) * new_measure
     decay_rate: f64,
     metric: M,
+    /// if set, drop fee rates more than this many MADs above the block median
+    outlier_max_mads: Option<f64>,
 }
 
 impl<M: CostMetric> ScalarFeeRateEstimator<M> {
     /// Open a fee rate estimator at the given db path. Creates if not existent.
-    pub fn open(p: &Path, metric: M) -> Result<Self, SqliteError> {
+    /// The smoothed estimates live in the db, so if it already holds estimates (e.g.
+    /// from before a node restart), they are served and updated from where they left off.
+    /// If `outlier_max_mads` is set, each block's fee rates are filtered with
+    /// `filter_outlier_fee_rates` before they are measured.
+    pub fn open(
+        p: &Path,
+        metric: M,
+        outlier_max_mads: Option<f64>,
+    ) -> Result<Self, EstimatorError> {
+        Self::open_with_decay(p, metric, outlier_max_mads, 0.5_f64)
+    }
+
+    /// Open a fee rate estimator like `open`, but weigh the current estimate by `decay_rate`
+    /// (and each new block's measure by `1 - decay_rate`) when folding in a block. A rate near
+    /// 0 tracks the latest block closely, and a rate near 1 smooths heavily. `open` uses 1/2.
+    /// Panics if `decay_rate` is not in [0, 1].
+    pub fn open_with_decay(
+        p: &Path,
+        metric: M,
+        outlier_max_mads: Option<f64>,
+        decay_rate: f64,
+    ) -> Result<Self, EstimatorError> {
+        assert!(
+            decay_rate >= 0_f64 && decay_rate <= 1_f64,
+            "Fee estimator decay rate must be in [0, 1], got {}",
+            decay_rate
+        );
         let mut db = sqlite_open(
             p,
             rusqlite::OpenFlags::SQLITE_OPEN_CREATE | rusqlite::OpenFlags::SQLITE_OPEN_READ_WRITE,
             false,
         )?;
 
-        // check if the db needs to be instantiated regardless of whether or not
-        //  it was newly created: the db itself may be shared with other fee estimators,
-        //  which would not have created the necessary table for this estimator.
+        // migrate the db regardless of whether or not it was newly created: the db
+        //  itself may be shared with other fee estimators, which would not have
+        //  created the necessary tables for this estimator.
         let tx = tx_begin_immediate_sqlite(&mut db)?;
-        Self::instantiate_db(&tx)?;
+        run_migrations(&tx)?;
         tx.commit()?;
 
         Ok(Self {
             db,
             metric,
-            decay_rate: 0.5_f64,
+            decay_rate,
+            outlier_max_mads,
         })
     }
 
-    /// Check if the SQL database was already created. Necessary to avoid races if
-    ///  different threads open an estimator at the same time.
-    fn db_already_instantiated(tx: &SqlTransaction) -> Result<bool, SqliteError> {
-        table_exists(tx, "scalar_fee_estimator")
-    }
-
-    fn instantiate_db(tx: &SqlTransaction) -> Result<(), SqliteError> {
-        if !Self::db_already_instantiated(tx)? {
-            tx.execute(CREATE_TABLE, rusqlite::NO_PARAMS)?;
-        }
-
-        Ok(())
-    }
-
-    fn update_estimate(&mut self, new_measure: FeeRateEstimate) {
-        let next_estimate = match self.get_rate_estimates() {
-            Ok(old_estimate) => {
-                // compute the exponential windowing:
-                // estimate = (a/b * old_estimate) + ((1 - a/b) * new_estimate)
-                let prior_component = old_estimate.clone() * self.decay_rate;
-                let next_component = new_measure.clone() * (1_f64 - self.decay_rate);
-                let mut next_computed = prior_component + next_component;
-
-                // because of integer math, we can end up with some edge effects
-                // when the estimate is < decay_rate_fraction.1, so just saturate
-                // on the low end at a rate of "1"
-                next_computed.high = if next_computed.high >= 1f64 {
-                    next_computed.high
-                } else {
-                    1f64
-                };
-                next_computed.middle = if next_computed.middle >= 1f64 {
-                    next_computed.middle
-                } else {
-                    1f64
-                };
-                next_computed.low = if next_computed.low >= 1f64 {
-                    next_computed.low
-                } else {
-                    1f64
-                };
-
-                next_computed
-            }
-            Err(EstimatorError::NoEstimateAvailable) => new_measure.clone(),
-            Err(e) => {
-                warn!("Error in fee estimator fetching current estimates"; "err" => ?e);
-                return;
-            }
-        };
-
-        debug!("Updating fee rate estimate for new block";
-               "new_measure_high" => new_measure.high,
-               "new_measure_middle" => new_measure.middle,
-               "new_measure_low" => new_measure.low,
-               "new_estimate_high" => next_estimate.high,
-               "new_estimate_middle" => next_estimate.middle,
-               "new_estimate_low" => next_estimate.low);
-
-        let sql = "INSERT OR REPLACE INTO scalar_fee_estimator
-                     (estimate_key, high, middle, low) VALUES (?, ?, ?, ?)";
-
-        let tx = tx_begin_immediate_sqlite(&mut self.db).expect("SQLite failure");
-
-        tx.execute(
-            sql,
-            rusqlite::params![
-                SINGLETON_ROW_ID,
-                next_estimate.high,
-                next_estimate.middle,
-                next_estimate.low,
-            ],
-        )
-        .expect("SQLite failure");
-
-        tx.commit().expect("SQLite failure");
-    }
-}
-
-impl<M: CostMetric> FeeEstimator for ScalarFeeRateEstimator<M> {
-    fn notify_block(
-        &mut self,
+    /// Compute the fee rate paid by each transaction in the block, in ascending order.
+    fn block_fee_rates(
+        &self,
         receipt: &StacksEpochReceipt,
         block_limit: &ExecutionCost,
-    ) -> Result<(), EstimatorError> {
+    ) -> Vec<f64> {
         let mut all_fee_rates: Vec<_> = receipt
             .tx_receipts
             .iter()
@@ -178,10 +162,12 @@ impl<M: CostMetric> FeeEstimator for ScalarFeeRateEstimator<M> {
                     }
                     TransactionPayload::PoisonMicroblock(_, _)
                     | TransactionPayload::ContractCall(_)
-                    | TransactionPayload::SmartContract(..) => {
+                    | TransactionPayload::SmartContract(..)
+                    | TransactionPayload::BatchTokenTransfer(..) => {
                         // These transaction payload types all "work" the same: they have associated ExecutionCosts
                         // and contibute to the block length limit with their tx_len
-                        self.metric.from_cost_and_len(
+                        self.metric.from_payload_cost_and_len(
+                            payload,
                             &tx_receipt.execution_cost,
                             &block_limit,
                             tx_size,
@@ -205,26 +191,85 @@ impl<M: CostMetric> FeeEstimator for ScalarFeeRateEstimator<M> {
             a.partial_cmp(b)
                 .expect("BUG: Fee rates should be orderable: NaN and infinite values are filtered")
         });
+        match self.outlier_max_mads {
+            Some(max_mads) => filter_outlier_fee_rates(all_fee_rates, max_mads),
+            None => all_fee_rates,
+        }
+    }
+
+    /// Fold a new block's measured estimate into the current estimate (if any).
+    fn next_estimate(
+        &self,
+        old_estimate: Option<FeeRateEstimate>,
+        new_measure: &FeeRateEstimate,
+    ) -> FeeRateEstimate {
+        let old_estimate = match old_estimate {
+            Some(old_estimate) => old_estimate,
+            None => return new_measure.clone(),
+        };
+
+        // compute the exponential windowing:
+        // estimate = (a/b * old_estimate) + ((1 - a/b) * new_estimate)
+        let prior_component = old_estimate * self.decay_rate;
+        let next_component = new_measure.clone() * (1_f64 - self.decay_rate);
+        let mut next_computed = prior_component + next_component;
 
-        let measures_len = all_fee_rates.len();
-        if measures_len > 0 {
-            // use 5th, 50th, and 95th percentiles from block
-            let highest_index = measures_len - cmp::max(1, measures_len / 20);
-            let median_index = measures_len / 2;
-            let lowest_index = measures_len / 20;
-            let block_estimate = FeeRateEstimate {
-                high: all_fee_rates[highest_index],
-                middle: all_fee_rates[median_index],
-                low: all_fee_rates[lowest_index],
-            };
-
-            self.update_estimate(block_estimate);
+        // because of integer math, we can end up with some edge effects
+        // when the estimate is < decay_rate_fraction.1, so just saturate
+        // on the low end at a rate of "1"
+        next_computed.high = if next_computed.high >= 1f64 {
+            next_computed.high
+        } else {
+            1f64
+        };
+        next_computed.middle = if next_computed.middle >= 1f64 {
+            next_computed.middle
+        } else {
+            1f64
+        };
+        next_computed.low = if next_computed.low >= 1f64 {
+            next_computed.low
+        } else {
+            1f64
+        };
+
+        next_computed
+    }
+
+    /// Fold a new block's measured fee rate at a single percentile into the
+    ///  current estimate for that percentile (if any).
+    fn next_percentile_estimate(&self, old_estimate: Option<f64>, new_measure: f64) -> f64 {
+        match old_estimate {
+            Some(old_estimate) => {
+                let next_computed =
+                    old_estimate * self.decay_rate + new_measure * (1_f64 - self.decay_rate);
+                // saturate on the low end at a rate of "1", same as the
+                //  low/middle/high estimates
+                if next_computed >= 1f64 {
+                    next_computed
+                } else {
+                    1f64
+                }
+            }
+            None => new_measure,
         }
+    }
 
-        Ok(())
+    /// Load the current estimate for every percentile in [0, 100], indexed by percentile.
+    fn get_percentile_estimates(&self) -> Vec<Option<f64>> {
+        let sql = "SELECT fee_rate FROM scalar_fee_estimator_percentiles WHERE percentile = ?";
+        (0..=100i64)
+            .map(|percentile| {
+                self.db
+                    .query_row(sql, &[percentile], |row| row.get(0))
+                    .optional()
+                    .expect("SQLite failure")
+            })
+            .collect()
     }
 
-    fn get_rate_estimates(&self) -> Result<FeeRateEstimate, EstimatorError> {
+    /// Load the current low, middle and high estimate.
+    fn get_estimate(&self) -> Result<FeeRateEstimate, EstimatorError> {
         let sql = "SELECT high, middle, low FROM scalar_fee_estimator WHERE estimate_key = ?";
         self.db
             .query_row(sql, &[SINGLETON_ROW_ID], |row| {
@@ -238,4 +283,240 @@ impl<M: CostMetric> FeeEstimator for ScalarFeeRateEstimator<M> {
             .map(|(high, middle, low)| FeeRateEstimate { high, middle, low })
             .ok_or_else(|| EstimatorError::NoEstimateAvailable)
     }
+
+    /// Load the current estimate for a single `percentile` in [0, 100].
+    fn get_percentile_estimate(&self, percentile: u8) -> Result<f64, EstimatorError> {
+        let sql = "SELECT fee_rate FROM scalar_fee_estimator_percentiles WHERE percentile = ?";
+        self.db
+            .query_row(sql, &[i64::from(percentile)], |row| row.get(0))
+            .optional()
+            .expect("SQLite failure")
+            .ok_or_else(|| EstimatorError::NoEstimateAvailable)
+    }
+
+    /// Fold the measures from each new block, in order, into the current estimates,
+    ///  and store the result. Each block's measures are its low/middle/high estimate and
+    ///  its fee rate at each percentile in [0, 100].
+    fn update_estimates(&mut self, block_measures: &[(FeeRateEstimate, Vec<f64>)]) {
+        let mut estimate = match self.get_rate_estimates() {
+            Ok(old_estimate) => Some(old_estimate),
+            Err(EstimatorError::NoEstimateAvailable) => None,
+            Err(e) => {
+                warn!("Error in fee estimator fetching current estimates"; "err" => ?e);
+                return;
+            }
+        };
+        let mut percentile_estimates = self.get_percentile_estimates();
+
+        for (new_measure, new_percentiles) in block_measures.iter() {
+            let next_estimate = self.next_estimate(estimate, new_measure);
+
+            debug!("Updating fee rate estimate for new block";
+                   "new_measure_high" => new_measure.high,
+                   "new_measure_middle" => new_measure.middle,
+                   "new_measure_low" => new_measure.low,
+                   "new_estimate_high" => next_estimate.high,
+                   "new_estimate_middle" => next_estimate.middle,
+                   "new_estimate_low" => next_estimate.low);
+
+            estimate = Some(next_estimate);
+            for (percentile_estimate, new_measure) in
+                percentile_estimates.iter_mut().zip(new_percentiles.iter())
+            {
+                *percentile_estimate =
+                    Some(self.next_percentile_estimate(*percentile_estimate, *new_measure));
+            }
+        }
+
+        let estimate = match estimate {
+            Some(estimate) => estimate,
+            None => return,
+        };
+
+        let sql = "INSERT OR REPLACE INTO scalar_fee_estimator
+                     (estimate_key, high, middle, low) VALUES (?, ?, ?, ?)";
+        let percentiles_sql = "INSERT OR REPLACE INTO scalar_fee_estimator_percentiles
+                     (percentile, fee_rate) VALUES (?, ?)";
+
+        let tx = tx_begin_immediate_sqlite(&mut self.db).expect("SQLite failure");
+
+        tx.execute(
+            sql,
+            rusqlite::params![
+                SINGLETON_ROW_ID,
+                estimate.high,
+                estimate.middle,
+                estimate.low,
+            ],
+        )
+        .expect("SQLite failure");
+
+        for (percentile, percentile_estimate) in percentile_estimates.iter().enumerate() {
+            if let Some(fee_rate) = percentile_estimate {
+                tx.execute(
+                    percentiles_sql,
+                    rusqlite::params![percentile as i64, fee_rate],
+                )
+                .expect("SQLite failure");
+            }
+        }
+
+        tx.commit().expect("SQLite failure");
+    }
+}
+
+impl<M: CostMetric> FeeEstimator for ScalarFeeRateEstimator<M> {
+    fn notify_block(
+        &mut self,
+        receipt: &StacksEpochReceipt,
+        block_limit: &ExecutionCost,
+    ) -> Result<(), EstimatorError> {
+        self.notify_block_batch(std::slice::from_ref(receipt), block_limit)
+    }
+
+    /// Computes each block's measures in memory, and then stores the resulting
+    ///  estimates in a single SQLite transaction.
+    fn notify_block_batch(
+        &mut self,
+        receipts: &[StacksEpochReceipt],
+        block_limit: &ExecutionCost,
+    ) -> Result<(), EstimatorError> {
+        let block_measures: Vec<_> = receipts
+            .iter()
+            .filter_map(|receipt| {
+                let all_fee_rates = self.block_fee_rates(receipt, block_limit);
+                let measures_len = all_fee_rates.len();
+                if measures_len == 0 {
+                    return None;
+                }
+
+                // use 5th, 50th, and 95th percentiles from block
+                let highest_index = measures_len - cmp::max(1, measures_len / 20);
+                let median_index = measures_len / 2;
+                let lowest_index = measures_len / 20;
+                let block_estimate = FeeRateEstimate {
+                    high: all_fee_rates[highest_index],
+                    middle: all_fee_rates[median_index],
+                    low: all_fee_rates[lowest_index],
+                };
+
+                let block_percentiles: Vec<f64> = (0..=100)
+                    .map(|percentile| {
+                        all_fee_rates[cmp::min(measures_len - 1, measures_len * percentile / 100)]
+                    })
+                    .collect();
+
+                Some((block_estimate, block_percentiles))
+            })
+            .collect();
+
+        if !block_measures.is_empty() {
+            self.update_estimates(&block_measures);
+        }
+
+        Ok(())
+    }
+
+    /// The 5th, 50th and 95th percentiles are served from the low, middle and high estimates,
+    ///  and every other percentile from its own decayed estimate.
+    fn get_rate_estimates_at(
+        &self,
+        percentiles: &[u8],
+    ) -> Result<BTreeMap<u8, f64>, EstimatorError> {
+        let estimate_percentiles = self.rate_estimate_percentiles();
+        let estimate = self.get_estimate();
+        percentiles
+            .iter()
+            .map(|percentile| {
+                let percentile_capped = cmp::min(*percentile, 100);
+                let fee_rate = if estimate_percentiles.contains(&percentile_capped) {
+                    let estimate = estimate
+                        .as_ref()
+                        .map_err(|_| EstimatorError::NoEstimateAvailable)?;
+                    estimate.at_percentiles(estimate_percentiles, &[percentile_capped])?
+                        [&percentile_capped]
+                } else {
+                    self.get_percentile_estimate(percentile_capped)?
+                };
+                Ok((*percentile, fee_rate))
+            })
+            .collect()
+    }
+}
+
+/// Load the schema version of the scalar fee estimator tables. Returns 0 if the
+/// db predates schema versioning (or is new).
+pub fn get_schema_version(conn: &Connection) -> Result<i64, EstimatorError> {
+    if !table_exists(conn, "scalar_fee_estimator_schema_version")? {
+        return Ok(0);
+    }
+    let version: Option<i64> = conn.query_row(
+        "SELECT MAX(version) FROM scalar_fee_estimator_schema_version",
+        rusqlite::NO_PARAMS,
+        |row| row.get(0),
+    )?;
+    Ok(version.unwrap_or(0))
+}
+
+/// Apply each schema migration the db has not yet seen, in version order, recording
+/// each applied version. Running this on an up-to-date db is a no-op. The caller
+/// should hold a transaction, so that a failed migration leaves the db unchanged.
+pub fn run_migrations(conn: &Connection) -> Result<(), EstimatorError> {
+    conn.execute(CREATE_SCHEMA_VERSION_TABLE, rusqlite::NO_PARAMS)?;
+    let mut version = get_schema_version(conn)?;
+    if version > SCALAR_FEE_ESTIMATOR_SCHEMA_VERSION {
+        warn!("Fee estimator db has a newer schema than this node knows about";
+              "version" => version,
+              "expected" => SCALAR_FEE_ESTIMATOR_SCHEMA_VERSION);
+        return Ok(());
+    }
+
+    while version < SCALAR_FEE_ESTIMATOR_SCHEMA_VERSION {
+        let migration = SCALAR_FEE_ESTIMATOR_MIGRATIONS
+            [usize::try_from(version).expect("BUG: negative fee estimator schema version")];
+        for cmd in migration.iter() {
+            conn.execute_batch(cmd)?;
+        }
+        version += 1;
+        conn.execute(
+            "INSERT OR IGNORE INTO scalar_fee_estimator_schema_version (version) VALUES (?)",
+            &[version],
+        )?;
+        debug!("Migrated fee estimator db"; "version" => version);
+    }
+
+    Ok(())
+}
+
+/// Drop the fee rates in `sorted_fee_rates` (ascending) which exceed the median by more
+/// than `max_mads` median absolute deviations. Only high outliers are dropped, and the
+/// median itself is always kept, so a non-empty input never yields an empty output.
+/// If most fee rates equal the median, the MAD is zero and says nothing about how far the
+/// others are spread, so nothing is dropped.
+pub fn filter_outlier_fee_rates(mut sorted_fee_rates: Vec<f64>, max_mads: f64) -> Vec<f64> {
+    if sorted_fee_rates.is_empty() {
+        return sorted_fee_rates;
+    }
+
+    let median = sorted_fee_rates[sorted_fee_rates.len() / 2];
+    let mut deviations: Vec<f64> = sorted_fee_rates
+        .iter()
+        .map(|fee_rate| (fee_rate - median).abs())
+        .collect();
+    deviations.sort_by(|a, b| {
+        a.partial_cmp(b)
+            .expect("BUG: Fee rates should be orderable: NaN and infinite values are filtered")
+    });
+    let mad = deviations[deviations.len() / 2];
+    if mad == 0f64 {
+        return sorted_fee_rates;
+    }
+
+    let cutoff = median + max_mads * mad;
+    let keep = sorted_fee_rates
+        .iter()
+        .position(|fee_rate| *fee_rate > cutoff)
+        .unwrap_or(sorted_fee_rates.len());
+    sorted_fee_rates.truncate(keep);
+    sorted_fee_rates
 }
@@ -1,5 +1,7 @@
 use std::cmp;
+use std::convert::TryFrom;
 
+use crate::chainstate::stacks::TransactionPayload;
 use crate::vm::costs::ExecutionCost;
 
 /// This trait defines metrics used to convert `ExecutionCost` and tx_len usage into single-dimensional
@@ -15,6 +17,20 @@ pub trait CostMetric: Send {
     /// Should return the amount that a metric result will change per
     ///  additional byte in the transaction length
     fn change_per_byte(&self) -> f64;
+    /// Like `from_cost_and_len`, but also given the payload of the transaction, so that a
+    ///  metric can weight different kinds of transactions (e.g., contract deploys and contract
+    ///  calls) differently.
+    ///
+    /// The default implementation ignores the payload and delegates to `from_cost_and_len`.
+    fn from_payload_cost_and_len(
+        &self,
+        _payload: &TransactionPayload,
+        cost: &ExecutionCost,
+        block_limit: &ExecutionCost,
+        tx_len: u64,
+    ) -> u64 {
+        self.from_cost_and_len(cost, block_limit, tx_len)
+    }
 }
 
 impl CostMetric for Box<dyn CostMetric> {
@@ -34,6 +50,17 @@ impl CostMetric for Box<dyn CostMetric> {
     fn change_per_byte(&self) -> f64 {
         self.as_ref().change_per_byte()
     }
+
+    fn from_payload_cost_and_len(
+        &self,
+        payload: &TransactionPayload,
+        cost: &ExecutionCost,
+        block_limit: &ExecutionCost,
+        tx_len: u64,
+    ) -> u64 {
+        self.as_ref()
+            .from_payload_cost_and_len(payload, cost, block_limit, tx_len)
+    }
 }
 
 pub const PROPORTION_RESOLUTION: u64 = 10_000;
@@ -48,6 +75,27 @@ pub struct ProportionalDotProduct {
     block_size_limit: u64,
 }
 
+/// Fixed-point scaling applied to the weights of a `WeightedCostMetric`: a stored
+/// weight of `WEIGHT_RESOLUTION` corresponds to a weight of 1.
+pub const WEIGHT_RESOLUTION: u64 = 1_000;
+
+/// This metric calculates a single dimensional value for a transaction's
+/// consumption as a weighted sum of the proportion of each of the block limit's
+/// dimensions that the transaction consumed (including the block length).
+///
+/// With all weights equal to 1, this is equivalent to `ProportionalDotProduct`
+/// (up to rounding). Operators can use the weights to emphasize whichever
+/// resource they consider to be the bottleneck.
+pub struct WeightedCostMetric {
+    block_size_limit: u64,
+    runtime_weight: u64,
+    read_count_weight: u64,
+    write_count_weight: u64,
+    read_len_weight: u64,
+    write_len_weight: u64,
+    tx_len_weight: u64,
+}
+
 /// This metric always returns a unit value for all execution costs and tx lengths.
 /// When used, this metric will cause block assembly to consider transactions based
 /// solely on their raw transaction fee, not any kind of rate estimation.
@@ -88,6 +136,110 @@ impl CostMetric for ProportionalDotProduct {
     }
 }
 
+/// Compute `PROPORTION_RESOLUTION * value / limit`, saturating at `PROPORTION_RESOLUTION`.
+fn proportion_of_limit(value: u64, limit: u64) -> u64 {
+    //  use MAX(1, limit) to guard against divide by zero
+    //  use MIN(1, value/limit) to guard against value > limit
+    (PROPORTION_RESOLUTION as f64 * 1_f64.min(value as f64 / 1_f64.max(limit as f64))) as u64
+}
+
+impl WeightedCostMetric {
+    /// Create a new metric. `weights` is the tuple
+    ///  `(runtime, read_count, write_count, read_len, write_len, tx_len)`.
+    ///
+    /// Panics if any weight is negative or not finite, or if all of the weights are zero.
+    pub fn new(
+        block_size_limit: u64,
+        weights: (f64, f64, f64, f64, f64, f64),
+    ) -> WeightedCostMetric {
+        let (runtime, read_count, write_count, read_len, write_len, tx_len) = weights;
+        let to_fixed_point = |weight: f64| -> u64 {
+            assert!(
+                weight.is_finite() && weight >= 0f64,
+                "WeightedCostMetric weights must be finite and non-negative"
+            );
+            (weight * WEIGHT_RESOLUTION as f64) as u64
+        };
+        let metric = WeightedCostMetric {
+            block_size_limit,
+            runtime_weight: to_fixed_point(runtime),
+            read_count_weight: to_fixed_point(read_count),
+            write_count_weight: to_fixed_point(write_count),
+            read_len_weight: to_fixed_point(read_len),
+            write_len_weight: to_fixed_point(write_len),
+            tx_len_weight: to_fixed_point(tx_len),
+        };
+        assert!(
+            metric.runtime_weight > 0
+                || metric.read_count_weight > 0
+                || metric.write_count_weight > 0
+                || metric.read_len_weight > 0
+                || metric.write_len_weight > 0
+                || metric.tx_len_weight > 0,
+            "WeightedCostMetric requires at least one non-zero weight"
+        );
+        metric
+    }
+
+    /// Sum `proportion * weight` over each (proportion, weight) pair, and scale
+    ///  the result back down by `WEIGHT_RESOLUTION`. Never returns zero, so
+    ///  that fee rates computed from the result are always finite.
+    fn weighted_sum(terms: &[(u64, u64)]) -> u64 {
+        let sum = terms.iter().fold(0u128, |acc, (proportion, weight)| {
+            acc.saturating_add(u128::from(*proportion) * u128::from(*weight))
+        }) / u128::from(WEIGHT_RESOLUTION);
+        cmp::max(u64::try_from(sum).unwrap_or(u64::MAX), 1)
+    }
+}
+
+impl CostMetric for WeightedCostMetric {
+    fn from_cost_and_len(
+        &self,
+        cost: &ExecutionCost,
+        block_limit: &ExecutionCost,
+        tx_len: u64,
+    ) -> u64 {
+        Self::weighted_sum(&[
+            (
+                proportion_of_limit(cost.runtime, block_limit.runtime),
+                self.runtime_weight,
+            ),
+            (
+                proportion_of_limit(cost.read_count, block_limit.read_count),
+                self.read_count_weight,
+            ),
+            (
+                proportion_of_limit(cost.write_count, block_limit.write_count),
+                self.write_count_weight,
+            ),
+            (
+                proportion_of_limit(cost.read_length, block_limit.read_length),
+                self.read_len_weight,
+            ),
+            (
+                proportion_of_limit(cost.write_length, block_limit.write_length),
+                self.write_len_weight,
+            ),
+            (
+                proportion_of_limit(tx_len, self.block_size_limit),
+                self.tx_len_weight,
+            ),
+        ])
+    }
+
+    fn from_len(&self, tx_len: u64) -> u64 {
+        Self::weighted_sum(&[(
+            proportion_of_limit(tx_len, self.block_size_limit),
+            self.tx_len_weight,
+        )])
+    }
+
+    fn change_per_byte(&self) -> f64 {
+        (PROPORTION_RESOLUTION as f64) * (self.tx_len_weight as f64 / WEIGHT_RESOLUTION as f64)
+            / 1_f64.max(self.block_size_limit as f64)
+    }
+}
+
 impl CostMetric for UnitMetric {
     fn from_cost_and_len(
         &self,
@@ -1,19 +1,26 @@
 use std::cmp;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::iter::FromIterator;
 use std::ops::{Add, Div, Mul, Rem, Sub};
 use std::path::Path;
 use std::{error::Error, fmt::Display};
 
 use crate::chainstate::stacks::events::{StacksTransactionReceipt, TransactionOrigin};
-use crate::chainstate::stacks::{StacksBlock, TransactionPayload};
+use crate::chainstate::stacks::{StacksBlock, TransactionContractCall, TransactionPayload};
 use clarity::vm::costs::ExecutionCost;
+use clarity::vm::types::QualifiedContractIdentifier;
+use clarity::vm::ClarityName;
 use rusqlite::Error as SqliteError;
+use stacks_common::types::chainstate::StacksAddress;
 
 use crate::burnchains::Txid;
 use crate::chainstate::stacks::db::StacksEpochReceipt;
 
+pub mod contract_costs;
+pub mod fee_http;
 pub mod fee_medians;
+pub mod fee_percentiles;
+pub mod fee_rate_floor;
 pub mod fee_rate_fuzzer;
 pub mod fee_scalar;
 pub mod metrics;
@@ -25,6 +32,7 @@ pub mod tests;
 use crate::chainstate::stacks::StacksTransaction;
 use crate::core::StacksEpochId;
 
+pub use self::contract_costs::ContractFunctionCostEstimator;
 use self::metrics::CostMetric;
 pub use self::pessimistic::PessimisticEstimator;
 
@@ -47,8 +55,49 @@ pub trait FeeEstimator {
         receipt: &StacksEpochReceipt,
         block_limit: &ExecutionCost,
     ) -> Result<(), EstimatorError>;
-    /// Get the current estimates for fee rate
-    fn get_rate_estimates(&self) -> Result<FeeRateEstimate, EstimatorError>;
+    /// This method is invoked to update the fee estimator with many block receipts at
+    ///  once, e.g., when replaying historical blocks. `receipts` must be in the order that
+    ///  the blocks were processed.
+    ///
+    /// A default implementation is provided which feeds each receipt into
+    ///  `FeeEstimator::notify_block()`. Implementations can override this to avoid
+    ///  per-receipt storage overhead.
+    fn notify_block_batch(
+        &mut self,
+        receipts: &[StacksEpochReceipt],
+        block_limit: &ExecutionCost,
+    ) -> Result<(), EstimatorError> {
+        for receipt in receipts.iter() {
+            self.notify_block(receipt, block_limit)?;
+        }
+        Ok(())
+    }
+    /// Get the current estimates for fee rate, as the low, middle and high estimates at
+    ///  `FeeEstimator::rate_estimate_percentiles()`.
+    ///
+    /// This is a thin wrapper over `FeeEstimator::get_rate_estimates_at()`.
+    fn get_rate_estimates(&self) -> Result<FeeRateEstimate, EstimatorError> {
+        let percentiles = self.rate_estimate_percentiles();
+        let estimates = self.get_rate_estimates_at(&percentiles)?;
+        FeeRateEstimate::from_percentile_estimates(percentiles, &estimates)
+    }
+    /// Get the current fee rate estimates at each of the given `percentiles`, keyed by
+    ///  the requested percentile. Percentiles are in the range [0, 100], and values above
+    ///  100 are treated as 100.
+    ///
+    /// Estimators which only track the low, middle and high estimates should serve the
+    ///  percentiles in `FeeEstimator::rate_estimate_percentiles()` from those (see
+    ///  `FeeRateEstimate::at_percentiles()`), and return `EstimatorError::NoEstimateAvailable`
+    ///  for any other percentile.
+    fn get_rate_estimates_at(
+        &self,
+        percentiles: &[u8],
+    ) -> Result<BTreeMap<u8, f64>, EstimatorError>;
+    /// The percentiles reported as the low, middle and high estimates by
+    ///  `FeeEstimator::get_rate_estimates()`.
+    fn rate_estimate_percentiles(&self) -> [u8; 3] {
+        FeeRateEstimate::PERCENTILES
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
@@ -73,9 +122,55 @@ fn saturating_f64_math(res: f64) -> f64 {
 }
 
 impl FeeRateEstimate {
+    /// The default percentiles of the low, middle and high estimates.
+    pub const PERCENTILES: [u8; 3] = [5, 50, 95];
+
     pub fn to_vec(self) -> Vec<f64> {
         vec![self.low, self.middle, self.high]
     }
+
+    /// Assemble a `FeeRateEstimate` from the per-percentile `estimates` at the low, middle and
+    ///  high `percentiles`. Returns `EstimatorError::NoEstimateAvailable` if any is missing.
+    pub fn from_percentile_estimates(
+        percentiles: [u8; 3],
+        estimates: &BTreeMap<u8, f64>,
+    ) -> Result<FeeRateEstimate, EstimatorError> {
+        let [low, middle, high] = percentiles;
+        let estimate_at = |percentile| {
+            estimates
+                .get(&percentile)
+                .copied()
+                .ok_or(EstimatorError::NoEstimateAvailable)
+        };
+        Ok(FeeRateEstimate {
+            high: estimate_at(high)?,
+            middle: estimate_at(middle)?,
+            low: estimate_at(low)?,
+        })
+    }
+
+    /// Serve the requested `percentiles` from this estimate, whose low, middle and high
+    ///  estimates are at `estimate_percentiles`. Any other percentile cannot be reconstructed,
+    ///  so it results in `EstimatorError::NoEstimateAvailable`.
+    pub fn at_percentiles(
+        &self,
+        estimate_percentiles: [u8; 3],
+        percentiles: &[u8],
+    ) -> Result<BTreeMap<u8, f64>, EstimatorError> {
+        let [low, middle, high] = estimate_percentiles;
+        percentiles
+            .iter()
+            .map(|percentile| {
+                let fee_rate = match cmp::min(*percentile, 100) {
+                    p if p == low => self.low,
+                    p if p == middle => self.middle,
+                    p if p == high => self.high,
+                    _ => return Err(EstimatorError::NoEstimateAvailable),
+                };
+                Ok((*percentile, fee_rate))
+            })
+            .collect()
+    }
 }
 
 impl Mul<f64> for FeeRateEstimate {
@@ -112,7 +207,8 @@ pub fn estimate_fee_rate<CE: CostEstimator + ?Sized, CM: CostMetric + ?Sized>(
     stacks_epoch_id: &StacksEpochId,
 ) -> Result<f64, EstimatorError> {
     let cost_estimate = estimator.estimate_cost(&tx.payload, stacks_epoch_id)?;
-    let metric_estimate = metric.from_cost_and_len(&cost_estimate, block_limit, tx.tx_len());
+    let metric_estimate =
+        metric.from_payload_cost_and_len(&tx.payload, &cost_estimate, block_limit, tx.tx_len());
     Ok(tx.get_tx_fee() as f64 / metric_estimate as f64)
 }
 
@@ -144,6 +240,27 @@ pub trait CostEstimator: Send {
         evaluated_epoch: &StacksEpochId,
     ) -> Result<ExecutionCost, EstimatorError>;
 
+    /// This method is used to obtain an estimate for a call to `function` in `contract`,
+    /// independent of the call's arguments.
+    ///
+    /// A default implementation is provided which estimates the cost of a contract-call payload
+    /// for that function via `CostEstimator::estimate_cost()`. Estimators which track costs per
+    /// contract function can override this.
+    fn estimate_for_contract_call(
+        &self,
+        contract: &QualifiedContractIdentifier,
+        function: &ClarityName,
+        evaluated_epoch: &StacksEpochId,
+    ) -> Result<ExecutionCost, EstimatorError> {
+        let payload = TransactionPayload::ContractCall(TransactionContractCall {
+            address: StacksAddress::from(contract.issuer.clone()),
+            contract_name: contract.name.clone(),
+            function_name: function.clone(),
+            function_args: vec![],
+        });
+        self.estimate_cost(&payload, evaluated_epoch)
+    }
+
     /// This method is invoked by the `stacks-node` to notify the estimator of all the transaction
     /// receipts in a given block.
     ///
@@ -267,7 +384,72 @@ impl FeeEstimator for () {
         Ok(())
     }
 
-    fn get_rate_estimates(&self) -> Result<FeeRateEstimate, EstimatorError> {
+    fn get_rate_estimates_at(
+        &self,
+        _percentiles: &[u8],
+    ) -> Result<BTreeMap<u8, f64>, EstimatorError> {
+        Err(EstimatorError::NoEstimateAvailable)
+    }
+}
+
+/// Forwards to the boxed estimator, so that a configured `Box<dyn FeeEstimator>` can itself be
+/// wrapped (e.g., by a `FeeRateFloor`).
+impl FeeEstimator for Box<dyn FeeEstimator> {
+    fn notify_block(
+        &mut self,
+        receipt: &StacksEpochReceipt,
+        block_limit: &ExecutionCost,
+    ) -> Result<(), EstimatorError> {
+        (**self).notify_block(receipt, block_limit)
+    }
+
+    fn notify_block_batch(
+        &mut self,
+        receipts: &[StacksEpochReceipt],
+        block_limit: &ExecutionCost,
+    ) -> Result<(), EstimatorError> {
+        (**self).notify_block_batch(receipts, block_limit)
+    }
+
+    fn get_rate_estimates_at(
+        &self,
+        percentiles: &[u8],
+    ) -> Result<BTreeMap<u8, f64>, EstimatorError> {
+        (**self).get_rate_estimates_at(percentiles)
+    }
+
+    fn rate_estimate_percentiles(&self) -> [u8; 3] {
+        (**self).rate_estimate_percentiles()
+    }
+}
+
+/// This fee estimator never produces an estimate, and does not keep any state. It is
+/// intended for nodes which do not mine, and do not want to maintain a fee estimation
+/// database: `notify_block` is a no-op, and `get_rate_estimates_at` always returns
+/// `EstimatorError::NoEstimateAvailable`.
+pub struct DisabledFeeEstimator;
+
+impl FeeEstimator for DisabledFeeEstimator {
+    fn notify_block(
+        &mut self,
+        _receipt: &StacksEpochReceipt,
+        _block_limit: &ExecutionCost,
+    ) -> Result<(), EstimatorError> {
+        Ok(())
+    }
+
+    fn notify_block_batch(
+        &mut self,
+        _receipts: &[StacksEpochReceipt],
+        _block_limit: &ExecutionCost,
+    ) -> Result<(), EstimatorError> {
+        Ok(())
+    }
+
+    fn get_rate_estimates_at(
+        &self,
+        _percentiles: &[u8],
+    ) -> Result<BTreeMap<u8, f64>, EstimatorError> {
         Err(EstimatorError::NoEstimateAvailable)
     }
 }
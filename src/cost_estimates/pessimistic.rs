@@ -57,7 +57,7 @@ iterable_enum!(CostField {
 
 impl CostField {
     /// Select `self` out of the given ExecutionCost
-    fn select_key(&self, from_cost: &ExecutionCost) -> u64 {
+    pub fn select_key(&self, from_cost: &ExecutionCost) -> u64 {
         match self {
             CostField::RuntimeCost => from_cost.runtime,
             CostField::WriteLength => from_cost.write_length,
@@ -223,25 +223,14 @@ impl PessimisticEstimator {
     ) -> String {
         let tx_descriptor = match tx {
             TransactionPayload::TokenTransfer(..) => "stx-transfer".to_string(),
-            TransactionPayload::ContractCall(cc) => {
-                // Epoch key is "" before Epoch2_05 for backwards compatibility.
-                let epoch_marker = match evaluated_epoch {
-                    StacksEpochId::Epoch10 => "",
-                    StacksEpochId::Epoch20 => "",
-                    StacksEpochId::Epoch2_05 => ":2.05",
-                    StacksEpochId::Epoch21 => ":2.1",
-                    // reuse cost estimates in Epoch22
-                    StacksEpochId::Epoch22 => ":2.1",
-                    // reuse cost estimates in Epoch23
-                    StacksEpochId::Epoch23 => ":2.1",
-                    // reuse cost estimates in Epoch24
-                    StacksEpochId::Epoch24 => ":2.1",
-                };
-                format!(
-                    "cc{}:{}:{}.{}",
-                    epoch_marker, cc.address, cc.contract_name, cc.function_name
-                )
-            }
+            TransactionPayload::BatchTokenTransfer(..) => "stx-batch-transfer".to_string(),
+            TransactionPayload::ContractCall(cc) => format!(
+                "cc{}:{}:{}.{}",
+                contract_call_epoch_marker(evaluated_epoch),
+                cc.address,
+                cc.contract_name,
+                cc.function_name
+            ),
             TransactionPayload::SmartContract(..) => "contract-publish".to_string(),
             TransactionPayload::PoisonMicroblock(_, _) => "poison-ublock".to_string(),
             TransactionPayload::Coinbase(..) => "coinbase".to_string(),
@@ -251,6 +240,26 @@ impl PessimisticEstimator {
     }
 }
 
+/// The marker that separates contract-call cost estimates made under different cost contracts.
+/// Epochs that share a cost contract share a marker, so their estimates carry over.
+pub(crate) fn contract_call_epoch_marker(evaluated_epoch: &StacksEpochId) -> &'static str {
+    // Epoch key is "" before Epoch2_05 for backwards compatibility.
+    match evaluated_epoch {
+        StacksEpochId::Epoch10 => "",
+        StacksEpochId::Epoch20 => "",
+        StacksEpochId::Epoch2_05 => ":2.05",
+        StacksEpochId::Epoch21 => ":2.1",
+        // reuse cost estimates in Epoch22
+        StacksEpochId::Epoch22 => ":2.1",
+        // reuse cost estimates in Epoch23
+        StacksEpochId::Epoch23 => ":2.1",
+        // reuse cost estimates in Epoch24
+        StacksEpochId::Epoch24 => ":2.1",
+        // costs-4 is the default cost contract from Epoch25 onward
+        StacksEpochId::Epoch25 => ":2.5",
+    }
+}
+
 impl From<SqliteError> for EstimatorError {
     fn from(e: SqliteError) -> Self {
         EstimatorError::SqliteError(e)
@@ -0,0 +1,266 @@
+use std::env;
+
+use rand::Rng;
+
+use crate::chainstate::stacks::{TokenTransferMemo, TransactionContractCall, TransactionPayload};
+use crate::core::StacksEpochId;
+use crate::core::BLOCK_LIMIT_MAINNET_20;
+use crate::cost_estimates::{ContractFunctionCostEstimator, CostEstimator, EstimatorError};
+use crate::vm::types::{PrincipalData, QualifiedContractIdentifier, StandardPrincipalData};
+use crate::vm::ClarityName;
+use clarity::vm::costs::ExecutionCost;
+use stacks_common::types::chainstate::StacksAddress;
+use stacks_common::util::hash::{to_hex, Hash160};
+
+fn instantiate_test_db() -> ContractFunctionCostEstimator {
+    let mut path = env::temp_dir();
+    let random_bytes = rand::thread_rng().gen::<[u8; 32]>();
+    path.push(&format!("cost_db_{}.sqlite", &to_hex(&random_bytes)[0..8]));
+
+    ContractFunctionCostEstimator::open(&path).expect("Test failure: could not open cost DB")
+}
+
+fn make_dummy_cc_payload(contract_name: &str, function_name: &str) -> TransactionPayload {
+    TransactionPayload::ContractCall(TransactionContractCall {
+        address: StacksAddress::new(0, Hash160([0; 20])),
+        contract_name: contract_name.into(),
+        function_name: function_name.into(),
+        function_args: vec![],
+    })
+}
+
+fn dummy_contract(contract_name: &str) -> QualifiedContractIdentifier {
+    QualifiedContractIdentifier::new(
+        StandardPrincipalData::from(StacksAddress::new(0, Hash160([0; 20]))),
+        contract_name.into(),
+    )
+}
+
+fn cost_with_runtime(runtime: u64) -> ExecutionCost {
+    ExecutionCost {
+        write_length: 1,
+        write_count: 1,
+        read_length: 10,
+        read_count: 2,
+        runtime,
+    }
+}
+
+/// Returns true iff `estimate` is within 10% of `expected`.
+fn is_within_10_pct(estimate: u64, expected: u64) -> bool {
+    (estimate as f64 - expected as f64).abs() <= 0.1 * expected as f64
+}
+
+#[test]
+fn test_empty_contract_function_estimator() {
+    let estimator = instantiate_test_db();
+    assert_eq!(
+        estimator
+            .estimate_function_cost(
+                &dummy_contract("contract-1"),
+                &ClarityName::from("func1"),
+                &StacksEpochId::Epoch21
+            )
+            .expect_err("Empty estimator should error."),
+        EstimatorError::NoEstimateAvailable
+    );
+}
+
+/// After observing 20 calls of a function, its estimate is the median of the observed costs.
+#[test]
+fn test_contract_function_estimate_tracks_median() {
+    let mut estimator = instantiate_test_db();
+    let payload = make_dummy_cc_payload("contract-1", "func1");
+
+    // runtimes 1000, 1100, ..., 2900, fed in an arbitrary order: the median is 2000
+    for i in 0..20u64 {
+        let runtime = 1000 + 100 * ((i * 7) % 20);
+        estimator
+            .notify_event(
+                &payload,
+                &cost_with_runtime(runtime),
+                &BLOCK_LIMIT_MAINNET_20,
+                &StacksEpochId::Epoch21,
+            )
+            .expect("Should be able to process event");
+    }
+
+    let estimate = estimator
+        .estimate_function_cost(
+            &dummy_contract("contract-1"),
+            &ClarityName::from("func1"),
+            &StacksEpochId::Epoch21,
+        )
+        .expect("Should be able to create estimate now");
+    assert!(
+        is_within_10_pct(estimate.runtime, 2000),
+        "runtime estimate {} is not within 10% of 2000",
+        estimate.runtime
+    );
+    assert!(is_within_10_pct(estimate.read_length, 10));
+    assert_eq!(estimate.read_count, 2);
+    assert_eq!(estimate.write_count, 1);
+    assert_eq!(estimate.write_length, 1);
+
+    // the trait methods agree with the direct estimate
+    assert_eq!(
+        estimator
+            .estimate_cost(&payload, &StacksEpochId::Epoch21)
+            .unwrap(),
+        estimate
+    );
+    assert_eq!(
+        estimator
+            .estimate_for_contract_call(
+                &dummy_contract("contract-1"),
+                &ClarityName::from("func1"),
+                &StacksEpochId::Epoch21
+            )
+            .unwrap(),
+        estimate
+    );
+}
+
+/// Each (contract, function) pair has its own histogram.
+#[test]
+fn test_contract_function_estimates_are_separate() {
+    let mut estimator = instantiate_test_db();
+
+    for _ in 0..5 {
+        for (contract_name, function_name, runtime) in [
+            ("contract-1", "cheap", 10),
+            ("contract-1", "expensive", 10_000),
+            ("contract-2", "cheap", 500),
+        ] {
+            estimator
+                .notify_event(
+                    &make_dummy_cc_payload(contract_name, function_name),
+                    &cost_with_runtime(runtime),
+                    &BLOCK_LIMIT_MAINNET_20,
+                    &StacksEpochId::Epoch21,
+                )
+                .expect("Should be able to process event");
+        }
+    }
+
+    for (contract_name, function_name, runtime) in [
+        ("contract-1", "cheap", 10),
+        ("contract-1", "expensive", 10_000),
+        ("contract-2", "cheap", 500),
+    ] {
+        let estimate = estimator
+            .estimate_function_cost(
+                &dummy_contract(contract_name),
+                &ClarityName::from(function_name),
+                &StacksEpochId::Epoch21,
+            )
+            .unwrap();
+        assert!(is_within_10_pct(estimate.runtime, runtime));
+    }
+
+    assert_eq!(
+        estimator
+            .estimate_function_cost(
+                &dummy_contract("contract-2"),
+                &ClarityName::from("other"),
+                &StacksEpochId::Epoch21
+            )
+            .expect_err("Unobserved function should error."),
+        EstimatorError::NoEstimateAvailable
+    );
+}
+
+/// Costs observed under one cost contract do not inform estimates under another, but epochs that
+/// share a cost contract share estimates.
+#[test]
+fn test_contract_function_estimates_are_per_epoch() {
+    let mut estimator = instantiate_test_db();
+    let payload = make_dummy_cc_payload("contract-1", "func1");
+
+    for (runtime, epoch) in [
+        (1000, StacksEpochId::Epoch21),
+        (5000, StacksEpochId::Epoch25),
+    ] {
+        for _ in 0..5 {
+            estimator
+                .notify_event(
+                    &payload,
+                    &cost_with_runtime(runtime),
+                    &BLOCK_LIMIT_MAINNET_20,
+                    &epoch,
+                )
+                .expect("Should be able to process event");
+        }
+    }
+
+    for (runtime, epoch) in [
+        (1000, StacksEpochId::Epoch21),
+        (1000, StacksEpochId::Epoch24),
+        (5000, StacksEpochId::Epoch25),
+    ] {
+        let estimate = estimator.estimate_cost(&payload, &epoch).unwrap();
+        assert!(is_within_10_pct(estimate.runtime, runtime));
+    }
+    assert_eq!(
+        estimator
+            .estimate_cost(&payload, &StacksEpochId::Epoch2_05)
+            .expect_err("Unobserved epoch should error."),
+        EstimatorError::NoEstimateAvailable
+    );
+}
+
+/// Older observations decay, so the estimate follows a function whose cost has changed even
+/// before most of its observations reflect the change.
+#[test]
+fn test_contract_function_estimate_decays() {
+    let mut estimator = instantiate_test_db();
+    let payload = make_dummy_cc_payload("contract-1", "func1");
+
+    for (runtime, count) in [(1000, 50), (5000, 40)] {
+        for _ in 0..count {
+            estimator
+                .notify_event(
+                    &payload,
+                    &cost_with_runtime(runtime),
+                    &BLOCK_LIMIT_MAINNET_20,
+                    &StacksEpochId::Epoch21,
+                )
+                .expect("Should be able to process event");
+        }
+    }
+
+    let estimate = estimator
+        .estimate_cost(&payload, &StacksEpochId::Epoch21)
+        .unwrap();
+    assert!(
+        is_within_10_pct(estimate.runtime, 5000),
+        "runtime estimate {} is not within 10% of 5000",
+        estimate.runtime
+    );
+}
+
+/// Only contract calls are tracked.
+#[test]
+fn test_contract_function_estimator_ignores_other_payloads() {
+    let mut estimator = instantiate_test_db();
+    let transfer = TransactionPayload::TokenTransfer(
+        PrincipalData::Standard(StandardPrincipalData(0, [0; 20])),
+        1,
+        TokenTransferMemo([0; 34]),
+    );
+
+    estimator
+        .notify_event(
+            &transfer,
+            &cost_with_runtime(100),
+            &BLOCK_LIMIT_MAINNET_20,
+            &StacksEpochId::Epoch21,
+        )
+        .expect("Should be able to process event");
+    assert_eq!(
+        estimator
+            .estimate_cost(&transfer, &StacksEpochId::Epoch21)
+            .expect_err("Transfers should not be estimated."),
+        EstimatorError::NoEstimateAvailable
+    );
+}
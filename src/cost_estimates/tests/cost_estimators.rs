@@ -30,8 +30,8 @@ use crate::cost_estimates::tests::common::*;
 use crate::cost_estimates::CostEstimator;
 use crate::cost_estimates::FeeRateEstimate;
 use crate::cost_estimates::PessimisticEstimator;
-use crate::vm::types::{PrincipalData, StandardPrincipalData};
-use crate::vm::Value;
+use crate::vm::types::{PrincipalData, QualifiedContractIdentifier, StandardPrincipalData};
+use crate::vm::{ClarityName, Value};
 
 fn instantiate_test_db() -> PessimisticEstimator {
     let mut path = env::temp_dir();
@@ -76,6 +76,49 @@ fn test_empty_pessimistic_estimator() {
     );
 }
 
+/// The default `estimate_for_contract_call` estimates a contract-call payload for the function.
+#[test]
+fn test_pessimistic_estimate_for_contract_call() {
+    let mut estimator = instantiate_test_db();
+    let contract = QualifiedContractIdentifier::new(
+        StandardPrincipalData::from(StacksAddress::new(0, Hash160([0; 20]))),
+        "contract-1".into(),
+    );
+    let function = ClarityName::from("func1");
+
+    assert_eq!(
+        estimator
+            .estimate_for_contract_call(&contract, &function, &StacksEpochId::Epoch21)
+            .expect_err("Empty pessimistic estimator should error."),
+        EstimatorError::NoEstimateAvailable
+    );
+
+    let payload = make_dummy_cc_payload("contract-1", "func1");
+    estimator
+        .notify_event(
+            &payload,
+            &ExecutionCost {
+                write_length: 1,
+                write_count: 2,
+                read_length: 3,
+                read_count: 4,
+                runtime: 5,
+            },
+            &BLOCK_LIMIT_MAINNET_20,
+            &StacksEpochId::Epoch21,
+        )
+        .expect("Should be able to process event");
+
+    assert_eq!(
+        estimator
+            .estimate_for_contract_call(&contract, &function, &StacksEpochId::Epoch21)
+            .unwrap(),
+        estimator
+            .estimate_cost(&payload, &StacksEpochId::Epoch21)
+            .unwrap()
+    );
+}
+
 fn make_dummy_coinbase_tx() -> StacksTransactionReceipt {
     StacksTransactionReceipt::from_coinbase(StacksTransaction::new(
         TransactionVersion::Mainnet,
@@ -875,3 +918,76 @@ fn test_cost_estimator_epochs_independent() {
         cost_205.clone(),
     );
 }
+
+/// Test that observed costs are persisted, so that a restarted node which replays blocks
+/// through `notify_event` keeps building on what it had already learned.
+#[test]
+fn test_cost_estimator_survives_reopen() {
+    let mut path = env::temp_dir();
+    let random_bytes = rand::thread_rng().gen::<[u8; 32]>();
+    path.push(&format!("fee_db_{}.sqlite", &to_hex(&random_bytes)[0..8]));
+
+    let cost_1 = ExecutionCost {
+        write_length: 10,
+        write_count: 10,
+        read_length: 10,
+        read_count: 10,
+        runtime: 10,
+    };
+    let cost_2 = ExecutionCost {
+        write_length: 30,
+        write_count: 30,
+        read_length: 30,
+        read_count: 30,
+        runtime: 30,
+    };
+
+    let mut estimator =
+        PessimisticEstimator::open(&path, true).expect("Test failure: could not open fee rate DB");
+    estimator
+        .notify_event(
+            &make_dummy_cc_payload("contract-1", "func1"),
+            &cost_1,
+            &BLOCK_LIMIT_MAINNET_20,
+            &StacksEpochId::Epoch21,
+        )
+        .expect("Should be able to process event");
+    drop(estimator);
+
+    let mut estimator = PessimisticEstimator::open(&path, true)
+        .expect("Test failure: could not re-open fee rate DB");
+    assert_eq!(
+        estimator
+            .estimate_cost(
+                &make_dummy_cc_payload("contract-1", "func1"),
+                &StacksEpochId::Epoch21
+            )
+            .expect("Should be able to provide cost estimate after reopening"),
+        cost_1
+    );
+
+    // New observations are combined with the samples recorded before the restart.
+    estimator
+        .notify_event(
+            &make_dummy_cc_payload("contract-1", "func1"),
+            &cost_2,
+            &BLOCK_LIMIT_MAINNET_20,
+            &StacksEpochId::Epoch21,
+        )
+        .expect("Should be able to process event");
+    assert_eq!(
+        estimator
+            .estimate_cost(
+                &make_dummy_cc_payload("contract-1", "func1"),
+                &StacksEpochId::Epoch21
+            )
+            .expect("Should be able to provide cost estimate now"),
+        ExecutionCost {
+            write_length: 20,
+            write_count: 20,
+            read_length: 20,
+            read_count: 20,
+            runtime: 20,
+        }
+    );
+}
@@ -0,0 +1,158 @@
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::thread;
+use std::time::Duration;
+
+use clarity::vm::costs::ExecutionCost;
+
+use crate::chainstate::stacks::events::StacksTransactionReceipt;
+use crate::cost_estimates::fee_http::HttpFeeRateEstimator;
+use crate::cost_estimates::tests::common::make_block_receipt;
+use crate::cost_estimates::tests::fee_scalar::make_dummy_coinbase_tx;
+use crate::cost_estimates::{EstimatorError, FeeEstimator, FeeRateEstimate};
+
+/// Serve each of `responses`, in order, to one connection apiece. Returns the oracle's base URL,
+/// and a handle that yields the request lines that were received.
+fn spawn_oracle(responses: Vec<String>) -> (String, thread::JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = thread::spawn(move || {
+        let mut request_lines = vec![];
+        for response in responses.into_iter() {
+            let (mut sock, _) = listener.accept().unwrap();
+            let mut request = vec![];
+            let mut buf = [0u8; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let nr = sock.read(&mut buf).unwrap();
+                assert!(nr > 0);
+                request.extend_from_slice(&buf[0..nr]);
+            }
+            let request = String::from_utf8(request).unwrap();
+            request_lines.push(request.lines().next().unwrap().to_string());
+            sock.write_all(response.as_bytes()).unwrap();
+        }
+        request_lines
+    });
+    (base_url, handle)
+}
+
+fn make_response(status: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.0 {}\r\nContent-Type: application/json; charset=utf-8\r\nContent-Length: {}\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+}
+
+#[test]
+fn test_http_estimator_url() {
+    let estimator =
+        HttpFeeRateEstimator::new("http://127.0.0.1:20443", Duration::from_secs(1)).unwrap();
+    assert_eq!(
+        estimator.url().as_str(),
+        "http://127.0.0.1:20443/fee_rate_estimates"
+    );
+
+    let estimator =
+        HttpFeeRateEstimator::new("http://oracle.example.com/v1/", Duration::from_secs(1)).unwrap();
+    assert_eq!(
+        estimator.url().as_str(),
+        "http://oracle.example.com/v1/fee_rate_estimates"
+    );
+
+    assert!(
+        HttpFeeRateEstimator::new("https://oracle.example.com", Duration::from_secs(1)).is_err()
+    );
+    assert!(HttpFeeRateEstimator::new("not a url", Duration::from_secs(1)).is_err());
+}
+
+/// Estimates come from the oracle, and the last one is used when the oracle is unavailable.
+#[test]
+fn test_http_estimator_caches_last_estimate() {
+    let (base_url, oracle) = spawn_oracle(vec![
+        make_response("200 OK", r#"{"high": 30.5, "middle": 20, "low": 10}"#),
+        make_response("500 Internal Server Error", r#"{"error": "oops"}"#),
+        make_response("200 OK", "not json"),
+        make_response("200 OK", r#"{"high": 3, "middle": 2, "low": 1}"#),
+    ]);
+    let mut estimator = HttpFeeRateEstimator::new(&base_url, Duration::from_secs(5)).unwrap();
+
+    // blocks are ignored
+    let block_receipt = make_block_receipt(vec![StacksTransactionReceipt::from_coinbase(
+        make_dummy_coinbase_tx(),
+    )]);
+    estimator
+        .notify_block(&block_receipt, &ExecutionCost::max_value())
+        .unwrap();
+
+    let first_estimate = FeeRateEstimate {
+        high: 30.5,
+        middle: 20f64,
+        low: 10f64,
+    };
+    assert_eq!(estimator.get_rate_estimates().unwrap(), first_estimate);
+
+    // an error status and a malformed reply both fall back to the cached estimate
+    assert_eq!(estimator.get_rate_estimates().unwrap(), first_estimate);
+    assert_eq!(estimator.get_rate_estimates().unwrap(), first_estimate);
+
+    // a new estimate replaces the cached one
+    let last_estimate = FeeRateEstimate {
+        high: 3f64,
+        middle: 2f64,
+        low: 1f64,
+    };
+    assert_eq!(estimator.get_rate_estimates().unwrap(), last_estimate);
+
+    let request_lines = oracle.join().unwrap();
+    assert_eq!(request_lines.len(), 4);
+    for request_line in request_lines.iter() {
+        assert_eq!(request_line, "GET /fee_rate_estimates HTTP/1.0");
+    }
+
+    // the oracle is gone
+    assert_eq!(estimator.get_rate_estimates().unwrap(), last_estimate);
+}
+
+/// Without a cached estimate, a failed request means no estimate is available.
+#[test]
+fn test_http_estimator_no_estimate_without_cache() {
+    // nothing is listening on this port once the listener is dropped
+    let base_url = {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    };
+    let estimator = HttpFeeRateEstimator::new(&base_url, Duration::from_secs(1)).unwrap();
+    assert_eq!(
+        estimator.get_rate_estimates().unwrap_err(),
+        EstimatorError::NoEstimateAvailable
+    );
+
+    // negative rates are rejected
+    let (base_url, oracle) = spawn_oracle(vec![make_response(
+        "200 OK",
+        r#"{"high": 3, "middle": 2, "low": -1}"#,
+    )]);
+    let estimator = HttpFeeRateEstimator::new(&base_url, Duration::from_secs(5)).unwrap();
+    assert_eq!(
+        estimator.get_rate_estimates().unwrap_err(),
+        EstimatorError::NoEstimateAvailable
+    );
+    oracle.join().unwrap();
+}
+
+/// An oracle that never replies is given up on after the timeout.
+#[test]
+fn test_http_estimator_timeout() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let estimator = HttpFeeRateEstimator::new(&base_url, Duration::from_millis(100)).unwrap();
+
+    // the connection is accepted by the OS, but the listener never answers
+    assert_eq!(
+        estimator.get_rate_estimates().unwrap_err(),
+        EstimatorError::NoEstimateAvailable
+    );
+    drop(listener);
+}
@@ -218,6 +218,49 @@ fn test_one_block_mostly_filled() {
     ));
 }
 
+/// The 5th, 50th and 95th percentiles are served from the low, middle and high estimates, and
+/// no other percentile is available.
+#[test]
+fn test_rate_estimates_at_tracked_percentiles() {
+    let metric = ProportionalDotProduct::new(10_000);
+    let mut estimator = instantiate_test_db(metric);
+
+    assert_eq!(
+        estimator
+            .get_rate_estimates_at(&[50])
+            .expect_err("Empty rate estimator should error."),
+        EstimatorError::NoEstimateAvailable
+    );
+
+    let single_tx_receipt = make_block_receipt(vec![
+        StacksTransactionReceipt::from_coinbase(make_dummy_coinbase_tx()),
+        make_dummy_cc_tx(10 * half_operation_cost_basis, &half_operation_cost),
+        make_dummy_cc_tx(10 * tenth_operation_cost_basis, &tenth_operation_cost),
+        make_dummy_cc_tx(10 * tenth_operation_cost_basis, &tenth_operation_cost),
+        make_dummy_cc_tx(10 * tenth_operation_cost_basis, &tenth_operation_cost),
+    ]);
+    estimator
+        .notify_block(&single_tx_receipt, &block_limit)
+        .expect("Should be able to process block receipt");
+
+    let estimate = estimator
+        .get_rate_estimates()
+        .expect("Should be able to create estimate now");
+    let estimates = estimator
+        .get_rate_estimates_at(&[5, 50, 95])
+        .expect("Tracked percentiles should be available");
+    assert_eq!(estimates[&5], estimate.low);
+    assert_eq!(estimates[&50], estimate.middle);
+    assert_eq!(estimates[&95], estimate.high);
+
+    assert_eq!(
+        estimator
+            .get_rate_estimates_at(&[25, 50])
+            .expect_err("Untracked percentiles are not available"),
+        EstimatorError::NoEstimateAvailable
+    );
+}
+
 /// Tests the effect of adding blocks over time. We add five blocks with an easy to calculate
 /// median.
 ///
@@ -286,6 +329,53 @@ fn test_window_size_forget_something() {
     ));
 }
 
+/// Tests that the window is persisted, so that an estimator re-opened at the same path reports
+/// the same estimate.
+#[test]
+fn test_window_survives_reopen() {
+    let mut path = env::temp_dir();
+    let random_bytes = rand::thread_rng().gen::<[u8; 32]>();
+    path.push(&format!("fee_db_{}.sqlite", &to_hex(&random_bytes)[0..8]));
+
+    let mut estimator =
+        WeightedMedianFeeRateEstimator::open(&path, ProportionalDotProduct::new(10_000), 5)
+            .expect("Test failure: could not open fee rate DB");
+
+    for i in 1..6 {
+        let single_tx_receipt = make_block_receipt(vec![
+            StacksTransactionReceipt::from_coinbase(make_dummy_coinbase_tx()),
+            make_dummy_cc_tx(i * 10 * half_operation_cost_basis, &half_operation_cost),
+            make_dummy_cc_tx(i * 10 * half_operation_cost_basis, &half_operation_cost),
+        ]);
+
+        estimator
+            .notify_block(&single_tx_receipt, &block_limit)
+            .expect("Should be able to process block receipt");
+    }
+
+    let before = estimator
+        .get_rate_estimates()
+        .expect("Should be able to create estimate now");
+    drop(estimator);
+
+    let estimator =
+        WeightedMedianFeeRateEstimator::open(&path, ProportionalDotProduct::new(10_000), 5)
+            .expect("Test failure: could not re-open fee rate DB");
+    let after = estimator
+        .get_rate_estimates()
+        .expect("Re-opened estimator should still have an estimate");
+
+    assert!(is_close(before, after.clone()));
+    assert!(is_close(
+        after,
+        FeeRateEstimate {
+            high: 30f64,
+            middle: 30f64,
+            low: 30f64
+        }
+    ));
+}
+
 #[test]
 fn test_fee_rate_estimate_5_vs_95() {
     assert_eq!(
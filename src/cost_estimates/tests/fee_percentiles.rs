@@ -0,0 +1,246 @@
+use std::env;
+
+use rand::Rng;
+
+use crate::cost_estimates::metrics::CostMetric;
+use crate::cost_estimates::{EstimatorError, FeeEstimator};
+use clarity::vm::costs::ExecutionCost;
+
+use crate::chainstate::stacks::events::StacksTransactionReceipt;
+use stacks_common::util::hash::{to_hex, Hash160};
+
+use crate::chainstate::stacks::{
+    CoinbasePayload, StacksTransaction, TransactionAuth, TransactionContractCall,
+    TransactionPayload, TransactionSpendingCondition, TransactionVersion,
+};
+use crate::cost_estimates::fee_percentiles::fee_rate_estimate_from_sorted_fees;
+use crate::cost_estimates::fee_percentiles::PercentileFeeRateEstimator;
+use crate::cost_estimates::metrics::ProportionalDotProduct;
+use crate::cost_estimates::tests::common::*;
+use crate::cost_estimates::FeeRateEstimate;
+use crate::types::chainstate::StacksAddress;
+use crate::vm::Value;
+
+/// Returns true iff `b` is within `0.1%` of `a`.
+fn is_close_f64(a: f64, b: f64) -> bool {
+    let error = (a - b).abs() / a.abs();
+    error < 0.001
+}
+
+/// Returns `true` iff each value in `left` "close" to its counterpart in `right`.
+fn is_close(left: FeeRateEstimate, right: FeeRateEstimate) -> bool {
+    let is_ok = is_close_f64(left.high, right.high)
+        && is_close_f64(left.middle, right.middle)
+        && is_close_f64(left.low, right.low);
+    if !is_ok {
+        warn!("FeeRateEstimate's are not close. {:?} vs {:?}", left, right);
+    }
+    is_ok
+}
+
+fn instantiate_test_db<CM: CostMetric>(m: CM) -> PercentileFeeRateEstimator<CM> {
+    let mut path = env::temp_dir();
+    let random_bytes = rand::thread_rng().gen::<[u8; 32]>();
+    path.push(&format!("fee_db_{}.sqlite", &to_hex(&random_bytes)[0..8]));
+
+    let window_size = 5;
+    PercentileFeeRateEstimator::open(&path, m, window_size)
+        .expect("Test failure: could not open fee rate DB")
+}
+
+fn make_dummy_coinbase_tx() -> StacksTransaction {
+    StacksTransaction::new(
+        TransactionVersion::Mainnet,
+        TransactionAuth::Standard(TransactionSpendingCondition::new_initial_sighash()),
+        TransactionPayload::Coinbase(CoinbasePayload([0; 32]), None),
+    )
+}
+
+fn make_dummy_cc_tx(fee: u64, execution_cost: &ExecutionCost) -> StacksTransactionReceipt {
+    let mut tx = StacksTransaction::new(
+        TransactionVersion::Mainnet,
+        TransactionAuth::Standard(TransactionSpendingCondition::new_initial_sighash()),
+        TransactionPayload::ContractCall(TransactionContractCall {
+            address: StacksAddress::new(0, Hash160([0; 20])),
+            contract_name: "cc-dummy".into(),
+            function_name: "func-name".into(),
+            function_args: vec![],
+        }),
+    );
+    tx.set_tx_fee(fee);
+    StacksTransactionReceipt::from_contract_call(
+        tx,
+        vec![],
+        Value::okay(Value::Bool(true)).unwrap(),
+        0,
+        execution_cost.clone(),
+    )
+}
+
+const block_limit: ExecutionCost = ExecutionCost {
+    write_length: 100,
+    write_count: 100,
+    read_length: 100,
+    read_count: 100,
+    runtime: 100,
+};
+
+const half_operation_cost: ExecutionCost = ExecutionCost {
+    write_length: 0,
+    write_count: 0,
+    read_length: 0,
+    read_count: 0,
+    runtime: 50,
+};
+
+// The scalar cost of `make_dummy_cc_tx(_, &half_operation_cost)`.
+const half_operation_cost_basis: u64 = 5164;
+
+/// Tests that we have no estimate available until we `notify`.
+#[test]
+fn test_empty_fee_estimator() {
+    let metric = ProportionalDotProduct::new(10_000);
+    let estimator = instantiate_test_db(metric);
+    assert_eq!(
+        estimator
+            .get_rate_estimates()
+            .expect_err("Empty rate estimator should error."),
+        EstimatorError::NoEstimateAvailable
+    );
+}
+
+/// Blocks containing only a coinbase should not produce an estimate.
+#[test]
+fn test_coinbase_only_block_not_counted() {
+    let metric = ProportionalDotProduct::new(10_000);
+    let mut estimator = instantiate_test_db(metric);
+
+    let coinbase_only_receipt = make_block_receipt(vec![StacksTransactionReceipt::from_coinbase(
+        make_dummy_coinbase_tx(),
+    )]);
+    estimator
+        .notify_block(&coinbase_only_receipt, &block_limit)
+        .expect("Should be able to process block receipt");
+
+    assert_eq!(
+        estimator
+            .get_rate_estimates()
+            .expect_err("Coinbase-only blocks should not produce an estimate."),
+        EstimatorError::NoEstimateAvailable
+    );
+}
+
+/// Tests the percentiles computed from a single block with fee rates [1, 2, .., 10].
+#[test]
+fn test_one_block_percentiles() {
+    let metric = ProportionalDotProduct::new(10_000);
+    let mut estimator = instantiate_test_db(metric);
+
+    let mut tx_receipts = vec![StacksTransactionReceipt::from_coinbase(
+        make_dummy_coinbase_tx(),
+    )];
+    for i in 1..11 {
+        tx_receipts.push(make_dummy_cc_tx(
+            i * half_operation_cost_basis,
+            &half_operation_cost,
+        ));
+    }
+    estimator
+        .notify_block(&make_block_receipt(tx_receipts), &block_limit)
+        .expect("Should be able to process block receipt");
+
+    assert!(is_close(
+        estimator
+            .get_rate_estimates()
+            .expect("Should be able to create estimate now"),
+        FeeRateEstimate {
+            high: 9.1f64,
+            middle: 5.5f64,
+            low: 1.9f64
+        }
+    ));
+}
+
+/// We add 10 blocks with window size 5 so the first 5 should be forgotten. Coinbase-only
+/// blocks interleaved with them should not take up space in the window.
+#[test]
+fn test_window_size_forget_something() {
+    let metric = ProportionalDotProduct::new(10_000);
+    let mut estimator = instantiate_test_db(metric);
+
+    for i in 1..11 {
+        let single_tx_receipt = make_block_receipt(vec![
+            StacksTransactionReceipt::from_coinbase(make_dummy_coinbase_tx()),
+            make_dummy_cc_tx(i * 10 * half_operation_cost_basis, &half_operation_cost),
+        ]);
+        estimator
+            .notify_block(&single_tx_receipt, &block_limit)
+            .expect("Should be able to process block receipt");
+
+        let coinbase_only_receipt =
+            make_block_receipt(vec![StacksTransactionReceipt::from_coinbase(
+                make_dummy_coinbase_tx(),
+            )]);
+        estimator
+            .notify_block(&coinbase_only_receipt, &block_limit)
+            .expect("Should be able to process block receipt");
+    }
+
+    // The window holds [60, 70, 80, 90, 100].
+    assert!(is_close(
+        estimator
+            .get_rate_estimates()
+            .expect("Should be able to create estimate now"),
+        FeeRateEstimate {
+            high: 96f64,
+            middle: 80f64,
+            low: 64f64
+        }
+    ));
+}
+
+#[test]
+fn test_fee_rate_estimate_single_value() {
+    assert!(is_close(
+        fee_rate_estimate_from_sorted_fees(&[7f64]),
+        FeeRateEstimate {
+            high: 7f64,
+            middle: 7f64,
+            low: 7f64
+        }
+    ));
+}
+
+/// Tests estimates at arbitrary percentiles from a single block with fee rates [1, 2, .., 10].
+#[test]
+fn test_rate_estimates_at_percentiles() {
+    let metric = ProportionalDotProduct::new(10_000);
+    let mut estimator = instantiate_test_db(metric);
+
+    assert_eq!(
+        estimator
+            .get_rate_estimates_at(&[50])
+            .expect_err("Empty rate estimator should error."),
+        EstimatorError::NoEstimateAvailable
+    );
+
+    let mut tx_receipts = vec![];
+    for i in 1..11 {
+        tx_receipts.push(make_dummy_cc_tx(
+            i * half_operation_cost_basis,
+            &half_operation_cost,
+        ));
+    }
+    estimator
+        .notify_block(&make_block_receipt(tx_receipts), &block_limit)
+        .expect("Should be able to process block receipt");
+
+    let estimates = estimator
+        .get_rate_estimates_at(&[0, 25, 75, 100])
+        .expect("Should be able to create estimate now");
+    assert_eq!(estimates.len(), 4);
+    assert!(is_close_f64(estimates[&0], 1f64));
+    assert!(is_close_f64(estimates[&25], 3.25f64));
+    assert!(is_close_f64(estimates[&75], 7.75f64));
+    assert!(is_close_f64(estimates[&100], 10f64));
+}
@@ -0,0 +1,142 @@
+use clarity::vm::costs::ExecutionCost;
+
+use crate::chainstate::stacks::events::StacksTransactionReceipt;
+use crate::cost_estimates::fee_rate_floor::FeeRateFloor;
+use crate::cost_estimates::{EstimatorError, FeeEstimator, FeeRateEstimate};
+
+use crate::cost_estimates::tests::common::make_block_receipt;
+use crate::cost_estimates::tests::fee_scalar::{
+    instantiate_test_db, make_dummy_cc_tx, make_dummy_coinbase_tx, TestCostMetric,
+};
+
+/// A quiet chain makes the scalar estimator report 1 in each bucket, which the floor raises.
+#[test]
+fn test_floor_clamps_scalar_estimates() {
+    let mut estimator = FeeRateFloor::new(
+        instantiate_test_db(TestCostMetric),
+        FeeRateEstimate {
+            high: 10f64,
+            middle: 5f64,
+            low: 2f64,
+        },
+    );
+
+    assert_eq!(
+        estimator
+            .get_rate_estimates()
+            .expect_err("Empty rate estimator should error."),
+        EstimatorError::NoEstimateAvailable,
+        "The floor should not make up an estimate"
+    );
+
+    let block_limit = ExecutionCost::max_value();
+    let single_tx_receipt = make_block_receipt(vec![
+        StacksTransactionReceipt::from_coinbase(make_dummy_coinbase_tx()),
+        make_dummy_cc_tx(1),
+    ]);
+    estimator
+        .notify_block(&single_tx_receipt, &block_limit)
+        .expect("Should be able to process block receipt");
+
+    assert_eq!(
+        estimator
+            .get_rate_estimates()
+            .expect("Should be able to create estimate now"),
+        FeeRateEstimate {
+            high: 10f64,
+            middle: 5f64,
+            low: 2f64
+        }
+    );
+}
+
+/// Estimates above the floor are passed through unchanged, bucket by bucket.
+#[test]
+fn test_floor_passes_higher_estimates() {
+    let mut estimator = FeeRateFloor::new(
+        instantiate_test_db(TestCostMetric),
+        FeeRateEstimate {
+            high: 1f64,
+            middle: 1f64,
+            low: 1f64,
+        },
+    );
+
+    let block_limit = ExecutionCost::max_value();
+    let single_tx_receipt = make_block_receipt(vec![
+        StacksTransactionReceipt::from_coinbase(make_dummy_coinbase_tx()),
+        make_dummy_cc_tx(100),
+    ]);
+    estimator
+        .notify_block(&single_tx_receipt, &block_limit)
+        .expect("Should be able to process block receipt");
+
+    assert_eq!(
+        estimator
+            .get_rate_estimates()
+            .expect("Should be able to create estimate now"),
+        FeeRateEstimate {
+            high: 100f64,
+            middle: 100f64,
+            low: 100f64
+        }
+    );
+
+    // only the buckets whose estimate is below their floor are raised
+    let estimator = FeeRateFloor::new(
+        estimator,
+        FeeRateEstimate {
+            high: 200f64,
+            middle: 1f64,
+            low: 1f64,
+        },
+    );
+    assert_eq!(
+        estimator
+            .get_rate_estimates()
+            .expect("Should be able to create estimate now"),
+        FeeRateEstimate {
+            high: 200f64,
+            middle: 100f64,
+            low: 100f64
+        }
+    );
+}
+
+/// Each percentile is held to the floor of the bucket it falls in.
+#[test]
+fn test_floor_clamps_percentile_estimates() {
+    let mut estimator = FeeRateFloor::new(
+        instantiate_test_db(TestCostMetric),
+        FeeRateEstimate {
+            high: 10f64,
+            middle: 5f64,
+            low: 2f64,
+        },
+    );
+
+    let block_limit = ExecutionCost::max_value();
+    let single_tx_receipt = make_block_receipt(vec![
+        StacksTransactionReceipt::from_coinbase(make_dummy_coinbase_tx()),
+        make_dummy_cc_tx(1),
+    ]);
+    estimator
+        .notify_block(&single_tx_receipt, &block_limit)
+        .expect("Should be able to process block receipt");
+
+    let estimates = estimator
+        .get_rate_estimates_at(&[0, 5, 25, 50, 75, 95, 100])
+        .expect("Should be able to create estimate now");
+    assert_eq!(
+        estimates.into_iter().collect::<Vec<_>>(),
+        vec![
+            (0, 2f64),
+            (5, 2f64),
+            (25, 2f64),
+            (50, 5f64),
+            (75, 5f64),
+            (95, 10f64),
+            (100, 10f64)
+        ]
+    );
+}
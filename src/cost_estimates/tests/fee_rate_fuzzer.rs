@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use crate::cost_estimates::{EstimatorError, FeeEstimator};
 use clarity::vm::costs::ExecutionCost;
 
@@ -32,12 +34,16 @@ impl FeeEstimator for ConstantFeeEstimator {
         Ok(())
     }
 
-    fn get_rate_estimates(&self) -> Result<FeeRateEstimate, EstimatorError> {
-        Ok(FeeRateEstimate {
+    fn get_rate_estimates_at(
+        &self,
+        percentiles: &[u8],
+    ) -> Result<BTreeMap<u8, f64>, EstimatorError> {
+        FeeRateEstimate {
             high: 95f64,
             middle: 50f64,
             low: 5f64,
-        })
+        }
+        .at_percentiles(FeeRateEstimate::PERCENTILES, percentiles)
     }
 }
 
@@ -89,6 +95,31 @@ fn test_fuzzing_seed2() {
     );
 }
 
+/// The per-percentile estimates are all fuzzed by the same factor as `get_rate_estimates`.
+#[test]
+fn test_fuzzing_percentiles() {
+    let mock_estimator = ConstantFeeEstimator {};
+    let rng_creator = Box::new(|| {
+        let seed = [0u8; 32];
+        let rng: StdRng = SeedableRng::from_seed(seed);
+        let r: Box<dyn RngCore> = Box::new(rng);
+        r
+    });
+    let fuzzed_estimator = FeeRateFuzzer::new_custom_creator(mock_estimator, rng_creator, 0.1);
+
+    let estimates = fuzzed_estimator
+        .get_rate_estimates_at(&[5, 50, 95])
+        .expect("Estimate should exist.");
+    assert_eq!(estimates[&5], 5.0634451882632465f64);
+    assert_eq!(estimates[&50], 50.63445188263247f64);
+    assert_eq!(estimates[&95], 96.20545857700169f64);
+
+    assert_eq!(
+        fuzzed_estimator.get_rate_estimates_at(&[25]),
+        Err(EstimatorError::NoEstimateAvailable)
+    );
+}
+
 struct CountingFeeEstimator {
     counter: u64,
 }
@@ -105,12 +136,16 @@ impl FeeEstimator for CountingFeeEstimator {
         Ok(())
     }
 
-    fn get_rate_estimates(&self) -> Result<FeeRateEstimate, EstimatorError> {
-        Ok(FeeRateEstimate {
+    fn get_rate_estimates_at(
+        &self,
+        percentiles: &[u8],
+    ) -> Result<BTreeMap<u8, f64>, EstimatorError> {
+        FeeRateEstimate {
             high: self.counter as f64,
             middle: self.counter as f64,
             low: self.counter as f64,
-        })
+        }
+        .at_percentiles(FeeRateEstimate::PERCENTILES, percentiles)
     }
 }
 
@@ -19,29 +19,34 @@ use stacks_common::util::vrf::VRFProof;
 
 use crate::chainstate::stacks::{
     CoinbasePayload, StacksTransaction, TokenTransferMemo, TransactionAuth,
-    TransactionContractCall, TransactionPayload, TransactionSpendingCondition, TransactionVersion,
+    TransactionContractCall, TransactionPayload, TransactionSmartContract,
+    TransactionSpendingCondition, TransactionVersion,
 };
 use crate::core::StacksEpochId;
-use crate::cost_estimates::fee_scalar::ScalarFeeRateEstimator;
+use crate::cost_estimates::fee_scalar::{
+    filter_outlier_fee_rates, get_schema_version, run_migrations, ScalarFeeRateEstimator,
+    SCALAR_FEE_ESTIMATOR_SCHEMA_VERSION,
+};
 use crate::cost_estimates::FeeRateEstimate;
 use crate::types::chainstate::StacksAddress;
+use crate::util_lib::strings::StacksString;
 use crate::vm::types::{PrincipalData, StandardPrincipalData};
 use crate::vm::Value;
 
 use crate::cost_estimates::tests::common::make_block_receipt;
 
-fn instantiate_test_db<CM: CostMetric>(m: CM) -> ScalarFeeRateEstimator<CM> {
+pub fn instantiate_test_db<CM: CostMetric>(m: CM) -> ScalarFeeRateEstimator<CM> {
     let mut path = env::temp_dir();
     let random_bytes = rand::thread_rng().gen::<[u8; 32]>();
     path.push(&format!("fee_db_{}.sqlite", &to_hex(&random_bytes)[0..8]));
 
-    ScalarFeeRateEstimator::open(&path, m).expect("Test failure: could not open fee rate DB")
+    ScalarFeeRateEstimator::open(&path, m, None).expect("Test failure: could not open fee rate DB")
 }
 
 /// This struct implements a simple metric used for unit testing the
 /// the fee rate estimator. It always returns a cost of 1, making the
 /// fee rate of a transaction always equal to the paid fee.
-struct TestCostMetric;
+pub struct TestCostMetric;
 
 impl CostMetric for TestCostMetric {
     fn from_cost_and_len(
@@ -62,6 +67,42 @@ impl CostMetric for TestCostMetric {
     }
 }
 
+/// This metric weights contract deploys ten times as heavily as any other transaction, so the
+/// fee rate of a deploy is a tenth of its fee, and the fee rate of anything else is its fee.
+struct PayloadCostMetric;
+
+impl CostMetric for PayloadCostMetric {
+    fn from_cost_and_len(
+        &self,
+        _cost: &ExecutionCost,
+        _block_limit: &ExecutionCost,
+        _tx_len: u64,
+    ) -> u64 {
+        1
+    }
+
+    fn from_len(&self, _tx_len: u64) -> u64 {
+        1
+    }
+
+    fn change_per_byte(&self) -> f64 {
+        0f64
+    }
+
+    fn from_payload_cost_and_len(
+        &self,
+        payload: &TransactionPayload,
+        _cost: &ExecutionCost,
+        _block_limit: &ExecutionCost,
+        _tx_len: u64,
+    ) -> u64 {
+        match payload {
+            TransactionPayload::SmartContract(..) => 10,
+            _ => 1,
+        }
+    }
+}
+
 #[test]
 fn test_empty_fee_estimator() {
     let metric = TestCostMetric;
@@ -74,7 +115,7 @@ fn test_empty_fee_estimator() {
     );
 }
 
-fn make_dummy_coinbase_tx() -> StacksTransaction {
+pub fn make_dummy_coinbase_tx() -> StacksTransaction {
     StacksTransaction::new(
         TransactionVersion::Mainnet,
         TransactionAuth::Standard(TransactionSpendingCondition::new_initial_sighash()),
@@ -102,7 +143,7 @@ fn make_dummy_transfer_tx(fee: u64) -> StacksTransactionReceipt {
     )
 }
 
-fn make_dummy_cc_tx(fee: u64) -> StacksTransactionReceipt {
+pub fn make_dummy_cc_tx(fee: u64) -> StacksTransactionReceipt {
     let mut tx = StacksTransaction::new(
         TransactionVersion::Mainnet,
         TransactionAuth::Standard(TransactionSpendingCondition::new_initial_sighash()),
@@ -123,6 +164,91 @@ fn make_dummy_cc_tx(fee: u64) -> StacksTransactionReceipt {
     )
 }
 
+fn make_dummy_deploy_tx(fee: u64) -> StacksTransactionReceipt {
+    let mut tx = StacksTransaction::new(
+        TransactionVersion::Mainnet,
+        TransactionAuth::Standard(TransactionSpendingCondition::new_initial_sighash()),
+        TransactionPayload::SmartContract(
+            TransactionSmartContract {
+                name: "deploy-dummy".into(),
+                code_body: StacksString::from_str("(+ 1 1)").unwrap(),
+            },
+            None,
+        ),
+    );
+    tx.set_tx_fee(fee);
+    // the estimator only looks at the receipt's transaction and execution cost
+    StacksTransactionReceipt::from_contract_call(
+        tx,
+        vec![],
+        Value::okay(Value::Bool(true)).unwrap(),
+        0,
+        ExecutionCost::zero(),
+    )
+}
+
+/// The estimator passes each transaction's payload to the metric.
+#[test]
+fn test_fee_estimator_payload_aware_metric() {
+    let block_limit = ExecutionCost::max_value();
+    let deploy_receipt = make_block_receipt(vec![
+        StacksTransactionReceipt::from_coinbase(make_dummy_coinbase_tx()),
+        make_dummy_deploy_tx(100),
+    ]);
+    let cc_receipt = make_block_receipt(vec![
+        StacksTransactionReceipt::from_coinbase(make_dummy_coinbase_tx()),
+        make_dummy_cc_tx(100),
+    ]);
+
+    // the payload-aware metric discounts the deploy's fee rate...
+    let mut estimator = instantiate_test_db(PayloadCostMetric);
+    estimator
+        .notify_block(&deploy_receipt, &block_limit)
+        .expect("Should be able to process block receipt");
+    assert_eq!(
+        estimator
+            .get_rate_estimates()
+            .expect("Should be able to create estimate now"),
+        FeeRateEstimate {
+            high: 10f64,
+            middle: 10f64,
+            low: 10f64
+        }
+    );
+
+    // ...but not the fee rate of a contract call with the same fee and cost
+    let mut estimator = instantiate_test_db(PayloadCostMetric);
+    estimator
+        .notify_block(&cc_receipt, &block_limit)
+        .expect("Should be able to process block receipt");
+    assert_eq!(
+        estimator
+            .get_rate_estimates()
+            .expect("Should be able to create estimate now"),
+        FeeRateEstimate {
+            high: 100f64,
+            middle: 100f64,
+            low: 100f64
+        }
+    );
+
+    // metrics which don't look at the payload treat both the same
+    let mut estimator = instantiate_test_db(TestCostMetric);
+    estimator
+        .notify_block(&deploy_receipt, &block_limit)
+        .expect("Should be able to process block receipt");
+    assert_eq!(
+        estimator
+            .get_rate_estimates()
+            .expect("Should be able to create estimate now"),
+        FeeRateEstimate {
+            high: 100f64,
+            middle: 100f64,
+            low: 100f64
+        }
+    );
+}
+
 #[test]
 fn test_fee_estimator() {
     let metric = TestCostMetric;
@@ -258,7 +384,7 @@ fn test_fee_estimator() {
     );
 
     // estimate should increase for "high" and "middle":
-    // new value: 10 * 1/2 + 9.4375 * 1/2 = 9
+    // new value: 10 * 1/2 + 9.4375 * 1/2 = 9.71875
     estimator
         .notify_block(&double_tx_receipt, &block_limit)
         .expect("Should be able to process block receipt");
@@ -298,3 +424,487 @@ fn test_fee_estimator() {
         }
     );
 }
+
+#[test]
+fn test_fee_estimator_at_percentiles() {
+    let metric = TestCostMetric;
+    let mut estimator = instantiate_test_db(metric);
+    let block_limit = ExecutionCost::max_value();
+
+    assert_eq!(
+        estimator
+            .get_rate_estimates_at(&[10, 50, 90])
+            .expect_err("Empty rate estimator should error."),
+        EstimatorError::NoEstimateAvailable
+    );
+
+    // measured fee rate at percentile `p` is 10 * p
+    let mut receipts: Vec<_> = (0..100).map(|i| make_dummy_cc_tx(i * 10)).collect();
+    let mut rng = rand::thread_rng();
+    receipts.shuffle(&mut rng);
+    let block_receipt = make_block_receipt(receipts);
+
+    estimator
+        .notify_block(&block_receipt, &block_limit)
+        .expect("Should be able to process block receipt");
+
+    let estimates = estimator
+        .get_rate_estimates_at(&[10, 25, 50, 75, 90, 200])
+        .expect("Should be able to create estimate now");
+    assert_eq!(
+        estimates.into_iter().collect::<Vec<_>>(),
+        vec![
+            (10, 100f64),
+            (25, 250f64),
+            (50, 500f64),
+            (75, 750f64),
+            (90, 900f64),
+            (200, 990f64)
+        ]
+    );
+
+    // a block with a single transaction paying 100 moves every percentile halfway to 100
+    estimator
+        .notify_block(
+            &make_block_receipt(vec![make_dummy_cc_tx(100)]),
+            &block_limit,
+        )
+        .expect("Should be able to process block receipt");
+
+    let estimates = estimator
+        .get_rate_estimates_at(&[10, 90])
+        .expect("Should be able to create estimate now");
+    assert_eq!(
+        estimates.into_iter().collect::<Vec<_>>(),
+        vec![(10, 100f64), (90, 500f64)]
+    );
+}
+
+/// The estimate should converge to the true fee rate, rather than getting
+/// stuck on a truncated value.
+#[test]
+fn test_fee_estimator_converges() {
+    let metric = TestCostMetric;
+    let mut estimator = instantiate_test_db(metric);
+    let block_limit = ExecutionCost::max_value();
+
+    estimator
+        .notify_block(&make_block_receipt(vec![make_dummy_cc_tx(1)]), &block_limit)
+        .expect("Should be able to process block receipt");
+
+    let block_receipt = make_block_receipt(vec![make_dummy_cc_tx(10)]);
+    for _i in 0..64 {
+        estimator
+            .notify_block(&block_receipt, &block_limit)
+            .expect("Should be able to process block receipt");
+    }
+
+    assert_eq!(
+        estimator
+            .get_rate_estimates()
+            .expect("Should be able to create estimate now"),
+        FeeRateEstimate {
+            high: 10f64,
+            middle: 10f64,
+            low: 10f64
+        }
+    );
+}
+
+/// Estimates persisted as integers should still load, and subsequent
+/// updates should keep fractional precision.
+#[test]
+fn test_fee_estimator_loads_integer_rows() {
+    let mut path = env::temp_dir();
+    let random_bytes = rand::thread_rng().gen::<[u8; 32]>();
+    path.push(&format!("fee_db_{}.sqlite", &to_hex(&random_bytes)[0..8]));
+
+    let estimator = ScalarFeeRateEstimator::open(&path, TestCostMetric, None)
+        .expect("Test failure: could not open fee rate DB");
+    drop(estimator);
+
+    let conn = rusqlite::Connection::open(&path).expect("Test failure: could not open DB");
+    conn.execute(
+        "INSERT INTO scalar_fee_estimator (estimate_key, high, middle, low) VALUES (1, 11, 5, 1)",
+        rusqlite::NO_PARAMS,
+    )
+    .expect("Test failure: could not insert integer estimate");
+    drop(conn);
+
+    let mut estimator = ScalarFeeRateEstimator::open(&path, TestCostMetric, None)
+        .expect("Test failure: could not re-open fee rate DB");
+    assert_eq!(
+        estimator
+            .get_rate_estimates()
+            .expect("Should be able to load integer estimate"),
+        FeeRateEstimate {
+            high: 11f64,
+            middle: 5f64,
+            low: 1f64
+        }
+    );
+
+    estimator
+        .notify_block(
+            &make_block_receipt(vec![make_dummy_cc_tx(10)]),
+            &ExecutionCost::max_value(),
+        )
+        .expect("Should be able to process block receipt");
+    assert_eq!(
+        estimator
+            .get_rate_estimates()
+            .expect("Should be able to create estimate now"),
+        FeeRateEstimate {
+            high: 10.5f64,
+            middle: 7.5f64,
+            low: 5.5f64
+        }
+    );
+}
+
+/// A db written before the schema was versioned (and before per-percentile estimates
+/// were tracked) is migrated on open, keeping its stored estimate.
+#[test]
+fn test_fee_estimator_migrates_unversioned_db() {
+    let mut path = env::temp_dir();
+    let random_bytes = rand::thread_rng().gen::<[u8; 32]>();
+    path.push(&format!("fee_db_{}.sqlite", &to_hex(&random_bytes)[0..8]));
+
+    let conn = rusqlite::Connection::open(&path).expect("Test failure: could not open DB");
+    conn.execute_batch(
+        "CREATE TABLE scalar_fee_estimator (
+            estimate_key NUMBER PRIMARY KEY,
+            high NUMBER NOT NULL,
+            middle NUMBER NOT NULL,
+            low NUMBER NOT NULL
+        );
+        INSERT INTO scalar_fee_estimator (estimate_key, high, middle, low) VALUES (1, 11, 5, 1);",
+    )
+    .expect("Test failure: could not create unversioned DB");
+    assert_eq!(get_schema_version(&conn).unwrap(), 0);
+    drop(conn);
+
+    let estimator = ScalarFeeRateEstimator::open(&path, TestCostMetric, None)
+        .expect("Test failure: could not open fee rate DB");
+    assert_eq!(
+        estimator
+            .get_rate_estimates()
+            .expect("Should keep the stored estimate"),
+        FeeRateEstimate {
+            high: 11f64,
+            middle: 5f64,
+            low: 1f64
+        }
+    );
+    assert_eq!(
+        estimator
+            .get_rate_estimates_at(&[50])
+            .expect("The middle estimate is stored")
+            .into_iter()
+            .collect::<Vec<_>>(),
+        vec![(50, 5f64)]
+    );
+    assert_eq!(
+        estimator
+            .get_rate_estimates_at(&[25])
+            .expect_err("No percentile estimates stored yet"),
+        EstimatorError::NoEstimateAvailable
+    );
+    drop(estimator);
+
+    let conn = rusqlite::Connection::open(&path).expect("Test failure: could not open DB");
+    assert_eq!(
+        get_schema_version(&conn).unwrap(),
+        SCALAR_FEE_ESTIMATOR_SCHEMA_VERSION
+    );
+}
+
+/// Running the migrations on an up-to-date db changes nothing.
+#[test]
+fn test_fee_estimator_migrations_idempotent() {
+    let conn = rusqlite::Connection::open_in_memory().expect("Test failure: could not open DB");
+    run_migrations(&conn).expect("Should migrate a new DB");
+    assert_eq!(
+        get_schema_version(&conn).unwrap(),
+        SCALAR_FEE_ESTIMATOR_SCHEMA_VERSION
+    );
+    conn.execute(
+        "INSERT INTO scalar_fee_estimator (estimate_key, high, middle, low) VALUES (1, 3, 2, 1)",
+        rusqlite::NO_PARAMS,
+    )
+    .expect("Test failure: could not insert estimate");
+
+    run_migrations(&conn).expect("Should re-run migrations");
+    assert_eq!(
+        get_schema_version(&conn).unwrap(),
+        SCALAR_FEE_ESTIMATOR_SCHEMA_VERSION
+    );
+    let versions: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM scalar_fee_estimator_schema_version",
+            rusqlite::NO_PARAMS,
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(versions, SCALAR_FEE_ESTIMATOR_SCHEMA_VERSION);
+    let high: f64 = conn
+        .query_row(
+            "SELECT high FROM scalar_fee_estimator WHERE estimate_key = 1",
+            rusqlite::NO_PARAMS,
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(high, 3f64);
+}
+
+/// The smoothed estimates are persisted, so re-opening the estimator (e.g. on node restart)
+/// serves the prior estimates immediately, and new blocks are folded into them.
+#[test]
+fn test_fee_estimator_survives_restart() {
+    let mut path = env::temp_dir();
+    let random_bytes = rand::thread_rng().gen::<[u8; 32]>();
+    path.push(&format!("fee_db_{}.sqlite", &to_hex(&random_bytes)[0..8]));
+
+    // never restarted, for comparison
+    let mut control = instantiate_test_db(TestCostMetric);
+
+    let mut estimator = ScalarFeeRateEstimator::open(&path, TestCostMetric, None)
+        .expect("Test failure: could not open fee rate DB");
+    for fee in [1, 5, 20, 3, 8] {
+        let receipt = make_block_receipt(vec![
+            make_dummy_cc_tx(fee),
+            make_dummy_cc_tx(2 * fee),
+            make_dummy_transfer_tx(fee),
+        ]);
+        estimator
+            .notify_block(&receipt, &ExecutionCost::max_value())
+            .expect("Should be able to process block receipt");
+        control
+            .notify_block(&receipt, &ExecutionCost::max_value())
+            .expect("Should be able to process block receipt");
+    }
+    let estimate = estimator
+        .get_rate_estimates()
+        .expect("Should be able to create estimate now");
+    let percentiles = estimator
+        .get_rate_estimates_at(&[0, 10, 50, 90, 100])
+        .expect("Should be able to create estimate now");
+    drop(estimator);
+
+    let mut estimator = ScalarFeeRateEstimator::open(&path, TestCostMetric, None)
+        .expect("Test failure: could not re-open fee rate DB");
+    assert_eq!(
+        estimator
+            .get_rate_estimates()
+            .expect("Estimate should survive re-opening"),
+        estimate
+    );
+    assert_eq!(
+        estimator
+            .get_rate_estimates_at(&[0, 10, 50, 90, 100])
+            .expect("Estimate should survive re-opening"),
+        percentiles
+    );
+
+    let receipt = make_block_receipt(vec![make_dummy_cc_tx(50)]);
+    estimator
+        .notify_block(&receipt, &ExecutionCost::max_value())
+        .expect("Should be able to process block receipt");
+    control
+        .notify_block(&receipt, &ExecutionCost::max_value())
+        .expect("Should be able to process block receipt");
+    assert_eq!(
+        estimator.get_rate_estimates().unwrap(),
+        control.get_rate_estimates().unwrap()
+    );
+}
+
+/// Notifying a batch of blocks should produce the same estimates as notifying
+/// each block in turn.
+#[test]
+fn test_notify_block_batch_matches_sequential() {
+    let block_limit = ExecutionCost::max_value();
+    let mut rng = rand::thread_rng();
+    let block_receipts: Vec<_> = (1..20)
+        .map(|i| {
+            let mut receipts: Vec<_> = (0..i).map(|j| make_dummy_cc_tx(i * 7 + j * 3)).collect();
+            receipts.push(make_dummy_transfer_tx(i * 11));
+            receipts.shuffle(&mut rng);
+            make_block_receipt(receipts)
+        })
+        .collect();
+
+    let mut sequential = instantiate_test_db(TestCostMetric);
+    for block_receipt in block_receipts.iter() {
+        sequential
+            .notify_block(block_receipt, &block_limit)
+            .expect("Should be able to process block receipt");
+    }
+
+    let mut batched = instantiate_test_db(TestCostMetric);
+    batched
+        .notify_block_batch(&block_receipts, &block_limit)
+        .expect("Should be able to process block receipts");
+
+    assert_eq!(
+        batched
+            .get_rate_estimates()
+            .expect("Should be able to create estimate now"),
+        sequential
+            .get_rate_estimates()
+            .expect("Should be able to create estimate now"),
+    );
+    let percentiles: Vec<u8> = (0..=100).collect();
+    assert_eq!(
+        batched
+            .get_rate_estimates_at(&percentiles)
+            .expect("Should be able to create estimate now"),
+        sequential
+            .get_rate_estimates_at(&percentiles)
+            .expect("Should be able to create estimate now"),
+    );
+
+    // an empty batch leaves the estimates untouched
+    batched
+        .notify_block_batch(&[], &block_limit)
+        .expect("Should be able to process empty batch");
+    assert_eq!(
+        batched.get_rate_estimates(),
+        sequential.get_rate_estimates()
+    );
+}
+
+/// A single 10x outlier should spike the high estimate, unless the estimator filters
+/// outliers by median absolute deviation.
+#[test]
+fn test_fee_estimator_outlier_filter() {
+    let block_limit = ExecutionCost::max_value();
+    let make_block = |with_outlier: bool| {
+        let mut receipts: Vec<_> = (10..20).map(|fee| make_dummy_cc_tx(fee)).collect();
+        if with_outlier {
+            receipts.push(make_dummy_cc_tx(150));
+        }
+        make_block_receipt(receipts)
+    };
+
+    let mut path = env::temp_dir();
+    let random_bytes = rand::thread_rng().gen::<[u8; 32]>();
+    path.push(&format!("fee_db_{}.sqlite", &to_hex(&random_bytes)[0..8]));
+    let mut filtered = ScalarFeeRateEstimator::open(&path, TestCostMetric, Some(3f64))
+        .expect("Test failure: could not open fee rate DB");
+    let mut unfiltered = instantiate_test_db(TestCostMetric);
+
+    for estimator in [&mut filtered, &mut unfiltered].iter_mut() {
+        estimator
+            .notify_block(&make_block(false), &block_limit)
+            .expect("Should be able to process block receipt");
+        assert_eq!(
+            estimator
+                .get_rate_estimates()
+                .expect("Should be able to create estimate now"),
+            FeeRateEstimate {
+                high: 19f64,
+                middle: 15f64,
+                low: 10f64
+            }
+        );
+        estimator
+            .notify_block(&make_block(true), &block_limit)
+            .expect("Should be able to process block receipt");
+    }
+
+    // the outlier was dropped, so the estimate is unchanged
+    assert_eq!(
+        filtered
+            .get_rate_estimates()
+            .expect("Should be able to create estimate now"),
+        FeeRateEstimate {
+            high: 19f64,
+            middle: 15f64,
+            low: 10f64
+        }
+    );
+    // the outlier is the new block's high measure
+    assert_eq!(
+        unfiltered
+            .get_rate_estimates()
+            .expect("Should be able to create estimate now"),
+        FeeRateEstimate {
+            high: 84.5f64,
+            middle: 15f64,
+            low: 10f64
+        }
+    );
+}
+
+/// A decay rate near 0 makes the estimate follow the latest block, and one near 1 makes it
+/// barely move.
+#[test]
+fn test_fee_estimator_decay_rate() {
+    let block_limit = ExecutionCost::max_value();
+    let is_close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+
+    for (decay_rate, expected) in [(0.01f64, 99.1f64), (0.99f64, 10.9f64)].iter() {
+        let mut path = env::temp_dir();
+        let random_bytes = rand::thread_rng().gen::<[u8; 32]>();
+        path.push(&format!("fee_db_{}.sqlite", &to_hex(&random_bytes)[0..8]));
+        let mut estimator =
+            ScalarFeeRateEstimator::open_with_decay(&path, TestCostMetric, None, *decay_rate)
+                .expect("Test failure: could not open fee rate DB");
+
+        estimator
+            .notify_block(
+                &make_block_receipt(vec![make_dummy_cc_tx(10)]),
+                &block_limit,
+            )
+            .expect("Should be able to process block receipt");
+        estimator
+            .notify_block(
+                &make_block_receipt(vec![make_dummy_cc_tx(100)]),
+                &block_limit,
+            )
+            .expect("Should be able to process block receipt");
+
+        let estimate = estimator
+            .get_rate_estimates()
+            .expect("Should be able to create estimate now");
+        assert!(
+            is_close(estimate.high, *expected)
+                && is_close(estimate.middle, *expected)
+                && is_close(estimate.low, *expected),
+            "Decay rate {} gave {:?}, expected {}",
+            decay_rate,
+            estimate,
+            expected
+        );
+
+        let percentile_estimates = estimator
+            .get_rate_estimates_at(&[50])
+            .expect("Should be able to create estimate now");
+        assert!(is_close(percentile_estimates[&50], *expected));
+    }
+}
+
+#[test]
+fn test_filter_outlier_fee_rates() {
+    assert_eq!(filter_outlier_fee_rates(vec![], 3f64), Vec::<f64>::new());
+    assert_eq!(filter_outlier_fee_rates(vec![5f64], 3f64), vec![5f64]);
+    assert_eq!(
+        filter_outlier_fee_rates(vec![1f64, 2f64, 3f64, 4f64, 5f64], 3f64),
+        vec![1f64, 2f64, 3f64, 4f64, 5f64]
+    );
+    // with a MAD of zero, nothing is dropped
+    assert_eq!(
+        filter_outlier_fee_rates(vec![1f64, 1f64, 1f64, 100f64], 3f64),
+        vec![1f64, 1f64, 1f64, 100f64]
+    );
+    assert_eq!(
+        filter_outlier_fee_rates(vec![1f64, 1f64, 1f64, 2f64, 3f64], 3f64),
+        vec![1f64, 1f64, 1f64, 2f64, 3f64]
+    );
+    // low outliers are kept
+    assert_eq!(
+        filter_outlier_fee_rates(vec![1f64, 50f64, 51f64, 52f64, 500f64], 3f64),
+        vec![1f64, 50f64, 51f64, 52f64]
+    );
+}
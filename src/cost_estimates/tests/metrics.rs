@@ -1,6 +1,6 @@
 use crate::chainstate::stacks::MAX_BLOCK_LEN;
 use crate::core::BLOCK_LIMIT_MAINNET_20;
-use crate::cost_estimates::metrics::{CostMetric, ProportionalDotProduct};
+use crate::cost_estimates::metrics::{CostMetric, ProportionalDotProduct, WeightedCostMetric};
 use clarity::vm::costs::ExecutionCost;
 
 #[test]
@@ -167,3 +167,76 @@ fn test_proportional_dot_product_with_mainnet_lims() {
         60_000
     );
 }
+
+#[test]
+// Test that a metric which only weights tx_len ignores the execution cost,
+//  so that fee rates are computed per byte.
+fn test_weighted_cost_metric_tx_len_only() {
+    let metric = WeightedCostMetric::new(10_000, (0.0, 0.0, 0.0, 0.0, 0.0, 1.0));
+    let byte_metric = ProportionalDotProduct::new(10_000);
+    let block_limit = ExecutionCost {
+        write_length: 5_000,
+        write_count: 6_000,
+        read_length: 7_000,
+        read_count: 8_000,
+        runtime: 9_000,
+    };
+
+    for tx_len in [1, 100, 2_500, 10_000, 20_000].iter() {
+        let expected = byte_metric.from_len(*tx_len);
+        assert_eq!(metric.from_len(*tx_len), expected);
+        assert_eq!(
+            metric.from_cost_and_len(&ExecutionCost::zero(), &block_limit, *tx_len),
+            expected
+        );
+        assert_eq!(
+            metric.from_cost_and_len(&block_limit, &block_limit, *tx_len),
+            expected
+        );
+    }
+
+    // doubling the length doubles the metric, so the fee rate is a simple fee-per-byte.
+    assert_eq!(
+        metric.from_cost_and_len(&block_limit, &block_limit, 200),
+        2 * metric.from_cost_and_len(&block_limit, &block_limit, 100)
+    );
+    assert_eq!(metric.change_per_byte(), byte_metric.change_per_byte());
+}
+
+#[test]
+fn test_weighted_cost_metric() {
+    let block_limit = ExecutionCost {
+        write_length: 5_000,
+        write_count: 6_000,
+        read_length: 7_000,
+        read_count: 8_000,
+        runtime: 9_000,
+    };
+
+    // with unit weights, a maxed block is the same as the proportional dot product
+    let metric = WeightedCostMetric::new(10_000, (1.0, 1.0, 1.0, 1.0, 1.0, 1.0));
+    assert_eq!(
+        metric.from_cost_and_len(&block_limit, &block_limit, 10_000),
+        ProportionalDotProduct::new(10_000).from_cost_and_len(&block_limit, &block_limit, 10_000)
+    );
+
+    // only runtime counts, with a weight of 2.5
+    let metric = WeightedCostMetric::new(10_000, (2.5, 0.0, 0.0, 0.0, 0.0, 0.0));
+    assert_eq!(
+        metric.from_cost_and_len(&ExecutionCost::runtime(4_500), &block_limit, 10_000),
+        12_500
+    );
+    assert_eq!(metric.change_per_byte(), 0f64);
+
+    // the metric is never zero
+    assert_eq!(
+        metric.from_cost_and_len(&ExecutionCost::zero(), &block_limit, 0),
+        1
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_weighted_cost_metric_all_zero_weights() {
+    WeightedCostMetric::new(10_000, (0.0, 0.0, 0.0, 0.0, 0.0, 0.0));
+}
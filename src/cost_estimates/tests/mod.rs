@@ -1,8 +1,14 @@
-use crate::cost_estimates::FeeRateEstimate;
+use crate::cost_estimates::tests::common::make_block_receipt;
+use crate::cost_estimates::{DisabledFeeEstimator, EstimatorError, FeeEstimator, FeeRateEstimate};
+use clarity::vm::costs::ExecutionCost;
 
 pub mod common;
+pub mod contract_costs;
 pub mod cost_estimators;
+pub mod fee_http;
 pub mod fee_medians;
+pub mod fee_percentiles;
+pub mod fee_rate_floor;
 pub mod fee_rate_fuzzer;
 pub mod fee_scalar;
 pub mod metrics;
@@ -26,3 +32,31 @@ fn fee_rate_estimate_math_units() {
         "Fee rate estimate math should saturate"
     );
 }
+
+#[test]
+fn disabled_fee_estimator_never_estimates() {
+    // the estimator holds no state, so there is no database for it to touch
+    assert_eq!(std::mem::size_of::<DisabledFeeEstimator>(), 0);
+
+    let mut estimator = DisabledFeeEstimator;
+    let block_limit = ExecutionCost::max_value();
+    let receipts: Vec<_> = (0..10).map(|_| make_block_receipt(vec![])).collect();
+
+    for receipt in receipts.iter() {
+        estimator
+            .notify_block(receipt, &block_limit)
+            .expect("Disabled estimator should accept every block");
+    }
+    estimator
+        .notify_block_batch(&receipts, &block_limit)
+        .expect("Disabled estimator should accept every batch");
+
+    assert_eq!(
+        estimator.get_rate_estimates(),
+        Err(EstimatorError::NoEstimateAvailable)
+    );
+    assert_eq!(
+        estimator.get_rate_estimates_at(&[50]),
+        Err(EstimatorError::NoEstimateAvailable)
+    );
+}
@@ -19,6 +19,7 @@ use std::{fmt, fs, path::PathBuf};
 use rusqlite::{OpenFlags, OptionalExtension};
 
 use crate::burnchains::BurnchainSigner;
+use crate::chainstate::stacks::{StacksBlock, StacksTransaction};
 use crate::util_lib::db::sqlite_open;
 use crate::util_lib::db::Error as DatabaseError;
 use crate::{
@@ -28,12 +29,15 @@ use crate::{
     util::get_epoch_time_secs,
     util_lib::db::{tx_busy_handler, DBConn},
 };
+#[cfg(feature = "monitoring_prom")]
+use crate::{chainstate::stacks::TransactionPayload, codec::StacksMessageCodec};
 use clarity::vm::costs::ExecutionCost;
 use stacks_common::util::uint::{Uint256, Uint512};
 use std::convert::TryInto;
 use std::error::Error;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Mutex;
+use std::time::Duration;
 
 #[cfg(feature = "monitoring_prom")]
 mod prometheus;
@@ -58,6 +62,11 @@ where
     #[cfg(feature = "monitoring_prom")]
     increment_rpc_calls_counter();
 
+    #[cfg(feature = "monitoring_prom")]
+    prometheus::RPC_REQUESTS_BY_PATH_COUNTER
+        .with_label_values(&[req.get_path()])
+        .inc();
+
     #[cfg(feature = "monitoring_prom")]
     let timer = prometheus::new_rpc_call_timer(req.get_path());
 
@@ -149,6 +158,16 @@ pub fn increment_stx_blocks_mined_counter() {
     prometheus::STX_BLOCKS_MINED_COUNTER.inc();
 }
 
+pub fn increment_sortitions_won_counter() {
+    #[cfg(feature = "monitoring_prom")]
+    prometheus::SORTITIONS_WON_COUNTER.inc();
+}
+
+pub fn increment_sortitions_lost_counter() {
+    #[cfg(feature = "monitoring_prom")]
+    prometheus::SORTITIONS_LOST_COUNTER.inc();
+}
+
 pub fn increment_warning_emitted_counter() {
     #[cfg(feature = "monitoring_prom")]
     prometheus::WARNING_EMITTED_COUNTER.inc();
@@ -251,6 +270,39 @@ pub fn log_transaction_processed(
     Ok(())
 }
 
+/// The `payload_type` label of a transaction in the transaction size histograms
+#[cfg(feature = "monitoring_prom")]
+fn tx_size_payload_type(tx: &StacksTransaction) -> &'static str {
+    match tx.payload {
+        TransactionPayload::TokenTransfer(..) => "token_transfer",
+        TransactionPayload::BatchTokenTransfer(..) => "batch_token_transfer",
+        TransactionPayload::ContractCall(..) => "contract_call",
+        TransactionPayload::SmartContract(..) => "smart_contract",
+        TransactionPayload::Coinbase(..) => "coinbase",
+        TransactionPayload::PoisonMicroblock(..) => "poison_microblock",
+    }
+}
+
+/// Record the size of a transaction that was just accepted into the mempool.
+#[allow(unused_variables)]
+pub fn observe_mempool_tx_size(tx: &StacksTransaction, tx_size: u64) {
+    #[cfg(feature = "monitoring_prom")]
+    prometheus::MEMPOOL_TX_SIZE_HISTOGRAM
+        .with_label_values(&[tx_size_payload_type(tx)])
+        .observe(tx_size as f64);
+}
+
+/// Record the sizes of the transactions in a block this miner just committed to.
+#[allow(unused_variables)]
+pub fn observe_mined_tx_sizes(block: &StacksBlock) {
+    #[cfg(feature = "monitoring_prom")]
+    for tx in block.txs.iter() {
+        prometheus::MINED_TX_SIZE_HISTOGRAM
+            .with_label_values(&[tx_size_payload_type(tx)])
+            .observe(tx.serialize_to_vec().len() as f64);
+    }
+}
+
 #[allow(unused_variables)]
 pub fn update_active_miners_count_gauge(value: i64) {
     #[cfg(feature = "monitoring_prom")]
@@ -313,6 +365,35 @@ pub fn increment_msg_counter(name: String) {
         .inc();
 }
 
+/// Report the number and total size of the transactions in `mempool`.  The mempool is only
+/// queried if metrics are being collected.
+#[allow(unused_variables)]
+pub fn update_mempool_size(mempool: &MemPoolDB) {
+    #[cfg(feature = "monitoring_prom")]
+    {
+        match (
+            MemPoolDB::get_num_txs(mempool.conn()),
+            MemPoolDB::get_total_bytes(mempool.conn()),
+        ) {
+            (Ok(num_txs), Ok(total_bytes)) => {
+                prometheus::MEMPOOL_TX_COUNT_GAUGE.set(num_txs as i64);
+                prometheus::MEMPOOL_BYTES_GAUGE.set(total_bytes as i64);
+            }
+            (Err(e), _) | (_, Err(e)) => {
+                warn!("Failed to query mempool size: {:?}", &e);
+            }
+        }
+    }
+}
+
+#[allow(unused_variables)]
+pub fn observe_event_observer_delivery_latency(endpoint: &str, latency: Duration) {
+    #[cfg(feature = "monitoring_prom")]
+    prometheus::EVENT_OBSERVER_DELIVERY_LATENCIES_HISTOGRAM
+        .with_label_values(&[endpoint])
+        .observe(latency.as_secs_f64());
+}
+
 pub fn increment_stx_mempool_gc() {
     #[cfg(feature = "monitoring_prom")]
     prometheus::STX_MEMPOOL_GC.inc();
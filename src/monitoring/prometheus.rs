@@ -25,6 +25,12 @@ lazy_static! {
         labels! {"handler" => "all",}
     )).unwrap();
 
+    pub static ref RPC_REQUESTS_BY_PATH_COUNTER: IntCounterVec = register_int_counter_vec!(
+        "stacks_node_rpc_requests_by_path_total",
+        "Total number of RPC requests made, by endpoint",
+        &["path"]
+    ).unwrap();
+
     pub static ref RPC_CALL_LATENCIES_HISTOGRAM: HistogramVec = register_histogram_vec!(histogram_opts!(
         "stacks_node_rpc_call_latencies_histogram",
         "Time (seconds) measuring RPC calls latency"
@@ -81,6 +87,16 @@ lazy_static! {
         "Total number of stacks blocks mined by node"
     )).unwrap();
 
+    pub static ref SORTITIONS_WON_COUNTER: IntCounter = register_int_counter!(opts!(
+        "stacks_node_sortitions_won_total",
+        "Total number of sortitions won by this miner"
+    )).unwrap();
+
+    pub static ref SORTITIONS_LOST_COUNTER: IntCounter = register_int_counter!(opts!(
+        "stacks_node_sortitions_lost_total",
+        "Total number of sortitions processed by this miner that it did not win"
+    )).unwrap();
+
     pub static ref WARNING_EMITTED_COUNTER: IntCounter = register_int_counter!(opts!(
         "stacks_node_warning_emitted_total",
         "Total number of warning logs emitted by node"
@@ -173,6 +189,21 @@ lazy_static! {
     ).unwrap();
 
 
+    pub static ref MEMPOOL_TX_COUNT_GAUGE: IntGauge = register_int_gauge!(opts!(
+        "stacks_node_mempool_txs",
+        "Number of transactions in the mempool"
+    )).unwrap();
+
+    pub static ref MEMPOOL_BYTES_GAUGE: IntGauge = register_int_gauge!(opts!(
+        "stacks_node_mempool_bytes",
+        "Total size (bytes) of the transactions in the mempool"
+    )).unwrap();
+
+    pub static ref EVENT_OBSERVER_DELIVERY_LATENCIES_HISTOGRAM: HistogramVec = register_histogram_vec!(histogram_opts!(
+        "stacks_node_event_observer_delivery_latencies_histogram",
+        "Time (seconds) between a payload being handed to an event observer's queue and the observer acknowledging it"
+    ), &["endpoint"]).unwrap();
+
     pub static ref STX_MEMPOOL_GC: IntCounter = register_int_counter!(opts!(
         "stacks_node_mempool_gc_count",
         "Total count of all mempool garbage collections"
@@ -196,6 +227,18 @@ lazy_static! {
         labels! {"handler".to_string() => "all".to_string(),}
     )).unwrap();
 
+    pub static ref MEMPOOL_TX_SIZE_HISTOGRAM: HistogramVec = register_histogram_vec!(histogram_opts!(
+        "stacks_mempool_tx_size_bytes",
+        "Size (bytes) of transactions accepted into this node's mempool",
+        vec![128.0, 512.0, 1024.0, 4096.0, 16384.0, 65536.0]
+    ), &["payload_type"]).unwrap();
+
+    pub static ref MINED_TX_SIZE_HISTOGRAM: HistogramVec = register_histogram_vec!(histogram_opts!(
+        "stacks_mined_tx_size_bytes",
+        "Size (bytes) of transactions in the blocks this miner committed to",
+        vec![128.0, 512.0, 1024.0, 4096.0, 16384.0, 65536.0]
+    ), &["payload_type"]).unwrap();
+
     pub static ref COMPUTED_RELATIVE_MINER_SCORE: Gauge = register_gauge!(opts!(
         "stacks_node_computed_relative_miner_score",
         "Percentage of the u256 range that this miner is assigned in a particular round of sortition"
@@ -637,6 +637,13 @@ impl ConversationP2P {
         (peer_services & expected_bits) == expected_bits
     }
 
+    /// Does this remote neighbor support streaming a range of blocks from its data URL?  It will
+    /// if it has both RPC and BLOCK_STREAM bits set.
+    pub fn supports_block_stream(peer_services: u16) -> bool {
+        let expected_bits = (ServiceFlags::RPC as u16) | (ServiceFlags::BLOCK_STREAM as u16);
+        (peer_services & expected_bits) == expected_bits
+    }
+
     /// Determine whether or not a given (height, burn_header_hash) pair _disagrees_ with our
     /// burnchain view.  If it does, return true.  If it doesn't (including if the given pair is
     /// simply absent from the chain_view), then return False.
@@ -1169,6 +1176,7 @@ impl ConversationP2P {
              "services" => &to_hex(&handshake_data.services.to_be_bytes()),
              "expires_block_height" => handshake_data.expire_block_height,
              "supports_mempool_query" => Self::supports_mempool_query(handshake_data.services),
+             "supports_block_stream" => Self::supports_block_stream(handshake_data.services),
         );
 
         if updated {
@@ -48,6 +48,7 @@ use crate::net::ProtocolFamily;
 use crate::net::RelayData;
 use crate::net::StacksHttp;
 use crate::net::StacksP2P;
+use crate::net::DENY_BAN_DURATION;
 
 use crate::net::download::BLOCK_DOWNLOAD_INTERVAL;
 use crate::net::inv::{INV_REWARD_CYCLES, INV_SYNC_INTERVAL};
@@ -358,6 +359,8 @@ pub struct ConnectionOptions {
     pub max_attachment_retry_count: u64,
     pub read_only_call_limit: ExecutionCost,
     pub maximum_call_argument_size: u32,
+    /// how long a transaction dry-run may execute before it is aborted, in milliseconds
+    pub dry_run_timeout_ms: u64,
     pub max_block_push_bandwidth: u64,
     pub max_microblocks_push_bandwidth: u64,
     pub max_transaction_push_bandwidth: u64,
@@ -380,6 +383,13 @@ pub struct ConnectionOptions {
     pub mempool_max_tx_query: u64,
     /// how long a mempool sync is allowed to take, in total, before timing out
     pub mempool_sync_timeout: u64,
+    /// how many invalid blocks or microblocks a neighbor may send us within
+    /// `block_strike_window` seconds before we ban it
+    pub block_strike_threshold: u64,
+    /// how far back to count a neighbor's invalid blocks and microblocks, in seconds
+    pub block_strike_window: u64,
+    /// how long to ban a neighbor that exceeds `block_strike_threshold`, in seconds
+    pub block_strike_ban_duration: u64,
 
     // fault injection
     pub disable_neighbor_walk: bool,
@@ -447,6 +457,7 @@ impl std::default::Default for ConnectionOptions {
                 runtime: 1_000_000_000,
             },
             maximum_call_argument_size: 20 * BOUND_VALUE_SERIALIZATION_HEX,
+            dry_run_timeout_ms: 5_000, // how long a transaction dry-run may execute
             max_block_push_bandwidth: 0, // infinite upload bandwidth allowed
             max_microblocks_push_bandwidth: 0, // infinite upload bandwidth allowed
             max_transaction_push_bandwidth: 0, // infinite upload bandwidth allowed
@@ -466,6 +477,9 @@ impl std::default::Default for ConnectionOptions {
             mempool_sync_interval: 30, // number of seconds in-between mempool sync
             mempool_max_tx_query: 128, // maximum number of transactions to visit per mempool query
             mempool_sync_timeout: 180, // how long a mempool sync can go for (3 minutes)
+            block_strike_threshold: 2, // ban a neighbor on its third invalid block within the window
+            block_strike_window: 3600, // count invalid blocks sent within the last hour
+            block_strike_ban_duration: DENY_BAN_DURATION, // how long to ban a neighbor for sending invalid blocks
 
             // no faults on by default
             disable_neighbor_walk: false,
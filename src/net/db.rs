@@ -64,7 +64,7 @@ use crate::core::NETWORK_P2P_PORT;
 
 use crate::util_lib::strings::UrlString;
 
-pub const PEERDB_VERSION: &'static str = "1";
+pub const PEERDB_VERSION: &'static str = "2";
 
 const NUM_SLOTS: usize = 8;
 
@@ -360,8 +360,21 @@ const PEERDB_INITIAL_SCHEMA: &'static [&'static str] = &[
     );"#,
 ];
 
-const PEERDB_INDEXES: &'static [&'static str] =
-    &["CREATE INDEX IF NOT EXISTS peer_address_index ON frontier(network_id,addrbytes,port);"];
+// Invalid blocks and microblocks sent to us by each peer, so that a peer is banned only once it
+// sends too many of them in too short a time.  Kept apart from `frontier`, since strikes can be
+// recorded against peers that are not (or no longer) in the frontier.
+const PEERDB_SCHEMA_2: &'static [&'static str] = &[r#"
+    CREATE TABLE block_strikes(
+        network_id INTEGER NOT NULL,
+        addrbytes TEXT NOT NULL,
+        port INTEGER NOT NULL,
+        strike_time INTEGER NOT NULL
+    );"#];
+
+const PEERDB_INDEXES: &'static [&'static str] = &[
+    "CREATE INDEX IF NOT EXISTS peer_address_index ON frontier(network_id,addrbytes,port);",
+    "CREATE INDEX IF NOT EXISTS block_strikes_address_index ON block_strikes(network_id,addrbytes,port,strike_time);",
+];
 
 #[derive(Debug)]
 pub struct PeerDB {
@@ -397,6 +410,9 @@ impl PeerDB {
         for row_text in PEERDB_INITIAL_SCHEMA {
             tx.execute_batch(row_text).map_err(db_error::SqliteError)?;
         }
+        for row_text in PEERDB_SCHEMA_2 {
+            tx.execute_batch(row_text).map_err(db_error::SqliteError)?;
+        }
 
         tx.execute(
             "INSERT INTO db_config (version) VALUES (?1)",
@@ -447,6 +463,40 @@ impl PeerDB {
         Ok(())
     }
 
+    /// Get the database schema version
+    fn get_schema_version(conn: &Connection) -> Result<String, db_error> {
+        let version =
+            query_row::<String, _>(conn, "SELECT MAX(version) FROM db_config", NO_PARAMS)?
+                .unwrap_or("1".to_string());
+        Ok(version)
+    }
+
+    fn apply_schema_2(tx: &Transaction) -> Result<(), db_error> {
+        for row_text in PEERDB_SCHEMA_2 {
+            tx.execute_batch(row_text).map_err(db_error::SqliteError)?;
+        }
+        tx.execute("UPDATE db_config SET version = ?1", &["2"])
+            .map_err(db_error::SqliteError)?;
+        Ok(())
+    }
+
+    /// Migrate the peer DB to its latest version
+    fn check_schema_version_and_update(&mut self) -> Result<(), db_error> {
+        loop {
+            let version = PeerDB::get_schema_version(&self.conn)?;
+            if version == "1" {
+                debug!("Migrate peer DB from schema 1 to 2");
+                let tx = self.tx_begin()?;
+                PeerDB::apply_schema_2(&tx)?;
+                tx.commit()?;
+            } else if version == PEERDB_VERSION {
+                return Ok(());
+            } else {
+                panic!("The schema version of the peer DB is invalid: {}", &version);
+            }
+        }
+    }
+
     fn add_indexes(&mut self) -> Result<(), db_error> {
         let tx = self.tx_begin()?;
         for row_text in PEERDB_INDEXES {
@@ -580,6 +630,7 @@ impl PeerDB {
                 }
             }
         } else {
+            db.check_schema_version_and_update()?;
             db.update_local_peer(network_id, parent_network_id, data_url, p2p_port)?;
 
             {
@@ -835,6 +886,73 @@ impl PeerDB {
         }
     }
 
+    /// Record that a peer sent us an invalid block or microblock at `strike_time`.
+    pub fn add_block_strike<'a>(
+        tx: &mut Transaction<'a>,
+        network_id: u32,
+        peer_addr: &PeerAddress,
+        peer_port: u16,
+        strike_time: u64,
+    ) -> Result<(), db_error> {
+        let args: &[&dyn ToSql] = &[
+            &network_id,
+            &peer_addr.to_bin(),
+            &peer_port,
+            &u64_to_sql(strike_time)?,
+        ];
+        tx.execute(
+            "INSERT INTO block_strikes (network_id, addrbytes, port, strike_time) VALUES (?1, ?2, ?3, ?4)",
+            args,
+        )
+        .map_err(db_error::SqliteError)?;
+        Ok(())
+    }
+
+    /// How many strikes has a peer received at or after `since`?
+    pub fn get_block_strike_count(
+        conn: &DBConn,
+        network_id: u32,
+        peer_addr: &PeerAddress,
+        peer_port: u16,
+        since: u64,
+    ) -> Result<u64, db_error> {
+        let qry = "SELECT COUNT(*) FROM block_strikes WHERE network_id = ?1 AND addrbytes = ?2 AND port = ?3 AND strike_time >= ?4".to_string();
+        let args: &[&dyn ToSql] = &[
+            &network_id,
+            &peer_addr.to_bin(),
+            &peer_port,
+            &u64_to_sql(since)?,
+        ];
+        let count = query_count(conn, &qry, args)?;
+        Ok(count as u64)
+    }
+
+    /// Forget all of a peer's strikes (e.g. once it has been banned for them).
+    pub fn clear_block_strikes<'a>(
+        tx: &mut Transaction<'a>,
+        network_id: u32,
+        peer_addr: &PeerAddress,
+        peer_port: u16,
+    ) -> Result<(), db_error> {
+        let args: &[&dyn ToSql] = &[&network_id, &peer_addr.to_bin(), &peer_port];
+        tx.execute(
+            "DELETE FROM block_strikes WHERE network_id = ?1 AND addrbytes = ?2 AND port = ?3",
+            args,
+        )
+        .map_err(db_error::SqliteError)?;
+        Ok(())
+    }
+
+    /// Forget all strikes, against any peer, that were received before `before`.
+    pub fn expire_block_strikes<'a>(tx: &mut Transaction<'a>, before: u64) -> Result<(), db_error> {
+        tx.execute(
+            "DELETE FROM block_strikes WHERE strike_time < ?1",
+            &[&u64_to_sql(before)?],
+        )
+        .map_err(db_error::SqliteError)?;
+        Ok(())
+    }
+
     /// Is a peer always allowed?
     pub fn is_peer_always_allowed(
         conn: &DBConn,
@@ -1951,6 +2069,89 @@ mod test {
         assert_eq!(peer_allowed.allowed, 20000000);
     }
 
+    #[test]
+    fn test_peer_block_strikes() {
+        let mut db = PeerDB::connect_memory(
+            0x9abcdef0,
+            12345,
+            0,
+            "http://foo.com".into(),
+            &vec![],
+            &vec![],
+        )
+        .unwrap();
+
+        let addr_1 = PeerAddress([0x1; 16]);
+        let addr_2 = PeerAddress([0x2; 16]);
+
+        // no strikes yet, even for a peer we don't know about
+        assert_eq!(
+            PeerDB::get_block_strike_count(db.conn(), 0x9abcdef0, &addr_1, 12345, 0).unwrap(),
+            0
+        );
+
+        {
+            let mut tx = db.tx_begin().unwrap();
+            for strike_time in [100, 200, 300] {
+                PeerDB::add_block_strike(&mut tx, 0x9abcdef0, &addr_1, 12345, strike_time).unwrap();
+            }
+            PeerDB::add_block_strike(&mut tx, 0x9abcdef0, &addr_2, 12345, 300).unwrap();
+            tx.commit().unwrap();
+        }
+
+        // strikes are counted per peer, and only within the window
+        assert_eq!(
+            PeerDB::get_block_strike_count(db.conn(), 0x9abcdef0, &addr_1, 12345, 0).unwrap(),
+            3
+        );
+        assert_eq!(
+            PeerDB::get_block_strike_count(db.conn(), 0x9abcdef0, &addr_1, 12345, 200).unwrap(),
+            2
+        );
+        assert_eq!(
+            PeerDB::get_block_strike_count(db.conn(), 0x9abcdef0, &addr_1, 12346, 0).unwrap(),
+            0
+        );
+        assert_eq!(
+            PeerDB::get_block_strike_count(db.conn(), 0x9abcdef1, &addr_1, 12345, 0).unwrap(),
+            0
+        );
+        assert_eq!(
+            PeerDB::get_block_strike_count(db.conn(), 0x9abcdef0, &addr_2, 12345, 0).unwrap(),
+            1
+        );
+
+        // expiring old strikes drops them for every peer
+        {
+            let mut tx = db.tx_begin().unwrap();
+            PeerDB::expire_block_strikes(&mut tx, 300).unwrap();
+            tx.commit().unwrap();
+        }
+        assert_eq!(
+            PeerDB::get_block_strike_count(db.conn(), 0x9abcdef0, &addr_1, 12345, 0).unwrap(),
+            1
+        );
+        assert_eq!(
+            PeerDB::get_block_strike_count(db.conn(), 0x9abcdef0, &addr_2, 12345, 0).unwrap(),
+            1
+        );
+
+        // clearing a peer's strikes leaves the others' alone
+        {
+            let mut tx = db.tx_begin().unwrap();
+            PeerDB::clear_block_strikes(&mut tx, 0x9abcdef0, &addr_1, 12345).unwrap();
+            tx.commit().unwrap();
+        }
+        assert_eq!(
+            PeerDB::get_block_strike_count(db.conn(), 0x9abcdef0, &addr_1, 12345, 0).unwrap(),
+            0
+        );
+        assert_eq!(
+            PeerDB::get_block_strike_count(db.conn(), 0x9abcdef0, &addr_2, 12345, 0).unwrap(),
+            1
+        );
+    }
+
     #[test]
     fn test_peer_cidr_lists() {
         let mut db = PeerDB::connect_memory(
@@ -2392,4 +2593,59 @@ mod test {
         let local_peer = PeerDB::get_local_peer(db.conn()).unwrap();
         assert_eq!(local_peer.private_key, key2);
     }
+
+    #[test]
+    fn test_migrate_block_strikes() {
+        let path = "/tmp/test-migrate-block-strikes.db".to_string();
+        if fs::metadata(&path).is_ok() {
+            fs::remove_file(&path).unwrap();
+        }
+        let connect = || {
+            PeerDB::connect(
+                &path,
+                true,
+                0x80000000,
+                0,
+                None,
+                i64::MAX as u64,
+                PeerAddress::from_ipv4(127, 0, 0, 1),
+                12345,
+                UrlString::try_from("http://foo.com").unwrap(),
+                &vec![],
+                None,
+            )
+        };
+
+        // make a peer DB from before block strikes were tracked
+        let db = connect().unwrap();
+        assert_eq!(
+            PeerDB::get_schema_version(db.conn()).unwrap(),
+            PEERDB_VERSION
+        );
+        db.conn()
+            .execute_batch(
+                "DROP INDEX block_strikes_address_index;
+                 DROP TABLE block_strikes;
+                 UPDATE db_config SET version = '1';",
+            )
+            .unwrap();
+        drop(db);
+
+        // opening it again migrates it
+        let mut db = connect().unwrap();
+        assert_eq!(
+            PeerDB::get_schema_version(db.conn()).unwrap(),
+            PEERDB_VERSION
+        );
+        let addr = PeerAddress([0x1; 16]);
+        {
+            let mut tx = db.tx_begin().unwrap();
+            PeerDB::add_block_strike(&mut tx, 0x80000000, &addr, 12345, 100).unwrap();
+            tx.commit().unwrap();
+        }
+        assert_eq!(
+            PeerDB::get_block_strike_count(db.conn(), 0x80000000, &addr, 12345, 0).unwrap(),
+            1
+        );
+    }
 }
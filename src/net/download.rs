@@ -50,6 +50,7 @@ use crate::core::FIRST_BURNCHAIN_CONSENSUS_HASH;
 use crate::core::FIRST_STACKS_BLOCK_HASH;
 use crate::net::asn::ASEntry4;
 use crate::net::atlas::AttachmentsDownloader;
+use crate::net::chat::ConversationP2P;
 use crate::net::codec::*;
 use crate::net::connection::ConnectionOptions;
 use crate::net::connection::ReplyHandleHttp;
@@ -186,6 +187,72 @@ impl std::fmt::Display for BlockRequestKey {
     }
 }
 
+/// A request for the blocks in a range of sortitions from a single peer that supports block
+/// streams.  It covers the individual block requests in `keys`.
+#[derive(Debug, PartialEq, Clone, Hash, Eq)]
+pub struct BlockStreamRequestKey {
+    pub neighbor: NeighborKey,
+    pub data_url: UrlString,
+    pub start_height: u64, // burnchain block height, not sortition height
+    pub end_height: u64,   // ditto
+    pub keys: Vec<BlockRequestKey>,
+    pub canonical_stacks_tip_height: u64,
+}
+
+impl BlockStreamRequestKey {
+    pub fn new(
+        neighbor: NeighborKey,
+        data_url: UrlString,
+        first_block_height: u64,
+        keys: Vec<BlockRequestKey>,
+    ) -> BlockStreamRequestKey {
+        let sortition_heights = keys.iter().map(|key| key.sortition_height);
+        let start_height = first_block_height + sortition_heights.clone().min().unwrap_or(0);
+        let end_height = first_block_height + sortition_heights.max().unwrap_or(0);
+        let canonical_stacks_tip_height = keys
+            .iter()
+            .map(|key| key.canonical_stacks_tip_height)
+            .max()
+            .unwrap_or(0);
+        BlockStreamRequestKey {
+            neighbor,
+            data_url,
+            start_height,
+            end_height,
+            keys,
+            canonical_stacks_tip_height,
+        }
+    }
+}
+
+impl Requestable for BlockStreamRequestKey {
+    fn get_url(&self) -> &UrlString {
+        &self.data_url
+    }
+
+    fn make_request_type(&self, peer_host: PeerHost) -> HttpRequestType {
+        HttpRequestType::GetBlockStream(
+            HttpRequestMetadata::from_host(peer_host, Some(self.canonical_stacks_tip_height)),
+            self.start_height,
+            self.end_height,
+        )
+    }
+}
+
+impl std::fmt::Display for BlockStreamRequestKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "<Request<BlockStream>: {}-{} ({} blocks) {} {:?}>",
+            self.start_height,
+            self.end_height,
+            self.keys.len(),
+            self.neighbor,
+            self.data_url
+        )
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Copy)]
 pub enum BlockDownloaderState {
     DNSLookupBegin,
@@ -239,6 +306,8 @@ pub struct BlockDownloader {
     /// The key for each of these is the sortition height and _index_ block hash.
     getblock_requests: HashMap<BlockRequestKey, usize>,
     getmicroblocks_requests: HashMap<BlockRequestKey, usize>,
+    /// In-flight requests for ranges of blocks from peers that support block streams
+    getblock_stream_requests: HashMap<BlockStreamRequestKey, usize>,
     blocks: HashMap<BlockRequestKey, StacksBlock>,
     microblocks: HashMap<BlockRequestKey, Vec<StacksMicroblock>>,
 
@@ -289,6 +358,7 @@ impl BlockDownloader {
 
             getblock_requests: HashMap::new(),
             getmicroblocks_requests: HashMap::new(),
+            getblock_stream_requests: HashMap::new(),
             blocks: HashMap::new(),
             microblocks: HashMap::new(),
 
@@ -312,6 +382,7 @@ impl BlockDownloader {
 
         self.getblock_requests.clear();
         self.getmicroblocks_requests.clear();
+        self.getblock_stream_requests.clear();
         self.blocks_to_try.clear();
         self.microblocks_to_try.clear();
         self.blocks.clear();
@@ -430,14 +501,70 @@ impl BlockDownloader {
         Ok(inflight == 0)
     }
 
-    pub fn getblocks_begin(&mut self, requests: HashMap<BlockRequestKey, usize>) -> () {
+    pub fn getblocks_begin(
+        &mut self,
+        requests: HashMap<BlockRequestKey, usize>,
+        stream_requests: HashMap<BlockStreamRequestKey, usize>,
+    ) -> () {
         assert_eq!(self.state, BlockDownloaderState::GetBlocksBegin);
 
         // don't touch blocks-to-try -- that's managed by the peer network directly.
         self.getblock_requests = requests;
+        self.getblock_stream_requests = stream_requests;
         self.state = BlockDownloaderState::GetBlocksFinish;
     }
 
+    /// Match the blocks returned by a block stream request against the block requests it
+    /// covers.  Blocks we asked for are stored; requests the peer had no block for will be
+    /// retried on a later pass.
+    fn finish_block_stream(
+        &mut self,
+        stream_key: BlockStreamRequestKey,
+        mut blocks: Vec<StacksBlock>,
+    ) -> () {
+        let mut blocks_by_hash: HashMap<BlockHeaderHash, StacksBlock> = blocks
+            .drain(..)
+            .map(|block| (block.block_hash(), block))
+            .collect();
+
+        for block_key in stream_key.keys.into_iter() {
+            match blocks_by_hash.remove(&block_key.anchor_block_hash) {
+                Some(block) => {
+                    debug!(
+                        "Got streamed block {}: {}/{}",
+                        &block_key.sortition_height,
+                        &block_key.consensus_hash,
+                        block.block_hash()
+                    );
+                    self.blocks.insert(block_key, block);
+                }
+                None => {
+                    info!(
+                        "Remote neighbor {:?} ({:?}) did not stream block {} indexed at {} ({})",
+                        &block_key.neighbor,
+                        &block_key.data_url,
+                        block_key.sortition_height,
+                        &block_key.index_block_hash,
+                        &block_key.consensus_hash
+                    );
+
+                    // try again
+                    self.requested_blocks.remove(&block_key.index_block_hash);
+                }
+            }
+        }
+
+        if blocks_by_hash.len() > 0 {
+            // the range can include blocks we already have, or that we'd ask someone else for
+            debug!(
+                "Ignoring {} unrequested blocks streamed from {:?} ({:?})",
+                blocks_by_hash.len(),
+                &stream_key.neighbor,
+                &stream_key.data_url
+            );
+        }
+    }
+
     /// Finish fetching blocks.  Return true once all reply handles have been fulfilled (either
     /// with data, or with an error).
     /// Store blocks as we get them.
@@ -446,8 +573,76 @@ impl BlockDownloader {
 
         // requests that are still pending
         let mut pending_block_requests = HashMap::new();
+        let mut pending_block_stream_requests = HashMap::new();
 
         PeerNetwork::with_http(network, |ref mut network, ref mut http| {
+            let block_stream_requests: Vec<_> = self.getblock_stream_requests.drain().collect();
+            for (stream_key, event_id) in block_stream_requests.into_iter() {
+                match http.get_conversation(event_id) {
+                    None => {
+                        if http.is_connecting(event_id) {
+                            debug!(
+                                "Event {} ({:?}, {:?} for block stream {}-{}) is not connected yet",
+                                event_id,
+                                &stream_key.neighbor,
+                                &stream_key.data_url,
+                                stream_key.start_height,
+                                stream_key.end_height
+                            );
+                            pending_block_stream_requests.insert(stream_key, event_id);
+                        } else {
+                            self.dead_peers.push(event_id);
+
+                            // try again
+                            for block_key in stream_key.keys.iter() {
+                                self.requested_blocks.remove(&block_key.index_block_hash);
+                            }
+
+                            let is_always_allowed = match PeerDB::get_peer(
+                                &network.peerdb.conn(),
+                                stream_key.neighbor.network_id,
+                                &stream_key.neighbor.addrbytes,
+                                stream_key.neighbor.port,
+                            ) {
+                                Ok(Some(neighbor)) => neighbor.is_always_allowed(),
+                                _ => false,
+                            };
+
+                            if !is_always_allowed {
+                                debug!("Event {} ({:?}, {:?}) for block stream {}-{} failed to connect. Temporarily blocking URL", event_id, &stream_key.neighbor, &stream_key.data_url, stream_key.start_height, stream_key.end_height);
+
+                                // don't try this again for a while
+                                self.blocked_urls.insert(
+                                    stream_key.data_url,
+                                    get_epoch_time_secs() + BLOCK_DOWNLOAD_BAN_URL,
+                                );
+                            }
+                        }
+                    }
+                    Some(ref mut convo) => match convo.try_get_response() {
+                        None => {
+                            // still waiting
+                            debug!("Event {} ({:?}, {:?} for block stream {}-{}) is still waiting for a response", event_id, &stream_key.neighbor, &stream_key.data_url, stream_key.start_height, stream_key.end_height);
+                            pending_block_stream_requests.insert(stream_key, event_id);
+                        }
+                        Some(http_response) => match http_response {
+                            HttpResponseType::Blocks(_md, blocks) => {
+                                self.finish_block_stream(stream_key, blocks);
+                            }
+                            _ => {
+                                // wrong message response
+                                info!(
+                                    "Got bad HTTP response from {:?}: {:?}",
+                                    &stream_key.data_url, &http_response
+                                );
+                                self.broken_peers.push(event_id);
+                                self.broken_neighbors.push(stream_key.neighbor.clone());
+                            }
+                        },
+                    },
+                }
+            }
+
             for (block_key, event_id) in self.getblock_requests.drain() {
                 match http.get_conversation(event_id) {
                     None => {
@@ -550,7 +745,7 @@ impl BlockDownloader {
         });
 
         // are we done?
-        if pending_block_requests.len() == 0 {
+        if pending_block_requests.len() == 0 && pending_block_stream_requests.len() == 0 {
             self.state = BlockDownloaderState::GetMicroblocksBegin;
             return Ok(true);
         }
@@ -559,6 +754,9 @@ impl BlockDownloader {
         for (block_key, event_id) in pending_block_requests.drain() {
             self.getblock_requests.insert(block_key, event_id);
         }
+        for (stream_key, event_id) in pending_block_stream_requests.drain() {
+            self.getblock_stream_requests.insert(stream_key, event_id);
+        }
         return Ok(false);
     }
 
@@ -2031,6 +2229,68 @@ impl PeerNetwork {
         None
     }
 
+    /// Batch up block requests for peers that support block streams.  The next request at each
+    /// sortition height is grouped by the peer it would go to, and each run of two or more
+    /// requests to the same peer within MAX_BLOCK_STREAM_RANGE sortitions is taken out of
+    /// `blocks_to_try` and folded into a single block stream request.
+    fn make_block_stream_requests(
+        network: &PeerNetwork,
+        blocks_to_try: &mut HashMap<u64, VecDeque<BlockRequestKey>>,
+    ) -> Vec<BlockStreamRequestKey> {
+        let mut sortition_heights: Vec<u64> = blocks_to_try.keys().map(|h| *h).collect();
+        sortition_heights.sort();
+
+        let mut heights_by_peer: HashMap<(NeighborKey, UrlString), Vec<u64>> = HashMap::new();
+        for sortition_height in sortition_heights.into_iter() {
+            let key = match blocks_to_try
+                .get(&sortition_height)
+                .and_then(|keys| keys.front())
+            {
+                Some(key) => key,
+                None => {
+                    continue;
+                }
+            };
+            let supports_block_stream = network
+                .get_convo(&key.neighbor)
+                .map(|convo| ConversationP2P::supports_block_stream(convo.peer_services))
+                .unwrap_or(false);
+            if !supports_block_stream {
+                continue;
+            }
+            heights_by_peer
+                .entry((key.neighbor.clone(), key.data_url.clone()))
+                .or_insert(vec![])
+                .push(sortition_height);
+        }
+
+        let mut stream_requests = vec![];
+        for ((neighbor, data_url), heights) in heights_by_peer.into_iter() {
+            let mut i = 0;
+            while i < heights.len() {
+                let mut j = i;
+                while j + 1 < heights.len() && heights[j + 1] - heights[i] < MAX_BLOCK_STREAM_RANGE
+                {
+                    j += 1;
+                }
+                if j > i {
+                    let keys: Vec<BlockRequestKey> = heights[i..=j]
+                        .iter()
+                        .filter_map(|h| blocks_to_try.get_mut(h).and_then(|keys| keys.pop_front()))
+                        .collect();
+                    stream_requests.push(BlockStreamRequestKey::new(
+                        neighbor.clone(),
+                        data_url.clone(),
+                        network.burnchain.first_block_height,
+                        keys,
+                    ));
+                }
+                i = j + 1;
+            }
+        }
+        stream_requests
+    }
+
     /// Start fetching blocks
     pub fn block_getblocks_begin(
         &mut self,
@@ -2039,9 +2299,47 @@ impl PeerNetwork {
     ) -> Result<(), net_error> {
         test_debug!("{:?}: block_getblocks_begin", &self.local_peer);
         PeerNetwork::with_downloader_state(self, |ref mut network, ref mut downloader| {
+            // prefer fetching runs of blocks in one go from peers that can stream them
+            let mut stream_requests = HashMap::new();
+            let mut streamed_heights = HashSet::new();
+            for stream_key in
+                PeerNetwork::make_block_stream_requests(network, &mut downloader.blocks_to_try)
+            {
+                let block_keys = stream_key.keys.clone();
+                let mut stream_keys = VecDeque::from(vec![stream_key]);
+                match PeerNetwork::begin_request(
+                    network,
+                    &downloader.dns_lookups,
+                    &mut stream_keys,
+                    mempool,
+                    chainstate,
+                ) {
+                    Some((stream_key, handle)) => {
+                        for block_key in stream_key.keys.iter() {
+                            streamed_heights.insert(block_key.sortition_height);
+                        }
+                        stream_requests.insert(stream_key, handle);
+                    }
+                    None => {
+                        // fall back to asking for these blocks one at a time
+                        for block_key in block_keys.into_iter() {
+                            if let Some(keys) = downloader
+                                .blocks_to_try
+                                .get_mut(&block_key.sortition_height)
+                            {
+                                keys.push_front(block_key);
+                            }
+                        }
+                    }
+                }
+            }
+
             let mut priority = PeerNetwork::prioritize_requests(&downloader.blocks_to_try);
             let mut requests = HashMap::new();
             for sortition_height in priority.drain(..) {
+                if streamed_heights.contains(&sortition_height) {
+                    continue;
+                }
                 match downloader.blocks_to_try.get_mut(&sortition_height) {
                     Some(ref mut keys) => {
                         match PeerNetwork::begin_request(
@@ -2066,7 +2364,7 @@ impl PeerNetwork {
                 }
             }
 
-            downloader.getblocks_begin(requests);
+            downloader.getblocks_begin(requests, stream_requests);
             Ok(())
         })
     }
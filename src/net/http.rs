@@ -17,7 +17,7 @@
  along with Blockstack. If not, see <http://www.gnu.org/licenses/>.
 */
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::convert::TryFrom;
 use std::fmt;
 use std::io;
@@ -40,7 +40,7 @@ use url::{form_urlencoded, Url};
 use crate::burnchains::{Address, Txid};
 use crate::chainstate::burn::ConsensusHash;
 use crate::chainstate::stacks::{
-    StacksBlock, StacksMicroblock, StacksPublicKey, StacksTransaction,
+    StacksBlock, StacksMicroblock, StacksPublicKey, StacksTransaction, MAX_BLOCK_LEN,
 };
 use crate::deps::httparse;
 use crate::net::atlas::Attachment;
@@ -69,10 +69,12 @@ use crate::net::UnconfirmedTransactionStatus;
 use crate::net::HTTP_PREAMBLE_MAX_ENCODED_SIZE;
 use crate::net::HTTP_PREAMBLE_MAX_NUM_HEADERS;
 use crate::net::HTTP_REQUEST_ID_RESERVED;
+use crate::net::MAX_BLOCK_STREAM_RANGE;
 use crate::net::MAX_HEADERS;
 use crate::net::MAX_MICROBLOCKS_UNCONFIRMED;
 use crate::net::{CallReadOnlyRequestBody, TipRequest};
 use crate::net::{GetAttachmentResponse, GetAttachmentsInvResponse, PostTransactionRequestBody};
+use crate::net::{PostTransactionBatchRequestBody, MAX_TRANSACTION_BATCH_LEN};
 use clarity::vm::ast::parser::v1::CLARITY_NAME_REGEX;
 use clarity::vm::types::{StandardPrincipalData, TraitIdentifier};
 use clarity::vm::{
@@ -102,26 +104,49 @@ use super::FeeRateEstimateRequestBody;
 lazy_static! {
     static ref PATH_GETINFO: Regex = Regex::new(r#"^/v2/info$"#).unwrap();
     static ref PATH_GETPOXINFO: Regex = Regex::new(r#"^/v2/pox$"#).unwrap();
+    static ref PATH_GET_POX_REWARD_CYCLE: Regex =
+        Regex::new(r#"^/v2/pox/reward-cycle/([0-9]+)$"#).unwrap();
+    static ref PATH_GET_POX_SUNSET_BURN: Regex =
+        Regex::new(r#"^/v2/pox/sunset-burn/([0-9]+)/([0-9]+)$"#).unwrap();
+    static ref PATH_GET_BLOCK_COMMIT_BY_HEIGHT: Regex =
+        Regex::new(r#"^/v2/block-commits/([0-9]+)$"#).unwrap();
+    static ref PATH_GET_BLOCK_COMMIT_BY_STACKS_BLOCK: Regex =
+        Regex::new(r#"^/v2/block-commits/stacks-block/([0-9a-f]{64})$"#).unwrap();
     static ref PATH_GETNEIGHBORS: Regex = Regex::new(r#"^/v2/neighbors$"#).unwrap();
     static ref PATH_GETHEADERS: Regex = Regex::new(r#"^/v2/headers/([0-9]+)$"#).unwrap();
     static ref PATH_GETBLOCK: Regex = Regex::new(r#"^/v2/blocks/([0-9a-f]{64})$"#).unwrap();
+    static ref PATH_GETBLOCK_STREAM: Regex = Regex::new(r#"^/v2/blocks/stream$"#).unwrap();
+    static ref PATH_GETBLOCK_BY_HEIGHT: Regex =
+        Regex::new(r#"^/v2/blocks/height/([0-9]{1,20})$"#).unwrap();
     static ref PATH_GETMICROBLOCKS_INDEXED: Regex =
         Regex::new(r#"^/v2/microblocks/([0-9a-f]{64})$"#).unwrap();
     static ref PATH_GETMICROBLOCKS_CONFIRMED: Regex =
         Regex::new(r#"^/v2/microblocks/confirmed/([0-9a-f]{64})$"#).unwrap();
     static ref PATH_GETMICROBLOCKS_UNCONFIRMED: Regex =
-        Regex::new(r#"^/v2/microblocks/unconfirmed/([0-9a-f]{64})/([0-9]{1,5})$"#).unwrap();
+        Regex::new(r#"^/v2/microblocks/unconfirmed/([0-9a-f]{64})/([^/]+)$"#).unwrap();
     static ref PATH_GETTRANSACTION_UNCONFIRMED: Regex =
         Regex::new(r#"^/v2/transactions/unconfirmed/([0-9a-f]{64})$"#).unwrap();
     static ref PATH_POSTTRANSACTION: Regex = Regex::new(r#"^/v2/transactions$"#).unwrap();
+    static ref PATH_POSTTRANSACTION_DRY_RUN: Regex =
+        Regex::new(r#"^/v2/transactions/dry-run$"#).unwrap();
+    static ref PATH_POSTTRANSACTION_BATCH: Regex =
+        Regex::new(r#"^/v2/transactions/batch$"#).unwrap();
     static ref PATH_POST_FEE_RATE_ESIMATE: Regex = Regex::new(r#"^/v2/fees/transaction$"#).unwrap();
     static ref PATH_POSTBLOCK: Regex = Regex::new(r#"^/v2/blocks/upload/([0-9a-f]{40})$"#).unwrap();
+    static ref PATH_POSTBLOCK_VALIDATE: Regex = Regex::new(r#"^/v2/blocks/validate$"#).unwrap();
     static ref PATH_POSTMICROBLOCK: Regex = Regex::new(r#"^/v2/microblocks$"#).unwrap();
+    static ref PATH_POSTMICROBLOCKS_VALIDATE: Regex =
+        Regex::new(r#"^/v2/microblocks/validate$"#).unwrap();
     static ref PATH_GET_ACCOUNT: Regex = Regex::new(&format!(
         "^/v2/accounts/(?P<principal>{})$",
         *PRINCIPAL_DATA_REGEX_STRING
     ))
     .unwrap();
+    static ref PATH_GET_ACCOUNT_STACKING: Regex = Regex::new(&format!(
+        "^/v2/accounts/(?P<principal>{})/stacking$",
+        *PRINCIPAL_DATA_REGEX_STRING
+    ))
+    .unwrap();
     static ref PATH_GET_DATA_VAR: Regex = Regex::new(&format!(
         "^/v2/data_var/(?P<address>{})/(?P<contract>{})/(?P<varname>{})$",
         *STANDARD_PRINCIPAL_REGEX_STRING, *CONTRACT_NAME_REGEX_STRING, *CLARITY_NAME_REGEX
@@ -153,11 +178,20 @@ lazy_static! {
     ))
     .unwrap();
     static ref PATH_GET_TRANSFER_COST: Regex = Regex::new("^/v2/fees/transfer$").unwrap();
+    static ref PATH_GET_FEE_RATE: Regex = Regex::new("^/v2/fees/rate$").unwrap();
+    static ref PATH_GET_FEE_RATE_PERCENTILES: Regex =
+        Regex::new("^/v2/fees/rate/percentiles$").unwrap();
     static ref PATH_GET_ATTACHMENTS_INV: Regex = Regex::new("^/v2/attachments/inv$").unwrap();
     static ref PATH_GET_ATTACHMENT: Regex =
         Regex::new(r#"^/v2/attachments/([0-9a-f]{40})$"#).unwrap();
     static ref PATH_POST_MEMPOOL_QUERY: Regex =
         Regex::new(r#"^/v2/mempool/query$"#).unwrap();
+    static ref PATH_GET_MEMPOOL_TRANSACTIONS_BY_ADDRESS: Regex = Regex::new(&format!(
+        "^/v2/mempool/transactions/(?P<address>{})$",
+        *STANDARD_PRINCIPAL_REGEX_STRING
+    ))
+    .unwrap();
+    static ref PATH_GET_MEMPOOL_STATS: Regex = Regex::new(r#"^/v2/mempool/stats$"#).unwrap();
     static ref PATH_OPTIONS_WILDCARD: Regex = Regex::new("^/v2/.{0,4096}$").unwrap();
 }
 
@@ -1499,6 +1533,26 @@ impl HttpRequestType {
         )] = &[
             ("GET", &PATH_GETINFO, &HttpRequestType::parse_getinfo),
             ("GET", &PATH_GETPOXINFO, &HttpRequestType::parse_getpoxinfo),
+            (
+                "GET",
+                &PATH_GET_POX_REWARD_CYCLE,
+                &HttpRequestType::parse_get_pox_reward_cycle,
+            ),
+            (
+                "GET",
+                &PATH_GET_POX_SUNSET_BURN,
+                &HttpRequestType::parse_get_pox_sunset_burn,
+            ),
+            (
+                "GET",
+                &PATH_GET_BLOCK_COMMIT_BY_HEIGHT,
+                &HttpRequestType::parse_get_block_commit_by_height,
+            ),
+            (
+                "GET",
+                &PATH_GET_BLOCK_COMMIT_BY_STACKS_BLOCK,
+                &HttpRequestType::parse_get_block_commit_by_stacks_block,
+            ),
             (
                 "GET",
                 &PATH_GETNEIGHBORS,
@@ -1506,6 +1560,16 @@ impl HttpRequestType {
             ),
             ("GET", &PATH_GETHEADERS, &HttpRequestType::parse_getheaders),
             ("GET", &PATH_GETBLOCK, &HttpRequestType::parse_getblock),
+            (
+                "GET",
+                &PATH_GETBLOCK_STREAM,
+                &HttpRequestType::parse_getblock_stream,
+            ),
+            (
+                "GET",
+                &PATH_GETBLOCK_BY_HEIGHT,
+                &HttpRequestType::parse_getblock_by_height,
+            ),
             (
                 "GET",
                 &PATH_GETMICROBLOCKS_INDEXED,
@@ -1536,17 +1600,42 @@ impl HttpRequestType {
                 &PATH_POSTTRANSACTION,
                 &HttpRequestType::parse_posttransaction,
             ),
+            (
+                "POST",
+                &PATH_POSTTRANSACTION_DRY_RUN,
+                &HttpRequestType::parse_posttransaction_dry_run,
+            ),
+            (
+                "POST",
+                &PATH_POSTTRANSACTION_BATCH,
+                &HttpRequestType::parse_posttransaction_batch,
+            ),
             ("POST", &PATH_POSTBLOCK, &HttpRequestType::parse_postblock),
+            (
+                "POST",
+                &PATH_POSTBLOCK_VALIDATE,
+                &HttpRequestType::parse_postblock_validate,
+            ),
             (
                 "POST",
                 &PATH_POSTMICROBLOCK,
                 &HttpRequestType::parse_postmicroblock,
             ),
+            (
+                "POST",
+                &PATH_POSTMICROBLOCKS_VALIDATE,
+                &HttpRequestType::parse_postmicroblocks_validate,
+            ),
             (
                 "GET",
                 &PATH_GET_ACCOUNT,
                 &HttpRequestType::parse_get_account,
             ),
+            (
+                "GET",
+                &PATH_GET_ACCOUNT_STACKING,
+                &HttpRequestType::parse_get_account_stacking,
+            ),
             (
                 "GET",
                 &PATH_GET_DATA_VAR,
@@ -1562,6 +1651,16 @@ impl HttpRequestType {
                 &PATH_GET_TRANSFER_COST,
                 &HttpRequestType::parse_get_transfer_cost,
             ),
+            (
+                "GET",
+                &PATH_GET_FEE_RATE,
+                &HttpRequestType::parse_get_fee_rate,
+            ),
+            (
+                "GET",
+                &PATH_GET_FEE_RATE_PERCENTILES,
+                &HttpRequestType::parse_get_fee_rate_percentiles,
+            ),
             (
                 "GET",
                 &PATH_GET_CONTRACT_SRC,
@@ -1602,6 +1701,16 @@ impl HttpRequestType {
                 &PATH_POST_MEMPOOL_QUERY,
                 &HttpRequestType::parse_post_mempool_query,
             ),
+            (
+                "GET",
+                &PATH_GET_MEMPOOL_TRANSACTIONS_BY_ADDRESS,
+                &HttpRequestType::parse_get_mempool_transactions_by_address,
+            ),
+            (
+                "GET",
+                &PATH_GET_MEMPOOL_STATS,
+                &HttpRequestType::parse_get_mempool_stats,
+            ),
         ];
 
         // use url::Url to parse path and query string
@@ -1688,6 +1797,140 @@ impl HttpRequestType {
         ))
     }
 
+    fn parse_get_pox_reward_cycle<R: Read>(
+        _protocol: &mut StacksHttp,
+        preamble: &HttpRequestPreamble,
+        captures: &Captures,
+        query: Option<&str>,
+        _fd: &mut R,
+    ) -> Result<HttpRequestType, net_error> {
+        if preamble.get_content_length() != 0 {
+            return Err(net_error::DeserializeError(
+                "Invalid Http request: expected 0-length body for GetPoxRewardCycle".to_string(),
+            ));
+        }
+
+        let height_str = captures
+            .get(1)
+            .ok_or(net_error::DeserializeError(
+                "Failed to match path to burn block height group".to_string(),
+            ))?
+            .as_str();
+
+        let burn_height: u64 = height_str.parse().map_err(|_| {
+            net_error::DeserializeError("Failed to parse burn block height".to_string())
+        })?;
+
+        let tip = HttpRequestType::get_chain_tip_query(query);
+
+        Ok(HttpRequestType::GetPoxRewardCycle(
+            HttpRequestMetadata::from_preamble(preamble),
+            burn_height,
+            tip,
+        ))
+    }
+
+    fn parse_get_pox_sunset_burn<R: Read>(
+        _protocol: &mut StacksHttp,
+        preamble: &HttpRequestPreamble,
+        captures: &Captures,
+        _query: Option<&str>,
+        _fd: &mut R,
+    ) -> Result<HttpRequestType, net_error> {
+        if preamble.get_content_length() != 0 {
+            return Err(net_error::DeserializeError(
+                "Invalid Http request: expected 0-length body for GetPoxSunsetBurn".to_string(),
+            ));
+        }
+
+        let burn_height: u64 = captures
+            .get(1)
+            .ok_or(net_error::DeserializeError(
+                "Failed to match path to burn block height group".to_string(),
+            ))?
+            .as_str()
+            .parse()
+            .map_err(|_| {
+                net_error::DeserializeError("Failed to parse burn block height".to_string())
+            })?;
+
+        let burn_fee_cap: u64 = captures
+            .get(2)
+            .ok_or(net_error::DeserializeError(
+                "Failed to match path to burn fee cap group".to_string(),
+            ))?
+            .as_str()
+            .parse()
+            .map_err(|_| net_error::DeserializeError("Failed to parse burn fee cap".to_string()))?;
+
+        Ok(HttpRequestType::GetPoxSunsetBurn(
+            HttpRequestMetadata::from_preamble(preamble),
+            burn_height,
+            burn_fee_cap,
+        ))
+    }
+
+    fn parse_get_block_commit_by_height<R: Read>(
+        _protocol: &mut StacksHttp,
+        preamble: &HttpRequestPreamble,
+        captures: &Captures,
+        _query: Option<&str>,
+        _fd: &mut R,
+    ) -> Result<HttpRequestType, net_error> {
+        if preamble.get_content_length() != 0 {
+            return Err(net_error::DeserializeError(
+                "Invalid Http request: expected 0-length body for GetBlockCommitByHeight"
+                    .to_string(),
+            ));
+        }
+
+        let burn_height: u64 = captures
+            .get(1)
+            .ok_or(net_error::DeserializeError(
+                "Failed to match path to burn block height group".to_string(),
+            ))?
+            .as_str()
+            .parse()
+            .map_err(|_| {
+                net_error::DeserializeError("Failed to parse burn block height".to_string())
+            })?;
+
+        Ok(HttpRequestType::GetBlockCommitByHeight(
+            HttpRequestMetadata::from_preamble(preamble),
+            burn_height,
+        ))
+    }
+
+    fn parse_get_block_commit_by_stacks_block<R: Read>(
+        _protocol: &mut StacksHttp,
+        preamble: &HttpRequestPreamble,
+        captures: &Captures,
+        _query: Option<&str>,
+        _fd: &mut R,
+    ) -> Result<HttpRequestType, net_error> {
+        if preamble.get_content_length() != 0 {
+            return Err(net_error::DeserializeError(
+                "Invalid Http request: expected 0-length body for GetBlockCommitByStacksBlock"
+                    .to_string(),
+            ));
+        }
+
+        let block_hash_str = captures
+            .get(1)
+            .ok_or(net_error::DeserializeError(
+                "Failed to match path to block hash group".to_string(),
+            ))?
+            .as_str();
+
+        let block_hash = StacksBlockId::from_hex(block_hash_str)
+            .map_err(|_e| net_error::DeserializeError("Failed to parse block hash".to_string()))?;
+
+        Ok(HttpRequestType::GetBlockCommitByStacksBlock(
+            HttpRequestMetadata::from_preamble(preamble),
+            block_hash,
+        ))
+    }
+
     fn parse_getneighbors<R: Read>(
         _protocol: &mut StacksHttp,
         preamble: &HttpRequestPreamble,
@@ -1724,6 +1967,74 @@ impl HttpRequestType {
         ))
     }
 
+    fn parse_get_fee_rate<R: Read>(
+        _protocol: &mut StacksHttp,
+        preamble: &HttpRequestPreamble,
+        _regex: &Captures,
+        _query: Option<&str>,
+        _fd: &mut R,
+    ) -> Result<HttpRequestType, net_error> {
+        if preamble.get_content_length() != 0 {
+            return Err(net_error::DeserializeError(
+                "Invalid Http request: expected 0-length body for GetFeeRate".to_string(),
+            ));
+        }
+
+        Ok(HttpRequestType::GetFeeRate(
+            HttpRequestMetadata::from_preamble(preamble),
+        ))
+    }
+
+    /// Parse a GET for the fee rate estimates at the comma-separated `percentiles` in the query
+    /// string, e.g. `?percentiles=5,25,50`. Each percentile must be in [0, 100].
+    fn parse_get_fee_rate_percentiles<R: Read>(
+        _protocol: &mut StacksHttp,
+        preamble: &HttpRequestPreamble,
+        _regex: &Captures,
+        query: Option<&str>,
+        _fd: &mut R,
+    ) -> Result<HttpRequestType, net_error> {
+        if preamble.get_content_length() != 0 {
+            return Err(net_error::DeserializeError(
+                "Invalid Http request: expected 0-length body for GetFeeRatePercentiles"
+                    .to_string(),
+            ));
+        }
+
+        let mut percentiles = vec![];
+        if let Some(query) = query {
+            for (key, value) in form_urlencoded::parse(query.as_bytes()) {
+                if key != "percentiles" {
+                    continue;
+                }
+                for entry in value.split(",") {
+                    let percentile = match entry.parse::<u8>() {
+                        Ok(percentile) if percentile <= 100 => percentile,
+                        _ => {
+                            return Err(net_error::ClientError(ClientError::Message(format!(
+                                "Failed to parse percentiles: expected a number in [0, 100], got '{}'",
+                                entry
+                            ))));
+                        }
+                    };
+                    percentiles.push(percentile);
+                }
+            }
+        }
+        if percentiles.is_empty() {
+            return Err(net_error::ClientError(ClientError::Message(
+                "Failed to parse percentiles: expected at least one percentile".to_string(),
+            )));
+        }
+        percentiles.sort();
+        percentiles.dedup();
+
+        Ok(HttpRequestType::GetFeeRatePercentiles(
+            HttpRequestMetadata::from_preamble(preamble),
+            percentiles,
+        ))
+    }
+
     /// Check whether the given option query string sets proof=0 (setting proof to false).
     /// Defaults to true.
     fn get_proof_query(query: Option<&str>) -> bool {
@@ -1739,6 +2050,19 @@ impl HttpRequestType {
         !no_proof
     }
 
+    /// Check whether the given optional query string sets unanchored=true.
+    /// Defaults to false.
+    fn get_unanchored_query(query: Option<&str>) -> bool {
+        if let Some(query_string) = query {
+            form_urlencoded::parse(query_string.as_bytes())
+                .find(|(key, _v)| key == "unanchored")
+                .map(|(_k, value)| value == "true")
+                .unwrap_or(false)
+        } else {
+            false
+        }
+    }
+
     /// get the chain tip optional query argument (`tip`)
     /// Take the first value we can parse.
     fn get_chain_tip_query(query: Option<&str>) -> TipRequest {
@@ -1764,6 +2088,59 @@ impl HttpRequestType {
         }
     }
 
+    /// get the historical block optional query argument (`until_block`), which is either a block
+    /// height or an index block hash.  Unlike `tip`, a malformed value is an error.
+    fn get_until_block_query(query: Option<&str>) -> Result<Option<TipRequest>, net_error> {
+        let query_string = match query {
+            Some(query_string) => query_string,
+            None => {
+                return Ok(None);
+            }
+        };
+        for (key, value) in form_urlencoded::parse(query_string.as_bytes()) {
+            if key != "until_block" {
+                continue;
+            }
+            if let Ok(height) = value.parse::<u64>() {
+                if height < u32::MAX as u64 {
+                    return Ok(Some(TipRequest::CanonicalAncestorAtHeight(height)));
+                }
+            }
+            if let Ok(block_id) = StacksBlockId::from_hex(&value) {
+                return Ok(Some(TipRequest::CanonicalAncestor(block_id)));
+            }
+            return Err(net_error::DeserializeError(format!(
+                "Failed to parse until_block: expected a block height or an index block hash, got '{}'",
+                value
+            )));
+        }
+        Ok(None)
+    }
+
+    /// get the maximum number of microblocks to return (`limit`).  Unlike `tip`, a malformed or
+    /// zero value is an error.
+    fn get_microblocks_limit_query(query: Option<&str>) -> Result<Option<u16>, net_error> {
+        let query_string = match query {
+            Some(query_string) => query_string,
+            None => {
+                return Ok(None);
+            }
+        };
+        for (key, value) in form_urlencoded::parse(query_string.as_bytes()) {
+            if key != "limit" {
+                continue;
+            }
+            return match value.parse::<u16>() {
+                Ok(limit) if limit > 0 => Ok(Some(limit)),
+                _ => Err(net_error::ClientError(ClientError::Message(format!(
+                    "Failed to parse limit: expected a positive number of microblocks, got '{}'",
+                    value
+                )))),
+            };
+        }
+        Ok(None)
+    }
+
     /// get the mempool page ID optional query argument (`page_id`)
     /// Take the first value we can parse.
     fn get_mempool_page_id_query(query: Option<&str>) -> Option<Txid> {
@@ -1803,7 +2180,18 @@ impl HttpRequestType {
         })?;
 
         let with_proof = HttpRequestType::get_proof_query(query);
-        let tip = HttpRequestType::get_chain_tip_query(query);
+        let tip = match HttpRequestType::get_until_block_query(query)? {
+            Some(until_block) => {
+                if HttpRequestType::get_chain_tip_query(query) != TipRequest::UseLatestAnchoredTip {
+                    return Err(net_error::DeserializeError(
+                        "Invalid Http request: `tip` and `until_block` are mutually exclusive"
+                            .to_string(),
+                    ));
+                }
+                until_block
+            }
+            None => HttpRequestType::get_chain_tip_query(query),
+        };
 
         Ok(HttpRequestType::GetAccount(
             HttpRequestMetadata::from_preamble(preamble),
@@ -1813,6 +2201,30 @@ impl HttpRequestType {
         ))
     }
 
+    fn parse_get_account_stacking<R: Read>(
+        _protocol: &mut StacksHttp,
+        preamble: &HttpRequestPreamble,
+        captures: &Captures,
+        query: Option<&str>,
+        _fd: &mut R,
+    ) -> Result<HttpRequestType, net_error> {
+        if preamble.get_content_length() != 0 {
+            return Err(net_error::DeserializeError(
+                "Invalid Http request: expected 0-length body for GetAccountStacking".to_string(),
+            ));
+        }
+
+        let principal = PrincipalData::parse(&captures["principal"]).map_err(|_e| {
+            net_error::DeserializeError("Failed to parse account principal".into())
+        })?;
+
+        Ok(HttpRequestType::GetAccountStacking(
+            HttpRequestMetadata::from_preamble(preamble),
+            principal,
+            HttpRequestType::get_chain_tip_query(query),
+        ))
+    }
+
     fn parse_get_data_var<R: Read>(
         _protocol: &mut StacksHttp,
         preamble: &HttpRequestPreamble,
@@ -2117,16 +2529,115 @@ impl HttpRequestType {
         ))
     }
 
-    fn parse_getmicroblocks_indexed<R: Read>(
-        _protocol: &mut StacksHttp,
-        preamble: &HttpRequestPreamble,
-        captures: &Captures,
-        _query: Option<&str>,
-        _fd: &mut R,
-    ) -> Result<HttpRequestType, net_error> {
-        if preamble.get_content_length() != 0 {
-            return Err(net_error::DeserializeError(
-                "Invalid Http request: expected 0-length body for GetMicroblocksIndexed"
+    /// get the required `start_height` and `end_height` query arguments of a block stream
+    /// request.  The range is inclusive, and may span at most MAX_BLOCK_STREAM_RANGE sortitions.
+    fn get_block_stream_range_query(query: Option<&str>) -> Result<(u64, u64), net_error> {
+        let mut start_height = None;
+        let mut end_height = None;
+        if let Some(query_string) = query {
+            for (key, value) in form_urlencoded::parse(query_string.as_bytes()) {
+                let height = if key == "start_height" {
+                    &mut start_height
+                } else if key == "end_height" {
+                    &mut end_height
+                } else {
+                    continue;
+                };
+                *height = Some(value.parse::<u64>().map_err(|_| {
+                    net_error::ClientError(ClientError::Message(format!(
+                        "Failed to parse {}: expected a burnchain block height, got '{}'",
+                        key, value
+                    )))
+                })?);
+            }
+        }
+
+        let (start_height, end_height) = match (start_height, end_height) {
+            (Some(start_height), Some(end_height)) => (start_height, end_height),
+            _ => {
+                return Err(net_error::ClientError(ClientError::Message(
+                    "Block stream requires both start_height and end_height".to_string(),
+                )));
+            }
+        };
+        if start_height > end_height {
+            return Err(net_error::ClientError(ClientError::Message(format!(
+                "Invalid block stream range: start_height {} is above end_height {}",
+                start_height, end_height
+            ))));
+        }
+        if end_height - start_height >= MAX_BLOCK_STREAM_RANGE {
+            return Err(net_error::ClientError(ClientError::Message(format!(
+                "Invalid block stream range: at most {} sortitions may be requested",
+                MAX_BLOCK_STREAM_RANGE
+            ))));
+        }
+        Ok((start_height, end_height))
+    }
+
+    fn parse_getblock_stream<R: Read>(
+        _protocol: &mut StacksHttp,
+        preamble: &HttpRequestPreamble,
+        _captures: &Captures,
+        query: Option<&str>,
+        _fd: &mut R,
+    ) -> Result<HttpRequestType, net_error> {
+        if preamble.get_content_length() != 0 {
+            return Err(net_error::DeserializeError(
+                "Invalid Http request: expected 0-length body for GetBlockStream".to_string(),
+            ));
+        }
+
+        let (start_height, end_height) = HttpRequestType::get_block_stream_range_query(query)?;
+
+        Ok(HttpRequestType::GetBlockStream(
+            HttpRequestMetadata::from_preamble(preamble),
+            start_height,
+            end_height,
+        ))
+    }
+
+    fn parse_getblock_by_height<R: Read>(
+        _protocol: &mut StacksHttp,
+        preamble: &HttpRequestPreamble,
+        captures: &Captures,
+        query: Option<&str>,
+        _fd: &mut R,
+    ) -> Result<HttpRequestType, net_error> {
+        if preamble.get_content_length() != 0 {
+            return Err(net_error::DeserializeError(
+                "Invalid Http request: expected 0-length body for GetBlockByHeight".to_string(),
+            ));
+        }
+
+        let height = captures
+            .get(1)
+            .ok_or(net_error::DeserializeError(
+                "Failed to match path to block height group".to_string(),
+            ))?
+            .as_str()
+            .parse::<u64>()
+            .map_err(|_e| {
+                net_error::DeserializeError("Failed to parse block height".to_string())
+            })?;
+
+        Ok(HttpRequestType::GetBlockByHeight(
+            HttpRequestMetadata::from_preamble(preamble),
+            height,
+            HttpRequestType::get_unanchored_query(query),
+        ))
+    }
+
+    fn parse_getmicroblocks_indexed<R: Read>(
+        _protocol: &mut StacksHttp,
+        preamble: &HttpRequestPreamble,
+        captures: &Captures,
+        _query: Option<&str>,
+        _fd: &mut R,
+    ) -> Result<HttpRequestType, net_error> {
+        if preamble.get_content_length() != 0 {
+            return Err(net_error::DeserializeError(
+                "Invalid Http request: expected 0-length body for GetMicroblocksIndexed"
                     .to_string(),
             ));
         }
@@ -2182,7 +2693,7 @@ impl HttpRequestType {
         _protocol: &mut StacksHttp,
         preamble: &HttpRequestPreamble,
         captures: &Captures,
-        _query: Option<&str>,
+        query: Option<&str>,
         _fd: &mut R,
     ) -> Result<HttpRequestType, net_error> {
         if preamble.get_content_length() != 0 {
@@ -2211,13 +2722,17 @@ impl HttpRequestType {
         })?;
 
         let min_seq = min_seq_str.parse::<u16>().map_err(|_e| {
-            net_error::DeserializeError("Failed to parse microblock minimum sequence".to_string())
+            net_error::ClientError(ClientError::Message(format!(
+                "Failed to parse microblock minimum sequence '{}'",
+                min_seq_str
+            )))
         })?;
 
         Ok(HttpRequestType::GetMicroblocksUnconfirmed(
             HttpRequestMetadata::from_preamble(preamble),
             block_hash,
             min_seq,
+            HttpRequestType::get_microblocks_limit_query(query)?,
         ))
     }
 
@@ -2257,6 +2772,47 @@ impl HttpRequestType {
         ))
     }
 
+    fn parse_get_mempool_transactions_by_address<R: Read>(
+        _protocol: &mut StacksHttp,
+        preamble: &HttpRequestPreamble,
+        captures: &Captures,
+        _query: Option<&str>,
+        _fd: &mut R,
+    ) -> Result<HttpRequestType, net_error> {
+        if preamble.get_content_length() != 0 {
+            return Err(net_error::DeserializeError(
+                "Invalid Http request: expected 0-length body for GetMempoolTransactionsByAddress"
+                    .to_string(),
+            ));
+        }
+
+        let address = StacksAddress::from_string(&captures["address"])
+            .ok_or_else(|| net_error::DeserializeError("Failed to parse address".into()))?;
+
+        Ok(HttpRequestType::GetMempoolTransactionsByAddress(
+            HttpRequestMetadata::from_preamble(preamble),
+            address,
+        ))
+    }
+
+    fn parse_get_mempool_stats<R: Read>(
+        _protocol: &mut StacksHttp,
+        preamble: &HttpRequestPreamble,
+        _captures: &Captures,
+        _query: Option<&str>,
+        _fd: &mut R,
+    ) -> Result<HttpRequestType, net_error> {
+        if preamble.get_content_length() != 0 {
+            return Err(net_error::DeserializeError(
+                "Invalid Http request: expected 0-length body for GetMempoolStats".to_string(),
+            ));
+        }
+
+        Ok(HttpRequestType::GetMempoolStats(
+            HttpRequestMetadata::from_preamble(preamble),
+        ))
+    }
+
     fn parse_post_fee_rate_estimate<R: Read>(
         _protocol: &mut StacksHttp,
         preamble: &HttpRequestPreamble,
@@ -2354,13 +2910,102 @@ impl HttpRequestType {
         }
     }
 
+    fn parse_posttransaction_dry_run<R: Read>(
+        protocol: &mut StacksHttp,
+        preamble: &HttpRequestPreamble,
+        regex: &Captures,
+        query: Option<&str>,
+        fd: &mut R,
+    ) -> Result<HttpRequestType, net_error> {
+        // the body is the same as that of a transaction broadcast
+        let tx = match HttpRequestType::parse_posttransaction(protocol, preamble, regex, query, fd)?
+        {
+            HttpRequestType::PostTransaction(_, tx, None) => tx,
+            HttpRequestType::PostTransaction(_, _, Some(_)) => {
+                return Err(net_error::DeserializeError(
+                    "Invalid Http request: PostTransactionDryRun does not take an attachment"
+                        .to_string(),
+                ));
+            }
+            _ => {
+                return Err(net_error::DeserializeError(
+                    "Invalid Http request: expected a transaction for PostTransactionDryRun"
+                        .to_string(),
+                ));
+            }
+        };
+
+        Ok(HttpRequestType::PostTransactionDryRun(
+            HttpRequestMetadata::from_preamble(preamble),
+            tx,
+            HttpRequestType::get_chain_tip_query(query),
+        ))
+    }
+
+    fn parse_posttransaction_batch<R: Read>(
+        _protocol: &mut StacksHttp,
+        preamble: &HttpRequestPreamble,
+        _regex: &Captures,
+        _query: Option<&str>,
+        fd: &mut R,
+    ) -> Result<HttpRequestType, net_error> {
+        if preamble.get_content_length() == 0 {
+            return Err(net_error::DeserializeError(
+                "Invalid Http request: expected non-zero-length body for PostTransactionBatch"
+                    .to_string(),
+            ));
+        }
+
+        if preamble.get_content_length() > MAX_PAYLOAD_LEN {
+            return Err(net_error::DeserializeError(
+                "Invalid Http request: PostTransactionBatch body is too big".to_string(),
+            ));
+        }
+
+        match preamble.content_type {
+            Some(HttpContentType::JSON) => {}
+            _ => {
+                return Err(net_error::DeserializeError(
+                    "Wrong Content-Type for transaction batch; expected application/json"
+                        .to_string(),
+                ));
+            }
+        }
+
+        let mut bound_fd = BoundReader::from_reader(fd, preamble.get_content_length() as u64);
+        let body: PostTransactionBatchRequestBody = serde_json::from_reader(&mut bound_fd)
+            .map_err(|_e| net_error::DeserializeError("Failed to parse body".into()))?;
+
+        if body.txs.len() > MAX_TRANSACTION_BATCH_LEN {
+            return Err(net_error::ClientError(ClientError::Message(format!(
+                "Too many transactions in batch: {} > {}",
+                body.txs.len(),
+                MAX_TRANSACTION_BATCH_LEN
+            ))));
+        }
+
+        // each transaction is only decoded when it is submitted, so that one malformed
+        // transaction does not cause the rest of the batch to be rejected
+        let txs = body
+            .txs
+            .iter()
+            .map(|tx_hex| hex_bytes(tx_hex))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_e| net_error::DeserializeError("Failed to parse tx".into()))?;
+
+        Ok(HttpRequestType::PostTransactionBatch(
+            HttpRequestMetadata::from_preamble(preamble),
+            txs,
+        ))
+    }
+
     fn parse_posttransaction_octets<R: Read>(
         preamble: &HttpRequestPreamble,
         fd: &mut R,
     ) -> Result<HttpRequestType, net_error> {
         let tx = StacksTransaction::consensus_deserialize(fd).map_err(|e| {
             if let codec_error::DeserializeError(msg) = e {
-                net_error::ClientError(ClientError::Message(format!(
+                net_error::ClientError(ClientError::MalformedTransaction(format!(
                     "Failed to deserialize posted transaction: {}",
                     msg
                 )))
@@ -2383,11 +3028,14 @@ impl HttpRequestType {
             .map_err(|_e| net_error::DeserializeError("Failed to parse body".into()))?;
 
         let tx = {
-            let tx_bytes = hex_bytes(&body.tx)
-                .map_err(|_e| net_error::DeserializeError("Failed to parse tx".into()))?;
+            let tx_bytes = hex_bytes(&body.tx).map_err(|_e| {
+                net_error::ClientError(ClientError::MalformedTransaction(
+                    "Failed to decode hex-encoded transaction".into(),
+                ))
+            })?;
             StacksTransaction::consensus_deserialize(&mut &tx_bytes[..]).map_err(|e| {
                 if let codec_error::DeserializeError(msg) = e {
-                    net_error::ClientError(ClientError::Message(format!(
+                    net_error::ClientError(ClientError::MalformedTransaction(format!(
                         "Failed to deserialize posted transaction: {}",
                         msg
                     )))
@@ -2472,6 +3120,52 @@ impl HttpRequestType {
         ))
     }
 
+    fn parse_postblock_validate<R: Read>(
+        _protocol: &mut StacksHttp,
+        preamble: &HttpRequestPreamble,
+        _regex: &Captures,
+        _query: Option<&str>,
+        fd: &mut R,
+    ) -> Result<HttpRequestType, net_error> {
+        if preamble.get_content_length() == 0 {
+            return Err(net_error::DeserializeError(
+                "Invalid Http request: expected non-zero-length body for PostBlockValidate"
+                    .to_string(),
+            ));
+        }
+
+        if preamble.get_content_length() > MAX_PAYLOAD_LEN {
+            return Err(net_error::DeserializeError(
+                "Invalid Http request: PostBlockValidate body is too big".to_string(),
+            ));
+        }
+
+        // content-type must be given, and must be application/octet-stream
+        match preamble.content_type {
+            None => {
+                return Err(net_error::DeserializeError(
+                    "Missing Content-Type for Stacks block".to_string(),
+                ));
+            }
+            Some(ref c) => {
+                if *c != HttpContentType::Bytes {
+                    return Err(net_error::DeserializeError(
+                        "Wrong Content-Type for Stacks block; expected application/octet-stream"
+                            .to_string(),
+                    ));
+                }
+            }
+        };
+
+        let mut bound_fd = BoundReader::from_reader(fd, preamble.get_content_length() as u64);
+        let stacks_block = StacksBlock::consensus_deserialize(&mut bound_fd)?;
+
+        Ok(HttpRequestType::PostBlockValidate(
+            HttpRequestMetadata::from_preamble(preamble),
+            stacks_block,
+        ))
+    }
+
     fn parse_postmicroblock<R: Read>(
         _protocol: &mut StacksHttp,
         preamble: &HttpRequestPreamble,
@@ -2521,6 +3215,58 @@ impl HttpRequestType {
         ))
     }
 
+    fn parse_postmicroblocks_validate<R: Read>(
+        _protocol: &mut StacksHttp,
+        preamble: &HttpRequestPreamble,
+        _regex: &Captures,
+        _query: Option<&str>,
+        fd: &mut R,
+    ) -> Result<HttpRequestType, net_error> {
+        if preamble.get_content_length() == 0 {
+            return Err(net_error::DeserializeError(
+                "Invalid Http request: expected non-zero-length body for PostMicroblocksValidate"
+                    .to_string(),
+            ));
+        }
+
+        if preamble.get_content_length() > MAX_PAYLOAD_LEN {
+            return Err(net_error::DeserializeError(
+                "Invalid Http request: PostMicroblocksValidate body is too big".to_string(),
+            ));
+        }
+
+        // content-type must be given, and must be application/octet-stream
+        match preamble.content_type {
+            None => {
+                return Err(net_error::DeserializeError(
+                    "Missing Content-Type for microblock stream".to_string(),
+                ));
+            }
+            Some(ref c) => {
+                if *c != HttpContentType::Bytes {
+                    return Err(net_error::DeserializeError(
+                        "Wrong Content-Type for microblock stream; expected application/octet-stream"
+                            .to_string(),
+                    ));
+                }
+            }
+        };
+
+        // the body is a length-prefixed list of microblocks
+        let mut bound_fd = BoundReader::from_reader(fd, preamble.get_content_length() as u64);
+        let microblocks: Vec<StacksMicroblock> = read_next(&mut bound_fd)?;
+        if microblocks.len() == 0 {
+            return Err(net_error::DeserializeError(
+                "Invalid Http request: expected at least one microblock".to_string(),
+            ));
+        }
+
+        Ok(HttpRequestType::PostMicroblocksValidate(
+            HttpRequestMetadata::from_preamble(preamble),
+            microblocks,
+        ))
+    }
+
     fn parse_get_attachment<R: Read>(
         _protocol: &mut StacksHttp,
         preamble: &HttpRequestPreamble,
@@ -2681,20 +3427,35 @@ impl HttpRequestType {
         match *self {
             HttpRequestType::GetInfo(ref md) => md,
             HttpRequestType::GetPoxInfo(ref md, ..) => md,
+            HttpRequestType::GetPoxRewardCycle(ref md, ..) => md,
+            HttpRequestType::GetPoxSunsetBurn(ref md, ..) => md,
+            HttpRequestType::GetBlockCommitByHeight(ref md, ..) => md,
+            HttpRequestType::GetBlockCommitByStacksBlock(ref md, ..) => md,
             HttpRequestType::GetNeighbors(ref md) => md,
             HttpRequestType::GetHeaders(ref md, ..) => md,
             HttpRequestType::GetBlock(ref md, _) => md,
+            HttpRequestType::GetBlockStream(ref md, ..) => md,
+            HttpRequestType::GetBlockByHeight(ref md, ..) => md,
             HttpRequestType::GetMicroblocksIndexed(ref md, _) => md,
             HttpRequestType::GetMicroblocksConfirmed(ref md, _) => md,
-            HttpRequestType::GetMicroblocksUnconfirmed(ref md, _, _) => md,
+            HttpRequestType::GetMicroblocksUnconfirmed(ref md, ..) => md,
             HttpRequestType::GetTransactionUnconfirmed(ref md, _) => md,
+            HttpRequestType::GetMempoolTransactionsByAddress(ref md, _) => md,
+            HttpRequestType::GetMempoolStats(ref md) => md,
             HttpRequestType::PostTransaction(ref md, _, _) => md,
+            HttpRequestType::PostTransactionDryRun(ref md, _, _) => md,
+            HttpRequestType::PostTransactionBatch(ref md, _) => md,
             HttpRequestType::PostBlock(ref md, ..) => md,
+            HttpRequestType::PostBlockValidate(ref md, ..) => md,
             HttpRequestType::PostMicroblock(ref md, ..) => md,
+            HttpRequestType::PostMicroblocksValidate(ref md, ..) => md,
             HttpRequestType::GetAccount(ref md, ..) => md,
+            HttpRequestType::GetAccountStacking(ref md, ..) => md,
             HttpRequestType::GetDataVar(ref md, ..) => md,
             HttpRequestType::GetMapEntry(ref md, ..) => md,
             HttpRequestType::GetTransferCost(ref md) => md,
+            HttpRequestType::GetFeeRate(ref md) => md,
+            HttpRequestType::GetFeeRatePercentiles(ref md, _) => md,
             HttpRequestType::GetContractABI(ref md, ..) => md,
             HttpRequestType::GetContractSrc(ref md, ..) => md,
             HttpRequestType::GetIsTraitImplemented(ref md, ..) => md,
@@ -2712,20 +3473,35 @@ impl HttpRequestType {
         match *self {
             HttpRequestType::GetInfo(ref mut md) => md,
             HttpRequestType::GetPoxInfo(ref mut md, ..) => md,
+            HttpRequestType::GetPoxRewardCycle(ref mut md, ..) => md,
+            HttpRequestType::GetPoxSunsetBurn(ref mut md, ..) => md,
+            HttpRequestType::GetBlockCommitByHeight(ref mut md, ..) => md,
+            HttpRequestType::GetBlockCommitByStacksBlock(ref mut md, ..) => md,
             HttpRequestType::GetNeighbors(ref mut md) => md,
             HttpRequestType::GetHeaders(ref mut md, ..) => md,
             HttpRequestType::GetBlock(ref mut md, _) => md,
+            HttpRequestType::GetBlockStream(ref mut md, ..) => md,
+            HttpRequestType::GetBlockByHeight(ref mut md, ..) => md,
             HttpRequestType::GetMicroblocksIndexed(ref mut md, _) => md,
             HttpRequestType::GetMicroblocksConfirmed(ref mut md, _) => md,
-            HttpRequestType::GetMicroblocksUnconfirmed(ref mut md, _, _) => md,
+            HttpRequestType::GetMicroblocksUnconfirmed(ref mut md, ..) => md,
             HttpRequestType::GetTransactionUnconfirmed(ref mut md, _) => md,
+            HttpRequestType::GetMempoolTransactionsByAddress(ref mut md, _) => md,
+            HttpRequestType::GetMempoolStats(ref mut md) => md,
             HttpRequestType::PostTransaction(ref mut md, _, _) => md,
+            HttpRequestType::PostTransactionDryRun(ref mut md, _, _) => md,
+            HttpRequestType::PostTransactionBatch(ref mut md, _) => md,
             HttpRequestType::PostBlock(ref mut md, ..) => md,
+            HttpRequestType::PostBlockValidate(ref mut md, ..) => md,
             HttpRequestType::PostMicroblock(ref mut md, ..) => md,
+            HttpRequestType::PostMicroblocksValidate(ref mut md, ..) => md,
             HttpRequestType::GetAccount(ref mut md, ..) => md,
+            HttpRequestType::GetAccountStacking(ref mut md, ..) => md,
             HttpRequestType::GetDataVar(ref mut md, ..) => md,
             HttpRequestType::GetMapEntry(ref mut md, ..) => md,
             HttpRequestType::GetTransferCost(ref mut md) => md,
+            HttpRequestType::GetFeeRate(ref mut md) => md,
+            HttpRequestType::GetFeeRatePercentiles(ref mut md, _) => md,
             HttpRequestType::GetContractABI(ref mut md, ..) => md,
             HttpRequestType::GetContractSrc(ref mut md, ..) => md,
             HttpRequestType::GetIsTraitImplemented(ref mut md, ..) => md,
@@ -2747,6 +3523,16 @@ impl HttpRequestType {
             TipRequest::SpecificTip(tip) => {
                 format!("?tip={}{}", tip, if with_proof { "" } else { "&proof=0" })
             }
+            TipRequest::CanonicalAncestorAtHeight(height) => format!(
+                "?until_block={}{}",
+                height,
+                if with_proof { "" } else { "&proof=0" }
+            ),
+            TipRequest::CanonicalAncestor(block_id) => format!(
+                "?until_block={}{}",
+                block_id,
+                if with_proof { "" } else { "&proof=0" }
+            ),
             TipRequest::UseLatestAnchoredTip => {
                 if !with_proof {
                     format!("?proof=0")
@@ -2764,6 +3550,20 @@ impl HttpRequestType {
                 "/v2/pox{}",
                 HttpRequestType::make_tip_query_string(tip_req, true)
             ),
+            HttpRequestType::GetPoxRewardCycle(_md, burn_height, tip_req) => format!(
+                "/v2/pox/reward-cycle/{}{}",
+                burn_height,
+                HttpRequestType::make_tip_query_string(tip_req, true)
+            ),
+            HttpRequestType::GetPoxSunsetBurn(_md, burn_height, burn_fee_cap) => {
+                format!("/v2/pox/sunset-burn/{}/{}", burn_height, burn_fee_cap)
+            }
+            HttpRequestType::GetBlockCommitByHeight(_md, burn_height) => {
+                format!("/v2/block-commits/{}", burn_height)
+            }
+            HttpRequestType::GetBlockCommitByStacksBlock(_md, block_hash) => {
+                format!("/v2/block-commits/stacks-block/{}", block_hash.to_hex())
+            }
             HttpRequestType::GetNeighbors(_md) => "/v2/neighbors".to_string(),
             HttpRequestType::GetHeaders(_md, quantity, tip_req) => format!(
                 "/v2/headers/{}{}",
@@ -2773,26 +3573,52 @@ impl HttpRequestType {
             HttpRequestType::GetBlock(_md, block_hash) => {
                 format!("/v2/blocks/{}", block_hash.to_hex())
             }
+            HttpRequestType::GetBlockStream(_md, start_height, end_height) => format!(
+                "/v2/blocks/stream?start_height={}&end_height={}",
+                start_height, end_height
+            ),
+            HttpRequestType::GetBlockByHeight(_md, height, unanchored) => format!(
+                "/v2/blocks/height/{}{}",
+                height,
+                if *unanchored { "?unanchored=true" } else { "" }
+            ),
             HttpRequestType::GetMicroblocksIndexed(_md, block_hash) => {
                 format!("/v2/microblocks/{}", block_hash.to_hex())
             }
             HttpRequestType::GetMicroblocksConfirmed(_md, block_hash) => {
                 format!("/v2/microblocks/confirmed/{}", block_hash.to_hex())
             }
-            HttpRequestType::GetMicroblocksUnconfirmed(_md, block_hash, min_seq) => format!(
-                "/v2/microblocks/unconfirmed/{}/{}",
-                block_hash.to_hex(),
-                min_seq
-            ),
+            HttpRequestType::GetMicroblocksUnconfirmed(_md, block_hash, min_seq, limit) => {
+                format!(
+                    "/v2/microblocks/unconfirmed/{}/{}{}",
+                    block_hash.to_hex(),
+                    min_seq,
+                    match limit {
+                        Some(limit) => format!("?limit={}", limit),
+                        None => "".to_string(),
+                    }
+                )
+            }
             HttpRequestType::GetTransactionUnconfirmed(_md, txid) => {
                 format!("/v2/transactions/unconfirmed/{}", txid)
             }
+            HttpRequestType::GetMempoolTransactionsByAddress(_md, address) => {
+                format!("/v2/mempool/transactions/{}", address)
+            }
+            HttpRequestType::GetMempoolStats(_md) => "/v2/mempool/stats".to_string(),
             HttpRequestType::PostTransaction(_md, ..) => "/v2/transactions".to_string(),
+            HttpRequestType::PostTransactionDryRun(_md, _, tip_req) => format!(
+                "/v2/transactions/dry-run{}",
+                HttpRequestType::make_tip_query_string(tip_req, true)
+            ),
+            HttpRequestType::PostTransactionBatch(_md, _) => "/v2/transactions/batch".to_string(),
             HttpRequestType::PostBlock(_md, ch, ..) => format!("/v2/blocks/upload/{}", &ch),
+            HttpRequestType::PostBlockValidate(..) => "/v2/blocks/validate".to_string(),
             HttpRequestType::PostMicroblock(_md, _, tip_req) => format!(
                 "/v2/microblocks{}",
                 HttpRequestType::make_tip_query_string(tip_req, true)
             ),
+            HttpRequestType::PostMicroblocksValidate(..) => "/v2/microblocks/validate".to_string(),
             HttpRequestType::GetAccount(_md, principal, tip_req, with_proof) => {
                 format!(
                     "/v2/accounts/{}{}",
@@ -2800,6 +3626,11 @@ impl HttpRequestType {
                     HttpRequestType::make_tip_query_string(tip_req, *with_proof,)
                 )
             }
+            HttpRequestType::GetAccountStacking(_md, principal, tip_req) => format!(
+                "/v2/accounts/{}/stacking{}",
+                &principal.to_string(),
+                HttpRequestType::make_tip_query_string(tip_req, true)
+            ),
             HttpRequestType::GetDataVar(
                 _md,
                 contract_addr,
@@ -2830,6 +3661,15 @@ impl HttpRequestType {
                 HttpRequestType::make_tip_query_string(tip_req, *with_proof)
             ),
             HttpRequestType::GetTransferCost(_md) => "/v2/fees/transfer".into(),
+            HttpRequestType::GetFeeRate(_md) => "/v2/fees/rate".into(),
+            HttpRequestType::GetFeeRatePercentiles(_md, percentiles) => format!(
+                "/v2/fees/rate/percentiles?percentiles={}",
+                percentiles
+                    .iter()
+                    .map(|percentile| percentile.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
             HttpRequestType::GetContractABI(_, contract_addr, contract_name, tip_req) => format!(
                 "/v2/contracts/interface/{}/{}{}",
                 contract_addr,
@@ -2916,22 +3756,41 @@ impl HttpRequestType {
         match self {
             HttpRequestType::GetInfo(..) => "/v2/info",
             HttpRequestType::GetPoxInfo(..) => "/v2/pox",
+            HttpRequestType::GetPoxRewardCycle(..) => "/v2/pox/reward-cycle/:height",
+            HttpRequestType::GetPoxSunsetBurn(..) => "/v2/pox/sunset-burn/:height/:burn_fee_cap",
+            HttpRequestType::GetBlockCommitByHeight(..) => "/v2/block-commits/:height",
+            HttpRequestType::GetBlockCommitByStacksBlock(..) => {
+                "/v2/block-commits/stacks-block/:hash"
+            }
             HttpRequestType::GetNeighbors(..) => "/v2/neighbors",
             HttpRequestType::GetHeaders(..) => "/v2/headers/:height",
             HttpRequestType::GetBlock(..) => "/v2/blocks/:hash",
+            HttpRequestType::GetBlockStream(..) => "/v2/blocks/stream",
+            HttpRequestType::GetBlockByHeight(..) => "/v2/blocks/height/:height",
             HttpRequestType::GetMicroblocksIndexed(..) => "/v2/microblocks/:hash",
             HttpRequestType::GetMicroblocksConfirmed(..) => "/v2/microblocks/confirmed/:hash",
             HttpRequestType::GetMicroblocksUnconfirmed(..) => {
                 "/v2/microblocks/unconfirmed/:hash/:seq"
             }
             HttpRequestType::GetTransactionUnconfirmed(..) => "/v2/transactions/unconfirmed/:txid",
+            HttpRequestType::GetMempoolTransactionsByAddress(..) => {
+                "/v2/mempool/transactions/:address"
+            }
+            HttpRequestType::GetMempoolStats(..) => "/v2/mempool/stats",
             HttpRequestType::PostTransaction(..) => "/v2/transactions",
+            HttpRequestType::PostTransactionDryRun(..) => "/v2/transactions/dry-run",
+            HttpRequestType::PostTransactionBatch(..) => "/v2/transactions/batch",
             HttpRequestType::PostBlock(..) => "/v2/blocks/upload/:block",
+            HttpRequestType::PostBlockValidate(..) => "/v2/blocks/validate",
             HttpRequestType::PostMicroblock(..) => "/v2/microblocks",
+            HttpRequestType::PostMicroblocksValidate(..) => "/v2/microblocks/validate",
             HttpRequestType::GetAccount(..) => "/v2/accounts/:principal",
+            HttpRequestType::GetAccountStacking(..) => "/v2/accounts/:principal/stacking",
             HttpRequestType::GetDataVar(..) => "/v2/data_var/:principal/:contract_name/:var_name",
             HttpRequestType::GetMapEntry(..) => "/v2/map_entry/:principal/:contract_name/:map_name",
             HttpRequestType::GetTransferCost(..) => "/v2/fees/transfer",
+            HttpRequestType::GetFeeRate(..) => "/v2/fees/rate",
+            HttpRequestType::GetFeeRatePercentiles(..) => "/v2/fees/rate/percentiles",
             HttpRequestType::GetContractABI(..) => {
                 "/v2/contracts/interface/:principal/:contract_name"
             }
@@ -2994,7 +3853,52 @@ impl HttpRequestType {
                 fd.write_all(&request_body_bytes)
                     .map_err(net_error::WriteError)?;
             }
-            HttpRequestType::PostBlock(md, _ch, block) => {
+            HttpRequestType::PostTransactionDryRun(md, tx, _) => {
+                let mut tx_bytes = vec![];
+                write_next(&mut tx_bytes, tx)?;
+
+                HttpRequestPreamble::new_serialized(
+                    fd,
+                    &md.version,
+                    "POST",
+                    &self.request_path(),
+                    &md.peer,
+                    md.keep_alive,
+                    Some(tx_bytes.len() as u32),
+                    Some(&HttpContentType::Bytes),
+                    |fd| stacks_height_headers(fd, md),
+                )?;
+                fd.write_all(&tx_bytes).map_err(net_error::WriteError)?;
+            }
+            HttpRequestType::PostTransactionBatch(md, txs) => {
+                let request_body = PostTransactionBatchRequestBody {
+                    txs: txs.iter().map(|tx_bytes| to_hex(&tx_bytes[..])).collect(),
+                };
+
+                let mut request_body_bytes = vec![];
+                serde_json::to_writer(&mut request_body_bytes, &request_body).map_err(|e| {
+                    net_error::SerializeError(format!(
+                        "Failed to serialize transaction batch to JSON: {:?}",
+                        &e
+                    ))
+                })?;
+
+                HttpRequestPreamble::new_serialized(
+                    fd,
+                    &md.version,
+                    "POST",
+                    &self.request_path(),
+                    &md.peer,
+                    md.keep_alive,
+                    Some(request_body_bytes.len() as u32),
+                    Some(&HttpContentType::JSON),
+                    |fd| stacks_height_headers(fd, md),
+                )?;
+                fd.write_all(&request_body_bytes)
+                    .map_err(net_error::WriteError)?;
+            }
+            HttpRequestType::PostBlock(md, _ch, block)
+            | HttpRequestType::PostBlockValidate(md, block) => {
                 let mut block_bytes = vec![];
                 write_next(&mut block_bytes, block)?;
 
@@ -3011,9 +3915,26 @@ impl HttpRequestType {
                 )?;
                 fd.write_all(&block_bytes).map_err(net_error::WriteError)?;
             }
-            HttpRequestType::PostMicroblock(md, mb, ..) => {
-                let mut mb_bytes = vec![];
-                write_next(&mut mb_bytes, mb)?;
+            HttpRequestType::PostMicroblock(md, mb, ..) => {
+                let mut mb_bytes = vec![];
+                write_next(&mut mb_bytes, mb)?;
+
+                HttpRequestPreamble::new_serialized(
+                    fd,
+                    &md.version,
+                    "POST",
+                    &self.request_path(),
+                    &md.peer,
+                    md.keep_alive,
+                    Some(mb_bytes.len() as u32),
+                    Some(&HttpContentType::Bytes),
+                    |fd| stacks_height_headers(fd, md),
+                )?;
+                fd.write_all(&mb_bytes).map_err(net_error::WriteError)?;
+            }
+            HttpRequestType::PostMicroblocksValidate(md, mblocks) => {
+                let mut mblocks_bytes = vec![];
+                write_next(&mut mblocks_bytes, mblocks)?;
 
                 HttpRequestPreamble::new_serialized(
                     fd,
@@ -3022,11 +3943,12 @@ impl HttpRequestType {
                     &self.request_path(),
                     &md.peer,
                     md.keep_alive,
-                    Some(mb_bytes.len() as u32),
+                    Some(mblocks_bytes.len() as u32),
                     Some(&HttpContentType::Bytes),
                     |fd| stacks_height_headers(fd, md),
                 )?;
-                fd.write_all(&mb_bytes).map_err(net_error::WriteError)?;
+                fd.write_all(&mblocks_bytes)
+                    .map_err(net_error::WriteError)?;
             }
             HttpRequestType::GetMapEntry(
                 md,
@@ -3197,6 +4119,7 @@ impl HttpResponseType {
             402 => HttpResponseType::PaymentRequired(md, error_text),
             403 => HttpResponseType::Forbidden(md, error_text),
             404 => HttpResponseType::NotFound(md, error_text),
+            409 => HttpResponseType::Conflict(md, error_text),
             500 => HttpResponseType::ServerError(md, error_text),
             503 => HttpResponseType::ServiceUnavailable(md, error_text),
             _ => HttpResponseType::Error(md, preamble.status_code, error_text),
@@ -3364,9 +4287,30 @@ impl HttpResponseType {
         )] = &[
             (&PATH_GETINFO, &HttpResponseType::parse_peerinfo),
             (&PATH_GETPOXINFO, &HttpResponseType::parse_poxinfo),
+            (
+                &PATH_GET_POX_REWARD_CYCLE,
+                &HttpResponseType::parse_pox_reward_cycle,
+            ),
+            (
+                &PATH_GET_POX_SUNSET_BURN,
+                &HttpResponseType::parse_pox_sunset_burn,
+            ),
+            (
+                &PATH_GET_BLOCK_COMMIT_BY_HEIGHT,
+                &HttpResponseType::parse_block_commit_info,
+            ),
+            (
+                &PATH_GET_BLOCK_COMMIT_BY_STACKS_BLOCK,
+                &HttpResponseType::parse_block_commit_info,
+            ),
             (&PATH_GETNEIGHBORS, &HttpResponseType::parse_neighbors),
             (&PATH_GETHEADERS, &HttpResponseType::parse_headers),
             (&PATH_GETBLOCK, &HttpResponseType::parse_block),
+            (&PATH_GETBLOCK_STREAM, &HttpResponseType::parse_block_range),
+            (
+                &PATH_GETBLOCK_BY_HEIGHT,
+                &HttpResponseType::parse_block_by_height,
+            ),
             (&PATH_GET_DATA_VAR, &HttpResponseType::parse_get_data_var),
             (&PATH_GET_MAP_ENTRY, &HttpResponseType::parse_get_map_entry),
             (
@@ -3386,15 +4330,35 @@ impl HttpResponseType {
                 &HttpResponseType::parse_transaction_unconfirmed,
             ),
             (&PATH_POSTTRANSACTION, &HttpResponseType::parse_txid),
+            (
+                &PATH_POSTTRANSACTION_DRY_RUN,
+                &HttpResponseType::parse_transaction_dry_run,
+            ),
+            (
+                &PATH_POSTTRANSACTION_BATCH,
+                &HttpResponseType::parse_transaction_batch,
+            ),
             (
                 &PATH_POSTBLOCK,
                 &HttpResponseType::parse_stacks_block_accepted,
             ),
+            (
+                &PATH_POSTBLOCK_VALIDATE,
+                &HttpResponseType::parse_block_proposal_validation,
+            ),
             (
                 &PATH_POSTMICROBLOCK,
                 &HttpResponseType::parse_microblock_hash,
             ),
+            (
+                &PATH_POSTMICROBLOCKS_VALIDATE,
+                &HttpResponseType::parse_microblock_stream_validation,
+            ),
             (&PATH_GET_ACCOUNT, &HttpResponseType::parse_get_account),
+            (
+                &PATH_GET_ACCOUNT_STACKING,
+                &HttpResponseType::parse_get_account_stacking,
+            ),
             (
                 &PATH_GET_CONTRACT_SRC,
                 &HttpResponseType::parse_get_contract_src,
@@ -3423,6 +4387,18 @@ impl HttpResponseType {
                 &PATH_POST_MEMPOOL_QUERY,
                 &HttpResponseType::parse_post_mempool_query,
             ),
+            (
+                &PATH_GET_MEMPOOL_TRANSACTIONS_BY_ADDRESS,
+                &HttpResponseType::parse_mempool_transactions_by_address,
+            ),
+            (
+                &PATH_GET_MEMPOOL_STATS,
+                &HttpResponseType::parse_mempool_stats,
+            ),
+            (
+                &PATH_GET_FEE_RATE_PERCENTILES,
+                &HttpResponseType::parse_fee_rate_percentiles,
+            ),
         ];
 
         // use url::Url to parse path and query string
@@ -3501,6 +4477,51 @@ impl HttpResponseType {
         ))
     }
 
+    fn parse_pox_reward_cycle<R: Read>(
+        _protocol: &mut StacksHttp,
+        request_version: HttpVersion,
+        preamble: &HttpResponsePreamble,
+        fd: &mut R,
+        len_hint: Option<usize>,
+    ) -> Result<HttpResponseType, net_error> {
+        let reward_cycle =
+            HttpResponseType::parse_json(preamble, fd, len_hint, MAX_MESSAGE_LEN as u64)?;
+        Ok(HttpResponseType::PoxRewardCycle(
+            HttpResponseMetadata::from_preamble(request_version, preamble),
+            reward_cycle,
+        ))
+    }
+
+    fn parse_pox_sunset_burn<R: Read>(
+        _protocol: &mut StacksHttp,
+        request_version: HttpVersion,
+        preamble: &HttpResponsePreamble,
+        fd: &mut R,
+        len_hint: Option<usize>,
+    ) -> Result<HttpResponseType, net_error> {
+        let sunset_burn =
+            HttpResponseType::parse_json(preamble, fd, len_hint, MAX_MESSAGE_LEN as u64)?;
+        Ok(HttpResponseType::PoxSunsetBurn(
+            HttpResponseMetadata::from_preamble(request_version, preamble),
+            sunset_burn,
+        ))
+    }
+
+    fn parse_block_commit_info<R: Read>(
+        _protocol: &mut StacksHttp,
+        request_version: HttpVersion,
+        preamble: &HttpResponsePreamble,
+        fd: &mut R,
+        len_hint: Option<usize>,
+    ) -> Result<HttpResponseType, net_error> {
+        let block_commit_info =
+            HttpResponseType::parse_json(preamble, fd, len_hint, MAX_MESSAGE_LEN as u64)?;
+        Ok(HttpResponseType::BlockCommitInfo(
+            HttpResponseMetadata::from_preamble(request_version, preamble),
+            block_commit_info,
+        ))
+    }
+
     fn parse_neighbors<R: Read>(
         _protocol: &mut StacksHttp,
         request_version: HttpVersion,
@@ -3546,6 +4567,177 @@ impl HttpResponseType {
         ))
     }
 
+    /// Decode a stream of blocks, each prefixed with its 4-byte big-endian length, until EOF.
+    fn decode_block_range_stream<R: Read>(fd: &mut R) -> Result<Vec<StacksBlock>, net_error> {
+        let mut blocks = vec![];
+        loop {
+            let mut len_buf = [0u8; 4];
+            match fd.read(&mut len_buf[0..1]) {
+                Ok(0) => {
+                    // end of stream -- this is fine
+                    break;
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    if e.kind() == io::ErrorKind::Interrupted {
+                        continue;
+                    }
+                    return Err(net_error::ReadError(e));
+                }
+            }
+            fd.read_exact(&mut len_buf[1..])
+                .map_err(net_error::ReadError)?;
+
+            let block_len = u32::from_be_bytes(len_buf);
+            if block_len == 0 || block_len > MAX_BLOCK_LEN {
+                return Err(net_error::DeserializeError(format!(
+                    "Invalid block length {} in block stream",
+                    block_len
+                )));
+            }
+            if (blocks.len() as u64) >= MAX_BLOCK_STREAM_RANGE {
+                return Err(net_error::DeserializeError(
+                    "Too many blocks in block stream".to_string(),
+                ));
+            }
+
+            let mut block_bytes = vec![0u8; block_len as usize];
+            fd.read_exact(&mut block_bytes)
+                .map_err(net_error::ReadError)?;
+
+            let mut block_ptr = &block_bytes[..];
+            let block = StacksBlock::consensus_deserialize(&mut block_ptr)?;
+            if block_ptr.len() > 0 {
+                return Err(net_error::DeserializeError(
+                    "Block length prefix does not match block in block stream".to_string(),
+                ));
+            }
+            blocks.push(block);
+        }
+        Ok(blocks)
+    }
+
+    fn parse_block_range<R: Read>(
+        _protocol: &mut StacksHttp,
+        request_version: HttpVersion,
+        preamble: &HttpResponsePreamble,
+        fd: &mut R,
+        len_hint: Option<usize>,
+    ) -> Result<HttpResponseType, net_error> {
+        // content-type has to be Bytes
+        if preamble.content_type != HttpContentType::Bytes {
+            return Err(net_error::DeserializeError(
+                "Invalid content-type: expected application/octet-stream".to_string(),
+            ));
+        }
+
+        let max_len = MAX_BLOCK_STREAM_RANGE * (4 + MAX_BLOCK_LEN as u64);
+        let blocks = if preamble.is_chunked() && len_hint.is_none() {
+            let mut chunked_fd = HttpChunkedTransferReader::from_reader(fd, max_len);
+            HttpResponseType::decode_block_range_stream(&mut chunked_fd)?
+        } else {
+            let content_length = match (preamble.content_length, len_hint) {
+                (Some(l), _) => l as u64,
+                (None, Some(l)) => l as u64,
+                (None, None) => {
+                    // unsupported headers
+                    trace!("preamble: {:?}", preamble);
+                    return Err(net_error::DeserializeError(
+                        "Invalid headers: need either Transfer-Encoding or Content-Length"
+                            .to_string(),
+                    ));
+                }
+            };
+            if content_length > max_len {
+                return Err(net_error::DeserializeError(
+                    "Invalid Content-Length header: too long".to_string(),
+                ));
+            }
+            let mut bound_fd = BoundReader::from_reader(fd, content_length);
+            HttpResponseType::decode_block_range_stream(&mut bound_fd)?
+        };
+
+        Ok(HttpResponseType::Blocks(
+            HttpResponseMetadata::from_preamble(request_version, preamble),
+            blocks,
+        ))
+    }
+
+    /// Decode a block, optionally followed by the microblock stream it confirmed.
+    fn decode_block_with_microblocks<R: Read>(
+        fd: &mut R,
+    ) -> Result<(StacksBlock, Option<Vec<StacksMicroblock>>), net_error> {
+        let block: StacksBlock = read_next(fd)?;
+
+        let mut microblock_bytes = vec![];
+        fd.read_to_end(&mut microblock_bytes)
+            .map_err(net_error::ReadError)?;
+        if microblock_bytes.len() == 0 {
+            return Ok((block, None));
+        }
+
+        let mut microblock_ptr = &microblock_bytes[..];
+        let microblocks: Vec<StacksMicroblock> = read_next(&mut microblock_ptr)?;
+        if microblock_ptr.len() > 0 {
+            return Err(net_error::DeserializeError(
+                "Trailing bytes after block and microblock stream".to_string(),
+            ));
+        }
+        Ok((block, Some(microblocks)))
+    }
+
+    fn parse_block_by_height<R: Read>(
+        _protocol: &mut StacksHttp,
+        request_version: HttpVersion,
+        preamble: &HttpResponsePreamble,
+        fd: &mut R,
+        len_hint: Option<usize>,
+    ) -> Result<HttpResponseType, net_error> {
+        // content-type has to be Bytes
+        if preamble.content_type != HttpContentType::Bytes {
+            return Err(net_error::DeserializeError(
+                "Invalid content-type: expected application/octet-stream".to_string(),
+            ));
+        }
+
+        // the block, and possibly a microblock stream
+        let max_len = 2 * (MAX_MESSAGE_LEN as u64);
+        let (block, microblocks_opt) = if preamble.is_chunked() && len_hint.is_none() {
+            let mut chunked_fd = HttpChunkedTransferReader::from_reader(fd, max_len);
+            HttpResponseType::decode_block_with_microblocks(&mut chunked_fd)?
+        } else {
+            let content_length = match (preamble.content_length, len_hint) {
+                (Some(l), _) => l as u64,
+                (None, Some(l)) => l as u64,
+                (None, None) => {
+                    // unsupported headers
+                    trace!("preamble: {:?}", preamble);
+                    return Err(net_error::DeserializeError(
+                        "Invalid headers: need either Transfer-Encoding or Content-Length"
+                            .to_string(),
+                    ));
+                }
+            };
+            if content_length > max_len {
+                return Err(net_error::DeserializeError(
+                    "Invalid Content-Length header: too long".to_string(),
+                ));
+            }
+            let mut bound_fd = BoundReader::from_reader(fd, content_length);
+            HttpResponseType::decode_block_with_microblocks(&mut bound_fd)?
+        };
+
+        let md = HttpResponseMetadata::from_preamble(request_version, preamble);
+        match microblocks_opt {
+            Some(microblocks) => Ok(HttpResponseType::BlockWithMicroblocks(
+                md,
+                block,
+                microblocks,
+            )),
+            None => Ok(HttpResponseType::Block(md, block)),
+        }
+    }
+
     fn parse_microblocks<R: Read>(
         _protocol: &mut StacksHttp,
         request_version: HttpVersion,
@@ -3576,6 +4768,21 @@ impl HttpResponseType {
         ))
     }
 
+    fn parse_get_account_stacking<R: Read>(
+        _protocol: &mut StacksHttp,
+        request_version: HttpVersion,
+        preamble: &HttpResponsePreamble,
+        fd: &mut R,
+        len_hint: Option<usize>,
+    ) -> Result<HttpResponseType, net_error> {
+        let stacking_status =
+            HttpResponseType::parse_json(preamble, fd, len_hint, MAX_MESSAGE_LEN as u64)?;
+        Ok(HttpResponseType::GetAccountStacking(
+            HttpResponseMetadata::from_preamble(request_version, preamble),
+            stacking_status,
+        ))
+    }
+
     fn parse_get_data_var<R: Read>(
         _protocol: &mut StacksHttp,
         request_version: HttpVersion,
@@ -3729,6 +4936,77 @@ impl HttpResponseType {
         ))
     }
 
+    fn parse_mempool_stats<R: Read>(
+        _protocol: &mut StacksHttp,
+        request_version: HttpVersion,
+        preamble: &HttpResponsePreamble,
+        fd: &mut R,
+        len_hint: Option<usize>,
+    ) -> Result<HttpResponseType, net_error> {
+        let stats = HttpResponseType::parse_json(preamble, fd, len_hint, MAX_MESSAGE_LEN as u64)?;
+        Ok(HttpResponseType::MempoolStats(
+            HttpResponseMetadata::from_preamble(request_version, preamble),
+            stats,
+        ))
+    }
+
+    fn parse_fee_rate_percentiles<R: Read>(
+        _protocol: &mut StacksHttp,
+        request_version: HttpVersion,
+        preamble: &HttpResponsePreamble,
+        fd: &mut R,
+        len_hint: Option<usize>,
+    ) -> Result<HttpResponseType, net_error> {
+        let fee_rates =
+            HttpResponseType::parse_json(preamble, fd, len_hint, MAX_MESSAGE_LEN as u64)?;
+        Ok(HttpResponseType::FeeRatePercentiles(
+            HttpResponseMetadata::from_preamble(request_version, preamble),
+            fee_rates,
+        ))
+    }
+
+    fn parse_mempool_transactions_by_address<R: Read>(
+        _protocol: &mut StacksHttp,
+        request_version: HttpVersion,
+        preamble: &HttpResponsePreamble,
+        fd: &mut R,
+        len_hint: Option<usize>,
+    ) -> Result<HttpResponseType, net_error> {
+        let entries = HttpResponseType::parse_json(preamble, fd, len_hint, MAX_MESSAGE_LEN as u64)?;
+        Ok(HttpResponseType::MempoolTransactionsByAddress(
+            HttpResponseMetadata::from_preamble(request_version, preamble),
+            entries,
+        ))
+    }
+
+    fn parse_transaction_dry_run<R: Read>(
+        _protocol: &mut StacksHttp,
+        request_version: HttpVersion,
+        preamble: &HttpResponsePreamble,
+        fd: &mut R,
+        len_hint: Option<usize>,
+    ) -> Result<HttpResponseType, net_error> {
+        let dry_run = HttpResponseType::parse_json(preamble, fd, len_hint, MAX_MESSAGE_LEN as u64)?;
+        Ok(HttpResponseType::TransactionDryRun(
+            HttpResponseMetadata::from_preamble(request_version, preamble),
+            dry_run,
+        ))
+    }
+
+    fn parse_transaction_batch<R: Read>(
+        _protocol: &mut StacksHttp,
+        request_version: HttpVersion,
+        preamble: &HttpResponsePreamble,
+        fd: &mut R,
+        len_hint: Option<usize>,
+    ) -> Result<HttpResponseType, net_error> {
+        let entries = HttpResponseType::parse_json(preamble, fd, len_hint, MAX_MESSAGE_LEN as u64)?;
+        Ok(HttpResponseType::TransactionBatch(
+            HttpResponseMetadata::from_preamble(request_version, preamble),
+            entries,
+        ))
+    }
+
     fn parse_txid<R: Read>(
         _protocol: &mut StacksHttp,
         request_version: HttpVersion,
@@ -3799,6 +5077,36 @@ impl HttpResponseType {
         ))
     }
 
+    fn parse_block_proposal_validation<R: Read>(
+        _protocol: &mut StacksHttp,
+        request_version: HttpVersion,
+        preamble: &HttpResponsePreamble,
+        fd: &mut R,
+        len_hint: Option<usize>,
+    ) -> Result<HttpResponseType, net_error> {
+        let validation =
+            HttpResponseType::parse_json(preamble, fd, len_hint, MAX_MESSAGE_LEN as u64)?;
+        Ok(HttpResponseType::BlockProposalValidation(
+            HttpResponseMetadata::from_preamble(request_version, preamble),
+            validation,
+        ))
+    }
+
+    fn parse_microblock_stream_validation<R: Read>(
+        _protocol: &mut StacksHttp,
+        request_version: HttpVersion,
+        preamble: &HttpResponsePreamble,
+        fd: &mut R,
+        len_hint: Option<usize>,
+    ) -> Result<HttpResponseType, net_error> {
+        let validation =
+            HttpResponseType::parse_json(preamble, fd, len_hint, MAX_MESSAGE_LEN as u64)?;
+        Ok(HttpResponseType::MicroblockStreamValidation(
+            HttpResponseMetadata::from_preamble(request_version, preamble),
+            validation,
+        ))
+    }
+
     fn parse_microblock_hash<R: Read>(
         _protocol: &mut StacksHttp,
         request_version: HttpVersion,
@@ -3965,6 +5273,7 @@ impl HttpResponseType {
             402 => "Payment Required",
             403 => "Forbidden",
             404 => "Not Found",
+            409 => "Conflict",
             500 => "Internal Server Error",
             503 => "Service Temporarily Unavailable",
             _ => "Error",
@@ -3996,11 +5305,17 @@ impl HttpResponseType {
         match *self {
             HttpResponseType::PeerInfo(ref md, _) => md,
             HttpResponseType::PoxInfo(ref md, _) => md,
+            HttpResponseType::PoxRewardCycle(ref md, _) => md,
+            HttpResponseType::PoxSunsetBurn(ref md, _) => md,
+            HttpResponseType::BlockCommitInfo(ref md, _) => md,
             HttpResponseType::Neighbors(ref md, _) => md,
             HttpResponseType::HeaderStream(ref md) => md,
             HttpResponseType::Headers(ref md, _) => md,
             HttpResponseType::Block(ref md, _) => md,
             HttpResponseType::BlockStream(ref md) => md,
+            HttpResponseType::Blocks(ref md, _) => md,
+            HttpResponseType::BlockRangeStream(ref md) => md,
+            HttpResponseType::BlockWithMicroblocks(ref md, ..) => md,
             HttpResponseType::Microblocks(ref md, _) => md,
             HttpResponseType::MicroblockStream(ref md) => md,
             HttpResponseType::TransactionID(ref md, _) => md,
@@ -4010,17 +5325,26 @@ impl HttpResponseType {
             HttpResponseType::GetDataVar(ref md, _) => md,
             HttpResponseType::GetMapEntry(ref md, _) => md,
             HttpResponseType::GetAccount(ref md, _) => md,
+            HttpResponseType::GetAccountStacking(ref md, _) => md,
             HttpResponseType::GetContractABI(ref md, _) => md,
             HttpResponseType::GetContractSrc(ref md, _) => md,
             HttpResponseType::GetIsTraitImplemented(ref md, _) => md,
             HttpResponseType::CallReadOnlyFunction(ref md, _) => md,
             HttpResponseType::UnconfirmedTransaction(ref md, _) => md,
+            HttpResponseType::MempoolTransactionsByAddress(ref md, _) => md,
+            HttpResponseType::MempoolStats(ref md, _) => md,
             HttpResponseType::GetAttachment(ref md, _) => md,
             HttpResponseType::GetAttachmentsInv(ref md, _) => md,
             HttpResponseType::MemPoolTxStream(ref md) => md,
             HttpResponseType::MemPoolTxs(ref md, ..) => md,
             HttpResponseType::OptionsPreflight(ref md) => md,
             HttpResponseType::TransactionFeeEstimation(ref md, _) => md,
+            HttpResponseType::TransactionDryRun(ref md, _) => md,
+            HttpResponseType::BlockProposalValidation(ref md, _) => md,
+            HttpResponseType::MicroblockStreamValidation(ref md, _) => md,
+            HttpResponseType::TransactionBatch(ref md, _) => md,
+            HttpResponseType::FeeRate(ref md, _) => md,
+            HttpResponseType::FeeRatePercentiles(ref md, _) => md,
             // errors
             HttpResponseType::BadRequestJSON(ref md, _) => md,
             HttpResponseType::BadRequest(ref md, _) => md,
@@ -4030,6 +5354,9 @@ impl HttpResponseType {
             HttpResponseType::NotFound(ref md, _) => md,
             HttpResponseType::ServerError(ref md, _) => md,
             HttpResponseType::ServiceUnavailable(ref md, _) => md,
+            HttpResponseType::ServiceUnavailableJSON(ref md, _) => md,
+            HttpResponseType::Conflict(ref md, _) => md,
+            HttpResponseType::ConflictJSON(ref md, _) => md,
             HttpResponseType::Error(ref md, _, _) => md,
         }
     }
@@ -4098,9 +5425,37 @@ impl HttpResponseType {
         match *self {
             HttpResponseType::GetAccount(ref md, ref account_data) => {
                 HttpResponsePreamble::ok_JSON_from_md(fd, md)?;
-                HttpResponseType::send_json(protocol, md, fd, account_data)?;
+                HttpResponseType::send_json(protocol, md, fd, account_data)?;
+            }
+            HttpResponseType::GetAccountStacking(ref md, ref stacking_status) => {
+                HttpResponsePreamble::ok_JSON_from_md(fd, md)?;
+                HttpResponseType::send_json(protocol, md, fd, stacking_status)?;
+            }
+            HttpResponseType::TransactionFeeEstimation(ref md, ref data) => {
+                HttpResponsePreamble::ok_JSON_from_md(fd, md)?;
+                HttpResponseType::send_json(protocol, md, fd, data)?;
+            }
+            HttpResponseType::TransactionDryRun(ref md, ref data) => {
+                HttpResponsePreamble::ok_JSON_from_md(fd, md)?;
+                HttpResponseType::send_json(protocol, md, fd, data)?;
+            }
+            HttpResponseType::BlockProposalValidation(ref md, ref data) => {
+                HttpResponsePreamble::ok_JSON_from_md(fd, md)?;
+                HttpResponseType::send_json(protocol, md, fd, data)?;
+            }
+            HttpResponseType::MicroblockStreamValidation(ref md, ref data) => {
+                HttpResponsePreamble::ok_JSON_from_md(fd, md)?;
+                HttpResponseType::send_json(protocol, md, fd, data)?;
             }
-            HttpResponseType::TransactionFeeEstimation(ref md, ref data) => {
+            HttpResponseType::TransactionBatch(ref md, ref data) => {
+                HttpResponsePreamble::ok_JSON_from_md(fd, md)?;
+                HttpResponseType::send_json(protocol, md, fd, data)?;
+            }
+            HttpResponseType::FeeRate(ref md, ref data) => {
+                HttpResponsePreamble::ok_JSON_from_md(fd, md)?;
+                HttpResponseType::send_json(protocol, md, fd, data)?;
+            }
+            HttpResponseType::FeeRatePercentiles(ref md, ref data) => {
                 HttpResponsePreamble::ok_JSON_from_md(fd, md)?;
                 HttpResponseType::send_json(protocol, md, fd, data)?;
             }
@@ -4140,6 +5495,18 @@ impl HttpResponseType {
                 HttpResponsePreamble::ok_JSON_from_md(fd, md)?;
                 HttpResponseType::send_json(protocol, md, fd, pox_info)?;
             }
+            HttpResponseType::PoxRewardCycle(ref md, ref reward_cycle) => {
+                HttpResponsePreamble::ok_JSON_from_md(fd, md)?;
+                HttpResponseType::send_json(protocol, md, fd, reward_cycle)?;
+            }
+            HttpResponseType::PoxSunsetBurn(ref md, ref sunset_burn) => {
+                HttpResponsePreamble::ok_JSON_from_md(fd, md)?;
+                HttpResponseType::send_json(protocol, md, fd, sunset_burn)?;
+            }
+            HttpResponseType::BlockCommitInfo(ref md, ref block_commit_info) => {
+                HttpResponsePreamble::ok_JSON_from_md(fd, md)?;
+                HttpResponseType::send_json(protocol, md, fd, block_commit_info)?;
+            }
             HttpResponseType::Neighbors(ref md, ref neighbor_data) => {
                 HttpResponsePreamble::ok_JSON_from_md(fd, md)?;
                 HttpResponseType::send_json(protocol, md, fd, neighbor_data)?;
@@ -4202,6 +5569,51 @@ impl HttpResponseType {
                     |ref mut fd| keep_alive_headers(fd, md),
                 )?;
             }
+            HttpResponseType::Blocks(ref md, ref blocks) => {
+                HttpResponsePreamble::new_serialized(
+                    fd,
+                    200,
+                    "OK",
+                    md.content_length.clone(),
+                    &HttpContentType::Bytes,
+                    md.request_id,
+                    |ref mut fd| keep_alive_headers(fd, md),
+                )?;
+                let mut block_bytes = vec![];
+                for block in blocks.iter() {
+                    let mut next_block_bytes = block.serialize_to_vec();
+                    block_bytes.extend_from_slice(&(next_block_bytes.len() as u32).to_be_bytes());
+                    block_bytes.append(&mut next_block_bytes);
+                }
+                HttpResponseType::send_text(protocol, md, fd, &block_bytes)?;
+            }
+            HttpResponseType::BlockRangeStream(ref md) => {
+                // only send the preamble.  The caller will need to figure out how to send along
+                // the length-prefixed block data itself.
+                HttpResponsePreamble::new_serialized(
+                    fd,
+                    200,
+                    "OK",
+                    None,
+                    &HttpContentType::Bytes,
+                    md.request_id,
+                    |ref mut fd| keep_alive_headers(fd, md),
+                )?;
+            }
+            HttpResponseType::BlockWithMicroblocks(ref md, ref block, ref microblocks) => {
+                HttpResponsePreamble::new_serialized(
+                    fd,
+                    200,
+                    "OK",
+                    md.content_length.clone(),
+                    &HttpContentType::Bytes,
+                    md.request_id,
+                    |ref mut fd| keep_alive_headers(fd, md),
+                )?;
+                let mut bytes = block.serialize_to_vec();
+                bytes.append(&mut microblocks.serialize_to_vec());
+                HttpResponseType::send_text(protocol, md, fd, &bytes)?;
+            }
             HttpResponseType::Microblocks(ref md, ref microblocks) => {
                 HttpResponsePreamble::new_serialized(
                     fd,
@@ -4273,6 +5685,14 @@ impl HttpResponseType {
                 HttpResponsePreamble::ok_JSON_from_md(fd, md)?;
                 HttpResponseType::send_json(protocol, md, fd, unconfirmed_status)?;
             }
+            HttpResponseType::MempoolTransactionsByAddress(ref md, ref entries) => {
+                HttpResponsePreamble::ok_JSON_from_md(fd, md)?;
+                HttpResponseType::send_json(protocol, md, fd, entries)?;
+            }
+            HttpResponseType::MempoolStats(ref md, ref stats) => {
+                HttpResponsePreamble::ok_JSON_from_md(fd, md)?;
+                HttpResponseType::send_json(protocol, md, fd, stats)?;
+            }
             HttpResponseType::MemPoolTxStream(ref md) => {
                 // only send the preamble.  The caller will need to figure out how to send along
                 // the tx data itself.
@@ -4342,11 +5762,36 @@ impl HttpResponseType {
                 )?;
                 HttpResponseType::send_json(protocol, md, fd, data)?;
             }
+            HttpResponseType::ServiceUnavailableJSON(ref md, ref data) => {
+                HttpResponsePreamble::new_serialized(
+                    fd,
+                    503,
+                    HttpResponseType::error_reason(503),
+                    md.content_length.clone(),
+                    &HttpContentType::JSON,
+                    md.request_id,
+                    |ref mut fd| keep_alive_headers(fd, md),
+                )?;
+                HttpResponseType::send_json(protocol, md, fd, data)?;
+            }
+            HttpResponseType::ConflictJSON(ref md, ref data) => {
+                HttpResponsePreamble::new_serialized(
+                    fd,
+                    409,
+                    HttpResponseType::error_reason(409),
+                    md.content_length.clone(),
+                    &HttpContentType::JSON,
+                    md.request_id,
+                    |ref mut fd| keep_alive_headers(fd, md),
+                )?;
+                HttpResponseType::send_json(protocol, md, fd, data)?;
+            }
             HttpResponseType::BadRequest(_, ref msg) => self.error_response(fd, 400, msg)?,
             HttpResponseType::Unauthorized(_, ref msg) => self.error_response(fd, 401, msg)?,
             HttpResponseType::PaymentRequired(_, ref msg) => self.error_response(fd, 402, msg)?,
             HttpResponseType::Forbidden(_, ref msg) => self.error_response(fd, 403, msg)?,
             HttpResponseType::NotFound(_, ref msg) => self.error_response(fd, 404, msg)?,
+            HttpResponseType::Conflict(_, ref msg) => self.error_response(fd, 409, msg)?,
             HttpResponseType::ServerError(_, ref msg) => self.error_response(fd, 500, msg)?,
             HttpResponseType::ServiceUnavailable(_, ref msg) => {
                 self.error_response(fd, 503, msg)?
@@ -4410,24 +5855,41 @@ impl MessageSequence for StacksHttpMessage {
             StacksHttpMessage::Request(ref req) => match req {
                 HttpRequestType::GetInfo(_) => "HTTP(GetInfo)",
                 HttpRequestType::GetPoxInfo(_, _) => "HTTP(GetPoxInfo)",
+                HttpRequestType::GetPoxRewardCycle(..) => "HTTP(GetPoxRewardCycle)",
+                HttpRequestType::GetPoxSunsetBurn(..) => "HTTP(GetPoxSunsetBurn)",
+                HttpRequestType::GetBlockCommitByHeight(..) => "HTTP(GetBlockCommitByHeight)",
+                HttpRequestType::GetBlockCommitByStacksBlock(..) => {
+                    "HTTP(GetBlockCommitByStacksBlock)"
+                }
                 HttpRequestType::GetNeighbors(_) => "HTTP(GetNeighbors)",
                 HttpRequestType::GetHeaders(..) => "HTTP(GetHeaders)",
                 HttpRequestType::GetBlock(_, _) => "HTTP(GetBlock)",
+                HttpRequestType::GetBlockStream(..) => "HTTP(GetBlockStream)",
+                HttpRequestType::GetBlockByHeight(..) => "HTTP(GetBlockByHeight)",
                 HttpRequestType::GetMicroblocksIndexed(_, _) => "HTTP(GetMicroblocksIndexed)",
                 HttpRequestType::GetMicroblocksConfirmed(_, _) => "HTTP(GetMicroblocksConfirmed)",
-                HttpRequestType::GetMicroblocksUnconfirmed(_, _, _) => {
-                    "HTTP(GetMicroblocksUnconfirmed)"
-                }
+                HttpRequestType::GetMicroblocksUnconfirmed(..) => "HTTP(GetMicroblocksUnconfirmed)",
                 HttpRequestType::GetTransactionUnconfirmed(_, _) => {
                     "HTTP(GetTransactionUnconfirmed)"
                 }
+                HttpRequestType::GetMempoolTransactionsByAddress(_, _) => {
+                    "HTTP(GetMempoolTransactionsByAddress)"
+                }
+                HttpRequestType::GetMempoolStats(_) => "HTTP(GetMempoolStats)",
                 HttpRequestType::PostTransaction(_, _, _) => "HTTP(PostTransaction)",
+                HttpRequestType::PostTransactionDryRun(_, _, _) => "HTTP(PostTransactionDryRun)",
+                HttpRequestType::PostTransactionBatch(_, _) => "HTTP(PostTransactionBatch)",
                 HttpRequestType::PostBlock(..) => "HTTP(PostBlock)",
+                HttpRequestType::PostBlockValidate(..) => "HTTP(PostBlockValidate)",
                 HttpRequestType::PostMicroblock(..) => "HTTP(PostMicroblock)",
+                HttpRequestType::PostMicroblocksValidate(..) => "HTTP(PostMicroblocksValidate)",
                 HttpRequestType::GetAccount(..) => "HTTP(GetAccount)",
+                HttpRequestType::GetAccountStacking(..) => "HTTP(GetAccountStacking)",
                 HttpRequestType::GetDataVar(..) => "HTTP(GetDataVar)",
                 HttpRequestType::GetMapEntry(..) => "HTTP(GetMapEntry)",
                 HttpRequestType::GetTransferCost(_) => "HTTP(GetTransferCost)",
+                HttpRequestType::GetFeeRate(_) => "HTTP(GetFeeRate)",
+                HttpRequestType::GetFeeRatePercentiles(..) => "HTTP(GetFeeRatePercentiles)",
                 HttpRequestType::GetContractABI(..) => "HTTP(GetContractABI)",
                 HttpRequestType::GetContractSrc(..) => "HTTP(GetContractSrc)",
                 HttpRequestType::GetIsTraitImplemented(..) => "HTTP(GetIsTraitImplemented)",
@@ -4444,6 +5906,7 @@ impl MessageSequence for StacksHttpMessage {
                 HttpResponseType::GetDataVar(_, _) => "HTTP(GetDataVar)",
                 HttpResponseType::GetMapEntry(_, _) => "HTTP(GetMapEntry)",
                 HttpResponseType::GetAccount(_, _) => "HTTP(GetAccount)",
+                HttpResponseType::GetAccountStacking(_, _) => "HTTP(GetAccountStacking)",
                 HttpResponseType::GetContractABI(..) => "HTTP(GetContractABI)",
                 HttpResponseType::GetContractSrc(..) => "HTTP(GetContractSrc)",
                 HttpResponseType::GetIsTraitImplemented(..) => "HTTP(GetIsTraitImplemented)",
@@ -4452,17 +5915,27 @@ impl MessageSequence for StacksHttpMessage {
                 HttpResponseType::GetAttachmentsInv(_, _) => "HTTP(GetAttachmentsInv)",
                 HttpResponseType::PeerInfo(_, _) => "HTTP(PeerInfo)",
                 HttpResponseType::PoxInfo(_, _) => "HTTP(PeerInfo)",
+                HttpResponseType::PoxRewardCycle(_, _) => "HTTP(PoxRewardCycle)",
+                HttpResponseType::PoxSunsetBurn(_, _) => "HTTP(PoxSunsetBurn)",
+                HttpResponseType::BlockCommitInfo(_, _) => "HTTP(BlockCommitInfo)",
                 HttpResponseType::Neighbors(_, _) => "HTTP(Neighbors)",
                 HttpResponseType::Headers(..) => "HTTP(Headers)",
                 HttpResponseType::HeaderStream(..) => "HTTP(HeaderStream)",
                 HttpResponseType::Block(_, _) => "HTTP(Block)",
                 HttpResponseType::BlockStream(_) => "HTTP(BlockStream)",
+                HttpResponseType::Blocks(_, _) => "HTTP(Blocks)",
+                HttpResponseType::BlockRangeStream(_) => "HTTP(BlockRangeStream)",
+                HttpResponseType::BlockWithMicroblocks(..) => "HTTP(BlockWithMicroblocks)",
                 HttpResponseType::Microblocks(_, _) => "HTTP(Microblocks)",
                 HttpResponseType::MicroblockStream(_) => "HTTP(MicroblockStream)",
                 HttpResponseType::TransactionID(_, _) => "HTTP(Transaction)",
                 HttpResponseType::StacksBlockAccepted(..) => "HTTP(StacksBlockAccepted)",
                 HttpResponseType::MicroblockHash(_, _) => "HTTP(MicroblockHash)",
                 HttpResponseType::UnconfirmedTransaction(_, _) => "HTTP(UnconfirmedTransaction)",
+                HttpResponseType::MempoolTransactionsByAddress(_, _) => {
+                    "HTTP(MempoolTransactionsByAddress)"
+                }
+                HttpResponseType::MempoolStats(_, _) => "HTTP(MempoolStats)",
                 HttpResponseType::MemPoolTxStream(..) => "HTTP(MemPoolTxStream)",
                 HttpResponseType::MemPoolTxs(..) => "HTTP(MemPoolTxs)",
                 HttpResponseType::OptionsPreflight(_) => "HTTP(OptionsPreflight)",
@@ -4473,12 +5946,22 @@ impl MessageSequence for StacksHttpMessage {
                 HttpResponseType::PaymentRequired(_, _) => "HTTP(402)",
                 HttpResponseType::Forbidden(_, _) => "HTTP(403)",
                 HttpResponseType::NotFound(_, _) => "HTTP(404)",
+                HttpResponseType::ConflictJSON(..) | HttpResponseType::Conflict(..) => "HTTP(409)",
                 HttpResponseType::ServerError(_, _) => "HTTP(500)",
-                HttpResponseType::ServiceUnavailable(_, _) => "HTTP(503)",
+                HttpResponseType::ServiceUnavailableJSON(..)
+                | HttpResponseType::ServiceUnavailable(..) => "HTTP(503)",
                 HttpResponseType::Error(_, _, _) => "HTTP(other)",
                 HttpResponseType::TransactionFeeEstimation(_, _) => {
                     "HTTP(TransactionFeeEstimation)"
                 }
+                HttpResponseType::TransactionDryRun(_, _) => "HTTP(TransactionDryRun)",
+                HttpResponseType::BlockProposalValidation(_, _) => "HTTP(BlockProposalValidation)",
+                HttpResponseType::MicroblockStreamValidation(_, _) => {
+                    "HTTP(MicroblockStreamValidation)"
+                }
+                HttpResponseType::TransactionBatch(_, _) => "HTTP(TransactionBatch)",
+                HttpResponseType::FeeRate(_, _) => "HTTP(FeeRate)",
+                HttpResponseType::FeeRatePercentiles(_, _) => "HTTP(FeeRatePercentiles)",
             },
         }
     }
@@ -6108,6 +7591,52 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_http_post_transaction_batch_codec() {
+        let md = HttpRequestMetadata::from_host(PeerHost::DNS("www.foo.com".to_string(), 80), None);
+        let txs = vec![
+            make_test_transaction().serialize_to_vec(),
+            vec![0x00, 0x01, 0x02],
+        ];
+        let req = HttpRequestType::PostTransactionBatch(md, txs.clone());
+
+        let mut bytes = vec![];
+        let mut http = StacksHttp::new("127.0.0.1:20443".parse().unwrap());
+        http.write_message(&mut bytes, &StacksHttpMessage::Request(req))
+            .unwrap();
+
+        // a malformed transaction does not prevent the batch from being parsed
+        let mut http = StacksHttp::new("127.0.0.1:20443".parse().unwrap());
+        let (preamble, offset) = http.read_preamble(&bytes).unwrap();
+        let (msg, _) = http.read_payload(&preamble, &bytes[offset..]).unwrap();
+        match msg {
+            StacksHttpMessage::Request(HttpRequestType::PostTransactionBatch(_, parsed_txs)) => {
+                assert_eq!(parsed_txs, txs);
+            }
+            _ => panic!("Did not parse a PostTransactionBatch: {:?}", &msg),
+        }
+
+        // too many transactions
+        let body = serde_json::to_string(&PostTransactionBatchRequestBody {
+            txs: vec!["00".to_string(); MAX_TRANSACTION_BATCH_LEN + 1],
+        })
+        .unwrap();
+        let request = format!(
+            "POST /v2/transactions/batch HTTP/1.1\r\nUser-Agent: stacks/2.0\r\nHost: localhost:20443\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            &body
+        );
+        let mut http = StacksHttp::new("127.0.0.1:20443".parse().unwrap());
+        let (preamble, offset) = http.read_preamble(request.as_bytes()).unwrap();
+        let e = http.read_payload(&preamble, &request.as_bytes()[offset..]);
+        assert!(e.is_err(), "{:?}", &e);
+        assert!(e
+            .unwrap_err()
+            .to_string()
+            .find("Too many transactions in batch")
+            .is_some());
+    }
+
     #[test]
     fn test_http_response_type_codec() {
         let test_neighbors_info = RPCNeighborsInfo {
@@ -6164,6 +7693,17 @@ mod test {
             .consensus_serialize(&mut test_microblock_info_bytes)
             .unwrap();
 
+        let test_block_range_info = vec![test_block_info.clone(), test_block_info.clone()];
+        let mut test_block_range_info_bytes = vec![];
+        for _ in 0..2 {
+            test_block_range_info_bytes
+                .extend_from_slice(&(test_block_info_bytes.len() as u32).to_be_bytes());
+            test_block_range_info_bytes.extend_from_slice(&test_block_info_bytes);
+        }
+
+        let mut test_block_with_microblocks_bytes = test_block_info_bytes.clone();
+        test_block_with_microblocks_bytes.extend_from_slice(&test_microblock_info_bytes);
+
         let tests = vec![
             // length is known
             (
@@ -6192,6 +7732,33 @@ mod test {
                 ),
                 format!("/v2/blocks/{}", test_block_info.block_hash().to_hex()),
             ),
+            (
+                HttpResponseType::Blocks(
+                    HttpResponseMetadata::new(
+                        HttpVersion::Http11,
+                        123,
+                        Some(test_block_range_info_bytes.len() as u32),
+                        true,
+                        None,
+                    ),
+                    test_block_range_info.clone(),
+                ),
+                "/v2/blocks/stream?start_height=1&end_height=2".to_string(),
+            ),
+            (
+                HttpResponseType::BlockWithMicroblocks(
+                    HttpResponseMetadata::new(
+                        HttpVersion::Http11,
+                        123,
+                        Some(test_block_with_microblocks_bytes.len() as u32),
+                        true,
+                        None,
+                    ),
+                    test_block_info.clone(),
+                    test_microblock_info.clone(),
+                ),
+                "/v2/blocks/height/5?unanchored=true".to_string(),
+            ),
             (
                 HttpResponseType::Microblocks(
                     HttpResponseMetadata::new(
@@ -6236,6 +7803,21 @@ mod test {
                 ),
                 format!("/v2/blocks/{}", test_block_info.block_hash().to_hex()),
             ),
+            (
+                HttpResponseType::Blocks(
+                    HttpResponseMetadata::new(HttpVersion::Http11, 123, None, true, None),
+                    test_block_range_info.clone(),
+                ),
+                "/v2/blocks/stream?start_height=1&end_height=2".to_string(),
+            ),
+            (
+                HttpResponseType::BlockWithMicroblocks(
+                    HttpResponseMetadata::new(HttpVersion::Http11, 123, None, true, None),
+                    test_block_info.clone(),
+                    test_microblock_info.clone(),
+                ),
+                "/v2/blocks/height/5?unanchored=true".to_string(),
+            ),
             (
                 HttpResponseType::Microblocks(
                     HttpResponseMetadata::new(HttpVersion::Http11, 123, None, true, None),
@@ -6289,6 +7871,13 @@ mod test {
                 ),
                 "/v2/neighbors".to_string(),
             ),
+            (
+                HttpResponseType::Conflict(
+                    HttpResponseMetadata::new(HttpVersion::Http11, 123, Some(0), true, None),
+                    "".to_string(),
+                ),
+                "/v2/neighbors".to_string(),
+            ),
             (
                 HttpResponseType::ServerError(
                     HttpResponseMetadata::new(HttpVersion::Http11, 123, Some(0), true, None),
@@ -6347,6 +7936,13 @@ mod test {
                 ),
                 "/v2/neighbors".to_string(),
             ),
+            (
+                HttpResponseType::Conflict(
+                    HttpResponseMetadata::new(HttpVersion::Http11, 123, Some(3), true, None),
+                    "foo".to_string(),
+                ),
+                "/v2/neighbors".to_string(),
+            ),
             (
                 HttpResponseType::ServerError(
                     HttpResponseMetadata::new(HttpVersion::Http11, 123, Some(3), true, None),
@@ -6389,6 +7985,22 @@ mod test {
                 true,
                 123,
             ),
+            HttpResponsePreamble::new(
+                200,
+                "OK".to_string(),
+                Some(test_block_range_info_bytes.len() as u32),
+                HttpContentType::Bytes,
+                true,
+                123,
+            ),
+            HttpResponsePreamble::new(
+                200,
+                "OK".to_string(),
+                Some(test_block_with_microblocks_bytes.len() as u32),
+                HttpContentType::Bytes,
+                true,
+                123,
+            ),
             HttpResponsePreamble::new(
                 200,
                 "OK".to_string(),
@@ -6430,6 +8042,22 @@ mod test {
                 true,
                 123,
             ),
+            HttpResponsePreamble::new(
+                200,
+                "OK".to_string(),
+                None,
+                HttpContentType::Bytes,
+                true,
+                123,
+            ),
+            HttpResponsePreamble::new(
+                200,
+                "OK".to_string(),
+                None,
+                HttpContentType::Bytes,
+                true,
+                123,
+            ),
             HttpResponsePreamble::new(
                 200,
                 "OK".to_string(),
@@ -6466,6 +8094,8 @@ mod test {
                 .as_bytes()
                 .to_vec(),
             test_block_info_bytes.clone(),
+            test_block_range_info_bytes.clone(),
+            test_block_with_microblocks_bytes.clone(),
             test_microblock_info_bytes.clone(),
             Txid([0x1; 32]).to_hex().as_bytes().to_vec(),
             // with transfer-encoding: chunked
@@ -6474,6 +8104,8 @@ mod test {
                 .as_bytes()
                 .to_vec(),
             test_block_info_bytes,
+            test_block_range_info_bytes,
+            test_block_with_microblocks_bytes,
             test_microblock_info_bytes,
             Txid([0x1; 32]).to_hex().as_bytes().to_vec(),
             // errors
@@ -6979,6 +8611,220 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_http_parse_until_block_query() {
+        assert_eq!(
+            HttpRequestType::get_until_block_query(Some("until_block=123&proof=0")).unwrap(),
+            Some(TipRequest::CanonicalAncestorAtHeight(123))
+        );
+        assert_eq!(
+            HttpRequestType::get_until_block_query(Some(
+                "until_block=7070f213d719143d6045e08fd80f85014a161f8bbd3a42d1251576740826a392"
+            ))
+            .unwrap(),
+            Some(TipRequest::CanonicalAncestor(
+                StacksBlockId::from_hex(
+                    "7070f213d719143d6045e08fd80f85014a161f8bbd3a42d1251576740826a392"
+                )
+                .unwrap()
+            ))
+        );
+        assert_eq!(
+            HttpRequestType::get_until_block_query(Some("proof=0")).unwrap(),
+            None
+        );
+        assert_eq!(HttpRequestType::get_until_block_query(None).unwrap(), None);
+
+        // unlike `tip`, a bad value is an error
+        assert!(HttpRequestType::get_until_block_query(Some("until_block=bad")).is_err());
+        assert!(HttpRequestType::get_until_block_query(Some("until_block=-1")).is_err());
+    }
+
+    #[test]
+    fn test_http_parse_microblocks_limit_query() {
+        assert_eq!(
+            HttpRequestType::get_microblocks_limit_query(Some("limit=10")).unwrap(),
+            Some(10)
+        );
+        assert_eq!(
+            HttpRequestType::get_microblocks_limit_query(Some("tip=latest")).unwrap(),
+            None
+        );
+        assert_eq!(
+            HttpRequestType::get_microblocks_limit_query(None).unwrap(),
+            None
+        );
+
+        assert!(HttpRequestType::get_microblocks_limit_query(Some("limit=0")).is_err());
+        assert!(HttpRequestType::get_microblocks_limit_query(Some("limit=bad")).is_err());
+        assert!(HttpRequestType::get_microblocks_limit_query(Some("limit=65536")).is_err());
+    }
+
+    #[test]
+    fn test_http_parse_block_stream_range_query() {
+        assert_eq!(
+            HttpRequestType::get_block_stream_range_query(Some("start_height=10&end_height=12"))
+                .unwrap(),
+            (10, 12)
+        );
+        assert_eq!(
+            HttpRequestType::get_block_stream_range_query(Some("end_height=10&start_height=10"))
+                .unwrap(),
+            (10, 10)
+        );
+        assert_eq!(
+            HttpRequestType::get_block_stream_range_query(Some(&format!(
+                "start_height=1&end_height={}",
+                MAX_BLOCK_STREAM_RANGE
+            )))
+            .unwrap(),
+            (1, MAX_BLOCK_STREAM_RANGE)
+        );
+
+        // both ends are required
+        assert!(HttpRequestType::get_block_stream_range_query(None).is_err());
+        assert!(HttpRequestType::get_block_stream_range_query(Some("start_height=10")).is_err());
+        assert!(HttpRequestType::get_block_stream_range_query(Some("end_height=10")).is_err());
+
+        // must be well-formed, in order, and not too long
+        assert!(HttpRequestType::get_block_stream_range_query(Some(
+            "start_height=bad&end_height=10"
+        ))
+        .is_err());
+        assert!(HttpRequestType::get_block_stream_range_query(Some(
+            "start_height=12&end_height=10"
+        ))
+        .is_err());
+        assert!(HttpRequestType::get_block_stream_range_query(Some(&format!(
+            "start_height=0&end_height={}",
+            MAX_BLOCK_STREAM_RANGE
+        )))
+        .is_err());
+    }
+
+    #[test]
+    fn test_http_parse_getblock_by_height() {
+        for (path, height, unanchored) in [
+            ("/v2/blocks/height/12", 12, false),
+            ("/v2/blocks/height/0?unanchored=true", 0, true),
+            ("/v2/blocks/height/12?unanchored=false", 12, false),
+            ("/v2/blocks/height/12?unanchored=1", 12, false),
+        ] {
+            let request = format!(
+                "GET {} HTTP/1.1\r\nUser-Agent: stacks/2.0\r\nHost: localhost:20443\r\n\r\n",
+                path
+            );
+            let mut http = StacksHttp::new("127.0.0.1:20443".parse().unwrap());
+            let (preamble, offset) = http.read_preamble(request.as_bytes()).unwrap();
+            let (message, _) = http
+                .read_payload(&preamble, &request.as_bytes()[offset..])
+                .unwrap();
+            match message {
+                StacksHttpMessage::Request(req) => {
+                    match req {
+                        HttpRequestType::GetBlockByHeight(_, ref h, ref u) => {
+                            assert_eq!(*h, height);
+                            assert_eq!(*u, unanchored);
+                        }
+                        _ => panic!("Parsed {} as {:?}", path, &req),
+                    }
+                    let expected_path = if unanchored {
+                        format!("/v2/blocks/height/{}?unanchored=true", height)
+                    } else {
+                        format!("/v2/blocks/height/{}", height)
+                    };
+                    assert_eq!(req.request_path(), expected_path);
+                }
+                _ => panic!("Did not parse a request"),
+            }
+        }
+
+        // heights must be numbers
+        let request =
+            "GET /v2/blocks/height/abc HTTP/1.1\r\nUser-Agent: stacks/2.0\r\nHost: localhost:20443\r\n\r\n";
+        let mut http = StacksHttp::new("127.0.0.1:20443".parse().unwrap());
+        let (preamble, offset) = http.read_preamble(request.as_bytes()).unwrap();
+        let (message, _) = http
+            .read_payload(&preamble, &request.as_bytes()[offset..])
+            .unwrap();
+        match message {
+            StacksHttpMessage::Request(HttpRequestType::ClientError(..)) => {}
+            _ => panic!("Parsed a non-numeric height: {:?}", &message),
+        }
+    }
+
+    #[test]
+    fn test_http_parse_fee_rate_percentiles() {
+        for (path, percentiles) in [
+            ("/v2/fees/rate/percentiles?percentiles=50", vec![50]),
+            (
+                "/v2/fees/rate/percentiles?percentiles=95,5,50",
+                vec![5, 50, 95],
+            ),
+            (
+                "/v2/fees/rate/percentiles?percentiles=0,100,0",
+                vec![0, 100],
+            ),
+            (
+                "/v2/fees/rate/percentiles?percentiles=10&percentiles=90",
+                vec![10, 90],
+            ),
+        ] {
+            let request = format!(
+                "GET {} HTTP/1.1\r\nUser-Agent: stacks/2.0\r\nHost: localhost:20443\r\n\r\n",
+                path
+            );
+            let mut http = StacksHttp::new("127.0.0.1:20443".parse().unwrap());
+            let (preamble, offset) = http.read_preamble(request.as_bytes()).unwrap();
+            let (message, _) = http
+                .read_payload(&preamble, &request.as_bytes()[offset..])
+                .unwrap();
+            match message {
+                StacksHttpMessage::Request(req) => {
+                    match req {
+                        HttpRequestType::GetFeeRatePercentiles(_, ref p) => {
+                            assert_eq!(*p, percentiles);
+                        }
+                        _ => panic!("Parsed {} as {:?}", path, &req),
+                    }
+                    let expected_path = format!(
+                        "/v2/fees/rate/percentiles?percentiles={}",
+                        percentiles
+                            .iter()
+                            .map(|p| p.to_string())
+                            .collect::<Vec<_>>()
+                            .join(",")
+                    );
+                    assert_eq!(req.request_path(), expected_path);
+                }
+                _ => panic!("Did not parse a request"),
+            }
+        }
+
+        // percentiles must be given, and must be numbers in [0, 100]
+        for path in [
+            "/v2/fees/rate/percentiles",
+            "/v2/fees/rate/percentiles?percentiles=",
+            "/v2/fees/rate/percentiles?percentiles=5,abc",
+            "/v2/fees/rate/percentiles?percentiles=101",
+            "/v2/fees/rate/percentiles?percentiles=-1",
+        ] {
+            let request = format!(
+                "GET {} HTTP/1.1\r\nUser-Agent: stacks/2.0\r\nHost: localhost:20443\r\n\r\n",
+                path
+            );
+            let mut http = StacksHttp::new("127.0.0.1:20443".parse().unwrap());
+            let (preamble, offset) = http.read_preamble(request.as_bytes()).unwrap();
+            let (message, _) = http
+                .read_payload(&preamble, &request.as_bytes()[offset..])
+                .unwrap();
+            match message {
+                StacksHttpMessage::Request(HttpRequestType::ClientError(..)) => {}
+                _ => panic!("Parsed {}: {:?}", path, &message),
+            }
+        }
+    }
+
     #[test]
     fn test_http_live_headers() {
         // headers pulled from prod
@@ -16,7 +16,7 @@
 
 use std::borrow::Borrow;
 use std::cmp::PartialEq;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::convert::From;
 use std::convert::TryFrom;
 use std::error;
@@ -271,6 +271,8 @@ pub enum ClientError {
     Message(String),
     /// 404
     NotFound(String),
+    /// 400, for a POSTed transaction that could not be decoded
+    MalformedTransaction(String),
 }
 
 impl error::Error for ClientError {
@@ -284,6 +286,7 @@ impl fmt::Display for ClientError {
         match self {
             ClientError::Message(s) => write!(f, "{}", s),
             ClientError::NotFound(s) => write!(f, "HTTP path not matched: {}", s),
+            ClientError::MalformedTransaction(s) => write!(f, "Malformed transaction: {}", s),
         }
     }
 }
@@ -856,6 +859,7 @@ pub struct HandshakeData {
 pub enum ServiceFlags {
     RELAY = 0x01,
     RPC = 0x02,
+    BLOCK_STREAM = 0x04,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -1086,6 +1090,28 @@ pub struct RPCPeerInfoData {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_pox_anchor: Option<RPCLastPoxAnchorData>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub miner: Option<RPCMinerInfoData>,
+}
+
+/// The miner section of GET /v2/info, present once the node has sent a block-commit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RPCMinerInfoData {
+    /// the burn the miner chose for its most recent block-commit
+    pub last_burn_fee: u64,
+    /// the burn chain height of the burn block the block-commit built off of
+    pub last_burn_fee_block_height: u64,
+    /// number of sortitions in the miner's win-rate window that it won, if its burn fee
+    /// strategy tracks them
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recent_sortitions_won: Option<u64>,
+    /// number of sortitions in the miner's win-rate window that it entered, if its burn fee
+    /// strategy tracks them
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recent_sortitions_entered: Option<u64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -1143,6 +1169,58 @@ pub struct RPCPoxInfoData {
     pub contract_versions: Vec<RPCPoxContractVersion>,
 }
 
+/// A single reward address entry in the reward set of a PoX reward cycle
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RPCPoxRewardAddress {
+    pub address: String,
+    pub amount: u128,
+}
+
+/// The data we return on GET /v2/pox/reward-cycle/{burn_block_height}
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RPCPoxRewardCycleInfo {
+    pub cycle_number: u64,
+    pub start_height: u64,
+    pub end_height: u64,
+    pub is_prepare_phase: bool,
+    pub reward_addresses: Vec<RPCPoxRewardAddress>,
+}
+
+/// The data we return on GET /v2/pox/sunset-burn/{burn_block_height}/{burn_fee_cap}
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RPCPoxSunsetBurnInfo {
+    pub burn_block_height: u64,
+    pub burn_fee_cap: u64,
+    pub expected_sunset_burn: u64,
+    pub is_prepare_phase: bool,
+}
+
+/// A block-commit that won sortition, as reported by the block-commit endpoints
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RPCBlockCommitData {
+    pub txid: Txid,
+    pub vtxindex: u32,
+    pub block_header_hash: BlockHeaderHash,
+    pub burn_fee: u64,
+    pub sunset_burn: u64,
+    pub commit_outs: Vec<String>,
+    pub parent_block_ptr: u32,
+    pub parent_vtxindex: u16,
+    pub key_block_ptr: u32,
+    pub key_vtxindex: u16,
+}
+
+/// The data we return on GET /v2/block-commits/{burn_block_height} and
+/// GET /v2/block-commits/stacks-block/{index_block_hash}
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RPCBlockCommitInfo {
+    pub burn_block_height: u64,
+    pub burn_header_hash: BurnchainHeaderHash,
+    pub consensus_hash: ConsensusHash,
+    /// None if no block-commit won sortition in this burnchain block
+    pub winning_block_commit: Option<RPCBlockCommitData>,
+}
+
 /// Headers response payload
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ExtendedStacksHeader {
@@ -1201,19 +1279,21 @@ pub struct RPCFeeEstimate {
 }
 
 impl RPCFeeEstimate {
+    /// Fee rates are reported at full precision; the fee itself is rounded to
+    ///  the nearest microSTX.
     pub fn estimate_fees(scalar: u64, fee_rates: FeeRateEstimate) -> Vec<RPCFeeEstimate> {
         let estimated_fees_f64 = fee_rates.clone() * (scalar as f64);
         vec![
             RPCFeeEstimate {
-                fee: estimated_fees_f64.low as u64,
+                fee: estimated_fees_f64.low.round() as u64,
                 fee_rate: fee_rates.low,
             },
             RPCFeeEstimate {
-                fee: estimated_fees_f64.middle as u64,
+                fee: estimated_fees_f64.middle.round() as u64,
                 fee_rate: fee_rates.middle,
             },
             RPCFeeEstimate {
-                fee: estimated_fees_f64.high as u64,
+                fee: estimated_fees_f64.high.round() as u64,
                 fee_rate: fee_rates.high,
             },
         ]
@@ -1228,6 +1308,48 @@ pub struct RPCFeeEstimateResponse {
     pub cost_scalar_change_by_byte: f64,
 }
 
+/// The outcome of executing a contract-call transaction against a read-only snapshot of the
+/// chainstate, without broadcasting it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransactionDryRunResponse {
+    pub okay: bool,
+    /// hex-serialized Clarity value returned by the called function
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cause: Option<String>,
+    pub execution_cost: ExecutionCost,
+    /// events emitted by the call, in the same form sent to event observers
+    pub events: Vec<serde_json::Value>,
+}
+
+/// The outcome of checking a proposed anchored block against the canonical Stacks tip, without
+/// storing it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockProposalValidationResponse {
+    pub valid: bool,
+    pub block_hash: BlockHeaderHash,
+    /// transactions in the proposal that could not be mined
+    pub invalid_txids: Vec<Txid>,
+    pub execution_cost: ExecutionCost,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// The outcome of checking a microblock stream against the canonical Stacks tip, without
+/// storing it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MicroblockStreamValidationResponse {
+    pub valid: bool,
+    /// sequence number of the first invalid microblock, if any
+    pub first_invalid_seq: Option<u16>,
+    /// why the first invalid microblock is invalid
+    pub errors: Vec<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Copy, Hash)]
 #[repr(u8)]
 pub enum HttpVersion {
@@ -1265,6 +1387,9 @@ pub struct MapEntryResponse {
 pub struct ContractSrcResponse {
     pub source: String,
     pub publish_height: u32,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clarity_version: Option<u8>,
     #[serde(rename = "proof")]
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1299,6 +1424,36 @@ pub struct AccountEntryResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub nonce_proof: Option<String>,
+    /// The account's nonce in the unconfirmed microblock state.  Only reported when reading the
+    /// latest chain tip.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub unanchored_nonce: Option<u64>,
+    /// The smallest nonce the account uses in neither the chain state nor the mempool.  Only
+    /// reported when reading the latest chain tip.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub possible_next_nonce: Option<u64>,
+}
+
+/// The PoX lock currently held on an account's STX, as reported by
+/// `/v2/accounts/:principal/stacking`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccountStackingInfo {
+    pub stacked_amount: u128,
+    pub unlock_height: u64,
+    pub delegated_to: Option<String>,
+    pub pox_address: Option<String>,
+    pub reward_cycle: u64,
+    pub lock_period: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccountStackingResponse {
+    pub stacked: bool,
+    /// Only present if `stacked` is true
+    #[serde(flatten)]
+    pub stacking: Option<AccountStackingInfo>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -1314,6 +1469,28 @@ pub enum UnconfirmedTransactionStatus {
 pub struct UnconfirmedTransactionResponse {
     pub tx: String,
     pub status: UnconfirmedTransactionStatus,
+    /// the fee the transaction pays, in microSTX
+    #[serde(default)]
+    pub fee: u64,
+    /// the origin account's nonce in this transaction
+    #[serde(default)]
+    pub nonce: u64,
+    /// when this node's mempool first received the transaction, in seconds since the epoch, if
+    /// it is (still) in the mempool
+    #[serde(default)]
+    pub receipt_time: Option<u64>,
+}
+
+/// A pending transaction sent or sponsored by an address, as reported by
+/// `GET /v2/mempool/transactions/{address}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MempoolTransactionEntry {
+    pub txid: Txid,
+    /// the nonce the queried address uses in this transaction
+    pub nonce: u64,
+    pub fee_rate: Option<f64>,
+    pub payload_type: String,
+    pub sponsored: bool,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -1322,6 +1499,96 @@ pub struct PostTransactionRequestBody {
     pub attachment: Option<String>,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct PostTransactionBatchRequestBody {
+    /// hex-encoded transactions
+    pub txs: Vec<String>,
+}
+
+/// Why a transaction submitted over RPC was not accepted.  This is the body of every 4xx
+/// response to a transaction submission, and serializes as a `PostTransactionErrorBody`.
+#[derive(Debug)]
+pub enum PostTransactionError {
+    /// The request did not hold a transaction that could be decoded
+    Malformed(String),
+    /// The transaction with this txid was decoded, but the mempool would not admit it
+    Rejected(Txid, MemPoolRejection),
+}
+
+/// The body of a 4xx response to a transaction submission
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PostTransactionErrorBody {
+    /// the rejected transaction, if it could be decoded
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub txid: Option<Txid>,
+    /// a stable code for the error, such as `BadNonce` or `FeeTooLow`
+    pub error: String,
+    /// a human-readable explanation of the error
+    pub reason: String,
+    /// the error's structured details, such as the expected and actual nonce
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason_data: Option<serde_json::Value>,
+}
+
+impl PostTransactionError {
+    /// The code that clients see for a transaction that could not be decoded.  It is the same
+    /// as that of `MemPoolRejection::DeserializationFailure`.
+    pub const MALFORMED_ERROR_CODE: &'static str = "Deserialization";
+
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            PostTransactionError::Malformed(_) => PostTransactionError::MALFORMED_ERROR_CODE,
+            PostTransactionError::Rejected(_, rejection) => rejection.error_code(),
+        }
+    }
+
+    /// Is this the rejection of a transaction that conflicts with one already in the mempool?
+    /// These are reported with 409 instead of 400.
+    pub fn is_conflict(&self) -> bool {
+        matches!(
+            self,
+            PostTransactionError::Rejected(_, MemPoolRejection::ConflictingNonceInMempool(_))
+        )
+    }
+
+    pub fn to_body(&self) -> PostTransactionErrorBody {
+        match self {
+            PostTransactionError::Malformed(msg) => PostTransactionErrorBody {
+                txid: None,
+                error: self.error_code().to_string(),
+                reason: msg.clone(),
+                reason_data: None,
+            },
+            PostTransactionError::Rejected(txid, rejection) => PostTransactionErrorBody {
+                txid: Some(txid.clone()),
+                error: self.error_code().to_string(),
+                reason: rejection.reason(),
+                reason_data: rejection.reason_data(),
+            },
+        }
+    }
+}
+
+impl Serialize for PostTransactionError {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        self.to_body().serialize(s)
+    }
+}
+
+/// The outcome of submitting one transaction of a batch to the mempool
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PostTransactionBatchEntry {
+    pub txid: Txid,
+    pub accepted: bool,
+    /// why the transaction was rejected, in the same form as the body of a rejected single
+    /// transaction POST
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rejection: Option<PostTransactionErrorBody>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct GetAttachmentResponse {
     pub attachment: Attachment,
@@ -1461,6 +1728,10 @@ pub enum TipRequest {
     UseLatestAnchoredTip,
     UseLatestUnconfirmedTip,
     SpecificTip(StacksBlockId),
+    /// The ancestor of the canonical anchored tip at the given height (`until_block=<height>`)
+    CanonicalAncestorAtHeight(u64),
+    /// The given block, which must be on the canonical fork (`until_block=<index block hash>`)
+    CanonicalAncestor(StacksBlockId),
 }
 
 /// All HTTP request paths we support, and the arguments they carry in their paths
@@ -1468,17 +1739,32 @@ pub enum TipRequest {
 pub enum HttpRequestType {
     GetInfo(HttpRequestMetadata),
     GetPoxInfo(HttpRequestMetadata, TipRequest),
+    GetPoxRewardCycle(HttpRequestMetadata, u64, TipRequest),
+    GetPoxSunsetBurn(HttpRequestMetadata, u64, u64),
+    GetBlockCommitByHeight(HttpRequestMetadata, u64),
+    GetBlockCommitByStacksBlock(HttpRequestMetadata, StacksBlockId),
     GetNeighbors(HttpRequestMetadata),
     GetHeaders(HttpRequestMetadata, u64, TipRequest),
     GetBlock(HttpRequestMetadata, StacksBlockId),
+    GetBlockStream(HttpRequestMetadata, u64, u64),
+    /// block height, and whether or not to include the confirmed microblock stream
+    GetBlockByHeight(HttpRequestMetadata, u64, bool),
     GetMicroblocksIndexed(HttpRequestMetadata, StacksBlockId),
     GetMicroblocksConfirmed(HttpRequestMetadata, StacksBlockId),
-    GetMicroblocksUnconfirmed(HttpRequestMetadata, StacksBlockId, u16),
+    GetMicroblocksUnconfirmed(HttpRequestMetadata, StacksBlockId, u16, Option<u16>),
     GetTransactionUnconfirmed(HttpRequestMetadata, Txid),
+    GetMempoolTransactionsByAddress(HttpRequestMetadata, StacksAddress),
+    GetMempoolStats(HttpRequestMetadata),
     PostTransaction(HttpRequestMetadata, StacksTransaction, Option<Attachment>),
+    PostTransactionDryRun(HttpRequestMetadata, StacksTransaction, TipRequest),
+    /// consensus-serialized transactions
+    PostTransactionBatch(HttpRequestMetadata, Vec<Vec<u8>>),
     PostBlock(HttpRequestMetadata, ConsensusHash, StacksBlock),
+    PostBlockValidate(HttpRequestMetadata, StacksBlock),
     PostMicroblock(HttpRequestMetadata, StacksMicroblock, TipRequest),
+    PostMicroblocksValidate(HttpRequestMetadata, Vec<StacksMicroblock>),
     GetAccount(HttpRequestMetadata, PrincipalData, TipRequest, bool),
+    GetAccountStacking(HttpRequestMetadata, PrincipalData, TipRequest),
     GetDataVar(
         HttpRequestMetadata,
         StacksAddress,
@@ -1508,6 +1794,8 @@ pub enum HttpRequestType {
         TipRequest,
     ),
     GetTransferCost(HttpRequestMetadata),
+    GetFeeRate(HttpRequestMetadata),
+    GetFeeRatePercentiles(HttpRequestMetadata, Vec<u8>),
     GetContractSrc(
         HttpRequestMetadata,
         StacksAddress,
@@ -1619,14 +1907,22 @@ impl HttpResponseMetadata {
 pub enum HttpResponseType {
     PeerInfo(HttpResponseMetadata, RPCPeerInfoData),
     PoxInfo(HttpResponseMetadata, RPCPoxInfoData),
+    PoxRewardCycle(HttpResponseMetadata, RPCPoxRewardCycleInfo),
+    PoxSunsetBurn(HttpResponseMetadata, RPCPoxSunsetBurnInfo),
+    BlockCommitInfo(HttpResponseMetadata, RPCBlockCommitInfo),
     Neighbors(HttpResponseMetadata, RPCNeighborsInfo),
     Headers(HttpResponseMetadata, Vec<ExtendedStacksHeader>),
     HeaderStream(HttpResponseMetadata),
     Block(HttpResponseMetadata, StacksBlock),
     BlockStream(HttpResponseMetadata),
+    Blocks(HttpResponseMetadata, Vec<StacksBlock>),
+    BlockRangeStream(HttpResponseMetadata),
+    /// an anchored block and the microblock stream it confirmed
+    BlockWithMicroblocks(HttpResponseMetadata, StacksBlock, Vec<StacksMicroblock>),
     Microblocks(HttpResponseMetadata, Vec<StacksMicroblock>),
     MicroblockStream(HttpResponseMetadata),
     TransactionID(HttpResponseMetadata, Txid),
+    TransactionBatch(HttpResponseMetadata, Vec<PostTransactionBatchEntry>),
     StacksBlockAccepted(HttpResponseMetadata, StacksBlockId, bool),
     MicroblockHash(HttpResponseMetadata, BlockHeaderHash),
     TokenTransferCost(HttpResponseMetadata, u64),
@@ -1634,16 +1930,24 @@ pub enum HttpResponseType {
     GetMapEntry(HttpResponseMetadata, MapEntryResponse),
     CallReadOnlyFunction(HttpResponseMetadata, CallReadOnlyResponse),
     GetAccount(HttpResponseMetadata, AccountEntryResponse),
+    GetAccountStacking(HttpResponseMetadata, AccountStackingResponse),
     GetContractABI(HttpResponseMetadata, ContractInterface),
     GetContractSrc(HttpResponseMetadata, ContractSrcResponse),
     GetIsTraitImplemented(HttpResponseMetadata, GetIsTraitImplementedResponse),
     UnconfirmedTransaction(HttpResponseMetadata, UnconfirmedTransactionResponse),
+    MempoolTransactionsByAddress(HttpResponseMetadata, Vec<MempoolTransactionEntry>),
+    MempoolStats(HttpResponseMetadata, MemPoolStats),
     GetAttachment(HttpResponseMetadata, GetAttachmentResponse),
     GetAttachmentsInv(HttpResponseMetadata, GetAttachmentsInvResponse),
     MemPoolTxStream(HttpResponseMetadata),
     MemPoolTxs(HttpResponseMetadata, Option<Txid>, Vec<StacksTransaction>),
     OptionsPreflight(HttpResponseMetadata),
     TransactionFeeEstimation(HttpResponseMetadata, RPCFeeEstimateResponse),
+    TransactionDryRun(HttpResponseMetadata, TransactionDryRunResponse),
+    BlockProposalValidation(HttpResponseMetadata, BlockProposalValidationResponse),
+    MicroblockStreamValidation(HttpResponseMetadata, MicroblockStreamValidationResponse),
+    FeeRate(HttpResponseMetadata, FeeRateEstimate),
+    FeeRatePercentiles(HttpResponseMetadata, BTreeMap<u8, f64>),
     // peer-given error responses
     BadRequest(HttpResponseMetadata, String),
     BadRequestJSON(HttpResponseMetadata, serde_json::Value),
@@ -1651,8 +1955,11 @@ pub enum HttpResponseType {
     PaymentRequired(HttpResponseMetadata, String),
     Forbidden(HttpResponseMetadata, String),
     NotFound(HttpResponseMetadata, String),
+    Conflict(HttpResponseMetadata, String),
+    ConflictJSON(HttpResponseMetadata, serde_json::Value),
     ServerError(HttpResponseMetadata, String),
     ServiceUnavailable(HttpResponseMetadata, String),
+    ServiceUnavailableJSON(HttpResponseMetadata, serde_json::Value),
     Error(HttpResponseMetadata, u16, String),
 }
 
@@ -1926,6 +2233,12 @@ pub const MAX_MICROBLOCKS_UNCONFIRMED: usize = 1024;
 // maximum number of block headers we'll get streamed to us
 pub const MAX_HEADERS: usize = 2100;
 
+// maximum number of sortitions a single block stream request can span
+pub const MAX_BLOCK_STREAM_RANGE: u64 = 32;
+
+// maximum number of transactions a single transaction batch request can carry
+pub const MAX_TRANSACTION_BATCH_LEN: usize = 128;
+
 // how long a peer will be denied for if it misbehaves
 #[cfg(test)]
 pub const DENY_BAN_DURATION: u64 = 30; // seconds
@@ -2424,6 +2737,16 @@ pub mod test {
             })
         }
 
+        fn announce_epoch_transition(
+            &self,
+            _block_id: &StacksBlockId,
+            _burn_block_height: u64,
+            _old_epoch: StacksEpochId,
+            _new_epoch: StacksEpochId,
+        ) {
+            // pass
+        }
+
         fn announce_burn_block(
             &self,
             _burn_block: &BurnchainHeaderHash,
@@ -2434,6 +2757,16 @@ pub mod test {
         ) {
             // pass
         }
+
+        fn announce_burn_block_reorg(
+            &self,
+            _old_canonical_tip: &BurnchainHeaderHash,
+            _new_canonical_tip: &BurnchainHeaderHash,
+            _reorg_depth: u64,
+            _orphaned_stacks_blocks: &[BlockHeaderHash],
+        ) {
+            // pass
+        }
     }
 
     // describes a peer's initial configuration
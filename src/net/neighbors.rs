@@ -326,6 +326,8 @@ pub struct NeighborWalk {
     walk_reset_interval: u64, // how long a walk can last, in wall-clock time
     walk_state_time: u64,     // when the walk entered this state
     walk_state_timeout: u64,  // how long the walk can remain in this state
+
+    block_strike_window: u64, // how far back to count a neighbor's invalid blocks when scoring it
 }
 
 impl NeighborWalk {
@@ -387,6 +389,8 @@ impl NeighborWalk {
             walk_reset_interval: connection_opts.walk_reset_interval,
             walk_state_time: get_epoch_time_secs(),
             walk_state_timeout: connection_opts.walk_state_timeout,
+
+            block_strike_window: connection_opts.block_strike_window,
         }
     }
 
@@ -1314,13 +1318,28 @@ impl NeighborWalk {
     /// stepping to a neighbor in MHRWDA.  We estimate each neighbor's undirected degree, and then
     /// measure how represented each neighbor's AS is in the peer graph.  We *bias* the sample so
     /// that peers in under-represented ASs are more likely to be walked to than they otherwise
-    /// would be if considering only neighbor degrees.
-    fn degree_ratio(peerdb_conn: &DBConn, n1: &Neighbor, n2: &Neighbor) -> f64 {
+    /// would be if considering only neighbor degrees.  We also *penalize* peers that recently sent
+    /// us invalid blocks or microblocks, so that each such strike makes them less likely to be
+    /// walked to.
+    fn degree_ratio(
+        peerdb_conn: &DBConn,
+        n1: &Neighbor,
+        n2: &Neighbor,
+        block_strike_window: u64,
+    ) -> f64 {
         let d1 = n1.degree() as f64;
         let d2 = n2.degree() as f64;
         let as_d1 = PeerDB::asn_count(peerdb_conn, n1.asn).unwrap_or(1) as f64;
         let as_d2 = PeerDB::asn_count(peerdb_conn, n2.asn).unwrap_or(1) as f64;
-        (d1 * as_d2) / (d2 * as_d1)
+        let strikes_2 = PeerDB::get_block_strike_count(
+            peerdb_conn,
+            n2.addr.network_id,
+            &n2.addr.addrbytes,
+            n2.addr.port,
+            get_epoch_time_secs().saturating_sub(block_strike_window),
+        )
+        .unwrap_or(0) as f64;
+        (d1 * as_d2) / (d2 * as_d1 * (1.0 + strikes_2))
     }
 
     /// Do the MHRWDA step -- try to step from our cur_neighbor to an immediate neighbor, if there
@@ -1363,7 +1382,12 @@ impl NeighborWalk {
             if walk_prob
                 < fmin!(
                     1.0,
-                    NeighborWalk::degree_ratio(peerdb_conn, &self.cur_neighbor, &next_neighbor)
+                    NeighborWalk::degree_ratio(
+                        peerdb_conn,
+                        &self.cur_neighbor,
+                        &next_neighbor,
+                        self.block_strike_window
+                    )
                 )
             {
                 match self.prev_neighbor {
@@ -1389,11 +1413,13 @@ impl NeighborWalk {
                                     peerdb_conn,
                                     &self.cur_neighbor,
                                     &alt_next_neighbor,
+                                    self.block_strike_window,
                                 );
                                 let prev_to_cur = NeighborWalk::degree_ratio(
                                     peerdb_conn,
                                     &prev_neighbor,
                                     &self.cur_neighbor,
+                                    self.block_strike_window,
                                 );
                                 let trans_prob = fmin!(
                                     fmin!(1.0, cur_to_alt * cur_to_alt),
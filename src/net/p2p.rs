@@ -98,6 +98,7 @@ use clarity::vm::ast::ASTRules;
 #[derive(Debug)]
 pub enum NetworkRequest {
     Ban(Vec<NeighborKey>),
+    Strike(Vec<NeighborKey>), // count a strike against each neighbor for sending us an invalid block or microblock
     AdvertizeBlocks(BlocksAvailableMap, HashMap<ConsensusHash, StacksBlock>), // announce to all wanting neighbors that we have these blocks
     AdvertizeMicroblocks(
         BlocksAvailableMap,
@@ -149,6 +150,13 @@ impl NetworkHandle {
         self.send_request(req)
     }
 
+    /// Record a strike against each peer for sending us an invalid block or microblock.
+    /// A peer is banned once it racks up too many strikes in too short a time.
+    pub fn strike_peers(&mut self, neighbor_keys: Vec<NeighborKey>) -> Result<(), net_error> {
+        let req = NetworkRequest::Strike(neighbor_keys);
+        self.send_request(req)
+    }
+
     /// Advertize blocks
     pub fn advertize_blocks(
         &mut self,
@@ -1060,6 +1068,14 @@ impl PeerNetwork {
                 }
                 Ok(())
             }
+            NetworkRequest::Strike(neighbor_keys) => {
+                let banned = self.process_block_strikes(&neighbor_keys)?;
+                for neighbor_key in banned.iter() {
+                    self.relayer_stats.process_neighbor_ban(neighbor_key);
+                    self.deregister_neighbor(neighbor_key);
+                }
+                Ok(())
+            }
             NetworkRequest::AdvertizeBlocks(blocks, block_data) => {
                 if !(cfg!(test) && self.connection_opts.disable_block_advertisement) {
                     self.advertize_blocks(blocks, block_data)?;
@@ -1173,6 +1189,89 @@ impl PeerNetwork {
         }
     }
 
+    /// Record a strike in the peer database against each neighbor that sent us an invalid block or
+    /// microblock.  A neighbor with more than `block_strike_threshold` strikes within the last
+    /// `block_strike_window` seconds is denied for `block_strike_ban_duration` seconds, and its
+    /// strikes are cleared.  Return the neighbors that were denied, so the caller can disconnect
+    /// from them.
+    fn process_block_strikes(
+        &mut self,
+        neighbor_keys: &[NeighborKey],
+    ) -> Result<Vec<NeighborKey>, net_error> {
+        if cfg!(test) && self.connection_opts.disable_network_bans {
+            return Ok(vec![]);
+        }
+
+        let now = get_epoch_time_secs();
+        let window_start = now.saturating_sub(self.connection_opts.block_strike_window);
+        let mut tx = self.peerdb.tx_begin()?;
+        let mut banned = vec![];
+
+        PeerDB::expire_block_strikes(&mut tx, window_start)?;
+        for neighbor_key in neighbor_keys.iter() {
+            if let Some(neighbor) = PeerDB::get_peer(
+                &tx,
+                neighbor_key.network_id,
+                &neighbor_key.addrbytes,
+                neighbor_key.port,
+            )? {
+                if neighbor.is_allowed() {
+                    debug!(
+                        "Misbehaving neighbor {:?} is allowed; will not strike",
+                        neighbor_key
+                    );
+                    continue;
+                }
+            }
+
+            PeerDB::add_block_strike(
+                &mut tx,
+                neighbor_key.network_id,
+                &neighbor_key.addrbytes,
+                neighbor_key.port,
+                now,
+            )?;
+            let strikes = PeerDB::get_block_strike_count(
+                &tx,
+                neighbor_key.network_id,
+                &neighbor_key.addrbytes,
+                neighbor_key.port,
+                window_start,
+            )?;
+            debug!(
+                "Neighbor {:?} has {} block strike(s) in the last {}s",
+                neighbor_key, strikes, self.connection_opts.block_strike_window
+            );
+
+            if strikes > self.connection_opts.block_strike_threshold {
+                info!(
+                    "Ban peer {:?} for {}s: sent {} invalid blocks or microblocks in {}s",
+                    neighbor_key,
+                    self.connection_opts.block_strike_ban_duration,
+                    strikes,
+                    self.connection_opts.block_strike_window
+                );
+                PeerDB::set_deny_peer(
+                    &mut tx,
+                    neighbor_key.network_id,
+                    &neighbor_key.addrbytes,
+                    neighbor_key.port,
+                    now + self.connection_opts.block_strike_ban_duration,
+                )?;
+                PeerDB::clear_block_strikes(
+                    &mut tx,
+                    neighbor_key.network_id,
+                    &neighbor_key.addrbytes,
+                    neighbor_key.port,
+                )?;
+                banned.push(neighbor_key.clone());
+            }
+        }
+
+        tx.commit()?;
+        Ok(banned)
+    }
+
     /// Process ban requests.  Update the deny in the peer database.  Return the vec of event IDs to disconnect from.
     fn process_bans(&mut self) -> Result<Vec<usize>, net_error> {
         if cfg!(test) && self.connection_opts.disable_network_bans {
@@ -5621,6 +5720,130 @@ mod test {
         })
     }
 
+    #[test]
+    fn test_process_block_strikes() {
+        let mut p2p = make_test_p2p_network(&vec![]);
+        p2p.connection_opts.block_strike_threshold = 2;
+        p2p.connection_opts.block_strike_window = 3600;
+        p2p.connection_opts.block_strike_ban_duration = 1000;
+
+        let bad_neighbor = make_test_neighbor(2500).addr;
+        let allowed_neighbor = make_test_neighbor(2501).addr;
+        {
+            let mut tx = p2p.peerdb.tx_begin().unwrap();
+            PeerDB::set_allow_peer(
+                &mut tx,
+                allowed_neighbor.network_id,
+                &allowed_neighbor.addrbytes,
+                allowed_neighbor.port,
+                -1,
+            )
+            .unwrap();
+            tx.commit().unwrap();
+        }
+
+        // strikes up to the threshold do not ban
+        for strike in 1..=2 {
+            let banned = p2p
+                .process_block_strikes(&[bad_neighbor.clone(), allowed_neighbor.clone()])
+                .unwrap();
+            assert!(banned.is_empty());
+            assert_eq!(
+                PeerDB::get_block_strike_count(
+                    p2p.peerdb.conn(),
+                    bad_neighbor.network_id,
+                    &bad_neighbor.addrbytes,
+                    bad_neighbor.port,
+                    0
+                )
+                .unwrap(),
+                strike
+            );
+            assert!(!PeerDB::is_peer_denied(
+                p2p.peerdb.conn(),
+                bad_neighbor.network_id,
+                &bad_neighbor.addrbytes,
+                bad_neighbor.port
+            )
+            .unwrap());
+        }
+
+        // exceeding the threshold bans the peer and clears its strikes
+        let before_ban = get_epoch_time_secs();
+        let banned = p2p
+            .process_block_strikes(&[bad_neighbor.clone(), allowed_neighbor.clone()])
+            .unwrap();
+        assert_eq!(banned, vec![bad_neighbor.clone()]);
+        assert!(PeerDB::is_peer_denied(
+            p2p.peerdb.conn(),
+            bad_neighbor.network_id,
+            &bad_neighbor.addrbytes,
+            bad_neighbor.port
+        )
+        .unwrap());
+        let denied = PeerDB::get_peer(
+            p2p.peerdb.conn(),
+            bad_neighbor.network_id,
+            &bad_neighbor.addrbytes,
+            bad_neighbor.port,
+        )
+        .unwrap()
+        .unwrap()
+        .denied as u64;
+        assert!(denied >= before_ban + 1000 && denied <= get_epoch_time_secs() + 1000);
+        assert_eq!(
+            PeerDB::get_block_strike_count(
+                p2p.peerdb.conn(),
+                bad_neighbor.network_id,
+                &bad_neighbor.addrbytes,
+                bad_neighbor.port,
+                0
+            )
+            .unwrap(),
+            0
+        );
+
+        // allowed peers are never struck
+        assert_eq!(
+            PeerDB::get_block_strike_count(
+                p2p.peerdb.conn(),
+                allowed_neighbor.network_id,
+                &allowed_neighbor.addrbytes,
+                allowed_neighbor.port,
+                0
+            )
+            .unwrap(),
+            0
+        );
+
+        // strikes outside of the window are forgotten
+        {
+            let mut tx = p2p.peerdb.tx_begin().unwrap();
+            PeerDB::add_block_strike(
+                &mut tx,
+                bad_neighbor.network_id,
+                &bad_neighbor.addrbytes,
+                bad_neighbor.port,
+                before_ban - 7200,
+            )
+            .unwrap();
+            tx.commit().unwrap();
+        }
+        let banned = p2p.process_block_strikes(&[bad_neighbor.clone()]).unwrap();
+        assert!(banned.is_empty());
+        assert_eq!(
+            PeerDB::get_block_strike_count(
+                p2p.peerdb.conn(),
+                bad_neighbor.network_id,
+                &bad_neighbor.addrbytes,
+                bad_neighbor.port,
+                0
+            )
+            .unwrap(),
+            1
+        );
+    }
+
     // tests relay_signed_message()
     #[test]
     #[ignore]
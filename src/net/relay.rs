@@ -816,7 +816,8 @@ impl Relayer {
         let mut bad_neighbors = vec![];
 
         // process blocks pushed to us.
-        // If a neighbor sends us an invalid block, ban them.
+        // If a neighbor sends us an invalid block, strike them.  Valid blocks that we don't
+        // accept (e.g. because they lost a fork) are not strikes.
         for (neighbor_key, blocks_datas) in network_result.pushed_blocks.iter() {
             for blocks_data in blocks_datas.iter() {
                 match Relayer::validate_blocks_push(sort_ic, blocks_data) {
@@ -1013,7 +1014,7 @@ impl Relayer {
 
     /// Preprocess all unconfirmed microblocks pushed to us.
     /// Return the list of MicroblockData messages we need to broadcast to our neighbors, as well
-    /// as the list of neighbors we need to ban because they sent us invalid microblocks.
+    /// as the list of neighbors we need to strike because they sent us invalid microblocks.
     fn preprocess_pushed_microblocks(
         sort_ic: &SortitionDBConn,
         network_result: &mut NetworkResult,
@@ -1026,7 +1027,7 @@ impl Relayer {
         let mut bad_neighbors = vec![];
 
         // process unconfirmed microblocks pushed to us.
-        // If a neighbor sends us bad microblocks, ban them.
+        // If a neighbor sends us bad microblocks, strike them.
         // Remember which ones we _don't_ have, and remember the prior relay hints.
         for (neighbor_key, mblock_datas) in network_result.pushed_microblocks.iter() {
             for (mblock_relayers, mblock_data) in mblock_datas.iter() {
@@ -1339,7 +1340,7 @@ impl Relayer {
     /// * set of consensus hashes that elected the newly-discovered blocks, and the blocks, so we can turn them into BlocksAvailable / BlocksData messages
     /// * set of confirmed microblock consensus hashes for newly-discovered microblock streams, and the streams, so we can turn them into MicroblocksAvailable / MicroblocksData messages
     /// * list of unconfirmed microblocks that got pushed to us, as well as their relayers (so we can forward them)
-    /// * list of neighbors that served us invalid data (so we can ban them)
+    /// * list of neighbors that served us invalid data (so we can strike them)
     pub fn process_new_blocks(
         network_result: &mut NetworkResult,
         sortdb: &mut SortitionDB,
@@ -1579,17 +1580,24 @@ impl Relayer {
             ret.push((vec![], tx.clone()));
         }
 
-        // garbage-collect
-        if chain_height > MEMPOOL_MAX_TRANSACTION_AGE {
-            let min_height = chain_height.saturating_sub(MEMPOOL_MAX_TRANSACTION_AGE);
-            let mut mempool_tx = mempool.tx_begin()?;
-
-            debug!(
-                "Remove all transactions beneath block height {}",
-                min_height
-            );
-            MemPoolDB::garbage_collect(&mut mempool_tx, min_height, event_observer)?;
-            mempool_tx.commit()?;
+        // garbage-collect, if we have a new chain tip or burnchain block
+        let chain_tip_id = StacksBlockHeader::make_index_block_hash(
+            &chain_tip.consensus_hash,
+            &chain_tip.anchored_block_hash,
+        );
+        if let Some(gc_result) = chainstate.with_read_only_clarity_tx(
+            &sortdb.index_conn(),
+            &chain_tip_id,
+            |clarity_tx| {
+                mempool.garbage_collect_at_tip(
+                    &chain_tip_id,
+                    network_result.burn_height,
+                    clarity_tx,
+                    event_observer,
+                )
+            },
+        ) {
+            gc_result?;
         }
         update_stacks_tip_height(chain_height as i64);
 
@@ -1707,7 +1715,7 @@ impl Relayer {
     /// * Forward transactions we didn't already have.
     /// * Reload the unconfirmed state, if necessary.
     /// Mask errors from invalid data -- all errors due to invalid blocks and invalid data should be captured, and
-    /// turned into peer bans.
+    /// turned into peer strikes (and eventually bans).
     pub fn process_network_result(
         &mut self,
         _local_peer: &LocalPeer,
@@ -1730,15 +1738,16 @@ impl Relayer {
                 num_new_unconfirmed_microblocks = new_microblocks.len() as u64;
 
                 // attempt to relay messages (note that this is all best-effort).
-                // punish bad peers
+                // punish bad peers.  Each invalid block or microblock is a strike against the
+                // peer that sent it; the p2p thread bans peers with too many recent strikes.
                 if bad_block_neighbors.len() > 0 {
                     debug!(
-                        "{:?}: Ban {} peers",
+                        "{:?}: Strike {} peers",
                         &_local_peer,
                         bad_block_neighbors.len()
                     );
-                    if let Err(e) = self.p2p.ban_peers(bad_block_neighbors) {
-                        warn!("Failed to ban bad-block peers: {:?}", &e);
+                    if let Err(e) = self.p2p.strike_peers(bad_block_neighbors) {
+                        warn!("Failed to strike bad-block peers: {:?}", &e);
                     }
                 }
 
@@ -4790,6 +4799,142 @@ pub mod test {
         })
     }
 
+    /// Peer 0 pushes peer 1 a valid block, and then blocks that do not match their sortitions.
+    /// The valid block is not a strike against peer 0, but each invalid block is, and peer 1 bans
+    /// peer 0 once it exceeds its strike threshold.
+    #[test]
+    #[ignore]
+    fn test_get_blocks_2_peers_push_invalid_blocks_strikes() {
+        with_timeout(600, || {
+            let mut peer_configs = vec![
+                TestPeerConfig::new(function_name!(), 4252, 4253),
+                TestPeerConfig::new(function_name!(), 4254, 4255),
+            ];
+            for peer_config in peer_configs.iter_mut() {
+                peer_config.burnchain.first_block_height = 0;
+                peer_config.connection_opts.disable_natpunch = true;
+                peer_config.connection_opts.disable_inv_sync = true;
+                peer_config.connection_opts.disable_block_download = true;
+                peer_config.connection_opts.disable_block_advertisement = true;
+                peer_config.connection_opts.max_block_push = 0;
+                peer_config.connection_opts.max_microblock_push = 0;
+            }
+            peer_configs[1].connection_opts.block_strike_threshold = 2;
+            peer_configs[1].connection_opts.block_strike_ban_duration = 3600;
+
+            let peer_0 = peer_configs[0].to_neighbor();
+            let peer_1 = peer_configs[1].to_neighbor();
+            peer_configs[0].add_neighbor(&peer_1);
+            peer_configs[1].add_neighbor(&peer_0);
+
+            let mut peers: Vec<TestPeer> = peer_configs.drain(..).map(TestPeer::new).collect();
+            let peer_0_nk = peer_0.addr;
+            let peer_1_nk = peer_1.addr;
+
+            // peer 0 mines a block; peer 1 only sees its sortition
+            let (mut burn_ops, stacks_block, microblocks) = peers[0].make_default_tenure();
+            let (_, burn_header_hash, consensus_hash) =
+                peers[0].next_burnchain_block(burn_ops.clone());
+            peers[0].process_stacks_epoch_at_tip(&stacks_block, &microblocks);
+            TestPeer::set_ops_burn_header_hash(&mut burn_ops, &burn_header_hash);
+            peers[1].next_burnchain_block_raw(burn_ops);
+
+            let get_strikes = |peer: &TestPeer| {
+                PeerDB::get_block_strike_count(
+                    peer.network.peerdb.conn(),
+                    peer_0_nk.network_id,
+                    &peer_0_nk.addrbytes,
+                    peer_0_nk.port,
+                    0,
+                )
+                .unwrap()
+            };
+            let is_denied = |peer: &TestPeer| {
+                PeerDB::is_peer_denied(
+                    peer.network.peerdb.conn(),
+                    peer_0_nk.network_id,
+                    &peer_0_nk.addrbytes,
+                    peer_0_nk.port,
+                )
+                .unwrap()
+            };
+
+            let mut pushed_valid_block = false;
+            let mut num_invalid_blocks = 0u8;
+            let mut round = 0;
+            while !is_denied(&peers[1]) {
+                assert!(round < 1000, "peer 1 never banned peer 0");
+                round += 1;
+
+                for peer in peers.iter_mut() {
+                    let mut result = peer.step().unwrap();
+                    let lp = peer.network.local_peer.clone();
+                    peer.with_db_state(|sortdb, chainstate, relayer, mempool| {
+                        relayer.process_network_result(
+                            &lp,
+                            &mut result,
+                            sortdb,
+                            chainstate,
+                            mempool,
+                            false,
+                            None,
+                            None,
+                        )
+                    })
+                    .unwrap();
+                }
+
+                if !is_peer_connected(&peers[0], &peer_1_nk) {
+                    continue;
+                }
+
+                let has_valid_block = StacksChainState::get_staging_block_status(
+                    peers[1].chainstate().db(),
+                    &consensus_hash,
+                    &stacks_block.block_hash(),
+                )
+                .unwrap()
+                .is_some();
+
+                if !has_valid_block {
+                    // an honest push
+                    if !pushed_valid_block {
+                        pushed_valid_block = push_block(
+                            &mut peers[0],
+                            &peer_1_nk,
+                            vec![],
+                            consensus_hash.clone(),
+                            stacks_block.clone(),
+                        );
+                    }
+                    continue;
+                }
+
+                if num_invalid_blocks == 0 {
+                    // storing the valid block did not count against peer 0
+                    assert_eq!(get_strikes(&peers[1]), 0);
+                }
+
+                // a block that does not match the sortition
+                let mut invalid_block = stacks_block.clone();
+                invalid_block.header.state_index_root = TrieHash([num_invalid_blocks; 32]);
+                if push_block(
+                    &mut peers[0],
+                    &peer_1_nk,
+                    vec![],
+                    consensus_hash.clone(),
+                    invalid_block,
+                ) {
+                    num_invalid_blocks += 1;
+                }
+            }
+
+            // peer 0 was banned on its third strike, and its strikes were cleared
+            assert!(num_invalid_blocks >= 3);
+            assert_eq!(get_strikes(&peers[1]), 0);
+        })
+    }
+
     pub fn make_contract_tx(
         sender: &StacksPrivateKey,
         cur_nonce: u64,
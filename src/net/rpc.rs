@@ -17,6 +17,7 @@
  along with Blockstack. If not, see <http://www.gnu.org/licenses/>.
 */
 
+use std::cmp;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
@@ -24,7 +25,7 @@ use std::io;
 use std::io::prelude::*;
 use std::io::{Read, Seek, SeekFrom, Write};
 use std::net::SocketAddr;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use std::{convert::TryFrom, fmt};
 
 use rand::prelude::*;
@@ -36,11 +37,13 @@ use crate::burnchains::Burnchain;
 use crate::burnchains::BurnchainView;
 use crate::burnchains::*;
 use crate::chainstate::burn::db::sortdb::SortitionDB;
-use crate::chainstate::burn::ConsensusHash;
+use crate::chainstate::burn::{BlockSnapshot, ConsensusHash};
 use crate::chainstate::stacks::db::blocks::CheckError;
+use crate::chainstate::stacks::db::blocks::MemPoolRejection;
 use crate::chainstate::stacks::db::{
     blocks::MINIMUM_TX_FEE_RATE_PER_BYTE, StacksChainState, StreamCursor,
 };
+use crate::chainstate::stacks::miner::StacksMicroblockBuilder;
 use crate::chainstate::stacks::Error as chain_error;
 use crate::chainstate::stacks::*;
 use crate::clarity_vm::clarity::ClarityConnection;
@@ -48,6 +51,7 @@ use crate::codec::StacksMessageCodec;
 use crate::core::mempool::*;
 use crate::cost_estimates::metrics::CostMetric;
 use crate::cost_estimates::CostEstimator;
+use crate::cost_estimates::EstimatorError;
 use crate::cost_estimates::FeeEstimator;
 use crate::monitoring;
 use crate::net::atlas::{AtlasDB, Attachment, MAX_ATTACHMENT_INV_PAGES_PER_REQUEST};
@@ -59,6 +63,7 @@ use crate::net::http::*;
 use crate::net::p2p::PeerMap;
 use crate::net::p2p::PeerNetwork;
 use crate::net::relay::Relayer;
+use crate::net::BlockProposalValidationResponse;
 use crate::net::BlocksDatum;
 use crate::net::Error as net_error;
 use crate::net::HttpRequestMetadata;
@@ -66,17 +71,22 @@ use crate::net::HttpRequestType;
 use crate::net::HttpResponseMetadata;
 use crate::net::HttpResponseType;
 use crate::net::MemPoolSyncData;
+use crate::net::MempoolTransactionEntry;
+use crate::net::MicroblockStreamValidationResponse;
 use crate::net::MicroblocksData;
 use crate::net::NeighborAddress;
 use crate::net::NeighborsData;
 use crate::net::PeerAddress;
 use crate::net::PeerHost;
+use crate::net::PostTransactionBatchEntry;
+use crate::net::PostTransactionError;
 use crate::net::ProtocolFamily;
 use crate::net::RPCFeeEstimate;
 use crate::net::RPCFeeEstimateResponse;
 use crate::net::StacksHttp;
 use crate::net::StacksHttpMessage;
 use crate::net::StacksMessageType;
+use crate::net::TransactionDryRunResponse;
 use crate::net::UnconfirmedTransactionResponse;
 use crate::net::UnconfirmedTransactionStatus;
 use crate::net::UrlString;
@@ -84,18 +94,22 @@ use crate::net::HTTP_REQUEST_ID_RESERVED;
 use crate::net::MAX_HEADERS;
 use crate::net::MAX_NEIGHBORS_DATA_LEN;
 use crate::net::{
-    AccountEntryResponse, AttachmentPage, CallReadOnlyResponse, ContractSrcResponse,
-    DataVarResponse, GetAttachmentResponse, GetAttachmentsInvResponse, MapEntryResponse,
+    AccountEntryResponse, AccountStackingInfo, AccountStackingResponse, AttachmentPage,
+    CallReadOnlyResponse, ContractSrcResponse, DataVarResponse, GetAttachmentResponse,
+    GetAttachmentsInvResponse, MapEntryResponse,
 };
 use crate::net::{BlocksData, GetIsTraitImplementedResponse};
 use crate::net::{ClientError, TipRequest};
 use crate::net::{
-    RPCAffirmationData, RPCLastPoxAnchorData, RPCPeerInfoData, RPCPoxContractVersion,
-    RPCPoxInfoData,
+    RPCAffirmationData, RPCLastPoxAnchorData, RPCMinerInfoData, RPCPeerInfoData,
+    RPCPoxContractVersion, RPCPoxInfoData, RPCPoxRewardAddress, RPCPoxRewardCycleInfo,
+    RPCPoxSunsetBurnInfo,
 };
+use crate::net::{RPCBlockCommitData, RPCBlockCommitInfo};
 use crate::net::{RPCNeighbor, RPCNeighborsInfo};
 use crate::util_lib::db::DBConn;
 use crate::util_lib::db::Error as db_error;
+use clarity::vm::contexts::OwnedEnvironment;
 use clarity::vm::database::clarity_store::make_contract_hash_key;
 use clarity::vm::types::TraitIdentifier;
 use clarity::vm::ClarityVersion;
@@ -110,13 +124,15 @@ use clarity::vm::{
     errors::Error as ClarityRuntimeError,
     errors::Error::Unchecked,
     errors::InterpreterError,
-    types::{PrincipalData, QualifiedContractIdentifier, StandardPrincipalData},
-    ClarityName, ContractName, SymbolicExpression, Value,
+    types::{PrincipalData, QualifiedContractIdentifier, StandardPrincipalData, TupleData},
+    ClarityName, ContractContext, ContractName, Environment, EvalHook, ExecutionResult,
+    LocalContext, SymbolicExpression, Value,
 };
 use stacks_common::util::get_epoch_time_secs;
 use stacks_common::util::hash::Hash160;
 use stacks_common::util::hash::{hex_bytes, to_hex};
 
+use crate::chainstate::stacks::address::PoxAddress;
 use crate::chainstate::stacks::boot::{POX_1_NAME, POX_2_NAME, POX_3_NAME};
 use crate::chainstate::stacks::StacksBlockHeader;
 use crate::clarity_vm::database::marf::MarfedKV;
@@ -145,6 +161,8 @@ pub struct RPCHandlerArgs<'a> {
     pub cost_estimator: Option<&'a dyn CostEstimator>,
     pub fee_estimator: Option<&'a dyn FeeEstimator>,
     pub cost_metric: Option<&'a dyn CostMetric>,
+    /// what the node's miner last reported, for GET /v2/info
+    pub miner_info: Option<RPCMinerInfoData>,
 }
 
 pub struct ConversationHttp {
@@ -273,6 +291,7 @@ impl RPCPeerInfoData {
                 anchor_block_hash: network.last_anchor_block_hash.clone(),
                 anchor_block_txid: network.last_anchor_block_txid.clone(),
             }),
+            miner: None,
         }
     }
 }
@@ -612,6 +631,57 @@ impl RPCNeighborsInfo {
     }
 }
 
+/// Wall-clock deadline for a transaction dry-run.  Clarity evaluation can't be interrupted from
+/// the outside, so once the deadline passes, this hook exhausts the call's cost budget and the
+/// next cost charge aborts it.
+struct DryRunDeadline {
+    deadline: Instant,
+    timed_out: bool,
+    /// cost spent by the call up to the deadline
+    spent: Option<ExecutionCost>,
+}
+
+impl DryRunDeadline {
+    fn new(timeout_ms: u64) -> DryRunDeadline {
+        DryRunDeadline {
+            deadline: Instant::now() + Duration::from_millis(timeout_ms),
+            timed_out: false,
+            spent: None,
+        }
+    }
+}
+
+impl EvalHook for DryRunDeadline {
+    fn will_begin_eval(
+        &mut self,
+        env: &mut Environment,
+        _context: &LocalContext,
+        _expr: &SymbolicExpression,
+    ) {
+        if !self.timed_out {
+            if Instant::now() < self.deadline {
+                return;
+            }
+            self.timed_out = true;
+            self.spent = Some(env.global_context.cost_track.get_total());
+        }
+        env.global_context
+            .cost_track
+            .set_total(ExecutionCost::max_value());
+    }
+
+    fn did_finish_eval(
+        &mut self,
+        _env: &mut Environment,
+        _context: &LocalContext,
+        _expr: &SymbolicExpression,
+        _res: &Result<Value, ClarityRuntimeError>,
+    ) {
+    }
+
+    fn did_complete(&mut self, _result: Result<&mut ExecutionResult, String>) {}
+}
+
 impl ConversationHttp {
     pub fn new(
         peer_addr: SocketAddr,
@@ -752,12 +822,13 @@ impl ConversationHttp {
     ) -> Result<(), net_error> {
         let response_metadata =
             HttpResponseMetadata::from_http_request_type(req, Some(canonical_stacks_tip_height));
-        let pi = RPCPeerInfoData::from_network(
+        let mut pi = RPCPeerInfoData::from_network(
             network,
             chainstate,
             handler_args.exit_at_block_height.clone(),
             &handler_args.genesis_chainstate_hash,
         );
+        pi.miner = handler_args.miner_info.clone();
         let response = HttpResponseType::PeerInfo(response_metadata, pi);
         response.send(http, fd)
     }
@@ -801,6 +872,299 @@ impl ConversationHttp {
         }
     }
 
+    /// Handle a GET for the PoX reward cycle containing a given burnchain block height.
+    /// The reward set is read as of the given Stacks chain tip.
+    /// The response will be synchronously written to the given fd (so use a fd that can buffer!)
+    fn handle_get_pox_reward_cycle<W: Write>(
+        http: &mut StacksHttp,
+        fd: &mut W,
+        req: &HttpRequestType,
+        sortdb: &SortitionDB,
+        chainstate: &mut StacksChainState,
+        tip: &StacksBlockId,
+        burnchain: &Burnchain,
+        burn_height: u64,
+        canonical_stacks_tip_height: u64,
+    ) -> Result<(), net_error> {
+        let response_metadata =
+            HttpResponseMetadata::from_http_request_type(req, Some(canonical_stacks_tip_height));
+
+        let cycle_number = match burnchain.block_height_to_reward_cycle(burn_height) {
+            Some(cycle) => cycle,
+            None => {
+                let msg = format!(
+                    "Burn block height {} is before the first burnchain block height {}",
+                    burn_height, burnchain.first_block_height
+                );
+                let response = HttpResponseType::BadRequest(response_metadata, msg);
+                return response.send(http, fd);
+            }
+        };
+
+        let start_height = burnchain.reward_cycle_to_block_height(cycle_number);
+        let end_height = start_height + u64::from(burnchain.pox_constants.reward_cycle_length) - 1;
+        let is_prepare_phase = burnchain.is_in_prepare_phase(burn_height);
+
+        match chainstate.get_reward_addresses(burnchain, sortdb, burn_height, tip) {
+            Ok(entries) => {
+                let reward_addresses = entries
+                    .into_iter()
+                    .map(|entry| RPCPoxRewardAddress {
+                        address: entry.reward_address.to_b58(),
+                        amount: entry.amount_stacked,
+                    })
+                    .collect();
+                let response = HttpResponseType::PoxRewardCycle(
+                    response_metadata,
+                    RPCPoxRewardCycleInfo {
+                        cycle_number,
+                        start_height,
+                        end_height,
+                        is_prepare_phase,
+                        reward_addresses,
+                    },
+                );
+                response.send(http, fd)
+            }
+            Err(e) => {
+                warn!(
+                    "Failed to get reward set for reward cycle {} {:?}: {:?}",
+                    cycle_number, req, &e
+                );
+                let response = HttpResponseType::ServerError(
+                    response_metadata,
+                    "Failed to query reward set".to_string(),
+                );
+                response.send(http, fd)
+            }
+        }
+    }
+
+    /// Handle a GET for the sunset burn a block-commit at the given burnchain block height must
+    /// carry, given the total amount the miner is willing to commit.
+    /// The response will be synchronously written to the given fd (so use a fd that can buffer!)
+    fn handle_get_pox_sunset_burn<W: Write>(
+        http: &mut StacksHttp,
+        fd: &mut W,
+        req: &HttpRequestType,
+        sortdb: &SortitionDB,
+        burnchain: &Burnchain,
+        burn_height: u64,
+        burn_fee_cap: u64,
+        canonical_stacks_tip_height: u64,
+    ) -> Result<(), net_error> {
+        let response_metadata =
+            HttpResponseMetadata::from_http_request_type(req, Some(canonical_stacks_tip_height));
+
+        if burn_height > burnchain.pox_constants.sunset_end {
+            let msg = format!(
+                "Burn block height {} is beyond the PoX sunset end height {}",
+                burn_height, burnchain.pox_constants.sunset_end
+            );
+            let response = HttpResponseType::BadRequest(response_metadata, msg);
+            return response.send(http, fd);
+        }
+
+        match SortitionDB::get_stacks_epoch(sortdb.conn(), burn_height) {
+            Ok(Some(epoch)) => {
+                let response = HttpResponseType::PoxSunsetBurn(
+                    response_metadata,
+                    RPCPoxSunsetBurnInfo {
+                        burn_block_height: burn_height,
+                        burn_fee_cap,
+                        expected_sunset_burn: burnchain.expected_sunset_burn(
+                            burn_height,
+                            burn_fee_cap,
+                            epoch.epoch_id,
+                        ),
+                        is_prepare_phase: burnchain.is_in_prepare_phase(burn_height),
+                    },
+                );
+                response.send(http, fd)
+            }
+            Ok(None) => {
+                let msg = format!("No epoch defined at burn block height {}", burn_height);
+                let response = HttpResponseType::BadRequest(response_metadata, msg);
+                response.send(http, fd)
+            }
+            Err(e) => {
+                warn!("Failed to load epoch {:?}: {:?}", req, &e);
+                let response = HttpResponseType::ServerError(
+                    response_metadata,
+                    "Failed to query epoch".to_string(),
+                );
+                response.send(http, fd)
+            }
+        }
+    }
+
+    /// Send the block-commit that won sortition in the given snapshot's burnchain block, if any.
+    fn send_block_commit_info<W: Write>(
+        http: &mut StacksHttp,
+        fd: &mut W,
+        req: &HttpRequestType,
+        response_metadata: HttpResponseMetadata,
+        sortdb: &SortitionDB,
+        snapshot: &BlockSnapshot,
+    ) -> Result<(), net_error> {
+        let winning_block_commit = if snapshot.sortition {
+            match SortitionDB::get_block_commit(
+                sortdb.conn(),
+                &snapshot.winning_block_txid,
+                &snapshot.sortition_id,
+            ) {
+                Ok(commit_opt) => commit_opt.map(|commit| RPCBlockCommitData {
+                    txid: commit.txid,
+                    vtxindex: commit.vtxindex,
+                    block_header_hash: commit.block_header_hash,
+                    burn_fee: commit.burn_fee,
+                    sunset_burn: commit.sunset_burn,
+                    commit_outs: commit
+                        .commit_outs
+                        .into_iter()
+                        .map(|out| out.to_b58())
+                        .collect(),
+                    parent_block_ptr: commit.parent_block_ptr,
+                    parent_vtxindex: commit.parent_vtxindex,
+                    key_block_ptr: commit.key_block_ptr,
+                    key_vtxindex: commit.key_vtxindex,
+                }),
+                Err(e) => {
+                    warn!("Failed to load block-commit {:?}: {:?}", req, &e);
+                    let response = HttpResponseType::ServerError(
+                        response_metadata,
+                        "Failed to query block-commit".to_string(),
+                    );
+                    return response.send(http, fd);
+                }
+            }
+        } else {
+            None
+        };
+
+        let response = HttpResponseType::BlockCommitInfo(
+            response_metadata,
+            RPCBlockCommitInfo {
+                burn_block_height: snapshot.block_height,
+                burn_header_hash: snapshot.burn_header_hash.clone(),
+                consensus_hash: snapshot.consensus_hash.clone(),
+                winning_block_commit,
+            },
+        );
+        response.send(http, fd)
+    }
+
+    /// Handle a GET for the block-commit that won sortition at the given burnchain block height
+    /// on the canonical burnchain fork.
+    /// The response will be synchronously written to the given fd (so use a fd that can buffer!)
+    fn handle_get_block_commit_by_height<W: Write>(
+        http: &mut StacksHttp,
+        fd: &mut W,
+        req: &HttpRequestType,
+        sortdb: &SortitionDB,
+        burn_height: u64,
+        canonical_stacks_tip_height: u64,
+    ) -> Result<(), net_error> {
+        let response_metadata =
+            HttpResponseMetadata::from_http_request_type(req, Some(canonical_stacks_tip_height));
+
+        let snapshot_res =
+            SortitionDB::get_canonical_burn_chain_tip(sortdb.conn()).and_then(|tip| {
+                if burn_height > tip.block_height {
+                    return Ok(None);
+                }
+                SortitionDB::get_ancestor_snapshot(
+                    &sortdb.index_conn(),
+                    burn_height,
+                    &tip.sortition_id,
+                )
+            });
+
+        match snapshot_res {
+            Ok(Some(snapshot)) => ConversationHttp::send_block_commit_info(
+                http,
+                fd,
+                req,
+                response_metadata,
+                sortdb,
+                &snapshot,
+            ),
+            Ok(None) => {
+                let msg = format!("No burnchain block at height {}", burn_height);
+                let response = HttpResponseType::NotFound(response_metadata, msg);
+                response.send(http, fd)
+            }
+            Err(e) => {
+                warn!("Failed to load snapshot {:?}: {:?}", req, &e);
+                let response = HttpResponseType::ServerError(
+                    response_metadata,
+                    "Failed to query snapshot".to_string(),
+                );
+                response.send(http, fd)
+            }
+        }
+    }
+
+    /// Handle a GET for the block-commit that produced the given Stacks block.
+    /// The response will be synchronously written to the given fd (so use a fd that can buffer!)
+    fn handle_get_block_commit_by_stacks_block<W: Write>(
+        http: &mut StacksHttp,
+        fd: &mut W,
+        req: &HttpRequestType,
+        sortdb: &SortitionDB,
+        chainstate: &StacksChainState,
+        index_block_hash: &StacksBlockId,
+        canonical_stacks_tip_height: u64,
+    ) -> Result<(), net_error> {
+        let response_metadata =
+            HttpResponseMetadata::from_http_request_type(req, Some(canonical_stacks_tip_height));
+
+        let header_info = match StacksChainState::get_stacks_block_header_info_by_index_block_hash(
+            chainstate.db(),
+            index_block_hash,
+        ) {
+            Ok(Some(header_info)) => header_info,
+            Ok(None) => {
+                let msg = format!("No such Stacks block {}", index_block_hash);
+                let response = HttpResponseType::NotFound(response_metadata, msg);
+                return response.send(http, fd);
+            }
+            Err(e) => {
+                warn!("Failed to load Stacks block header {:?}: {:?}", req, &e);
+                let response = HttpResponseType::ServerError(
+                    response_metadata,
+                    "Failed to query Stacks block header".to_string(),
+                );
+                return response.send(http, fd);
+            }
+        };
+
+        match SortitionDB::get_block_snapshot_consensus(sortdb.conn(), &header_info.consensus_hash)
+        {
+            Ok(Some(snapshot)) => ConversationHttp::send_block_commit_info(
+                http,
+                fd,
+                req,
+                response_metadata,
+                sortdb,
+                &snapshot,
+            ),
+            Ok(None) => {
+                let msg = format!("No snapshot for Stacks block {}", index_block_hash);
+                let response = HttpResponseType::NotFound(response_metadata, msg);
+                response.send(http, fd)
+            }
+            Err(e) => {
+                warn!("Failed to load snapshot {:?}: {:?}", req, &e);
+                let response = HttpResponseType::ServerError(
+                    response_metadata,
+                    "Failed to query snapshot".to_string(),
+                );
+                response.send(http, fd)
+            }
+        }
+    }
+
     fn handle_getattachmentsinv<W: Write>(
         http: &mut StacksHttp,
         fd: &mut W,
@@ -1040,71 +1404,277 @@ impl ConversationHttp {
         }
     }
 
-    /// Handle a GET confirmed microblock stream, by _anchor block hash_.  Start streaming the reply.
+    /// Find the anchored blocks that won sortition at each burnchain height in
+    /// [start_height, end_height] on the canonical burnchain fork, and that we have stored.
+    /// Returns their index block hashes in height order.
+    fn load_block_stream_ids(
+        sortdb: &SortitionDB,
+        chainstate: &StacksChainState,
+        start_height: u64,
+        end_height: u64,
+    ) -> Result<Vec<StacksBlockId>, net_error> {
+        let tip = SortitionDB::get_canonical_burn_chain_tip(sortdb.conn())?;
+        let mut index_block_hashes = vec![];
+        if start_height > tip.block_height {
+            return Ok(index_block_hashes);
+        }
+        let ic = sortdb.index_conn();
+        for burn_height in start_height..=end_height.min(tip.block_height) {
+            let snapshot =
+                match SortitionDB::get_ancestor_snapshot(&ic, burn_height, &tip.sortition_id)? {
+                    Some(sn) => sn,
+                    None => {
+                        continue;
+                    }
+                };
+            if !snapshot.sortition {
+                continue;
+            }
+            let index_block_hash = StacksBlockHeader::make_index_block_hash(
+                &snapshot.consensus_hash,
+                &snapshot.winning_stacks_block_hash,
+            );
+            if StacksChainState::has_valid_block_indexed(
+                &chainstate.blocks_path,
+                &index_block_hash,
+            )? {
+                index_block_hashes.push(index_block_hash);
+            }
+        }
+        Ok(index_block_hashes)
+    }
+
+    /// Handle a GET block stream over a range of burnchain block heights.  Start streaming the
+    /// reply, which is every block we have that won sortition in that range on the canonical
+    /// burnchain fork, in height order, each preceded by its 4-byte big-endian length.
     /// The response's preamble (but not the block data) will be synchronously written to the fd
     /// (so use a fd that can buffer!)
-    /// Return a StreamCursor struct for the block that we're sending, so we can continue to
-    /// make progress sending it.
-    fn handle_getmicroblocks_confirmed<W: Write>(
+    /// Return a StreamCursor struct for the blocks that we're sending, so we can continue to
+    /// make progress sending them.
+    fn handle_get_block_stream<W: Write>(
         http: &mut StacksHttp,
         fd: &mut W,
         req: &HttpRequestType,
-        index_anchor_block_hash: &StacksBlockId,
+        sortdb: &SortitionDB,
         chainstate: &StacksChainState,
+        start_height: u64,
+        end_height: u64,
         canonical_stacks_tip_height: u64,
     ) -> Result<Option<StreamCursor>, net_error> {
-        monitoring::increment_stx_confirmed_micro_blocks_served_counter();
         let response_metadata =
             HttpResponseMetadata::from_http_request_type(req, Some(canonical_stacks_tip_height));
 
-        match chainstate.has_processed_microblocks(index_anchor_block_hash) {
-            Ok(true) => {}
-            Ok(false) => {
-                return ConversationHttp::handle_notfound(
-                    http,
-                    fd,
-                    response_metadata,
-                    format!(
-                        "No such confirmed microblock stream for anchor block {}",
-                        &index_anchor_block_hash
-                    ),
-                );
+        match ConversationHttp::load_block_stream_ids(sortdb, chainstate, start_height, end_height)
+        {
+            Ok(index_block_hashes) => {
+                for _ in 0..index_block_hashes.len() {
+                    monitoring::increment_stx_blocks_served_counter();
+                }
+                let stream = StreamCursor::new_blocks(index_block_hashes);
+                let response = HttpResponseType::BlockRangeStream(response_metadata);
+                response.send(http, fd).and_then(|_| Ok(Some(stream)))
             }
             Err(e) => {
-                return ConversationHttp::handle_server_error(
-                    http,
-                    fd,
+                warn!("Failed to serve block stream {:?}: {:?}", req, &e);
+                let response = HttpResponseType::ServerError(
                     response_metadata,
                     format!(
-                        "Failed to query confirmed microblock stream {:?}: {:?}",
-                        req, &e
+                        "Failed to query blocks between burnchain heights {} and {}",
+                        start_height, end_height
                     ),
                 );
+                response.send(http, fd).and_then(|_| Ok(None))
             }
         }
+    }
 
-        match chainstate.get_confirmed_microblock_index_hash(index_anchor_block_hash) {
+    /// Load an anchored block and the microblock stream it confirmed (which is empty if it
+    /// confirmed none).  Returns Ok(None) if we don't have the block, or it is known to be invalid.
+    fn load_block_with_microblocks(
+        chainstate: &StacksChainState,
+        index_block_hash: &StacksBlockId,
+    ) -> Result<Option<(StacksBlock, Vec<StacksMicroblock>)>, chain_error> {
+        let header_info = match StacksChainState::get_stacks_block_header_info_by_index_block_hash(
+            chainstate.db(),
+            index_block_hash,
+        )? {
+            Some(header_info) => header_info,
+            None => {
+                return Ok(None);
+            }
+        };
+        let block = match StacksChainState::load_block(
+            &chainstate.blocks_path,
+            &header_info.consensus_hash,
+            &header_info.anchored_header.block_hash(),
+        ) {
+            Ok(Some(block)) => block,
+            Ok(None) | Err(chain_error::DBError(db_error::NotFoundError)) => {
+                return Ok(None);
+            }
             Err(e) => {
-                return ConversationHttp::handle_server_error(
-                    http,
-                    fd,
-                    response_metadata,
-                    format!(
-                        "Failed to serve confirmed microblock stream {:?}: {:?}",
-                        req, &e
-                    ),
-                );
+                return Err(e);
             }
-            Ok(None) => {
-                return ConversationHttp::handle_notfound(
-                    http,
-                    fd,
-                    response_metadata,
-                    format!(
-                        "No such confirmed microblock stream for anchor block {}",
-                        &index_anchor_block_hash
-                    ),
-                );
+        };
+        if !block.header.has_microblock_parent() {
+            return Ok(Some((block, vec![])));
+        }
+
+        let microblocks = match StacksChainState::get_parent_block_header_hashes(
+            chainstate.db(),
+            index_block_hash,
+        )? {
+            Some((parent_consensus_hash, parent_block_hash)) => {
+                StacksChainState::load_processed_microblock_stream_fork(
+                    chainstate.db(),
+                    &parent_consensus_hash,
+                    &parent_block_hash,
+                    &block.header.parent_microblock,
+                )?
+                .unwrap_or(vec![])
+            }
+            None => vec![],
+        };
+        Ok(Some((block, microblocks)))
+    }
+
+    /// Handle a GET block by height.  The height is resolved against the canonical Stacks
+    /// tip, so blocks on other forks are never served.  If `unanchored` is false, the block is
+    /// streamed back just as it is for a GET block by hash.  Otherwise, the reply is the block
+    /// followed by the microblock stream it confirmed, and is written out in full.
+    /// Return a StreamCursor struct for the block if we're streaming it, so we can continue to
+    /// make progress sending it.
+    fn handle_getblock_by_height<W: Write>(
+        http: &mut StacksHttp,
+        fd: &mut W,
+        req: &HttpRequestType,
+        height: u64,
+        unanchored: bool,
+        sortdb: &SortitionDB,
+        chainstate: &mut StacksChainState,
+        canonical_stacks_tip_height: u64,
+    ) -> Result<Option<StreamCursor>, net_error> {
+        let index_block_hash = match ConversationHttp::handle_load_stacks_chain_tip(
+            http,
+            fd,
+            req,
+            &TipRequest::CanonicalAncestorAtHeight(height),
+            sortdb,
+            chainstate,
+            canonical_stacks_tip_height,
+        )? {
+            Some(index_block_hash) => index_block_hash,
+            None => {
+                // already replied
+                return Ok(None);
+            }
+        };
+
+        if !unanchored {
+            return ConversationHttp::handle_getblock(
+                http,
+                fd,
+                req,
+                &index_block_hash,
+                chainstate,
+                canonical_stacks_tip_height,
+            );
+        }
+
+        monitoring::increment_stx_blocks_served_counter();
+        let response_metadata =
+            HttpResponseMetadata::from_http_request_type(req, Some(canonical_stacks_tip_height));
+        match ConversationHttp::load_block_with_microblocks(chainstate, &index_block_hash) {
+            Ok(Some((block, microblocks))) => {
+                let response =
+                    HttpResponseType::BlockWithMicroblocks(response_metadata, block, microblocks);
+                response.send(http, fd).and_then(|_| Ok(None))
+            }
+            Ok(None) => ConversationHttp::handle_notfound(
+                http,
+                fd,
+                response_metadata,
+                format!("No such block {}", index_block_hash.to_hex()),
+            ),
+            Err(e) => ConversationHttp::handle_server_error(
+                http,
+                fd,
+                response_metadata,
+                format!(
+                    "Failed to load block {} at height {}: {:?}",
+                    index_block_hash.to_hex(),
+                    height,
+                    &e
+                ),
+            ),
+        }
+    }
+
+    /// Handle a GET confirmed microblock stream, by _anchor block hash_.  Start streaming the reply.
+    /// The response's preamble (but not the block data) will be synchronously written to the fd
+    /// (so use a fd that can buffer!)
+    /// Return a StreamCursor struct for the block that we're sending, so we can continue to
+    /// make progress sending it.
+    fn handle_getmicroblocks_confirmed<W: Write>(
+        http: &mut StacksHttp,
+        fd: &mut W,
+        req: &HttpRequestType,
+        index_anchor_block_hash: &StacksBlockId,
+        chainstate: &StacksChainState,
+        canonical_stacks_tip_height: u64,
+    ) -> Result<Option<StreamCursor>, net_error> {
+        monitoring::increment_stx_confirmed_micro_blocks_served_counter();
+        let response_metadata =
+            HttpResponseMetadata::from_http_request_type(req, Some(canonical_stacks_tip_height));
+
+        match chainstate.has_processed_microblocks(index_anchor_block_hash) {
+            Ok(true) => {}
+            Ok(false) => {
+                return ConversationHttp::handle_notfound(
+                    http,
+                    fd,
+                    response_metadata,
+                    format!(
+                        "No such confirmed microblock stream for anchor block {}",
+                        &index_anchor_block_hash
+                    ),
+                );
+            }
+            Err(e) => {
+                return ConversationHttp::handle_server_error(
+                    http,
+                    fd,
+                    response_metadata,
+                    format!(
+                        "Failed to query confirmed microblock stream {:?}: {:?}",
+                        req, &e
+                    ),
+                );
+            }
+        }
+
+        match chainstate.get_confirmed_microblock_index_hash(index_anchor_block_hash) {
+            Err(e) => {
+                return ConversationHttp::handle_server_error(
+                    http,
+                    fd,
+                    response_metadata,
+                    format!(
+                        "Failed to serve confirmed microblock stream {:?}: {:?}",
+                        req, &e
+                    ),
+                );
+            }
+            Ok(None) => {
+                return ConversationHttp::handle_notfound(
+                    http,
+                    fd,
+                    response_metadata,
+                    format!(
+                        "No such confirmed microblock stream for anchor block {}",
+                        &index_anchor_block_hash
+                    ),
+                );
             }
             Ok(Some(tail_index_microblock_hash)) => {
                 let (response, stream_opt) = match StreamCursor::new_microblock_confirmed(
@@ -1254,14 +1824,170 @@ impl ConversationHttp {
         response.send(http, fd).map(|_| ())
     }
 
+    /// Handle a GET for the node's current fee rate estimates.  Reply the entire response.
+    /// If the fee estimator has not yet seen enough blocks to produce an estimate, a 503 is
+    /// returned.
+    fn handle_get_fee_rate<W: Write>(
+        http: &mut StacksHttp,
+        fd: &mut W,
+        req: &HttpRequestType,
+        handler_args: &RPCHandlerArgs,
+        canonical_stacks_tip_height: u64,
+    ) -> Result<(), net_error> {
+        let response_metadata =
+            HttpResponseMetadata::from_http_request_type(req, Some(canonical_stacks_tip_height));
+
+        let fee_estimator = match handler_args.fee_estimator {
+            Some(fee_estimator) => fee_estimator,
+            None => {
+                debug!("Fee estimation not configured on this stacks node");
+                let response = HttpResponseType::BadRequestJSON(
+                    response_metadata,
+                    json!({
+                        "error": "Fee Estimation not configured on this Stacks node",
+                        "reason": "FeeEstimationDisabled",
+                    }),
+                );
+                return response.send(http, fd);
+            }
+        };
+
+        let response = match fee_estimator.get_rate_estimates() {
+            Ok(fee_rates) => HttpResponseType::FeeRate(response_metadata, fee_rates),
+            Err(e @ EstimatorError::NoEstimateAvailable) => {
+                debug!("Estimator RPC endpoint has no fee rate estimate yet");
+                HttpResponseType::ServiceUnavailableJSON(response_metadata, e.into_json())
+            }
+            Err(e) => {
+                warn!(
+                    "Estimator RPC endpoint failed to load fee rate estimates: {}",
+                    e
+                );
+                HttpResponseType::ServerError(response_metadata, e.to_string())
+            }
+        };
+        response.send(http, fd)
+    }
+
+    /// Handle a GET for the node's current fee rate estimates at each of `percentiles`.  Reply
+    /// the entire response.  If the fee estimator cannot produce an estimate at every requested
+    /// percentile, a 503 is returned.
+    fn handle_get_fee_rate_percentiles<W: Write>(
+        http: &mut StacksHttp,
+        fd: &mut W,
+        req: &HttpRequestType,
+        percentiles: &[u8],
+        handler_args: &RPCHandlerArgs,
+        canonical_stacks_tip_height: u64,
+    ) -> Result<(), net_error> {
+        let response_metadata =
+            HttpResponseMetadata::from_http_request_type(req, Some(canonical_stacks_tip_height));
+
+        let fee_estimator = match handler_args.fee_estimator {
+            Some(fee_estimator) => fee_estimator,
+            None => {
+                debug!("Fee estimation not configured on this stacks node");
+                let response = HttpResponseType::BadRequestJSON(
+                    response_metadata,
+                    json!({
+                        "error": "Fee Estimation not configured on this Stacks node",
+                        "reason": "FeeEstimationDisabled",
+                    }),
+                );
+                return response.send(http, fd);
+            }
+        };
+
+        let response = match fee_estimator.get_rate_estimates_at(percentiles) {
+            Ok(fee_rates) => HttpResponseType::FeeRatePercentiles(response_metadata, fee_rates),
+            Err(e @ EstimatorError::NoEstimateAvailable) => {
+                debug!("Estimator RPC endpoint has no fee rate estimate at the requested percentiles";
+                       "percentiles" => ?percentiles);
+                HttpResponseType::ServiceUnavailableJSON(response_metadata, e.into_json())
+            }
+            Err(e) => {
+                warn!(
+                    "Estimator RPC endpoint failed to load fee rate estimates: {}",
+                    e
+                );
+                HttpResponseType::ServerError(response_metadata, e.to_string())
+            }
+        };
+        response.send(http, fd)
+    }
+
+    /// Load an account's nonce in the unconfirmed microblock state, and the smallest nonce it uses
+    /// in neither the chain state nor the mempool, given its nonce at the requested chain tip.
+    /// Returns None if the request is not for the latest chain tip, or if the account is a
+    /// contract (which cannot originate or sponsor transactions).
+    fn load_account_next_nonces(
+        sortdb: &SortitionDB,
+        chainstate: &mut StacksChainState,
+        mempool: &MemPoolDB,
+        tip_req: &TipRequest,
+        account: &PrincipalData,
+        nonce: u64,
+    ) -> Option<(u64, u64)> {
+        match tip_req {
+            TipRequest::UseLatestAnchoredTip | TipRequest::UseLatestUnconfirmedTip => {}
+            _ => {
+                return None;
+            }
+        }
+        let addr = match account {
+            PrincipalData::Standard(principal) => StacksAddress::from(principal.clone()),
+            PrincipalData::Contract(..) => {
+                return None;
+            }
+        };
+
+        let unconfirmed_tip_opt = match chainstate.unconfirmed_state {
+            Some(ref mut unconfirmed_state) => unconfirmed_state
+                .get_unconfirmed_state_if_exists()
+                .ok()
+                .flatten(),
+            None => None,
+        };
+        let unanchored_nonce = match unconfirmed_tip_opt {
+            Some(unconfirmed_tip) => chainstate
+                .maybe_read_only_clarity_tx(&sortdb.index_conn(), &unconfirmed_tip, |clarity_tx| {
+                    clarity_tx.with_clarity_db_readonly(|clarity_db| {
+                        clarity_db.get_account_nonce(account)
+                    })
+                })
+                .ok()
+                .flatten()
+                .unwrap_or(nonce),
+            None => nonce,
+        };
+
+        let possible_next_nonce = match MemPoolDB::get_possible_next_nonce(
+            mempool.conn(),
+            &addr,
+            cmp::max(nonce, unanchored_nonce),
+        ) {
+            Ok(next_nonce) => next_nonce,
+            Err(e) => {
+                warn!("Failed to query mempool nonces of {}: {:?}", &addr, &e);
+                return None;
+            }
+        };
+
+        Some((unanchored_nonce, possible_next_nonce))
+    }
+
     /// Handle a GET on an existing account, given the current chain tip.  Optionally supplies a
-    /// MARF proof for each account detail loaded from the chain tip.
+    /// MARF proof for each account detail loaded from the chain tip.  If the request is for the
+    /// latest chain tip, it also reports the account's nonce in the unconfirmed microblock state
+    /// and the next nonce it could use given the transactions it has in the mempool.
     fn handle_get_account_entry<W: Write>(
         http: &mut StacksHttp,
         fd: &mut W,
         req: &HttpRequestType,
         sortdb: &SortitionDB,
         chainstate: &mut StacksChainState,
+        mempool: &MemPoolDB,
+        tip_req: &TipRequest,
         tip: &StacksBlockId,
         account: &PrincipalData,
         with_proof: bool,
@@ -1269,7 +1995,7 @@ impl ConversationHttp {
     ) -> Result<(), net_error> {
         let response_metadata =
             HttpResponseMetadata::from_http_request_type(req, Some(canonical_stacks_tip_height));
-        let response =
+        let account_entry =
             match chainstate.maybe_read_only_clarity_tx(&sortdb.index_conn(), tip, |clarity_tx| {
                 clarity_tx.with_clarity_db_readonly(|clarity_db| {
                     let key = ClarityDatabase::make_key_for_account_balance(&account);
@@ -1322,10 +2048,187 @@ impl ConversationHttp {
                         nonce,
                         balance_proof,
                         nonce_proof,
+                        unanchored_nonce: None,
+                        possible_next_nonce: None,
                     }
                 })
             }) {
-                Ok(Some(data)) => HttpResponseType::GetAccount(response_metadata, data),
+                Ok(Some(data)) => Some(data),
+                Ok(None) | Err(_) => None,
+            };
+
+        let response = match account_entry {
+            Some(mut data) => {
+                if let Some((unanchored_nonce, possible_next_nonce)) =
+                    ConversationHttp::load_account_next_nonces(
+                        sortdb, chainstate, mempool, tip_req, account, data.nonce,
+                    )
+                {
+                    data.unanchored_nonce = Some(unanchored_nonce);
+                    data.possible_next_nonce = Some(possible_next_nonce);
+                }
+                HttpResponseType::GetAccount(response_metadata, data)
+            }
+            None => HttpResponseType::NotFound(response_metadata, "Chain tip not found".into()),
+        };
+
+        response.send(http, fd).map(|_| ())
+    }
+
+    /// Load an account's PoX stacking status from the chain tip's Clarity state.  Returns
+    /// Ok(None) if neither the account's balance nor any PoX contract has a record of the account
+    /// stacking, and Err(..) if its STX are locked but the PoX contract that locked them has no
+    /// stacking state for it.
+    fn load_account_stacking(
+        clarity_db: &mut ClarityDatabase,
+        account: &PrincipalData,
+        mainnet: bool,
+    ) -> Result<Option<AccountStackingResponse>, String> {
+        let burn_block_height = clarity_db.get_current_burnchain_block_height() as u64;
+        let v1_unlock_height = clarity_db.get_v1_unlock_height();
+        let v2_unlock_height = clarity_db.get_v2_unlock_height();
+        let epoch = clarity_db.get_clarity_epoch_version();
+
+        let balance = clarity_db.get_account_stx_balance(account);
+        let (stacked_amount, unlock_height) = balance.get_locked_balance_at_burn_block(
+            burn_block_height,
+            v1_unlock_height,
+            v2_unlock_height,
+        );
+
+        // the balance remembers which PoX contract locked it, even after the lock expires
+        let lock_contract_name = if balance.was_locked_by_v1() {
+            Some(POX_1_NAME)
+        } else if balance.was_locked_by_v2() {
+            Some(POX_2_NAME)
+        } else if balance.was_locked_by_v3() {
+            Some(POX_3_NAME)
+        } else {
+            None
+        };
+
+        let stacker_key = Value::Tuple(
+            TupleData::from_data(vec![("stacker".into(), Value::Principal(account.clone()))])
+                .expect("FATAL: failed to construct PoX stacker key"),
+        );
+        let fetch_pox_entry =
+            |clarity_db: &mut ClarityDatabase, pox_contract_name: &str, map_name: &str| {
+                // a PoX contract that isn't instantiated yet has no entries
+                clarity_db
+                    .fetch_entry_unknown_descriptor(
+                        &boot_code_id(pox_contract_name, mainnet),
+                        map_name,
+                        &stacker_key,
+                        &epoch,
+                    )
+                    .ok()?
+                    .expect_optional()
+                    .map(|entry| entry.expect_tuple())
+            };
+
+        if stacked_amount == 0 {
+            // a PoX contract's stacking state outlives the lock it describes, unless the stacker
+            // was auto-unlocked
+            let has_stacked = lock_contract_name.is_some()
+                || [POX_1_NAME, POX_2_NAME, POX_3_NAME]
+                    .iter()
+                    .any(|name| fetch_pox_entry(clarity_db, name, "stacking-state").is_some());
+
+            return Ok(if has_stacked {
+                Some(AccountStackingResponse {
+                    stacked: false,
+                    stacking: None,
+                })
+            } else {
+                None
+            });
+        }
+
+        let pox_contract_name = lock_contract_name
+            .ok_or_else(|| format!("Locked account {} has no PoX lock", account))?;
+        let stacking_state = fetch_pox_entry(clarity_db, pox_contract_name, "stacking-state")
+            .ok_or_else(|| {
+                format!(
+                    "Locked account {} has no stacking state in {}",
+                    account, pox_contract_name
+                )
+            })?;
+
+        // PoX-3 records the delegate in the stacking state.  Earlier PoX contracts only track it
+        // in the stacker's delegation state.
+        let delegate = if pox_contract_name == POX_3_NAME {
+            stacking_state
+                .get("delegated-to")
+                .ok()
+                .and_then(|delegate| delegate.clone().expect_optional())
+        } else {
+            fetch_pox_entry(clarity_db, pox_contract_name, "delegation-state")
+                .and_then(|delegation_state| delegation_state.get_owned("delegated-to").ok())
+        };
+        let delegated_to = delegate.map(|delegate| delegate.expect_principal().to_string());
+
+        let pox_address = stacking_state
+            .get("pox-addr")
+            .ok()
+            .and_then(|pox_addr| PoxAddress::try_from_pox_tuple(mainnet, pox_addr))
+            .map(|pox_addr| pox_addr.to_b58());
+
+        let reward_cycle = stacking_state
+            .get("first-reward-cycle")
+            .map_err(|e| e.to_string())?
+            .clone()
+            .expect_u128() as u64;
+        let lock_period = stacking_state
+            .get("lock-period")
+            .map_err(|e| e.to_string())?
+            .clone()
+            .expect_u128() as u64;
+
+        Ok(Some(AccountStackingResponse {
+            stacked: true,
+            stacking: Some(AccountStackingInfo {
+                stacked_amount,
+                unlock_height,
+                delegated_to,
+                pox_address,
+                reward_cycle,
+                lock_period,
+            }),
+        }))
+    }
+
+    /// Handle a GET on an account's PoX stacking status, given the current chain tip.  Responds
+    /// with 404 if the account has never stacked.
+    fn handle_get_account_stacking<W: Write>(
+        http: &mut StacksHttp,
+        fd: &mut W,
+        req: &HttpRequestType,
+        sortdb: &SortitionDB,
+        chainstate: &mut StacksChainState,
+        tip: &StacksBlockId,
+        account: &PrincipalData,
+        canonical_stacks_tip_height: u64,
+    ) -> Result<(), net_error> {
+        let response_metadata =
+            HttpResponseMetadata::from_http_request_type(req, Some(canonical_stacks_tip_height));
+        let mainnet = chainstate.mainnet;
+        let response =
+            match chainstate.maybe_read_only_clarity_tx(&sortdb.index_conn(), tip, |clarity_tx| {
+                clarity_tx.with_clarity_db_readonly(|clarity_db| {
+                    ConversationHttp::load_account_stacking(clarity_db, account, mainnet)
+                })
+            }) {
+                Ok(Some(Ok(Some(data)))) => {
+                    HttpResponseType::GetAccountStacking(response_metadata, data)
+                }
+                Ok(Some(Ok(None))) => HttpResponseType::NotFound(
+                    response_metadata,
+                    format!("Account {} has never stacked", account),
+                ),
+                Ok(Some(Err(msg))) => {
+                    warn!("Failed to load stacking status of {}: {}", account, &msg);
+                    HttpResponseType::ServerError(response_metadata, msg)
+                }
                 Ok(None) | Err(_) => {
                     HttpResponseType::NotFound(response_metadata, "Chain tip not found".into())
                 }
@@ -1593,9 +2496,18 @@ impl ConversationHttp {
                     };
 
                     let publish_height = contract_commit.block_height;
+                    let clarity_version =
+                        db.get_contract(&contract_identifier).ok().map(|contract| {
+                            match contract.contract_context.get_clarity_version() {
+                                ClarityVersion::Clarity1 => 1,
+                                ClarityVersion::Clarity2 => 2,
+                                ClarityVersion::Clarity3 => 3,
+                            }
+                        });
                     Some(ContractSrcResponse {
                         source,
                         publish_height,
+                        clarity_version,
                         marf_proof: proof,
                     })
                 })
@@ -1723,6 +2635,7 @@ impl ConversationHttp {
         req: &HttpRequestType,
         index_anchor_block_hash: &StacksBlockId,
         min_seq: u16,
+        limit: Option<u16>,
         chainstate: &StacksChainState,
         canonical_stacks_tip_height: u64,
     ) -> Result<Option<StreamCursor>, net_error> {
@@ -1765,6 +2678,7 @@ impl ConversationHttp {
                     chainstate,
                     index_anchor_block_hash.clone(),
                     min_seq,
+                    limit,
                 ) {
                     Ok(stream) => (
                         HttpResponseType::MicroblockStream(response_metadata),
@@ -1802,46 +2716,131 @@ impl ConversationHttp {
         }
     }
 
-    /// Handle a GET unconfirmed transaction.
+    /// Handle a GET for the pending mempool transactions sent or sponsored by an address.
+    /// Only the mempool DB is read, so the chainstate is not locked.
     /// The response will be synchronously written to the fd.
-    fn handle_gettransaction_unconfirmed<W: Write>(
+    fn handle_get_mempool_transactions_by_address<W: Write>(
         http: &mut StacksHttp,
         fd: &mut W,
         req: &HttpRequestType,
-        chainstate: &StacksChainState,
         mempool: &MemPoolDB,
-        txid: &Txid,
+        address: &StacksAddress,
         canonical_stacks_tip_height: u64,
     ) -> Result<(), net_error> {
         let response_metadata =
             HttpResponseMetadata::from_http_request_type(req, Some(canonical_stacks_tip_height));
 
-        // present in the unconfirmed state?
-        if let Some(ref unconfirmed) = chainstate.unconfirmed_state.as_ref() {
-            if let Some((transaction, mblock_hash, seq)) =
-                unconfirmed.get_unconfirmed_transaction(txid)
-            {
-                let response = HttpResponseType::UnconfirmedTransaction(
+        let response = match MemPoolDB::get_txs_by_address(mempool.conn(), address) {
+            Ok(txs) => {
+                let entries = txs
+                    .into_iter()
+                    .map(|txinfo| {
+                        let md = &txinfo.tx.metadata;
+                        let nonce = if md.origin_address == *address {
+                            md.origin_nonce
+                        } else {
+                            md.sponsor_nonce
+                        };
+                        MempoolTransactionEntry {
+                            txid: md.txid.clone(),
+                            nonce,
+                            fee_rate: txinfo.fee_rate,
+                            payload_type: txinfo.tx.tx.payload.name().to_string(),
+                            sponsored: txinfo.tx.tx.auth.is_sponsored(),
+                        }
+                    })
+                    .collect();
+                HttpResponseType::MempoolTransactionsByAddress(response_metadata, entries)
+            }
+            Err(e) => {
+                warn!("Failed to query mempool for {}: {:?}", address, &e);
+                HttpResponseType::ServerError(
                     response_metadata,
-                    UnconfirmedTransactionResponse {
-                        status: UnconfirmedTransactionStatus::Microblock {
-                            block_hash: mblock_hash,
-                            seq: seq,
-                        },
-                        tx: to_hex(&transaction.serialize_to_vec()),
-                    },
+                    "Failed to query mempool".to_string(),
+                )
+            }
+        };
+        response.send(http, fd).map(|_| ())
+    }
+
+    /// Handle a GET for the mempool's admission counters and its number of pending transactions.
+    /// The response will be synchronously written to the fd.
+    fn handle_get_mempool_stats<W: Write>(
+        http: &mut StacksHttp,
+        fd: &mut W,
+        req: &HttpRequestType,
+        mempool: &MemPoolDB,
+        canonical_stacks_tip_height: u64,
+    ) -> Result<(), net_error> {
+        let response_metadata =
+            HttpResponseMetadata::from_http_request_type(req, Some(canonical_stacks_tip_height));
+
+        let response = match MemPoolStats::load(mempool) {
+            Ok(stats) => HttpResponseType::MempoolStats(response_metadata, stats),
+            Err(e) => {
+                warn!("Failed to load mempool stats: {:?}", &e);
+                HttpResponseType::ServerError(
+                    response_metadata,
+                    "Failed to query mempool".to_string(),
+                )
+            }
+        };
+        response.send(http, fd).map(|_| ())
+    }
+
+    /// Handle a GET unconfirmed transaction.
+    /// The response will be synchronously written to the fd.
+    fn handle_gettransaction_unconfirmed<W: Write>(
+        http: &mut StacksHttp,
+        fd: &mut W,
+        req: &HttpRequestType,
+        chainstate: &StacksChainState,
+        mempool: &MemPoolDB,
+        txid: &Txid,
+        canonical_stacks_tip_height: u64,
+    ) -> Result<(), net_error> {
+        let response_metadata =
+            HttpResponseMetadata::from_http_request_type(req, Some(canonical_stacks_tip_height));
+
+        // when did the mempool receive it?  Transactions mined into microblocks stay in the
+        // mempool until an anchored block confirms them.
+        let mempool_txinfo = MemPoolDB::get_tx(mempool.conn(), txid)?;
+        let receipt_time = mempool_txinfo
+            .as_ref()
+            .map(|txinfo| txinfo.metadata.accept_time);
+
+        // present in the unconfirmed state?
+        if let Some(ref unconfirmed) = chainstate.unconfirmed_state.as_ref() {
+            if let Some((transaction, mblock_hash, seq)) =
+                unconfirmed.get_unconfirmed_transaction(txid)
+            {
+                let response = HttpResponseType::UnconfirmedTransaction(
+                    response_metadata,
+                    UnconfirmedTransactionResponse {
+                        status: UnconfirmedTransactionStatus::Microblock {
+                            block_hash: mblock_hash,
+                            seq: seq,
+                        },
+                        tx: to_hex(&transaction.serialize_to_vec()),
+                        fee: transaction.get_tx_fee(),
+                        nonce: transaction.get_origin_nonce(),
+                        receipt_time,
+                    },
                 );
                 return response.send(http, fd).map(|_| ());
             }
         }
 
         // present in the mempool?
-        if let Some(txinfo) = MemPoolDB::get_tx(mempool.conn(), txid)? {
+        if let Some(txinfo) = mempool_txinfo {
             let response = HttpResponseType::UnconfirmedTransaction(
                 response_metadata,
                 UnconfirmedTransactionResponse {
                     status: UnconfirmedTransactionStatus::Mempool,
                     tx: to_hex(&txinfo.tx.serialize_to_vec()),
+                    fee: txinfo.metadata.tx_fee,
+                    nonce: txinfo.metadata.origin_nonce,
+                    receipt_time,
                 },
             );
             return response.send(http, fd).map(|_| ());
@@ -1855,6 +2854,51 @@ impl ConversationHttp {
         return response.send(http, fd).map(|_| ());
     }
 
+    /// Find the block named by an `until_block` tip request on the fork ending at
+    /// `canonical_tip`.  Returns the inner `Err` with a human-readable reason if the block is
+    /// unknown or is not on that fork.
+    fn resolve_canonical_ancestor(
+        chainstate: &StacksChainState,
+        tip_req: &TipRequest,
+        canonical_tip: &StacksBlockId,
+    ) -> Result<Result<StacksBlockId, String>, net_error> {
+        match tip_req {
+            TipRequest::CanonicalAncestorAtHeight(height) => {
+                match chainstate
+                    .index_conn()?
+                    .get_ancestor_block_hash(*height, canonical_tip)?
+                {
+                    Some(ancestor) => Ok(Ok(ancestor)),
+                    None => Ok(Err(format!(
+                        "No block at height {} on the canonical Stacks fork",
+                        height
+                    ))),
+                }
+            }
+            TipRequest::CanonicalAncestor(block_id) => {
+                if StacksChainState::get_stacks_block_header_info_by_index_block_hash(
+                    chainstate.db(),
+                    block_id,
+                )?
+                .is_none()
+                {
+                    return Ok(Err(format!("Unknown Stacks block {}", block_id)));
+                }
+                match chainstate
+                    .index_conn()?
+                    .get_ancestor_block_height(block_id, canonical_tip)?
+                {
+                    Some(_) => Ok(Ok(block_id.clone())),
+                    None => Ok(Err(format!(
+                        "Stacks block {} is not on the canonical Stacks fork",
+                        block_id
+                    ))),
+                }
+            }
+            _ => Ok(Err(format!("Not a historical block request"))),
+        }
+    }
+
     /// Load up the canonical Stacks chain tip.  Note that this is subject to both burn chain block
     /// Stacks block availability -- different nodes with different partial replicas of the Stacks chain state
     /// will return different values here.
@@ -1869,7 +2913,9 @@ impl ConversationHttp {
     /// # Inputs
     /// - `tip_req` is given by the HTTP request as the optional query parameter for the chain tip
     /// hash.  It will be UseLatestAnchoredTip if there was no parameter given. If it is set to
-    /// `latest`, the parameter will be set to UseLatestUnconfirmedTip.
+    /// `latest`, the parameter will be set to UseLatestUnconfirmedTip.  Historical requests
+    /// (`until_block`) are resolved against the canonical anchored tip, and answered with a 404
+    /// if the block is unknown or not on the canonical fork.
     fn handle_load_stacks_chain_tip<W: Write>(
         http: &mut StacksHttp,
         fd: &mut W,
@@ -1923,6 +2969,41 @@ impl ConversationHttp {
                 }
             }
             TipRequest::SpecificTip(tip) => Ok(Some(*tip).clone()),
+            TipRequest::CanonicalAncestorAtHeight(..) | TipRequest::CanonicalAncestor(..) => {
+                let canonical_tip = match chainstate.get_stacks_chain_tip(sortdb)? {
+                    Some(tip) => StacksBlockHeader::make_index_block_hash(
+                        &tip.consensus_hash,
+                        &tip.anchored_block_hash,
+                    ),
+                    None => {
+                        let response_metadata = HttpResponseMetadata::from_http_request_type(
+                            req,
+                            Some(canonical_stacks_tip_height),
+                        );
+                        warn!("Failed to load Stacks chain tip");
+                        let response = HttpResponseType::ServerError(
+                            response_metadata,
+                            format!("Failed to load Stacks chain tip"),
+                        );
+                        return response.send(http, fd).and_then(|_| Ok(None));
+                    }
+                };
+                match ConversationHttp::resolve_canonical_ancestor(
+                    chainstate,
+                    tip_req,
+                    &canonical_tip,
+                )? {
+                    Ok(ancestor) => Ok(Some(ancestor)),
+                    Err(msg) => {
+                        let response_metadata = HttpResponseMetadata::from_http_request_type(
+                            req,
+                            Some(canonical_stacks_tip_height),
+                        );
+                        let response = HttpResponseType::NotFound(response_metadata, msg);
+                        response.send(http, fd).and_then(|_| Ok(None))
+                    }
+                }
+            }
             TipRequest::UseLatestAnchoredTip => match chainstate.get_stacks_chain_tip(sortdb)? {
                 Some(tip) => Ok(Some(StacksBlockHeader::make_index_block_hash(
                     &tip.consensus_hash,
@@ -2002,8 +3083,12 @@ impl ConversationHttp {
                 }
             };
 
-            let scalar_cost =
-                metric.from_cost_and_len(&estimated_cost, &stacks_epoch.block_limit, estimated_len);
+            let scalar_cost = metric.from_payload_cost_and_len(
+                tx,
+                &estimated_cost,
+                &stacks_epoch.block_limit,
+                estimated_len,
+            );
             let fee_rates = match fee_estimator.get_rate_estimates() {
                 Ok(x) => x,
                 Err(e) => {
@@ -2049,10 +3134,261 @@ impl ConversationHttp {
         }
     }
 
+    /// Handle a POST to dry-run a contract-call transaction on the given chain tip.  The call is
+    /// executed against a read-only view of the chainstate, so none of its writes are kept and
+    /// the transaction is never broadcast.  Returns the call's result, the events it emitted,
+    /// and its execution cost in a TransactionDryRunResponse.
+    fn handle_post_transaction_dry_run<W: Write>(
+        http: &mut StacksHttp,
+        fd: &mut W,
+        req: &HttpRequestType,
+        sortdb: &SortitionDB,
+        chainstate: &mut StacksChainState,
+        tip: &StacksBlockId,
+        tx: &StacksTransaction,
+        options: &ConnectionOptions,
+        canonical_stacks_tip_height: u64,
+    ) -> Result<(), net_error> {
+        let response_metadata =
+            HttpResponseMetadata::from_http_request_type(req, Some(canonical_stacks_tip_height));
+        let contract_call = match tx.payload {
+            TransactionPayload::ContractCall(ref contract_call) => contract_call,
+            _ => {
+                debug!(
+                    "Dry-run RPC endpoint rejected non-contract-call tx: {}",
+                    tx.payload.name()
+                );
+                return HttpResponseType::BadRequestJSON(
+                    response_metadata,
+                    json!({
+                        "error": "Only contract-call transactions can be dry-run",
+                        "reason": "NotContractCall",
+                    }),
+                )
+                .send(http, fd);
+            }
+        };
+
+        let burn_tip = SortitionDB::get_canonical_burn_chain_tip(sortdb.conn())?;
+        let stacks_epoch = SortitionDB::get_stacks_epoch(sortdb.conn(), burn_tip.block_height)?
+                .ok_or_else(|| {
+                    warn!(
+                        "Failed to dry-run transaction because could not load Stacks epoch for canonical burn height = {}",
+                        burn_tip.block_height
+                    );
+                    net_error::ChainstateError("Could not load Stacks epoch for canonical burn height".into())
+                })?;
+
+        let txid = tx.txid();
+        let contract_identifier = contract_call.to_clarity_contract_id();
+        let args: Vec<_> = contract_call
+            .function_args
+            .iter()
+            .map(|x| SymbolicExpression::atom_value(x.clone()))
+            .collect();
+        let sender = tx.origin_address().to_account_principal();
+        let sponsor = tx.sponsor_address().map(|a| a.to_account_principal());
+        let mainnet = chainstate.mainnet;
+        let chain_id = chainstate.chain_id;
+        let cost_limit = stacks_epoch.block_limit.clone();
+        let mut deadline = DryRunDeadline::new(options.dry_run_timeout_ms);
+
+        let data_opt_res =
+            chainstate.maybe_read_only_clarity_tx(&sortdb.index_conn(), tip, |clarity_tx| {
+                let epoch = clarity_tx.get_epoch();
+                let cost_track = clarity_tx
+                    .with_clarity_db_readonly(|clarity_db| {
+                        LimitedCostTracker::new_mid_block(
+                            mainnet, chain_id, cost_limit, clarity_db, epoch,
+                        )
+                    })
+                    .map_err(|_| {
+                        ClarityRuntimeError::from(InterpreterError::CostContractLoadFailure)
+                    })?;
+
+                let clarity_version = clarity_tx
+                    .with_analysis_db_readonly(|analysis_db| {
+                        analysis_db.get_clarity_version(&contract_identifier)
+                    })
+                    .map_err(|_| {
+                        ClarityRuntimeError::from(CheckErrors::NoSuchContract(format!(
+                            "{}",
+                            &contract_identifier
+                        )))
+                    })?;
+
+                // same as `with_readonly_clarity_env()`, but with the deadline installed and the
+                // call's events and cost kept.  The read-only connection discards any writes.
+                let res = clarity_tx.with_clarity_db_readonly_owned(|clarity_db| {
+                    let initial_context = ContractContext::new(
+                        QualifiedContractIdentifier::transient(),
+                        clarity_version,
+                    );
+                    let mut vm_env = OwnedEnvironment::new_cost_limited(
+                        mainnet, chain_id, clarity_db, cost_track, epoch,
+                    );
+                    vm_env.add_eval_hook(&mut deadline);
+                    let result =
+                        vm_env.execute_in_env(sender, sponsor, Some(initial_context), |env| {
+                            env.execute_contract(
+                                &contract_identifier,
+                                contract_call.function_name.as_str(),
+                                &args,
+                                false,
+                            )
+                        });
+                    let cost = vm_env.get_cost_total();
+                    let (db, _) = vm_env
+                        .destruct()
+                        .expect("Failed to recover database reference after executing transaction");
+                    ((result, cost), db)
+                });
+                Ok::<_, ClarityRuntimeError>(res)
+            });
+
+        let response = match data_opt_res {
+            Ok(Some(Ok((result, cost)))) => {
+                let execution_cost = deadline.spent.take().unwrap_or(cost);
+                match result {
+                    Ok((value, _, events)) => {
+                        // events from a call that returns `(err ..)` would not be committed
+                        let committed = match value {
+                            Value::Response(ref data) => data.committed,
+                            _ => true,
+                        };
+                        HttpResponseType::TransactionDryRun(
+                            response_metadata,
+                            TransactionDryRunResponse {
+                                okay: true,
+                                result: Some(format!("0x{}", value.serialize_to_hex())),
+                                cause: None,
+                                execution_cost,
+                                events: events
+                                    .iter()
+                                    .enumerate()
+                                    .map(|(event_index, event)| {
+                                        event.json_serialize(event_index, &txid, committed)
+                                    })
+                                    .collect(),
+                            },
+                        )
+                    }
+                    Err(e) => {
+                        let cause = if deadline.timed_out {
+                            "DryRunTimeout".to_string()
+                        } else {
+                            e.to_string()
+                        };
+                        HttpResponseType::TransactionDryRun(
+                            response_metadata,
+                            TransactionDryRunResponse {
+                                okay: false,
+                                result: None,
+                                cause: Some(cause),
+                                execution_cost,
+                                events: vec![],
+                            },
+                        )
+                    }
+                }
+            }
+            Ok(Some(Err(e))) => HttpResponseType::TransactionDryRun(
+                response_metadata,
+                TransactionDryRunResponse {
+                    okay: false,
+                    result: None,
+                    cause: Some(e.to_string()),
+                    execution_cost: ExecutionCost::zero(),
+                    events: vec![],
+                },
+            ),
+            Ok(None) | Err(_) => {
+                HttpResponseType::NotFound(response_metadata, "Chain tip not found".into())
+            }
+        };
+        response.send(http, fd).map(|_| ())
+    }
+
+    /// Directly submit a POSTed transaction to the mempool.
+    /// Returns Ok(true) if the mempool accepted it (and thus it needs to be forwarded), Ok(false)
+    /// if the mempool already had it or it was silently dropped as problematic, and Err(..) with
+    /// the rejection reason if the mempool rejected it.
+    /// A transaction that conflicts with one already in the mempool is rejected by the mempool's
+    /// conflict cache, without opening a mempool DB transaction, where possible.
+    fn submit_posted_transaction(
+        chainstate: &mut StacksChainState,
+        sortdb: &SortitionDB,
+        consensus_hash: &ConsensusHash,
+        block_hash: &BlockHeaderHash,
+        mempool: &mut MemPoolDB,
+        tx: &StacksTransaction,
+        event_observer: Option<&dyn MemPoolEventDispatcher>,
+        ast_rules: ASTRules,
+    ) -> Result<Result<bool, MemPoolRejection>, net_error> {
+        let txid = tx.txid();
+        if mempool.has_tx(&txid) {
+            debug!("Mempool already has POSTed transaction {}", &txid);
+            return Ok(Ok(false));
+        }
+
+        if let Err(e) = mempool.check_conflicting_nonce(consensus_hash, block_hash, tx) {
+            debug!("Mempool rejected POSTed transaction {}: {:?}", &txid, &e);
+            return Ok(Err(e));
+        }
+
+        let tip = SortitionDB::get_canonical_burn_chain_tip(sortdb.conn())?;
+        let stacks_epoch = sortdb
+            .index_conn()
+            .get_stacks_epoch(tip.block_height as u32)
+            .ok_or_else(|| {
+                warn!(
+                    "Failed to store transaction because could not load Stacks epoch for canonical burn height = {}",
+                    tip.block_height
+                );
+                net_error::ChainstateError("Could not load Stacks epoch for canonical burn height".into())
+            })?;
+
+        if Relayer::do_static_problematic_checks()
+            && !Relayer::static_check_problematic_relayed_tx(
+                chainstate.mainnet,
+                stacks_epoch.epoch_id,
+                tx,
+                ast_rules,
+            )
+            .is_ok()
+        {
+            debug!(
+                "Transaction {} is problematic in rules {:?}; will not store or relay",
+                &txid, ast_rules
+            );
+            return Ok(Ok(false));
+        }
+
+        match mempool.submit(
+            chainstate,
+            sortdb,
+            consensus_hash,
+            block_hash,
+            tx,
+            event_observer,
+            &stacks_epoch.block_limit,
+            &stacks_epoch.epoch_id,
+        ) {
+            Ok(_) => {
+                debug!("Mempool accepted POSTed transaction {}", &txid);
+                Ok(Ok(true))
+            }
+            Err(e) => {
+                debug!("Mempool rejected POSTed transaction {}: {:?}", &txid, &e);
+                Ok(Err(e))
+            }
+        }
+    }
+
     /// Handle a transaction.  Directly submit it to the mempool so the client can see any
     /// rejection reasons up-front (different from how the peer network handles it).  Indicate
     /// whether or not the transaction was accepted (and thus needs to be forwarded) in the return
-    /// value.
+    /// value.  A transaction that conflicts with one already in the mempool is rejected with 409.
     fn handle_post_transaction<W: Write>(
         http: &mut StacksHttp,
         fd: &mut W,
@@ -2072,68 +3408,32 @@ impl ConversationHttp {
         let txid = tx.txid();
         let response_metadata =
             HttpResponseMetadata::from_http_request_type(req, Some(canonical_stacks_tip_height));
-        let (response, accepted) = if mempool.has_tx(&txid) {
-            debug!("Mempool already has POSTed transaction {}", &txid);
-            (
+        let (response, accepted) = match ConversationHttp::submit_posted_transaction(
+            chainstate,
+            sortdb,
+            &consensus_hash,
+            &block_hash,
+            mempool,
+            &tx,
+            event_observer,
+            ast_rules,
+        )? {
+            Ok(accepted) => (
                 HttpResponseType::TransactionID(response_metadata, txid),
-                false,
-            )
-        } else {
-            let tip = SortitionDB::get_canonical_burn_chain_tip(sortdb.conn())?;
-            let stacks_epoch = sortdb
-                .index_conn()
-                .get_stacks_epoch(tip.block_height as u32)
-                .ok_or_else(|| {
-                    warn!(
-                        "Failed to store transaction because could not load Stacks epoch for canonical burn height = {}",
-                        tip.block_height
-                    );
-                    net_error::ChainstateError("Could not load Stacks epoch for canonical burn height".into())
-                })?;
-
-            if Relayer::do_static_problematic_checks()
-                && !Relayer::static_check_problematic_relayed_tx(
-                    chainstate.mainnet,
-                    stacks_epoch.epoch_id,
-                    &tx,
-                    ast_rules,
-                )
-                .is_ok()
-            {
-                debug!(
-                    "Transaction {} is problematic in rules {:?}; will not store or relay",
-                    &tx.txid(),
-                    ast_rules
-                );
-                (
-                    HttpResponseType::TransactionID(response_metadata, txid),
-                    false,
-                )
-            } else {
-                match mempool.submit(
-                    chainstate,
-                    sortdb,
-                    &consensus_hash,
-                    &block_hash,
-                    &tx,
-                    event_observer,
-                    &stacks_epoch.block_limit,
-                    &stacks_epoch.epoch_id,
-                ) {
-                    Ok(_) => {
-                        debug!("Mempool accepted POSTed transaction {}", &txid);
-                        (
-                            HttpResponseType::TransactionID(response_metadata, txid),
-                            true,
-                        )
-                    }
-                    Err(e) => {
-                        debug!("Mempool rejected POSTed transaction {}: {:?}", &txid, &e);
-                        (
-                            HttpResponseType::BadRequestJSON(response_metadata, e.into_json(&txid)),
-                            false,
-                        )
-                    }
+                accepted,
+            ),
+            Err(rejection) => {
+                let error = PostTransactionError::Rejected(txid, rejection);
+                if error.is_conflict() {
+                    (
+                        HttpResponseType::ConflictJSON(response_metadata, json!(error)),
+                        false,
+                    )
+                } else {
+                    (
+                        HttpResponseType::BadRequestJSON(response_metadata, json!(error)),
+                        false,
+                    )
                 }
             }
         };
@@ -2153,18 +3453,99 @@ impl ConversationHttp {
         response.send(http, fd).and_then(|_| Ok(accepted))
     }
 
-    /// Handle a block.  Directly submit a Stacks block to this node's chain state.
-    /// Indicate whether or not the block was accepted (i.e. it was new, and valid)
-    fn handle_post_block<W: Write>(
+    /// Handle a batch of transactions.  Each one is decoded and submitted to the mempool in
+    /// order, as if it had been POSTed on its own, and the client gets back each one's outcome.
+    /// One transaction being rejected does not affect the others.
+    /// Returns the transactions the mempool accepted (and thus need to be forwarded).
+    fn handle_post_transaction_batch<W: Write>(
         http: &mut StacksHttp,
         fd: &mut W,
         req: &HttpRequestType,
-        sortdb: &SortitionDB,
         chainstate: &mut StacksChainState,
-        consensus_hash: &ConsensusHash,
-        block: &StacksBlock,
-        canonical_stacks_tip_height: u64,
-    ) -> Result<bool, net_error> {
+        sortdb: &SortitionDB,
+        consensus_hash: ConsensusHash,
+        block_hash: BlockHeaderHash,
+        mempool: &mut MemPoolDB,
+        txs: &[Vec<u8>],
+        event_observer: Option<&dyn MemPoolEventDispatcher>,
+        canonical_stacks_tip_height: u64,
+        ast_rules: ASTRules,
+    ) -> Result<Vec<StacksTransaction>, net_error> {
+        let response_metadata =
+            HttpResponseMetadata::from_http_request_type(req, Some(canonical_stacks_tip_height));
+        let mut entries = Vec::with_capacity(txs.len());
+        let mut to_forward = vec![];
+
+        for tx_bytes in txs.iter() {
+            let tx = match StacksTransaction::consensus_deserialize(&mut &tx_bytes[..]) {
+                Ok(tx) => tx,
+                Err(e) => {
+                    let txid = Txid::from_stacks_tx(&tx_bytes[..]);
+                    debug!("Failed to decode POSTed transaction {}: {:?}", &txid, &e);
+                    entries.push(PostTransactionBatchEntry {
+                        txid: txid.clone(),
+                        accepted: false,
+                        rejection: Some(
+                            PostTransactionError::Rejected(
+                                txid.clone(),
+                                MemPoolRejection::DeserializationFailure(e),
+                            )
+                            .to_body(),
+                        ),
+                    });
+                    continue;
+                }
+            };
+
+            let txid = tx.txid();
+            match ConversationHttp::submit_posted_transaction(
+                chainstate,
+                sortdb,
+                &consensus_hash,
+                &block_hash,
+                mempool,
+                &tx,
+                event_observer,
+                ast_rules,
+            )? {
+                Ok(accepted) => {
+                    entries.push(PostTransactionBatchEntry {
+                        txid,
+                        accepted: true,
+                        rejection: None,
+                    });
+                    if accepted {
+                        to_forward.push(tx);
+                    }
+                }
+                Err(rejection) => {
+                    let rejection =
+                        PostTransactionError::Rejected(txid.clone(), rejection).to_body();
+                    entries.push(PostTransactionBatchEntry {
+                        txid,
+                        accepted: false,
+                        rejection: Some(rejection),
+                    });
+                }
+            }
+        }
+
+        let response = HttpResponseType::TransactionBatch(response_metadata, entries);
+        response.send(http, fd).and_then(|_| Ok(to_forward))
+    }
+
+    /// Handle a block.  Directly submit a Stacks block to this node's chain state.
+    /// Indicate whether or not the block was accepted (i.e. it was new, and valid)
+    fn handle_post_block<W: Write>(
+        http: &mut StacksHttp,
+        fd: &mut W,
+        req: &HttpRequestType,
+        sortdb: &SortitionDB,
+        chainstate: &mut StacksChainState,
+        consensus_hash: &ConsensusHash,
+        block: &StacksBlock,
+        canonical_stacks_tip_height: u64,
+    ) -> Result<bool, net_error> {
         let response_metadata =
             HttpResponseMetadata::from_http_request_type(req, Some(canonical_stacks_tip_height));
         // is this a consensus hash we recognize?
@@ -2272,6 +3653,147 @@ impl ConversationHttp {
         response.send(http, fd).and_then(|_| Ok(accepted))
     }
 
+    /// Handle a block proposal.  The proposed block must build off of the canonical Stacks tip.
+    /// Its transactions are re-mined off of that tip and the resulting block is compared against
+    /// the proposal, but nothing is stored and the block is not relayed.
+    fn handle_post_block_validate<W: Write>(
+        http: &mut StacksHttp,
+        fd: &mut W,
+        req: &HttpRequestType,
+        sortdb: &SortitionDB,
+        chainstate: &mut StacksChainState,
+        block: &StacksBlock,
+        canonical_stacks_tip_height: u64,
+    ) -> Result<(), net_error> {
+        let response_metadata =
+            HttpResponseMetadata::from_http_request_type(req, Some(canonical_stacks_tip_height));
+        let parent_header_info = match chainstate.get_stacks_chain_tip(sortdb)? {
+            Some(tip) => StacksChainState::get_anchored_block_header_info(
+                chainstate.db(),
+                &tip.consensus_hash,
+                &tip.anchored_block_hash,
+            )?,
+            None => None,
+        };
+        let parent_header_info = match parent_header_info {
+            Some(header_info) => header_info,
+            None => {
+                warn!("Failed to load Stacks chain tip");
+                return HttpResponseType::ServerError(
+                    response_metadata,
+                    "Failed to load Stacks chain tip".to_string(),
+                )
+                .send(http, fd);
+            }
+        };
+
+        let block_hash = block.block_hash();
+        let response = match StacksBlockBuilder::validate_anchored_block_proposal(
+            chainstate,
+            &sortdb.index_conn(),
+            &parent_header_info,
+            block,
+        ) {
+            Ok(validation) => {
+                debug!(
+                    "Validated block proposal {}: {:?}",
+                    &block_hash, &validation.reason
+                );
+                HttpResponseType::BlockProposalValidation(
+                    response_metadata,
+                    BlockProposalValidationResponse {
+                        valid: validation.is_valid(),
+                        block_hash,
+                        invalid_txids: validation.invalid_txids,
+                        execution_cost: validation.execution_cost,
+                        reason: validation.reason,
+                    },
+                )
+            }
+            Err(e) => {
+                warn!(
+                    "Failed to validate block proposal {}: {:?}",
+                    &block_hash, &e
+                );
+                HttpResponseType::ServerError(
+                    response_metadata,
+                    format!(
+                        "Failed to validate block proposal {}: {:?}",
+                        &block_hash, &e
+                    ),
+                )
+            }
+        };
+        response.send(http, fd).map(|_| ())
+    }
+
+    /// Handle a request to check a microblock stream off of the canonical Stacks chain tip,
+    /// without storing or relaying it.
+    fn handle_post_microblocks_validate<W: Write>(
+        http: &mut StacksHttp,
+        fd: &mut W,
+        req: &HttpRequestType,
+        sortdb: &SortitionDB,
+        chainstate: &mut StacksChainState,
+        microblocks: &[StacksMicroblock],
+        canonical_stacks_tip_height: u64,
+    ) -> Result<(), net_error> {
+        let response_metadata =
+            HttpResponseMetadata::from_http_request_type(req, Some(canonical_stacks_tip_height));
+        let parent_header_info = match chainstate.get_stacks_chain_tip(sortdb)? {
+            Some(tip) => StacksChainState::get_anchored_block_header_info(
+                chainstate.db(),
+                &tip.consensus_hash,
+                &tip.anchored_block_hash,
+            )?,
+            None => None,
+        };
+        let parent_header_info = match parent_header_info {
+            Some(header_info) => header_info,
+            None => {
+                warn!("Failed to load Stacks chain tip");
+                return HttpResponseType::ServerError(
+                    response_metadata,
+                    "Failed to load Stacks chain tip".to_string(),
+                )
+                .send(http, fd);
+            }
+        };
+
+        let response = match StacksMicroblockBuilder::validate_microblock_stream(
+            chainstate,
+            &sortdb.index_conn(),
+            &parent_header_info,
+            microblocks,
+        ) {
+            Ok(validation) => {
+                debug!(
+                    "Validated {} microblock(s) off of {}/{}: first invalid sequence {:?}",
+                    microblocks.len(),
+                    &parent_header_info.consensus_hash,
+                    &parent_header_info.anchored_header.block_hash(),
+                    &validation.first_invalid_seq
+                );
+                HttpResponseType::MicroblockStreamValidation(
+                    response_metadata,
+                    MicroblockStreamValidationResponse {
+                        valid: validation.is_valid(),
+                        first_invalid_seq: validation.first_invalid_seq,
+                        errors: validation.errors,
+                    },
+                )
+            }
+            Err(e) => {
+                warn!("Failed to validate microblock stream: {:?}", &e);
+                HttpResponseType::ServerError(
+                    response_metadata,
+                    format!("Failed to validate microblock stream: {:?}", &e),
+                )
+            }
+        };
+        response.send(http, fd).map(|_| ())
+    }
+
     /// Handle a microblock.  Directly submit it to the microblock store so the client can see any
     /// rejection reasons up-front (different from how the peer network handles it).  Indicate
     /// whether or not the microblock was accepted (and thus needs to be forwarded) in the return
@@ -2406,8 +3928,8 @@ impl ConversationHttp {
     /// Handle an external HTTP request.
     /// Some requests, such as those for blocks, will create new reply streams.  This method adds
     /// those new streams into the `reply_streams` set.
-    /// Returns a StacksMessageType option -- it's Some(...) if we need to forward a message to the
-    /// peer network (like a transaction or a block or microblock)
+    /// Returns the StacksMessageTypes we need to forward to the peer network (like transactions or
+    /// a block or microblock)
     pub fn handle_request(
         &mut self,
         req: HttpRequestType,
@@ -2416,10 +3938,10 @@ impl ConversationHttp {
         chainstate: &mut StacksChainState,
         mempool: &mut MemPoolDB,
         handler_opts: &RPCHandlerArgs,
-    ) -> Result<Option<StacksMessageType>, net_error> {
+    ) -> Result<Vec<StacksMessageType>, net_error> {
         let mut reply = self.connection.make_relay_handle(self.conn_id)?;
         let keep_alive = req.metadata().keep_alive;
-        let mut ret = None;
+        let mut ret = vec![];
 
         let stream_opt = match req {
             HttpRequestType::GetInfo(ref _md) => {
@@ -2457,6 +3979,66 @@ impl ConversationHttp {
                 }
                 None
             }
+            HttpRequestType::GetPoxRewardCycle(ref _md, ref burn_height, ref tip_req) => {
+                if let Some(tip) = ConversationHttp::handle_load_stacks_chain_tip(
+                    &mut self.connection.protocol,
+                    &mut reply,
+                    &req,
+                    tip_req,
+                    sortdb,
+                    chainstate,
+                    network.burnchain_tip.canonical_stacks_tip_height,
+                )? {
+                    ConversationHttp::handle_get_pox_reward_cycle(
+                        &mut self.connection.protocol,
+                        &mut reply,
+                        &req,
+                        sortdb,
+                        chainstate,
+                        &tip,
+                        &network.burnchain,
+                        *burn_height,
+                        network.burnchain_tip.canonical_stacks_tip_height,
+                    )?;
+                }
+                None
+            }
+            HttpRequestType::GetPoxSunsetBurn(ref _md, ref burn_height, ref burn_fee_cap) => {
+                ConversationHttp::handle_get_pox_sunset_burn(
+                    &mut self.connection.protocol,
+                    &mut reply,
+                    &req,
+                    sortdb,
+                    &network.burnchain,
+                    *burn_height,
+                    *burn_fee_cap,
+                    network.burnchain_tip.canonical_stacks_tip_height,
+                )?;
+                None
+            }
+            HttpRequestType::GetBlockCommitByHeight(ref _md, ref burn_height) => {
+                ConversationHttp::handle_get_block_commit_by_height(
+                    &mut self.connection.protocol,
+                    &mut reply,
+                    &req,
+                    sortdb,
+                    *burn_height,
+                    network.burnchain_tip.canonical_stacks_tip_height,
+                )?;
+                None
+            }
+            HttpRequestType::GetBlockCommitByStacksBlock(ref _md, ref index_block_hash) => {
+                ConversationHttp::handle_get_block_commit_by_stacks_block(
+                    &mut self.connection.protocol,
+                    &mut reply,
+                    &req,
+                    sortdb,
+                    chainstate,
+                    index_block_hash,
+                    network.burnchain_tip.canonical_stacks_tip_height,
+                )?;
+                None
+            }
             HttpRequestType::GetNeighbors(ref _md) => {
                 ConversationHttp::handle_getneighbors(
                     &mut self.connection.protocol,
@@ -2500,6 +4082,30 @@ impl ConversationHttp {
                     network.burnchain_tip.canonical_stacks_tip_height,
                 )?
             }
+            HttpRequestType::GetBlockStream(ref _md, ref start_height, ref end_height) => {
+                ConversationHttp::handle_get_block_stream(
+                    &mut self.connection.protocol,
+                    &mut reply,
+                    &req,
+                    sortdb,
+                    chainstate,
+                    *start_height,
+                    *end_height,
+                    network.burnchain_tip.canonical_stacks_tip_height,
+                )?
+            }
+            HttpRequestType::GetBlockByHeight(ref _md, ref height, ref unanchored) => {
+                ConversationHttp::handle_getblock_by_height(
+                    &mut self.connection.protocol,
+                    &mut reply,
+                    &req,
+                    *height,
+                    *unanchored,
+                    sortdb,
+                    chainstate,
+                    network.burnchain_tip.canonical_stacks_tip_height,
+                )?
+            }
             HttpRequestType::GetMicroblocksIndexed(ref _md, ref index_head_hash) => {
                 ConversationHttp::handle_getmicroblocks_indexed(
                     &mut self.connection.protocol,
@@ -2524,12 +4130,14 @@ impl ConversationHttp {
                 ref _md,
                 ref index_anchor_block_hash,
                 ref min_seq,
+                ref limit,
             ) => ConversationHttp::handle_getmicroblocks_unconfirmed(
                 &mut self.connection.protocol,
                 &mut reply,
                 &req,
                 index_anchor_block_hash,
                 *min_seq,
+                *limit,
                 chainstate,
                 network.burnchain_tip.canonical_stacks_tip_height,
             )?,
@@ -2545,6 +4153,27 @@ impl ConversationHttp {
                 )?;
                 None
             }
+            HttpRequestType::GetMempoolTransactionsByAddress(ref _md, ref address) => {
+                ConversationHttp::handle_get_mempool_transactions_by_address(
+                    &mut self.connection.protocol,
+                    &mut reply,
+                    &req,
+                    mempool,
+                    address,
+                    network.burnchain_tip.canonical_stacks_tip_height,
+                )?;
+                None
+            }
+            HttpRequestType::GetMempoolStats(ref _md) => {
+                ConversationHttp::handle_get_mempool_stats(
+                    &mut self.connection.protocol,
+                    &mut reply,
+                    &req,
+                    mempool,
+                    network.burnchain_tip.canonical_stacks_tip_height,
+                )?;
+                None
+            }
             HttpRequestType::GetAccount(ref _md, ref principal, ref tip_req, ref with_proof) => {
                 if let Some(tip) = ConversationHttp::handle_load_stacks_chain_tip(
                     &mut self.connection.protocol,
@@ -2561,6 +4190,8 @@ impl ConversationHttp {
                         &req,
                         sortdb,
                         chainstate,
+                        mempool,
+                        tip_req,
                         &tip,
                         principal,
                         *with_proof,
@@ -2569,6 +4200,29 @@ impl ConversationHttp {
                 }
                 None
             }
+            HttpRequestType::GetAccountStacking(ref _md, ref principal, ref tip_req) => {
+                if let Some(tip) = ConversationHttp::handle_load_stacks_chain_tip(
+                    &mut self.connection.protocol,
+                    &mut reply,
+                    &req,
+                    tip_req,
+                    sortdb,
+                    chainstate,
+                    network.burnchain_tip.canonical_stacks_tip_height,
+                )? {
+                    ConversationHttp::handle_get_account_stacking(
+                        &mut self.connection.protocol,
+                        &mut reply,
+                        &req,
+                        sortdb,
+                        chainstate,
+                        &tip,
+                        principal,
+                        network.burnchain_tip.canonical_stacks_tip_height,
+                    )?;
+                }
+                None
+            }
             HttpRequestType::GetDataVar(
                 ref _md,
                 ref contract_addr,
@@ -2646,6 +4300,27 @@ impl ConversationHttp {
                 )?;
                 None
             }
+            HttpRequestType::GetFeeRate(ref _md) => {
+                ConversationHttp::handle_get_fee_rate(
+                    &mut self.connection.protocol,
+                    &mut reply,
+                    &req,
+                    handler_opts,
+                    network.burnchain_tip.canonical_stacks_tip_height,
+                )?;
+                None
+            }
+            HttpRequestType::GetFeeRatePercentiles(ref _md, ref percentiles) => {
+                ConversationHttp::handle_get_fee_rate_percentiles(
+                    &mut self.connection.protocol,
+                    &mut reply,
+                    &req,
+                    percentiles,
+                    handler_opts,
+                    network.burnchain_tip.canonical_stacks_tip_height,
+                )?;
+                None
+            }
             HttpRequestType::GetContractABI(
                 ref _md,
                 ref contract_addr,
@@ -2757,6 +4432,30 @@ impl ConversationHttp {
                 }
                 None
             }
+            HttpRequestType::PostTransactionDryRun(ref _md, ref tx, ref tip_req) => {
+                if let Some(tip) = ConversationHttp::handle_load_stacks_chain_tip(
+                    &mut self.connection.protocol,
+                    &mut reply,
+                    &req,
+                    tip_req,
+                    sortdb,
+                    chainstate,
+                    network.burnchain_tip.canonical_stacks_tip_height,
+                )? {
+                    ConversationHttp::handle_post_transaction_dry_run(
+                        &mut self.connection.protocol,
+                        &mut reply,
+                        &req,
+                        sortdb,
+                        chainstate,
+                        &tip,
+                        tx,
+                        &self.connection.options,
+                        network.burnchain_tip.canonical_stacks_tip_height,
+                    )?;
+                }
+                None
+            }
             HttpRequestType::PostTransaction(ref _md, ref tx, ref attachment) => {
                 match chainstate.get_stacks_chain_tip(sortdb)? {
                     Some(tip) => {
@@ -2778,7 +4477,7 @@ impl ConversationHttp {
                         )?;
                         if accepted {
                             // forward to peer network
-                            ret = Some(StacksMessageType::Transaction(tx.clone()));
+                            ret.push(StacksMessageType::Transaction(tx.clone()));
                         }
                     }
                     None => {
@@ -2796,6 +4495,41 @@ impl ConversationHttp {
                 }
                 None
             }
+            HttpRequestType::PostTransactionBatch(ref _md, ref txs) => {
+                match chainstate.get_stacks_chain_tip(sortdb)? {
+                    Some(tip) => {
+                        let accepted = ConversationHttp::handle_post_transaction_batch(
+                            &mut self.connection.protocol,
+                            &mut reply,
+                            &req,
+                            chainstate,
+                            sortdb,
+                            tip.consensus_hash,
+                            tip.anchored_block_hash,
+                            mempool,
+                            txs,
+                            handler_opts.event_observer.as_deref(),
+                            network.burnchain_tip.canonical_stacks_tip_height,
+                            network.ast_rules,
+                        )?;
+                        // forward to peer network
+                        ret.extend(accepted.into_iter().map(StacksMessageType::Transaction));
+                    }
+                    None => {
+                        let response_metadata = HttpResponseMetadata::from_http_request_type(
+                            &req,
+                            Some(network.burnchain_tip.canonical_stacks_tip_height),
+                        );
+                        warn!("Failed to load Stacks chain tip");
+                        let response = HttpResponseType::ServerError(
+                            response_metadata,
+                            format!("Failed to load Stacks chain tip"),
+                        );
+                        response.send(&mut self.connection.protocol, &mut reply)?;
+                    }
+                }
+                None
+            }
             HttpRequestType::GetAttachment(ref _md, ref content_hash) => {
                 ConversationHttp::handle_getattachment(
                     &mut self.connection.protocol,
@@ -2837,12 +4571,36 @@ impl ConversationHttp {
                 )?;
                 if accepted {
                     // inform the peer network so it can announce its presence
-                    ret = Some(StacksMessageType::Blocks(BlocksData {
+                    ret.push(StacksMessageType::Blocks(BlocksData {
                         blocks: vec![BlocksDatum(consensus_hash.clone(), block.clone())],
                     }));
                 }
                 None
             }
+            HttpRequestType::PostBlockValidate(ref _md, ref block) => {
+                ConversationHttp::handle_post_block_validate(
+                    &mut self.connection.protocol,
+                    &mut reply,
+                    &req,
+                    sortdb,
+                    chainstate,
+                    block,
+                    network.burnchain_tip.canonical_stacks_tip_height,
+                )?;
+                None
+            }
+            HttpRequestType::PostMicroblocksValidate(ref _md, ref mblocks) => {
+                ConversationHttp::handle_post_microblocks_validate(
+                    &mut self.connection.protocol,
+                    &mut reply,
+                    &req,
+                    sortdb,
+                    chainstate,
+                    mblocks,
+                    network.burnchain_tip.canonical_stacks_tip_height,
+                )?;
+                None
+            }
             HttpRequestType::PostMicroblock(ref _md, ref mblock, ref tip_req) => {
                 if let Some(tip) = ConversationHttp::handle_load_stacks_chain_tip(
                     &mut self.connection.protocol,
@@ -2880,7 +4638,7 @@ impl ConversationHttp {
                                 &consensus_hash,
                                 &block_hash,
                             );
-                            ret = Some(StacksMessageType::Microblocks(MicroblocksData {
+                            ret.push(StacksMessageType::Microblocks(MicroblocksData {
                                 index_anchor_block: tip,
                                 microblocks: vec![(*mblock).clone()],
                             }));
@@ -2957,6 +4715,10 @@ impl ConversationHttp {
                     ClientError::NotFound(path) => {
                         HttpResponseType::NotFound(response_metadata, path.clone())
                     }
+                    ClientError::MalformedTransaction(s) => HttpResponseType::BadRequestJSON(
+                        response_metadata,
+                        json!(PostTransactionError::Malformed(s.to_string())),
+                    ),
                 };
 
                 response
@@ -3264,15 +5026,13 @@ impl ConversationHttp {
                     }
                     let start_time = Instant::now();
                     let path = req.get_path();
-                    let msg_opt = monitoring::instrument_http_request_handler(req, |req| {
+                    let msgs = monitoring::instrument_http_request_handler(req, |req| {
                         self.handle_request(req, network, sortdb, chainstate, mempool, handler_args)
                     })?;
 
                     debug!("Processed HTTPRequest"; "path" => %path, "processing_time_ms" => start_time.elapsed().as_millis(), "conn_id" => self.conn_id, "peer_addr" => &self.peer_addr);
 
-                    if let Some(msg) = msg_opt {
-                        ret.push(msg);
-                    }
+                    ret.extend(msgs);
                 }
                 StacksHttpMessage::Response(resp) => {
                     // Is there someone else waiting for this message?  If so, pass it along.
@@ -3379,6 +5139,47 @@ impl ConversationHttp {
         )
     }
 
+    /// Make a new request for the PoX reward cycle containing a burn block height
+    pub fn new_get_pox_reward_cycle(
+        &self,
+        burn_height: u64,
+        tip_req: TipRequest,
+    ) -> HttpRequestType {
+        HttpRequestType::GetPoxRewardCycle(
+            HttpRequestMetadata::from_host(self.peer_host.clone(), None),
+            burn_height,
+            tip_req,
+        )
+    }
+
+    /// Make a new request for the expected sunset burn of a block-commit
+    pub fn new_get_pox_sunset_burn(&self, burn_height: u64, burn_fee_cap: u64) -> HttpRequestType {
+        HttpRequestType::GetPoxSunsetBurn(
+            HttpRequestMetadata::from_host(self.peer_host.clone(), None),
+            burn_height,
+            burn_fee_cap,
+        )
+    }
+
+    /// Make a new request for the block-commit that won sortition at a burnchain block height
+    pub fn new_get_block_commit_by_height(&self, burn_height: u64) -> HttpRequestType {
+        HttpRequestType::GetBlockCommitByHeight(
+            HttpRequestMetadata::from_host(self.peer_host.clone(), None),
+            burn_height,
+        )
+    }
+
+    /// Make a new request for the block-commit that produced a Stacks block
+    pub fn new_get_block_commit_by_stacks_block(
+        &self,
+        index_block_hash: StacksBlockId,
+    ) -> HttpRequestType {
+        HttpRequestType::GetBlockCommitByStacksBlock(
+            HttpRequestMetadata::from_host(self.peer_host.clone(), None),
+            index_block_hash,
+        )
+    }
+
     /// Make a new getneighbors request to this endpoint
     pub fn new_getneighbors(&self) -> HttpRequestType {
         HttpRequestType::GetNeighbors(HttpRequestMetadata::from_host(self.peer_host.clone(), None))
@@ -3401,6 +5202,24 @@ impl ConversationHttp {
         )
     }
 
+    /// Make a new block stream request to this endpoint
+    pub fn new_get_block_stream(&self, start_height: u64, end_height: u64) -> HttpRequestType {
+        HttpRequestType::GetBlockStream(
+            HttpRequestMetadata::from_host(self.peer_host.clone(), None),
+            start_height,
+            end_height,
+        )
+    }
+
+    /// Make a new get-block-by-height request to this endpoint
+    pub fn new_getblock_by_height(&self, height: u64, unanchored: bool) -> HttpRequestType {
+        HttpRequestType::GetBlockByHeight(
+            HttpRequestMetadata::from_host(self.peer_host.clone(), None),
+            height,
+            unanchored,
+        )
+    }
+
     /// Make a new get-microblocks request to this endpoint
     pub fn new_getmicroblocks_indexed(
         &self,
@@ -3428,11 +5247,13 @@ impl ConversationHttp {
         &self,
         anchored_index_block_hash: StacksBlockId,
         min_seq: u16,
+        limit: Option<u16>,
     ) -> HttpRequestType {
         HttpRequestType::GetMicroblocksUnconfirmed(
             HttpRequestMetadata::from_host(self.peer_host.clone(), None),
             anchored_index_block_hash,
             min_seq,
+            limit,
         )
     }
 
@@ -3444,6 +5265,33 @@ impl ConversationHttp {
         )
     }
 
+    /// Make a new get-mempool-transactions-by-address request
+    pub fn new_get_mempool_transactions_by_address(
+        &self,
+        address: StacksAddress,
+    ) -> HttpRequestType {
+        HttpRequestType::GetMempoolTransactionsByAddress(
+            HttpRequestMetadata::from_host(self.peer_host.clone(), None),
+            address,
+        )
+    }
+
+    /// Make a new get-mempool-stats request
+    pub fn new_get_mempool_stats(&self) -> HttpRequestType {
+        HttpRequestType::GetMempoolStats(HttpRequestMetadata::from_host(
+            self.peer_host.clone(),
+            None,
+        ))
+    }
+
+    /// Make a new get-fee-rate-percentiles request
+    pub fn new_get_fee_rate_percentiles(&self, percentiles: Vec<u8>) -> HttpRequestType {
+        HttpRequestType::GetFeeRatePercentiles(
+            HttpRequestMetadata::from_host(self.peer_host.clone(), None),
+            percentiles,
+        )
+    }
+
     /// Make a new post-transaction request
     pub fn new_post_transaction(&self, tx: StacksTransaction) -> HttpRequestType {
         HttpRequestType::PostTransaction(
@@ -3453,6 +5301,35 @@ impl ConversationHttp {
         )
     }
 
+    /// Make a new batched post-transaction request
+    pub fn new_post_transaction_batch(&self, txs: Vec<StacksTransaction>) -> HttpRequestType {
+        HttpRequestType::PostTransactionBatch(
+            HttpRequestMetadata::from_host(self.peer_host.clone(), None),
+            txs.iter().map(|tx| tx.serialize_to_vec()).collect(),
+        )
+    }
+
+    /// Make a new post-transaction-dry-run request
+    pub fn new_post_transaction_dry_run(
+        &self,
+        tx: StacksTransaction,
+        tip_req: TipRequest,
+    ) -> HttpRequestType {
+        HttpRequestType::PostTransactionDryRun(
+            HttpRequestMetadata::from_host(self.peer_host.clone(), None),
+            tx,
+            tip_req,
+        )
+    }
+
+    /// Make a new post-block-validate request
+    pub fn new_post_block_validate(&self, block: StacksBlock) -> HttpRequestType {
+        HttpRequestType::PostBlockValidate(
+            HttpRequestMetadata::from_host(self.peer_host.clone(), None),
+            block,
+        )
+    }
+
     /// Make a new post-block request
     pub fn new_post_block(&self, ch: ConsensusHash, block: StacksBlock) -> HttpRequestType {
         HttpRequestType::PostBlock(
@@ -3475,6 +5352,14 @@ impl ConversationHttp {
         )
     }
 
+    /// Make a new post-microblocks-validate request
+    pub fn new_post_microblocks_validate(&self, mblocks: Vec<StacksMicroblock>) -> HttpRequestType {
+        HttpRequestType::PostMicroblocksValidate(
+            HttpRequestMetadata::from_host(self.peer_host.clone(), None),
+            mblocks,
+        )
+    }
+
     /// Make a new request for an account
     pub fn new_getaccount(
         &self,
@@ -3490,6 +5375,19 @@ impl ConversationHttp {
         )
     }
 
+    /// Make a new request for an account's stacking status
+    pub fn new_getaccountstacking(
+        &self,
+        principal: PrincipalData,
+        tip_req: TipRequest,
+    ) -> HttpRequestType {
+        HttpRequestType::GetAccountStacking(
+            HttpRequestMetadata::from_host(self.peer_host.clone(), None),
+            principal,
+            tip_req,
+        )
+    }
+
     /// Make a new request for a data var
     pub fn new_getdatavar(
         &self,
@@ -3615,6 +5513,7 @@ impl ConversationHttp {
 #[cfg(test)]
 mod test {
     use std::cell::RefCell;
+    use std::collections::BTreeMap;
     use std::convert::TryInto;
     use std::iter::FromIterator;
 
@@ -3624,8 +5523,11 @@ mod test {
     use crate::burnchains::*;
     use crate::chainstate::burn::ConsensusHash;
     use crate::chainstate::stacks::db::blocks::test::*;
-    use crate::chainstate::stacks::db::StacksChainState;
+    use crate::chainstate::stacks::db::test::instantiate_chainstate;
     use crate::chainstate::stacks::db::StreamCursor;
+    use crate::chainstate::stacks::db::{
+        MinerPaymentSchedule, StacksChainState, StacksEpochReceipt, StacksHeaderInfo,
+    };
     use crate::chainstate::stacks::miner::*;
     use crate::chainstate::stacks::test::*;
     use crate::chainstate::stacks::Error as chain_error;
@@ -3637,14 +5539,18 @@ mod test {
     use clarity::vm::types::*;
     use stacks_common::address::*;
     use stacks_common::util::get_epoch_time_secs;
-    use stacks_common::util::hash::hex_bytes;
+    use stacks_common::util::hash::{hex_bytes, Sha512Trunc256Sum};
     use stacks_common::util::pipe::*;
 
     use crate::chainstate::stacks::C32_ADDRESS_VERSION_TESTNET_SINGLESIG;
     use crate::types::chainstate::BlockHeaderHash;
     use crate::types::chainstate::BurnchainHeaderHash;
 
+    use crate::chainstate::stacks::db::transactions::TransactionNonceMismatch;
+    use crate::codec::Error as codec_error;
     use crate::core::mempool::{BLOOM_COUNTER_ERROR_RATE, MAX_BLOOM_COUNTER_TXS};
+    use crate::core::StacksEpochId;
+    use crate::cost_estimates::FeeRateEstimate;
 
     use super::*;
 
@@ -4449,18 +6355,59 @@ mod test {
 
     #[test]
     #[ignore]
-    fn test_rpc_getneighbors() {
+    fn test_rpc_get_pox_reward_cycle() {
+        // Test v2/pox/reward-cycle/:height (aka GetPoxRewardCycle) endpoint.
+        // The reward cycle boundaries and reward set reported for the current burnchain tip
+        // must match what the chainstate computes for the canonical Stacks tip.
+        let expected_info = RefCell::new(None);
         test_rpc(
             function_name!(),
-            40010,
-            40011,
-            50010,
-            50011,
+            40186,
+            40187,
+            50186,
+            50187,
             true,
             |ref mut peer_client,
              ref mut convo_client,
              ref mut peer_server,
-             ref mut convo_server| { convo_client.new_getneighbors() },
+             ref mut convo_server| {
+                let sortdb = peer_server.sortdb.as_ref().unwrap();
+                let chainstate = &mut peer_server.stacks_node.as_mut().unwrap().chainstate;
+                let burnchain = &peer_client.config.burnchain;
+                let burn_height = SortitionDB::get_canonical_burn_chain_tip(sortdb.conn())
+                    .unwrap()
+                    .block_height;
+                let stacks_block_id = {
+                    let tip = chainstate.get_stacks_chain_tip(sortdb).unwrap().unwrap();
+                    StacksBlockHeader::make_index_block_hash(
+                        &tip.consensus_hash,
+                        &tip.anchored_block_hash,
+                    )
+                };
+
+                let cycle_number = burnchain.block_height_to_reward_cycle(burn_height).unwrap();
+                let start_height = burnchain.reward_cycle_to_block_height(cycle_number);
+                let reward_addresses = chainstate
+                    .get_reward_addresses(burnchain, sortdb, burn_height, &stacks_block_id)
+                    .unwrap()
+                    .into_iter()
+                    .map(|entry| RPCPoxRewardAddress {
+                        address: entry.reward_address.to_b58(),
+                        amount: entry.amount_stacked,
+                    })
+                    .collect();
+
+                *expected_info.borrow_mut() = Some(RPCPoxRewardCycleInfo {
+                    cycle_number,
+                    start_height,
+                    end_height: start_height
+                        + u64::from(burnchain.pox_constants.reward_cycle_length)
+                        - 1,
+                    is_prepare_phase: burnchain.is_in_prepare_phase(burn_height),
+                    reward_addresses,
+                });
+                convo_client.new_get_pox_reward_cycle(burn_height, TipRequest::UseLatestAnchoredTip)
+            },
             |ref http_request,
              ref http_response,
              ref mut peer_client,
@@ -4469,14 +6416,8 @@ mod test {
              ref convo_server| {
                 let req_md = http_request.metadata().clone();
                 match http_response {
-                    HttpResponseType::Neighbors(response_md, neighbor_info) => {
-                        assert_eq!(neighbor_info.sample.len(), 1);
-                        assert_eq!(neighbor_info.sample[0].port, peer_client.config.server_port); // we see ourselves as the neighbor
-                        assert_eq!(neighbor_info.bootstrap.len(), 1);
-                        assert_eq!(
-                            neighbor_info.bootstrap[0].port,
-                            peer_client.config.server_port
-                        ); // we see ourselves as the bootstrap
+                    HttpResponseType::PoxRewardCycle(response_md, reward_cycle) => {
+                        assert_eq!(Some((*reward_cycle).clone()), *expected_info.borrow());
                         true
                     }
                     _ => {
@@ -4490,67 +6431,22 @@ mod test {
 
     #[test]
     #[ignore]
-    fn test_rpc_getheaders() {
-        let server_blocks_cell = RefCell::new(None);
-
+    fn test_rpc_get_pox_sunset_burn_after_sunset_end() {
+        // Test v2/pox/sunset-burn/:height/:burn_fee_cap (aka GetPoxSunsetBurn) endpoint.
+        // Heights beyond the PoX sunset end are rejected.
         test_rpc(
             function_name!(),
-            40012,
-            40013,
-            50012,
-            50013,
+            40188,
+            40189,
+            50188,
+            50189,
             true,
             |ref mut peer_client,
              ref mut convo_client,
              ref mut peer_server,
              ref mut convo_server| {
-                // have "server" peer store a few continuous block to staging
-                let mut blocks: Vec<StacksBlock> = vec![];
-                let mut index_block_hashes = vec![];
-                for i in 0..25 {
-                    let mut peer_server_block = make_codec_test_block(25);
-
-                    peer_server_block.header.total_work.work = (i + 1) as u64;
-                    peer_server_block.header.total_work.burn = (i + 1) as u64;
-                    peer_server_block.header.parent_block = blocks
-                        .last()
-                        .map(|blk| blk.block_hash())
-                        .unwrap_or(BlockHeaderHash([0u8; 32]));
-
-                    let peer_server_consensus_hash = ConsensusHash([(i + 1) as u8; 20]);
-                    let index_block_hash = StacksBlockHeader::make_index_block_hash(
-                        &peer_server_consensus_hash,
-                        &peer_server_block.block_hash(),
-                    );
-
-                    test_debug!("Store peer server index block {:?}", &index_block_hash);
-                    store_staging_block(
-                        peer_server.chainstate(),
-                        &peer_server_consensus_hash,
-                        &peer_server_block,
-                        &ConsensusHash([i as u8; 20]),
-                        456,
-                        123,
-                    );
-                    set_block_processed(
-                        peer_server.chainstate(),
-                        &peer_server_consensus_hash,
-                        &peer_server_block.block_hash(),
-                        true,
-                    );
-
-                    index_block_hashes.push(index_block_hash);
-                    blocks.push(peer_server_block);
-                }
-
-                let rev_blocks: Vec<_> = blocks.into_iter().rev().collect();
-                let rev_ibhs: Vec<_> = index_block_hashes.into_iter().rev().collect();
-
-                let tip = rev_ibhs[0].clone();
-                *server_blocks_cell.borrow_mut() = Some((rev_blocks, rev_ibhs));
-
-                // now ask for it
-                convo_client.new_getheaders(25, TipRequest::SpecificTip(tip))
+                let sunset_end = peer_server.config.burnchain.pox_constants.sunset_end;
+                convo_client.new_get_pox_sunset_burn(sunset_end + 1, 100_000)
             },
             |ref http_request,
              ref http_response,
@@ -4560,16 +6456,8 @@ mod test {
              ref convo_server| {
                 let req_md = http_request.metadata().clone();
                 match http_response {
-                    HttpResponseType::Headers(response_md, headers) => {
-                        assert_eq!(headers.len(), 25);
-                        let expected = server_blocks_cell.borrow().clone().unwrap();
-                        for (i, h) in headers.iter().enumerate() {
-                            assert_eq!(h.header, expected.0[i].header);
-                            assert_eq!(h.consensus_hash, ConsensusHash([(25 - i) as u8; 20]));
-                            if i + 1 < headers.len() {
-                                assert_eq!(h.parent_block_id, expected.1[i + 1]);
-                            }
-                        }
+                    HttpResponseType::BadRequest(_, msg) => {
+                        assert!(msg.contains("beyond the PoX sunset end height"));
                         true
                     }
                     _ => {
@@ -4583,39 +6471,302 @@ mod test {
 
     #[test]
     #[ignore]
-    fn test_rpc_unconfirmed_getblock() {
-        let server_block_cell = RefCell::new(None);
-
+    fn test_rpc_get_block_commit_by_stacks_block() {
+        // Test v2/block-commits/stacks-block/:hash (aka GetBlockCommitByStacksBlock) endpoint.
+        // The canonical Stacks tip must be reported with the block-commit that produced it.
+        let expected_commit = RefCell::new(None);
         test_rpc(
             function_name!(),
-            40020,
-            40021,
-            50020,
-            50021,
+            40194,
+            40195,
+            50194,
+            50195,
             true,
             |ref mut peer_client,
              ref mut convo_client,
              ref mut peer_server,
              ref mut convo_server| {
-                // have "server" peer store a block to staging
-                let peer_server_block = make_codec_test_block(25);
-                let peer_server_consensus_hash = ConsensusHash([0x02; 20]);
-                let index_block_hash = StacksBlockHeader::make_index_block_hash(
-                    &peer_server_consensus_hash,
-                    &peer_server_block.block_hash(),
-                );
-
-                test_debug!("Store peer server index block {:?}", &index_block_hash);
-                store_staging_block(
-                    peer_server.chainstate(),
-                    &peer_server_consensus_hash,
-                    &peer_server_block,
-                    &ConsensusHash([0x03; 20]),
-                    456,
-                    123,
-                );
-
-                *server_block_cell.borrow_mut() = Some(peer_server_block);
+                let sortdb = peer_server.sortdb.as_ref().unwrap();
+                let chainstate = &mut peer_server.stacks_node.as_mut().unwrap().chainstate;
+                let tip = chainstate.get_stacks_chain_tip(sortdb).unwrap().unwrap();
+                let snapshot =
+                    SortitionDB::get_block_snapshot_consensus(sortdb.conn(), &tip.consensus_hash)
+                        .unwrap()
+                        .unwrap();
+                let commit = SortitionDB::get_block_commit(
+                    sortdb.conn(),
+                    &snapshot.winning_block_txid,
+                    &snapshot.sortition_id,
+                )
+                .unwrap()
+                .unwrap();
+                assert_eq!(commit.block_header_hash, tip.anchored_block_hash);
+
+                *expected_commit.borrow_mut() = Some((snapshot, commit));
+                convo_client.new_get_block_commit_by_stacks_block(
+                    StacksBlockHeader::make_index_block_hash(
+                        &tip.consensus_hash,
+                        &tip.anchored_block_hash,
+                    ),
+                )
+            },
+            |ref http_request,
+             ref http_response,
+             ref mut peer_client,
+             ref mut peer_server,
+             ref convo_client,
+             ref convo_server| {
+                let req_md = http_request.metadata().clone();
+                let (snapshot, commit) = expected_commit.borrow().clone().unwrap();
+                match http_response {
+                    HttpResponseType::BlockCommitInfo(response_md, block_commit_info) => {
+                        assert_eq!(block_commit_info.burn_block_height, snapshot.block_height);
+                        assert_eq!(block_commit_info.consensus_hash, snapshot.consensus_hash);
+                        let winning_block_commit =
+                            block_commit_info.winning_block_commit.clone().unwrap();
+                        assert_eq!(winning_block_commit.txid, commit.txid);
+                        assert_eq!(
+                            winning_block_commit.block_header_hash,
+                            commit.block_header_hash
+                        );
+                        assert_eq!(winning_block_commit.burn_fee, commit.burn_fee);
+                        assert_eq!(winning_block_commit.sunset_burn, commit.sunset_burn);
+                        assert_eq!(
+                            winning_block_commit.commit_outs,
+                            commit
+                                .commit_outs
+                                .iter()
+                                .map(|out| out.clone().to_b58())
+                                .collect::<Vec<_>>()
+                        );
+                        assert_eq!(
+                            winning_block_commit.parent_block_ptr,
+                            commit.parent_block_ptr
+                        );
+                        assert_eq!(winning_block_commit.key_block_ptr, commit.key_block_ptr);
+                        true
+                    }
+                    _ => {
+                        error!("Invalid response: {:?}", &http_response);
+                        false
+                    }
+                }
+            },
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn test_rpc_get_block_commit_by_height_beyond_tip() {
+        // Test v2/block-commits/:height (aka GetBlockCommitByHeight) endpoint.
+        // Heights beyond the canonical burnchain tip are not found.
+        test_rpc(
+            function_name!(),
+            40196,
+            40197,
+            50196,
+            50197,
+            true,
+            |ref mut peer_client,
+             ref mut convo_client,
+             ref mut peer_server,
+             ref mut convo_server| {
+                let sortdb = peer_server.sortdb.as_ref().unwrap();
+                let burn_height = SortitionDB::get_canonical_burn_chain_tip(sortdb.conn())
+                    .unwrap()
+                    .block_height;
+                convo_client.new_get_block_commit_by_height(burn_height + 1)
+            },
+            |ref http_request,
+             ref http_response,
+             ref mut peer_client,
+             ref mut peer_server,
+             ref convo_client,
+             ref convo_server| {
+                let req_md = http_request.metadata().clone();
+                match http_response {
+                    HttpResponseType::NotFound(_, msg) => {
+                        assert!(msg.contains("No burnchain block at height"));
+                        true
+                    }
+                    _ => {
+                        error!("Invalid response: {:?}", &http_response);
+                        false
+                    }
+                }
+            },
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn test_rpc_getneighbors() {
+        test_rpc(
+            function_name!(),
+            40010,
+            40011,
+            50010,
+            50011,
+            true,
+            |ref mut peer_client,
+             ref mut convo_client,
+             ref mut peer_server,
+             ref mut convo_server| { convo_client.new_getneighbors() },
+            |ref http_request,
+             ref http_response,
+             ref mut peer_client,
+             ref mut peer_server,
+             ref convo_client,
+             ref convo_server| {
+                let req_md = http_request.metadata().clone();
+                match http_response {
+                    HttpResponseType::Neighbors(response_md, neighbor_info) => {
+                        assert_eq!(neighbor_info.sample.len(), 1);
+                        assert_eq!(neighbor_info.sample[0].port, peer_client.config.server_port); // we see ourselves as the neighbor
+                        assert_eq!(neighbor_info.bootstrap.len(), 1);
+                        assert_eq!(
+                            neighbor_info.bootstrap[0].port,
+                            peer_client.config.server_port
+                        ); // we see ourselves as the bootstrap
+                        true
+                    }
+                    _ => {
+                        error!("Invalid response: {:?}", &http_response);
+                        false
+                    }
+                }
+            },
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn test_rpc_getheaders() {
+        let server_blocks_cell = RefCell::new(None);
+
+        test_rpc(
+            function_name!(),
+            40012,
+            40013,
+            50012,
+            50013,
+            true,
+            |ref mut peer_client,
+             ref mut convo_client,
+             ref mut peer_server,
+             ref mut convo_server| {
+                // have "server" peer store a few continuous block to staging
+                let mut blocks: Vec<StacksBlock> = vec![];
+                let mut index_block_hashes = vec![];
+                for i in 0..25 {
+                    let mut peer_server_block = make_codec_test_block(25);
+
+                    peer_server_block.header.total_work.work = (i + 1) as u64;
+                    peer_server_block.header.total_work.burn = (i + 1) as u64;
+                    peer_server_block.header.parent_block = blocks
+                        .last()
+                        .map(|blk| blk.block_hash())
+                        .unwrap_or(BlockHeaderHash([0u8; 32]));
+
+                    let peer_server_consensus_hash = ConsensusHash([(i + 1) as u8; 20]);
+                    let index_block_hash = StacksBlockHeader::make_index_block_hash(
+                        &peer_server_consensus_hash,
+                        &peer_server_block.block_hash(),
+                    );
+
+                    test_debug!("Store peer server index block {:?}", &index_block_hash);
+                    store_staging_block(
+                        peer_server.chainstate(),
+                        &peer_server_consensus_hash,
+                        &peer_server_block,
+                        &ConsensusHash([i as u8; 20]),
+                        456,
+                        123,
+                    );
+                    set_block_processed(
+                        peer_server.chainstate(),
+                        &peer_server_consensus_hash,
+                        &peer_server_block.block_hash(),
+                        true,
+                    );
+
+                    index_block_hashes.push(index_block_hash);
+                    blocks.push(peer_server_block);
+                }
+
+                let rev_blocks: Vec<_> = blocks.into_iter().rev().collect();
+                let rev_ibhs: Vec<_> = index_block_hashes.into_iter().rev().collect();
+
+                let tip = rev_ibhs[0].clone();
+                *server_blocks_cell.borrow_mut() = Some((rev_blocks, rev_ibhs));
+
+                // now ask for it
+                convo_client.new_getheaders(25, TipRequest::SpecificTip(tip))
+            },
+            |ref http_request,
+             ref http_response,
+             ref mut peer_client,
+             ref mut peer_server,
+             ref convo_client,
+             ref convo_server| {
+                let req_md = http_request.metadata().clone();
+                match http_response {
+                    HttpResponseType::Headers(response_md, headers) => {
+                        assert_eq!(headers.len(), 25);
+                        let expected = server_blocks_cell.borrow().clone().unwrap();
+                        for (i, h) in headers.iter().enumerate() {
+                            assert_eq!(h.header, expected.0[i].header);
+                            assert_eq!(h.consensus_hash, ConsensusHash([(25 - i) as u8; 20]));
+                            if i + 1 < headers.len() {
+                                assert_eq!(h.parent_block_id, expected.1[i + 1]);
+                            }
+                        }
+                        true
+                    }
+                    _ => {
+                        error!("Invalid response: {:?}", &http_response);
+                        false
+                    }
+                }
+            },
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn test_rpc_unconfirmed_getblock() {
+        let server_block_cell = RefCell::new(None);
+
+        test_rpc(
+            function_name!(),
+            40020,
+            40021,
+            50020,
+            50021,
+            true,
+            |ref mut peer_client,
+             ref mut convo_client,
+             ref mut peer_server,
+             ref mut convo_server| {
+                // have "server" peer store a block to staging
+                let peer_server_block = make_codec_test_block(25);
+                let peer_server_consensus_hash = ConsensusHash([0x02; 20]);
+                let index_block_hash = StacksBlockHeader::make_index_block_hash(
+                    &peer_server_consensus_hash,
+                    &peer_server_block.block_hash(),
+                );
+
+                test_debug!("Store peer server index block {:?}", &index_block_hash);
+                store_staging_block(
+                    peer_server.chainstate(),
+                    &peer_server_consensus_hash,
+                    &peer_server_block,
+                    &ConsensusHash([0x03; 20]),
+                    456,
+                    123,
+                );
+
+                *server_block_cell.borrow_mut() = Some(peer_server_block);
 
                 // now ask for it
                 convo_client.new_getblock(index_block_hash)
@@ -4715,37 +6866,258 @@ mod test {
 
     #[test]
     #[ignore]
-    fn test_rpc_get_indexed_microblocks() {
-        let server_microblocks_cell = RefCell::new(vec![]);
-
+    fn test_rpc_get_block_stream() {
+        // Test v2/blocks/stream (aka GetBlockStream) endpoint.
+        // Every stored block that won sortition in the range comes back, in height order.
+        let expected_blocks = RefCell::new(vec![]);
         test_rpc(
             function_name!(),
-            40040,
-            40041,
-            50040,
-            50041,
+            40198,
+            40199,
+            50198,
+            50199,
             true,
             |ref mut peer_client,
              ref mut convo_client,
              ref mut peer_server,
              ref mut convo_server| {
-                let privk = StacksPrivateKey::from_hex(
-                    "eb05c83546fdd2c79f10f5ad5434a90dd28f7e3acb7c092157aa1bc3656b012c01",
-                )
-                .unwrap();
-
-                let parent_block = make_codec_test_block(25);
-                let parent_consensus_hash = ConsensusHash([0x02; 20]);
-                let parent_index_block_hash = StacksBlockHeader::make_index_block_hash(
-                    &parent_consensus_hash,
-                    &parent_block.block_hash(),
-                );
-
-                let mut mblocks = make_sample_microblock_stream(&privk, &parent_block.block_hash());
-                mblocks.truncate(15);
+                let sortdb = peer_server.sortdb.as_ref().unwrap();
+                let chainstate = &mut peer_server.stacks_node.as_mut().unwrap().chainstate;
+                let tip = SortitionDB::get_canonical_burn_chain_tip(sortdb.conn()).unwrap();
+                let start_height = tip.block_height.saturating_sub(3);
 
-                let mut child_block = make_codec_test_block(25);
-                let child_consensus_hash = ConsensusHash([0x03; 20]);
+                let mut blocks = vec![];
+                for burn_height in start_height..=tip.block_height {
+                    let snapshot = SortitionDB::get_ancestor_snapshot(
+                        &sortdb.index_conn(),
+                        burn_height,
+                        &tip.sortition_id,
+                    )
+                    .unwrap()
+                    .unwrap();
+                    if !snapshot.sortition {
+                        continue;
+                    }
+                    if let Some(block) = StacksChainState::load_block(
+                        &chainstate.blocks_path,
+                        &snapshot.consensus_hash,
+                        &snapshot.winning_stacks_block_hash,
+                    )
+                    .unwrap()
+                    {
+                        blocks.push(block);
+                    }
+                }
+                assert!(blocks.len() > 0);
+
+                *expected_blocks.borrow_mut() = blocks;
+                convo_client.new_get_block_stream(start_height, tip.block_height)
+            },
+            |ref http_request,
+             ref http_response,
+             ref mut peer_client,
+             ref mut peer_server,
+             ref convo_client,
+             ref convo_server| {
+                let req_md = http_request.metadata().clone();
+                match http_response {
+                    HttpResponseType::Blocks(response_md, blocks) => {
+                        assert_eq!(*blocks, *expected_blocks.borrow());
+                        true
+                    }
+                    _ => {
+                        error!("Invalid response: {:?}", &http_response);
+                        false
+                    }
+                }
+            },
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn test_rpc_get_block_by_height() {
+        // Test v2/blocks/height (aka GetBlockByHeight) endpoint.
+        // The block at the canonical tip's height is the canonical tip.
+        let expected_block = RefCell::new(None);
+        test_rpc(
+            function_name!(),
+            40200,
+            40201,
+            50200,
+            50201,
+            false,
+            |ref mut peer_client,
+             ref mut convo_client,
+             ref mut peer_server,
+             ref mut convo_server| {
+                let sortdb = peer_server.sortdb.as_ref().unwrap();
+                let chainstate = &mut peer_server.stacks_node.as_mut().unwrap().chainstate;
+                let tip = chainstate.get_stacks_chain_tip(sortdb).unwrap().unwrap();
+                let tip_index_block_hash = StacksBlockHeader::make_index_block_hash(
+                    &tip.consensus_hash,
+                    &tip.anchored_block_hash,
+                );
+                let index_block_hash = chainstate
+                    .index_conn()
+                    .unwrap()
+                    .get_ancestor_block_hash(tip.height, &tip_index_block_hash)
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(index_block_hash, tip_index_block_hash);
+
+                let block = StacksChainState::load_block(
+                    &chainstate.blocks_path,
+                    &tip.consensus_hash,
+                    &tip.anchored_block_hash,
+                )
+                .unwrap()
+                .unwrap();
+                *expected_block.borrow_mut() = Some(block);
+                convo_client.new_getblock_by_height(tip.height, false)
+            },
+            |ref http_request,
+             ref http_response,
+             ref mut peer_client,
+             ref mut peer_server,
+             ref convo_client,
+             ref convo_server| {
+                let req_md = http_request.metadata().clone();
+                match http_response {
+                    HttpResponseType::Block(response_md, block) => {
+                        assert_eq!(Some(block), expected_block.borrow().as_ref());
+                        true
+                    }
+                    _ => {
+                        error!("Invalid response: {:?}", &http_response);
+                        false
+                    }
+                }
+            },
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn test_rpc_get_block_by_height_unanchored() {
+        // The canonical tip confirmed no microblocks, so it comes back with an empty stream.
+        let expected_block = RefCell::new(None);
+        test_rpc(
+            function_name!(),
+            40202,
+            40203,
+            50202,
+            50203,
+            true,
+            |ref mut peer_client,
+             ref mut convo_client,
+             ref mut peer_server,
+             ref mut convo_server| {
+                let sortdb = peer_server.sortdb.as_ref().unwrap();
+                let chainstate = &mut peer_server.stacks_node.as_mut().unwrap().chainstate;
+                let tip = chainstate.get_stacks_chain_tip(sortdb).unwrap().unwrap();
+                let block = StacksChainState::load_block(
+                    &chainstate.blocks_path,
+                    &tip.consensus_hash,
+                    &tip.anchored_block_hash,
+                )
+                .unwrap()
+                .unwrap();
+                *expected_block.borrow_mut() = Some(block);
+                convo_client.new_getblock_by_height(tip.height, true)
+            },
+            |ref http_request,
+             ref http_response,
+             ref mut peer_client,
+             ref mut peer_server,
+             ref convo_client,
+             ref convo_server| {
+                let req_md = http_request.metadata().clone();
+                match http_response {
+                    HttpResponseType::BlockWithMicroblocks(response_md, block, microblocks) => {
+                        assert_eq!(Some(block), expected_block.borrow().as_ref());
+                        assert_eq!(microblocks.len(), 0);
+                        true
+                    }
+                    _ => {
+                        error!("Invalid response: {:?}", &http_response);
+                        false
+                    }
+                }
+            },
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn test_rpc_get_block_by_height_beyond_tip() {
+        test_rpc(
+            function_name!(),
+            40204,
+            40205,
+            50204,
+            50205,
+            false,
+            |ref mut peer_client,
+             ref mut convo_client,
+             ref mut peer_server,
+             ref mut convo_server| {
+                let sortdb = peer_server.sortdb.as_ref().unwrap();
+                let chainstate = &mut peer_server.stacks_node.as_mut().unwrap().chainstate;
+                let tip = chainstate.get_stacks_chain_tip(sortdb).unwrap().unwrap();
+                convo_client.new_getblock_by_height(tip.height + 1, false)
+            },
+            |ref http_request,
+             ref http_response,
+             ref mut peer_client,
+             ref mut peer_server,
+             ref convo_client,
+             ref convo_server| {
+                let req_md = http_request.metadata().clone();
+                match http_response {
+                    HttpResponseType::NotFound(response_md, msg) => true,
+                    _ => {
+                        error!("Invalid response: {:?}", &http_response);
+                        false
+                    }
+                }
+            },
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn test_rpc_get_indexed_microblocks() {
+        let server_microblocks_cell = RefCell::new(vec![]);
+
+        test_rpc(
+            function_name!(),
+            40040,
+            40041,
+            50040,
+            50041,
+            true,
+            |ref mut peer_client,
+             ref mut convo_client,
+             ref mut peer_server,
+             ref mut convo_server| {
+                let privk = StacksPrivateKey::from_hex(
+                    "eb05c83546fdd2c79f10f5ad5434a90dd28f7e3acb7c092157aa1bc3656b012c01",
+                )
+                .unwrap();
+
+                let parent_block = make_codec_test_block(25);
+                let parent_consensus_hash = ConsensusHash([0x02; 20]);
+                let parent_index_block_hash = StacksBlockHeader::make_index_block_hash(
+                    &parent_consensus_hash,
+                    &parent_block.block_hash(),
+                );
+
+                let mut mblocks = make_sample_microblock_stream(&privk, &parent_block.block_hash());
+                mblocks.truncate(15);
+
+                let mut child_block = make_codec_test_block(25);
+                let child_consensus_hash = ConsensusHash([0x03; 20]);
 
                 child_block.header.parent_block = parent_block.block_hash();
                 child_block.header.parent_microblock =
@@ -4986,7 +7358,7 @@ mod test {
                 *server_microblocks_cell.borrow_mut() = mblocks;
 
                 // start at seq 5
-                convo_client.new_getmicroblocks_unconfirmed(index_block_hash, 5)
+                convo_client.new_getmicroblocks_unconfirmed(index_block_hash, 5, None)
             },
             |ref http_request,
              ref http_response,
@@ -5013,6 +7385,74 @@ mod test {
         );
     }
 
+    #[test]
+    #[ignore]
+    fn test_rpc_unconfirmed_microblocks_limit() {
+        let server_microblocks_cell = RefCell::new(vec![]);
+
+        test_rpc(
+            function_name!(),
+            40192,
+            40193,
+            50192,
+            50193,
+            true,
+            |ref mut peer_client,
+             ref mut convo_client,
+             ref mut peer_server,
+             ref mut convo_server| {
+                let privk = StacksPrivateKey::from_hex(
+                    "eb05c83546fdd2c79f10f5ad5434a90dd28f7e3acb7c092157aa1bc3656b012c01",
+                )
+                .unwrap();
+
+                let consensus_hash = ConsensusHash([0x02; 20]);
+                let anchored_block_hash = BlockHeaderHash([0x03; 32]);
+                let index_block_hash =
+                    StacksBlockHeader::make_index_block_hash(&consensus_hash, &anchored_block_hash);
+
+                let mut mblocks = make_sample_microblock_stream(&privk, &anchored_block_hash);
+                mblocks.truncate(15);
+
+                for mblock in mblocks.iter() {
+                    store_staging_microblock(
+                        peer_server.chainstate(),
+                        &consensus_hash,
+                        &anchored_block_hash,
+                        &mblock,
+                    );
+                }
+
+                *server_microblocks_cell.borrow_mut() = mblocks;
+
+                // page of 4, starting at seq 5
+                convo_client.new_getmicroblocks_unconfirmed(index_block_hash, 5, Some(4))
+            },
+            |ref http_request,
+             ref http_response,
+             ref mut peer_client,
+             ref mut peer_server,
+             ref convo_client,
+             ref convo_server| {
+                let req_md = http_request.metadata().clone();
+                match http_response {
+                    HttpResponseType::Microblocks(response_md, microblocks) => {
+                        assert_eq!(microblocks.len(), 4);
+                        assert_eq!(
+                            *microblocks,
+                            (*server_microblocks_cell.borrow())[5..9].to_vec()
+                        );
+                        true
+                    }
+                    _ => {
+                        error!("Invalid response: {:?}", &http_response);
+                        false
+                    }
+                }
+            },
+        );
+    }
+
     #[test]
     #[ignore]
     fn test_rpc_unconfirmed_transaction() {
@@ -5070,23 +7510,503 @@ mod test {
              ref convo_server| {
                 let req_md = http_request.metadata().clone();
                 match http_response {
-                    HttpResponseType::UnconfirmedTransaction(response_md, unconfirmed_resp) => {
-                        assert_eq!(
-                            unconfirmed_resp.status,
-                            UnconfirmedTransactionStatus::Microblock {
-                                block_hash: (*last_mblock.borrow()).clone(),
-                                seq: 0
-                            }
-                        );
-                        let tx = StacksTransaction::consensus_deserialize(
-                            &mut &hex_bytes(&unconfirmed_resp.tx).unwrap()[..],
-                        )
-                        .unwrap();
-                        assert_eq!(tx.txid(), *last_txid.borrow());
+                    HttpResponseType::UnconfirmedTransaction(response_md, unconfirmed_resp) => {
+                        assert_eq!(
+                            unconfirmed_resp.status,
+                            UnconfirmedTransactionStatus::Microblock {
+                                block_hash: (*last_mblock.borrow()).clone(),
+                                seq: 0
+                            }
+                        );
+                        let tx = StacksTransaction::consensus_deserialize(
+                            &mut &hex_bytes(&unconfirmed_resp.tx).unwrap()[..],
+                        )
+                        .unwrap();
+                        assert_eq!(tx.txid(), *last_txid.borrow());
+                        assert_eq!(unconfirmed_resp.fee, tx.get_tx_fee());
+                        assert_eq!(unconfirmed_resp.nonce, tx.get_origin_nonce());
+                        true
+                    }
+                    _ => {
+                        error!("Invalid response: {:?}", &http_response);
+                        false
+                    }
+                }
+            },
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn test_rpc_missing_getblock() {
+        test_rpc(
+            function_name!(),
+            40060,
+            40061,
+            50060,
+            50061,
+            true,
+            |ref mut peer_client,
+             ref mut convo_client,
+             ref mut peer_server,
+             ref mut convo_server| {
+                let peer_server_block_hash = BlockHeaderHash([0x04; 32]);
+                let peer_server_consensus_hash = ConsensusHash([0x02; 20]);
+                let index_block_hash = StacksBlockHeader::make_index_block_hash(
+                    &peer_server_consensus_hash,
+                    &peer_server_block_hash,
+                );
+
+                // now ask for it
+                convo_client.new_getblock(index_block_hash)
+            },
+            |ref http_request,
+             ref http_response,
+             ref mut peer_client,
+             ref mut peer_server,
+             ref convo_client,
+             ref convo_server| {
+                let req_md = http_request.metadata().clone();
+                match http_response {
+                    HttpResponseType::NotFound(response_md, msg) => true,
+                    _ => {
+                        error!("Invalid response: {:?}", &http_response);
+                        false
+                    }
+                }
+            },
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn test_rpc_missing_index_getmicroblocks() {
+        test_rpc(
+            function_name!(),
+            40070,
+            40071,
+            50070,
+            50071,
+            true,
+            |ref mut peer_client,
+             ref mut convo_client,
+             ref mut peer_server,
+             ref mut convo_server| {
+                let peer_server_block_hash = BlockHeaderHash([0x04; 32]);
+                let peer_server_consensus_hash = ConsensusHash([0x02; 20]);
+                let index_block_hash = StacksBlockHeader::make_index_block_hash(
+                    &peer_server_consensus_hash,
+                    &peer_server_block_hash,
+                );
+
+                // now ask for it
+                convo_client.new_getmicroblocks_indexed(index_block_hash)
+            },
+            |ref http_request,
+             ref http_response,
+             ref mut peer_client,
+             ref mut peer_server,
+             ref convo_client,
+             ref convo_server| {
+                let req_md = http_request.metadata().clone();
+                match http_response {
+                    HttpResponseType::NotFound(response_md, msg) => true,
+                    _ => {
+                        error!("Invalid response: {:?}", &http_response);
+                        false
+                    }
+                }
+            },
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn test_rpc_missing_confirmed_getmicroblocks() {
+        test_rpc(
+            function_name!(),
+            40072,
+            40073,
+            50072,
+            50073,
+            true,
+            |ref mut peer_client,
+             ref mut convo_client,
+             ref mut peer_server,
+             ref mut convo_server| {
+                let peer_server_block_hash = BlockHeaderHash([0x04; 32]);
+                let peer_server_consensus_hash = ConsensusHash([0x02; 20]);
+                let index_block_hash = StacksBlockHeader::make_index_block_hash(
+                    &peer_server_consensus_hash,
+                    &peer_server_block_hash,
+                );
+
+                // now ask for it
+                convo_client.new_getmicroblocks_confirmed(index_block_hash)
+            },
+            |ref http_request,
+             ref http_response,
+             ref mut peer_client,
+             ref mut peer_server,
+             ref convo_client,
+             ref convo_server| {
+                let req_md = http_request.metadata().clone();
+                match http_response {
+                    HttpResponseType::NotFound(response_md, msg) => true,
+                    _ => {
+                        error!("Invalid response: {:?}", &http_response);
+                        false
+                    }
+                }
+            },
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn test_rpc_missing_unconfirmed_microblocks() {
+        let server_microblocks_cell = RefCell::new(vec![]);
+
+        test_rpc(
+            function_name!(),
+            40080,
+            40081,
+            50080,
+            50081,
+            true,
+            |ref mut peer_client,
+             ref mut convo_client,
+             ref mut peer_server,
+             ref mut convo_server| {
+                let privk = StacksPrivateKey::from_hex(
+                    "eb05c83546fdd2c79f10f5ad5434a90dd28f7e3acb7c092157aa1bc3656b012c01",
+                )
+                .unwrap();
+
+                let consensus_hash = ConsensusHash([0x02; 20]);
+                let anchored_block_hash = BlockHeaderHash([0x03; 32]);
+                let index_block_hash =
+                    StacksBlockHeader::make_index_block_hash(&consensus_hash, &anchored_block_hash);
+
+                let mut mblocks = make_sample_microblock_stream(&privk, &anchored_block_hash);
+                mblocks.truncate(15);
+
+                for mblock in mblocks.iter() {
+                    store_staging_microblock(
+                        peer_server.chainstate(),
+                        &consensus_hash,
+                        &anchored_block_hash,
+                        &mblock,
+                    );
+                }
+
+                *server_microblocks_cell.borrow_mut() = mblocks;
+
+                // start at seq 16 (which doesn't exist)
+                convo_client.new_getmicroblocks_unconfirmed(index_block_hash, 16, None)
+            },
+            |ref http_request,
+             ref http_response,
+             ref mut peer_client,
+             ref mut peer_server,
+             ref convo_client,
+             ref convo_server| {
+                let req_md = http_request.metadata().clone();
+                match http_response {
+                    HttpResponseType::NotFound(response_md, msg) => true,
+                    _ => {
+                        error!("Invalid response: {:?}", &http_response);
+                        false
+                    }
+                }
+            },
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn test_rpc_get_contract_src() {
+        // Test v2/contracts/source (aka GetContractSrc) endpoint.
+        // In this test, we don't set any tip parameters, and allow the endpoint to execute against
+        // the canonical Stacks tip.
+        // The contract source we are querying for exists in the anchored state, so we expect the
+        // query to succeed.
+        test_rpc(
+            function_name!(),
+            40090,
+            40091,
+            50090,
+            50091,
+            true,
+            |ref mut peer_client,
+             ref mut convo_client,
+             ref mut peer_server,
+             ref mut convo_server| {
+                convo_client.new_getcontractsrc(
+                    StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
+                        .unwrap(),
+                    "hello-world".try_into().unwrap(),
+                    TipRequest::UseLatestAnchoredTip,
+                    false,
+                )
+            },
+            |ref http_request,
+             ref http_response,
+             ref mut peer_client,
+             ref mut peer_server,
+             ref convo_client,
+             ref convo_server| {
+                let req_md = http_request.metadata().clone();
+                match http_response {
+                    HttpResponseType::GetContractSrc(response_md, data) => {
+                        assert_eq!(data.source, TEST_CONTRACT);
+                        assert!(data.clarity_version.is_some());
+                        true
+                    }
+                    _ => {
+                        error!("Invalid response; {:?}", &http_response);
+                        false
+                    }
+                }
+            },
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn test_rpc_get_contract_src_unconfirmed_with_canonical_tip() {
+        // Test v2/contracts/source (aka GetContractSrc) endpoint.
+        // In this test, we don't set any tip parameters, and allow the endpoint to execute against
+        // the canonical Stacks tip.
+        // The contract source we are querying for only exists in the unconfirmed state, so we
+        // expect the query to fail.
+        test_rpc(
+            function_name!(),
+            40100,
+            40101,
+            50100,
+            50101,
+            true,
+            |ref mut peer_client,
+             ref mut convo_client,
+             ref mut peer_server,
+             ref mut convo_server| {
+                convo_client.new_getcontractsrc(
+                    StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
+                        .unwrap(),
+                    "hello-world-unconfirmed".try_into().unwrap(),
+                    TipRequest::UseLatestAnchoredTip,
+                    false,
+                )
+            },
+            |ref http_request,
+             ref http_response,
+             ref mut peer_client,
+             ref mut peer_server,
+             ref convo_client,
+             ref convo_server| {
+                let req_md = http_request.metadata().clone();
+                match http_response {
+                    HttpResponseType::NotFound(_, error_str) => {
+                        assert_eq!(error_str, "No contract source data found");
+                        true
+                    }
+                    _ => {
+                        error!("Invalid response; {:?}", &http_response);
+                        false
+                    }
+                }
+            },
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn test_rpc_get_contract_src_with_unconfirmed_tip() {
+        // Test v2/contracts/source (aka GetContractSrc) endpoint.
+        // In this test, we set `tip_req` to be the unconfirmed chain tip.
+        // The contract source we are querying for exists in the unconfirmed state, so we expect
+        // the query to succeed.
+        test_rpc(
+            function_name!(),
+            40102,
+            40103,
+            50102,
+            50103,
+            true,
+            |ref mut peer_client,
+             ref mut convo_client,
+             ref mut peer_server,
+             ref mut convo_server| {
+                let unconfirmed_tip = peer_client
+                    .chainstate()
+                    .unconfirmed_state
+                    .as_ref()
+                    .unwrap()
+                    .unconfirmed_chain_tip
+                    .clone();
+                convo_client.new_getcontractsrc(
+                    StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
+                        .unwrap(),
+                    "hello-world-unconfirmed".try_into().unwrap(),
+                    TipRequest::SpecificTip(unconfirmed_tip),
+                    false,
+                )
+            },
+            |ref http_request,
+             ref http_response,
+             ref mut peer_client,
+             ref mut peer_server,
+             ref convo_client,
+             ref convo_server| {
+                let req_md = http_request.metadata().clone();
+                match http_response {
+                    HttpResponseType::GetContractSrc(response_md, data) => {
+                        assert_eq!(data.source, TEST_CONTRACT_UNCONFIRMED);
+                        true
+                    }
+                    _ => {
+                        error!("Invalid response; {:?}", &http_response);
+                        false
+                    }
+                }
+            },
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn test_rpc_get_contract_src_use_latest_tip() {
+        // Test v2/contracts/source (aka GetContractSrc) endpoint.
+        // In this test, we set `tip_req` to UseLatestUnconfirmedTip.
+        // The contract source we are querying for exists in the unconfirmed state, so we expect
+        // the query to succeed.
+        test_rpc(
+            function_name!(),
+            40104,
+            40105,
+            50104,
+            50105,
+            true,
+            |ref mut peer_client,
+             ref mut convo_client,
+             ref mut peer_server,
+             ref mut convo_server| {
+                convo_client.new_getcontractsrc(
+                    StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
+                        .unwrap(),
+                    "hello-world-unconfirmed".try_into().unwrap(),
+                    TipRequest::UseLatestAnchoredTip,
+                    false,
+                )
+            },
+            |ref http_request,
+             ref http_response,
+             ref mut peer_client,
+             ref mut peer_server,
+             ref convo_client,
+             ref convo_server| {
+                let req_md = http_request.metadata().clone();
+                match http_response {
+                    HttpResponseType::GetContractSrc(response_md, data) => {
+                        assert_eq!(data.source, TEST_CONTRACT_UNCONFIRMED);
+                        true
+                    }
+                    _ => {
+                        error!("Invalid response; {:?}", &http_response);
+                        false
+                    }
+                }
+            },
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn test_rpc_get_account() {
+        test_rpc(
+            function_name!(),
+            40110,
+            40111,
+            50110,
+            50111,
+            true,
+            |ref mut peer_client,
+             ref mut convo_client,
+             ref mut peer_server,
+             ref mut convo_server| {
+                convo_client.new_getaccount(
+                    StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
+                        .unwrap()
+                        .to_account_principal(),
+                    TipRequest::UseLatestAnchoredTip,
+                    false,
+                )
+            },
+            |ref http_request,
+             ref http_response,
+             ref mut peer_client,
+             ref mut peer_server,
+             ref convo_client,
+             ref convo_server| {
+                let req_md = http_request.metadata().clone();
+                match http_response {
+                    HttpResponseType::GetAccount(response_md, data) => {
+                        assert_eq!(data.nonce, 2);
+                        let balance = u128::from_str_radix(&data.balance[2..], 16).unwrap();
+                        assert_eq!(balance, 1000000000);
+                        true
+                    }
+                    _ => {
+                        error!("Invalid response; {:?}", &http_response);
+                        false
+                    }
+                }
+            },
+        );
+    }
+
+    /// In this test, the query parameter `tip_req` is set to UseLatestUnconfirmedTip, and so we expect the
+    /// tip used for the query to be the latest microblock.
+    /// We check that the account state matches the state in the most recent microblock.
+    #[test]
+    #[ignore]
+    fn test_rpc_get_account_use_latest_tip() {
+        test_rpc(
+            function_name!(),
+            40112,
+            40113,
+            50112,
+            50113,
+            true,
+            |ref mut peer_client,
+             ref mut convo_client,
+             ref mut peer_server,
+             ref mut convo_server| {
+                convo_client.new_getaccount(
+                    StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
+                        .unwrap()
+                        .to_account_principal(),
+                    TipRequest::UseLatestAnchoredTip,
+                    false,
+                )
+            },
+            |ref http_request,
+             ref http_response,
+             ref mut peer_client,
+             ref mut peer_server,
+             ref convo_client,
+             ref convo_server| {
+                let req_md = http_request.metadata().clone();
+                match http_response {
+                    HttpResponseType::GetAccount(response_md, data) => {
+                        assert_eq!(data.nonce, 4);
+                        let balance = u128::from_str_radix(&data.balance[2..], 16).unwrap();
+                        assert_eq!(balance, 999999877);
                         true
                     }
                     _ => {
-                        error!("Invalid response: {:?}", &http_response);
+                        error!("Invalid response; {:?}", &http_response);
                         false
                     }
                 }
@@ -5094,29 +8014,31 @@ mod test {
         );
     }
 
+    /// In this test, the query parameter `tip_req` is set to UseLatestUnconfirmedTip, but we did not generate
+    /// microblocks in the rpc test. Thus, we expect the tip used for the query to be the previous
+    /// anchor block (which is the latest tip).
+    /// We check that the account state matches the state in the previous anchor block.
     #[test]
     #[ignore]
-    fn test_rpc_missing_getblock() {
+    fn test_rpc_get_account_use_latest_tip_no_microblocks() {
         test_rpc(
             function_name!(),
-            40060,
-            40061,
-            50060,
-            50061,
-            true,
+            40114,
+            40115,
+            50114,
+            50115,
+            false,
             |ref mut peer_client,
              ref mut convo_client,
              ref mut peer_server,
              ref mut convo_server| {
-                let peer_server_block_hash = BlockHeaderHash([0x04; 32]);
-                let peer_server_consensus_hash = ConsensusHash([0x02; 20]);
-                let index_block_hash = StacksBlockHeader::make_index_block_hash(
-                    &peer_server_consensus_hash,
-                    &peer_server_block_hash,
-                );
-
-                // now ask for it
-                convo_client.new_getblock(index_block_hash)
+                convo_client.new_getaccount(
+                    StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
+                        .unwrap()
+                        .to_account_principal(),
+                    TipRequest::UseLatestAnchoredTip,
+                    false,
+                )
             },
             |ref http_request,
              ref http_response,
@@ -5126,9 +8048,14 @@ mod test {
              ref convo_server| {
                 let req_md = http_request.metadata().clone();
                 match http_response {
-                    HttpResponseType::NotFound(response_md, msg) => true,
+                    HttpResponseType::GetAccount(response_md, data) => {
+                        assert_eq!(data.nonce, 2);
+                        let balance = u128::from_str_radix(&data.balance[2..], 16).unwrap();
+                        assert_eq!(balance, 1000000000);
+                        true
+                    }
                     _ => {
-                        error!("Invalid response: {:?}", &http_response);
+                        error!("Invalid response; {:?}", &http_response);
                         false
                     }
                 }
@@ -5138,27 +8065,32 @@ mod test {
 
     #[test]
     #[ignore]
-    fn test_rpc_missing_index_getmicroblocks() {
+    fn test_rpc_get_account_unconfirmed() {
         test_rpc(
             function_name!(),
-            40070,
-            40071,
-            50070,
-            50071,
+            40120,
+            40121,
+            50120,
+            50121,
             true,
             |ref mut peer_client,
              ref mut convo_client,
              ref mut peer_server,
              ref mut convo_server| {
-                let peer_server_block_hash = BlockHeaderHash([0x04; 32]);
-                let peer_server_consensus_hash = ConsensusHash([0x02; 20]);
-                let index_block_hash = StacksBlockHeader::make_index_block_hash(
-                    &peer_server_consensus_hash,
-                    &peer_server_block_hash,
-                );
-
-                // now ask for it
-                convo_client.new_getmicroblocks_indexed(index_block_hash)
+                let unconfirmed_tip = peer_client
+                    .chainstate()
+                    .unconfirmed_state
+                    .as_ref()
+                    .unwrap()
+                    .unconfirmed_chain_tip
+                    .clone();
+                convo_client.new_getaccount(
+                    StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
+                        .unwrap()
+                        .to_account_principal(),
+                    TipRequest::SpecificTip(unconfirmed_tip),
+                    false,
+                )
             },
             |ref http_request,
              ref http_response,
@@ -5168,9 +8100,14 @@ mod test {
              ref convo_server| {
                 let req_md = http_request.metadata().clone();
                 match http_response {
-                    HttpResponseType::NotFound(response_md, msg) => true,
+                    HttpResponseType::GetAccount(response_md, data) => {
+                        assert_eq!(data.nonce, 4);
+                        let balance = u128::from_str_radix(&data.balance[2..], 16).unwrap();
+                        assert_eq!(balance, 1000000000 - 123);
+                        true
+                    }
                     _ => {
-                        error!("Invalid response: {:?}", &http_response);
+                        error!("Invalid response; {:?}", &http_response);
                         false
                     }
                 }
@@ -5178,29 +8115,28 @@ mod test {
         );
     }
 
+    /// Query an account as of the boot block, before the test block spent from it.
     #[test]
     #[ignore]
-    fn test_rpc_missing_confirmed_getmicroblocks() {
+    fn test_rpc_get_account_until_block() {
         test_rpc(
             function_name!(),
-            40072,
-            40073,
-            50072,
-            50073,
+            40122,
+            40123,
+            50122,
+            50123,
             true,
             |ref mut peer_client,
              ref mut convo_client,
              ref mut peer_server,
              ref mut convo_server| {
-                let peer_server_block_hash = BlockHeaderHash([0x04; 32]);
-                let peer_server_consensus_hash = ConsensusHash([0x02; 20]);
-                let index_block_hash = StacksBlockHeader::make_index_block_hash(
-                    &peer_server_consensus_hash,
-                    &peer_server_block_hash,
-                );
-
-                // now ask for it
-                convo_client.new_getmicroblocks_confirmed(index_block_hash)
+                convo_client.new_getaccount(
+                    StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
+                        .unwrap()
+                        .to_account_principal(),
+                    TipRequest::CanonicalAncestorAtHeight(0),
+                    true,
+                )
             },
             |ref http_request,
              ref http_response,
@@ -5210,9 +8146,16 @@ mod test {
              ref convo_server| {
                 let req_md = http_request.metadata().clone();
                 match http_response {
-                    HttpResponseType::NotFound(response_md, msg) => true,
+                    HttpResponseType::GetAccount(response_md, data) => {
+                        assert_eq!(data.nonce, 0);
+                        let balance = u128::from_str_radix(&data.balance[2..], 16).unwrap();
+                        assert_eq!(balance, 1000000000);
+                        assert!(data.balance_proof.is_some());
+                        assert!(data.nonce_proof.is_some());
+                        true
+                    }
                     _ => {
-                        error!("Invalid response: {:?}", &http_response);
+                        error!("Invalid response; {:?}", &http_response);
                         false
                     }
                 }
@@ -5222,46 +8165,25 @@ mod test {
 
     #[test]
     #[ignore]
-    fn test_rpc_missing_unconfirmed_microblocks() {
-        let server_microblocks_cell = RefCell::new(vec![]);
-
+    fn test_rpc_get_account_until_unknown_block() {
         test_rpc(
             function_name!(),
-            40080,
-            40081,
-            50080,
-            50081,
+            40124,
+            40125,
+            50124,
+            50125,
             true,
             |ref mut peer_client,
              ref mut convo_client,
              ref mut peer_server,
              ref mut convo_server| {
-                let privk = StacksPrivateKey::from_hex(
-                    "eb05c83546fdd2c79f10f5ad5434a90dd28f7e3acb7c092157aa1bc3656b012c01",
+                convo_client.new_getaccount(
+                    StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
+                        .unwrap()
+                        .to_account_principal(),
+                    TipRequest::CanonicalAncestor(StacksBlockId([0x11; 32])),
+                    false,
                 )
-                .unwrap();
-
-                let consensus_hash = ConsensusHash([0x02; 20]);
-                let anchored_block_hash = BlockHeaderHash([0x03; 32]);
-                let index_block_hash =
-                    StacksBlockHeader::make_index_block_hash(&consensus_hash, &anchored_block_hash);
-
-                let mut mblocks = make_sample_microblock_stream(&privk, &anchored_block_hash);
-                mblocks.truncate(15);
-
-                for mblock in mblocks.iter() {
-                    store_staging_microblock(
-                        peer_server.chainstate(),
-                        &consensus_hash,
-                        &anchored_block_hash,
-                        &mblock,
-                    );
-                }
-
-                *server_microblocks_cell.borrow_mut() = mblocks;
-
-                // start at seq 16 (which doesn't exist)
-                convo_client.new_getmicroblocks_unconfirmed(index_block_hash, 16)
             },
             |ref http_request,
              ref http_response,
@@ -5271,9 +8193,12 @@ mod test {
              ref convo_server| {
                 let req_md = http_request.metadata().clone();
                 match http_response {
-                    HttpResponseType::NotFound(response_md, msg) => true,
+                    HttpResponseType::NotFound(_, msg) => {
+                        assert!(msg.contains("Unknown Stacks block"));
+                        true
+                    }
                     _ => {
-                        error!("Invalid response: {:?}", &http_response);
+                        error!("Invalid response; {:?}", &http_response);
                         false
                     }
                 }
@@ -5283,29 +8208,23 @@ mod test {
 
     #[test]
     #[ignore]
-    fn test_rpc_get_contract_src() {
-        // Test v2/contracts/source (aka GetContractSrc) endpoint.
-        // In this test, we don't set any tip parameters, and allow the endpoint to execute against
-        // the canonical Stacks tip.
-        // The contract source we are querying for exists in the anchored state, so we expect the
-        // query to succeed.
+    fn test_rpc_get_account_stacking_never_stacked() {
         test_rpc(
             function_name!(),
-            40090,
-            40091,
-            50090,
-            50091,
+            40821,
+            40822,
+            50821,
+            50822,
             true,
             |ref mut peer_client,
              ref mut convo_client,
              ref mut peer_server,
              ref mut convo_server| {
-                convo_client.new_getcontractsrc(
+                convo_client.new_getaccountstacking(
                     StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
-                        .unwrap(),
-                    "hello-world".try_into().unwrap(),
+                        .unwrap()
+                        .to_account_principal(),
                     TipRequest::UseLatestAnchoredTip,
-                    false,
                 )
             },
             |ref http_request,
@@ -5316,8 +8235,8 @@ mod test {
              ref convo_server| {
                 let req_md = http_request.metadata().clone();
                 match http_response {
-                    HttpResponseType::GetContractSrc(response_md, data) => {
-                        assert_eq!(data.source, TEST_CONTRACT);
+                    HttpResponseType::NotFound(_, msg) => {
+                        assert!(msg.contains("has never stacked"));
                         true
                     }
                     _ => {
@@ -5329,29 +8248,145 @@ mod test {
         );
     }
 
+    /// Append a header-only block to `parent` in the chainstate's index.  `salt` distinguishes
+    /// siblings, so that appending twice to the same parent makes a fork.
+    fn append_test_header(
+        chainstate: &mut StacksChainState,
+        parent: &StacksHeaderInfo,
+        salt: u8,
+    ) -> StacksHeaderInfo {
+        let mut new_tip = parent.clone();
+        new_tip.anchored_header.parent_block = parent.anchored_header.block_hash();
+        new_tip.anchored_header.tx_merkle_root = Sha512Trunc256Sum([salt; 32]);
+        new_tip.anchored_header.total_work.work = parent.anchored_header.total_work.work + 1;
+        new_tip.microblock_tail = None;
+        new_tip.stacks_block_height = parent.stacks_block_height + 1;
+        new_tip.consensus_hash =
+            ConsensusHash(Hash160::from_data(&[&parent.consensus_hash.0[..], &[salt]].concat()).0);
+        new_tip.burn_header_hash =
+            BurnchainHeaderHash(Sha512Trunc256Sum::from_data(&new_tip.consensus_hash.0).0);
+        new_tip.burn_header_height = parent.burn_header_height + 1;
+
+        let mut block_reward = MinerPaymentSchedule::genesis(false);
+        block_reward.parent_consensus_hash = parent.consensus_hash.clone();
+        block_reward.parent_block_hash = parent.anchored_header.block_hash();
+        block_reward.consensus_hash = new_tip.consensus_hash.clone();
+        block_reward.block_hash = new_tip.anchored_header.block_hash();
+
+        let mut tx = chainstate.index_tx_begin().unwrap();
+        let tip = StacksChainState::advance_tip(
+            &mut tx,
+            &parent.anchored_header,
+            &parent.consensus_hash,
+            &new_tip.anchored_header,
+            &new_tip.consensus_hash,
+            &new_tip.burn_header_hash,
+            new_tip.burn_header_height,
+            new_tip.burn_header_timestamp,
+            None,
+            &block_reward,
+            &[],
+            None,
+            &ExecutionCost::zero(),
+            123,
+            false,
+            vec![],
+            vec![],
+            vec![],
+            parent.anchored_header.total_work.work + 1,
+        )
+        .unwrap();
+        tx.commit().unwrap();
+        tip
+    }
+
+    #[test]
+    fn test_rpc_resolve_canonical_ancestor() {
+        let mut chainstate = instantiate_chainstate(false, 0x80000000, function_name!());
+        let genesis = StacksHeaderInfo::regtest_genesis();
+
+        // canonical fork: genesis <- a_1 <- a_2
+        // orphaned fork:  genesis <- b_1
+        let a_1 = append_test_header(&mut chainstate, &genesis, 1);
+        let a_2 = append_test_header(&mut chainstate, &a_1, 1);
+        let b_1 = append_test_header(&mut chainstate, &genesis, 2);
+        assert_ne!(a_1.index_block_hash(), b_1.index_block_hash());
+
+        let resolve = |tip_req: TipRequest, tip: &StacksHeaderInfo| {
+            ConversationHttp::resolve_canonical_ancestor(
+                &chainstate,
+                &tip_req,
+                &tip.index_block_hash(),
+            )
+            .unwrap()
+        };
+
+        // by height
+        assert_eq!(
+            resolve(TipRequest::CanonicalAncestorAtHeight(0), &a_2),
+            Ok(genesis.index_block_hash())
+        );
+        assert_eq!(
+            resolve(TipRequest::CanonicalAncestorAtHeight(1), &a_2),
+            Ok(a_1.index_block_hash())
+        );
+        assert_eq!(
+            resolve(TipRequest::CanonicalAncestorAtHeight(2), &a_2),
+            Ok(a_2.index_block_hash())
+        );
+        assert!(resolve(TipRequest::CanonicalAncestorAtHeight(3), &a_2)
+            .unwrap_err()
+            .contains("No block at height 3"));
+
+        // by index block hash
+        assert_eq!(
+            resolve(TipRequest::CanonicalAncestor(a_1.index_block_hash()), &a_2),
+            Ok(a_1.index_block_hash())
+        );
+        assert_eq!(
+            resolve(TipRequest::CanonicalAncestor(a_2.index_block_hash()), &a_2),
+            Ok(a_2.index_block_hash())
+        );
+        assert!(
+            resolve(TipRequest::CanonicalAncestor(b_1.index_block_hash()), &a_2)
+                .unwrap_err()
+                .contains("is not on the canonical Stacks fork")
+        );
+        assert!(resolve(
+            TipRequest::CanonicalAncestor(StacksBlockId([0x11; 32])),
+            &a_2
+        )
+        .unwrap_err()
+        .contains("Unknown Stacks block"));
+
+        // if the other fork were canonical, the answers would flip
+        assert_eq!(
+            resolve(TipRequest::CanonicalAncestorAtHeight(1), &b_1),
+            Ok(b_1.index_block_hash())
+        );
+        assert!(resolve(TipRequest::CanonicalAncestorAtHeight(2), &b_1).is_err());
+        assert!(resolve(TipRequest::CanonicalAncestor(a_1.index_block_hash()), &b_1).is_err());
+    }
+
     #[test]
     #[ignore]
-    fn test_rpc_get_contract_src_unconfirmed_with_canonical_tip() {
-        // Test v2/contracts/source (aka GetContractSrc) endpoint.
-        // In this test, we don't set any tip parameters, and allow the endpoint to execute against
-        // the canonical Stacks tip.
-        // The contract source we are querying for only exists in the unconfirmed state, so we
-        // expect the query to fail.
+    fn test_rpc_get_data_var() {
         test_rpc(
             function_name!(),
-            40100,
-            40101,
-            50100,
-            50101,
+            40122,
+            40123,
+            50122,
+            50123,
             true,
             |ref mut peer_client,
              ref mut convo_client,
              ref mut peer_server,
              ref mut convo_server| {
-                convo_client.new_getcontractsrc(
+                convo_client.new_getdatavar(
                     StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
                         .unwrap(),
-                    "hello-world-unconfirmed".try_into().unwrap(),
+                    "hello-world".try_into().unwrap(),
+                    "bar".try_into().unwrap(),
                     TipRequest::UseLatestAnchoredTip,
                     false,
                 )
@@ -5364,8 +8399,11 @@ mod test {
              ref convo_server| {
                 let req_md = http_request.metadata().clone();
                 match http_response {
-                    HttpResponseType::NotFound(_, error_str) => {
-                        assert_eq!(error_str, "No contract source data found");
+                    HttpResponseType::GetDataVar(response_md, data) => {
+                        assert_eq!(
+                            Value::try_deserialize_hex_untyped(&data.data).unwrap(),
+                            Value::Int(0)
+                        );
                         true
                     }
                     _ => {
@@ -5379,17 +8417,13 @@ mod test {
 
     #[test]
     #[ignore]
-    fn test_rpc_get_contract_src_with_unconfirmed_tip() {
-        // Test v2/contracts/source (aka GetContractSrc) endpoint.
-        // In this test, we set `tip_req` to be the unconfirmed chain tip.
-        // The contract source we are querying for exists in the unconfirmed state, so we expect
-        // the query to succeed.
+    fn test_rpc_get_data_var_unconfirmed() {
         test_rpc(
             function_name!(),
-            40102,
-            40103,
-            50102,
-            50103,
+            40124,
+            40125,
+            50124,
+            50125,
             true,
             |ref mut peer_client,
              ref mut convo_client,
@@ -5402,10 +8436,11 @@ mod test {
                     .unwrap()
                     .unconfirmed_chain_tip
                     .clone();
-                convo_client.new_getcontractsrc(
+                convo_client.new_getdatavar(
                     StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
                         .unwrap(),
-                    "hello-world-unconfirmed".try_into().unwrap(),
+                    "hello-world".try_into().unwrap(),
+                    "bar".try_into().unwrap(),
                     TipRequest::SpecificTip(unconfirmed_tip),
                     false,
                 )
@@ -5418,8 +8453,11 @@ mod test {
              ref convo_server| {
                 let req_md = http_request.metadata().clone();
                 match http_response {
-                    HttpResponseType::GetContractSrc(response_md, data) => {
-                        assert_eq!(data.source, TEST_CONTRACT_UNCONFIRMED);
+                    HttpResponseType::GetDataVar(response_md, data) => {
+                        assert_eq!(
+                            Value::try_deserialize_hex_untyped(&data.data).unwrap(),
+                            Value::Int(1)
+                        );
                         true
                     }
                     _ => {
@@ -5433,26 +8471,23 @@ mod test {
 
     #[test]
     #[ignore]
-    fn test_rpc_get_contract_src_use_latest_tip() {
-        // Test v2/contracts/source (aka GetContractSrc) endpoint.
-        // In this test, we set `tip_req` to UseLatestUnconfirmedTip.
-        // The contract source we are querying for exists in the unconfirmed state, so we expect
-        // the query to succeed.
+    fn test_rpc_get_data_var_nonexistant() {
         test_rpc(
             function_name!(),
-            40104,
-            40105,
-            50104,
-            50105,
+            40125,
+            40126,
+            50125,
+            50126,
             true,
             |ref mut peer_client,
              ref mut convo_client,
              ref mut peer_server,
              ref mut convo_server| {
-                convo_client.new_getcontractsrc(
+                convo_client.new_getdatavar(
                     StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
                         .unwrap(),
-                    "hello-world-unconfirmed".try_into().unwrap(),
+                    "hello-world".try_into().unwrap(),
+                    "bar-nonexistant".try_into().unwrap(),
                     TipRequest::UseLatestAnchoredTip,
                     false,
                 )
@@ -5465,8 +8500,8 @@ mod test {
              ref convo_server| {
                 let req_md = http_request.metadata().clone();
                 match http_response {
-                    HttpResponseType::GetContractSrc(response_md, data) => {
-                        assert_eq!(data.source, TEST_CONTRACT_UNCONFIRMED);
+                    HttpResponseType::NotFound(_, msg) => {
+                        assert_eq!(msg, "Data var not found");
                         true
                     }
                     _ => {
@@ -5480,22 +8515,34 @@ mod test {
 
     #[test]
     #[ignore]
-    fn test_rpc_get_account() {
+    fn test_rpc_get_map_entry() {
+        // Test v2/map_entry (aka GetMapEntry) endpoint.
+        // In this test, we don't set any tip parameters, and we expect that querying for map data
+        // against the canonical Stacks tip will succeed.
         test_rpc(
             function_name!(),
-            40110,
-            40111,
-            50110,
-            50111,
+            40130,
+            40131,
+            50130,
+            50131,
             true,
             |ref mut peer_client,
              ref mut convo_client,
              ref mut peer_server,
              ref mut convo_server| {
-                convo_client.new_getaccount(
+                let principal =
                     StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
                         .unwrap()
-                        .to_account_principal(),
+                        .to_account_principal();
+                convo_client.new_getmapentry(
+                    StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
+                        .unwrap(),
+                    "hello-world".try_into().unwrap(),
+                    "unit-map".try_into().unwrap(),
+                    Value::Tuple(
+                        TupleData::from_data(vec![("account".into(), Value::Principal(principal))])
+                            .unwrap(),
+                    ),
                     TipRequest::UseLatestAnchoredTip,
                     false,
                 )
@@ -5508,10 +8555,15 @@ mod test {
              ref convo_server| {
                 let req_md = http_request.metadata().clone();
                 match http_response {
-                    HttpResponseType::GetAccount(response_md, data) => {
-                        assert_eq!(data.nonce, 2);
-                        let balance = u128::from_str_radix(&data.balance[2..], 16).unwrap();
-                        assert_eq!(balance, 1000000000);
+                    HttpResponseType::GetMapEntry(response_md, data) => {
+                        assert_eq!(
+                            Value::try_deserialize_hex_untyped(&data.data).unwrap(),
+                            Value::some(Value::Tuple(
+                                TupleData::from_data(vec![("units".into(), Value::Int(123))])
+                                    .unwrap()
+                            ))
+                            .unwrap()
+                        );
                         true
                     }
                     _ => {
@@ -5523,28 +8575,44 @@ mod test {
         );
     }
 
-    /// In this test, the query parameter `tip_req` is set to UseLatestUnconfirmedTip, and so we expect the
-    /// tip used for the query to be the latest microblock.
-    /// We check that the account state matches the state in the most recent microblock.
     #[test]
     #[ignore]
-    fn test_rpc_get_account_use_latest_tip() {
+    fn test_rpc_get_map_entry_unconfirmed() {
+        // Test v2/map_entry (aka GetMapEntry) endpoint.
+        // In this test, we set `tip_req` to UseLatestUnconfirmedTip, and we expect that querying for map data
+        // against the unconfirmed state will succeed.
         test_rpc(
             function_name!(),
-            40112,
-            40113,
-            50112,
-            50113,
+            40140,
+            40141,
+            50140,
+            50141,
             true,
             |ref mut peer_client,
              ref mut convo_client,
              ref mut peer_server,
              ref mut convo_server| {
-                convo_client.new_getaccount(
+                let unconfirmed_tip = peer_client
+                    .chainstate()
+                    .unconfirmed_state
+                    .as_ref()
+                    .unwrap()
+                    .unconfirmed_chain_tip
+                    .clone();
+                let principal =
                     StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
                         .unwrap()
-                        .to_account_principal(),
-                    TipRequest::UseLatestAnchoredTip,
+                        .to_account_principal();
+                convo_client.new_getmapentry(
+                    StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
+                        .unwrap(),
+                    "hello-world".try_into().unwrap(),
+                    "unit-map".try_into().unwrap(),
+                    Value::Tuple(
+                        TupleData::from_data(vec![("account".into(), Value::Principal(principal))])
+                            .unwrap(),
+                    ),
+                    TipRequest::SpecificTip(unconfirmed_tip),
                     false,
                 )
             },
@@ -5556,10 +8624,15 @@ mod test {
              ref convo_server| {
                 let req_md = http_request.metadata().clone();
                 match http_response {
-                    HttpResponseType::GetAccount(response_md, data) => {
-                        assert_eq!(data.nonce, 4);
-                        let balance = u128::from_str_radix(&data.balance[2..], 16).unwrap();
-                        assert_eq!(balance, 999999877);
+                    HttpResponseType::GetMapEntry(response_md, data) => {
+                        assert_eq!(
+                            Value::try_deserialize_hex_untyped(&data.data).unwrap(),
+                            Value::some(Value::Tuple(
+                                TupleData::from_data(vec![("units".into(), Value::Int(1))])
+                                    .unwrap()
+                            ))
+                            .unwrap()
+                        );
                         true
                     }
                     _ => {
@@ -5571,28 +8644,33 @@ mod test {
         );
     }
 
-    /// In this test, the query parameter `tip_req` is set to UseLatestUnconfirmedTip, but we did not generate
-    /// microblocks in the rpc test. Thus, we expect the tip used for the query to be the previous
-    /// anchor block (which is the latest tip).
-    /// We check that the account state matches the state in the previous anchor block.
     #[test]
     #[ignore]
-    fn test_rpc_get_account_use_latest_tip_no_microblocks() {
+    fn test_rpc_get_map_entry_use_latest_tip() {
         test_rpc(
             function_name!(),
-            40114,
-            40115,
-            50114,
-            50115,
-            false,
+            40142,
+            40143,
+            50142,
+            50143,
+            true,
             |ref mut peer_client,
              ref mut convo_client,
              ref mut peer_server,
              ref mut convo_server| {
-                convo_client.new_getaccount(
+                let principal =
                     StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
                         .unwrap()
-                        .to_account_principal(),
+                        .to_account_principal();
+                convo_client.new_getmapentry(
+                    StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
+                        .unwrap(),
+                    "hello-world".try_into().unwrap(),
+                    "unit-map".try_into().unwrap(),
+                    Value::Tuple(
+                        TupleData::from_data(vec![("account".into(), Value::Principal(principal))])
+                            .unwrap(),
+                    ),
                     TipRequest::UseLatestAnchoredTip,
                     false,
                 )
@@ -5605,10 +8683,15 @@ mod test {
              ref convo_server| {
                 let req_md = http_request.metadata().clone();
                 match http_response {
-                    HttpResponseType::GetAccount(response_md, data) => {
-                        assert_eq!(data.nonce, 2);
-                        let balance = u128::from_str_radix(&data.balance[2..], 16).unwrap();
-                        assert_eq!(balance, 1000000000);
+                    HttpResponseType::GetMapEntry(response_md, data) => {
+                        assert_eq!(
+                            Value::try_deserialize_hex_untyped(&data.data).unwrap(),
+                            Value::some(Value::Tuple(
+                                TupleData::from_data(vec![("units".into(), Value::Int(1))])
+                                    .unwrap()
+                            ))
+                            .unwrap()
+                        );
                         true
                     }
                     _ => {
@@ -5622,31 +8705,26 @@ mod test {
 
     #[test]
     #[ignore]
-    fn test_rpc_get_account_unconfirmed() {
+    fn test_rpc_get_contract_abi() {
+        // Test /v2/contracts/interface (aka GetContractABI) endpoint.
+        // In this test, we don't set any tip parameters, and we expect that querying
+        // against the canonical Stacks tip will succeed.
         test_rpc(
             function_name!(),
-            40120,
-            40121,
-            50120,
-            50121,
+            40150,
+            40151,
+            50150,
+            50151,
             true,
             |ref mut peer_client,
              ref mut convo_client,
              ref mut peer_server,
              ref mut convo_server| {
-                let unconfirmed_tip = peer_client
-                    .chainstate()
-                    .unconfirmed_state
-                    .as_ref()
-                    .unwrap()
-                    .unconfirmed_chain_tip
-                    .clone();
-                convo_client.new_getaccount(
+                convo_client.new_getcontractabi(
                     StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
-                        .unwrap()
-                        .to_account_principal(),
-                    TipRequest::SpecificTip(unconfirmed_tip),
-                    false,
+                        .unwrap(),
+                    "hello-world-unconfirmed".try_into().unwrap(),
+                    TipRequest::UseLatestAnchoredTip,
                 )
             },
             |ref http_request,
@@ -5657,10 +8735,8 @@ mod test {
              ref convo_server| {
                 let req_md = http_request.metadata().clone();
                 match http_response {
-                    HttpResponseType::GetAccount(response_md, data) => {
-                        assert_eq!(data.nonce, 4);
-                        let balance = u128::from_str_radix(&data.balance[2..], 16).unwrap();
-                        assert_eq!(balance, 1000000000 - 123);
+                    HttpResponseType::NotFound(..) => {
+                        // not confirmed yet
                         true
                     }
                     _ => {
@@ -5674,25 +8750,33 @@ mod test {
 
     #[test]
     #[ignore]
-    fn test_rpc_get_data_var() {
+    fn test_rpc_get_contract_abi_unconfirmed() {
+        // Test /v2/contracts/interface (aka GetContractABI) endpoint.
+        // In this test, we set `tip_req` to UseLatestUnconfirmedTip, and we expect that querying
+        // against the unconfirmed state will succeed.
         test_rpc(
             function_name!(),
-            40122,
-            40123,
-            50122,
-            50123,
+            40152,
+            40153,
+            50152,
+            50153,
             true,
             |ref mut peer_client,
              ref mut convo_client,
              ref mut peer_server,
              ref mut convo_server| {
-                convo_client.new_getdatavar(
+                let unconfirmed_tip = peer_client
+                    .chainstate()
+                    .unconfirmed_state
+                    .as_ref()
+                    .unwrap()
+                    .unconfirmed_chain_tip
+                    .clone();
+                convo_client.new_getcontractabi(
                     StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
                         .unwrap(),
-                    "hello-world".try_into().unwrap(),
-                    "bar".try_into().unwrap(),
-                    TipRequest::UseLatestAnchoredTip,
-                    false,
+                    "hello-world-unconfirmed".try_into().unwrap(),
+                    TipRequest::SpecificTip(unconfirmed_tip),
                 )
             },
             |ref http_request,
@@ -5703,13 +8787,7 @@ mod test {
              ref convo_server| {
                 let req_md = http_request.metadata().clone();
                 match http_response {
-                    HttpResponseType::GetDataVar(response_md, data) => {
-                        assert_eq!(
-                            Value::try_deserialize_hex_untyped(&data.data).unwrap(),
-                            Value::Int(0)
-                        );
-                        true
-                    }
+                    HttpResponseType::GetContractABI(response_md, data) => true,
                     _ => {
                         error!("Invalid response; {:?}", &http_response);
                         false
@@ -5721,32 +8799,23 @@ mod test {
 
     #[test]
     #[ignore]
-    fn test_rpc_get_data_var_unconfirmed() {
+    fn test_rpc_get_contract_abi_use_latest_tip() {
         test_rpc(
             function_name!(),
-            40124,
-            40125,
-            50124,
-            50125,
+            40154,
+            40155,
+            50154,
+            50155,
             true,
             |ref mut peer_client,
              ref mut convo_client,
              ref mut peer_server,
              ref mut convo_server| {
-                let unconfirmed_tip = peer_client
-                    .chainstate()
-                    .unconfirmed_state
-                    .as_ref()
-                    .unwrap()
-                    .unconfirmed_chain_tip
-                    .clone();
-                convo_client.new_getdatavar(
+                convo_client.new_getcontractabi(
                     StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
                         .unwrap(),
-                    "hello-world".try_into().unwrap(),
-                    "bar".try_into().unwrap(),
-                    TipRequest::SpecificTip(unconfirmed_tip),
-                    false,
+                    "hello-world-unconfirmed".try_into().unwrap(),
+                    TipRequest::UseLatestAnchoredTip,
                 )
             },
             |ref http_request,
@@ -5757,13 +8826,7 @@ mod test {
              ref convo_server| {
                 let req_md = http_request.metadata().clone();
                 match http_response {
-                    HttpResponseType::GetDataVar(response_md, data) => {
-                        assert_eq!(
-                            Value::try_deserialize_hex_untyped(&data.data).unwrap(),
-                            Value::Int(1)
-                        );
-                        true
-                    }
+                    HttpResponseType::GetContractABI(response_md, data) => true,
                     _ => {
                         error!("Invalid response; {:?}", &http_response);
                         false
@@ -5775,25 +8838,32 @@ mod test {
 
     #[test]
     #[ignore]
-    fn test_rpc_get_data_var_nonexistant() {
+    fn test_rpc_call_read_only() {
+        // Test /v2/contracts/call-read (aka CallReadOnlyFunction) endpoint.
+        // In this test, we don't set any tip parameters, and we expect that querying
+        // against the canonical Stacks tip will succeed.
         test_rpc(
             function_name!(),
-            40125,
-            40126,
-            50125,
-            50126,
+            40170,
+            40171,
+            50170,
+            50171,
             true,
             |ref mut peer_client,
              ref mut convo_client,
              ref mut peer_server,
              ref mut convo_server| {
-                convo_client.new_getdatavar(
+                convo_client.new_callreadonlyfunction(
                     StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
                         .unwrap(),
-                    "hello-world".try_into().unwrap(),
-                    "bar-nonexistant".try_into().unwrap(),
+                    "hello-world-unconfirmed".try_into().unwrap(),
+                    StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
+                        .unwrap()
+                        .to_account_principal(),
+                    None,
+                    "ro-test".try_into().unwrap(),
+                    vec![],
                     TipRequest::UseLatestAnchoredTip,
-                    false,
                 )
             },
             |ref http_request,
@@ -5804,8 +8874,11 @@ mod test {
              ref convo_server| {
                 let req_md = http_request.metadata().clone();
                 match http_response {
-                    HttpResponseType::NotFound(_, msg) => {
-                        assert_eq!(msg, "Data var not found");
+                    HttpResponseType::CallReadOnlyFunction(response_md, data) => {
+                        assert!(data.cause.is_some());
+                        assert!(data.cause.clone().unwrap().find("NoSuchContract").is_some());
+                        assert!(!data.okay);
+                        assert!(data.result.is_none());
                         true
                     }
                     _ => {
@@ -5819,36 +8892,32 @@ mod test {
 
     #[test]
     #[ignore]
-    fn test_rpc_get_map_entry() {
-        // Test v2/map_entry (aka GetMapEntry) endpoint.
-        // In this test, we don't set any tip parameters, and we expect that querying for map data
-        // against the canonical Stacks tip will succeed.
+    fn test_rpc_call_read_only_use_latest_tip() {
+        // Test /v2/contracts/call-read (aka CallReadOnlyFunction) endpoint.
+        // In this test, we set `tip_req` to UseLatestUnconfirmedTip, and we expect that querying
+        // against the unconfirmed state will succeed.
         test_rpc(
             function_name!(),
-            40130,
-            40131,
-            50130,
-            50131,
+            40172,
+            40173,
+            50172,
+            50173,
             true,
             |ref mut peer_client,
              ref mut convo_client,
              ref mut peer_server,
              ref mut convo_server| {
-                let principal =
-                    StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
-                        .unwrap()
-                        .to_account_principal();
-                convo_client.new_getmapentry(
+                convo_client.new_callreadonlyfunction(
                     StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
                         .unwrap(),
-                    "hello-world".try_into().unwrap(),
-                    "unit-map".try_into().unwrap(),
-                    Value::Tuple(
-                        TupleData::from_data(vec![("account".into(), Value::Principal(principal))])
-                            .unwrap(),
-                    ),
+                    "hello-world-unconfirmed".try_into().unwrap(),
+                    StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
+                        .unwrap()
+                        .to_account_principal(),
+                    None,
+                    "ro-test".try_into().unwrap(),
+                    vec![],
                     TipRequest::UseLatestAnchoredTip,
-                    false,
                 )
             },
             |ref http_request,
@@ -5859,15 +8928,14 @@ mod test {
              ref convo_server| {
                 let req_md = http_request.metadata().clone();
                 match http_response {
-                    HttpResponseType::GetMapEntry(response_md, data) => {
+                    HttpResponseType::CallReadOnlyFunction(response_md, data) => {
+                        assert!(data.okay);
                         assert_eq!(
-                            Value::try_deserialize_hex_untyped(&data.data).unwrap(),
-                            Value::some(Value::Tuple(
-                                TupleData::from_data(vec![("units".into(), Value::Int(123))])
-                                    .unwrap()
-                            ))
-                            .unwrap()
+                            Value::try_deserialize_hex_untyped(&data.result.clone().unwrap())
+                                .unwrap(),
+                            Value::okay(Value::Int(1)).unwrap()
                         );
+                        assert!(data.cause.is_none());
                         true
                     }
                     _ => {
@@ -5881,16 +8949,13 @@ mod test {
 
     #[test]
     #[ignore]
-    fn test_rpc_get_map_entry_unconfirmed() {
-        // Test v2/map_entry (aka GetMapEntry) endpoint.
-        // In this test, we set `tip_req` to UseLatestUnconfirmedTip, and we expect that querying for map data
-        // against the unconfirmed state will succeed.
+    fn test_rpc_call_read_only_unconfirmed() {
         test_rpc(
             function_name!(),
-            40140,
-            40141,
-            50140,
-            50141,
+            40180,
+            40181,
+            50180,
+            50181,
             true,
             |ref mut peer_client,
              ref mut convo_client,
@@ -5903,21 +8968,17 @@ mod test {
                     .unwrap()
                     .unconfirmed_chain_tip
                     .clone();
-                let principal =
-                    StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
-                        .unwrap()
-                        .to_account_principal();
-                convo_client.new_getmapentry(
+                convo_client.new_callreadonlyfunction(
                     StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
                         .unwrap(),
-                    "hello-world".try_into().unwrap(),
-                    "unit-map".try_into().unwrap(),
-                    Value::Tuple(
-                        TupleData::from_data(vec![("account".into(), Value::Principal(principal))])
-                            .unwrap(),
-                    ),
+                    "hello-world-unconfirmed".try_into().unwrap(),
+                    StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
+                        .unwrap()
+                        .to_account_principal(),
+                    None,
+                    "ro-test".try_into().unwrap(),
+                    vec![],
                     TipRequest::SpecificTip(unconfirmed_tip),
-                    false,
                 )
             },
             |ref http_request,
@@ -5928,15 +8989,14 @@ mod test {
              ref convo_server| {
                 let req_md = http_request.metadata().clone();
                 match http_response {
-                    HttpResponseType::GetMapEntry(response_md, data) => {
+                    HttpResponseType::CallReadOnlyFunction(response_md, data) => {
+                        assert!(data.okay);
                         assert_eq!(
-                            Value::try_deserialize_hex_untyped(&data.data).unwrap(),
-                            Value::some(Value::Tuple(
-                                TupleData::from_data(vec![("units".into(), Value::Int(1))])
-                                    .unwrap()
-                            ))
-                            .unwrap()
+                            Value::try_deserialize_hex_untyped(&data.result.clone().unwrap())
+                                .unwrap(),
+                            Value::okay(Value::Int(1)).unwrap()
                         );
+                        assert!(data.cause.is_none());
                         true
                     }
                     _ => {
@@ -5950,34 +9010,38 @@ mod test {
 
     #[test]
     #[ignore]
-    fn test_rpc_get_map_entry_use_latest_tip() {
+    fn test_rpc_post_transaction_dry_run() {
+        // Test /v2/transactions/dry-run (aka PostTransactionDryRun) endpoint.
+        // The contract-call is executed but its write to `bar` is not kept.
         test_rpc(
             function_name!(),
-            40142,
-            40143,
-            50142,
-            50143,
+            40182,
+            40183,
+            50182,
+            50183,
             true,
             |ref mut peer_client,
              ref mut convo_client,
              ref mut peer_server,
              ref mut convo_server| {
-                let principal =
-                    StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
-                        .unwrap()
-                        .to_account_principal();
-                convo_client.new_getmapentry(
-                    StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
-                        .unwrap(),
-                    "hello-world".try_into().unwrap(),
-                    "unit-map".try_into().unwrap(),
-                    Value::Tuple(
-                        TupleData::from_data(vec![("account".into(), Value::Principal(principal))])
-                            .unwrap(),
-                    ),
-                    TipRequest::UseLatestAnchoredTip,
-                    false,
+                let privk = StacksPrivateKey::from_hex(
+                    "9f1f85a512a96a244e4c0d762788500687feb97481639572e3bffbd6860e6ab001",
                 )
+                .unwrap();
+                let mut tx = StacksTransaction::new(
+                    TransactionVersion::Testnet,
+                    TransactionAuth::from_p2pkh(&privk).unwrap(),
+                    TransactionPayload::new_contract_call(
+                        StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
+                            .unwrap(),
+                        "hello-world",
+                        "set-bar",
+                        vec![Value::Int(6), Value::Int(2)],
+                    )
+                    .unwrap(),
+                );
+                tx.chain_id = 0x80000000;
+                convo_client.new_post_transaction_dry_run(tx, TipRequest::UseLatestAnchoredTip)
             },
             |ref http_request,
              ref http_response,
@@ -5987,15 +9051,17 @@ mod test {
              ref convo_server| {
                 let req_md = http_request.metadata().clone();
                 match http_response {
-                    HttpResponseType::GetMapEntry(response_md, data) => {
+                    HttpResponseType::TransactionDryRun(response_md, data) => {
+                        assert!(data.okay);
                         assert_eq!(
-                            Value::try_deserialize_hex_untyped(&data.data).unwrap(),
-                            Value::some(Value::Tuple(
-                                TupleData::from_data(vec![("units".into(), Value::Int(1))])
-                                    .unwrap()
-                            ))
-                            .unwrap()
+                            Value::try_deserialize_hex_untyped(&data.result.clone().unwrap())
+                                .unwrap(),
+                            Value::okay(Value::Int(3)).unwrap()
                         );
+                        assert!(data.cause.is_none());
+                        assert!(data.execution_cost.runtime > 0);
+                        assert!(data.execution_cost.write_count > 0);
+                        assert!(data.events.is_empty());
                         true
                     }
                     _ => {
@@ -6009,27 +9075,35 @@ mod test {
 
     #[test]
     #[ignore]
-    fn test_rpc_get_contract_abi() {
-        // Test /v2/contracts/interface (aka GetContractABI) endpoint.
-        // In this test, we don't set any tip parameters, and we expect that querying
-        // against the canonical Stacks tip will succeed.
+    fn test_rpc_post_transaction_dry_run_not_contract_call() {
         test_rpc(
             function_name!(),
-            40150,
-            40151,
-            50150,
-            50151,
+            40184,
+            40185,
+            50184,
+            50185,
             true,
             |ref mut peer_client,
              ref mut convo_client,
              ref mut peer_server,
              ref mut convo_server| {
-                convo_client.new_getcontractabi(
-                    StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
-                        .unwrap(),
-                    "hello-world-unconfirmed".try_into().unwrap(),
-                    TipRequest::UseLatestAnchoredTip,
+                let privk = StacksPrivateKey::from_hex(
+                    "9f1f85a512a96a244e4c0d762788500687feb97481639572e3bffbd6860e6ab001",
                 )
+                .unwrap();
+                let mut tx = StacksTransaction::new(
+                    TransactionVersion::Testnet,
+                    TransactionAuth::from_p2pkh(&privk).unwrap(),
+                    TransactionPayload::TokenTransfer(
+                        StacksAddress::from_string("STVN97YYA10MY5F6KQJHKNYJNM24C4A1AT39WRW")
+                            .unwrap()
+                            .to_account_principal(),
+                        123,
+                        TokenTransferMemo([0u8; 34]),
+                    ),
+                );
+                tx.chain_id = 0x80000000;
+                convo_client.new_post_transaction_dry_run(tx, TipRequest::UseLatestAnchoredTip)
             },
             |ref http_request,
              ref http_response,
@@ -6039,8 +9113,8 @@ mod test {
              ref convo_server| {
                 let req_md = http_request.metadata().clone();
                 match http_response {
-                    HttpResponseType::NotFound(..) => {
-                        // not confirmed yet
+                    HttpResponseType::BadRequestJSON(response_md, data) => {
+                        assert_eq!(data["reason"], "NotContractCall");
                         true
                     }
                     _ => {
@@ -6052,36 +9126,44 @@ mod test {
         );
     }
 
-    #[test]
-    #[ignore]
-    fn test_rpc_get_contract_abi_unconfirmed() {
-        // Test /v2/contracts/interface (aka GetContractABI) endpoint.
-        // In this test, we set `tip_req` to UseLatestUnconfirmedTip, and we expect that querying
-        // against the unconfirmed state will succeed.
-        test_rpc(
-            function_name!(),
-            40152,
-            40153,
-            50152,
-            50153,
-            true,
-            |ref mut peer_client,
-             ref mut convo_client,
-             ref mut peer_server,
-             ref mut convo_server| {
-                let unconfirmed_tip = peer_client
-                    .chainstate()
-                    .unconfirmed_state
-                    .as_ref()
-                    .unwrap()
-                    .unconfirmed_chain_tip
-                    .clone();
-                convo_client.new_getcontractabi(
-                    StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
-                        .unwrap(),
-                    "hello-world-unconfirmed".try_into().unwrap(),
-                    TipRequest::SpecificTip(unconfirmed_tip),
+    #[test]
+    #[ignore]
+    fn test_rpc_post_transaction_bad_nonce() {
+        test_rpc(
+            function_name!(),
+            40206,
+            40207,
+            50206,
+            50207,
+            true,
+            |ref mut peer_client,
+             ref mut convo_client,
+             ref mut peer_server,
+             ref mut convo_server| {
+                let privk = StacksPrivateKey::from_hex(
+                    "9f1f85a512a96a244e4c0d762788500687feb97481639572e3bffbd6860e6ab001",
                 )
+                .unwrap();
+                let mut tx = StacksTransaction::new(
+                    TransactionVersion::Testnet,
+                    TransactionAuth::from_p2pkh(&privk).unwrap(),
+                    TransactionPayload::TokenTransfer(
+                        StacksAddress::from_string("STVN97YYA10MY5F6KQJHKNYJNM24C4A1AT39WRW")
+                            .unwrap()
+                            .to_account_principal(),
+                        123,
+                        TokenTransferMemo([0u8; 34]),
+                    ),
+                );
+                tx.chain_id = 0x80000000;
+                // nonce 0 was used by the coinbase
+                tx.auth.set_origin_nonce(0);
+                tx.set_tx_fee(1000);
+
+                let mut tx_signer = StacksTransactionSigner::new(&tx);
+                tx_signer.sign_origin(&privk).unwrap();
+                let tx = tx_signer.get_tx().unwrap();
+                convo_client.new_post_transaction(tx)
             },
             |ref http_request,
              ref http_response,
@@ -6091,7 +9173,17 @@ mod test {
              ref convo_server| {
                 let req_md = http_request.metadata().clone();
                 match http_response {
-                    HttpResponseType::GetContractABI(response_md, data) => true,
+                    HttpResponseType::BadRequestJSON(response_md, data) => {
+                        let body: PostTransactionErrorBody =
+                            serde_json::from_value(data.clone()).unwrap();
+                        assert_eq!(body.error, "BadNonce");
+                        assert!(body.txid.is_some());
+                        let reason_data = body.reason_data.unwrap();
+                        assert_eq!(reason_data["actual"], 0);
+                        assert!(reason_data["expected"].as_u64().unwrap() > 0);
+                        assert_eq!(reason_data["is_origin"], true);
+                        true
+                    }
                     _ => {
                         error!("Invalid response; {:?}", &http_response);
                         false
@@ -6101,26 +9193,193 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_post_transaction_error_codes() {
+        let txid = Txid([0x11; 32]);
+        let conflicting_txid = Txid([0x22; 32]);
+        let principal = StacksAddress::from_string("STVN97YYA10MY5F6KQJHKNYJNM24C4A1AT39WRW")
+            .unwrap()
+            .to_account_principal();
+
+        let rejections = vec![
+            (
+                MemPoolRejection::DeserializationFailure(codec_error::DeserializeError(
+                    "bad".into(),
+                )),
+                "Deserialization",
+            ),
+            (
+                MemPoolRejection::FailedToValidate(chain_error::InvalidStacksTransaction(
+                    "bad signature".into(),
+                    false,
+                )),
+                "SignatureValidation",
+            ),
+            (MemPoolRejection::FeeTooLow(1, 180), "FeeTooLow"),
+            (MemPoolRejection::BelowMinFeeRate(0, 1), "BelowMinFeeRate"),
+            (
+                MemPoolRejection::BadNonces(TransactionNonceMismatch {
+                    expected: 3,
+                    actual: 1,
+                    txid: txid.clone(),
+                    principal: principal.clone(),
+                    is_origin: true,
+                    quiet: false,
+                }),
+                "BadNonce",
+            ),
+            (
+                MemPoolRejection::TooMuchChaining {
+                    max_nonce: 25,
+                    actual_nonce: 30,
+                    principal: principal.clone(),
+                    is_origin: true,
+                },
+                "TooMuchChaining",
+            ),
+            (
+                MemPoolRejection::NotEnoughFunds(2000, 990),
+                "NotEnoughFunds",
+            ),
+            (
+                MemPoolRejection::InsufficientSponsorBalance {
+                    sponsor: principal.clone(),
+                    fee: 2000,
+                    balance: 990,
+                },
+                "InsufficientSponsorBalance",
+            ),
+            (
+                MemPoolRejection::ConflictingNonceInMempool(conflicting_txid.clone()),
+                "ConflictingNonceInMempool",
+            ),
+            (MemPoolRejection::NoSuchContract, "NoSuchContract"),
+            (
+                MemPoolRejection::NoSuchPublicFunction,
+                "NoSuchPublicFunction",
+            ),
+            (
+                MemPoolRejection::TransferRecipientIsSender(principal.clone()),
+                "TransferRecipientCannotEqualSender",
+            ),
+            (
+                MemPoolRejection::TransferAmountMustBePositive,
+                "TransferAmountMustBePositive",
+            ),
+            (
+                MemPoolRejection::BadTransactionVersion,
+                "BadTransactionVersion",
+            ),
+            (
+                MemPoolRejection::BadAddressVersionByte,
+                "BadAddressVersionByte",
+            ),
+            (
+                MemPoolRejection::NoCoinbaseViaMempool,
+                "NoCoinbaseViaMempool",
+            ),
+            (
+                MemPoolRejection::TemporarilyBlacklisted,
+                "TemporarilyBlacklisted",
+            ),
+            (MemPoolRejection::Other("oops".into()), "ServerFailureOther"),
+        ];
+
+        for (rejection, code) in rejections.into_iter() {
+            let error = PostTransactionError::Rejected(txid.clone(), rejection);
+            assert_eq!(error.error_code(), code);
+
+            // clients see the same code, and can parse the body back
+            let json = serde_json::to_value(&error).unwrap();
+            assert_eq!(json["error"], code);
+            assert_eq!(json["txid"], txid.to_hex());
+            assert!(!json["reason"].as_str().unwrap().is_empty());
+
+            let body: PostTransactionErrorBody = serde_json::from_value(json).unwrap();
+            assert_eq!(body, error.to_body());
+            assert_eq!(
+                error.is_conflict(),
+                code == "ConflictingNonceInMempool",
+                "{}",
+                code
+            );
+        }
+
+        // structured fields carry the details of the rejection
+        let body = PostTransactionError::Rejected(
+            txid.clone(),
+            MemPoolRejection::BadNonces(TransactionNonceMismatch {
+                expected: 3,
+                actual: 1,
+                txid: txid.clone(),
+                principal: principal.clone(),
+                is_origin: false,
+                quiet: false,
+            }),
+        )
+        .to_body();
+        let reason_data = body.reason_data.unwrap();
+        assert_eq!(reason_data["expected"], 3);
+        assert_eq!(reason_data["actual"], 1);
+        assert_eq!(reason_data["principal"], principal.to_string());
+        assert_eq!(reason_data["is_origin"], false);
+
+        let body =
+            PostTransactionError::Rejected(txid.clone(), MemPoolRejection::FeeTooLow(1, 180))
+                .to_body();
+        let reason_data = body.reason_data.unwrap();
+        assert_eq!(reason_data["expected"], 180);
+        assert_eq!(reason_data["actual"], 1);
+
+        let body = PostTransactionError::Rejected(
+            txid.clone(),
+            MemPoolRejection::ConflictingNonceInMempool(conflicting_txid.clone()),
+        )
+        .to_body();
+        assert_eq!(body.reason_data.unwrap()["txid"], conflicting_txid.to_hex());
+
+        // a transaction that can't be decoded has no txid
+        let error = PostTransactionError::Malformed("truncated".into());
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "error": "Deserialization",
+                "reason": "truncated",
+            })
+        );
+        assert!(!error.is_conflict());
+    }
+
     #[test]
     #[ignore]
-    fn test_rpc_get_contract_abi_use_latest_tip() {
+    fn test_rpc_post_block_validate_stale_parent() {
+        // Test /v2/blocks/validate (aka PostBlockValidate) endpoint.
+        // Re-proposing the chain tip is invalid, since it does not build off of the chain tip.
+        let expected_block_hash = RefCell::new(None);
         test_rpc(
             function_name!(),
-            40154,
-            40155,
-            50154,
-            50155,
+            40819,
+            40820,
+            50819,
+            50820,
             true,
             |ref mut peer_client,
              ref mut convo_client,
              ref mut peer_server,
              ref mut convo_server| {
-                convo_client.new_getcontractabi(
-                    StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
-                        .unwrap(),
-                    "hello-world-unconfirmed".try_into().unwrap(),
-                    TipRequest::UseLatestAnchoredTip,
+                let sortdb = peer_server.sortdb.as_ref().unwrap();
+                let chainstate = &mut peer_server.stacks_node.as_mut().unwrap().chainstate;
+                let tip = chainstate.get_stacks_chain_tip(sortdb).unwrap().unwrap();
+                let block = StacksChainState::load_block(
+                    &chainstate.blocks_path,
+                    &tip.consensus_hash,
+                    &tip.anchored_block_hash,
                 )
+                .unwrap()
+                .unwrap();
+                *expected_block_hash.borrow_mut() = Some(block.block_hash());
+                convo_client.new_post_block_validate(block)
             },
             |ref http_request,
              ref http_response,
@@ -6130,7 +9389,13 @@ mod test {
              ref convo_server| {
                 let req_md = http_request.metadata().clone();
                 match http_response {
-                    HttpResponseType::GetContractABI(response_md, data) => true,
+                    HttpResponseType::BlockProposalValidation(response_md, data) => {
+                        assert!(!data.valid);
+                        assert!(data.reason.is_some());
+                        assert!(data.invalid_txids.is_empty());
+                        assert_eq!(Some(data.block_hash), *expected_block_hash.borrow());
+                        true
+                    }
                     _ => {
                         error!("Invalid response; {:?}", &http_response);
                         false
@@ -6140,35 +9405,45 @@ mod test {
         );
     }
 
+    /// Load the microblock stream that `test_rpc` stores off of the server's Stacks chain tip
+    fn load_tip_microblock_stream(peer_server: &mut TestPeer) -> Vec<StacksMicroblock> {
+        let sortdb = peer_server.sortdb.take().unwrap();
+        let chainstate = peer_server.chainstate();
+        let tip = chainstate.get_stacks_chain_tip(&sortdb).unwrap().unwrap();
+        let mblocks = StacksChainState::load_descendant_staging_microblock_stream(
+            chainstate.db(),
+            &StacksBlockHeader::make_index_block_hash(
+                &tip.consensus_hash,
+                &tip.anchored_block_hash,
+            ),
+            0,
+            u16::MAX,
+        )
+        .unwrap()
+        .unwrap();
+        peer_server.sortdb = Some(sortdb);
+        mblocks
+    }
+
     #[test]
     #[ignore]
-    fn test_rpc_call_read_only() {
-        // Test /v2/contracts/call-read (aka CallReadOnlyFunction) endpoint.
-        // In this test, we don't set any tip parameters, and we expect that querying
-        // against the canonical Stacks tip will succeed.
+    fn test_rpc_post_microblocks_validate() {
+        // Test /v2/microblocks/validate (aka PostMicroblocksValidate) endpoint.
+        // The stream stored off of the chain tip is valid.
         test_rpc(
             function_name!(),
-            40170,
-            40171,
-            50170,
-            50171,
+            40208,
+            40209,
+            50208,
+            50209,
             true,
             |ref mut peer_client,
              ref mut convo_client,
              ref mut peer_server,
              ref mut convo_server| {
-                convo_client.new_callreadonlyfunction(
-                    StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
-                        .unwrap(),
-                    "hello-world-unconfirmed".try_into().unwrap(),
-                    StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
-                        .unwrap()
-                        .to_account_principal(),
-                    None,
-                    "ro-test".try_into().unwrap(),
-                    vec![],
-                    TipRequest::UseLatestAnchoredTip,
-                )
+                let mblocks = load_tip_microblock_stream(peer_server);
+                assert_eq!(mblocks.len(), 1);
+                convo_client.new_post_microblocks_validate(mblocks)
             },
             |ref http_request,
              ref http_response,
@@ -6178,11 +9453,10 @@ mod test {
              ref convo_server| {
                 let req_md = http_request.metadata().clone();
                 match http_response {
-                    HttpResponseType::CallReadOnlyFunction(response_md, data) => {
-                        assert!(data.cause.is_some());
-                        assert!(data.cause.clone().unwrap().find("NoSuchContract").is_some());
-                        assert!(!data.okay);
-                        assert!(data.result.is_none());
+                    HttpResponseType::MicroblockStreamValidation(response_md, data) => {
+                        assert!(data.valid);
+                        assert_eq!(data.first_invalid_seq, None);
+                        assert!(data.errors.is_empty());
                         true
                     }
                     _ => {
@@ -6196,33 +9470,24 @@ mod test {
 
     #[test]
     #[ignore]
-    fn test_rpc_call_read_only_use_latest_tip() {
-        // Test /v2/contracts/call-read (aka CallReadOnlyFunction) endpoint.
-        // In this test, we set `tip_req` to UseLatestUnconfirmedTip, and we expect that querying
-        // against the unconfirmed state will succeed.
+    fn test_rpc_post_microblocks_validate_bad_sequence() {
+        // Test /v2/microblocks/validate (aka PostMicroblocksValidate) endpoint.
+        // A stream off of the chain tip that does not start at sequence 0 is invalid, and so is
+        // the microblock's signature once its header is altered.
         test_rpc(
             function_name!(),
-            40172,
-            40173,
-            50172,
-            50173,
+            40210,
+            40211,
+            50210,
+            50211,
             true,
             |ref mut peer_client,
              ref mut convo_client,
              ref mut peer_server,
              ref mut convo_server| {
-                convo_client.new_callreadonlyfunction(
-                    StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
-                        .unwrap(),
-                    "hello-world-unconfirmed".try_into().unwrap(),
-                    StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
-                        .unwrap()
-                        .to_account_principal(),
-                    None,
-                    "ro-test".try_into().unwrap(),
-                    vec![],
-                    TipRequest::UseLatestAnchoredTip,
-                )
+                let mut mblocks = load_tip_microblock_stream(peer_server);
+                mblocks[0].header.sequence = 1;
+                convo_client.new_post_microblocks_validate(mblocks)
             },
             |ref http_request,
              ref http_response,
@@ -6232,14 +9497,12 @@ mod test {
              ref convo_server| {
                 let req_md = http_request.metadata().clone();
                 match http_response {
-                    HttpResponseType::CallReadOnlyFunction(response_md, data) => {
-                        assert!(data.okay);
-                        assert_eq!(
-                            Value::try_deserialize_hex_untyped(&data.result.clone().unwrap())
-                                .unwrap(),
-                            Value::okay(Value::Int(1)).unwrap()
-                        );
-                        assert!(data.cause.is_none());
+                    HttpResponseType::MicroblockStreamValidation(response_md, data) => {
+                        assert!(!data.valid);
+                        assert_eq!(data.first_invalid_seq, Some(1));
+                        assert_eq!(data.errors.len(), 2);
+                        assert!(data.errors[0].contains("has sequence 1, but expected 0"));
+                        assert!(data.errors[1].contains("failed to verify signature"));
                         true
                     }
                     _ => {
@@ -6253,37 +9516,65 @@ mod test {
 
     #[test]
     #[ignore]
-    fn test_rpc_call_read_only_unconfirmed() {
+    fn test_rpc_get_mempool_stats() {
+        let stats_before = RefCell::new(None);
+
         test_rpc(
             function_name!(),
-            40180,
-            40181,
-            50180,
-            50181,
-            true,
+            40817,
+            40818,
+            50817,
+            50818,
+            false,
             |ref mut peer_client,
              ref mut convo_client,
              ref mut peer_server,
              ref mut convo_server| {
-                let unconfirmed_tip = peer_client
-                    .chainstate()
-                    .unconfirmed_state
-                    .as_ref()
-                    .unwrap()
-                    .unconfirmed_chain_tip
-                    .clone();
-                convo_client.new_callreadonlyfunction(
-                    StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
-                        .unwrap(),
-                    "hello-world-unconfirmed".try_into().unwrap(),
-                    StacksAddress::from_string("ST2DS4MSWSGJ3W9FBC6BVT0Y92S345HY8N3T6AV7R")
-                        .unwrap()
-                        .to_account_principal(),
-                    None,
-                    "ro-test".try_into().unwrap(),
-                    vec![],
-                    TipRequest::SpecificTip(unconfirmed_tip),
+                let privk = StacksPrivateKey::from_hex(
+                    "9f1f85a512a96a244e4c0d762788500687feb97481639572e3bffbd6860e6ab001",
                 )
+                .unwrap();
+                let mut tx = StacksTransaction::new(
+                    TransactionVersion::Testnet,
+                    TransactionAuth::from_p2pkh(&privk).unwrap(),
+                    TransactionPayload::TokenTransfer(
+                        StacksAddress::from_string("STVN97YYA10MY5F6KQJHKNYJNM24C4A1AT39WRW")
+                            .unwrap()
+                            .to_account_principal(),
+                        123,
+                        TokenTransferMemo([0u8; 34]),
+                    ),
+                );
+                tx.chain_id = 0x80000000;
+                tx.set_tx_fee(0);
+
+                let mut mempool = peer_server.mempool.take().unwrap();
+                let sortdb = peer_server.sortdb.take().unwrap();
+                let (consensus_hash, block_hash) =
+                    SortitionDB::get_canonical_stacks_chain_tip_hash(sortdb.conn()).unwrap();
+
+                *stats_before.borrow_mut() = Some(MemPoolStats::load(&mempool).unwrap());
+
+                // a zero fee is too low once the mempool has a fee rate floor
+                mempool.min_fee_rate = 1;
+                match mempool.submit(
+                    peer_server.chainstate(),
+                    &sortdb,
+                    &consensus_hash,
+                    &block_hash,
+                    &tx,
+                    None,
+                    &ExecutionCost::max_value(),
+                    &StacksEpochId::Epoch20,
+                ) {
+                    Err(MemPoolRejection::BelowMinFeeRate(..)) => {}
+                    res => panic!("Unexpected submit result: {:?}", &res),
+                }
+
+                peer_server.mempool = Some(mempool);
+                peer_server.sortdb = Some(sortdb);
+
+                convo_client.new_get_mempool_stats()
             },
             |ref http_request,
              ref http_response,
@@ -6293,18 +9584,23 @@ mod test {
              ref convo_server| {
                 let req_md = http_request.metadata().clone();
                 match http_response {
-                    HttpResponseType::CallReadOnlyFunction(response_md, data) => {
-                        assert!(data.okay);
+                    HttpResponseType::MempoolStats(response_md, stats) => {
+                        // the counters are shared with any other test in this process
+                        let stats_before = stats_before.borrow().clone().unwrap();
+                        assert!(
+                            stats.rejected.fee_too_low >= stats_before.rejected.fee_too_low + 1
+                        );
+
+                        let mempool = peer_server.mempool.as_ref().unwrap();
                         assert_eq!(
-                            Value::try_deserialize_hex_untyped(&data.result.clone().unwrap())
-                                .unwrap(),
-                            Value::okay(Value::Int(1)).unwrap()
+                            stats.pending,
+                            MemPoolDB::get_num_txs(mempool.conn()).unwrap()
                         );
-                        assert!(data.cause.is_none());
+                        assert!(stats.pending > 0);
                         true
                     }
                     _ => {
-                        error!("Invalid response; {:?}", &http_response);
+                        error!("Invalid response: {:?}", &http_response);
                         false
                     }
                 }
@@ -6351,6 +9647,121 @@ mod test {
         );
     }
 
+    #[test]
+    #[ignore]
+    fn test_rpc_get_fee_rate_percentiles_disabled() {
+        test_rpc(
+            function_name!(),
+            40819,
+            40820,
+            50819,
+            50820,
+            false,
+            |ref mut peer_client,
+             ref mut convo_client,
+             ref mut peer_server,
+             ref mut convo_server| {
+                convo_client.new_get_fee_rate_percentiles(vec![5, 25, 50])
+            },
+            |ref http_request,
+             ref http_response,
+             ref mut peer_client,
+             ref mut peer_server,
+             ref convo_client,
+             ref convo_server| {
+                match http_response {
+                    HttpResponseType::BadRequestJSON(_, data) => {
+                        assert_eq!(data["reason"], "FeeEstimationDisabled");
+                        true
+                    }
+                    _ => {
+                        error!("Invalid response: {:?}", &http_response);
+                        false
+                    }
+                }
+            },
+        );
+    }
+
+    /// Only tracks the 5th, 50th and 95th percentiles.
+    struct ConstantFeeEstimator;
+
+    impl FeeEstimator for ConstantFeeEstimator {
+        fn notify_block(
+            &mut self,
+            _receipt: &StacksEpochReceipt,
+            _block_limit: &ExecutionCost,
+        ) -> Result<(), EstimatorError> {
+            Ok(())
+        }
+
+        fn get_rate_estimates_at(
+            &self,
+            percentiles: &[u8],
+        ) -> Result<BTreeMap<u8, f64>, EstimatorError> {
+            FeeRateEstimate {
+                high: 3f64,
+                middle: 2f64,
+                low: 1f64,
+            }
+            .at_percentiles(FeeRateEstimate::PERCENTILES, percentiles)
+        }
+    }
+
+    #[test]
+    fn test_rpc_handle_get_fee_rate_percentiles() {
+        let peer_addr: SocketAddr = "127.0.0.1:20443".parse().unwrap();
+        let fee_estimator = ConstantFeeEstimator;
+        let handler_args = RPCHandlerArgs {
+            fee_estimator: Some(&fee_estimator),
+            ..RPCHandlerArgs::default()
+        };
+
+        let get_fee_rate_percentiles = |percentiles: Vec<u8>| {
+            let req = HttpRequestType::GetFeeRatePercentiles(
+                HttpRequestMetadata::new("127.0.0.1".to_string(), 20443, None),
+                percentiles.clone(),
+            );
+            let mut http = StacksHttp::new(peer_addr.clone());
+            let mut bytes = vec![];
+            ConversationHttp::handle_get_fee_rate_percentiles(
+                &mut http,
+                &mut bytes,
+                &req,
+                &percentiles,
+                &handler_args,
+                1,
+            )
+            .unwrap();
+
+            let mut http = StacksHttp::new(peer_addr.clone());
+            http.begin_request(HttpVersion::Http11, req.request_path());
+            let (preamble, offset) = http.read_preamble(&bytes).unwrap();
+            let (message, _) = http.read_payload(&preamble, &bytes[offset..]).unwrap();
+            match message {
+                StacksHttpMessage::Response(response) => response,
+                StacksHttpMessage::Request(_) => panic!("parsed a request"),
+            }
+        };
+
+        // the tracked percentiles are served from the low, middle and high estimates
+        match get_fee_rate_percentiles(vec![5, 50, 95]) {
+            HttpResponseType::FeeRatePercentiles(_, fee_rates) => {
+                assert_eq!(
+                    fee_rates.into_iter().collect::<Vec<_>>(),
+                    vec![(5, 1f64), (50, 2f64), (95, 3f64)]
+                );
+            }
+            response => panic!("Invalid response: {:?}", &response),
+        }
+
+        // any other percentile is unavailable
+        match get_fee_rate_percentiles(vec![25, 50]) {
+            HttpResponseType::ServiceUnavailable(..) => {}
+            response => panic!("Invalid response: {:?}", &response),
+        }
+    }
+
     #[test]
     #[ignore]
     fn test_rpc_mempool_query_txtags() {
@@ -2,11 +2,13 @@ use async_h1::client;
 use async_std::io::ReadExt;
 use async_std::net::TcpStream;
 use base64::encode;
-use http_types::{Method, Request, Url};
+use http_types::{Method, Request, StatusCode, Url};
+use std::collections::HashMap;
+use std::fs;
 use std::io::Cursor;
 use std::sync::{
     atomic::{AtomicBool, Ordering},
-    Arc,
+    Arc, Mutex,
 };
 use std::time::Instant;
 
@@ -100,6 +102,12 @@ impl OngoingBlockCommit {
     fn sum_utxos(&self) -> u64 {
         self.utxos.total_available()
     }
+
+    /// Txids of every transaction broadcast for this block-commit, in the order they were sent.
+    /// All but the last one have been replaced by fee.
+    pub fn txids(&self) -> &Vec<Txid> {
+        &self.txids
+    }
 }
 
 #[derive(Clone)]
@@ -232,6 +240,11 @@ impl LeaderBlockCommitFees {
         self.sortition_fee / self.outputs_len
     }
 
+    /// Miner fees paid by this commit, including what was spent in prior RBF attempts
+    pub fn total_miner_fee(&self) -> u64 {
+        self.fee_rate * self.min_tx_size() + self.spent_in_attempts
+    }
+
     pub fn total_spent_in_outputs(&self) -> u64 {
         self.sunset_fee + self.sortition_fee
     }
@@ -1360,6 +1373,8 @@ impl BitcoinRegtestController {
                 None,
                 &vec![],
             )
+        } else if self.rbf_exceeds_fee_cap(&ongoing_op, &payload) {
+            None
         } else {
             // Case 2) ii): Attempt to RBF
             info!("Attempt to replace by fee an outdated leader block commit");
@@ -1381,6 +1396,32 @@ impl BitcoinRegtestController {
         res
     }
 
+    /// Would replacing the ongoing block-commit by fee push the miner fees spent on it past
+    /// `max_rbf_fee_cap` percent of the burn fee cap?
+    fn rbf_exceeds_fee_cap(
+        &self,
+        ongoing_op: &OngoingBlockCommit,
+        payload: &LeaderBlockCommitOp,
+    ) -> bool {
+        if self.config.burnchain.max_rbf_fee_cap == 0 {
+            return false;
+        }
+
+        let fee_cap =
+            self.config.burnchain.burn_fee_cap * self.config.burnchain.max_rbf_fee_cap / 100;
+        let next_fees = ongoing_op.fees.fees_from_previous_tx(payload, &self.config);
+        if next_fees.total_miner_fee() > fee_cap {
+            warn!(
+                "RBF'd block commit would spend more than {}% of the burn fee cap in fees, not resubmitting",
+                self.config.burnchain.max_rbf_fee_cap;
+                "miner_fee" => next_fees.total_miner_fee(),
+                "fee_cap" => fee_cap,
+            );
+            return true;
+        }
+        false
+    }
+
     fn get_miner_address(
         &self,
         epoch_id: StacksEpochId,
@@ -1763,6 +1804,39 @@ impl BitcoinRegtestController {
         }
     }
 
+    #[cfg(test)]
+    pub fn get_block_count(&self) -> u64 {
+        let request = BitcoinRPCRequest {
+            method: "getblockcount".into(),
+            params: vec![],
+            id: "stacks".into(),
+            jsonrpc: "2.0".into(),
+        };
+        match BitcoinRPCRequest::send(&self.config, request) {
+            Ok(v) => v.get("result").unwrap().as_u64().unwrap(),
+            Err(e) => {
+                error!("Bitcoin RPC failure: error getting block count {:?}", e);
+                panic!();
+            }
+        }
+    }
+
+    /// Bootstrap the chain until bitcoind has `height` blocks, generating only the blocks it does
+    /// not already have.  A test that restarts bitcoind against a datadir bootstrapped by an
+    /// earlier run resumes from that datadir's tip instead of mining the whole chain again.
+    #[cfg(test)]
+    pub fn bootstrap_chain_to_height(&mut self, height: u64) {
+        let current_height = self.get_block_count();
+        if current_height >= height {
+            info!(
+                "Bitcoin regtest chain already has {} blocks; not bootstrapping to {}",
+                current_height, height
+            );
+            return;
+        }
+        self.bootstrap_chain(height - current_height);
+    }
+
     #[cfg(test)]
     pub fn get_mining_pubkey(&self) -> Option<String> {
         self.config.burnchain.local_mining_public_key.clone()
@@ -2133,8 +2207,49 @@ pub enum RPCError {
 
 type RPCResult<T> = Result<T, RPCError>;
 
+lazy_static! {
+    /// The credentials read from each bitcoind RPC cookie file so far, by path
+    static ref RPC_COOKIES: Mutex<HashMap<String, String>> = Mutex::new(HashMap::new());
+}
+
 impl BitcoinRPCRequest {
-    fn build_rpc_request(config: &Config, payload: &BitcoinRPCRequest) -> Request {
+    /// Get the `user:password` credentials in the bitcoind RPC cookie file at `cookie_file`.
+    /// The file is only read if it has not been read before, or if `reload` is set.
+    fn get_rpc_cookie(cookie_file: &str, reload: bool) -> RPCResult<String> {
+        let mut cookies = RPC_COOKIES
+            .lock()
+            .expect("FATAL: RPC cookie cache lock poisoned");
+        if !reload {
+            if let Some(cookie) = cookies.get(cookie_file) {
+                return Ok(cookie.clone());
+            }
+        }
+
+        let cookie = fs::read_to_string(cookie_file).map_err(|e| {
+            RPCError::Network(format!(
+                "Bitcoin RPC: failed to read cookie file {}: {:?}",
+                cookie_file, &e
+            ))
+        })?;
+        let cookie = cookie.trim().to_string();
+        if !cookie.contains(':') {
+            return Err(RPCError::Parsing(format!(
+                "Bitcoin RPC: cookie file {} is not of the form user:password",
+                cookie_file
+            )));
+        }
+        debug!("Bitcoin RPC: read cookie file {}", cookie_file);
+        cookies.insert(cookie_file.to_string(), cookie.clone());
+        Ok(cookie)
+    }
+
+    /// Build an RPC request.  If bitcoind authenticates us with a cookie file, then it is re-read
+    /// if `reload_cookie` is set.
+    fn build_rpc_request(
+        config: &Config,
+        payload: &BitcoinRPCRequest,
+        reload_cookie: bool,
+    ) -> RPCResult<Request> {
         let url = {
             // some methods require a wallet ID
             let wallet_id = match payload.method.as_str() {
@@ -2151,14 +2266,20 @@ impl BitcoinRPCRequest {
 
         let mut req = Request::new(Method::Post, url);
 
-        match (&config.burnchain.username, &config.burnchain.password) {
-            (Some(username), Some(password)) => {
-                let auth_token = format!("Basic {}", encode(format!("{}:{}", username, password)));
-                req.append_header("Authorization", auth_token);
-            }
-            (_, _) => {}
-        };
-        req
+        if let Some(cookie_file) = config.burnchain.rpc_cookie_file.as_ref() {
+            let cookie = BitcoinRPCRequest::get_rpc_cookie(cookie_file, reload_cookie)?;
+            req.append_header("Authorization", format!("Basic {}", encode(cookie)));
+        } else {
+            match (&config.burnchain.username, &config.burnchain.password) {
+                (Some(username), Some(password)) => {
+                    let auth_token =
+                        format!("Basic {}", encode(format!("{}:{}", username, password)));
+                    req.append_header("Authorization", auth_token);
+                }
+                (_, _) => {}
+            };
+        }
+        Ok(req)
     }
 
     #[cfg(test)]
@@ -2423,14 +2544,56 @@ impl BitcoinRPCRequest {
     }
 
     fn send(config: &Config, payload: BitcoinRPCRequest) -> RPCResult<serde_json::Value> {
-        let mut request = BitcoinRPCRequest::build_rpc_request(&config, &payload);
-
         let body = match serde_json::to_vec(&json!(payload)) {
             Ok(body) => body,
             Err(err) => {
                 return Err(RPCError::Network(format!("RPC Error: {}", err)));
             }
         };
+
+        let (status, res, buffer) =
+            match BitcoinRPCRequest::send_request(config, &payload, body.clone(), false)? {
+                (StatusCode::Unauthorized, ..) if config.burnchain.rpc_cookie_file.is_some() => {
+                    // bitcoind writes a new cookie each time it starts, so ours may be stale
+                    debug!("Bitcoin RPC: cookie was rejected; re-reading cookie file");
+                    BitcoinRPCRequest::send_request(config, &payload, body, true)?
+                }
+                result => result,
+            };
+
+        if !status.is_success() {
+            return Err(RPCError::Network(format!(
+                "Bitcoin RPC: status({}) != success, body is '{:?}'",
+                status,
+                match serde_json::from_slice::<serde_json::Value>(&buffer[..]) {
+                    Ok(v) => v,
+                    Err(_e) => serde_json::from_str("\"(unparseable)\"")
+                        .expect("Failed to parse JSON literal"),
+                }
+            )));
+        }
+
+        if res.is_err() {
+            return Err(RPCError::Network(format!(
+                "Bitcoin RPC: unable to read body - {:?}",
+                res
+            )));
+        }
+
+        let payload = serde_json::from_slice::<serde_json::Value>(&buffer[..])
+            .map_err(|e| RPCError::Parsing(format!("Bitcoin RPC: {}", e)))?;
+        Ok(payload)
+    }
+
+    /// Send the serialized `payload` to bitcoind, and return the response's status, the result
+    /// of reading its body, and the body.
+    fn send_request(
+        config: &Config,
+        payload: &BitcoinRPCRequest,
+        body: Vec<u8>,
+        reload_cookie: bool,
+    ) -> RPCResult<(StatusCode, std::io::Result<usize>, Vec<u8>)> {
+        let mut request = BitcoinRPCRequest::build_rpc_request(&config, payload, reload_cookie)?;
         request.append_header("Content-Type", "application/json");
         request.set_body(body);
 
@@ -2464,28 +2627,6 @@ impl BitcoinRPCRequest {
             let res = body.read_to_end(&mut buffer).await;
             (res, buffer)
         });
-
-        if !status.is_success() {
-            return Err(RPCError::Network(format!(
-                "Bitcoin RPC: status({}) != success, body is '{:?}'",
-                status,
-                match serde_json::from_slice::<serde_json::Value>(&buffer[..]) {
-                    Ok(v) => v,
-                    Err(_e) => serde_json::from_str("\"(unparseable)\"")
-                        .expect("Failed to parse JSON literal"),
-                }
-            )));
-        }
-
-        if res.is_err() {
-            return Err(RPCError::Network(format!(
-                "Bitcoin RPC: unable to read body - {:?}",
-                res
-            )));
-        }
-
-        let payload = serde_json::from_slice::<serde_json::Value>(&buffer[..])
-            .map_err(|e| RPCError::Parsing(format!("Bitcoin RPC: {}", e)))?;
-        Ok(payload)
+        Ok((status, res, buffer))
     }
 }
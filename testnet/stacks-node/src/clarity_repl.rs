@@ -0,0 +1,186 @@
+// Copyright (C) 2013-2020 Blockstack PBC, a public benefit corporation
+// Copyright (C) 2020-2022 Stacks Open Internet Foundation
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A REPL that evaluates Clarity expressions against a node's chainstate, without changing it
+//! (`stacks-node clarity-repl`).
+
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+
+use stacks::burnchains::PoxConstants;
+use stacks::chainstate::burn::db::sortdb::SortitionDB;
+use stacks::chainstate::stacks::db::StacksChainState;
+use stacks::chainstate::stacks::StacksBlockHeader;
+use stacks::clarity_vm::clarity::ClarityConnection;
+use stacks::types::chainstate::StacksBlockId;
+use stacks::vm::ast::ASTRules;
+use stacks::vm::costs::{ExecutionCost, LimitedCostTracker};
+use stacks::vm::types::{PrincipalData, QualifiedContractIdentifier, StandardPrincipalData};
+use stacks::vm::{ClarityVersion, Value};
+
+/// A read-only view of a chainstate at its canonical Stacks chain tip, in which Clarity
+/// expressions can be evaluated.
+pub struct ClarityRepl {
+    chainstate: StacksChainState,
+    sortdb: SortitionDB,
+    tip: StacksBlockId,
+    /// If set, expressions are evaluated in this contract's context, so they can refer to its
+    /// data vars, maps, and private functions.
+    contract: Option<QualifiedContractIdentifier>,
+    sender: PrincipalData,
+}
+
+impl ClarityRepl {
+    /// Open the chainstate at `chainstate_path` and the sortition DB in `burnchain_path`
+    /// read-only.  If `burnchain_path` is not given, the `burnchain` directory next to the
+    /// chainstate is used, as laid out in a node's working directory.
+    pub fn open(
+        chainstate_path: &str,
+        burnchain_path: Option<&str>,
+        contract: Option<QualifiedContractIdentifier>,
+        sender: Option<PrincipalData>,
+    ) -> Result<ClarityRepl, String> {
+        let chainstate = StacksChainState::open_readonly(chainstate_path, None)
+            .map_err(|e| format!("Failed to open chainstate at {}: {:?}", chainstate_path, &e))?;
+
+        let sortdb_path = match burnchain_path {
+            Some(burnchain_path) => PathBuf::from(burnchain_path),
+            None => {
+                let mut path = PathBuf::from(chainstate_path);
+                path.pop();
+                path.push("burnchain");
+                path
+            }
+        }
+        .join("sortition");
+        let sortdb_path = sortdb_path
+            .to_str()
+            .ok_or_else(|| format!("Invalid sortition DB path {:?}", &sortdb_path))?
+            .to_string();
+
+        // don't let SortitionDB::open() create a new, empty sortition DB
+        if fs::metadata(&sortdb_path).is_err() {
+            return Err(format!("No sortition DB at {}", &sortdb_path));
+        }
+
+        let pox_constants = if chainstate.mainnet {
+            PoxConstants::mainnet_default()
+        } else {
+            PoxConstants::testnet_default()
+        };
+        let sortdb = SortitionDB::open(&sortdb_path, false, pox_constants)
+            .map_err(|e| format!("Failed to open sortition DB at {}: {:?}", &sortdb_path, &e))?;
+
+        let tip = chainstate
+            .get_stacks_chain_tip(&sortdb)
+            .map_err(|e| format!("Failed to load Stacks chain tip: {:?}", &e))?
+            .map(|tip| {
+                StacksBlockHeader::make_index_block_hash(
+                    &tip.consensus_hash,
+                    &tip.anchored_block_hash,
+                )
+            })
+            .ok_or_else(|| "The chainstate has no Stacks blocks yet".to_string())?;
+
+        Ok(ClarityRepl {
+            chainstate,
+            sortdb,
+            tip,
+            contract,
+            sender: sender
+                .unwrap_or_else(|| PrincipalData::Standard(StandardPrincipalData::transient())),
+        })
+    }
+
+    pub fn tip(&self) -> &StacksBlockId {
+        &self.tip
+    }
+
+    /// Evaluate `program` at the chain tip, and return its value and what it cost.  Any write
+    /// is an error, and the state is rolled back afterwards regardless.
+    pub fn eval(&mut self, program: &str) -> Result<(Value, ExecutionCost), String> {
+        let mainnet = self.chainstate.mainnet;
+        let chain_id = self.chainstate.chain_id;
+        let mut cost_limit = ExecutionCost::max_value();
+        cost_limit.write_length = 0;
+        cost_limit.write_count = 0;
+
+        let contract = &self.contract;
+        let sender = &self.sender;
+
+        self.chainstate
+            .with_read_only_clarity_tx(&self.sortdb.index_conn(), &self.tip, |clarity_tx| {
+                let epoch = clarity_tx.get_epoch();
+                let cost_track = clarity_tx
+                    .with_clarity_db_readonly(|clarity_db| {
+                        LimitedCostTracker::new_mid_block(
+                            mainnet, chain_id, cost_limit, clarity_db, epoch,
+                        )
+                    })
+                    .map_err(|e| format!("Failed to load cost contracts: {:?}", &e))?;
+
+                clarity_tx
+                    .with_readonly_clarity_env(
+                        mainnet,
+                        chain_id,
+                        ClarityVersion::default_for_epoch(epoch),
+                        sender.clone(),
+                        None,
+                        cost_track,
+                        |env| {
+                            let value = match contract {
+                                Some(contract_id) => env.eval_read_only_with_rules(
+                                    contract_id,
+                                    program,
+                                    ASTRules::PrecheckSize,
+                                )?,
+                                None => env.eval_raw_with_rules(program, ASTRules::PrecheckSize)?,
+                            };
+                            Ok((value, env.global_context.cost_track.get_total()))
+                        },
+                    )
+                    .map_err(|e| e.to_string())
+            })
+            .ok_or_else(|| format!("Chain tip {} not found", &self.tip))?
+    }
+
+    /// Evaluate each line of `input` as a Clarity expression until it is exhausted, printing
+    /// each result and its cost to `output`.
+    pub fn run<R: BufRead, W: Write>(&mut self, input: R, mut output: W) -> io::Result<()> {
+        write!(output, "> ")?;
+        output.flush()?;
+        for line in input.lines() {
+            let line = line?;
+            let program = line.trim();
+            if !program.is_empty() {
+                match self.eval(program) {
+                    Ok((value, cost)) => {
+                        writeln!(output, "{}", value)?;
+                        writeln!(output, "cost: {}", cost)?;
+                    }
+                    Err(e) => {
+                        writeln!(output, "error: {}", e)?;
+                    }
+                }
+            }
+            write!(output, "> ")?;
+            output.flush()?;
+        }
+        writeln!(output)?;
+        Ok(())
+    }
+}
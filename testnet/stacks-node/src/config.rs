@@ -4,6 +4,7 @@ use std::net::{SocketAddr, ToSocketAddrs};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::time::Duration;
 
 use rand::RngCore;
 
@@ -16,19 +17,27 @@ use stacks::chainstate::stacks::miner::BlockBuilderSettings;
 use stacks::chainstate::stacks::miner::MinerStatus;
 use stacks::chainstate::stacks::MAX_BLOCK_LEN;
 use stacks::core::mempool::MemPoolWalkSettings;
+use stacks::core::mempool::{
+    DEFAULT_RBF_FEE_INCREASE_PCT, MEMPOOL_MAX_TRANSACTION_AGE, MIN_RBF_FEE_INCREASE_PCT,
+};
 use stacks::core::StacksEpoch;
 use stacks::core::StacksEpochExtension;
 use stacks::core::StacksEpochId;
 use stacks::core::{
     CHAIN_ID_MAINNET, CHAIN_ID_TESTNET, PEER_VERSION_MAINNET, PEER_VERSION_TESTNET,
 };
+use stacks::cost_estimates::fee_http::HttpFeeRateEstimator;
 use stacks::cost_estimates::fee_medians::WeightedMedianFeeRateEstimator;
+use stacks::cost_estimates::fee_rate_floor::FeeRateFloor;
 use stacks::cost_estimates::fee_rate_fuzzer::FeeRateFuzzer;
 use stacks::cost_estimates::fee_scalar::ScalarFeeRateEstimator;
 use stacks::cost_estimates::metrics::CostMetric;
 use stacks::cost_estimates::metrics::ProportionalDotProduct;
+use stacks::cost_estimates::ContractFunctionCostEstimator;
 use stacks::cost_estimates::CostEstimator;
+use stacks::cost_estimates::DisabledFeeEstimator;
 use stacks::cost_estimates::FeeEstimator;
+use stacks::cost_estimates::FeeRateEstimate;
 use stacks::cost_estimates::PessimisticEstimator;
 use stacks::net::connection::ConnectionOptions;
 use stacks::net::{Neighbor, NeighborKey, PeerAddress};
@@ -38,6 +47,7 @@ use stacks::util::secp256k1::Secp256k1PrivateKey;
 use stacks::util::secp256k1::Secp256k1PublicKey;
 use stacks::vm::costs::ExecutionCost;
 use stacks::vm::types::{AssetIdentifier, PrincipalData, QualifiedContractIdentifier};
+use stacks_common::types::chainstate::StacksBlockId;
 
 const DEFAULT_SATS_PER_VB: u64 = 50;
 const DEFAULT_MAX_RBF_RATE: u64 = 150; // 1.5x
@@ -55,6 +65,8 @@ pub struct ConfigFile {
     pub connection_options: Option<ConnectionOptionsFile>,
     pub fee_estimation: Option<FeeEstimationConfigFile>,
     pub miner: Option<MinerConfigFile>,
+    pub mempool: Option<MempoolConfigFile>,
+    pub monitoring: Option<MonitoringConfigFile>,
 }
 
 #[derive(Clone, Deserialize, Default)]
@@ -129,6 +141,467 @@ mod tests {
         assert!(Config::from_config_file(ConfigFile::from_str("").unwrap()).is_ok());
     }
 
+    #[test]
+    fn test_monitoring_config() {
+        let config = Config::from_config_file(ConfigFile::from_str("").unwrap()).unwrap();
+        assert_eq!(config.node.prometheus_bind, None);
+
+        let config = Config::from_config_file(
+            ConfigFile::from_str(
+                r#"
+                [node]
+                prometheus_bind = "127.0.0.1:9153"
+                "#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            config.node.prometheus_bind,
+            Some("127.0.0.1:9153".to_string())
+        );
+
+        let config = Config::from_config_file(
+            ConfigFile::from_str(
+                r#"
+                [node]
+                prometheus_bind = "127.0.0.1:9153"
+
+                [monitoring]
+                prometheus_bind = "0.0.0.0:9154"
+                "#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            config.node.prometheus_bind,
+            Some("0.0.0.0:9154".to_string())
+        );
+    }
+
+    #[test]
+    fn test_mempool_config() {
+        let config = Config::from_config_file(ConfigFile::from_str("").unwrap()).unwrap();
+        assert!(!config.mempool.enable_rbf);
+        assert_eq!(
+            config.mempool.rbf_fee_increase_pct,
+            DEFAULT_RBF_FEE_INCREASE_PCT
+        );
+        assert_eq!(config.mempool.min_fee_rate, 0);
+        assert!(!config.mempool.validate_sponsor_balance);
+        assert_eq!(config.mempool.mempool_timeout, MEMPOOL_MAX_TRANSACTION_AGE);
+        assert_eq!(config.mempool.max_tx_count, None);
+        assert_eq!(config.mempool.max_total_bytes, None);
+
+        let config = Config::from_config_file(
+            ConfigFile::from_str(
+                r#"
+                [mempool]
+                enable_rbf = true
+                rbf_fee_increase_pct = 50
+                min_fee_rate = 5
+                validate_sponsor_balance = true
+                mempool_timeout = 10
+                max_tx_count = 5000
+                max_total_bytes = 2000000
+                "#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(config.mempool.enable_rbf);
+        assert_eq!(config.mempool.rbf_fee_increase_pct, 50);
+        assert_eq!(config.mempool.min_fee_rate, 5);
+        assert!(config.mempool.validate_sponsor_balance);
+        assert_eq!(config.mempool.mempool_timeout, 10);
+        assert_eq!(config.mempool.max_tx_count, Some(5000));
+        assert_eq!(config.mempool.max_total_bytes, Some(2000000));
+
+        // a bump below the 25% minimum is refused
+        assert!(Config::from_config_file(
+            ConfigFile::from_str(
+                r#"
+                [mempool]
+                enable_rbf = true
+                rbf_fee_increase_pct = 10
+                "#,
+            )
+            .unwrap(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_burn_fee_strategy_config() {
+        let config = Config::from_config_file(ConfigFile::from_str("").unwrap()).unwrap();
+        assert_eq!(config.miner.burn_fee_strategy, BurnFeeStrategy::Fixed);
+
+        let config = Config::from_config_file(
+            ConfigFile::from_str(
+                r#"
+                [miner]
+                burn_fee_strategy = "follow_winners"
+                burn_fee_follow_window = 3
+                burn_fee_follow_pct = 150
+                "#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            config.miner.burn_fee_strategy,
+            BurnFeeStrategy::FollowWinners
+        );
+        assert_eq!(config.miner.burn_fee_follow_window, 3);
+        assert_eq!(config.miner.burn_fee_follow_pct, 150);
+
+        let config = Config::from_config_file(
+            ConfigFile::from_str(
+                r#"
+                [miner]
+                burn_fee_strategy = "win_rate"
+                min_burn_fee = 5000
+                burn_fee_win_rate_window = 20
+                burn_fee_loss_increase_pct = 25
+                burn_fee_win_decrease_pct = 15
+                "#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(config.miner.burn_fee_strategy, BurnFeeStrategy::WinRate);
+        assert_eq!(config.miner.min_burn_fee, 5000);
+        assert_eq!(config.miner.burn_fee_win_rate_window, 20);
+        assert_eq!(config.miner.burn_fee_loss_increase_pct, 25);
+        assert_eq!(config.miner.burn_fee_win_decrease_pct, 15);
+
+        assert_eq!(
+            Config::from_config_file(
+                ConfigFile::from_str(
+                    r#"
+                    [miner]
+                    burn_fee_strategy = "win_rate"
+                    burn_fee_win_decrease_pct = 101
+                    "#,
+                )
+                .unwrap(),
+            )
+            .unwrap_err(),
+            "Setting miner.burn_fee_win_decrease_pct must be at most 100"
+        );
+
+        assert_eq!(
+            Config::from_config_file(
+                ConfigFile::from_str(
+                    r#"
+                    [miner]
+                    burn_fee_strategy = "balance_percent"
+                    burn_fee_balance_pct = 101
+                    "#,
+                )
+                .unwrap(),
+            )
+            .unwrap_err(),
+            "Setting miner.burn_fee_balance_pct must be between 1 and 100"
+        );
+    }
+
+    #[test]
+    fn test_http_fee_estimation_config() {
+        let config = Config::from_config_file(ConfigFile::from_str("").unwrap()).unwrap();
+        assert!(matches!(
+            config.estimation.fee_estimator,
+            Some(FeeEstimatorName::ScalarFeeRate)
+        ));
+        assert!(config.estimation.http_url.is_none());
+
+        let config = Config::from_config_file(
+            ConfigFile::from_str(
+                r#"
+                [fee_estimation]
+                backend = "http"
+
+                [fee_estimation.http]
+                url = "http://127.0.0.1:3999"
+                timeout_ms = 250
+                "#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(matches!(
+            config.estimation.fee_estimator,
+            Some(FeeEstimatorName::Http)
+        ));
+        assert_eq!(
+            config.estimation.http_url,
+            Some("http://127.0.0.1:3999".to_string())
+        );
+        assert_eq!(config.estimation.http_timeout_ms, 250);
+        assert!(config.make_fee_estimator().is_some());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_http_fee_estimation_config_requires_url() {
+        let _ = Config::from_config_file(
+            ConfigFile::from_str(
+                r#"
+                [fee_estimation]
+                backend = "http"
+                "#,
+            )
+            .unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_choose_burn_fee() {
+        let mut miner = MinerConfig::default();
+        let spend_cap = 20_000;
+
+        // fixed always bids the cap
+        assert_eq!(
+            miner.choose_burn_fee(spend_cap, Some(1_000), &[5_000], &[]),
+            spend_cap
+        );
+
+        miner.burn_fee_strategy = BurnFeeStrategy::BalancePercent;
+        miner.burn_fee_balance_pct = 10;
+        assert_eq!(
+            miner.choose_burn_fee(spend_cap, Some(100_000), &[], &[]),
+            10_000
+        );
+        assert_eq!(
+            miner.choose_burn_fee(spend_cap, Some(1_000_000), &[], &[]),
+            spend_cap
+        );
+        assert_eq!(miner.choose_burn_fee(spend_cap, Some(5), &[], &[]), 1);
+        assert_eq!(miner.choose_burn_fee(spend_cap, None, &[], &[]), spend_cap);
+
+        miner.burn_fee_strategy = BurnFeeStrategy::FollowWinners;
+        miner.burn_fee_follow_pct = 110;
+        assert_eq!(
+            miner.choose_burn_fee(spend_cap, None, &[1_000, 10_000, 5_000], &[]),
+            11_000
+        );
+        assert_eq!(
+            miner.choose_burn_fee(spend_cap, None, &[19_000], &[]),
+            spend_cap
+        );
+        assert_eq!(
+            miner.choose_burn_fee(spend_cap, None, &[u64::MAX], &[]),
+            spend_cap
+        );
+        assert_eq!(miner.choose_burn_fee(spend_cap, None, &[], &[]), spend_cap);
+
+        miner.burn_fee_strategy = BurnFeeStrategy::WinRate;
+        miner.min_burn_fee = 10_000;
+        miner.burn_fee_loss_increase_pct = 10;
+        miner.burn_fee_win_decrease_pct = 10;
+        assert_eq!(miner.choose_burn_fee(spend_cap, None, &[], &[]), 15_000);
+        assert_eq!(
+            miner.choose_burn_fee(spend_cap, None, &[], &[false]),
+            16_500
+        );
+        assert_eq!(miner.choose_burn_fee(spend_cap, None, &[], &[true]), 13_500);
+        // a cap below the floor wins out
+        assert_eq!(
+            miner.choose_burn_fee(5_000, None, &[], &[true, true]),
+            5_000
+        );
+    }
+
+    #[test]
+    fn test_win_rate_burn_fee_controller() {
+        let controller = WinRateBurnFeeController {
+            min_burn_fee: 1_000,
+            max_burn_fee: 3_000,
+            loss_increase_pct: 20,
+            win_decrease_pct: 10,
+        };
+
+        // no history starts halfway between the bounds
+        assert_eq!(controller.burn_fee(&[]), 2_000);
+
+        // outcomes are applied oldest first
+        assert_eq!(controller.burn_fee(&[false]), 2_400);
+        assert_eq!(controller.burn_fee(&[false, true]), 2_160);
+        assert_eq!(controller.burn_fee(&[true, false]), 2_160);
+        assert_eq!(controller.burn_fee(&[true, true]), 1_620);
+
+        // losing keeps raising the burn up to the cap, and winning keeps lowering it down to
+        // the floor
+        assert_eq!(controller.burn_fee(&[false; 10]), 3_000);
+        assert_eq!(controller.burn_fee(&[true; 10]), 1_000);
+
+        // the bounds are applied at every step, so a streak of losses that hit the cap
+        // doesn't have to be undone by an equally long streak of wins
+        let mut history = vec![false; 10];
+        history.push(true);
+        assert_eq!(controller.burn_fee(&history), 2_700);
+
+        // a loss always raises the burn, even when the percentage rounds down to nothing
+        let controller = WinRateBurnFeeController {
+            min_burn_fee: 1,
+            max_burn_fee: 3,
+            loss_increase_pct: 10,
+            win_decrease_pct: 100,
+        };
+        assert_eq!(controller.burn_fee(&[]), 2);
+        assert_eq!(controller.burn_fee(&[false]), 3);
+        assert_eq!(controller.burn_fee(&[true]), 1);
+        assert_eq!(controller.burn_fee(&[true, false]), 2);
+
+        // a floor above the cap is lowered to the cap
+        let controller = WinRateBurnFeeController {
+            min_burn_fee: 10_000,
+            max_burn_fee: 5_000,
+            loss_increase_pct: 10,
+            win_decrease_pct: 10,
+        };
+        assert_eq!(controller.burn_fee(&[]), 5_000);
+        assert_eq!(controller.burn_fee(&[true, false]), 5_000);
+    }
+
+    #[test]
+    fn test_configured_epochs() {
+        let config = Config::from_config_file(
+            ConfigFile::from_str(
+                r#"
+                [burnchain]
+                mode = "krypton"
+
+                [[burnchain.epoch]]
+                epoch_id = "1.0"
+                start_height = 0
+                end_height = 0
+
+                [[burnchain.epoch]]
+                epoch_id = "2.0"
+                start_height = 0
+                end_height = 100
+
+                [[burnchain.epoch]]
+                epoch_id = "2.05"
+                start_height = 100
+                "#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let epochs = config.burnchain.epochs.unwrap();
+        assert_eq!(
+            epochs
+                .iter()
+                .map(|epoch| (epoch.epoch_id, epoch.start_height, epoch.end_height))
+                .collect::<Vec<_>>(),
+            vec![
+                (StacksEpochId::Epoch10, 0, 0),
+                (StacksEpochId::Epoch20, 0, 100),
+                (StacksEpochId::Epoch2_05, 100, i64::MAX as u64),
+            ]
+        );
+
+        assert_eq!(
+            "Configured epochs must be contiguous: epoch 2.0 ends at 90, but epoch 2.05 starts at 100",
+            Config::from_config_file(
+                ConfigFile::from_str(
+                    r#"
+                    [burnchain]
+                    mode = "krypton"
+
+                    [[burnchain.epoch]]
+                    epoch_id = "1.0"
+                    start_height = 0
+
+                    [[burnchain.epoch]]
+                    epoch_id = "2.0"
+                    start_height = 0
+                    end_height = 90
+
+                    [[burnchain.epoch]]
+                    epoch_id = "2.05"
+                    start_height = 100
+                    "#,
+                )
+                .unwrap()
+            )
+            .unwrap_err()
+        );
+
+        assert_eq!(
+            "The last configured epoch (2.0) may not set an end height",
+            Config::from_config_file(
+                ConfigFile::from_str(
+                    r#"
+                    [burnchain]
+                    mode = "krypton"
+
+                    [[burnchain.epoch]]
+                    epoch_id = "1.0"
+                    start_height = 0
+
+                    [[burnchain.epoch]]
+                    epoch_id = "2.0"
+                    start_height = 0
+                    end_height = 100
+                    "#,
+                )
+                .unwrap()
+            )
+            .unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_check_epochs_against_burnchain_tip() {
+        let current = stacks::core::STACKS_EPOCHS_REGTEST.to_vec();
+        let moved = |epoch_id: StacksEpochId, start_height: u64| {
+            let mut epochs = current.clone();
+            for i in 0..epochs.len() {
+                if epochs[i].epoch_id == epoch_id {
+                    epochs[i].start_height = start_height;
+                    epochs[i - 1].end_height = start_height;
+                }
+            }
+            epochs
+        };
+        let epoch_21_start = current
+            .iter()
+            .find(|epoch| epoch.epoch_id == StacksEpochId::Epoch21)
+            .unwrap()
+            .start_height;
+
+        // unchanged epochs are fine, however far along the burnchain is
+        assert!(Config::check_epochs_against_burnchain_tip(&current, &current, u64::MAX).is_ok());
+
+        // an epoch which has not started yet may be moved, but not to the past
+        let tip = epoch_21_start - 2;
+        assert!(Config::check_epochs_against_burnchain_tip(
+            &moved(StacksEpochId::Epoch21, tip + 1),
+            &current,
+            tip
+        )
+        .is_ok());
+        assert!(Config::check_epochs_against_burnchain_tip(
+            &moved(StacksEpochId::Epoch21, tip),
+            &current,
+            tip
+        )
+        .is_err());
+
+        // an epoch which has already started may not be moved
+        let tip = epoch_21_start;
+        assert!(Config::check_epochs_against_burnchain_tip(
+            &moved(StacksEpochId::Epoch21, tip + 10),
+            &current,
+            tip
+        )
+        .is_err());
+    }
+
     #[test]
     fn should_load_legacy_mstx_balances_toml() {
         let config = ConfigFile::from_str(
@@ -173,6 +646,39 @@ mod tests {
             "ST2TFVBMRPS5SSNP98DQKQ5JNB2B6NZM91C4K3P7B"
         );
     }
+
+    #[test]
+    fn test_rpc_cookie_file_config() {
+        let config = Config::from_config_file(
+            ConfigFile::from_str(
+                r#"
+                [burnchain]
+                rpc_cookie_file = "/bitcoin/regtest/.cookie"
+                "#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            config.burnchain.rpc_cookie_file,
+            Some("/bitcoin/regtest/.cookie".to_string())
+        );
+        assert_eq!(config.burnchain.password, None);
+
+        let err = Config::from_config_file(
+            ConfigFile::from_str(
+                r#"
+                [burnchain]
+                username = "bitcoin"
+                password = "secret"
+                rpc_cookie_file = "/bitcoin/regtest/.cookie"
+                "#,
+            )
+            .unwrap(),
+        )
+        .unwrap_err();
+        assert!(err.contains("burnchain.password and burnchain.rpc_cookie_file"));
+    }
 }
 
 impl ConfigFile {
@@ -360,6 +866,7 @@ pub struct Config {
     pub connection_options: ConnectionOptions,
     pub miner: MinerConfig,
     pub estimation: FeeEstimationConfig,
+    pub mempool: MempoolConfig,
 }
 
 lazy_static! {
@@ -511,6 +1018,40 @@ impl Config {
         }
     }
 
+    /// Check `configured` epochs against the epochs the node's databases already use
+    /// (`current`), given the last known burnchain tip. An epoch's start height may only be
+    /// changed if neither its current nor its configured start height has been reached yet.
+    pub fn check_epochs_against_burnchain_tip(
+        configured: &[StacksEpoch],
+        current: &[StacksEpoch],
+        burn_tip_height: u64,
+    ) -> Result<(), String> {
+        for epoch in configured.iter() {
+            let current_start_height = current
+                .iter()
+                .find(|current_epoch| current_epoch.epoch_id == epoch.epoch_id)
+                .map(|current_epoch| current_epoch.start_height);
+            if current_start_height == Some(epoch.start_height) {
+                continue;
+            }
+            if epoch.start_height <= burn_tip_height {
+                return Err(format!(
+                    "Configured start height {} of epoch {} is not above the burnchain tip {}",
+                    epoch.start_height, epoch.epoch_id, burn_tip_height
+                ));
+            }
+            if let Some(current_start_height) = current_start_height {
+                if current_start_height <= burn_tip_height {
+                    return Err(format!(
+                        "Epoch {} already started at height {}, so it cannot be moved to {}",
+                        epoch.epoch_id, current_start_height, epoch.start_height
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn make_epochs(
         conf_epochs: &[StacksEpochConfigFile],
         burn_mode: &str,
@@ -525,6 +1066,7 @@ impl Config {
             BitcoinNetworkType::Regtest => Ok(stacks::core::STACKS_EPOCHS_REGTEST.to_vec()),
         }?;
         let mut matched_epochs = vec![];
+        let mut configured_end_heights = vec![];
         for configured_epoch in conf_epochs.iter() {
             let epoch_name = &configured_epoch.epoch_name;
             let epoch_id = if epoch_name == EPOCH_CONFIG_1_0_0 {
@@ -541,10 +1083,13 @@ impl Config {
                 Ok(StacksEpochId::Epoch23)
             } else if epoch_name == EPOCH_CONFIG_2_4_0 {
                 Ok(StacksEpochId::Epoch24)
+            } else if epoch_name == EPOCH_CONFIG_2_5_0 {
+                Ok(StacksEpochId::Epoch25)
             } else {
                 Err(format!("Unknown epoch name specified: {}", epoch_name))
             }?;
             matched_epochs.push((epoch_id, configured_epoch.start_height));
+            configured_end_heights.push((epoch_id, configured_epoch.end_height));
         }
 
         matched_epochs.sort_by_key(|(epoch_id, _)| *epoch_id);
@@ -557,6 +1102,31 @@ impl Config {
             );
         }
 
+        // explicit end heights must leave no gap or overlap with the next epoch
+        configured_end_heights.sort_by_key(|(epoch_id, _)| *epoch_id);
+        for (i, (epoch_id, end_height)) in configured_end_heights.iter().enumerate() {
+            let end_height = match end_height {
+                Some(end_height) => *end_height,
+                None => continue,
+            };
+            match matched_epochs.get(i + 1) {
+                Some((next_epoch_id, next_start_height)) => {
+                    if end_height != *next_start_height {
+                        return Err(format!(
+                            "Configured epochs must be contiguous: epoch {} ends at {}, but epoch {} starts at {}",
+                            epoch_id, end_height, next_epoch_id, next_start_height
+                        ));
+                    }
+                }
+                None => {
+                    return Err(format!(
+                        "The last configured epoch ({}) may not set an end height",
+                        epoch_id
+                    ));
+                }
+            }
+        }
+
         // epochs must be a prefix of [1.0, 2.0, 2.05, 2.1]
         let expected_list = [
             StacksEpochId::Epoch10,
@@ -566,6 +1136,7 @@ impl Config {
             StacksEpochId::Epoch22,
             StacksEpochId::Epoch23,
             StacksEpochId::Epoch24,
+            StacksEpochId::Epoch25,
         ];
         for (expected_epoch, configured_epoch) in expected_list
             .iter()
@@ -705,12 +1276,30 @@ impl Config {
                     chain_liveness_poll_time_secs: node
                         .chain_liveness_poll_time_secs
                         .unwrap_or(default_node_config.chain_liveness_poll_time_secs),
+                    event_observer_max_attempts: node
+                        .event_observer_max_attempts
+                        .unwrap_or(default_node_config.event_observer_max_attempts),
+                    cost_function_breakdown: node
+                        .cost_function_breakdown
+                        .unwrap_or(default_node_config.cost_function_breakdown),
+                    store_contract_source: node
+                        .store_contract_source
+                        .unwrap_or(default_node_config.store_contract_source),
                 };
                 (node_config, node.bootstrap_node, node.deny_nodes)
             }
             None => (default_node_config, None, None),
         };
 
+        // `[monitoring] prometheus_bind` takes precedence over `[node] prometheus_bind`
+        if let Some(prometheus_bind) = config_file
+            .monitoring
+            .as_ref()
+            .and_then(|monitoring| monitoring.prometheus_bind.clone())
+        {
+            node.prometheus_bind = Some(prometheus_bind);
+        }
+
         let default_burnchain_config = BurnchainConfig::default();
 
         let burnchain = match config_file.burnchain {
@@ -808,6 +1397,7 @@ impl Config {
                         .unwrap_or(default_burnchain_config.rpc_ssl),
                     username: burnchain.username,
                     password: burnchain.password,
+                    rpc_cookie_file: burnchain.rpc_cookie_file,
                     timeout: burnchain
                         .timeout
                         .unwrap_or(default_burnchain_config.timeout),
@@ -830,6 +1420,9 @@ impl Config {
                     max_rbf: burnchain
                         .max_rbf
                         .unwrap_or(default_burnchain_config.max_rbf),
+                    max_rbf_fee_cap: burnchain
+                        .max_rbf_fee_cap
+                        .unwrap_or(default_burnchain_config.max_rbf_fee_cap),
                     leader_key_tx_estimated_size: burnchain
                         .leader_key_tx_estimated_size
                         .unwrap_or(default_burnchain_config.leader_key_tx_estimated_size),
@@ -854,6 +1447,14 @@ impl Config {
                         .unwrap_or(default_burnchain_config.wallet_name.clone()),
                 };
 
+                if result.password.is_some() && result.rpc_cookie_file.is_some() {
+                    return Err(
+                        "burnchain.password and burnchain.rpc_cookie_file cannot both be set; \
+                         bitcoind RPC authenticates with one or the other"
+                            .into(),
+                    );
+                }
+
                 if let BitcoinNetworkType::Mainnet = result.get_bitcoin_network().1 {
                     // check that pox_2_activation hasn't been set in mainnet
                     if result.pox_2_activation.is_some()
@@ -909,10 +1510,56 @@ impl Config {
                 unprocessed_block_deadline_secs: miner
                     .unprocessed_block_deadline_secs
                     .unwrap_or(miner_default_config.unprocessed_block_deadline_secs),
+                rotate_vrf_keys: miner
+                    .rotate_vrf_keys
+                    .unwrap_or(miner_default_config.rotate_vrf_keys),
+                burn_fee_strategy: miner
+                    .burn_fee_strategy
+                    .clone()
+                    .map(BurnFeeStrategy::panic_parse)
+                    .unwrap_or(miner_default_config.burn_fee_strategy.clone()),
+                burn_fee_balance_pct: miner
+                    .burn_fee_balance_pct
+                    .unwrap_or(miner_default_config.burn_fee_balance_pct),
+                burn_fee_follow_window: miner
+                    .burn_fee_follow_window
+                    .unwrap_or(miner_default_config.burn_fee_follow_window),
+                burn_fee_follow_pct: miner
+                    .burn_fee_follow_pct
+                    .unwrap_or(miner_default_config.burn_fee_follow_pct),
+                min_burn_fee: miner
+                    .min_burn_fee
+                    .unwrap_or(miner_default_config.min_burn_fee),
+                burn_fee_win_rate_window: miner
+                    .burn_fee_win_rate_window
+                    .unwrap_or(miner_default_config.burn_fee_win_rate_window),
+                burn_fee_loss_increase_pct: miner
+                    .burn_fee_loss_increase_pct
+                    .unwrap_or(miner_default_config.burn_fee_loss_increase_pct),
+                burn_fee_win_decrease_pct: miner
+                    .burn_fee_win_decrease_pct
+                    .unwrap_or(miner_default_config.burn_fee_win_decrease_pct),
+                parent_block_override: miner.parent_block_override.as_ref().map(|id| {
+                    StacksBlockId::from_hex(id)
+                        .expect(&format!("FATAL: not a valid index block hash: {}", id))
+                }),
             },
             None => miner_default_config,
         };
 
+        if miner.burn_fee_balance_pct == 0 || miner.burn_fee_balance_pct > 100 {
+            return Err("Setting miner.burn_fee_balance_pct must be between 1 and 100".into());
+        }
+        if miner.burn_fee_follow_window == 0 {
+            return Err("Setting miner.burn_fee_follow_window must be at least 1".into());
+        }
+        if miner.burn_fee_win_rate_window == 0 {
+            return Err("Setting miner.burn_fee_win_rate_window must be at least 1".into());
+        }
+        if miner.burn_fee_win_decrease_pct > 100 {
+            return Err("Setting miner.burn_fee_win_decrease_pct must be at most 100".into());
+        }
+
         let supported_modes = vec![
             "mocknet", "helium", "neon", "argon", "krypton", "xenon", "mainnet",
         ];
@@ -1108,6 +1755,18 @@ impl Config {
                                 .clone()
                         },
                     ),
+                    dry_run_timeout_ms: opts
+                        .dry_run_timeout_ms
+                        .unwrap_or_else(|| HELIUM_DEFAULT_CONNECTION_OPTIONS.dry_run_timeout_ms),
+                    block_strike_threshold: opts.block_strike_threshold.unwrap_or_else(|| {
+                        HELIUM_DEFAULT_CONNECTION_OPTIONS.block_strike_threshold
+                    }),
+                    block_strike_window: opts
+                        .block_strike_window
+                        .unwrap_or_else(|| HELIUM_DEFAULT_CONNECTION_OPTIONS.block_strike_window),
+                    block_strike_ban_duration: opts.block_strike_ban_duration.unwrap_or_else(
+                        || HELIUM_DEFAULT_CONNECTION_OPTIONS.block_strike_ban_duration,
+                    ),
                     download_interval: opts.download_interval.unwrap_or_else(|| {
                         HELIUM_DEFAULT_CONNECTION_OPTIONS.download_interval.clone()
                     }),
@@ -1147,6 +1806,33 @@ impl Config {
             None => FeeEstimationConfig::default(),
         };
 
+        let mempool = match config_file.mempool {
+            Some(mempool) => {
+                if let Some(rbf_fee_increase_pct) = mempool.rbf_fee_increase_pct {
+                    if rbf_fee_increase_pct < MIN_RBF_FEE_INCREASE_PCT {
+                        return Err(format!(
+                            "mempool.rbf_fee_increase_pct must be at least {}, got {}",
+                            MIN_RBF_FEE_INCREASE_PCT, rbf_fee_increase_pct
+                        ));
+                    }
+                }
+                MempoolConfig {
+                    enable_rbf: mempool.enable_rbf.unwrap_or(false),
+                    rbf_fee_increase_pct: mempool
+                        .rbf_fee_increase_pct
+                        .unwrap_or(DEFAULT_RBF_FEE_INCREASE_PCT),
+                    min_fee_rate: mempool.min_fee_rate.unwrap_or(0),
+                    validate_sponsor_balance: mempool.validate_sponsor_balance.unwrap_or(false),
+                    mempool_timeout: mempool
+                        .mempool_timeout
+                        .unwrap_or(MEMPOOL_MAX_TRANSACTION_AGE),
+                    max_tx_count: mempool.max_tx_count,
+                    max_total_bytes: mempool.max_total_bytes,
+                }
+            }
+            None => MempoolConfig::default(),
+        };
+
         Ok(Config {
             node,
             burnchain,
@@ -1155,6 +1841,7 @@ impl Config {
             connection_options,
             estimation,
             miner,
+            mempool,
         })
     }
 
@@ -1183,6 +1870,30 @@ impl Config {
         path
     }
 
+    /// Returns the path `{get_chainstate_path()}/event_observers.sqlite`, and ensures its
+    /// directory exists.
+    pub fn get_event_observers_db_path(&self) -> PathBuf {
+        let mut path = self.get_chainstate_path();
+        fs::create_dir_all(&path).expect(&format!(
+            "Failed to create `chainstate` directory at {}",
+            path.to_string_lossy()
+        ));
+        path.push("event_observers.sqlite");
+        path
+    }
+
+    /// Returns the path `{get_chainstate_path()}/vrf_keys.sqlite`, and ensures its directory
+    /// exists.
+    pub fn get_vrf_keys_db_path(&self) -> PathBuf {
+        let mut path = self.get_chainstate_path();
+        fs::create_dir_all(&path).expect(&format!(
+            "Failed to create `chainstate` directory at {}",
+            path.to_string_lossy()
+        ));
+        path.push("vrf_keys.sqlite");
+        path
+    }
+
     pub fn get_chainstate_path_str(&self) -> String {
         self.get_chainstate_path()
             .to_str()
@@ -1315,6 +2026,7 @@ impl std::default::Default for Config {
             connection_options,
             estimation,
             miner: MinerConfig::default(),
+            mempool: MempoolConfig::default(),
         }
     }
 }
@@ -1333,6 +2045,10 @@ pub struct BurnchainConfig {
     pub rpc_ssl: bool,
     pub username: Option<String>,
     pub password: Option<String>,
+    /// Path to bitcoind's RPC cookie file, to authenticate with instead of `username` and
+    /// `password`.  It is re-read whenever bitcoind rejects the cookie, since bitcoind writes a
+    /// new one each time it starts.
+    pub rpc_cookie_file: Option<String>,
     pub timeout: u32,
     pub magic_bytes: MagicBytes,
     pub local_mining_public_key: Option<String>,
@@ -1340,6 +2056,9 @@ pub struct BurnchainConfig {
     pub poll_time_secs: u64,
     pub satoshis_per_byte: u64,
     pub max_rbf: u64,
+    /// Upper bound on the miner fees spent across all RBF attempts of a single block-commit,
+    /// expressed in percent of `burn_fee_cap`.  0 disables the bound.
+    pub max_rbf_fee_cap: u64,
     pub leader_key_tx_estimated_size: u64,
     pub block_commit_tx_estimated_size: u64,
     pub rbf_fee_increment: u64,
@@ -1368,6 +2087,7 @@ impl BurnchainConfig {
             rpc_ssl: false,
             username: None,
             password: None,
+            rpc_cookie_file: None,
             timeout: 300,
             magic_bytes: BLOCKSTACK_MAGIC_MAINNET.clone(),
             local_mining_public_key: None,
@@ -1375,6 +2095,7 @@ impl BurnchainConfig {
             poll_time_secs: 10, // TODO: this is a testnet specific value.
             satoshis_per_byte: DEFAULT_SATS_PER_VB,
             max_rbf: DEFAULT_MAX_RBF_RATE,
+            max_rbf_fee_cap: 0,
             leader_key_tx_estimated_size: LEADER_KEY_TX_ESTIM_SIZE,
             block_commit_tx_estimated_size: BLOCK_COMMIT_TX_ESTIM_SIZE,
             rbf_fee_increment: DEFAULT_RBF_FEE_RATE_INCREMENT,
@@ -1425,8 +2146,11 @@ impl BurnchainConfig {
 
 #[derive(Clone, Deserialize, Default, Debug)]
 pub struct StacksEpochConfigFile {
+    #[serde(alias = "epoch_id")]
     epoch_name: String,
     start_height: i64,
+    /// Defaults to the next epoch's start height. If given, it must equal it.
+    end_height: Option<i64>,
 }
 
 pub const EPOCH_CONFIG_1_0_0: &'static str = "1.0";
@@ -1436,6 +2160,7 @@ pub const EPOCH_CONFIG_2_1_0: &'static str = "2.1";
 pub const EPOCH_CONFIG_2_2_0: &'static str = "2.2";
 pub const EPOCH_CONFIG_2_3_0: &'static str = "2.3";
 pub const EPOCH_CONFIG_2_4_0: &'static str = "2.4";
+pub const EPOCH_CONFIG_2_5_0: &'static str = "2.5";
 
 #[derive(Clone, Deserialize, Default, Debug)]
 pub struct BurnchainConfigFile {
@@ -1449,6 +2174,7 @@ pub struct BurnchainConfigFile {
     pub rpc_ssl: Option<bool>,
     pub username: Option<String>,
     pub password: Option<String>,
+    pub rpc_cookie_file: Option<String>,
     pub timeout: Option<u32>,
     pub magic_bytes: Option<String>,
     pub local_mining_public_key: Option<String>,
@@ -1459,6 +2185,8 @@ pub struct BurnchainConfigFile {
     pub block_commit_tx_estimated_size: Option<u64>,
     pub rbf_fee_increment: Option<u64>,
     pub max_rbf: Option<u64>,
+    pub max_rbf_fee_cap: Option<u64>,
+    #[serde(alias = "epoch")]
     pub epochs: Option<Vec<StacksEpochConfigFile>>,
     pub pox_2_activation: Option<u32>,
     pub sunset_start: Option<u32>,
@@ -1499,17 +2227,32 @@ pub struct NodeConfig {
     /// At most, how often should the chain-liveness thread
     ///  wake up the chains-coordinator. Defaults to 300s (5 min).
     pub chain_liveness_poll_time_secs: u64,
+    /// How many times a payload is POSTed to an event observer before it is given up on, after
+    /// which nothing more is sent to that observer until the node restarts.
+    /// 0 retries forever.  Defaults to 20.
+    pub event_observer_max_attempts: u32,
+    /// Whether or not to record the cost charged by each Clarity cost function when processing
+    /// a transaction, and report it to event observers.  Defaults to false.
+    pub cost_function_breakdown: bool,
+    /// Whether or not to keep the source code of published contracts, so it can be served by
+    /// `/v2/contracts/source`.  Defaults to true.
+    pub store_contract_source: bool,
 }
 
 #[derive(Clone, Debug)]
 pub enum CostEstimatorName {
     NaivePessimistic,
+    ContractFunctionHistogram,
 }
 
 #[derive(Clone, Debug)]
 pub enum FeeEstimatorName {
     ScalarFeeRate,
+    WeightedMedianFeeRate,
     FuzzedWeightedMedianFeeRate,
+    Disabled,
+    /// Fee rate estimates are fetched from an external HTTP oracle.
+    Http,
 }
 
 #[derive(Clone, Debug)]
@@ -1539,6 +2282,8 @@ impl CostEstimatorName {
     fn panic_parse(s: String) -> CostEstimatorName {
         if &s.to_lowercase() == "naive_pessimistic" {
             CostEstimatorName::NaivePessimistic
+        } else if &s.to_lowercase() == "contract_function_histogram" {
+            CostEstimatorName::ContractFunctionHistogram
         } else {
             panic!(
                 "Bad cost estimator name supplied in configuration file: {}",
@@ -1552,8 +2297,12 @@ impl FeeEstimatorName {
     fn panic_parse(s: String) -> FeeEstimatorName {
         if &s.to_lowercase() == "scalar_fee_rate" {
             FeeEstimatorName::ScalarFeeRate
+        } else if &s.to_lowercase() == "weighted_median_fee_rate" {
+            FeeEstimatorName::WeightedMedianFeeRate
         } else if &s.to_lowercase() == "fuzzed_weighted_median_fee_rate" {
             FeeEstimatorName::FuzzedWeightedMedianFeeRate
+        } else if &s.to_lowercase() == "disabled" {
+            FeeEstimatorName::Disabled
         } else {
             panic!(
                 "Bad fee estimator name supplied in configuration file: {}",
@@ -1585,6 +2334,15 @@ pub struct FeeEstimationConfig {
     /// If using WeightedMedianFeeRateEstimator, the window size to use. See comments on
     /// WeightedMedianFeeRateEstimator.
     pub fee_rate_window_size: u64,
+    /// If set, the fee estimator's high, middle and low estimates are each raised to at least
+    /// the corresponding floor. See comments on FeeRateFloor.
+    pub fee_rate_floor: Option<FeeRateEstimate>,
+    /// If using HttpFeeRateEstimator, the base URL of the oracle. See comments on
+    /// HttpFeeRateEstimator.
+    pub http_url: Option<String>,
+    /// If using HttpFeeRateEstimator, how long to wait on the oracle before falling back to the
+    /// last estimate it gave.
+    pub http_timeout_ms: u64,
 }
 
 impl Default for FeeEstimationConfig {
@@ -1596,6 +2354,9 @@ impl Default for FeeEstimationConfig {
             log_error: false,
             fee_rate_fuzzer_fraction: 0.1f64,
             fee_rate_window_size: 5u64,
+            fee_rate_floor: None,
+            http_url: None,
+            http_timeout_ms: 5_000,
         }
     }
 }
@@ -1610,21 +2371,50 @@ impl From<FeeEstimationConfigFile> for FeeEstimationConfig {
                 log_error: false,
                 fee_rate_fuzzer_fraction: 0f64,
                 fee_rate_window_size: 0u64,
+                fee_rate_floor: None,
+                http_url: None,
+                http_timeout_ms: 0,
             };
         }
         let cost_estimator = f
             .cost_estimator
             .map(CostEstimatorName::panic_parse)
             .unwrap_or_default();
-        let fee_estimator = f
-            .fee_estimator
-            .map(FeeEstimatorName::panic_parse)
-            .unwrap_or_default();
+        let http = f.http.unwrap_or_default();
+        let fee_estimator = match f.backend.as_deref().map(str::to_lowercase).as_deref() {
+            Some("http") => {
+                if http.url.is_none() {
+                    panic!("The http fee estimation backend requires [fee_estimation.http] url");
+                }
+                FeeEstimatorName::Http
+            }
+            Some("local") | None => f
+                .fee_estimator
+                .map(FeeEstimatorName::panic_parse)
+                .unwrap_or_default(),
+            Some(_) => panic!(
+                "Bad fee estimation backend supplied in configuration file: {}",
+                f.backend.unwrap()
+            ),
+        };
         let cost_metric = f
             .cost_metric
             .map(CostMetricName::panic_parse)
             .unwrap_or_default();
         let log_error = f.log_error.unwrap_or(false);
+        // a bucket without a configured floor is not clamped
+        let fee_rate_floor = if f.fee_rate_floor_high.is_some()
+            || f.fee_rate_floor_middle.is_some()
+            || f.fee_rate_floor_low.is_some()
+        {
+            Some(FeeRateEstimate {
+                high: f.fee_rate_floor_high.unwrap_or(0f64),
+                middle: f.fee_rate_floor_middle.unwrap_or(0f64),
+                low: f.fee_rate_floor_low.unwrap_or(0f64),
+            })
+        } else {
+            None
+        };
         Self {
             cost_estimator: Some(cost_estimator),
             fee_estimator: Some(fee_estimator),
@@ -1632,6 +2422,9 @@ impl From<FeeEstimationConfigFile> for FeeEstimationConfig {
             log_error,
             fee_rate_fuzzer_fraction: f.fee_rate_fuzzer_fraction.unwrap_or(0.1f64),
             fee_rate_window_size: f.fee_rate_window_size.unwrap_or(5u64),
+            fee_rate_floor,
+            http_url: http.url,
+            http_timeout_ms: http.timeout_ms.unwrap_or(5_000),
         }
     }
 }
@@ -1644,6 +2437,10 @@ impl Config {
                     self.estimation
                         .make_pessimistic_cost_estimator(self.get_estimates_path()),
                 ),
+                CostEstimatorName::ContractFunctionHistogram => Box::new(
+                    self.estimation
+                        .make_contract_function_cost_estimator(self.get_estimates_path()),
+                ),
             };
 
         Some(cost_estimator)
@@ -1665,11 +2462,20 @@ impl Config {
             FeeEstimatorName::ScalarFeeRate => self
                 .estimation
                 .make_scalar_fee_estimator(self.get_estimates_path(), metric),
+            FeeEstimatorName::WeightedMedianFeeRate => self
+                .estimation
+                .make_weighted_median_fee_estimator(self.get_estimates_path(), metric),
             FeeEstimatorName::FuzzedWeightedMedianFeeRate => self
                 .estimation
                 .make_fuzzed_weighted_median_fee_estimator(self.get_estimates_path(), metric),
+            FeeEstimatorName::Disabled => Box::new(DisabledFeeEstimator),
+            FeeEstimatorName::Http => self.estimation.make_http_fee_estimator(),
         };
 
+        if let Some(floor) = self.estimation.fee_rate_floor.clone() {
+            return Some(Box::new(FeeRateFloor::new(fee_estimator, floor)));
+        }
+
         Some(fee_estimator)
     }
 }
@@ -1688,6 +2494,19 @@ impl FeeEstimationConfig {
         }
     }
 
+    pub fn make_contract_function_cost_estimator(
+        &self,
+        mut estimates_path: PathBuf,
+    ) -> ContractFunctionCostEstimator {
+        if let Some(CostEstimatorName::ContractFunctionHistogram) = self.cost_estimator.as_ref() {
+            estimates_path.push("cost_estimator_contract_function.sqlite");
+            ContractFunctionCostEstimator::open(&estimates_path)
+                .expect("Error opening cost estimator")
+        } else {
+            panic!("BUG: Expected to configure a contract function histogram cost estimator");
+        }
+    }
+
     pub fn make_scalar_fee_estimator<CM: CostMetric + 'static>(
         &self,
         mut estimates_path: PathBuf,
@@ -1696,7 +2515,7 @@ impl FeeEstimationConfig {
         if let Some(FeeEstimatorName::ScalarFeeRate) = self.fee_estimator.as_ref() {
             estimates_path.push("fee_estimator_scalar_rate.sqlite");
             Box::new(
-                ScalarFeeRateEstimator::open(&estimates_path, metric)
+                ScalarFeeRateEstimator::open(&estimates_path, metric, None)
                     .expect("Error opening fee estimator"),
             )
         } else {
@@ -1704,6 +2523,29 @@ impl FeeEstimationConfig {
         }
     }
 
+    // Creates a WeightedMedianFeeRateEstimator with the configured window size.
+    pub fn make_weighted_median_fee_estimator<CM: CostMetric + 'static>(
+        &self,
+        mut estimates_path: PathBuf,
+        metric: CM,
+    ) -> Box<dyn FeeEstimator> {
+        if let Some(FeeEstimatorName::WeightedMedianFeeRate) = self.fee_estimator.as_ref() {
+            estimates_path.push("fee_weighted_median.sqlite");
+            Box::new(
+                WeightedMedianFeeRateEstimator::open(
+                    &estimates_path,
+                    metric,
+                    self.fee_rate_window_size
+                        .try_into()
+                        .expect("Configured fee rate window size out of bounds."),
+                )
+                .expect("Error opening fee estimator"),
+            )
+        } else {
+            panic!("BUG: Expected to configure a weighted median fee estimator");
+        }
+    }
+
     // Creates a fuzzed WeightedMedianFeeRateEstimator with window_size 5. The fuzz
     // is uniform with bounds [+/- 0.5].
     pub fn make_fuzzed_weighted_median_fee_estimator<CM: CostMetric + 'static>(
@@ -1729,6 +2571,22 @@ impl FeeEstimationConfig {
             panic!("BUG: Expected to configure a weighted median fee estimator");
         }
     }
+
+    // Creates an HttpFeeRateEstimator which asks the configured oracle for its estimates.
+    pub fn make_http_fee_estimator(&self) -> Box<dyn FeeEstimator> {
+        if let Some(FeeEstimatorName::Http) = self.fee_estimator.as_ref() {
+            let url = self
+                .http_url
+                .as_ref()
+                .expect("BUG: Expected to configure a fee rate oracle URL");
+            Box::new(
+                HttpFeeRateEstimator::new(url, Duration::from_millis(self.http_timeout_ms))
+                    .expect("Error configuring fee estimator"),
+            )
+        } else {
+            panic!("BUG: Expected to configure an http fee estimator");
+        }
+    }
 }
 
 impl NodeConfig {
@@ -1777,6 +2635,9 @@ impl NodeConfig {
             require_affirmed_anchor_blocks: true,
             fault_injection_hide_blocks: false,
             chain_liveness_poll_time_secs: 300,
+            event_observer_max_attempts: 20,
+            cost_function_breakdown: false,
+            store_contract_source: true,
         }
     }
 
@@ -1890,6 +2751,36 @@ pub struct MinerConfig {
     pub nonce_cache_size: u64,
     pub candidate_retry_cache_size: u64,
     pub unprocessed_block_deadline_secs: u64,
+    /// Register a fresh VRF key, derived from the seed, at the start of every reward cycle
+    /// instead of deriving one from whichever burn block the miner happened to register at.
+    pub rotate_vrf_keys: bool,
+    /// How to choose the amount burnt by each block-commit.  Whatever the strategy, the miner
+    /// never burns more than `burnchain.burn_fee_cap`.
+    pub burn_fee_strategy: BurnFeeStrategy,
+    /// With `BurnFeeStrategy::BalancePercent`, burn this many percent of the miner's spendable
+    /// UTXO balance.
+    pub burn_fee_balance_pct: u64,
+    /// With `BurnFeeStrategy::FollowWinners`, how many of the most recent sortitions to inspect.
+    pub burn_fee_follow_window: u64,
+    /// With `BurnFeeStrategy::FollowWinners`, burn this many percent of the highest winning
+    /// block-commit in the window.
+    pub burn_fee_follow_pct: u64,
+    /// With `BurnFeeStrategy::WinRate`, the least a block-commit burns.
+    pub min_burn_fee: u64,
+    /// With `BurnFeeStrategy::WinRate`, how many of the most recent burn blocks to inspect.
+    pub burn_fee_win_rate_window: u64,
+    /// With `BurnFeeStrategy::WinRate`, raise the burn by this many percent after each sortition
+    /// the miner lost.
+    pub burn_fee_loss_increase_pct: u64,
+    /// With `BurnFeeStrategy::WinRate`, lower the burn by this many percent after each sortition
+    /// the miner won.
+    pub burn_fee_win_decrease_pct: u64,
+    /// Build Stacks blocks on this parent (an index block hash) instead of the canonical Stacks
+    /// tip, so that tests can deliberately create competing forks.  The miner falls back to the
+    /// canonical tip if the parent is not a processed block on the canonical burnchain fork.
+    /// This only seeds the override, which can be changed at runtime through
+    /// `RunLoop::get_miner_parent_override()`.
+    pub parent_block_override: Option<StacksBlockId>,
 }
 
 impl MinerConfig {
@@ -1906,10 +2797,185 @@ impl MinerConfig {
             nonce_cache_size: 10_000,
             candidate_retry_cache_size: 10_000,
             unprocessed_block_deadline_secs: 30,
+            rotate_vrf_keys: false,
+            burn_fee_strategy: BurnFeeStrategy::Fixed,
+            burn_fee_balance_pct: 10,
+            burn_fee_follow_window: 6,
+            burn_fee_follow_pct: 110,
+            min_burn_fee: 1,
+            burn_fee_win_rate_window: 12,
+            burn_fee_loss_increase_pct: 10,
+            burn_fee_win_decrease_pct: 5,
+            parent_block_override: None,
+        }
+    }
+
+    /// Choose how much a block-commit burns, given the most the miner may spend, the miner's
+    /// spendable balance (if known), the total burns of the recent winning block-commits, and
+    /// whether or not the miner won each of the recent sortitions it entered (oldest first).  The
+    /// result is always between 1 and `spend_cap`.  Strategies that lack the information they
+    /// need fall back to `spend_cap`.
+    pub fn choose_burn_fee(
+        &self,
+        spend_cap: u64,
+        balance: Option<u64>,
+        recent_winning_burns: &[u64],
+        recent_sortitions_won: &[bool],
+    ) -> u64 {
+        let burn_fee = match self.burn_fee_strategy {
+            BurnFeeStrategy::Fixed => spend_cap,
+            BurnFeeStrategy::BalancePercent => match balance {
+                Some(balance) => {
+                    ((balance as u128) * (self.burn_fee_balance_pct as u128) / 100) as u64
+                }
+                None => spend_cap,
+            },
+            BurnFeeStrategy::FollowWinners => match recent_winning_burns.iter().max() {
+                Some(highest) => {
+                    let follow = (*highest as u128) * (self.burn_fee_follow_pct as u128) / 100;
+                    u64::try_from(follow).unwrap_or(u64::MAX)
+                }
+                None => spend_cap,
+            },
+            BurnFeeStrategy::WinRate => WinRateBurnFeeController {
+                min_burn_fee: self.min_burn_fee,
+                max_burn_fee: spend_cap,
+                loss_increase_pct: self.burn_fee_loss_increase_pct,
+                win_decrease_pct: self.burn_fee_win_decrease_pct,
+            }
+            .burn_fee(recent_sortitions_won),
+        };
+        burn_fee.max(1).min(spend_cap)
+    }
+}
+
+/// The controller behind `BurnFeeStrategy::WinRate`.  Starting halfway between `min_burn_fee` and
+/// `max_burn_fee`, it replays the miner's recent sortitions from oldest to newest, raising the
+/// burn by `loss_increase_pct` percent after each loss and lowering it by `win_decrease_pct`
+/// percent after each win.  The burn is kept between `min_burn_fee` and `max_burn_fee` at every
+/// step.  Since the result only depends on the sortition history, it survives restarts.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WinRateBurnFeeController {
+    pub min_burn_fee: u64,
+    pub max_burn_fee: u64,
+    pub loss_increase_pct: u64,
+    pub win_decrease_pct: u64,
+}
+
+impl WinRateBurnFeeController {
+    /// Get the burn to use, given whether or not the miner won each of its recent sortitions
+    /// (oldest first).
+    pub fn burn_fee(&self, sortitions_won: &[bool]) -> u64 {
+        let max_burn_fee = self.max_burn_fee;
+        let min_burn_fee = self.min_burn_fee.min(max_burn_fee);
+        let mut burn_fee = min_burn_fee + (max_burn_fee - min_burn_fee) / 2;
+        for won in sortitions_won.iter() {
+            let next = if *won {
+                let pct = 100u128.saturating_sub(self.win_decrease_pct as u128);
+                (burn_fee as u128) * pct / 100
+            } else {
+                let pct = 100u128 + (self.loss_increase_pct as u128);
+                // always raise the burn after a loss, even if it's too small for the percentage
+                // to register
+                ((burn_fee as u128) * pct / 100).max((burn_fee as u128) + 1)
+            };
+            burn_fee = u64::try_from(next)
+                .unwrap_or(u64::MAX)
+                .max(min_burn_fee)
+                .min(max_burn_fee);
         }
+        burn_fee
     }
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub enum BurnFeeStrategy {
+    /// Always burn `burnchain.burn_fee_cap`.
+    Fixed,
+    /// Burn a percentage of the miner's spendable UTXO balance.
+    BalancePercent,
+    /// Burn a percentage of the highest recent winning block-commit.
+    FollowWinners,
+    /// Raise or lower the burn according to how many of the recent sortitions the miner won.
+    WinRate,
+}
+
+impl BurnFeeStrategy {
+    fn panic_parse(s: String) -> BurnFeeStrategy {
+        if &s.to_lowercase() == "fixed" {
+            BurnFeeStrategy::Fixed
+        } else if &s.to_lowercase() == "balance_percent" {
+            BurnFeeStrategy::BalancePercent
+        } else if &s.to_lowercase() == "follow_winners" {
+            BurnFeeStrategy::FollowWinners
+        } else if &s.to_lowercase() == "win_rate" {
+            BurnFeeStrategy::WinRate
+        } else {
+            panic!(
+                "Bad burn fee strategy name supplied in configuration file: {}",
+                s
+            );
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MempoolConfig {
+    /// Allow a transaction to replace a mempool transaction with the same origin or sponsor
+    /// nonce if it pays a sufficiently higher fee.
+    pub enable_rbf: bool,
+    /// How many percent more than the transaction it replaces a replace-by-fee transaction must
+    /// pay.  Must be at least `MIN_RBF_FEE_INCREASE_PCT`.
+    pub rbf_fee_increase_pct: u64,
+    /// Reject transactions that pay less than this many microSTX per byte.  Coinbases are
+    /// exempt.  0 disables the check.
+    pub min_fee_rate: u64,
+    /// Reject sponsored transactions whose sponsor cannot pay the fee at the chain tip they
+    /// are submitted against.  Off by default, since it reads the chainstate on every
+    /// submission.
+    pub validate_sponsor_balance: bool,
+    /// Evict transactions once they have been in the mempool for this many burnchain blocks.
+    pub mempool_timeout: u64,
+    /// If set, evict the lowest-fee-rate transactions as soon as accepting a transaction leaves
+    /// more than this many transactions in the mempool.
+    pub max_tx_count: Option<u64>,
+    /// If set, evict the lowest-fee-rate transactions as soon as accepting a transaction leaves
+    /// the mempool's transactions taking up more than this many bytes.
+    pub max_total_bytes: Option<u64>,
+}
+
+impl Default for MempoolConfig {
+    fn default() -> MempoolConfig {
+        MempoolConfig {
+            enable_rbf: false,
+            rbf_fee_increase_pct: DEFAULT_RBF_FEE_INCREASE_PCT,
+            min_fee_rate: 0,
+            validate_sponsor_balance: false,
+            mempool_timeout: MEMPOOL_MAX_TRANSACTION_AGE,
+            max_tx_count: None,
+            max_total_bytes: None,
+        }
+    }
+}
+
+#[derive(Clone, Default, Deserialize, Debug)]
+pub struct MempoolConfigFile {
+    pub enable_rbf: Option<bool>,
+    pub rbf_fee_increase_pct: Option<u64>,
+    pub min_fee_rate: Option<u64>,
+    pub validate_sponsor_balance: Option<bool>,
+    pub mempool_timeout: Option<u64>,
+    pub max_tx_count: Option<u64>,
+    pub max_total_bytes: Option<u64>,
+}
+
+#[derive(Clone, Default, Deserialize, Debug)]
+pub struct MonitoringConfigFile {
+    /// Address on which to serve Prometheus metrics at `/metrics`.  Only takes effect if the
+    /// node is built with the `monitoring_prom` feature.
+    pub prometheus_bind: Option<String>,
+}
+
 #[derive(Clone, Default, Deserialize, Debug)]
 pub struct ConnectionOptionsFile {
     pub inbox_maxlen: Option<usize>,
@@ -1942,6 +3008,10 @@ pub struct ConnectionOptionsFile {
     pub read_only_call_limit_read_count: Option<u64>,
     pub read_only_call_limit_runtime: Option<u64>,
     pub maximum_call_argument_size: Option<u32>,
+    pub dry_run_timeout_ms: Option<u64>,
+    pub block_strike_threshold: Option<u64>,
+    pub block_strike_window: Option<u64>,
+    pub block_strike_ban_duration: Option<u64>,
     pub download_interval: Option<u64>,
     pub inv_sync_interval: Option<u64>,
     pub full_inv_sync_interval: Option<u64>,
@@ -1983,6 +3053,16 @@ pub struct NodeConfigFile {
     /// At most, how often should the chain-liveness thread
     ///  wake up the chains-coordinator. Defaults to 300s (5 min).
     pub chain_liveness_poll_time_secs: Option<u64>,
+    /// How many times a payload is POSTed to an event observer before it is given up on, after
+    /// which nothing more is sent to that observer until the node restarts.
+    /// 0 retries forever.  Defaults to 20.
+    pub event_observer_max_attempts: Option<u32>,
+    /// Whether or not to record the cost charged by each Clarity cost function when processing
+    /// a transaction, and report it to event observers.  Defaults to false.
+    pub cost_function_breakdown: Option<bool>,
+    /// Whether or not to keep the source code of published contracts, so it can be served by
+    /// `/v2/contracts/source`.  Defaults to true.
+    pub store_contract_source: Option<bool>,
 }
 
 #[derive(Clone, Deserialize, Debug)]
@@ -1994,6 +3074,19 @@ pub struct FeeEstimationConfigFile {
     pub log_error: Option<bool>,
     pub fee_rate_fuzzer_fraction: Option<f64>,
     pub fee_rate_window_size: Option<u64>,
+    pub fee_rate_floor_high: Option<f64>,
+    pub fee_rate_floor_middle: Option<f64>,
+    pub fee_rate_floor_low: Option<f64>,
+    /// Where fee rate estimates come from: "local" (the default) uses `fee_estimator`, and
+    /// "http" asks the oracle configured in `[fee_estimation.http]`.
+    pub backend: Option<String>,
+    pub http: Option<HttpFeeEstimationConfigFile>,
+}
+
+#[derive(Clone, Deserialize, Default, Debug)]
+pub struct HttpFeeEstimationConfigFile {
+    pub url: Option<String>,
+    pub timeout_ms: Option<u64>,
 }
 
 impl Default for FeeEstimationConfigFile {
@@ -2006,6 +3099,11 @@ impl Default for FeeEstimationConfigFile {
             log_error: None,
             fee_rate_fuzzer_fraction: None,
             fee_rate_window_size: None,
+            fee_rate_floor_high: None,
+            fee_rate_floor_middle: None,
+            fee_rate_floor_low: None,
+            backend: None,
+            http: None,
         }
     }
 }
@@ -2022,6 +3120,16 @@ pub struct MinerConfigFile {
     pub nonce_cache_size: Option<u64>,
     pub candidate_retry_cache_size: Option<u64>,
     pub unprocessed_block_deadline_secs: Option<u64>,
+    pub rotate_vrf_keys: Option<bool>,
+    pub burn_fee_strategy: Option<String>,
+    pub burn_fee_balance_pct: Option<u64>,
+    pub burn_fee_follow_window: Option<u64>,
+    pub burn_fee_follow_pct: Option<u64>,
+    pub min_burn_fee: Option<u64>,
+    pub burn_fee_win_rate_window: Option<u64>,
+    pub burn_fee_loss_increase_pct: Option<u64>,
+    pub burn_fee_win_decrease_pct: Option<u64>,
+    pub parent_block_override: Option<String>,
 }
 
 #[derive(Clone, Deserialize, Default, Debug)]
@@ -2039,6 +3147,8 @@ pub struct EventObserverConfig {
 #[derive(Clone, Debug)]
 pub enum EventKeyType {
     SmartContractEvent((QualifiedContractIdentifier, String)),
+    /// Every print event emitted by the given contract
+    ContractEvent(QualifiedContractIdentifier),
     AssetEvent(AssetIdentifier),
     STXEvent,
     MemPoolTransactions,
@@ -2047,6 +3157,7 @@ pub enum EventKeyType {
     BurnchainBlocks,
     MinedBlocks,
     MinedMicroblocks,
+    EpochTransitions,
 }
 
 impl EventKeyType {
@@ -2071,9 +3182,18 @@ impl EventKeyType {
             return Some(EventKeyType::Microblocks);
         }
 
+        if raw_key == "epoch_transitions" {
+            return Some(EventKeyType::EpochTransitions);
+        }
+
         let comps: Vec<_> = raw_key.split("::").collect();
         if comps.len() == 1 {
             let split: Vec<_> = comps[0].split(".").collect();
+            if split.len() == 2 {
+                return QualifiedContractIdentifier::parse(comps[0])
+                    .ok()
+                    .map(EventKeyType::ContractEvent);
+            }
             if split.len() != 3 {
                 return None;
             }
@@ -1,11 +1,18 @@
+use std::cmp;
 use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{sync_channel, SyncSender};
+use std::sync::{Arc, Mutex};
+use std::thread;
 use std::thread::sleep;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use async_h1::client;
 use async_std::net::TcpStream;
 use http_types::{Method, Request, Url};
+use rusqlite::{params, Connection, OpenFlags, NO_PARAMS};
 use serde_json::json;
 
 use stacks::burnchains::{PoxConstants, Txid};
@@ -21,9 +28,14 @@ use stacks::chainstate::stacks::{
 use stacks::chainstate::stacks::{StacksBlock, StacksMicroblock};
 use stacks::codec::StacksMessageCodec;
 use stacks::core::mempool::{MemPoolDropReason, MemPoolEventDispatcher};
+use stacks::core::StacksEpochId;
+use stacks::monitoring;
 use stacks::net::atlas::{Attachment, AttachmentInstance};
 use stacks::types::chainstate::{BlockHeaderHash, BurnchainHeaderHash, StacksBlockId};
-use stacks::util::hash::bytes_to_hex;
+use stacks::util::hash::{bytes_to_hex, Sha256Sum};
+use stacks::util_lib::db::{
+    sqlite_open, table_exists, tx_begin_immediate_sqlite, u64_to_sql, Error as db_error,
+};
 use stacks::vm::analysis::contract_interface_builder::build_contract_interface;
 use stacks::vm::costs::ExecutionCost;
 use stacks::vm::events::{FTEventType, NFTEventType, STXEventType};
@@ -39,6 +51,29 @@ use stacks::chainstate::stacks::TransactionPayload;
 #[derive(Debug, Clone)]
 struct EventObserver {
     endpoint: String,
+    /// If set, every payload is written to this database before it is sent, and removed once
+    /// the observer acknowledges it, so that delivery survives a node restart. The connection
+    /// is opened once, when the observer is registered, and shared with its delivery thread.
+    pending_db: Option<Arc<Mutex<Connection>>>,
+    /// How many times a payload is POSTed before it is given up on. 0 retries forever.
+    max_attempts: u32,
+    /// If set, payloads are handed to this observer's delivery thread instead of being sent by
+    /// the caller, so that a slow observer does not stall the node until its queue fills up.
+    delivery_queue: Option<Arc<Mutex<SyncSender<QueuedPayload>>>>,
+    /// Set once a payload is given up on. No later payload is sent to this observer after that,
+    /// so that it never sees a gap in the events it receives.
+    halted: Arc<AtomicBool>,
+}
+
+/// A payload waiting in an observer's delivery queue
+#[derive(Debug)]
+struct QueuedPayload {
+    path: String,
+    body: Vec<u8>,
+    /// The payload's row in the pending payloads database, if it was persisted
+    pending_id: Option<i64>,
+    /// When the payload was queued, for measuring delivery latency
+    queued_at: Instant,
 }
 
 struct ReceiptPayloadInfo<'a> {
@@ -58,12 +93,34 @@ const STATUS_RESP_POST_CONDITION: &str = "abort_by_post_condition";
 pub const PATH_MICROBLOCK_SUBMIT: &str = "new_microblocks";
 pub const PATH_MEMPOOL_TX_SUBMIT: &str = "new_mempool_tx";
 pub const PATH_MEMPOOL_TX_DROP: &str = "drop_mempool_tx";
+pub const PATH_MEMPOOL_TX_EVICT: &str = "mempool_evicted";
 pub const PATH_MINED_BLOCK: &str = "mined_block";
 pub const PATH_MINED_MICROBLOCK: &str = "mined_microblock";
 pub const PATH_BURN_BLOCK_SUBMIT: &str = "new_burn_block";
+pub const PATH_EPOCH_TRANSITION: &str = "epoch_transition";
+pub const PATH_BURN_BLOCK_REORG: &str = "burn_block_reorg";
 pub const PATH_BLOCK_PROCESSED: &str = "new_block";
 pub const PATH_ATTACHMENT_PROCESSED: &str = "attachments/new";
 
+/// Delay before the first retry of a failed POST; doubled after every further failure.
+const INITIAL_BACKOFF_MS: u64 = 1_000;
+const MAX_BACKOFF_MS: u64 = 30_000;
+/// How long to wait for an observer to answer a POST before counting it as failed.
+const REQUEST_TIMEOUT_MS: u64 = 60_000;
+/// How many payloads may wait in an observer's delivery queue before sending blocks the caller.
+const DELIVERY_QUEUE_CAPACITY: usize = 1_000;
+
+const CREATE_PENDING_PAYLOADS_TABLE: &'static str = "
+CREATE TABLE pending_payloads (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    endpoint TEXT NOT NULL,
+    path TEXT NOT NULL,
+    block_height INTEGER NOT NULL,
+    payload_hash TEXT NOT NULL,
+    payload BLOB NOT NULL,
+    UNIQUE (endpoint, block_height, payload_hash)
+)";
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MinedBlockEvent {
     pub target_burn_height: u64,
@@ -94,6 +151,187 @@ impl EventObserver {
             }
         };
 
+        let block_height = payload_block_height(payload);
+        let pending_id = match self.with_pending_db(|conn| {
+            insert_pending_payload(conn, &self.endpoint, path, block_height, &body)
+        }) {
+            Some(Ok(id)) => Some(id),
+            Some(Err(err)) => {
+                error!("Event dispatcher: failed to persist payload  - {:?}", err);
+                None
+            }
+            None => None,
+        };
+
+        self.enqueue(QueuedPayload {
+            path: path.to_string(),
+            body,
+            pending_id,
+            queued_at: Instant::now(),
+        });
+    }
+
+    /// Hand `payload` to the delivery thread, or deliver it right away if there is none.
+    /// If the delivery thread has died, a new one is started to take over its queue, so that
+    /// this observer's payloads are never sent on the caller's thread once it had one.
+    fn enqueue(&self, payload: QueuedPayload) {
+        let queue = match self.delivery_queue.as_ref() {
+            Some(queue) => queue,
+            None => return self.deliver(payload),
+        };
+        let mut sender = queue
+            .lock()
+            .expect("FATAL: event observer delivery queue lock poisoned");
+        let payload = match sender.send(payload) {
+            Ok(()) => return,
+            Err(err) => err.0,
+        };
+
+        warn!(
+            "Event dispatcher: delivery thread for {} is gone, restarting it",
+            &self.endpoint
+        );
+        let payload = match self.start_delivery_thread() {
+            Ok(new_sender) => {
+                *sender = new_sender;
+                match sender.send(payload) {
+                    Ok(()) => return,
+                    Err(err) => err.0,
+                }
+            }
+            Err(err) => {
+                error!(
+                    "Event dispatcher: failed to restart delivery thread for {}  - {:?}",
+                    &self.endpoint, err
+                );
+                payload
+            }
+        };
+        drop(sender);
+        self.deliver(payload);
+    }
+
+    /// Send a queued payload, and forget its persisted copy once the observer acknowledges it.
+    /// A payload that is given up on stays in the pending payloads database, so it is replayed
+    /// when the node restarts. So does every later payload: once one is given up on, nothing
+    /// more is sent to this observer, so that it never receives events out of order.
+    fn deliver(&self, payload: QueuedPayload) {
+        if self.halted.load(Ordering::SeqCst) {
+            debug!(
+                "Event dispatcher: not sending payload to halted observer";
+                "endpoint" => &self.endpoint, "path" => &payload.path
+            );
+            return;
+        }
+        if !self.send_body(&payload.body, &payload.path) {
+            self.halted.store(true, Ordering::SeqCst);
+            error!(
+                "Event dispatcher: halting delivery to {} until the node restarts",
+                &self.endpoint
+            );
+            return;
+        }
+        monitoring::observe_event_observer_delivery_latency(
+            &self.endpoint,
+            payload.queued_at.elapsed(),
+        );
+        if let Some(id) = payload.pending_id {
+            self.delete_pending_payload(id);
+        }
+    }
+
+    /// Start a thread that drains a new delivery queue for this observer, in order, and return
+    /// the sending half of that queue. The queue is bounded, so an observer that falls behind
+    /// holds up the caller instead of buffering payloads without limit.
+    fn start_delivery_thread(&self) -> std::io::Result<SyncSender<QueuedPayload>> {
+        let (queue, queued) = sync_channel::<QueuedPayload>(DELIVERY_QUEUE_CAPACITY);
+        let observer = EventObserver {
+            delivery_queue: None,
+            ..self.clone()
+        };
+        thread::Builder::new()
+            .name(format!("event-observer:{}", &self.endpoint))
+            .spawn(move || {
+                while let Ok(payload) = queued.recv() {
+                    observer.deliver(payload);
+                }
+            })?;
+        Ok(queue)
+    }
+
+    /// Start the thread that drains this observer's delivery queue, and route all of this
+    /// observer's payloads through it. Every observer gets its own thread, so a slow or
+    /// unreachable observer only ever delays its own payloads.
+    fn spawn_delivery_thread(&mut self) {
+        match self.start_delivery_thread() {
+            Ok(queue) => self.delivery_queue = Some(Arc::new(Mutex::new(queue))),
+            Err(err) => error!(
+                "Event dispatcher: failed to start delivery thread for {}  - {:?}",
+                &self.endpoint, err
+            ),
+        }
+    }
+
+    /// Re-send every payload that was persisted for this observer but never acknowledged,
+    /// e.g. because the node shut down while the observer was unreachable. Payloads are sent
+    /// in the order in which they were originally generated.
+    pub fn send_pending_payloads(&self) {
+        let pending = match self.with_pending_db(|conn| get_pending_payloads(conn, &self.endpoint))
+        {
+            Some(Ok(pending)) => pending,
+            None => return,
+            Some(Err(err)) => {
+                error!(
+                    "Event dispatcher: failed to load pending payloads  - {:?}",
+                    err
+                );
+                return;
+            }
+        };
+
+        if !pending.is_empty() {
+            info!(
+                "Event dispatcher: replaying {} pending payloads to {}",
+                pending.len(),
+                &self.endpoint
+            );
+        }
+        for (id, path, body) in pending.into_iter() {
+            self.enqueue(QueuedPayload {
+                path,
+                body,
+                pending_id: Some(id),
+                queued_at: Instant::now(),
+            });
+        }
+    }
+
+    fn delete_pending_payload(&self, id: i64) {
+        if let Some(Err(err)) = self.with_pending_db(|conn| delete_pending_payload(conn, id)) {
+            error!(
+                "Event dispatcher: failed to delete delivered payload  - {:?}",
+                err
+            );
+        }
+    }
+
+    /// Run `f` against this observer's pending payloads database, if it has one.
+    fn with_pending_db<F, R>(&self, f: F) -> Option<Result<R, db_error>>
+    where
+        F: FnOnce(&Connection) -> Result<R, db_error>,
+    {
+        let conn = self
+            .pending_db
+            .as_ref()?
+            .lock()
+            .expect("FATAL: event observer pending payloads database lock poisoned");
+        Some(f(&conn))
+    }
+
+    /// POST `body` to `path`, retrying with exponential backoff until the observer acknowledges
+    /// it with a success status, or until `max_attempts` POSTs have failed.
+    /// Returns true if the observer acknowledged the payload.
+    fn send_body(&self, body: &[u8], path: &str) -> bool {
         let url = {
             let joined_components = match path.starts_with("/") {
                 true => format!("{}{}", &self.endpoint, path),
@@ -106,28 +344,43 @@ impl EventObserver {
             ))
         };
 
-        let backoff = Duration::from_millis((1.0 * 1_000.0) as u64);
+        let mut backoff = Duration::from_millis(INITIAL_BACKOFF_MS);
+        let mut attempts: u32 = 0;
 
         loop {
-            let body = body.clone();
+            let body = body.to_vec();
             let mut req = Request::new(Method::Post, url.clone());
             req.append_header("Content-Type", "application/json");
             req.set_body(body);
 
             let response = async_std::task::block_on(async {
-                let stream = match TcpStream::connect(self.endpoint.clone()).await {
-                    Ok(stream) => stream,
-                    Err(err) => {
-                        warn!("Event dispatcher: connection failed  - {:?}", err);
-                        return None;
+                let request = async {
+                    let stream = match TcpStream::connect(self.endpoint.clone()).await {
+                        Ok(stream) => stream,
+                        Err(err) => {
+                            warn!("Event dispatcher: connection failed  - {:?}", err);
+                            return None;
+                        }
+                    };
+
+                    match client::connect(stream, req).await {
+                        Ok(response) => Some(response),
+                        Err(err) => {
+                            warn!("Event dispatcher: rpc invocation failed  - {:?}", err);
+                            return None;
+                        }
                     }
                 };
 
-                match client::connect(stream, req).await {
-                    Ok(response) => Some(response),
-                    Err(err) => {
-                        warn!("Event dispatcher: rpc invocation failed  - {:?}", err);
-                        return None;
+                // an observer that accepts the connection but never answers must not hold up
+                // its queue forever
+                match async_std::future::timeout(Duration::from_millis(REQUEST_TIMEOUT_MS), request)
+                    .await
+                {
+                    Ok(response) => response,
+                    Err(_) => {
+                        warn!("Event dispatcher: rpc invocation timed out"; "url" => %url);
+                        None
                     }
                 }
             });
@@ -137,14 +390,23 @@ impl EventObserver {
                     debug!(
                         "Event dispatcher: Successful POST"; "url" => %url
                     );
-                    break;
+                    return true;
                 } else {
                     error!(
                         "Event dispatcher: Failed POST"; "url" => %url, "err" => ?response
                     );
                 }
             }
+            attempts = attempts.saturating_add(1);
+            if self.max_attempts > 0 && attempts >= self.max_attempts {
+                error!(
+                    "Event dispatcher: giving up on POST after {} attempts", attempts;
+                    "url" => %url
+                );
+                return false;
+            }
             sleep(backoff);
+            backoff = cmp::min(backoff * 2, Duration::from_millis(MAX_BACKOFF_MS));
         }
     }
 
@@ -190,6 +452,39 @@ impl EventObserver {
         })
     }
 
+    fn make_burn_block_reorg_payload(
+        old_canonical_tip: &BurnchainHeaderHash,
+        new_canonical_tip: &BurnchainHeaderHash,
+        reorg_depth: u64,
+        orphaned_stacks_blocks: &[BlockHeaderHash],
+    ) -> serde_json::Value {
+        let stacks_block_hashes = orphaned_stacks_blocks
+            .iter()
+            .map(|block_hash| json!(format!("0x{}", block_hash)))
+            .collect();
+
+        json!({
+            "old_canonical_tip": format!("0x{}", old_canonical_tip),
+            "new_canonical_tip": format!("0x{}", new_canonical_tip),
+            "reorg_depth": reorg_depth,
+            "stacks_block_hashes": serde_json::Value::Array(stacks_block_hashes),
+        })
+    }
+
+    fn make_epoch_transition_payload(
+        block_id: &StacksBlockId,
+        burn_block_height: u64,
+        old_epoch: StacksEpochId,
+        new_epoch: StacksEpochId,
+    ) -> serde_json::Value {
+        json!({
+            "index_block_hash": format!("0x{}", block_id),
+            "burn_block_height": burn_block_height,
+            "old_epoch": old_epoch.to_string(),
+            "new_epoch": new_epoch.to_string(),
+        })
+    }
+
     /// Returns tuple of (txid, success, raw_result, raw_tx, contract_interface_json)
     fn generate_payload_info_for_receipt(receipt: &StacksTransactionReceipt) -> ReceiptPayloadInfo {
         let tx = &receipt.transaction;
@@ -250,6 +545,39 @@ impl EventObserver {
     }
 
     /// Returns json payload to send for new block or microblock event
+    /// Did this transaction deploy or call one of `contracts`, or did one of them emit an event
+    /// while it ran?
+    fn receipt_involves_contracts(
+        receipt: &StacksTransactionReceipt,
+        contracts: &HashSet<QualifiedContractIdentifier>,
+    ) -> bool {
+        let emitted_event = receipt.events.iter().any(|event| match event {
+            StacksTransactionEvent::SmartContractEvent(event_data) => {
+                contracts.contains(&event_data.key.0)
+            }
+            _ => false,
+        });
+        if emitted_event {
+            return true;
+        }
+
+        match &receipt.transaction {
+            TransactionOrigin::Stacks(tx) => match &tx.payload {
+                TransactionPayload::ContractCall(contract_call) => {
+                    contracts.contains(&contract_call.to_clarity_contract_id())
+                }
+                TransactionPayload::SmartContract(smart_contract, _) => {
+                    contracts.contains(&QualifiedContractIdentifier::new(
+                        tx.origin_address().into(),
+                        smart_contract.name.clone(),
+                    ))
+                }
+                _ => false,
+            },
+            TransactionOrigin::Burn(_) => false,
+        }
+    }
+
     fn make_new_block_txs_payload(
         receipt: &StacksTransactionReceipt,
         tx_index: u32,
@@ -265,6 +593,8 @@ impl EventObserver {
             "contract_abi": receipt_payload_info.contract_interface_json,
             "burnchain_op": receipt_payload_info.burnchain_op_json,
             "execution_cost": receipt.execution_cost,
+            "cost_breakdown": receipt.cost_breakdown,
+            "cost_function_breakdown": receipt.cost_function_breakdown,
             "microblock_sequence": receipt.microblock_header.as_ref().map(|x| x.sequence),
             "microblock_hash": receipt.microblock_header.as_ref().map(|x| format!("0x{}", x.block_hash())),
             "microblock_parent_hash": receipt.microblock_header.as_ref().map(|x| format!("0x{}", x.prev_block)),
@@ -328,6 +658,10 @@ impl EventObserver {
         self.send_payload(payload, PATH_MEMPOOL_TX_DROP);
     }
 
+    fn send_evicted_mempool_tx(&self, payload: &serde_json::Value) {
+        self.send_payload(payload, PATH_MEMPOOL_TX_EVICT);
+    }
+
     fn send_mined_block(&self, payload: &serde_json::Value) {
         self.send_payload(payload, PATH_MINED_BLOCK);
     }
@@ -340,12 +674,21 @@ impl EventObserver {
         self.send_payload(payload, PATH_BURN_BLOCK_SUBMIT);
     }
 
+    fn send_epoch_transition(&self, payload: &serde_json::Value) {
+        self.send_payload(payload, PATH_EPOCH_TRANSITION);
+    }
+
+    fn send_burn_block_reorg(&self, payload: &serde_json::Value) {
+        self.send_payload(payload, PATH_BURN_BLOCK_REORG);
+    }
+
     fn make_new_block_processed_payload(
         &self,
         filtered_events: Vec<(usize, &(bool, Txid, &StacksTransactionEvent))>,
         block: &StacksBlock,
         metadata: &StacksHeaderInfo,
         receipts: &[StacksTransactionReceipt],
+        contracts_filter: Option<&HashSet<QualifiedContractIdentifier>>,
         parent_index_hash: &StacksBlockId,
         winner_txid: &Txid,
         mature_rewards: &serde_json::Value,
@@ -367,8 +710,13 @@ impl EventObserver {
         let mut tx_index: u32 = 0;
         let mut serialized_txs = vec![];
         for receipt in receipts.iter() {
-            let payload = EventObserver::make_new_block_txs_payload(receipt, tx_index);
-            serialized_txs.push(payload);
+            let related = contracts_filter
+                .map(|contracts| EventObserver::receipt_involves_contracts(receipt, contracts))
+                .unwrap_or(true);
+            if related {
+                let payload = EventObserver::make_new_block_txs_payload(receipt, tx_index);
+                serialized_txs.push(payload);
+            }
             tx_index += 1;
         }
 
@@ -399,10 +747,87 @@ impl EventObserver {
     }
 }
 
+/// Open the pending payloads database at `db_path`, creating it if necessary.
+fn open_pending_payloads_db(db_path: &Path) -> Result<Connection, db_error> {
+    let mut conn = sqlite_open(
+        db_path,
+        OpenFlags::SQLITE_OPEN_CREATE | OpenFlags::SQLITE_OPEN_READ_WRITE,
+        false,
+    )?;
+
+    // several threads dispatch events, so check for the table inside a transaction
+    let tx = tx_begin_immediate_sqlite(&mut conn)?;
+    if !table_exists(&tx, "pending_payloads")? {
+        tx.execute(CREATE_PENDING_PAYLOADS_TABLE, NO_PARAMS)?;
+    }
+    tx.commit()?;
+    Ok(conn)
+}
+
+/// Record a payload that is about to be sent, and return its id. Recording the same payload
+/// twice for the same endpoint and block height returns the existing id.
+fn insert_pending_payload(
+    conn: &Connection,
+    endpoint: &str,
+    path: &str,
+    block_height: u64,
+    body: &[u8],
+) -> Result<i64, db_error> {
+    let payload_hash = Sha256Sum::from_data(body).to_hex();
+    let block_height = u64_to_sql(block_height)?;
+    conn.execute(
+        "INSERT OR IGNORE INTO pending_payloads
+         (endpoint, path, block_height, payload_hash, payload) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![endpoint, path, block_height, payload_hash, body],
+    )?;
+    let id = conn.query_row(
+        "SELECT id FROM pending_payloads
+         WHERE endpoint = ?1 AND block_height = ?2 AND payload_hash = ?3",
+        params![endpoint, block_height, payload_hash],
+        |row| row.get(0),
+    )?;
+    Ok(id)
+}
+
+/// Load the `(id, path, payload)` of every unacknowledged payload for `endpoint`, oldest first.
+fn get_pending_payloads(
+    conn: &Connection,
+    endpoint: &str,
+) -> Result<Vec<(i64, String, Vec<u8>)>, db_error> {
+    let mut stmt = conn.prepare(
+        "SELECT id, path, payload FROM pending_payloads WHERE endpoint = ?1 ORDER BY id ASC",
+    )?;
+    let pending = stmt
+        .query_map(params![endpoint], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(pending)
+}
+
+fn delete_pending_payload(conn: &Connection, id: i64) -> Result<(), db_error> {
+    conn.execute("DELETE FROM pending_payloads WHERE id = ?1", params![id])?;
+    Ok(())
+}
+
+/// The block height a payload is keyed by: the Stacks or burnchain height it reports, or 0 for
+/// payloads (such as mempool events) that are not tied to a block.
+fn payload_block_height(payload: &serde_json::Value) -> u64 {
+    ["block_height", "burn_block_height", "stacks_height"]
+        .iter()
+        .find_map(|key| payload.get(key).and_then(|height| height.as_u64()))
+        .unwrap_or(0)
+}
+
 #[derive(Clone)]
 pub struct EventDispatcher {
     registered_observers: Vec<EventObserver>,
     contract_events_observers_lookup: HashMap<(QualifiedContractIdentifier, String), HashSet<u16>>,
+    contract_observers_lookup: HashMap<QualifiedContractIdentifier, HashSet<u16>>,
+    /// Observers that only subscribed to whole contracts, and the contracts they subscribed to.
+    /// These only receive new blocks that involve one of their contracts, and only the receipts
+    /// of the transactions that involve them.
+    contract_filtered_observers: HashMap<u16, HashSet<QualifiedContractIdentifier>>,
     assets_observers_lookup: HashMap<AssetIdentifier, HashSet<u16>>,
     burn_block_observers_lookup: HashSet<u16>,
     mempool_observers_lookup: HashSet<u16>,
@@ -411,6 +836,11 @@ pub struct EventDispatcher {
     any_event_observers_lookup: HashSet<u16>,
     miner_observers_lookup: HashSet<u16>,
     mined_microblocks_observers_lookup: HashSet<u16>,
+    epoch_transition_observers_lookup: HashSet<u16>,
+    /// Where observers persist their pending payloads, if anywhere.
+    db_path: Option<PathBuf>,
+    /// How many times observers POST a payload before giving up on it. 0 retries forever.
+    max_delivery_attempts: u32,
 }
 
 impl MemPoolEventDispatcher for EventDispatcher {
@@ -420,6 +850,10 @@ impl MemPoolEventDispatcher for EventDispatcher {
         }
     }
 
+    fn mempool_tx_evicted(&self, txid: Txid, reason: MemPoolDropReason) {
+        self.process_evicted_mempool_tx(txid, reason)
+    }
+
     fn mined_block_event(
         &self,
         target_burn_height: u64,
@@ -489,6 +923,16 @@ impl BlockEventDispatcher for EventDispatcher {
         )
     }
 
+    fn announce_epoch_transition(
+        &self,
+        block_id: &StacksBlockId,
+        burn_block_height: u64,
+        old_epoch: StacksEpochId,
+        new_epoch: StacksEpochId,
+    ) {
+        self.process_epoch_transition(block_id, burn_block_height, old_epoch, new_epoch)
+    }
+
     fn announce_burn_block(
         &self,
         burn_block: &BurnchainHeaderHash,
@@ -505,6 +949,21 @@ impl BlockEventDispatcher for EventDispatcher {
             recipient_info,
         )
     }
+
+    fn announce_burn_block_reorg(
+        &self,
+        old_canonical_tip: &BurnchainHeaderHash,
+        new_canonical_tip: &BurnchainHeaderHash,
+        reorg_depth: u64,
+        orphaned_stacks_blocks: &[BlockHeaderHash],
+    ) {
+        self.process_burn_block_reorg(
+            old_canonical_tip,
+            new_canonical_tip,
+            reorg_depth,
+            orphaned_stacks_blocks,
+        )
+    }
 }
 
 impl EventDispatcher {
@@ -512,6 +971,8 @@ impl EventDispatcher {
         EventDispatcher {
             registered_observers: vec![],
             contract_events_observers_lookup: HashMap::new(),
+            contract_observers_lookup: HashMap::new(),
+            contract_filtered_observers: HashMap::new(),
             assets_observers_lookup: HashMap::new(),
             stx_observers_lookup: HashSet::new(),
             any_event_observers_lookup: HashSet::new(),
@@ -520,6 +981,27 @@ impl EventDispatcher {
             microblock_observers_lookup: HashSet::new(),
             miner_observers_lookup: HashSet::new(),
             mined_microblocks_observers_lookup: HashSet::new(),
+            epoch_transition_observers_lookup: HashSet::new(),
+            db_path: None,
+            max_delivery_attempts: 0,
+        }
+    }
+
+    /// Create a dispatcher whose observers persist each payload in the sqlite database at
+    /// `db_path` until it is acknowledged, and give up on a payload after
+    /// `max_delivery_attempts` failed POSTs (0 retries forever).
+    pub fn new_with_db_path(db_path: PathBuf, max_delivery_attempts: u32) -> EventDispatcher {
+        EventDispatcher {
+            db_path: Some(db_path),
+            max_delivery_attempts,
+            ..EventDispatcher::new()
+        }
+    }
+
+    /// Re-send the payloads each observer had not acknowledged when the node last shut down.
+    pub fn process_pending_payloads(&self) {
+        for observer in self.registered_observers.iter() {
+            observer.send_pending_payloads();
         }
     }
 
@@ -558,6 +1040,76 @@ impl EventDispatcher {
         }
     }
 
+    /// Notify interested observers that the canonical burnchain fork moved from
+    /// `old_canonical_tip` to `new_canonical_tip`, abandoning `reorg_depth` burnchain blocks and
+    /// orphaning the Stacks blocks `orphaned_stacks_blocks` that were mined in them.
+    pub fn process_burn_block_reorg(
+        &self,
+        old_canonical_tip: &BurnchainHeaderHash,
+        new_canonical_tip: &BurnchainHeaderHash,
+        reorg_depth: u64,
+        orphaned_stacks_blocks: &[BlockHeaderHash],
+    ) {
+        let interested_observers: Vec<_> = self
+            .registered_observers
+            .iter()
+            .enumerate()
+            .filter(|(obs_id, _observer)| {
+                self.burn_block_observers_lookup.contains(&(*obs_id as u16))
+                    || self.any_event_observers_lookup.contains(&(*obs_id as u16))
+            })
+            .collect();
+        if interested_observers.len() < 1 {
+            return;
+        }
+
+        let payload = EventObserver::make_burn_block_reorg_payload(
+            old_canonical_tip,
+            new_canonical_tip,
+            reorg_depth,
+            orphaned_stacks_blocks,
+        );
+
+        for (_, observer) in interested_observers.iter() {
+            observer.send_burn_block_reorg(&payload);
+        }
+    }
+
+    /// Notify interested observers that the Stacks block `block_id`, at burn height
+    /// `burn_block_height`, is the first block evaluated in `new_epoch`.
+    pub fn process_epoch_transition(
+        &self,
+        block_id: &StacksBlockId,
+        burn_block_height: u64,
+        old_epoch: StacksEpochId,
+        new_epoch: StacksEpochId,
+    ) {
+        let interested_observers: Vec<_> = self
+            .registered_observers
+            .iter()
+            .enumerate()
+            .filter(|(obs_id, _observer)| {
+                self.epoch_transition_observers_lookup
+                    .contains(&(*obs_id as u16))
+                    || self.any_event_observers_lookup.contains(&(*obs_id as u16))
+            })
+            .collect();
+        if interested_observers.len() < 1 {
+            return;
+        }
+
+        let payload = EventObserver::make_epoch_transition_payload(
+            block_id,
+            burn_block_height,
+            old_epoch,
+            new_epoch,
+        );
+
+        for (_, observer) in interested_observers.iter() {
+            observer.send_epoch_transition(&payload);
+        }
+    }
+
     /// Iterates through tx receipts, and then the events corresponding to each receipt to
     /// generate a dispatch matrix & event vector.
     ///
@@ -592,6 +1144,13 @@ impl EventDispatcher {
                                 dispatch_matrix[*o_i as usize].insert(i);
                             }
                         }
+                        if let Some(observer_indexes) =
+                            self.contract_observers_lookup.get(&event_data.key.0)
+                        {
+                            for o_i in observer_indexes {
+                                dispatch_matrix[*o_i as usize].insert(i);
+                            }
+                        }
                     }
                     StacksTransactionEvent::STXEvent(STXEventType::STXTransferEvent(_))
                     | StacksTransactionEvent::STXEvent(STXEventType::STXMintEvent(_))
@@ -701,6 +1260,16 @@ impl EventDispatcher {
             let mature_rewards = serde_json::Value::Array(mature_rewards_vec);
 
             for (observer_id, filtered_events_ids) in dispatch_matrix.iter().enumerate() {
+                let contracts_filter = self.contract_filtered_observers.get(&(observer_id as u16));
+                if let Some(contracts) = contracts_filter {
+                    if !receipts.iter().any(|receipt| {
+                        EventObserver::receipt_involves_contracts(receipt, contracts)
+                    }) {
+                        // nothing in this block for this observer
+                        continue;
+                    }
+                }
+
                 let filtered_events: Vec<_> = filtered_events_ids
                     .iter()
                     .map(|event_id| (*event_id, &events[*event_id]))
@@ -712,6 +1281,7 @@ impl EventDispatcher {
                         block,
                         metadata,
                         receipts,
+                        contracts_filter,
                         parent_index_hash,
                         &winner_txid,
                         &mature_rewards,
@@ -910,6 +1480,31 @@ impl EventDispatcher {
         }
     }
 
+    pub fn process_evicted_mempool_tx(&self, txid: Txid, reason: MemPoolDropReason) {
+        // lazily assemble payload only if we have observers
+        let interested_observers: Vec<_> = self
+            .registered_observers
+            .iter()
+            .enumerate()
+            .filter(|(obs_id, _observer)| {
+                self.mempool_observers_lookup.contains(&(*obs_id as u16))
+                    || self.any_event_observers_lookup.contains(&(*obs_id as u16))
+            })
+            .collect();
+        if interested_observers.len() < 1 {
+            return;
+        }
+
+        let payload = json!({
+            "txid": format!("0x{}", &txid),
+            "reason": reason.to_string(),
+        });
+
+        for (_, observer) in interested_observers.iter() {
+            observer.send_evicted_mempool_tx(&payload);
+        }
+    }
+
     pub fn process_new_attachments(&self, attachments: &Vec<(AttachmentInstance, Attachment)>) {
         let interested_observers: Vec<_> = self.registered_observers.iter().enumerate().collect();
         if interested_observers.len() < 1 {
@@ -942,9 +1537,27 @@ impl EventDispatcher {
 
     pub fn register_observer(&mut self, conf: &EventObserverConfig) {
         info!("Registering event observer at: {}", conf.endpoint);
-        let event_observer = EventObserver {
+        let pending_db =
+            self.db_path
+                .as_ref()
+                .and_then(|db_path| match open_pending_payloads_db(db_path) {
+                    Ok(conn) => Some(Arc::new(Mutex::new(conn))),
+                    Err(err) => {
+                        error!(
+                            "Event dispatcher: failed to open pending payloads database  - {:?}",
+                            err
+                        );
+                        None
+                    }
+                });
+        let mut event_observer = EventObserver {
             endpoint: conf.endpoint.clone(),
+            pending_db,
+            max_attempts: self.max_delivery_attempts,
+            delivery_queue: None,
+            halted: Arc::new(AtomicBool::new(false)),
         };
+        event_observer.spawn_delivery_thread();
 
         let observer_index = self.registered_observers.len() as u16;
 
@@ -965,6 +1578,12 @@ impl EventDispatcher {
                         }
                     };
                 }
+                EventKeyType::ContractEvent(contract_identifier) => {
+                    self.contract_observers_lookup
+                        .entry(contract_identifier.clone())
+                        .or_insert_with(HashSet::new)
+                        .insert(observer_index);
+                }
                 EventKeyType::BurnchainBlocks => {
                     self.burn_block_observers_lookup.insert(observer_index);
                 }
@@ -999,26 +1618,71 @@ impl EventDispatcher {
                     self.mined_microblocks_observers_lookup
                         .insert(observer_index);
                 }
+                EventKeyType::EpochTransitions => {
+                    self.epoch_transition_observers_lookup
+                        .insert(observer_index);
+                }
             }
         }
 
+        let contract_only = !conf.events_keys.is_empty()
+            && conf
+                .events_keys
+                .iter()
+                .all(|key| matches!(key, EventKeyType::ContractEvent(_)));
+        if contract_only {
+            let contracts = conf
+                .events_keys
+                .iter()
+                .filter_map(|key| match key {
+                    EventKeyType::ContractEvent(contract_identifier) => {
+                        Some(contract_identifier.clone())
+                    }
+                    _ => None,
+                })
+                .collect();
+            self.contract_filtered_observers
+                .insert(observer_index, contracts);
+        }
+
         self.registered_observers.push(event_observer);
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::event_dispatcher::EventObserver;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::path::{Path, PathBuf};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::thread::sleep;
+    use std::time::{Duration, Instant};
+    use std::{env, fs, thread};
+
+    use rusqlite::Connection;
+    use serde_json::json;
+
+    use crate::config::{EventKeyType, EventObserverConfig};
+    use crate::event_dispatcher::{
+        get_pending_payloads, insert_pending_payload, open_pending_payloads_db,
+        payload_block_height, EventDispatcher, EventObserver, INITIAL_BACKOFF_MS,
+    };
     use clarity::vm::costs::ExecutionCost;
     use stacks::burnchains::{PoxConstants, Txid};
     use stacks::chainstate::stacks::db::StacksHeaderInfo;
     use stacks::chainstate::stacks::StacksBlock;
-    use stacks_common::types::chainstate::{BurnchainHeaderHash, StacksBlockId};
+    use stacks::core::StacksEpochId;
+    use stacks_common::types::chainstate::{BlockHeaderHash, BurnchainHeaderHash, StacksBlockId};
 
     #[test]
     fn build_block_processed_event() {
         let observer = EventObserver {
             endpoint: "nowhere".to_string(),
+            pending_db: None,
+            max_attempts: 0,
+            delivery_queue: None,
+            halted: Arc::new(AtomicBool::new(false)),
         };
 
         let filtered_events = vec![];
@@ -1040,6 +1704,7 @@ mod test {
             &block,
             &metadata,
             &receipts,
+            None,
             &parent_index_hash,
             &winner_txid,
             &mature_rewards,
@@ -1059,4 +1724,354 @@ mod test {
             pox_constants.v1_unlock_height as u64
         );
     }
+
+    #[test]
+    fn build_burn_block_reorg_event() {
+        let payload = EventObserver::make_burn_block_reorg_payload(
+            &BurnchainHeaderHash([0x01; 32]),
+            &BurnchainHeaderHash([0x02; 32]),
+            3,
+            &[BlockHeaderHash([0x03; 32]), BlockHeaderHash([0x04; 32])],
+        );
+        assert_eq!(
+            payload,
+            json!({
+                "old_canonical_tip": format!("0x{}", "01".repeat(32)),
+                "new_canonical_tip": format!("0x{}", "02".repeat(32)),
+                "reorg_depth": 3,
+                "stacks_block_hashes": [
+                    format!("0x{}", "03".repeat(32)),
+                    format!("0x{}", "04".repeat(32)),
+                ],
+            })
+        );
+
+        // keyed like other payloads that are not tied to a block height
+        assert_eq!(payload_block_height(&payload), 0);
+    }
+
+    fn open_test_pending_db(path: &Path) -> Option<Arc<Mutex<Connection>>> {
+        Some(Arc::new(Mutex::new(
+            open_pending_payloads_db(path).unwrap(),
+        )))
+    }
+
+    fn pending_payloads_db_path(name: &str) -> PathBuf {
+        let mut path = env::temp_dir();
+        path.push(format!("{}-event_observers.sqlite", name));
+        if path.exists() {
+            fs::remove_file(&path).unwrap();
+        }
+        path
+    }
+
+    #[test]
+    fn pending_payloads_are_keyed_and_ordered() {
+        let path = pending_payloads_db_path("pending_payloads_are_keyed_and_ordered");
+        let conn = open_pending_payloads_db(&path).unwrap();
+
+        let first = insert_pending_payload(&conn, "a:1", "new_block", 2, b"two").unwrap();
+        let second = insert_pending_payload(&conn, "a:1", "new_block", 1, b"one").unwrap();
+        let other = insert_pending_payload(&conn, "b:1", "new_block", 2, b"two").unwrap();
+        assert!(first < second);
+        assert!(second < other);
+
+        // the same payload at the same height for the same endpoint is only queued once
+        assert_eq!(
+            insert_pending_payload(&conn, "a:1", "new_block", 2, b"two").unwrap(),
+            first
+        );
+
+        // pending payloads survive reopening the database, in the order they were queued
+        drop(conn);
+        let conn = open_pending_payloads_db(&path).unwrap();
+        assert_eq!(
+            get_pending_payloads(&conn, "a:1").unwrap(),
+            vec![
+                (first, "new_block".to_string(), b"two".to_vec()),
+                (second, "new_block".to_string(), b"one".to_vec()),
+            ]
+        );
+        assert_eq!(get_pending_payloads(&conn, "b:1").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn payload_block_heights() {
+        assert_eq!(
+            payload_block_height(&serde_json::json!({ "block_height": 5 })),
+            5
+        );
+        assert_eq!(
+            payload_block_height(&serde_json::json!({ "burn_block_height": 7 })),
+            7
+        );
+        assert_eq!(
+            payload_block_height(&serde_json::json!({ "stacks_height": 9 })),
+            9
+        );
+        assert_eq!(payload_block_height(&serde_json::json!(["0x00"])), 0);
+    }
+
+    /// Acknowledge `count` POSTs, returning their paths and bodies
+    fn acknowledge_posts(
+        listener: TcpListener,
+        count: usize,
+    ) -> thread::JoinHandle<Vec<(String, String)>> {
+        thread::spawn(move || {
+            let mut received = vec![];
+            for stream in listener.incoming().take(count) {
+                let mut stream = BufReader::new(stream.unwrap());
+                let mut request_line = String::new();
+                stream.read_line(&mut request_line).unwrap();
+                let mut content_length = 0;
+                loop {
+                    let mut header = String::new();
+                    stream.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                    let mut parts = header.splitn(2, ':');
+                    let name = parts.next().unwrap().trim().to_lowercase();
+                    if name == "content-length" {
+                        content_length = parts.next().unwrap().trim().parse().unwrap();
+                    }
+                }
+                let mut body = vec![0u8; content_length];
+                stream.read_exact(&mut body).unwrap();
+                stream
+                    .get_mut()
+                    .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n")
+                    .unwrap();
+                let path = request_line.split_whitespace().nth(1).unwrap().to_string();
+                received.push((path, String::from_utf8(body).unwrap()));
+            }
+            received
+        })
+    }
+
+    /// Reserve a local port that nothing listens on until the caller binds it again
+    fn unused_endpoint() -> (String, u16) {
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        (format!("127.0.0.1:{}", port), port)
+    }
+
+    #[test]
+    fn queued_payloads_are_delivered_once_observer_resumes() {
+        let path = pending_payloads_db_path("queued_payloads_are_delivered_once_observer_resumes");
+        let (endpoint, port) = unused_endpoint();
+
+        let mut observer = EventObserver {
+            endpoint: endpoint.clone(),
+            pending_db: open_test_pending_db(&path),
+            max_attempts: 0,
+            delivery_queue: None,
+            halted: Arc::new(AtomicBool::new(false)),
+        };
+        observer.spawn_delivery_thread();
+
+        // the observer is down, but sending must not block the caller
+        let start = Instant::now();
+        observer.send_payload(&json!({ "block_height": 1 }), "/new_block");
+        observer.send_payload(&json!({ "block_height": 2 }), "/new_block");
+        assert!(start.elapsed() < Duration::from_millis(INITIAL_BACKOFF_MS));
+
+        let conn = open_pending_payloads_db(&path).unwrap();
+        assert_eq!(get_pending_payloads(&conn, &endpoint).unwrap().len(), 2);
+
+        // once it comes back, the buffered payloads arrive in order
+        let listener = TcpListener::bind(("127.0.0.1", port)).unwrap();
+        assert_eq!(
+            acknowledge_posts(listener, 2).join().unwrap(),
+            vec![
+                (
+                    "/new_block".to_string(),
+                    r#"{"block_height":1}"#.to_string()
+                ),
+                (
+                    "/new_block".to_string(),
+                    r#"{"block_height":2}"#.to_string()
+                ),
+            ]
+        );
+
+        // ...and are forgotten once acknowledged
+        let start = Instant::now();
+        while !get_pending_payloads(&conn, &endpoint).unwrap().is_empty() {
+            assert!(start.elapsed() < Duration::from_secs(10));
+            sleep(Duration::from_millis(100));
+        }
+    }
+
+    #[test]
+    fn gives_up_after_max_attempts() {
+        let path = pending_payloads_db_path("gives_up_after_max_attempts");
+        let (endpoint, _) = unused_endpoint();
+
+        let observer = EventObserver {
+            endpoint: endpoint.clone(),
+            pending_db: open_test_pending_db(&path),
+            max_attempts: 2,
+            delivery_queue: None,
+            halted: Arc::new(AtomicBool::new(false)),
+        };
+        assert!(!observer.send_body(b"{}", "/new_block"));
+
+        // a payload that was given up on is kept for replay after a restart
+        observer.send_payload(&json!({ "block_height": 1 }), "/new_block");
+        assert!(observer.halted.load(Ordering::SeqCst));
+        let conn = open_pending_payloads_db(&path).unwrap();
+        assert_eq!(get_pending_payloads(&conn, &endpoint).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn nothing_is_sent_after_giving_up() {
+        let path = pending_payloads_db_path("nothing_is_sent_after_giving_up");
+        let (endpoint, port) = unused_endpoint();
+
+        let observer = EventObserver {
+            endpoint: endpoint.clone(),
+            pending_db: open_test_pending_db(&path),
+            max_attempts: 1,
+            delivery_queue: None,
+            halted: Arc::new(AtomicBool::new(false)),
+        };
+        observer.send_payload(&json!({ "block_height": 1 }), "/new_block");
+        assert!(observer.halted.load(Ordering::SeqCst));
+
+        // the observer comes back, but must not receive block 2 without block 1
+        let listener = TcpListener::bind(("127.0.0.1", port)).unwrap();
+        listener.set_nonblocking(true).unwrap();
+        observer.send_payload(&json!({ "block_height": 2 }), "/new_block");
+        assert!(listener.accept().is_err());
+
+        // both are replayed, in order, after a restart
+        let conn = open_pending_payloads_db(&path).unwrap();
+        assert_eq!(
+            get_pending_payloads(&conn, &endpoint)
+                .unwrap()
+                .into_iter()
+                .map(|(_, _, body)| String::from_utf8(body).unwrap())
+                .collect::<Vec<_>>(),
+            vec![
+                r#"{"block_height":1}"#.to_string(),
+                r#"{"block_height":2}"#.to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn send_pending_payloads_replays_in_order() {
+        let path = pending_payloads_db_path("send_pending_payloads_replays_in_order");
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("127.0.0.1:{}", listener.local_addr().unwrap().port());
+
+        let conn = open_pending_payloads_db(&path).unwrap();
+        insert_pending_payload(&conn, &endpoint, "new_block", 1, br#"{"block_height":1}"#).unwrap();
+        insert_pending_payload(&conn, &endpoint, "new_block", 2, br#"{"block_height":2}"#).unwrap();
+
+        let server = acknowledge_posts(listener, 2);
+
+        let observer = EventObserver {
+            endpoint: endpoint.clone(),
+            pending_db: open_test_pending_db(&path),
+            max_attempts: 0,
+            delivery_queue: None,
+            halted: Arc::new(AtomicBool::new(false)),
+        };
+        observer.send_pending_payloads();
+
+        assert_eq!(
+            server.join().unwrap(),
+            vec![
+                (
+                    "/new_block".to_string(),
+                    r#"{"block_height":1}"#.to_string()
+                ),
+                (
+                    "/new_block".to_string(),
+                    r#"{"block_height":2}"#.to_string()
+                ),
+            ]
+        );
+        assert!(get_pending_payloads(&conn, &endpoint).unwrap().is_empty());
+    }
+
+    #[test]
+    fn blocked_observer_does_not_delay_others() {
+        // accepts connections, but never answers them
+        let blocking_listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let blocking_endpoint = format!(
+            "127.0.0.1:{}",
+            blocking_listener.local_addr().unwrap().port()
+        );
+        thread::spawn(move || {
+            let mut held = vec![];
+            for stream in blocking_listener.incoming() {
+                held.push(stream);
+            }
+        });
+
+        let burn_listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let burn_endpoint = format!("127.0.0.1:{}", burn_listener.local_addr().unwrap().port());
+        let mempool_listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mempool_endpoint = format!(
+            "127.0.0.1:{}",
+            mempool_listener.local_addr().unwrap().port()
+        );
+
+        let mut dispatcher = EventDispatcher::new();
+        dispatcher.register_observer(&EventObserverConfig {
+            endpoint: blocking_endpoint,
+            events_keys: vec![EventKeyType::AnyEvent],
+        });
+        dispatcher.register_observer(&EventObserverConfig {
+            endpoint: burn_endpoint,
+            events_keys: vec![EventKeyType::BurnchainBlocks],
+        });
+        dispatcher.register_observer(&EventObserverConfig {
+            endpoint: mempool_endpoint,
+            events_keys: vec![EventKeyType::MemPoolTransactions],
+        });
+
+        let burn_server = acknowledge_posts(burn_listener, 2);
+        let mempool_server = acknowledge_posts(mempool_listener, 1);
+
+        let start = Instant::now();
+        dispatcher.process_burn_block(&BurnchainHeaderHash([0x01; 32]), 1, vec![], 0, vec![]);
+        dispatcher.process_new_mempool_txs(vec![]);
+        dispatcher.process_epoch_transition(
+            &StacksBlockId([0x02; 32]),
+            2,
+            StacksEpochId::Epoch20,
+            StacksEpochId::Epoch2_05,
+        );
+        dispatcher.process_burn_block(&BurnchainHeaderHash([0x03; 32]), 3, vec![], 0, vec![]);
+
+        // each responsive observer gets exactly the events it subscribed to, in order
+        let burn_posts = burn_server.join().unwrap();
+        assert_eq!(
+            burn_posts
+                .iter()
+                .map(|(path, body)| {
+                    let payload: serde_json::Value = serde_json::from_str(body).unwrap();
+                    (path.clone(), payload_block_height(&payload))
+                })
+                .collect::<Vec<_>>(),
+            vec![
+                ("/new_burn_block".to_string(), 1),
+                ("/new_burn_block".to_string(), 3),
+            ]
+        );
+        assert_eq!(
+            mempool_server.join().unwrap(),
+            vec![("/new_mempool_tx".to_string(), "[]".to_string())]
+        );
+
+        // ...well before the blocked observer would have timed out even once
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
 }
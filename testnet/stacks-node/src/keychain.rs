@@ -1,22 +1,46 @@
+use std::path::Path;
+
+use rusqlite::{params, Connection, OpenFlags, NO_PARAMS};
+
 use stacks::address::AddressHashMode;
-use stacks::burnchains::BurnchainSigner;
+use stacks::burnchains::{Burnchain, BurnchainSigner};
 use stacks::chainstate::stacks::{
     StacksPrivateKey, StacksPublicKey, StacksTransactionSigner, TransactionAuth,
 };
 use stacks::types::chainstate::StacksAddress;
 use stacks::util::hash::{Hash160, Sha256Sum};
 use stacks::util::vrf::{VRFPrivateKey, VRFProof, VRFPublicKey, VRF};
+use stacks::util_lib::db::{
+    sqlite_open, table_exists, tx_begin_immediate_sqlite, u64_to_sql, Error as db_error,
+};
 
 use super::operations::BurnchainOpSigner;
+use crate::run_loop::RegisteredKey;
 
 use stacks_common::address::{
     C32_ADDRESS_VERSION_MAINNET_SINGLESIG, C32_ADDRESS_VERSION_TESTNET_SINGLESIG,
 };
 
+/// Domain separator for VRF keys derived per reward cycle
+const VRF_ROTATION_PATH: &'static [u8] = b"vrf-key-rotation";
+
+const CREATE_VRF_KEYS_TABLE: &'static str = "
+CREATE TABLE vrf_keys (
+    key_block_height INTEGER NOT NULL,
+    key_vtxindex INTEGER NOT NULL,
+    target_block_height INTEGER NOT NULL,
+    reward_cycle INTEGER NOT NULL,
+    vrf_public_key TEXT NOT NULL,
+    PRIMARY KEY (key_block_height, key_vtxindex)
+)";
+
 /// A wrapper around a node's seed, coupled with operations for using it
 #[derive(Clone)]
 pub struct Keychain {
     secret_state: Vec<u8>,
+    /// If set, VRF keys rotate every reward cycle.
+    /// This is the (first burn block height, reward cycle length) of the burnchain.
+    vrf_key_rotation: Option<(u64, u64)>,
 }
 
 impl Keychain {
@@ -49,18 +73,21 @@ impl Keychain {
     pub fn default(seed: Vec<u8>) -> Keychain {
         Keychain {
             secret_state: Keychain::make_secret_key_bytes(&seed),
+            vrf_key_rotation: None,
         }
     }
 
-    /// Generate a VRF keypair for this burn block height.
-    /// The keypair is unique to this burn block height.
-    pub fn make_vrf_keypair(&self, block_height: u64) -> (VRFPublicKey, VRFPrivateKey) {
-        let mut seed = {
-            let mut secret_state = self.secret_state.clone();
-            secret_state.extend_from_slice(&block_height.to_be_bytes());
-            Sha256Sum::from_data(&secret_state)
-        };
+    /// Rotate VRF keys on the given burnchain's reward cycles
+    pub fn with_vrf_key_rotation(mut self, burnchain: &Burnchain) -> Keychain {
+        self.vrf_key_rotation = Some((
+            burnchain.first_block_height,
+            burnchain.pox_constants.reward_cycle_length as u64,
+        ));
+        self
+    }
 
+    /// Find a VRF keypair from a seed.
+    fn make_vrf_keypair_from_seed(mut seed: Sha256Sum) -> (VRFPublicKey, VRFPrivateKey) {
         // Not every 256-bit number is a valid Ed25519 secret key.
         // As such, we continuously generate seeds through re-hashing until one works.
         let sk = loop {
@@ -73,6 +100,49 @@ impl Keychain {
         (pk, sk)
     }
 
+    /// Generate a VRF keypair for this burn block height.
+    /// The keypair is unique to this burn block height.
+    pub fn make_vrf_keypair(&self, block_height: u64) -> (VRFPublicKey, VRFPrivateKey) {
+        let seed = {
+            let mut secret_state = self.secret_state.clone();
+            secret_state.extend_from_slice(&block_height.to_be_bytes());
+            Sha256Sum::from_data(&secret_state)
+        };
+        Keychain::make_vrf_keypair_from_seed(seed)
+    }
+
+    /// Get the reward cycle whose VRF key is used at this burn block height.
+    /// Returns None if VRF keys do not rotate, or if the height precedes the first reward cycle.
+    pub fn vrf_key_reward_cycle(&self, burn_block_height: u64) -> Option<u64> {
+        let (first_block_height, reward_cycle_length) = self.vrf_key_rotation?;
+        Burnchain::static_block_height_to_reward_cycle(
+            burn_block_height,
+            first_block_height,
+            reward_cycle_length,
+        )
+    }
+
+    /// Generate the VRF keypair to register at this burn block height.
+    /// If VRF keys rotate, the keypair is unique to the reward cycle containing this height, and
+    /// is derived from the secret state along a path that is distinct from the per-block keys of
+    /// `make_vrf_keypair()` (so knowing one rotated key reveals nothing about the others).
+    /// Otherwise, this is `make_vrf_keypair()`.
+    pub fn rotate_vrf_keypair(&self, burn_block_height: u64) -> (VRFPublicKey, VRFPrivateKey) {
+        let reward_cycle = match self.vrf_key_reward_cycle(burn_block_height) {
+            Some(reward_cycle) => reward_cycle,
+            None => {
+                return self.make_vrf_keypair(burn_block_height);
+            }
+        };
+        let seed = {
+            let mut secret_state = Sha256Sum::from_data(&self.secret_state).as_bytes().to_vec();
+            secret_state.extend_from_slice(VRF_ROTATION_PATH);
+            secret_state.extend_from_slice(&reward_cycle.to_be_bytes());
+            Sha256Sum::from_data(&secret_state)
+        };
+        Keychain::make_vrf_keypair_from_seed(seed)
+    }
+
     /// Generate a Stacks keypair for this burn block height.
     /// The keypair is unique to this burn block height.
     pub fn make_stacks_keypair(
@@ -95,9 +165,9 @@ impl Keychain {
     }
 
     /// Generate a VRF proof over a given byte message.
-    /// `block_height` must be the _same_ block height called to make_vrf_keypair()
+    /// `block_height` must be the _same_ block height called to rotate_vrf_keypair()
     pub fn generate_proof(&self, block_height: u64, bytes: &[u8; 32]) -> VRFProof {
-        let (pk, sk) = self.make_vrf_keypair(block_height);
+        let (pk, sk) = self.rotate_vrf_keypair(block_height);
         let proof = VRF::prove(&sk, &bytes.to_vec());
 
         // Ensure that the proof is valid by verifying
@@ -183,12 +253,100 @@ impl Keychain {
     pub fn generate_op_signer(&self) -> BurnchainOpSigner {
         BurnchainOpSigner::new(self.get_secret_key(), false)
     }
+
+    /// Create the BurnchainOpSigner for a block-commit that uses the given registered VRF key.
+    /// Returns None if this keychain did not derive that key, in which case the commit's VRF
+    /// proof could not be verified against it.
+    pub fn generate_op_signer_for_key(&self, key: &RegisteredKey) -> Option<BurnchainOpSigner> {
+        let (vrf_pk, _) = self.rotate_vrf_keypair(key.target_block_height);
+        if vrf_pk != key.vrf_public_key {
+            return None;
+        }
+        Some(self.generate_op_signer())
+    }
+}
+
+/// Open the registered VRF keys database at `db_path`, creating it if necessary.
+pub fn open_vrf_keys_db(db_path: &Path) -> Result<Connection, db_error> {
+    let mut conn = sqlite_open(
+        db_path,
+        OpenFlags::SQLITE_OPEN_CREATE | OpenFlags::SQLITE_OPEN_READ_WRITE,
+        false,
+    )?;
+
+    let tx = tx_begin_immediate_sqlite(&mut conn)?;
+    if !table_exists(&tx, "vrf_keys")? {
+        tx.execute(CREATE_VRF_KEYS_TABLE, NO_PARAMS)?;
+    }
+    tx.commit()?;
+    Ok(conn)
+}
+
+/// Remember a VRF key registration that landed on the burnchain, keyed by its block pointer.
+/// `reward_cycle` is the reward cycle the key was derived for (0 if VRF keys do not rotate).
+pub fn insert_registered_vrf_key(
+    conn: &Connection,
+    reward_cycle: u64,
+    key: &RegisteredKey,
+) -> Result<(), db_error> {
+    conn.execute(
+        "INSERT OR REPLACE INTO vrf_keys
+         (key_block_height, key_vtxindex, target_block_height, reward_cycle, vrf_public_key)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            u64_to_sql(key.block_height)?,
+            key.op_vtxindex,
+            u64_to_sql(key.target_block_height)?,
+            u64_to_sql(reward_cycle)?,
+            key.vrf_public_key.to_hex()
+        ],
+    )?;
+    Ok(())
+}
+
+/// Load the VRF key registrations made for `reward_cycle`, most recent first.
+pub fn get_registered_vrf_keys(
+    conn: &Connection,
+    reward_cycle: u64,
+) -> Result<Vec<RegisteredKey>, db_error> {
+    let mut stmt = conn.prepare(
+        "SELECT key_block_height, key_vtxindex, target_block_height, vrf_public_key FROM vrf_keys
+         WHERE reward_cycle = ?1 ORDER BY key_block_height DESC, key_vtxindex DESC",
+    )?;
+    let rows = stmt
+        .query_map(params![u64_to_sql(reward_cycle)?], |row| {
+            let block_height: i64 = row.get(0)?;
+            let op_vtxindex: u32 = row.get(1)?;
+            let target_block_height: i64 = row.get(2)?;
+            let vrf_public_key: String = row.get(3)?;
+            Ok((
+                block_height,
+                op_vtxindex,
+                target_block_height,
+                vrf_public_key,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut keys = vec![];
+    for (block_height, op_vtxindex, target_block_height, vrf_public_key) in rows.into_iter() {
+        let vrf_public_key = VRFPublicKey::from_hex(&vrf_public_key).ok_or(db_error::ParseError)?;
+        keys.push(RegisteredKey {
+            target_block_height: target_block_height as u64,
+            block_height: block_height as u64,
+            op_vtxindex,
+            vrf_public_key,
+        });
+    }
+    Ok(keys)
 }
 
 #[cfg(test)]
 #[allow(dead_code)]
 mod tests {
     use std::collections::HashMap;
+    use std::path::PathBuf;
+    use std::{env, fs};
 
     use stacks::address::AddressHashMode;
     use stacks::burnchains::PrivateKey;
@@ -199,9 +357,12 @@ mod tests {
     use stacks::util::hash::{Hash160, Sha256Sum};
     use stacks::util::vrf::{VRFPrivateKey, VRFProof, VRFPublicKey, VRF};
 
+    use stacks::burnchains::Burnchain;
+
     use crate::operations::BurnchainOpSigner;
+    use crate::run_loop::RegisteredKey;
 
-    use super::Keychain;
+    use super::{get_registered_vrf_keys, insert_registered_vrf_key, open_vrf_keys_db, Keychain};
 
     use stacks::chainstate::stacks::StacksTransaction;
     use stacks::chainstate::stacks::TokenTransferMemo;
@@ -532,4 +693,114 @@ mod tests {
             assert_eq!(tx_1, tx_2);
         }
     }
+
+    #[test]
+    fn test_rotate_vrf_keypair() {
+        let mut burnchain = Burnchain::regtest("/unused");
+        burnchain.first_block_height = 100;
+        burnchain.pox_constants.reward_cycle_length = 5;
+
+        let static_keychain = Keychain::default(vec![1u8; 32]);
+        let keychain = static_keychain.clone().with_vrf_key_rotation(&burnchain);
+
+        // without rotation, every burn block height gets its own key
+        assert_eq!(
+            static_keychain.rotate_vrf_keypair(101).0,
+            static_keychain.make_vrf_keypair(101).0
+        );
+        assert_ne!(
+            static_keychain.rotate_vrf_keypair(101).0,
+            static_keychain.rotate_vrf_keypair(102).0
+        );
+
+        // with rotation, a reward cycle's blocks share a key
+        assert_eq!(keychain.vrf_key_reward_cycle(99), None);
+        assert_eq!(keychain.vrf_key_reward_cycle(104), Some(0));
+        assert_eq!(keychain.vrf_key_reward_cycle(105), Some(1));
+        assert_eq!(
+            keychain.rotate_vrf_keypair(100).0,
+            keychain.rotate_vrf_keypair(104).0
+        );
+        assert_ne!(
+            keychain.rotate_vrf_keypair(104).0,
+            keychain.rotate_vrf_keypair(105).0
+        );
+        assert_ne!(
+            keychain.rotate_vrf_keypair(105).0,
+            keychain.make_vrf_keypair(105).0
+        );
+
+        // rotated keys are still derived from the seed
+        let other_keychain = Keychain::default(vec![2u8; 32]).with_vrf_key_rotation(&burnchain);
+        assert_ne!(
+            keychain.rotate_vrf_keypair(105).0,
+            other_keychain.rotate_vrf_keypair(105).0
+        );
+
+        // proofs are made with the rotated key
+        let (vrf_pk, _) = keychain.rotate_vrf_keypair(106);
+        let proof = keychain.generate_proof(108, &[3u8; 32]);
+        assert!(VRF::verify(&vrf_pk, &proof, &[3u8; 32].to_vec()).unwrap());
+
+        // and block-commits can only be signed for keys we derived
+        let registered_key = RegisteredKey {
+            target_block_height: 106,
+            block_height: 107,
+            op_vtxindex: 1,
+            vrf_public_key: vrf_pk.clone(),
+        };
+        assert!(keychain
+            .generate_op_signer_for_key(&registered_key)
+            .is_some());
+        assert!(other_keychain
+            .generate_op_signer_for_key(&registered_key)
+            .is_none());
+        assert!(static_keychain
+            .generate_op_signer_for_key(&registered_key)
+            .is_none());
+    }
+
+    fn vrf_keys_db_path(name: &str) -> PathBuf {
+        let mut path = env::temp_dir();
+        path.push(format!("{}-vrf_keys.sqlite", name));
+        if path.exists() {
+            fs::remove_file(&path).unwrap();
+        }
+        path
+    }
+
+    #[test]
+    fn test_registered_vrf_keys_db() {
+        let path = vrf_keys_db_path("test_registered_vrf_keys_db");
+        let keychain = Keychain::default(vec![1u8; 32]);
+        let make_key = |target_block_height: u64, block_height: u64| RegisteredKey {
+            target_block_height,
+            block_height,
+            op_vtxindex: 2,
+            vrf_public_key: keychain.make_vrf_keypair(target_block_height).0,
+        };
+
+        let conn = open_vrf_keys_db(&path).unwrap();
+        insert_registered_vrf_key(&conn, 1, &make_key(105, 106)).unwrap();
+        insert_registered_vrf_key(&conn, 1, &make_key(108, 109)).unwrap();
+        insert_registered_vrf_key(&conn, 2, &make_key(110, 111)).unwrap();
+
+        // same key block pointer overwrites
+        insert_registered_vrf_key(&conn, 1, &make_key(108, 109)).unwrap();
+
+        // survives reopening
+        drop(conn);
+        let conn = open_vrf_keys_db(&path).unwrap();
+
+        let keys = get_registered_vrf_keys(&conn, 1).unwrap();
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys[0].block_height, 109);
+        assert_eq!(keys[0].target_block_height, 108);
+        assert_eq!(keys[0].op_vtxindex, 2);
+        assert_eq!(keys[0].vrf_public_key, keychain.make_vrf_keypair(108).0);
+        assert_eq!(keys[1].block_height, 106);
+
+        assert_eq!(get_registered_vrf_keys(&conn, 2).unwrap().len(), 1);
+        assert!(get_registered_vrf_keys(&conn, 3).unwrap().is_empty());
+    }
 }
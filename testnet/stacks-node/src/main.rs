@@ -17,12 +17,15 @@ extern crate stacks;
 #[macro_use(o, slog_log, slog_trace, slog_debug, slog_info, slog_warn, slog_error)]
 extern crate slog;
 
+use stacks::chainstate::stacks::db::StacksChainState;
 pub use stacks::util;
 use stacks::util::hash::hex_bytes;
+use stacks::vm::types::{PrincipalData, QualifiedContractIdentifier};
 
 pub mod monitoring;
 
 pub mod burnchains;
+pub mod clarity_repl;
 pub mod config;
 pub mod event_dispatcher;
 pub mod genesis_data;
@@ -37,6 +40,7 @@ pub mod tenure;
 pub use self::burnchains::{
     BitcoinRegtestController, BurnchainController, BurnchainTip, MocknetController,
 };
+pub use self::clarity_repl::ClarityRepl;
 pub use self::config::{Config, ConfigFile};
 pub use self::event_dispatcher::EventDispatcher;
 pub use self::keychain::Keychain;
@@ -79,6 +83,32 @@ fn main() {
     let mut args = Arguments::from_env();
     let subcommand = args.subcommand().unwrap().unwrap_or_default();
 
+    // this must be handled before anything is logged, since the logger is built on first use
+    let log_format: Option<String> = args
+        .opt_value_from_str("--log-format")
+        .expect("Failed to parse --log-format argument");
+
+    match log_format.as_deref() {
+        None => {}
+        Some("text") => {
+            env::remove_var("STACKS_LOG_JSON");
+        }
+        Some("json") => {
+            if !cfg!(feature = "slog_json") {
+                eprintln!("--log-format=json requires stacks-node to be built with the `slog_json` feature");
+                process::exit(1);
+            }
+            env::set_var("STACKS_LOG_JSON", "1");
+        }
+        Some(format) => {
+            eprintln!(
+                "Unrecognized --log-format '{}': expected 'text' or 'json'",
+                format
+            );
+            process::exit(1);
+        }
+    }
+
     info!("{}", version());
 
     let mine_start: Option<u64> = args
@@ -92,6 +122,10 @@ fn main() {
         );
     }
 
+    let compact_marf_height: Option<u32> = args
+        .opt_value_from_str("--compact-marf")
+        .expect("Failed to parse --compact-marf argument");
+
     let config_file = match subcommand.as_str() {
         "mocknet" => {
             args.finish().unwrap();
@@ -150,6 +184,43 @@ fn main() {
             println!("{}", &version());
             return;
         }
+        "clarity-repl" => {
+            let chainstate_path: String = args.value_from_str("--chainstate-path").unwrap();
+            let burnchain_path: Option<String> =
+                args.opt_value_from_str("--burnchain-path").unwrap();
+            let contract: Option<String> = args.opt_value_from_str("--contract").unwrap();
+            let sender: Option<String> = args.opt_value_from_str("--sender").unwrap();
+            args.finish().unwrap();
+
+            let contract = contract.map(|contract| {
+                QualifiedContractIdentifier::parse(&contract)
+                    .expect("--contract should be a contract identifier")
+            });
+            let sender = sender.map(|sender| {
+                PrincipalData::parse(&sender).expect("--sender should be a principal")
+            });
+
+            let mut repl = match ClarityRepl::open(
+                &chainstate_path,
+                burnchain_path.as_deref(),
+                contract,
+                sender,
+            ) {
+                Ok(repl) => repl,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    process::exit(1);
+                }
+            };
+            eprintln!("Evaluating read-only at Stacks chain tip {}", repl.tip());
+
+            let stdin = std::io::stdin();
+            if let Err(e) = repl.run(stdin.lock(), std::io::stdout()) {
+                eprintln!("Failed to run REPL: {}", e);
+                process::exit(1);
+            }
+            return;
+        }
         "key-for-seed" => {
             let seed = {
                 let config_path: Option<String> = args.opt_value_from_str("--config").unwrap();
@@ -195,6 +266,26 @@ fn main() {
     debug!("burnchain configuration {:?}", &conf.burnchain);
     debug!("connection configuration {:?}", &conf.connection_options);
 
+    if let Some(min_finality_height) = compact_marf_height {
+        info!(
+            "Compacting the chainstate MARFs below Stacks height {}",
+            min_finality_height
+        );
+        match StacksChainState::compact_marfs(
+            &conf.get_chainstate_path_str(),
+            Some(conf.node.get_marf_opts()),
+            min_finality_height,
+        ) {
+            Ok(freed) => {
+                info!("Compacted the chainstate MARFs, freeing {} bytes", freed);
+            }
+            Err(e) => {
+                error!("Failed to compact the chainstate MARFs: {:?}", &e);
+                process::exit(1);
+            }
+        }
+    }
+
     let num_round: u64 = 0; // Infinite number of rounds
 
     if conf.burnchain.mode == "helium" || conf.burnchain.mode == "mocknet" {
@@ -266,12 +357,29 @@ key-for-seed\tOutput the associated secret key for a burnchain signer created wi
 \t\tCan be passed a config file for the seed via the `--config=<file>` option *or* by supplying the hex seed on
 \t\tthe command line directly.
 
+clarity-repl\tEvaluate Clarity expressions read from stdin, one per line, against the canonical Stacks chain tip of an
+\t\texisting chainstate, and print each one's value and execution cost. The chainstate is opened read-only, and
+\t\tno expression may write to it.
+\t\tArguments:
+\t\t  --chainstate-path: path of the chainstate directory (`<working_dir>/<mode>/chainstate`).
+\t\t  --burnchain-path: optional path of the burnchain directory. Defaults to `burnchain` next to the chainstate.
+\t\t  --contract: optional contract in whose context to evaluate expressions, e.g. SP000000000000000000002Q6VF78.pox-2
+\t\t  --sender: optional principal to use as `tx-sender`.
+\t\tExample:
+\t\t  stacks-node clarity-repl --chainstate-path=/var/stacks/mainnet/chainstate
+
 help\t\tDisplay this help.
 
 OPTIONAL ARGUMENTS:
 
 \t\t--mine-at-height=<height>: optional argument for a miner to not attempt mining until Stacks block has sync'ed to <height>
 
+\t\t--log-format=<text|json>: optional argument to choose the log output format.  `json` emits one JSON object
+\t\tper line (same as STACKS_LOG_JSON=1), and requires a build with the `slog_json` feature.
+
+\t\t--compact-marf=<height>: optional argument to delete the chainstate's MARF tries for abandoned forks below Stacks
+\t\theight <height> before starting the node. Back up the chainstate first; this cannot be interrupted safely.
+
 ", argv[0]);
 }
 
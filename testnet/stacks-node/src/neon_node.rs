@@ -144,6 +144,7 @@ use std::convert::{TryFrom, TryInto};
 use std::default::Default;
 use std::mem;
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::mpsc::{Receiver, SyncSender, TrySendError};
 use std::sync::{atomic::AtomicBool, atomic::Ordering, Arc, Mutex};
 use std::time::Duration;
@@ -167,8 +168,8 @@ use stacks::chainstate::stacks::Error as ChainstateError;
 use stacks::chainstate::stacks::StacksPublicKey;
 use stacks::chainstate::stacks::{
     miner::get_mining_spend_amount, miner::signal_mining_blocked, miner::signal_mining_ready,
-    miner::BlockBuilderSettings, miner::MinerStatus, miner::StacksMicroblockBuilder,
-    StacksBlockBuilder, StacksBlockHeader,
+    miner::BlockAssemblyCursor, miner::BlockBuilderSettings, miner::MinerStatus,
+    miner::StacksMicroblockBuilder, StacksBlockBuilder, StacksBlockHeader,
 };
 use stacks::chainstate::stacks::{
     CoinbasePayload, StacksBlock, StacksMicroblock, StacksTransaction, StacksTransactionSigner,
@@ -177,12 +178,15 @@ use stacks::chainstate::stacks::{
 use stacks::codec::StacksMessageCodec;
 use stacks::core::mempool::MemPoolDB;
 use stacks::core::FIRST_BURNCHAIN_CONSENSUS_HASH;
-use stacks::core::STACKS_EPOCH_2_4_MARKER;
+use stacks::core::STACKS_EPOCH_2_5_MARKER;
 use stacks::cost_estimates::metrics::CostMetric;
 use stacks::cost_estimates::metrics::UnitMetric;
 use stacks::cost_estimates::UnitEstimator;
 use stacks::cost_estimates::{CostEstimator, FeeEstimator};
-use stacks::monitoring::{increment_stx_blocks_mined_counter, update_active_miners_count_gauge};
+use stacks::monitoring::{
+    increment_sortitions_lost_counter, increment_sortitions_won_counter,
+    increment_stx_blocks_mined_counter, observe_mined_tx_sizes, update_active_miners_count_gauge,
+};
 use stacks::net::{
     atlas::{AtlasConfig, AtlasDB, AttachmentInstance},
     db::{LocalPeer, PeerDB},
@@ -191,7 +195,7 @@ use stacks::net::{
     p2p::PeerNetwork,
     relay::Relayer,
     rpc::RPCHandlerArgs,
-    Error as NetError, NetworkResult, PeerAddress, ServiceFlags,
+    Error as NetError, NetworkResult, PeerAddress, RPCMinerInfoData, ServiceFlags,
 };
 use stacks::types::chainstate::{
     BlockHeaderHash, BurnchainHeaderHash, SortitionId, StacksAddress, VRFSeed,
@@ -208,12 +212,14 @@ use stacks::vm::costs::ExecutionCost;
 use crate::burnchains::bitcoin_regtest_controller::BitcoinRegtestController;
 use crate::burnchains::bitcoin_regtest_controller::OngoingBlockCommit;
 use crate::burnchains::make_bitcoin_indexer;
+use crate::config::BurnFeeStrategy;
 use crate::run_loop::neon::Counters;
 use crate::run_loop::neon::RunLoop;
 use crate::run_loop::RegisteredKey;
 use crate::ChainTip;
 
 use super::{BurnchainController, Config, EventDispatcher, Keychain};
+use crate::keychain::{get_registered_vrf_keys, insert_registered_vrf_key, open_vrf_keys_db};
 use crate::syncctl::PoxSyncWatchdogComms;
 use stacks::monitoring;
 
@@ -231,6 +237,17 @@ pub const BLOCK_PROCESSOR_STACK_SIZE: usize = 32 * 1024 * 1024; // 32 MB
 
 type MinedBlocks = HashMap<BlockHeaderHash, (AssembledAnchorBlock, Secp256k1PrivateKey)>;
 
+/// Make the keychain the miner uses, rotating its VRF keys on `burnchain`'s reward cycles if so
+/// configured.
+fn make_miner_keychain(config: &Config, burnchain: &Burnchain) -> Keychain {
+    let keychain = Keychain::default(config.node.seed.clone());
+    if config.miner.rotate_vrf_keys {
+        keychain.with_vrf_key_rotation(burnchain)
+    } else {
+        keychain
+    }
+}
+
 /// Result of running the miner thread.  It could produce a Stacks block or a microblock.
 enum MinerThreadResult {
     Block(
@@ -263,6 +280,8 @@ struct AssembledAnchorBlock {
     attempt: u64,
     /// Epoch timestamp in milliseconds when we started producing the block.
     tenure_begin: u128,
+    /// How far assembling the block got, so a later attempt off of the same parent can resume
+    assembly_cursor: BlockAssemblyCursor,
 }
 
 /// Command types for the relayer thread, issued to it by other threads
@@ -301,6 +320,12 @@ pub struct Globals {
     pub should_keep_running: Arc<AtomicBool>,
     /// Status of our VRF key registration state (shared between the main thread and the relayer)
     leader_key_registration_state: Arc<Mutex<LeaderKeyRegistrationState>>,
+    /// What the miner last reported about its block-commits (shared between the miner and the
+    /// p2p thread, which serves it from /v2/info)
+    miner_info: Arc<Mutex<Option<RPCMinerInfoData>>>,
+    /// Index block hash of the block the miner should build on instead of the canonical Stacks
+    /// tip, if any (shared between the miner and whoever owns the run loop)
+    miner_parent_override: Arc<Mutex<Option<StacksBlockId>>>,
 }
 
 /// Miner chain tip, on top of which to build microblocks
@@ -344,6 +369,7 @@ impl Globals {
         counters: Counters,
         sync_comms: PoxSyncWatchdogComms,
         should_keep_running: Arc<AtomicBool>,
+        miner_parent_override: Arc<Mutex<Option<StacksBlockId>>>,
     ) -> Globals {
         Globals {
             last_sortition: Arc::new(Mutex::new(None)),
@@ -357,9 +383,46 @@ impl Globals {
             leader_key_registration_state: Arc::new(Mutex::new(
                 LeaderKeyRegistrationState::Inactive,
             )),
+            miner_info: Arc::new(Mutex::new(None)),
+            miner_parent_override,
         }
     }
 
+    /// Get the block the miner has been told to build on instead of the canonical Stacks tip
+    pub fn get_miner_parent_override(&self) -> Option<StacksBlockId> {
+        match self.miner_parent_override.lock() {
+            Ok(parent_override) => parent_override.clone(),
+            Err(_) => {
+                error!("Miner parent override mutex poisoned!");
+                panic!();
+            }
+        }
+    }
+
+    /// Get what the miner last reported about its block-commits
+    pub fn get_miner_info(&self) -> Option<RPCMinerInfoData> {
+        match self.miner_info.lock() {
+            Ok(miner_info) => miner_info.clone(),
+            Err(_) => {
+                error!("Miner info mutex poisoned!");
+                panic!();
+            }
+        }
+    }
+
+    /// Set what the miner last reported about its block-commits
+    pub fn set_miner_info(&self, miner_info: RPCMinerInfoData) {
+        match self.miner_info.lock() {
+            Ok(mut miner_info_opt) => {
+                miner_info_opt.replace(miner_info);
+            }
+            Err(_) => {
+                error!("Miner info mutex poisoned!");
+                panic!();
+            }
+        };
+    }
+
     /// Get the last sortition processed by the relayer thread
     pub fn get_last_sortition(&self) -> Option<BlockSnapshot> {
         match self.last_sortition.lock() {
@@ -549,6 +612,10 @@ pub struct StacksNode {
     pub p2p_thread_handle: JoinHandle<()>,
     /// handle to the relayer thread
     pub relayer_thread_handle: JoinHandle<()>,
+    /// Keychain used to derive (and re-derive) this miner's VRF keys
+    keychain: Keychain,
+    /// Where this miner remembers the VRF keys it registered
+    vrf_keys_db_path: PathBuf,
 }
 
 /// Fault injection logic to artificially increase the length of a tenure.
@@ -1326,7 +1393,7 @@ impl BlockMinerThread {
             apparent_sender: sender,
             key_block_ptr: key.block_height as u32,
             key_vtxindex: key.op_vtxindex as u16,
-            memo: vec![STACKS_EPOCH_2_4_MARKER],
+            memo: vec![STACKS_EPOCH_2_5_MARKER],
             new_seed: vrf_seed,
             parent_block_ptr,
             parent_vtxindex,
@@ -1353,7 +1420,77 @@ impl BlockMinerThread {
         ret
     }
 
+    /// Load up the header of the block that the miner has been told to build on instead of the
+    /// canonical Stacks tip, if any.  The override is only honored if it names a processed
+    /// Stacks block whose sortition is on this tenure's burnchain fork; otherwise it is ignored
+    /// (and the miner builds on the canonical tip).
+    fn load_parent_override(
+        &self,
+        burn_db: &SortitionDB,
+        chain_state: &StacksChainState,
+    ) -> Option<StacksHeaderInfo> {
+        let parent_id = self.globals.get_miner_parent_override()?;
+        let parent_header = match StacksChainState::get_stacks_block_header_info_by_index_block_hash(
+            chain_state.db(),
+            &parent_id,
+        ) {
+            Ok(Some(parent_header)) => parent_header,
+            Ok(None) => {
+                warn!(
+                    "Miner: ignoring parent block override: not a processed Stacks block";
+                    "parent_index_block_hash" => %parent_id
+                );
+                return None;
+            }
+            Err(e) => {
+                warn!(
+                    "Miner: ignoring parent block override: failed to load its header";
+                    "parent_index_block_hash" => %parent_id,
+                    "err" => ?e
+                );
+                return None;
+            }
+        };
+
+        let ic = burn_db.index_conn();
+        let on_burnchain_fork = SortitionDB::get_block_snapshot_consensus(
+            burn_db.conn(),
+            &parent_header.consensus_hash,
+        )
+        .expect("FATAL: failed to query sortition DB")
+        .and_then(|parent_sn| {
+            SortitionDB::get_ancestor_snapshot(
+                &ic,
+                parent_sn.block_height,
+                &self.burn_block.sortition_id,
+            )
+            .expect("FATAL: failed to query sortition DB")
+        })
+        .map(|ancestor_sn| ancestor_sn.consensus_hash == parent_header.consensus_hash)
+        .unwrap_or(false);
+        if !on_burnchain_fork {
+            warn!(
+                "Miner: ignoring parent block override: not on this tenure's burnchain fork";
+                "parent_index_block_hash" => %parent_id,
+                "parent_consensus_hash" => %parent_header.consensus_hash,
+                "burn_block_sortition_id" => %self.burn_block.sortition_id
+            );
+            return None;
+        }
+
+        info!(
+            "Miner: building on overridden parent block instead of the canonical Stacks tip";
+            "parent_index_block_hash" => %parent_id,
+            "parent_consensus_hash" => %parent_header.consensus_hash,
+            "parent_block_hash" => %parent_header.anchored_header.block_hash(),
+            "parent_height" => parent_header.stacks_block_height
+        );
+        Some(parent_header)
+    }
+
     /// Load up the parent block info for mining.
+    /// This is the canonical Stacks tip, unless the miner has been told to build on another
+    /// block (see `load_parent_override()`).
     /// If there's no parent because this is the first block, then return the genesis block's info.
     /// If we can't find the parent in the DB but we expect one, return None.
     fn load_block_parent_info(
@@ -1361,10 +1498,17 @@ impl BlockMinerThread {
         burn_db: &mut SortitionDB,
         chain_state: &mut StacksChainState,
     ) -> Option<ParentStacksBlockInfo> {
-        if let Some(stacks_tip) = chain_state
-            .get_stacks_chain_tip(burn_db)
-            .expect("FATAL: could not query chain tip")
-        {
+        let mine_tip = match self.load_parent_override(burn_db, chain_state) {
+            Some(parent_header) => Some((
+                parent_header.consensus_hash,
+                parent_header.anchored_header.block_hash(),
+            )),
+            None => chain_state
+                .get_stacks_chain_tip(burn_db)
+                .expect("FATAL: could not query chain tip")
+                .map(|stacks_tip| (stacks_tip.consensus_hash, stacks_tip.anchored_block_hash)),
+        };
+        if let Some((mine_tip_ch, mine_tip_bh)) = mine_tip {
             let miner_address = self
                 .keychain
                 .origin_address(self.config.is_mainnet())
@@ -1374,8 +1518,8 @@ impl BlockMinerThread {
                 burn_db,
                 &self.burn_block,
                 miner_address,
-                &stacks_tip.consensus_hash,
-                &stacks_tip.anchored_block_hash,
+                &mine_tip_ch,
+                &mine_tip_bh,
             ) {
                 Ok(parent_info) => Some(parent_info),
                 Err(Error::BurnchainTipChanged) => {
@@ -1687,6 +1831,112 @@ impl BlockMinerThread {
         microblock_info_opt.map(|(stream, _)| stream)
     }
 
+    /// Get the miner's spendable UTXO balance, if the burn fee strategy needs it.
+    /// Returns None if the strategy doesn't need it, or if it can't be queried.
+    fn get_burn_fee_balance(
+        &self,
+        bitcoin_controller: &BitcoinRegtestController,
+        target_epoch_id: StacksEpochId,
+    ) -> Option<u64> {
+        if self.config.miner.burn_fee_strategy != BurnFeeStrategy::BalancePercent {
+            return None;
+        }
+        let mut op_signer = self
+            .keychain
+            .generate_op_signer_for_key(&self.registered_key)?;
+        let utxos = bitcoin_controller.get_utxos(
+            target_epoch_id,
+            &op_signer.get_public_key(),
+            0,
+            None,
+            self.burn_block.block_height,
+        )?;
+        Some(utxos.total_available())
+    }
+
+    /// Get the total burns (including sunset burns) of the winning block-commits in the most
+    /// recent `burn_fee_follow_window` sortitions, if the burn fee strategy needs them.
+    fn get_recent_winning_burns(&self, burn_db: &SortitionDB) -> Vec<u64> {
+        let mut burns = vec![];
+        if self.config.miner.burn_fee_strategy != BurnFeeStrategy::FollowWinners {
+            return burns;
+        }
+        let mut cursor = self.burn_block.clone();
+        for _ in 0..self.config.miner.burn_fee_follow_window {
+            if cursor.sortition {
+                match SortitionDB::get_block_commit(
+                    burn_db.conn(),
+                    &cursor.winning_block_txid,
+                    &cursor.sortition_id,
+                ) {
+                    Ok(Some(commit)) => {
+                        burns.push(commit.burn_fee.saturating_add(commit.sunset_burn))
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        warn!("Relayer: Failed to load winning block-commit: {:?}", &e);
+                        break;
+                    }
+                }
+            }
+            cursor = match SortitionDB::get_block_snapshot(
+                burn_db.conn(),
+                &cursor.parent_sortition_id,
+            ) {
+                Ok(Some(parent)) => parent,
+                Ok(None) => break,
+                Err(e) => {
+                    warn!("Relayer: Failed to load parent snapshot: {:?}", &e);
+                    break;
+                }
+            };
+        }
+        burns
+    }
+
+    /// Get whether or not the miner won each of the sortitions it entered in the most recent
+    /// `burn_fee_win_rate_window` burn blocks, oldest first, if the burn fee strategy needs them.
+    /// A block-commit is the miner's if it uses the miner's registered VRF key.
+    fn get_recent_sortitions_won(&self, burn_db: &SortitionDB) -> Vec<bool> {
+        let mut sortitions_won = vec![];
+        if self.config.miner.burn_fee_strategy != BurnFeeStrategy::WinRate {
+            return sortitions_won;
+        }
+        let mut cursor = self.burn_block.clone();
+        for _ in 0..self.config.miner.burn_fee_win_rate_window {
+            let commits =
+                match SortitionDB::get_block_commits_by_block(burn_db.conn(), &cursor.sortition_id)
+                {
+                    Ok(commits) => commits,
+                    Err(e) => {
+                        warn!("Relayer: Failed to load block-commits: {:?}", &e);
+                        break;
+                    }
+                };
+            let my_commit = commits.into_iter().find(|commit| {
+                u64::from(commit.key_block_ptr) == self.registered_key.block_height
+                    && u32::from(commit.key_vtxindex) == self.registered_key.op_vtxindex
+            });
+            if let Some(my_commit) = my_commit {
+                sortitions_won
+                    .push(cursor.sortition && cursor.winning_block_txid == my_commit.txid);
+            }
+            cursor = match SortitionDB::get_block_snapshot(
+                burn_db.conn(),
+                &cursor.parent_sortition_id,
+            ) {
+                Ok(Some(parent)) => parent,
+                Ok(None) => break,
+                Err(e) => {
+                    warn!("Relayer: Failed to load parent snapshot: {:?}", &e);
+                    break;
+                }
+            };
+        }
+        sortitions_won.reverse();
+        sortitions_won
+    }
+
     /// Produce the block-commit for this anchored block, if we can.
     /// Returns the op on success
     /// Returns None if we fail somehow.
@@ -1694,6 +1944,7 @@ impl BlockMinerThread {
         &self,
         burn_db: &mut SortitionDB,
         chain_state: &mut StacksChainState,
+        bitcoin_controller: &BitcoinRegtestController,
         block_hash: BlockHeaderHash,
         parent_block_burn_height: u64,
         parent_winning_vtxindex: u16,
@@ -1717,7 +1968,35 @@ impl BlockMinerThread {
         };
 
         // let burn_fee_cap = self.config.burnchain.burn_fee_cap;
-        let burn_fee_cap = get_mining_spend_amount(self.globals.get_miner_status());
+        let spend_cap = get_mining_spend_amount(self.globals.get_miner_status());
+        let recent_sortitions_won = self.get_recent_sortitions_won(burn_db);
+        let burn_fee_cap = self.config.miner.choose_burn_fee(
+            spend_cap,
+            self.get_burn_fee_balance(bitcoin_controller, target_epoch_id),
+            &self.get_recent_winning_burns(burn_db),
+            &recent_sortitions_won,
+        );
+        let (recent_sortitions_won, recent_sortitions_entered) =
+            if self.config.miner.burn_fee_strategy == BurnFeeStrategy::WinRate {
+                let won = recent_sortitions_won.iter().filter(|won| **won).count() as u64;
+                (Some(won), Some(recent_sortitions_won.len() as u64))
+            } else {
+                (None, None)
+            };
+        info!(
+            "Relayer: Chose block-commit burn";
+            "strategy" => ?self.config.miner.burn_fee_strategy,
+            "spend_cap" => spend_cap,
+            "burn_fee_cap" => burn_fee_cap,
+            "recent_sortitions_won" => ?recent_sortitions_won,
+            "recent_sortitions_entered" => ?recent_sortitions_entered
+        );
+        self.globals.set_miner_info(RPCMinerInfoData {
+            last_burn_fee: burn_fee_cap,
+            last_burn_fee_block_height: self.burn_block.block_height,
+            recent_sortitions_won,
+            recent_sortitions_entered,
+        });
         let sunset_burn = self.burnchain.expected_sunset_burn(
             self.burn_block.block_height + 1,
             burn_fee_cap,
@@ -1905,79 +2184,94 @@ impl BlockMinerThread {
             &mut parent_block_info,
         );
 
+        // pick up where the furthest attempt off of this same parent left off, if there was one
+        let parent_block_id = parent_block_info.stacks_parent_header.index_block_hash();
+        let resume_from =
+            Self::find_inflight_mined_blocks(self.burn_block.block_height, &self.last_mined_blocks)
+                .into_iter()
+                .filter(|prev_block| {
+                    prev_block.my_burn_hash == self.burn_block.burn_header_hash
+                        && prev_block.assembly_cursor.parent_block_id == parent_block_id
+                })
+                .max_by_key(|prev_block| prev_block.attempt)
+                .map(|prev_block| prev_block.assembly_cursor.clone());
+
         // build the block itself
-        let (anchored_block, _, _) = match StacksBlockBuilder::build_anchored_block(
-            &chain_state,
-            &burn_db.index_conn(),
-            &mut mem_pool,
-            &parent_block_info.stacks_parent_header,
-            parent_block_info.parent_block_total_burn,
-            vrf_proof.clone(),
-            mblock_pubkey_hash,
-            &coinbase_tx,
-            self.config.make_block_builder_settings(
-                attempt,
-                false,
-                self.globals.get_miner_status(),
-            ),
-            Some(&self.event_dispatcher),
-        ) {
-            Ok(block) => block,
-            Err(ChainstateError::InvalidStacksMicroblock(msg, mblock_header_hash)) => {
-                // part of the parent microblock stream is invalid, so try again
-                info!("Parent microblock stream is invalid; trying again without the offender {} (msg: {})", &mblock_header_hash, &msg);
-
-                // truncate the stream
-                parent_block_info.stacks_parent_header.microblock_tail = match microblocks_opt {
-                    Some(microblocks) => {
-                        let mut tail = None;
-                        for mblock in microblocks.into_iter() {
-                            if mblock.block_hash() == mblock_header_hash {
-                                break;
+        let (anchored_block, _, _, assembly_cursor, miner_metrics) =
+            match StacksBlockBuilder::build_anchored_block_resumable(
+                &chain_state,
+                &burn_db.index_conn(),
+                &mut mem_pool,
+                &parent_block_info.stacks_parent_header,
+                parent_block_info.parent_block_total_burn,
+                vrf_proof.clone(),
+                mblock_pubkey_hash,
+                &coinbase_tx,
+                self.config.make_block_builder_settings(
+                    attempt,
+                    false,
+                    self.globals.get_miner_status(),
+                ),
+                Some(&self.event_dispatcher),
+                resume_from.as_ref(),
+            ) {
+                Ok(block) => block,
+                Err(ChainstateError::InvalidStacksMicroblock(msg, mblock_header_hash)) => {
+                    // part of the parent microblock stream is invalid, so try again
+                    info!("Parent microblock stream is invalid; trying again without the offender {} (msg: {})", &mblock_header_hash, &msg);
+
+                    // truncate the stream
+                    parent_block_info.stacks_parent_header.microblock_tail = match microblocks_opt {
+                        Some(microblocks) => {
+                            let mut tail = None;
+                            for mblock in microblocks.into_iter() {
+                                if mblock.block_hash() == mblock_header_hash {
+                                    break;
+                                }
+                                tail = Some(mblock);
+                            }
+                            if let Some(ref t) = &tail {
+                                debug!(
+                                    "New parent microblock stream tail is {} (seq {})",
+                                    t.block_hash(),
+                                    t.header.sequence
+                                );
                             }
-                            tail = Some(mblock);
+                            tail.map(|t| t.header)
                         }
-                        if let Some(ref t) = &tail {
-                            debug!(
-                                "New parent microblock stream tail is {} (seq {})",
-                                t.block_hash(),
-                                t.header.sequence
-                            );
+                        None => None,
+                    };
+
+                    // try again
+                    match StacksBlockBuilder::build_anchored_block_resumable(
+                        &chain_state,
+                        &burn_db.index_conn(),
+                        &mut mem_pool,
+                        &parent_block_info.stacks_parent_header,
+                        parent_block_info.parent_block_total_burn,
+                        vrf_proof.clone(),
+                        mblock_pubkey_hash,
+                        &coinbase_tx,
+                        self.config.make_block_builder_settings(
+                            attempt,
+                            false,
+                            self.globals.get_miner_status(),
+                        ),
+                        Some(&self.event_dispatcher),
+                        resume_from.as_ref(),
+                    ) {
+                        Ok(block) => block,
+                        Err(e) => {
+                            error!("Relayer: Failure mining anchor block even after removing offending microblock {}: {}", &mblock_header_hash, &e);
+                            return None;
                         }
-                        tail.map(|t| t.header)
-                    }
-                    None => None,
-                };
-
-                // try again
-                match StacksBlockBuilder::build_anchored_block(
-                    &chain_state,
-                    &burn_db.index_conn(),
-                    &mut mem_pool,
-                    &parent_block_info.stacks_parent_header,
-                    parent_block_info.parent_block_total_burn,
-                    vrf_proof.clone(),
-                    mblock_pubkey_hash,
-                    &coinbase_tx,
-                    self.config.make_block_builder_settings(
-                        attempt,
-                        false,
-                        self.globals.get_miner_status(),
-                    ),
-                    Some(&self.event_dispatcher),
-                ) {
-                    Ok(block) => block,
-                    Err(e) => {
-                        error!("Relayer: Failure mining anchor block even after removing offending microblock {}: {}", &mblock_header_hash, &e);
-                        return None;
                     }
                 }
-            }
-            Err(e) => {
-                error!("Relayer: Failure mining anchored block: {}", e);
-                return None;
-            }
-        };
+                Err(e) => {
+                    error!("Relayer: Failure mining anchored block: {}", e);
+                    return None;
+                }
+            };
 
         info!(
             "Relayer: Succeeded assembling {} block #{}: {}, with {} txs, attempt {}",
@@ -1989,13 +2283,19 @@ impl BlockMinerThread {
             anchored_block.header.total_work.work,
             anchored_block.block_hash(),
             anchored_block.txs.len(),
-            attempt
+            attempt;
+            "txs_considered" => miner_metrics.txs_considered,
+            "txs_included" => miner_metrics.txs_included,
+            "txs_resumed" => miner_metrics.txs_resumed,
+            "deadline_reached" => miner_metrics.deadline_reached,
+            "assembly_time_ms" => miner_metrics.assembly_time_ms
         );
 
         // let's commit
         let op = self.make_block_commit(
             &mut burn_db,
             &mut chain_state,
+            &bitcoin_controller,
             anchored_block.block_hash(),
             parent_block_info.parent_block_burn_height,
             parent_block_info.parent_winning_vtxindex,
@@ -2026,8 +2326,13 @@ impl BlockMinerThread {
                 &chain_state,
                 self.config.miner.unprocessed_block_deadline_secs,
             );
-            if stacks_tip.anchored_block_hash != anchored_block.header.parent_block
-                || parent_block_info.parent_consensus_hash != stacks_tip.consensus_hash
+
+            // a block built on an overridden parent is not expected to extend the Stacks tip
+            let parent_overridden = self.globals.get_miner_parent_override()
+                == Some(parent_block_info.stacks_parent_header.index_block_hash());
+            if (!parent_overridden
+                && (stacks_tip.anchored_block_hash != anchored_block.header.parent_block
+                    || parent_block_info.parent_consensus_hash != stacks_tip.consensus_hash))
                 || cur_burn_chain_tip.burn_header_hash != self.burn_block.burn_header_hash
                 || is_miner_blocked
                 || has_unprocessed
@@ -2058,7 +2363,19 @@ impl BlockMinerThread {
             }
         }
 
-        let mut op_signer = self.keychain.generate_op_signer();
+        let mut op_signer = match self
+            .keychain
+            .generate_op_signer_for_key(&self.registered_key)
+        {
+            Some(op_signer) => op_signer,
+            None => {
+                warn!(
+                    "Relayer: VRF key {} was not derived by this keychain; not submitting block-commit",
+                    &self.registered_key.vrf_public_key.to_hex()
+                );
+                return None;
+            }
+        };
         debug!(
             "Relayer: Submit block-commit";
             "block_hash" => %anchored_block.block_hash(),
@@ -2086,6 +2403,7 @@ impl BlockMinerThread {
                 debug!("Relayer: Mock-mining enabled; not sending Bitcoin transaction");
             }
         }
+        observe_mined_tx_sizes(&anchored_block);
 
         Some(MinerThreadResult::Block(
             AssembledAnchorBlock {
@@ -2096,6 +2414,7 @@ impl BlockMinerThread {
                 anchored_block,
                 attempt,
                 tenure_begin,
+                assembly_cursor,
             },
             microblock_private_key,
             bitcoin_controller.get_ongoing_commit(),
@@ -2135,7 +2454,7 @@ impl RelayerThread {
         )
         .expect("Database failure opening mempool");
 
-        let keychain = Keychain::default(config.node.seed.clone());
+        let keychain = make_miner_keychain(&config, &runloop.get_burnchain());
         let bitcoin_controller = BitcoinRegtestController::new_dummy(config.clone());
 
         RelayerThread {
@@ -2314,8 +2633,9 @@ impl RelayerThread {
         }
 
         // synchronize unconfirmed tx index to p2p thread
-        self.with_chainstate(|relayer_thread, _sortdb, chainstate, _mempool| {
+        self.with_chainstate(|relayer_thread, _sortdb, chainstate, mempool| {
             relayer_thread.globals.send_unconfirmed_txs(chainstate);
+            monitoring::update_mempool_size(mempool);
         });
 
         // resume mining if we blocked it, and if we've done the requisite download
@@ -2539,6 +2859,7 @@ impl RelayerThread {
             );
 
             increment_stx_blocks_mined_counter();
+            increment_sortitions_won_counter();
             let has_new_data = match self.accept_winning_tenure(
                 &mined_block,
                 &consensus_hash,
@@ -2636,6 +2957,9 @@ impl RelayerThread {
                 "Relayer: Did not win sortition in {}, winning block was {}/{}",
                 &burn_hash, &consensus_hash, &block_header_hash
             );
+            if self.config.node.miner {
+                increment_sortitions_lost_counter();
+            }
             miner_tip = None;
         }
 
@@ -2877,7 +3201,7 @@ impl RelayerThread {
                 .expect("FATAL: failed to query sortition DB")
                 .expect("FATAL: no epoch defined")
                 .epoch_id;
-        let (vrf_pk, _) = self.keychain.make_vrf_keypair(burn_block.block_height);
+        let (vrf_pk, _) = self.keychain.rotate_vrf_keypair(burn_block.block_height);
 
         debug!(
             "Submit leader-key-register for {} {}",
@@ -3578,7 +3902,7 @@ impl PeerThread {
             .make_cost_metric()
             .unwrap_or_else(|| Box::new(UnitMetric));
 
-        let mempool = MemPoolDB::open(
+        let mut mempool = MemPoolDB::open(
             config.is_mainnet(),
             config.burnchain.chain_id,
             &config.get_chainstate_path_str(),
@@ -3586,6 +3910,13 @@ impl PeerThread {
             metric,
         )
         .expect("Database failure opening mempool");
+        mempool.enable_rbf = config.mempool.enable_rbf;
+        mempool.rbf_fee_increase_pct = config.mempool.rbf_fee_increase_pct;
+        mempool.min_fee_rate = config.mempool.min_fee_rate;
+        mempool.validate_sponsor_balance = config.mempool.validate_sponsor_balance;
+        mempool.max_tx_age = config.mempool.mempool_timeout;
+        mempool.max_tx_count = config.mempool.max_tx_count;
+        mempool.max_total_bytes = config.mempool.max_total_bytes;
 
         mempool
     }
@@ -3743,6 +4074,7 @@ impl PeerThread {
                 cost_estimator: Some(cost_estimator.as_ref()),
                 cost_metric: Some(cost_metric.as_ref()),
                 fee_estimator: fee_estimator.map(|boxed_estimator| boxed_estimator.as_ref()),
+                miner_info: p2p_thread.globals.get_miner_info(),
                 ..RPCHandlerArgs::default()
             };
             p2p_thread.with_network(|_, net| {
@@ -3893,7 +4225,7 @@ impl StacksNode {
             .make_cost_metric()
             .unwrap_or_else(|| Box::new(UnitMetric));
 
-        let mempool = MemPoolDB::open(
+        let mut mempool = MemPoolDB::open(
             config.is_mainnet(),
             config.burnchain.chain_id,
             &config.get_chainstate_path_str(),
@@ -3901,6 +4233,13 @@ impl StacksNode {
             metric,
         )
         .expect("BUG: failed to instantiate mempool");
+        mempool.enable_rbf = config.mempool.enable_rbf;
+        mempool.rbf_fee_increase_pct = config.mempool.rbf_fee_increase_pct;
+        mempool.min_fee_rate = config.mempool.min_fee_rate;
+        mempool.validate_sponsor_balance = config.mempool.validate_sponsor_balance;
+        mempool.max_tx_age = config.mempool.mempool_timeout;
+        mempool.max_tx_count = config.mempool.max_tx_count;
+        mempool.max_total_bytes = config.mempool.max_total_bytes;
 
         mempool
     }
@@ -3995,12 +4334,14 @@ impl StacksNode {
             tx.commit().unwrap();
         }
 
-        // update services to indicate we can support mempool sync
+        // update services to indicate we can support mempool sync and block streams
         {
             let mut tx = peerdb.tx_begin().unwrap();
             PeerDB::set_local_services(
                 &mut tx,
-                (ServiceFlags::RPC as u16) | (ServiceFlags::RELAY as u16),
+                (ServiceFlags::RPC as u16)
+                    | (ServiceFlags::RELAY as u16)
+                    | (ServiceFlags::BLOCK_STREAM as u16),
             )
             .unwrap();
             tx.commit().unwrap();
@@ -4158,7 +4499,7 @@ impl StacksNode {
         let is_miner = runloop.is_miner();
         let burnchain = runloop.get_burnchain();
         let atlas_config = AtlasConfig::default(config.is_mainnet());
-        let keychain = Keychain::default(config.node.seed.clone());
+        let keychain = make_miner_keychain(&config, &burnchain);
 
         // we can call _open_ here rather than _connect_, since connect is first called in
         //   make_genesis_block
@@ -4189,13 +4530,24 @@ impl StacksNode {
         // setup initial key registration
         let leader_key_registration_state = if config.node.mock_mining {
             // mock mining, pretend to have a registered key
-            let (vrf_public_key, _) = keychain.make_vrf_keypair(VRF_MOCK_MINER_KEY);
+            let (vrf_public_key, _) = keychain.rotate_vrf_keypair(VRF_MOCK_MINER_KEY);
             LeaderKeyRegistrationState::Active(RegisteredKey {
                 target_block_height: VRF_MOCK_MINER_KEY,
                 block_height: 1,
                 op_vtxindex: 1,
                 vrf_public_key,
             })
+        } else if let Some(registered_key) =
+            Self::load_registered_vrf_key(&config, &keychain, &sortdb)
+        {
+            // restarted, so carry on with the key we registered before
+            info!(
+                "Resuming with registered VRF key {} at ({},{})",
+                &registered_key.vrf_public_key.to_hex(),
+                registered_key.block_height,
+                registered_key.op_vtxindex
+            );
+            LeaderKeyRegistrationState::Active(registered_key)
         } else {
             LeaderKeyRegistrationState::Inactive
         };
@@ -4234,6 +4586,77 @@ impl StacksNode {
             is_miner,
             p2p_thread_handle,
             relayer_thread_handle,
+            keychain,
+            vrf_keys_db_path: config.get_vrf_keys_db_path(),
+        }
+    }
+
+    /// Find the most recent VRF key this miner registered that it can still use at the canonical
+    /// burnchain tip: it must be for the current reward cycle (if VRF keys rotate), it must be
+    /// derivable from our seed, and its registration must be on the canonical burnchain fork.
+    fn load_registered_vrf_key(
+        config: &Config,
+        keychain: &Keychain,
+        sortdb: &SortitionDB,
+    ) -> Option<RegisteredKey> {
+        if !config.node.miner {
+            return None;
+        }
+        let tip = SortitionDB::get_canonical_burn_chain_tip(sortdb.conn())
+            .expect("FATAL: failed to query canonical burn chain tip");
+        let reward_cycle = keychain.vrf_key_reward_cycle(tip.block_height).unwrap_or(0);
+        let conn = match open_vrf_keys_db(&config.get_vrf_keys_db_path()) {
+            Ok(conn) => conn,
+            Err(e) => {
+                warn!("Failed to open VRF keys DB: {:?}", &e);
+                return None;
+            }
+        };
+        let registered_keys = match get_registered_vrf_keys(&conn, reward_cycle) {
+            Ok(keys) => keys,
+            Err(e) => {
+                warn!("Failed to load registered VRF keys: {:?}", &e);
+                return None;
+            }
+        };
+        let ic = sortdb.index_handle(&tip.sortition_id);
+        for key in registered_keys.into_iter() {
+            if keychain.rotate_vrf_keypair(key.target_block_height).0 != key.vrf_public_key {
+                continue;
+            }
+            match ic.get_leader_key_at(key.block_height, key.op_vtxindex) {
+                Ok(Some(op)) if op.public_key == key.vrf_public_key => {
+                    return Some(key);
+                }
+                Ok(_) => {
+                    debug!(
+                        "VRF key {} is not registered on the canonical burnchain fork",
+                        &key.vrf_public_key.to_hex()
+                    );
+                }
+                Err(e) => {
+                    warn!("Failed to query leader key: {:?}", &e);
+                }
+            }
+        }
+        None
+    }
+
+    /// Remember the VRF key registration that just became active, so we can keep using it across
+    /// restarts.
+    fn store_registered_vrf_key(&self, key: &RegisteredKey) {
+        let reward_cycle = self
+            .keychain
+            .vrf_key_reward_cycle(key.target_block_height)
+            .unwrap_or(0);
+        let res = open_vrf_keys_db(&self.vrf_keys_db_path)
+            .and_then(|conn| insert_registered_vrf_key(&conn, reward_cycle, key));
+        if let Err(e) = res {
+            warn!(
+                "Failed to store registered VRF key {}: {:?}",
+                &key.vrf_public_key.to_hex(),
+                &e
+            );
         }
     }
 
@@ -4256,6 +4679,21 @@ impl StacksNode {
                 let leader_key_registration_state =
                     self.globals.get_leader_key_registration_state();
                 match leader_key_registration_state {
+                    LeaderKeyRegistrationState::Active(ref key)
+                        if self.keychain.vrf_key_reward_cycle(key.target_block_height)
+                            != self
+                                .keychain
+                                .vrf_key_reward_cycle(burnchain_tip.block_height) =>
+                    {
+                        info!(
+                            "Tenure: VRF key {:?} is from a past reward cycle. Registering a new one.",
+                            &key.vrf_public_key
+                        );
+                        self.globals
+                            .relay_send
+                            .send(RelayerDirective::RegisterKey(burnchain_tip))
+                            .is_ok()
+                    }
                     LeaderKeyRegistrationState::Active(ref key) => {
                         debug!(
                             "Tenure: Using key {:?} off of {}",
@@ -4378,8 +4816,16 @@ impl StacksNode {
 
         let num_key_registers = key_registers.len();
 
-        self.globals
-            .try_activate_leader_key_registration(block_height, key_registers);
+        if self
+            .globals
+            .try_activate_leader_key_registration(block_height, key_registers)
+        {
+            if let LeaderKeyRegistrationState::Active(ref key) =
+                self.globals.get_leader_key_registration_state()
+            {
+                self.store_registered_vrf_key(key);
+            }
+        }
 
         debug!(
             "Processed burnchain state at height {}: {} leader keys, {} block-commits (ibd = {})",
@@ -17,7 +17,7 @@ use stacks::deps::ctrlc as termination;
 use stacks::deps::ctrlc::SignalId;
 
 use stacks::burnchains::bitcoin::address::{BitcoinAddress, LegacyBitcoinAddressType};
-use stacks::burnchains::Burnchain;
+use stacks::burnchains::{Burnchain, Error as burnchain_error};
 use stacks::chainstate::burn::db::sortdb::SortitionDB;
 use stacks::chainstate::burn::BlockSnapshot;
 use stacks::chainstate::coordinator::comm::{CoordinatorChannels, CoordinatorReceivers};
@@ -27,7 +27,7 @@ use stacks::chainstate::coordinator::{
     ChainsCoordinatorConfig, CoordinatorCommunication, Error as coord_error,
 };
 use stacks::chainstate::stacks::db::{ChainStateBootData, StacksChainState};
-use stacks::core::StacksEpochId;
+use stacks::core::{StacksEpoch, StacksEpochId};
 use stacks::net::atlas::{AtlasConfig, Attachment, AttachmentInstance, ATTACHMENTS_CHANNEL_SIZE};
 use stacks::util_lib::db::Error as db_error;
 use stx_genesis::GenesisData;
@@ -51,6 +51,7 @@ use stacks_common::util::sleep_ms;
 
 use libc;
 use stacks::util::hash::Hash160;
+use stacks_common::types::chainstate::StacksBlockId;
 use stacks_common::types::PublicKey;
 pub const STDERR: i32 = 2;
 
@@ -155,6 +156,9 @@ pub struct RunLoop {
     /// NOTE: this is duplicated in self.globals, but it needs to be accessible before globals is
     /// instantiated (namely, so the test framework can access it).
     miner_status: Arc<Mutex<MinerStatus>>,
+    /// NOTE: this is duplicated in self.globals, but it needs to be accessible before globals is
+    /// instantiated (namely, so the test framework can access it).
+    miner_parent_override: Arc<Mutex<Option<StacksBlockId>>>,
 }
 
 /// Write to stderr in an async-safe manner.
@@ -184,8 +188,13 @@ impl RunLoop {
         let miner_status = Arc::new(Mutex::new(MinerStatus::make_ready(
             config.burnchain.burn_fee_cap,
         )));
+        let miner_parent_override =
+            Arc::new(Mutex::new(config.miner.parent_block_override.clone()));
 
-        let mut event_dispatcher = EventDispatcher::new();
+        let mut event_dispatcher = EventDispatcher::new_with_db_path(
+            config.get_event_observers_db_path(),
+            config.node.event_observer_max_attempts,
+        );
         for observer in config.events_observers.iter() {
             event_dispatcher.register_observer(observer);
         }
@@ -203,6 +212,7 @@ impl RunLoop {
             burnchain: None,
             pox_watchdog_comms,
             miner_status,
+            miner_parent_override,
         }
     }
 
@@ -260,8 +270,10 @@ impl RunLoop {
         self.pox_watchdog_comms.clone()
     }
 
+    /// Get the switch that stops this run loop when set to false.
+    /// This can be called before `start()`.
     pub fn get_termination_switch(&self) -> Arc<AtomicBool> {
-        self.get_globals().should_keep_running.clone()
+        self.should_keep_running.clone()
     }
 
     pub fn get_burnchain(&self) -> Burnchain {
@@ -280,6 +292,13 @@ impl RunLoop {
         self.miner_status.clone()
     }
 
+    /// Get the index block hash of the block the miner builds on instead of the canonical Stacks
+    /// tip.  Setting it to `None` makes the miner build on the canonical tip again.
+    /// This can be called before `start()`.
+    pub fn get_miner_parent_override(&self) -> Arc<Mutex<Option<StacksBlockId>>> {
+        self.miner_parent_override.clone()
+    }
+
     /// Set up termination handler.  Have a signal set the `should_keep_running` atomic bool to
     /// false.  Panics of called more than once.
     fn setup_termination_handler(&self) {
@@ -362,6 +381,36 @@ impl RunLoop {
         }
     }
 
+    /// Make sure that configured epochs do not move an epoch transition which the burnchain
+    /// has already reached.
+    /// Panics if they do.
+    fn check_configured_epochs(burnchain: &Burnchain, epochs: &[StacksEpoch]) {
+        let (sortdb, burndb) = match burnchain.open_db(false) {
+            Ok(dbs) => dbs,
+            // nothing processed yet, so every configured height is still ahead of us
+            Err(burnchain_error::DBError(db_error::NoDBError)) => return,
+            Err(e) => {
+                panic!("FATAL: unable to open burnchain databases: {:?}", &e);
+            }
+        };
+        let burn_tip_height = match burndb.get_canonical_chain_tip() {
+            Ok(tip) => tip.block_height,
+            Err(burnchain_error::MissingParentBlock) => return,
+            Err(e) => {
+                panic!("FATAL: unable to load the burnchain tip: {:?}", &e);
+            }
+        };
+        let current_epochs = SortitionDB::get_stacks_epochs(sortdb.conn())
+            .expect("FATAL: failed to load epochs from the sortition database");
+
+        if let Err(msg) =
+            Config::check_epochs_against_burnchain_tip(epochs, &current_epochs, burn_tip_height)
+        {
+            error!("FATAL: invalid epoch configuration: {}", msg);
+            panic!();
+        }
+    }
+
     /// Instantiate the burnchain client and databases.
     /// Fetches headers and instantiates the burnchain.
     /// Panics on failure.
@@ -403,6 +452,10 @@ impl RunLoop {
             }
         }
 
+        if self.config.burnchain.epochs.is_some() {
+            Self::check_configured_epochs(&burnchain, &epochs);
+        }
+
         info!("Start syncing Bitcoin headers, feel free to grab a cup of coffee, this can take a while");
 
         let burnchain_config = burnchain_controller.get_burnchain();
@@ -477,7 +530,7 @@ impl RunLoop {
             get_bulk_initial_names: Some(Box::new(move || get_names(use_test_genesis_data))),
         };
 
-        let (chain_state_db, receipts) = StacksChainState::open_and_exec(
+        let (mut chain_state_db, receipts) = StacksChainState::open_and_exec(
             self.config.is_mainnet(),
             self.config.burnchain.chain_id,
             &self.config.get_chainstate_path_str(),
@@ -485,6 +538,14 @@ impl RunLoop {
             Some(self.config.node.get_marf_opts()),
         )
         .unwrap();
+        // the chains coordinator processes every block, so its receipts are the ones that get
+        // reported to event observers
+        chain_state_db
+            .clarity_state
+            .set_record_cost_functions(self.config.node.cost_function_breakdown);
+        chain_state_db
+            .clarity_state
+            .set_store_contract_src(self.config.node.store_contract_source);
         run_loop::announce_boot_receipts(
             &mut self.event_dispatcher,
             &chain_state_db,
@@ -977,9 +1038,14 @@ impl RunLoop {
             self.counters.clone(),
             self.pox_watchdog_comms.clone(),
             self.should_keep_running.clone(),
+            self.get_miner_parent_override(),
         );
         self.set_globals(globals.clone());
 
+        // deliver whatever the event observers missed before the last shutdown, before any
+        // new events are generated
+        self.event_dispatcher.process_pending_payloads();
+
         // have headers; boot up the chains coordinator and instantiate the chain state
         let (coordinator_thread_handle, attachments_rx) = self.spawn_chains_coordinator(
             &burnchain_config,
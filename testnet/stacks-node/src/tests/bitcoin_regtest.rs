@@ -1,8 +1,10 @@
 use std::process::{Child, Command, Stdio};
 
+use crate::burnchains::bitcoin_regtest_controller::BitcoinRegtestController;
 use crate::config::InitialBalance;
 use crate::helium::RunLoop;
 use crate::tests::to_addr;
+use crate::BurnchainController;
 use crate::Config;
 
 use stacks::chainstate::burn::operations::BlockstackOperationType::{
@@ -15,6 +17,7 @@ use stacks::util::hash::hex_bytes;
 use super::PUBLISH_CONTRACT;
 use stacks::vm::costs::ExecutionCost;
 use std::env;
+use std::fs;
 use std::io::{BufRead, BufReader};
 
 pub enum BitcoinCoreError {
@@ -57,16 +60,21 @@ impl BitcoinCoreController {
             ))
             .arg(&format!("-rpcport={}", self.config.burnchain.rpc_port));
 
-        match (
-            &self.config.burnchain.username,
-            &self.config.burnchain.password,
-        ) {
-            (Some(username), Some(password)) => {
-                command
-                    .arg(&format!("-rpcuser={}", username))
-                    .arg(&format!("-rpcpassword={}", password));
+        if let Some(cookie_file) = self.config.burnchain.rpc_cookie_file.as_ref() {
+            // bitcoind only writes a cookie if it has no rpcuser/rpcpassword
+            command.arg(&format!("-rpccookiefile={}", cookie_file));
+        } else {
+            match (
+                &self.config.burnchain.username,
+                &self.config.burnchain.password,
+            ) {
+                (Some(username), Some(password)) => {
+                    command
+                        .arg(&format!("-rpcuser={}", username))
+                        .arg(&format!("-rpcpassword={}", password));
+                }
+                _ => {}
             }
-            _ => {}
         }
 
         eprintln!("bitcoind spawn: {:?}", command);
@@ -98,6 +106,18 @@ impl BitcoinCoreController {
         Ok(())
     }
 
+    /// Shut bitcoind down cleanly, so that its datadir can be reused by a later bitcoind.
+    pub fn stop_bitcoind(&mut self) {
+        if let Some(mut bitcoind_process) = self.bitcoind_process.take() {
+            Command::new("kill")
+                .arg("-TERM")
+                .arg(bitcoind_process.id().to_string())
+                .status()
+                .unwrap();
+            bitcoind_process.wait().unwrap();
+        }
+    }
+
     pub fn kill_bitcoind(&mut self) {
         if let Some(mut bitcoind_process) = self.bitcoind_process.take() {
             bitcoind_process.kill().unwrap();
@@ -113,6 +133,53 @@ impl Drop for BitcoinCoreController {
 
 const BITCOIND_INT_TEST_COMMITS: u64 = 11000;
 
+/// The regtest controller authenticates with bitcoind's cookie file, and picks up the new cookie
+/// that bitcoind writes when it restarts.
+#[test]
+#[ignore]
+fn bitcoind_cookie_auth_integration_test() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let mut conf = super::new_test_conf();
+    conf.burnchain.mode = "helium".to_string();
+    conf.burnchain.peer_host = "127.0.0.1".to_string();
+    conf.burnchain.rpc_port = 18443;
+    conf.burnchain.username = None;
+    conf.burnchain.password = None;
+    let cookie_file = format!("{}/rpc.cookie", conf.get_burnchain_path_str());
+    conf.burnchain.rpc_cookie_file = Some(cookie_file.clone());
+    conf.burnchain.local_mining_public_key = Some("04ee0b1602eb18fef7986887a7e8769a30c9df981d33c8380d255edef003abdcd243a0eb74afdf6740e6c423e62aec631519a24cf5b1d62bf8a3e06ddc695dcb77".to_string());
+
+    let mut controller = BitcoinCoreController::new(conf.clone());
+    controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(conf.clone(), None);
+    btc_regtest_controller.bootstrap_chain(201);
+    assert_eq!(btc_regtest_controller.get_block_count(), 201);
+
+    let first_cookie = fs::read_to_string(&cookie_file).unwrap();
+
+    // bitcoind writes a new cookie when it restarts, which invalidates the cached one
+    controller.stop_bitcoind();
+    controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed restarting bitcoind");
+
+    let second_cookie = fs::read_to_string(&cookie_file).unwrap();
+    assert_ne!(first_cookie, second_cookie);
+
+    btc_regtest_controller.build_next_block(1);
+    assert_eq!(btc_regtest_controller.get_block_count(), 202);
+
+    controller.kill_bitcoind();
+}
+
 #[test]
 #[ignore]
 fn bitcoind_integration_test() {
@@ -602,6 +602,15 @@ fn transition_empty_blocks() {
             );
             let rest_commit = burn_fee_cap - sunset_burn;
 
+            // the node must report the same sunset burn that we computed ourselves
+            let sunset_burn_info =
+                get_pox_sunset_burn(&http_origin, tip_info.burn_block_height + 1, burn_fee_cap);
+            assert_eq!(sunset_burn_info.expected_sunset_burn, sunset_burn);
+            assert_eq!(
+                sunset_burn_info.is_prepare_phase,
+                burnchain.is_in_prepare_phase(tip_info.burn_block_height + 1)
+            );
+
             let commit_outs = if tip_info.burn_block_height + 1 < burnchain.pox_constants.sunset_end
                 && !burnchain.is_in_prepare_phase(tip_info.burn_block_height + 1)
             {
@@ -655,6 +664,88 @@ fn transition_empty_blocks() {
     channel.stop_chains_coordinator();
 }
 
+/// Verify that an observer subscribed to epoch transitions is told exactly once that the chain
+/// crossed into 2.05, by the first Stacks block mined at the epoch's start height.
+#[test]
+#[ignore]
+fn epoch_transition_event() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let epoch_2_05 = 210;
+
+    let (mut conf, _miner_account) = neon_integration_test_conf();
+
+    let mut epochs = core::STACKS_EPOCHS_REGTEST.to_vec();
+    epochs[1].end_height = epoch_2_05;
+    epochs[2].start_height = epoch_2_05;
+
+    conf.burnchain.epochs = Some(epochs);
+
+    test_observer::spawn();
+    conf.events_observers.push(EventObserverConfig {
+        endpoint: format!("localhost:{}", test_observer::EVENT_OBSERVER_PORT),
+        events_keys: vec![EventKeyType::EpochTransitions],
+    });
+
+    let mut btcd_controller = BitcoinCoreController::new(conf.clone());
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(conf.clone(), None);
+
+    btc_regtest_controller.bootstrap_chain(epoch_2_05 - 5);
+
+    eprintln!("Chain bootstrapped...");
+
+    let mut run_loop = neon::RunLoop::new(conf.clone());
+    let blocks_processed = run_loop.get_blocks_processed_arc();
+
+    let channel = run_loop.get_coordinator_channel().unwrap();
+
+    thread::spawn(move || run_loop.start(None, 0));
+
+    // give the run loop some time to start up!
+    wait_for_runloop(&blocks_processed);
+
+    // first block wakes up the run loop
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // first block will hold our VRF registration
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // mine across the epoch boundary
+    while get_chain_info(&conf).burn_block_height < epoch_2_05 + 3 {
+        next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+    }
+
+    let transitions = test_observer::get_epoch_transitions();
+    assert_eq!(transitions.len(), 1);
+
+    let transition = &transitions[0];
+    assert_eq!(
+        transition.get("old_epoch").unwrap().as_str().unwrap(),
+        "2.0"
+    );
+    assert_eq!(
+        transition.get("new_epoch").unwrap().as_str().unwrap(),
+        "2.05"
+    );
+    assert_eq!(
+        transition
+            .get("burn_block_height")
+            .unwrap()
+            .as_u64()
+            .unwrap(),
+        epoch_2_05
+    );
+
+    channel.stop_chains_coordinator();
+}
+
 /// This test checks that the block limit is changed at Stacks 2.05. We lower the allowance, and
 /// check that we can 1) afford the function call before the target height and
 /// 2) cannot afford the function call after the target height.
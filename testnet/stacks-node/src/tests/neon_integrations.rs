@@ -16,13 +16,16 @@ use rusqlite::types::ToSql;
 use stacks::burnchains::bitcoin::address::{BitcoinAddress, LegacyBitcoinAddressType};
 use stacks::burnchains::bitcoin::BitcoinNetworkType;
 use stacks::burnchains::Txid;
+use stacks::chainstate::burn::operations::leader_block_commit::BURN_BLOCK_MINED_AT_MODULUS;
 use stacks::chainstate::burn::operations::{
-    BlockstackOperationType, DelegateStxOp, PreStxOp, TransferStxOp,
+    BlockstackOperationType, DelegateStxOp, LeaderBlockCommitOp, PreStxOp, TransferStxOp,
 };
 use stacks::chainstate::coordinator::comm::CoordinatorChannels;
+use stacks::chainstate::stacks::address::PoxAddress;
 use stacks::clarity_cli::vm_execute as execute;
 use stacks::codec::StacksMessageCodec;
 use stacks::core;
+use stacks::core::mempool::MemPoolStats;
 use stacks::core::{
     StacksEpoch, StacksEpochId, BLOCK_LIMIT_MAINNET_20, BLOCK_LIMIT_MAINNET_205,
     BLOCK_LIMIT_MAINNET_21, CHAIN_ID_TESTNET, HELIUM_BLOCK_LIMIT_20, PEER_VERSION_EPOCH_1_0,
@@ -30,19 +33,21 @@ use stacks::core::{
 };
 use stacks::net::atlas::{AtlasConfig, AtlasDB, MAX_ATTACHMENT_INV_PAGES_PER_REQUEST};
 use stacks::net::{
-    AccountEntryResponse, ContractSrcResponse, GetAttachmentResponse, GetAttachmentsInvResponse,
-    PostTransactionRequestBody, RPCPeerInfoData, StacksBlockAcceptedData,
-    UnconfirmedTransactionResponse,
+    AccountEntryResponse, AccountStackingResponse, ContractSrcResponse, DataVarResponse,
+    GetAttachmentResponse, GetAttachmentsInvResponse, MapEntryResponse, PostTransactionBatchEntry,
+    PostTransactionBatchRequestBody, PostTransactionErrorBody, PostTransactionRequestBody,
+    RPCPeerInfoData, StacksBlockAcceptedData, TransactionDryRunResponse,
+    UnconfirmedTransactionResponse, UnconfirmedTransactionStatus, MAX_TRANSACTION_BATCH_LEN,
 };
 use stacks::types::chainstate::{
-    BlockHeaderHash, BurnchainHeaderHash, StacksAddress, StacksBlockId,
+    BlockHeaderHash, BurnchainHeaderHash, StacksAddress, StacksBlockId, VRFSeed,
 };
 use stacks::util::hash::Hash160;
 use stacks::util::hash::{bytes_to_hex, hex_bytes, to_hex};
 use stacks::util::secp256k1::Secp256k1PublicKey;
 use stacks::util::{get_epoch_time_ms, get_epoch_time_secs, sleep_ms};
 use stacks::util_lib::boot::boot_code_id;
-use stacks::vm::types::PrincipalData;
+use stacks::vm::types::{PrincipalData, QualifiedContractIdentifier};
 use stacks::vm::ClarityVersion;
 use stacks::vm::Value;
 use stacks::{
@@ -59,22 +64,24 @@ use stacks::{
         StacksPrivateKey, StacksPublicKey, StacksTransaction, TransactionContractCall,
         TransactionPayload,
     },
-    net::RPCPoxInfoData,
+    net::{RPCBlockCommitInfo, RPCPoxInfoData, RPCPoxSunsetBurnInfo},
     util_lib::db::query_row_columns,
     util_lib::db::query_rows,
     util_lib::db::u64_to_sql,
 };
 
 use crate::{
+    burnchains::bitcoin_regtest_controller::make_bitcoin_indexer,
     burnchains::bitcoin_regtest_controller::UTXO, config::EventKeyType,
     config::EventObserverConfig, config::InitialBalance, neon, operations::BurnchainOpSigner,
-    syncctl::PoxSyncWatchdogComms, BitcoinRegtestController, BurnchainController, Config,
-    ConfigFile, Keychain,
+    syncctl::PoxSyncWatchdogComms, BitcoinRegtestController, BurnchainController, ClarityRepl,
+    Config, ConfigFile, Keychain,
 };
 
 use crate::util::hash::{MerkleTree, Sha512Trunc256Sum};
 use crate::util::secp256k1::MessageSignature;
 
+use crate::keychain::{get_registered_vrf_keys, open_vrf_keys_db};
 use crate::neon_node::StacksNode;
 
 use rand::Rng;
@@ -86,7 +93,7 @@ use super::{
     SK_2,
 };
 
-use crate::config::FeeEstimatorName;
+use crate::config::{BurnFeeStrategy, FeeEstimatorName};
 use crate::tests::SK_3;
 use clarity::vm::ast::stack_depth_checker::AST_CALL_STACK_DEPTH_BUFFER;
 use clarity::vm::ast::ASTRules;
@@ -96,6 +103,7 @@ use stacks::chainstate::stacks::miner::{
     signal_mining_blocked, signal_mining_ready, TransactionErrorEvent, TransactionEvent,
     TransactionSuccessEvent,
 };
+use stacks::cost_estimates::FeeRateEstimate;
 use stacks::net::RPCFeeEstimateResponse;
 use stacks::vm::ClarityName;
 use stacks::vm::ContractName;
@@ -202,6 +210,7 @@ pub mod test_observer {
     use std::thread;
 
     use tokio;
+    use tokio::sync::oneshot;
     use warp;
     use warp::Filter;
 
@@ -218,6 +227,9 @@ pub mod test_observer {
         pub static ref MEMTXS: Mutex<Vec<String>> = Mutex::new(Vec::new());
         pub static ref MEMTXS_DROPPED: Mutex<Vec<(String, String)>> = Mutex::new(Vec::new());
         pub static ref ATTACHMENTS: Mutex<Vec<serde_json::Value>> = Mutex::new(Vec::new());
+        pub static ref EPOCH_TRANSITIONS: Mutex<Vec<serde_json::Value>> = Mutex::new(Vec::new());
+        pub static ref BURN_BLOCK_REORGS: Mutex<Vec<serde_json::Value>> = Mutex::new(Vec::new());
+        static ref SHUTDOWN: Mutex<Option<oneshot::Sender<()>>> = Mutex::new(None);
     }
 
     async fn handle_burn_block(
@@ -228,6 +240,22 @@ pub mod test_observer {
         Ok(warp::http::StatusCode::OK)
     }
 
+    async fn handle_epoch_transition(
+        transition: serde_json::Value,
+    ) -> Result<impl warp::Reply, Infallible> {
+        let mut transitions = EPOCH_TRANSITIONS.lock().unwrap();
+        transitions.push(transition);
+        Ok(warp::http::StatusCode::OK)
+    }
+
+    async fn handle_burn_block_reorg(
+        reorg: serde_json::Value,
+    ) -> Result<impl warp::Reply, Infallible> {
+        let mut reorgs = BURN_BLOCK_REORGS.lock().unwrap();
+        reorgs.push(reorg);
+        Ok(warp::http::StatusCode::OK)
+    }
+
     async fn handle_block(block: serde_json::Value) -> Result<impl warp::Reply, Infallible> {
         let mut blocks = NEW_BLOCKS.lock().unwrap();
         blocks.push(block);
@@ -350,6 +378,14 @@ pub mod test_observer {
         BURN_BLOCKS.lock().unwrap().clone()
     }
 
+    pub fn get_epoch_transitions() -> Vec<serde_json::Value> {
+        EPOCH_TRANSITIONS.lock().unwrap().clone()
+    }
+
+    pub fn get_burn_block_reorgs() -> Vec<serde_json::Value> {
+        BURN_BLOCK_REORGS.lock().unwrap().clone()
+    }
+
     pub fn get_attachments() -> Vec<serde_json::Value> {
         ATTACHMENTS.lock().unwrap().clone()
     }
@@ -363,7 +399,7 @@ pub mod test_observer {
     }
 
     /// each path here should correspond to one of the paths listed in `event_dispatcher.rs`
-    async fn serve() {
+    async fn serve(shutdown: oneshot::Receiver<()>) {
         let new_blocks = warp::path!("new_block")
             .and(warp::post())
             .and(warp::body::json())
@@ -396,9 +432,17 @@ pub mod test_observer {
             .and(warp::post())
             .and(warp::body::json())
             .and_then(handle_mined_microblock);
+        let epoch_transitions = warp::path!("epoch_transition")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and_then(handle_epoch_transition);
+        let burn_block_reorgs = warp::path!("burn_block_reorg")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and_then(handle_burn_block_reorg);
 
         info!("Spawning warp server");
-        warp::serve(
+        let (_, server) = warp::serve(
             new_blocks
                 .or(mempool_txs)
                 .or(mempool_drop_txs)
@@ -406,17 +450,35 @@ pub mod test_observer {
                 .or(new_attachments)
                 .or(new_microblocks)
                 .or(mined_blocks)
-                .or(mined_microblocks),
+                .or(mined_microblocks)
+                .or(epoch_transitions)
+                .or(burn_block_reorgs),
         )
-        .run(([127, 0, 0, 1], EVENT_OBSERVER_PORT))
-        .await
+        .bind_with_graceful_shutdown(([127, 0, 0, 1], EVENT_OBSERVER_PORT), async {
+            shutdown.await.ok();
+        });
+        server.await
     }
 
     pub fn spawn() {
         clear();
+        restart();
+    }
+
+    /// Stop accepting events, keeping everything received so far.
+    pub fn kill() {
+        if let Some(shutdown) = SHUTDOWN.lock().unwrap().take() {
+            shutdown.send(()).ok();
+        }
+    }
+
+    /// Start accepting events again after `kill()`, keeping everything received so far.
+    pub fn restart() {
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        *SHUTDOWN.lock().unwrap() = Some(shutdown_tx);
         thread::spawn(|| {
             let rt = tokio::runtime::Runtime::new().expect("Failed to initialize tokio");
-            rt.block_on(serve());
+            rt.block_on(serve(shutdown_rx));
         });
     }
 
@@ -427,6 +489,8 @@ pub mod test_observer {
         MEMTXS.lock().unwrap().clear();
         MEMTXS_DROPPED.lock().unwrap().clear();
         MINED_BLOCKS.lock().unwrap().clear();
+        EPOCH_TRANSITIONS.lock().unwrap().clear();
+        BURN_BLOCK_REORGS.lock().unwrap().clear();
     }
 }
 
@@ -574,6 +638,21 @@ pub fn wait_for_microblocks(microblocks_processed: &Arc<AtomicU64>, timeout: u64
 
 /// returns Txid string
 pub fn submit_tx(http_origin: &str, tx: &Vec<u8>) -> String {
+    match submit_tx_fallible(http_origin, tx) {
+        Ok(txid) => txid,
+        Err(e) => {
+            eprintln!("Submit tx error: {:?}", &e);
+            panic!("");
+        }
+    }
+}
+
+/// Submit a transaction, returning its txid if it was accepted or the node's structured error
+/// body if it was rejected.
+pub fn submit_tx_fallible(
+    http_origin: &str,
+    tx: &Vec<u8>,
+) -> Result<String, PostTransactionErrorBody> {
     let client = reqwest::blocking::Client::new();
     let path = format!("{}/v2/transactions", http_origin);
     let res = client
@@ -591,26 +670,71 @@ pub fn submit_tx(http_origin: &str, tx: &Vec<u8>) -> String {
                 .txid()
                 .to_string()
         );
-        return res;
+        Ok(res)
     } else {
-        eprintln!("Submit tx error: {}", res.text().unwrap());
-        panic!("");
+        let status = res.status();
+        let body = res.text().unwrap();
+        match serde_json::from_str(&body) {
+            Ok(error) => Err(error),
+            Err(e) => panic!(
+                "Unparseable submit tx error ({}): {}: {:?}",
+                status, &body, &e
+            ),
+        }
     }
 }
 
+pub fn submit_tx_batch(http_origin: &str, txs: &[Vec<u8>]) -> Vec<PostTransactionBatchEntry> {
+    let client = reqwest::blocking::Client::new();
+    let path = format!("{}/v2/transactions/batch", http_origin);
+    let body = PostTransactionBatchRequestBody {
+        txs: txs.iter().map(|tx| to_hex(tx)).collect(),
+    };
+    let res = client.post(&path).json(&body).send().unwrap();
+    assert!(
+        res.status().is_success(),
+        "Submit tx batch error: {}",
+        res.text().unwrap()
+    );
+    res.json().unwrap()
+}
+
 pub fn get_unconfirmed_tx(http_origin: &str, txid: &Txid) -> Option<String> {
+    get_unconfirmed_tx_status(http_origin, txid).map(|res| res.tx)
+}
+
+pub fn get_unconfirmed_tx_status(
+    http_origin: &str,
+    txid: &Txid,
+) -> Option<UnconfirmedTransactionResponse> {
     let client = reqwest::blocking::Client::new();
     let path = format!("{}/v2/transactions/unconfirmed/{}", http_origin, txid);
     let res = client.get(&path).send().unwrap();
 
     if res.status().is_success() {
-        let res: UnconfirmedTransactionResponse = res.json().unwrap();
-        Some(res.tx)
+        Some(res.json().unwrap())
     } else {
         None
     }
 }
 
+pub fn dry_run_tx(http_origin: &str, tx: &Vec<u8>) -> TransactionDryRunResponse {
+    let client = reqwest::blocking::Client::new();
+    let path = format!("{}/v2/transactions/dry-run", http_origin);
+    let res = client
+        .post(&path)
+        .header("Content-Type", "application/octet-stream")
+        .body(tx.clone())
+        .send()
+        .unwrap();
+    assert!(
+        res.status().is_success(),
+        "Dry-run failed: {}",
+        res.text().unwrap()
+    );
+    res.json().unwrap()
+}
+
 pub fn submit_block(
     http_origin: &str,
     consensus_hash: &ConsensusHash,
@@ -683,6 +807,98 @@ pub fn get_block(http_origin: &str, block_id: &StacksBlockId) -> Option<StacksBl
     }
 }
 
+/// Get the block at a height on the canonical fork.  If `unanchored` is set, also get the
+/// microblock stream it confirmed.  Returns None if the node could not find such a block.
+pub fn get_block_by_height(
+    http_origin: &str,
+    height: u64,
+    unanchored: bool,
+) -> Option<(StacksBlock, Option<Vec<StacksMicroblock>>)> {
+    let client = reqwest::blocking::Client::new();
+    let path = if unanchored {
+        format!(
+            "{}/v2/blocks/height/{}?unanchored=true",
+            http_origin, height
+        )
+    } else {
+        format!("{}/v2/blocks/height/{}", http_origin, height)
+    };
+    let res = client.get(&path).send().unwrap();
+
+    if res.status().is_success() {
+        let res: Vec<u8> = res.bytes().unwrap().to_vec();
+        let mut cursor = &res[..];
+        let block = StacksBlock::consensus_deserialize(&mut cursor).unwrap();
+        let microblocks_opt = if unanchored {
+            Some(Vec::<StacksMicroblock>::consensus_deserialize(&mut cursor).unwrap())
+        } else {
+            None
+        };
+        assert_eq!(cursor.len(), 0);
+        Some((block, microblocks_opt))
+    } else {
+        None
+    }
+}
+
+/// Decode a run of back-to-back consensus-serialized microblocks, as sent by the unconfirmed
+/// microblocks endpoint.
+fn decode_microblocks(bytes: &[u8]) -> Vec<StacksMicroblock> {
+    let mut cursor = bytes;
+    let mut microblocks = vec![];
+    while cursor.len() > 0 {
+        microblocks.push(StacksMicroblock::consensus_deserialize(&mut cursor).unwrap());
+    }
+    microblocks
+}
+
+pub fn get_unconfirmed_microblocks(
+    http_origin: &str,
+    index_block_hash: &StacksBlockId,
+    min_seq: u16,
+    limit: Option<u16>,
+) -> Option<Vec<StacksMicroblock>> {
+    let client = reqwest::blocking::Client::new();
+    let path = match limit {
+        Some(limit) => format!(
+            "{}/v2/microblocks/unconfirmed/{}/{}?limit={}",
+            http_origin, index_block_hash, min_seq, limit
+        ),
+        None => format!(
+            "{}/v2/microblocks/unconfirmed/{}/{}",
+            http_origin, index_block_hash, min_seq
+        ),
+    };
+    let res = client.get(&path).send().unwrap();
+
+    if res.status().is_success() {
+        let res: Vec<u8> = res.bytes().unwrap().to_vec();
+        Some(decode_microblocks(&res))
+    } else {
+        None
+    }
+}
+
+pub fn get_confirmed_microblocks(
+    http_origin: &str,
+    index_block_hash: &StacksBlockId,
+) -> Option<Vec<StacksMicroblock>> {
+    let client = reqwest::blocking::Client::new();
+    let path = format!(
+        "{}/v2/microblocks/confirmed/{}",
+        http_origin, index_block_hash
+    );
+    let res = client.get(&path).send().unwrap();
+
+    if res.status().is_success() {
+        let res: Vec<u8> = res.bytes().unwrap().to_vec();
+        let microblocks = Vec::<StacksMicroblock>::consensus_deserialize(&mut &res[..]).unwrap();
+        Some(microblocks)
+    } else {
+        None
+    }
+}
+
 pub fn get_chain_info(conf: &Config) -> RPCPeerInfoData {
     let http_origin = format!("http://{}", &conf.node.rpc_bind);
     let client = reqwest::blocking::Client::new();
@@ -834,6 +1050,137 @@ fn bitcoind_integration_test() {
     channel.stop_chains_coordinator();
 }
 
+/// Read an unlabeled metric from the Prometheus endpoint at `prom_bind`
+#[cfg(feature = "monitoring_prom")]
+fn get_prometheus_metric(prom_bind: &str, name: &str) -> Option<f64> {
+    let client = reqwest::blocking::Client::new();
+    let res = client
+        .get(&format!("http://{}/metrics", prom_bind))
+        .send()
+        .unwrap()
+        .text()
+        .unwrap();
+    res.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        if parts.next() == Some(name) {
+            parts.next().and_then(|value| value.parse().ok())
+        } else {
+            None
+        }
+    })
+}
+
+/// The Prometheus endpoint set by `[monitoring] prometheus_bind` reports the blocks the node
+/// processes and the sortitions it wins as it mines.
+#[cfg(feature = "monitoring_prom")]
+#[test]
+#[ignore]
+fn prometheus_metrics_integration_test() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let (mut conf, _) = neon_integration_test_conf();
+    let prom_bind = format!("{}:{}", "127.0.0.1", 6001);
+    conf.node.prometheus_bind = Some(prom_bind.clone());
+
+    let mut btcd_controller = BitcoinCoreController::new(conf.clone());
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(conf.clone(), None);
+
+    btc_regtest_controller.bootstrap_chain(201);
+
+    eprintln!("Chain bootstrapped...");
+
+    let mut run_loop = neon::RunLoop::new(conf);
+    let blocks_processed = run_loop.get_blocks_processed_arc();
+    let channel = run_loop.get_coordinator_channel().unwrap();
+
+    thread::spawn(move || run_loop.start(None, 0));
+
+    // give the run loop some time to start up!
+    wait_for_runloop(&blocks_processed);
+
+    // first block wakes up the run loop
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // first block will hold our VRF registration
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // second block will be the first mined Stacks block
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    let blocks_before =
+        get_prometheus_metric(&prom_bind, "stacks_node_stx_blocks_processed_total").unwrap();
+    let burn_height_before =
+        get_prometheus_metric(&prom_bind, "stacks_node_burn_block_height").unwrap();
+
+    for _i in 0..3 {
+        next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+    }
+
+    let blocks_after =
+        get_prometheus_metric(&prom_bind, "stacks_node_stx_blocks_processed_total").unwrap();
+    let burn_height_after =
+        get_prometheus_metric(&prom_bind, "stacks_node_burn_block_height").unwrap();
+    assert!(blocks_after > blocks_before);
+    assert!(burn_height_after > burn_height_before);
+
+    // the only miner wins every sortition it takes part in
+    assert!(get_prometheus_metric(&prom_bind, "stacks_node_sortitions_won_total").unwrap() > 0.0);
+    assert!(get_prometheus_metric(&prom_bind, "stacks_node_mempool_txs").is_some());
+
+    channel.stop_chains_coordinator();
+}
+
+#[test]
+#[ignore]
+fn bitcoind_bootstrap_reuses_datadir() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let (conf, _miner_account) = neon_integration_test_conf();
+
+    let mut btcd_controller = BitcoinCoreController::new(conf.clone());
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(conf.clone(), None);
+    btc_regtest_controller.bootstrap_chain_to_height(201);
+    assert_eq!(btc_regtest_controller.get_block_count(), 201);
+    let tip_hash = btc_regtest_controller.get_block_hash(201);
+
+    // tear down the controller and bitcoind, but keep the datadir
+    drop(btc_regtest_controller);
+    btcd_controller.stop_bitcoind();
+
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed restarting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(conf.clone(), None);
+    btc_regtest_controller.bootstrap_chain_to_height(201);
+
+    // no blocks were generated, and the chain is the one from the first run
+    assert_eq!(btc_regtest_controller.get_block_count(), 201);
+    assert_eq!(btc_regtest_controller.get_block_hash(201), tip_hash);
+
+    // asking for a taller chain only generates the missing blocks
+    btc_regtest_controller.bootstrap_chain_to_height(205);
+    assert_eq!(btc_regtest_controller.get_block_count(), 205);
+    assert_eq!(btc_regtest_controller.get_block_hash(201), tip_hash);
+
+    btcd_controller.stop_bitcoind();
+}
+
 #[test]
 #[ignore]
 fn most_recent_utxo_integration_test() {
@@ -978,6 +1325,64 @@ pub fn get_account<F: std::fmt::Display>(http_origin: &str, account: &F) -> Acco
     }
 }
 
+/// Get the smallest nonce an account could use next, given its transactions in the chain state
+/// and the mempool.
+pub fn get_possible_next_nonce<F: std::fmt::Display>(http_origin: &str, account: &F) -> u64 {
+    let client = reqwest::blocking::Client::new();
+    let path = format!("{}/v2/accounts/{}?proof=0", http_origin, account);
+    let res = client
+        .get(&path)
+        .send()
+        .unwrap()
+        .json::<AccountEntryResponse>()
+        .unwrap();
+    info!("Account response: {:#?}", res);
+    res.possible_next_nonce
+        .expect("account response for the latest tip should have possible_next_nonce")
+}
+
+/// Get an account's PoX stacking status.  Returns None if the node reports that the account has
+/// never stacked.
+pub fn get_account_stacking<F: std::fmt::Display>(
+    http_origin: &str,
+    account: &F,
+) -> Option<AccountStackingResponse> {
+    let client = reqwest::blocking::Client::new();
+    let path = format!("{}/v2/accounts/{}/stacking", http_origin, account);
+    let res = client.get(&path).send().unwrap();
+    if res.status() == 404 {
+        return None;
+    }
+    let res = res.json::<AccountStackingResponse>().unwrap();
+    info!("Account stacking response: {:#?}", res);
+    Some(res)
+}
+
+/// Get an account's state as of a block on the canonical fork, named by its height or its index
+/// block hash.  Returns None if the node could not find such a block.
+pub fn get_account_until_block<F: std::fmt::Display>(
+    http_origin: &str,
+    account: &F,
+    until_block: &str,
+) -> Option<Account> {
+    let client = reqwest::blocking::Client::new();
+    let path = format!(
+        "{}/v2/accounts/{}?proof=0&until_block={}",
+        http_origin, account, until_block
+    );
+    let res = client.get(&path).send().unwrap();
+    if res.status() == reqwest::StatusCode::NOT_FOUND {
+        return None;
+    }
+    let res = res.json::<AccountEntryResponse>().unwrap();
+    info!("Account response: {:#?}", res);
+    Some(Account {
+        balance: u128::from_str_radix(&res.balance[2..], 16).unwrap(),
+        locked: u128::from_str_radix(&res.locked[2..], 16).unwrap(),
+        nonce: res.nonce,
+    })
+}
+
 pub fn get_pox_info(http_origin: &str) -> RPCPoxInfoData {
     let client = reqwest::blocking::Client::new();
     let path = format!("{}/v2/pox", http_origin);
@@ -989,6 +1394,63 @@ pub fn get_pox_info(http_origin: &str) -> RPCPoxInfoData {
         .unwrap()
 }
 
+pub fn get_mempool_stats(http_origin: &str) -> MemPoolStats {
+    let client = reqwest::blocking::Client::new();
+    let path = format!("{}/v2/mempool/stats", http_origin);
+    client
+        .get(&path)
+        .send()
+        .unwrap()
+        .json::<MemPoolStats>()
+        .unwrap()
+}
+
+pub fn get_pox_sunset_burn(
+    http_origin: &str,
+    burn_height: u64,
+    burn_fee_cap: u64,
+) -> RPCPoxSunsetBurnInfo {
+    let client = reqwest::blocking::Client::new();
+    let path = format!(
+        "{}/v2/pox/sunset-burn/{}/{}",
+        http_origin, burn_height, burn_fee_cap
+    );
+    client
+        .get(&path)
+        .send()
+        .unwrap()
+        .json::<RPCPoxSunsetBurnInfo>()
+        .unwrap()
+}
+
+pub fn get_block_commit_info(http_origin: &str, burn_height: u64) -> RPCBlockCommitInfo {
+    let client = reqwest::blocking::Client::new();
+    let path = format!("{}/v2/block-commits/{}", http_origin, burn_height);
+    client
+        .get(&path)
+        .send()
+        .unwrap()
+        .json::<RPCBlockCommitInfo>()
+        .unwrap()
+}
+
+pub fn get_block_commit_info_by_stacks_block(
+    http_origin: &str,
+    index_block_hash: &str,
+) -> RPCBlockCommitInfo {
+    let client = reqwest::blocking::Client::new();
+    let path = format!(
+        "{}/v2/block-commits/stacks-block/{}",
+        http_origin, index_block_hash
+    );
+    client
+        .get(&path)
+        .send()
+        .unwrap()
+        .json::<RPCBlockCommitInfo>()
+        .unwrap()
+}
+
 fn get_chain_tip(http_origin: &str) -> (ConsensusHash, BlockHeaderHash) {
     let client = reqwest::blocking::Client::new();
     let path = format!("{}/v2/info", http_origin);
@@ -1050,10 +1512,53 @@ pub fn get_contract_src(
     }
 }
 
-#[test]
-#[ignore]
-fn deep_contract() {
-    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+/// Read a contract's data var at the canonical tip, or None if it is not found
+pub fn get_data_var(
+    http_origin: &str,
+    contract_addr: &StacksAddress,
+    contract_name: &str,
+    var_name: &str,
+) -> Option<Value> {
+    let client = reqwest::blocking::Client::new();
+    let path = format!(
+        "{}/v2/data_var/{}/{}/{}?proof=0",
+        http_origin, contract_addr, contract_name, var_name
+    );
+    let res = client.get(&path).send().unwrap();
+    if res.status().as_u16() == 404 {
+        return None;
+    }
+    let data_var = res.json::<DataVarResponse>().unwrap();
+    Some(Value::try_deserialize_hex_untyped(&data_var.data[2..]).unwrap())
+}
+
+/// Read a contract's map entry for `key` at the canonical tip, as an optional value
+pub fn get_map_entry(
+    http_origin: &str,
+    contract_addr: &StacksAddress,
+    contract_name: &str,
+    map_name: &str,
+    key: &Value,
+) -> Value {
+    let client = reqwest::blocking::Client::new();
+    let path = format!(
+        "{}/v2/map_entry/{}/{}/{}?proof=0",
+        http_origin, contract_addr, contract_name, map_name
+    );
+    let res = client
+        .post(&path)
+        .json(&key.serialize_to_hex())
+        .send()
+        .unwrap()
+        .json::<MapEntryResponse>()
+        .unwrap();
+    Value::try_deserialize_hex_untyped(&res.data[2..]).unwrap()
+}
+
+#[test]
+#[ignore]
+fn deep_contract() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
         return;
     }
 
@@ -1240,6 +1745,320 @@ fn bad_microblock_pubkey() {
     test_observer::clear();
 }
 
+#[test]
+#[ignore]
+fn mempool_min_fee_rate_integration() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let spender_sk = StacksPrivateKey::new();
+    let spender_addr = to_addr(&spender_sk);
+    let spender_princ: PrincipalData = spender_addr.into();
+    let recipient: PrincipalData = to_addr(&StacksPrivateKey::new()).into();
+
+    let (mut conf, _miner_account) = neon_integration_test_conf();
+    conf.mempool.min_fee_rate = 1;
+
+    conf.initial_balances.push(InitialBalance {
+        address: spender_princ.clone(),
+        amount: 100_000,
+    });
+
+    let mut btcd_controller = BitcoinCoreController::new(conf.clone());
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(conf.clone(), None);
+    let http_origin = format!("http://{}", &conf.node.rpc_bind);
+
+    btc_regtest_controller.bootstrap_chain(201);
+
+    eprintln!("Chain bootstrapped...");
+
+    let mut run_loop = neon::RunLoop::new(conf.clone());
+    let blocks_processed = run_loop.get_blocks_processed_arc();
+    let channel = run_loop.get_coordinator_channel().unwrap();
+
+    thread::spawn(move || run_loop.start(None, 0));
+
+    // give the run loop some time to start up!
+    wait_for_runloop(&blocks_processed);
+
+    // first block wakes up the run loop
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // first block will hold our VRF registration
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // second block will be the first mined Stacks block
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    let stats_before = get_mempool_stats(&http_origin);
+
+    let transfer = make_stacks_transfer(&spender_sk, 0, 0, &recipient, 1000);
+    let res = reqwest::blocking::Client::new()
+        .post(&format!("{}/v2/transactions", &http_origin))
+        .header("Content-Type", "application/octet-stream")
+        .body(transfer)
+        .send()
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 400);
+
+    let body: serde_json::Value = res.json().unwrap();
+    assert_eq!(
+        body.get("error").unwrap().as_str().unwrap(),
+        "BelowMinFeeRate"
+    );
+    let reason_data = body.get("reason_data").unwrap();
+    assert_eq!(reason_data.get("fee_rate").unwrap().as_u64().unwrap(), 0);
+    assert_eq!(
+        reason_data.get("min_fee_rate").unwrap().as_u64().unwrap(),
+        1
+    );
+
+    let stats = get_mempool_stats(&http_origin);
+    assert_eq!(
+        stats.rejected.fee_too_low,
+        stats_before.rejected.fee_too_low + 1
+    );
+    assert_eq!(stats.admitted, stats_before.admitted);
+
+    // paying the floor is enough
+    let transfer = make_stacks_transfer(&spender_sk, 0, 1000, &recipient, 1000);
+    submit_tx(&http_origin, &transfer);
+
+    let stats = get_mempool_stats(&http_origin);
+    assert_eq!(stats.admitted, stats_before.admitted + 1);
+    assert_eq!(stats.pending, 1);
+
+    channel.stop_chains_coordinator();
+}
+
+/// A transaction with a nonce gap is never mined, so it is evicted once it has been in the
+/// mempool for `mempool_timeout` burnchain blocks.
+#[test]
+#[ignore]
+fn mempool_expire_by_burn_age_integration() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let spender_sk = StacksPrivateKey::new();
+    let spender_addr = to_addr(&spender_sk);
+    let spender_princ: PrincipalData = spender_addr.into();
+    let recipient: PrincipalData = to_addr(&StacksPrivateKey::new()).into();
+
+    let (mut conf, _miner_account) = neon_integration_test_conf();
+    conf.mempool.mempool_timeout = 3;
+
+    test_observer::spawn();
+
+    conf.events_observers.push(EventObserverConfig {
+        endpoint: format!("localhost:{}", test_observer::EVENT_OBSERVER_PORT),
+        events_keys: vec![EventKeyType::AnyEvent],
+    });
+
+    conf.initial_balances.push(InitialBalance {
+        address: spender_princ.clone(),
+        amount: 10_000_000,
+    });
+
+    let mut btcd_controller = BitcoinCoreController::new(conf.clone());
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(conf.clone(), None);
+    let http_origin = format!("http://{}", &conf.node.rpc_bind);
+
+    btc_regtest_controller.bootstrap_chain(201);
+
+    eprintln!("Chain bootstrapped...");
+
+    let mut run_loop = neon::RunLoop::new(conf.clone());
+    let blocks_processed = run_loop.get_blocks_processed_arc();
+    let channel = run_loop.get_coordinator_channel().unwrap();
+
+    thread::spawn(move || run_loop.start(None, 0));
+
+    // give the run loop some time to start up!
+    wait_for_runloop(&blocks_processed);
+
+    // first block wakes up the run loop
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // first block will hold our VRF registration
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // second block will be the first mined Stacks block
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    let stats_before = get_mempool_stats(&http_origin);
+
+    // nonce 0 is never sent, so this can't be mined
+    let transfer = make_stacks_transfer(&spender_sk, 1, 1000, &recipient, 1000);
+    let txid = submit_tx(&http_origin, &transfer);
+
+    let stats = get_mempool_stats(&http_origin);
+    assert_eq!(stats.pending, 1);
+
+    // the first burnchain block only starts the transaction's clock
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+    let stats = get_mempool_stats(&http_origin);
+    assert_eq!(stats.pending, 1);
+    assert_eq!(stats.expired, stats_before.expired);
+
+    // advance past the expiry window
+    for _i in 0..4 {
+        next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+    }
+
+    let stats = get_mempool_stats(&http_origin);
+    assert_eq!(stats.pending, 0);
+    assert_eq!(stats.expired, stats_before.expired + 1);
+
+    let dropped_txs = test_observer::get_memtx_drops();
+    assert_eq!(dropped_txs.len(), 1);
+    assert_eq!(&dropped_txs[0].1, "StaleGarbageCollect");
+    assert_eq!(&dropped_txs[0].0, &format!("0x{}", txid));
+
+    channel.stop_chains_coordinator();
+    test_observer::clear();
+}
+
+#[test]
+#[ignore]
+fn mempool_replace_by_fee_integration() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let spender_sk = StacksPrivateKey::new();
+    let spender_addr = to_addr(&spender_sk);
+    let spender_princ: PrincipalData = spender_addr.into();
+    let recipient: PrincipalData = to_addr(&StacksPrivateKey::new()).into();
+
+    let (mut conf, _miner_account) = neon_integration_test_conf();
+    conf.mempool.enable_rbf = true;
+
+    test_observer::spawn();
+
+    conf.events_observers.push(EventObserverConfig {
+        endpoint: format!("localhost:{}", test_observer::EVENT_OBSERVER_PORT),
+        events_keys: vec![EventKeyType::AnyEvent],
+    });
+
+    conf.initial_balances.push(InitialBalance {
+        address: spender_princ.clone(),
+        amount: 10_000_000,
+    });
+
+    let mut btcd_controller = BitcoinCoreController::new(conf.clone());
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(conf.clone(), None);
+    let http_origin = format!("http://{}", &conf.node.rpc_bind);
+
+    btc_regtest_controller.bootstrap_chain(201);
+
+    eprintln!("Chain bootstrapped...");
+
+    let mut run_loop = neon::RunLoop::new(conf.clone());
+    let blocks_processed = run_loop.get_blocks_processed_arc();
+    let channel = run_loop.get_coordinator_channel().unwrap();
+
+    thread::spawn(move || run_loop.start(None, 0));
+
+    // give the run loop some time to start up!
+    wait_for_runloop(&blocks_processed);
+
+    // first block wakes up the run loop
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // first block will hold our VRF registration
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // second block will be the first mined Stacks block
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    let transfer = make_stacks_transfer(&spender_sk, 0, 180_000, &recipient, 1000);
+    let replaced_txid = submit_tx(&http_origin, &transfer);
+
+    // a bump below the minimum 25% is rejected
+    let transfer = make_stacks_transfer(&spender_sk, 0, 190_000, &recipient, 1000);
+    let res = reqwest::blocking::Client::new()
+        .post(&format!("{}/v2/transactions", &http_origin))
+        .header("Content-Type", "application/octet-stream")
+        .body(transfer)
+        .send()
+        .unwrap();
+    assert_eq!(res.status().as_u16(), 409);
+    let body: serde_json::Value = res.json().unwrap();
+    assert_eq!(
+        body.get("error").unwrap().as_str().unwrap(),
+        "ConflictingNonceInMempool"
+    );
+    assert_eq!(
+        body.get("reason_data")
+            .unwrap()
+            .get("txid")
+            .unwrap()
+            .as_str()
+            .unwrap(),
+        replaced_txid
+    );
+
+    let transfer = make_stacks_transfer(&spender_sk, 0, 400_000, &recipient, 1000);
+    let replacement_txid = submit_tx(&http_origin, &transfer);
+
+    let dropped_txs = test_observer::get_memtx_drops();
+    assert_eq!(dropped_txs.len(), 1);
+    assert_eq!(&dropped_txs[0].1, "ReplaceByFee");
+    assert_eq!(&dropped_txs[0].0, &format!("0x{}", replaced_txid));
+
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    let mined_txids: Vec<String> = test_observer::get_blocks()
+        .iter()
+        .flat_map(|block| {
+            block
+                .get("transactions")
+                .unwrap()
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|tx| tx.get("txid").unwrap().as_str().unwrap().to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    assert!(mined_txids.contains(&format!("0x{}", replacement_txid)));
+    assert!(!mined_txids.contains(&format!("0x{}", replaced_txid)));
+
+    let account = get_account(&http_origin, &spender_addr);
+    assert_eq!(account.nonce, 1);
+    assert_eq!(account.balance, 10_000_000 - 400_000 - 1000);
+
+    // the nonce is now spent
+    let transfer = make_stacks_transfer(&spender_sk, 0, 500_000, &recipient, 1000);
+    let error = submit_tx_fallible(&http_origin, &transfer).unwrap_err();
+    assert_eq!(error.error, "BadNonce");
+    let reason_data = error.reason_data.unwrap();
+    assert_eq!(reason_data.get("expected").unwrap().as_u64().unwrap(), 1);
+    assert_eq!(reason_data.get("actual").unwrap().as_u64().unwrap(), 0);
+
+    test_observer::clear();
+    channel.stop_chains_coordinator();
+}
+
 #[test]
 #[ignore]
 fn liquid_ustx_integration() {
@@ -1258,6 +2077,8 @@ fn liquid_ustx_integration() {
     let spender_princ: PrincipalData = spender_addr.into();
 
     let (mut conf, _miner_account) = neon_integration_test_conf();
+    conf.mempool.enable_rbf = true;
+    conf.mempool.rbf_fee_increase_pct = 25;
 
     test_observer::spawn();
 
@@ -1318,7 +2139,17 @@ fn liquid_ustx_integration() {
 
     let replaced_txid = submit_tx(&http_origin, &publish);
 
-    let publish = make_contract_publish(&spender_sk, 0, 1100, "caller", caller_src);
+    // not enough of a fee bump to replace the first transaction
+    let publish = make_contract_publish(&spender_sk, 0, 1200, "caller", caller_src);
+    let res = reqwest::blocking::Client::new()
+        .post(&format!("{}/v2/transactions", &http_origin))
+        .header("Content-Type", "application/octet-stream")
+        .body(publish)
+        .send()
+        .unwrap();
+    assert!(!res.status().is_success());
+
+    let publish = make_contract_publish(&spender_sk, 0, 1250, "caller", caller_src);
     submit_tx(&http_origin, &publish);
 
     let dropped_txs = test_observer::get_memtx_drops();
@@ -2279,7 +3110,135 @@ fn should_fix_2771() {
     channel.stop_chains_coordinator();
 }
 
-/// Returns a StacksMicroblock with the given transactions, sequence, and parent block that is
+/// When a burnchain fork abandons the node's canonical burnchain tip, event observers are told
+/// which burnchain tip was abandoned, and which processed Stacks blocks were orphaned by it.
+#[test]
+#[ignore]
+fn burn_block_reorg_event_integration_test() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let (mut conf, _miner_account) = neon_integration_test_conf();
+
+    test_observer::spawn();
+
+    conf.events_observers.push(EventObserverConfig {
+        endpoint: format!("localhost:{}", test_observer::EVENT_OBSERVER_PORT),
+        events_keys: vec![EventKeyType::AnyEvent],
+    });
+
+    let mut btcd_controller = BitcoinCoreController::new(conf.clone());
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(conf.clone(), None);
+
+    btc_regtest_controller.bootstrap_chain(201);
+
+    eprintln!("Chain bootstrapped...");
+
+    let mut run_loop = neon::RunLoop::new(conf);
+    let blocks_processed = run_loop.get_blocks_processed_arc();
+
+    let channel = run_loop.get_coordinator_channel().unwrap();
+
+    thread::spawn(move || run_loop.start(None, 0));
+
+    // give the run loop some time to start up!
+    wait_for_runloop(&blocks_processed);
+
+    // first block wakes up the run loop
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // first block will hold our VRF registration
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    let mut sort_height = channel.get_sortitions_processed();
+    eprintln!("Sort height: {}", sort_height);
+
+    while sort_height < 210 {
+        next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+        sort_height = channel.get_sortitions_processed();
+        eprintln!("Sort height: {}", sort_height);
+    }
+
+    // no reorgs yet
+    assert!(test_observer::get_burn_block_reorgs().is_empty());
+
+    let old_tip = test_observer::get_burn_blocks()
+        .into_iter()
+        .max_by_key(|burn_block| {
+            burn_block
+                .get("burn_block_height")
+                .unwrap()
+                .as_u64()
+                .unwrap()
+        })
+        .unwrap();
+    let old_tip_height = old_tip.get("burn_block_height").unwrap().as_u64().unwrap();
+    let old_tip_hash = old_tip.get("burn_block_hash").unwrap().as_str().unwrap();
+
+    // fork away every burnchain block after the fork point
+    let fork_point_height = 205;
+    let stacks_blocks_to_orphan: HashSet<String> = test_observer::get_blocks()
+        .into_iter()
+        .filter(|block| {
+            block.get("burn_block_height").unwrap().as_u64().unwrap() > fork_point_height
+        })
+        .map(|block| {
+            block
+                .get("block_hash")
+                .unwrap()
+                .as_str()
+                .unwrap()
+                .to_string()
+        })
+        .collect();
+    assert!(!stacks_blocks_to_orphan.is_empty());
+
+    let burn_header_hash_to_fork = btc_regtest_controller.get_block_hash(fork_point_height + 1);
+    btc_regtest_controller.invalidate_block(&burn_header_hash_to_fork);
+    btc_regtest_controller.build_next_block(old_tip_height - fork_point_height + 1);
+
+    thread::sleep(Duration::from_secs(50));
+    eprintln!("Wait for block off of fork");
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    let reorgs = test_observer::get_burn_block_reorgs();
+    assert!(!reorgs.is_empty());
+
+    let reorg = &reorgs[0];
+    assert_eq!(
+        reorg.get("old_canonical_tip").unwrap().as_str().unwrap(),
+        old_tip_hash
+    );
+    assert_ne!(
+        reorg.get("new_canonical_tip").unwrap().as_str().unwrap(),
+        old_tip_hash
+    );
+    assert_eq!(
+        reorg.get("reorg_depth").unwrap().as_u64().unwrap(),
+        old_tip_height - fork_point_height
+    );
+
+    let orphaned_stacks_blocks = reorg
+        .get("stacks_block_hashes")
+        .unwrap()
+        .as_array()
+        .unwrap();
+    assert!(!orphaned_stacks_blocks.is_empty());
+    for block_hash in orphaned_stacks_blocks.iter() {
+        assert!(stacks_blocks_to_orphan.contains(block_hash.as_str().unwrap()));
+    }
+
+    channel.stop_chains_coordinator();
+    test_observer::clear();
+}
+
+/// Returns a StacksMicroblock with the given transactions, sequence, and parent block that is
 /// signed with the given private key.
 fn make_signed_microblock(
     block_privk: &StacksPrivateKey,
@@ -3050,6 +4009,179 @@ fn microblock_integration_test() {
     channel.stop_chains_coordinator();
 }
 
+/// Fetch the miner's unconfirmed microblock stream over the /v2/microblocks RPC family, page
+/// through it by sequence number, and fetch it again once an anchored block confirms it.
+#[test]
+#[ignore]
+fn microblocks_rpc_integration_test() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let spender_sk = StacksPrivateKey::new();
+    let spender_addr: PrincipalData = to_addr(&spender_sk).into();
+    let recipient: PrincipalData = to_addr(&StacksPrivateKey::new()).into();
+
+    let (mut conf, _) = neon_integration_test_conf();
+
+    conf.initial_balances.push(InitialBalance {
+        address: spender_addr.clone(),
+        amount: 100300,
+    });
+
+    conf.node.mine_microblocks = true;
+    conf.node.wait_time_for_microblocks = 1_000;
+    conf.node.microblock_frequency = 1_000;
+    conf.miner.microblock_attempt_time_ms = 1_000;
+
+    let mut btcd_controller = BitcoinCoreController::new(conf.clone());
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(conf.clone(), None);
+    let http_origin = format!("http://{}", &conf.node.rpc_bind);
+
+    btc_regtest_controller.bootstrap_chain(201);
+
+    eprintln!("Chain bootstrapped...");
+
+    let mut run_loop = neon::RunLoop::new(conf.clone());
+    let blocks_processed = run_loop.get_blocks_processed_arc();
+    let microblocks_processed = run_loop.get_microblocks_processed_arc();
+
+    let channel = run_loop.get_coordinator_channel().unwrap();
+
+    thread::spawn(move || run_loop.start(None, 0));
+
+    // give the run loop some time to start up!
+    wait_for_runloop(&blocks_processed);
+
+    // first block wakes up the run loop
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // first block will hold our VRF registration
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // second block will be the first mined Stacks block
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    let tip_info = get_chain_info(&conf);
+    let tip_index_block_hash = StacksBlockHeader::make_index_block_hash(
+        &tip_info.stacks_tip_consensus_hash,
+        &tip_info.stacks_tip,
+    );
+
+    let mut txids = vec![];
+    for nonce in 0..3 {
+        let tx = make_stacks_transfer_mblock_only(&spender_sk, nonce, 1000, &recipient, 1000);
+        txids.push(submit_tx(&http_origin, &tx));
+    }
+
+    // wait for the miner to put all of them into microblocks on top of the tip
+    let mut unconfirmed = vec![];
+    for _i in 0..60 {
+        wait_for_microblocks(&microblocks_processed, 10);
+        unconfirmed = get_unconfirmed_microblocks(&http_origin, &tip_index_block_hash, 0, None)
+            .unwrap_or(vec![]);
+        let mined_txids: Vec<String> = unconfirmed
+            .iter()
+            .flat_map(|mblock| mblock.txs.iter().map(|tx| tx.txid().to_string()))
+            .collect();
+        if txids.iter().all(|txid| mined_txids.contains(txid)) {
+            break;
+        }
+        sleep_ms(1_000);
+    }
+    assert!(
+        unconfirmed.len() > 0,
+        "No unconfirmed microblocks were mined"
+    );
+    for (seq, mblock) in unconfirmed.iter().enumerate() {
+        assert_eq!(mblock.header.sequence, seq as u16);
+    }
+
+    // page through the stream one microblock at a time
+    for (seq, mblock) in unconfirmed.iter().enumerate() {
+        let page =
+            get_unconfirmed_microblocks(&http_origin, &tip_index_block_hash, seq as u16, Some(1))
+                .unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(&page[0], mblock);
+    }
+
+    // unknown anchored blocks are 404s; malformed sequences and limits are 400s
+    let client = reqwest::blocking::Client::new();
+    let path = format!(
+        "{}/v2/microblocks/unconfirmed/{}/0",
+        &http_origin,
+        StacksBlockId([0x11; 32])
+    );
+    assert_eq!(client.get(&path).send().unwrap().status().as_u16(), 404);
+    let path = format!(
+        "{}/v2/microblocks/confirmed/{}",
+        &http_origin,
+        StacksBlockId([0x11; 32])
+    );
+    assert_eq!(client.get(&path).send().unwrap().status().as_u16(), 404);
+    let path = format!(
+        "{}/v2/microblocks/unconfirmed/{}/abc",
+        &http_origin, &tip_index_block_hash
+    );
+    assert_eq!(client.get(&path).send().unwrap().status().as_u16(), 400);
+    let path = format!(
+        "{}/v2/microblocks/unconfirmed/{}/70000",
+        &http_origin, &tip_index_block_hash
+    );
+    assert_eq!(client.get(&path).send().unwrap().status().as_u16(), 400);
+    let path = format!(
+        "{}/v2/microblocks/unconfirmed/{}/0?limit=0",
+        &http_origin, &tip_index_block_hash
+    );
+    assert_eq!(client.get(&path).send().unwrap().status().as_u16(), 400);
+
+    // subsequent anchored blocks confirm the stream, and the confirmed stream is served from the
+    // confirming block
+    let mut parent_index_block_hash = tip_index_block_hash;
+    let mut parent_unconfirmed = unconfirmed;
+    let mut confirmed_txids = vec![];
+    for _i in 0..5 {
+        next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+        let tip_info = get_chain_info(&conf);
+        let index_block_hash = StacksBlockHeader::make_index_block_hash(
+            &tip_info.stacks_tip_consensus_hash,
+            &tip_info.stacks_tip,
+        );
+        if index_block_hash == parent_index_block_hash {
+            continue;
+        }
+
+        if let Some(confirmed) = get_confirmed_microblocks(&http_origin, &index_block_hash) {
+            // the stream we fetched earlier is a prefix of the stream that got confirmed
+            for (mblock, unconfirmed_mblock) in confirmed.iter().zip(parent_unconfirmed.iter()) {
+                assert_eq!(mblock, unconfirmed_mblock);
+            }
+            for mblock in confirmed.iter() {
+                for tx in mblock.txs.iter() {
+                    confirmed_txids.push(tx.txid().to_string());
+                }
+            }
+        }
+        if txids.iter().all(|txid| confirmed_txids.contains(txid)) {
+            break;
+        }
+
+        parent_unconfirmed =
+            get_unconfirmed_microblocks(&http_origin, &index_block_hash, 0, None).unwrap_or(vec![]);
+        parent_index_block_hash = index_block_hash;
+    }
+    assert!(txids.iter().all(|txid| confirmed_txids.contains(txid)));
+
+    channel.stop_chains_coordinator();
+}
+
 #[test]
 #[ignore]
 fn filter_low_fee_tx_integration_test() {
@@ -5769,6 +6901,13 @@ fn pox_integration_test() {
     )
     .unwrap();
 
+    let pox_1_address = BitcoinAddress::from_bytes_legacy(
+        BitcoinNetworkType::Testnet,
+        LegacyBitcoinAddressType::PublicKeyHash,
+        &Hash160::from_node_public_key(&pox_pubkey).to_bytes(),
+    )
+    .unwrap();
+
     let (mut conf, miner_account) = neon_integration_test_conf();
 
     test_observer::spawn();
@@ -5874,6 +7013,9 @@ fn pox_integration_test() {
     assert_eq!(account.balance, first_bal as u128);
     assert_eq!(account.nonce, 0);
 
+    // none of whom have ever stacked
+    assert!(get_account_stacking(&http_origin, &spender_addr).is_none());
+
     let pox_info = get_pox_info(&http_origin);
 
     assert_eq!(
@@ -6015,6 +7157,18 @@ fn pox_integration_test() {
 
     assert!(tested, "Should have observed stack-stx transaction");
 
+    // the node reports the lock stack-stx created
+    let stacking = get_account_stacking(&http_origin, &spender_addr)
+        .expect("spender should have a stacking status");
+    assert!(stacking.stacked);
+    let stacking = stacking.stacking.unwrap();
+    assert_eq!(stacking.stacked_amount, stacked_bal);
+    assert_eq!(stacking.unlock_height, 300);
+    assert_eq!(stacking.reward_cycle, 14);
+    assert_eq!(stacking.lock_period, 6);
+    assert_eq!(stacking.delegated_to, None);
+    assert_eq!(stacking.pox_address, Some(pox_1_address.to_string()));
+
     // let's stack with spender 2 and spender 3...
 
     // now let's have sender_2 and sender_3 stack to pox spender_addr 2 in
@@ -6185,13 +7339,6 @@ fn pox_integration_test() {
         }
     }
 
-    let pox_1_address = BitcoinAddress::from_bytes_legacy(
-        BitcoinNetworkType::Testnet,
-        LegacyBitcoinAddressType::PublicKeyHash,
-        &Hash160::from_node_public_key(&pox_pubkey).to_bytes(),
-    )
-    .unwrap();
-
     assert_eq!(recipient_slots.len(), 2);
     assert_eq!(
         recipient_slots.get(&format!("{}", &pox_2_address)).cloned(),
@@ -7993,47 +9140,396 @@ fn fuzzed_median_fee_rate_estimation_test_window10() {
     fuzzed_median_fee_rate_estimation_test(10, 90080.5496)
 }
 
+/// Exercise `/v2/fees/transaction` against a live miner: before the node has seen a call to a
+/// function, the endpoint should answer with a structured 400 naming the `EstimatorError`
+/// variant; once a call has been mined, it should return low/middle/high fee suggestions along
+/// with the cost breakdown they were computed from.
 #[test]
 #[ignore]
-fn use_latest_tip_integration_test() {
-    // The purpose of this test is to check if setting the query parameter `tip` to `latest` is working
-    // as expected. Multiple endpoints accept this parameter, and in this test, we are using the
-    // GetContractSrc method to test it.
-    //
-    // The following scenarios are tested here:
-    // - The caller does not specify the tip paramater, and the canonical chain tip is used regardless of the
-    //    state of the unconfirmed microblock stream.
-    // - The caller passes tip=latest with an existing unconfirmed microblock stream, and
-    //   Clarity state from the unconfirmed microblock stream is successfully loaded.
-    // - The caller passes tip=latest with an empty unconfirmed microblock stream, and
-    //   Clarity state from the canonical chain tip is successfully loaded (i.e. you don't
-    //   get a 404 even though the unconfirmed chain tip points to a nonexistent MARF trie).
-    //
-    // Note: In this test, we are manually creating a microblock as well as reloading the unconfirmed
-    // state of the chainstate, instead of relying on `next_block_and_wait` to generate
-    // microblocks. We do this because the unconfirmed state is not automatically being initialized
-    // on the node, so attempting to validate any transactions against the expected unconfirmed
-    // state fails.
+fn fee_estimation_endpoint_integration_test() {
     if env::var("BITCOIND_TEST") != Ok("1".into()) {
         return;
     }
 
-    let spender_sk = StacksPrivateKey::from_hex(SK_1).unwrap();
-    let spender_stacks_addr = to_addr(&spender_sk);
-    let spender_addr: PrincipalData = spender_stacks_addr.into();
+    let contract_src = r#"
+(define-data-var counter int 0)
 
-    let (mut conf, _) = neon_integration_test_conf();
+(define-public (increment)
+  (begin
+    (var-set counter (+ (var-get counter) 1))
+    (ok (var-get counter))))
+    "#;
+
+    let spender_sk = StacksPrivateKey::new();
+    let spender_addr = to_addr(&spender_sk);
 
+    let (mut conf, _) = neon_integration_test_conf();
     conf.initial_balances.push(InitialBalance {
-        address: spender_addr.clone(),
-        amount: 100300,
+        address: spender_addr.clone().into(),
+        amount: 10000000000,
     });
 
-    conf.node.mine_microblocks = true;
-    conf.node.wait_time_for_microblocks = 10_000;
-    conf.node.microblock_frequency = 1_000;
-
-    test_observer::spawn();
+    let mut btcd_controller = BitcoinCoreController::new(conf.clone());
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(conf.clone(), None);
+    let http_origin = format!("http://{}", &conf.node.rpc_bind);
+
+    btc_regtest_controller.bootstrap_chain(200);
+
+    eprintln!("Chain bootstrapped...");
+
+    let mut run_loop = neon::RunLoop::new(conf.clone());
+    let blocks_processed = run_loop.get_blocks_processed_arc();
+
+    let channel = run_loop.get_coordinator_channel().unwrap();
+
+    thread::spawn(move || run_loop.start(None, 0));
+
+    wait_for_runloop(&blocks_processed);
+    run_until_burnchain_height(&mut btc_regtest_controller, &blocks_processed, 210, &conf);
+
+    let path = format!("{}/v2/fees/transaction", &http_origin);
+    let tx_payload = TransactionPayload::ContractCall(TransactionContractCall {
+        address: spender_addr.clone().into(),
+        contract_name: ContractName::try_from("increment-contract").unwrap(),
+        function_name: ClarityName::try_from("increment").unwrap(),
+        function_args: vec![],
+    });
+    let payload_hex = format!("0x{}", to_hex(&tx_payload.serialize_to_vec()));
+    let estimated_len = 1000;
+    let body = json!({
+        "transaction_payload": payload_hex,
+        "estimated_len": estimated_len,
+    });
+    let client = reqwest::blocking::Client::new();
+
+    // nothing has called `increment` yet, so there is no cost estimate for it
+    let res = client
+        .post(&path)
+        .json(&body)
+        .send()
+        .expect("Should be able to post");
+    assert_eq!(res.status().as_u16(), 400);
+    let error_body = res
+        .json::<serde_json::Value>()
+        .expect("Failed to parse error into JSON");
+    eprintln!("Fee estimate error before any calls: {:?}", &error_body);
+    assert_eq!(error_body["reason"], "NoEstimateAvailable");
+
+    submit_tx(
+        &http_origin,
+        &make_contract_publish(&spender_sk, 0, 110000, "increment-contract", contract_src),
+    );
+    run_until_burnchain_height(&mut btc_regtest_controller, &blocks_processed, 212, &conf);
+
+    submit_tx(
+        &http_origin,
+        &make_contract_call(
+            &spender_sk,
+            1,
+            100000,
+            &spender_addr.into(),
+            "increment-contract",
+            "increment",
+            &[],
+        ),
+    );
+    run_until_burnchain_height(&mut btc_regtest_controller, &blocks_processed, 214, &conf);
+
+    let res = client
+        .post(&path)
+        .json(&body)
+        .send()
+        .expect("Should be able to post");
+    assert!(res.status().is_success());
+    let fee_estimate = res
+        .json::<RPCFeeEstimateResponse>()
+        .expect("Failed to parse result into JSON");
+    eprintln!("Fee estimate after mining a call: {:?}", &fee_estimate);
+
+    assert!(fee_estimate.estimated_cost.runtime > 0);
+    assert!(fee_estimate.estimated_cost_scalar > 0);
+    assert_eq!(fee_estimate.estimations.len(), 3);
+    let minimum_fee =
+        estimated_len * stacks::chainstate::stacks::db::blocks::MINIMUM_TX_FEE_RATE_PER_BYTE;
+    for estimation in fee_estimate.estimations.iter() {
+        assert!(estimation.fee >= minimum_fee);
+    }
+    // estimations are ordered low, middle, high
+    for i in 1..fee_estimate.estimations.len() {
+        let lower = &fee_estimate.estimations[i - 1];
+        let higher = &fee_estimate.estimations[i];
+        assert!(lower.fee_rate <= higher.fee_rate);
+        assert!(lower.fee <= higher.fee);
+    }
+
+    channel.stop_chains_coordinator();
+}
+
+/// Exercise `/v2/fees/rate` against a live miner: before any fee-paying transaction has been
+/// mined, the endpoint should answer with a structured 503; afterwards, mining blocks of
+/// transfers and contract-calls that pay increasing fees should drive the reported low/middle/high
+/// fee rates up.
+#[test]
+#[ignore]
+fn fee_rate_endpoint_integration_test() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let contract_src = r#"
+(define-data-var counter int 0)
+
+(define-public (increment)
+  (begin
+    (var-set counter (+ (var-get counter) 1))
+    (ok (var-get counter))))
+    "#;
+
+    let spender_sk = StacksPrivateKey::new();
+    let spender_addr = to_addr(&spender_sk);
+    let recipient = PrincipalData::from(to_addr(&StacksPrivateKey::new()));
+
+    let (mut conf, _) = neon_integration_test_conf();
+    conf.initial_balances.push(InitialBalance {
+        address: spender_addr.clone().into(),
+        amount: 10000000000,
+    });
+
+    let mut btcd_controller = BitcoinCoreController::new(conf.clone());
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(conf.clone(), None);
+    let http_origin = format!("http://{}", &conf.node.rpc_bind);
+
+    btc_regtest_controller.bootstrap_chain(200);
+
+    eprintln!("Chain bootstrapped...");
+
+    let mut run_loop = neon::RunLoop::new(conf.clone());
+    let blocks_processed = run_loop.get_blocks_processed_arc();
+
+    let channel = run_loop.get_coordinator_channel().unwrap();
+
+    thread::spawn(move || run_loop.start(None, 0));
+
+    wait_for_runloop(&blocks_processed);
+    run_until_burnchain_height(&mut btc_regtest_controller, &blocks_processed, 210, &conf);
+
+    let path = format!("{}/v2/fees/rate", &http_origin);
+    let client = reqwest::blocking::Client::new();
+
+    // no fee-paying transactions have been mined yet
+    let res = client.get(&path).send().expect("Should be able to GET");
+    assert_eq!(res.status().as_u16(), 503);
+    let error_body = res
+        .json::<serde_json::Value>()
+        .expect("Failed to parse error into JSON");
+    eprintln!("Fee rate error before any transactions: {:?}", &error_body);
+    assert_eq!(error_body["reason"], "NoEstimateAvailable");
+
+    submit_tx(
+        &http_origin,
+        &make_contract_publish(&spender_sk, 0, 110000, "increment-contract", contract_src),
+    );
+    run_until_burnchain_height(&mut btc_regtest_controller, &blocks_processed, 212, &conf);
+
+    // Each round, mine a block with a transfer and a contract-call, paying more than the last.
+    let mut fee_rates: Vec<FeeRateEstimate> = vec![];
+    let mut nonce = 1;
+    for i in 1..6 {
+        submit_tx(
+            &http_origin,
+            &make_stacks_transfer(&spender_sk, nonce, i * 10000, &recipient, 1000),
+        );
+        submit_tx(
+            &http_origin,
+            &make_contract_call(
+                &spender_sk,
+                nonce + 1,
+                i * 100000,
+                &spender_addr.into(),
+                "increment-contract",
+                "increment",
+                &[],
+            ),
+        );
+        nonce += 2;
+        run_until_burnchain_height(
+            &mut btc_regtest_controller,
+            &blocks_processed,
+            212 + 2 * i,
+            &conf,
+        );
+
+        let res = client.get(&path).send().expect("Should be able to GET");
+        assert!(res.status().is_success());
+        let fee_rate = res
+            .json::<FeeRateEstimate>()
+            .expect("Failed to parse result into JSON");
+        eprintln!("Fee rate after round {}: {:?}", i, &fee_rate);
+        fee_rates.push(fee_rate);
+    }
+
+    for fee_rate in fee_rates.iter() {
+        assert!(fee_rate.low <= fee_rate.middle);
+        assert!(fee_rate.middle <= fee_rate.high);
+    }
+    for i in 1..fee_rates.len() {
+        assert!(fee_rates[i].low >= fee_rates[i - 1].low);
+        assert!(fee_rates[i].middle >= fee_rates[i - 1].middle);
+        assert!(fee_rates[i].high >= fee_rates[i - 1].high);
+    }
+    assert!(fee_rates.last().unwrap().middle > fee_rates.first().unwrap().middle);
+
+    channel.stop_chains_coordinator();
+}
+
+/// An observer registered for only `MemPoolTransactions` should learn about a transaction via
+/// `/new_mempool_tx` as soon as it is accepted into the mempool, before any `/new_block`
+/// payload includes it.
+#[test]
+#[ignore]
+fn mempool_tx_event_precedes_block_integration_test() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let spender_sk = StacksPrivateKey::new();
+    let spender_addr = to_addr(&spender_sk);
+    let recipient = PrincipalData::from(to_addr(&StacksPrivateKey::new()));
+
+    let (mut conf, _) = neon_integration_test_conf();
+    conf.initial_balances.push(InitialBalance {
+        address: spender_addr.clone().into(),
+        amount: 10000000000,
+    });
+    test_observer::spawn();
+    test_observer::clear();
+    conf.events_observers.push(EventObserverConfig {
+        endpoint: format!("localhost:{}", test_observer::EVENT_OBSERVER_PORT),
+        events_keys: vec![EventKeyType::MemPoolTransactions],
+    });
+
+    let mut btcd_controller = BitcoinCoreController::new(conf.clone());
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(conf.clone(), None);
+    let http_origin = format!("http://{}", &conf.node.rpc_bind);
+
+    btc_regtest_controller.bootstrap_chain(200);
+
+    eprintln!("Chain bootstrapped...");
+
+    let mut run_loop = neon::RunLoop::new(conf.clone());
+    let blocks_processed = run_loop.get_blocks_processed_arc();
+
+    let channel = run_loop.get_coordinator_channel().unwrap();
+
+    thread::spawn(move || run_loop.start(None, 0));
+
+    wait_for_runloop(&blocks_processed);
+    run_until_burnchain_height(&mut btc_regtest_controller, &blocks_processed, 210, &conf);
+
+    let tx = make_stacks_transfer(&spender_sk, 0, 1000, &recipient, 1000);
+    let raw_tx = format!("0x{}", &bytes_to_hex(&tx));
+    submit_tx(&http_origin, &tx);
+
+    let blocks_with_tx = || {
+        test_observer::get_blocks()
+            .iter()
+            .filter(|block| {
+                block
+                    .get("transactions")
+                    .unwrap()
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .any(|tx| tx.get("raw_tx").unwrap().as_str().unwrap() == raw_tx)
+            })
+            .count()
+    };
+
+    // wait for the mempool event, without mining
+    let start = Instant::now();
+    while !test_observer::get_memtxs().contains(&raw_tx) {
+        assert!(
+            start.elapsed() < Duration::from_secs(60),
+            "Timed out waiting for the mempool event"
+        );
+        sleep_ms(500);
+    }
+    assert_eq!(blocks_with_tx(), 0);
+
+    // now mine it
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    assert_eq!(blocks_with_tx(), 1);
+    assert_eq!(
+        test_observer::get_memtxs()
+            .iter()
+            .filter(|memtx| **memtx == raw_tx)
+            .count(),
+        1
+    );
+    assert!(test_observer::get_memtx_drops().is_empty());
+
+    test_observer::clear();
+    channel.stop_chains_coordinator();
+}
+
+#[test]
+#[ignore]
+fn use_latest_tip_integration_test() {
+    // The purpose of this test is to check if setting the query parameter `tip` to `latest` is working
+    // as expected. Multiple endpoints accept this parameter, and in this test, we are using the
+    // GetContractSrc method to test it.
+    //
+    // The following scenarios are tested here:
+    // - The caller does not specify the tip paramater, and the canonical chain tip is used regardless of the
+    //    state of the unconfirmed microblock stream.
+    // - The caller passes tip=latest with an existing unconfirmed microblock stream, and
+    //   Clarity state from the unconfirmed microblock stream is successfully loaded.
+    // - The caller passes tip=latest with an empty unconfirmed microblock stream, and
+    //   Clarity state from the canonical chain tip is successfully loaded (i.e. you don't
+    //   get a 404 even though the unconfirmed chain tip points to a nonexistent MARF trie).
+    //
+    // Note: In this test, we are manually creating a microblock as well as reloading the unconfirmed
+    // state of the chainstate, instead of relying on `next_block_and_wait` to generate
+    // microblocks. We do this because the unconfirmed state is not automatically being initialized
+    // on the node, so attempting to validate any transactions against the expected unconfirmed
+    // state fails.
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let spender_sk = StacksPrivateKey::from_hex(SK_1).unwrap();
+    let spender_stacks_addr = to_addr(&spender_sk);
+    let spender_addr: PrincipalData = spender_stacks_addr.into();
+
+    let (mut conf, _) = neon_integration_test_conf();
+
+    conf.initial_balances.push(InitialBalance {
+        address: spender_addr.clone(),
+        amount: 100300,
+    });
+
+    conf.node.mine_microblocks = true;
+    conf.node.wait_time_for_microblocks = 10_000;
+    conf.node.microblock_frequency = 1_000;
+
+    test_observer::spawn();
 
     conf.events_observers.push(EventObserverConfig {
         endpoint: format!("localhost:{}", test_observer::EVENT_OBSERVER_PORT),
@@ -10730,3 +12226,2231 @@ fn microblock_miner_multiple_attempts() {
 
     channel.stop_chains_coordinator();
 }
+
+/// Test that `/new_block` payloads generated while the event observer is down are delivered,
+/// in order, once it comes back, and that the pending payload queue is drained afterwards.
+#[test]
+#[ignore]
+fn event_observer_redelivery_integration_test() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let (mut conf, _) = neon_integration_test_conf();
+    test_observer::spawn();
+    conf.events_observers.push(EventObserverConfig {
+        endpoint: format!("localhost:{}", test_observer::EVENT_OBSERVER_PORT),
+        events_keys: vec![EventKeyType::AnyEvent],
+    });
+
+    let mut btcd_controller = BitcoinCoreController::new(conf.clone());
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(conf.clone(), None);
+
+    btc_regtest_controller.bootstrap_chain(200);
+
+    eprintln!("Chain bootstrapped...");
+
+    let mut run_loop = neon::RunLoop::new(conf.clone());
+    let blocks_processed = run_loop.get_blocks_processed_arc();
+
+    let channel = run_loop.get_coordinator_channel().unwrap();
+
+    thread::spawn(move || run_loop.start(None, 0));
+
+    wait_for_runloop(&blocks_processed);
+    run_until_burnchain_height(&mut btc_regtest_controller, &blocks_processed, 210, &conf);
+
+    let block_height =
+        |block: &serde_json::Value| block.get("block_height").unwrap().as_u64().unwrap();
+    let blocks_before = test_observer::get_blocks();
+    assert!(!blocks_before.is_empty());
+    let last_height = block_height(blocks_before.last().unwrap());
+
+    // mine two blocks while the observer is down. Payloads are queued for delivery, so block
+    // processing must not wait for the observer to come back.
+    test_observer::kill();
+    for _ in 0..2 {
+        assert!(next_block_and_wait(
+            &mut btc_regtest_controller,
+            &blocks_processed
+        ));
+    }
+    assert_eq!(test_observer::get_blocks().len(), blocks_before.len());
+
+    test_observer::restart();
+
+    // keep mining until both blocks have been delivered
+    let start = Instant::now();
+    while test_observer::get_blocks().len() < blocks_before.len() + 2 {
+        assert!(
+            start.elapsed() < Duration::from_secs(300),
+            "Timed out waiting for the payloads to be redelivered"
+        );
+        next_block_and_wait_with_timeout(&mut btc_regtest_controller, &blocks_processed, 60);
+    }
+
+    let blocks_after = test_observer::get_blocks();
+    for (i, block) in blocks_after[blocks_before.len()..].iter().enumerate() {
+        assert_eq!(block_height(block), last_height + 1 + i as u64);
+    }
+
+    // every delivered payload is acknowledged, so nothing is left to replay once the node
+    // stops producing events
+    channel.stop_chains_coordinator();
+    let db = rusqlite::Connection::open(conf.get_event_observers_db_path()).unwrap();
+    let count_pending = || -> i64 {
+        db.query_row(
+            "SELECT COUNT(*) FROM pending_payloads",
+            rusqlite::NO_PARAMS,
+            |row| row.get(0),
+        )
+        .unwrap()
+    };
+    let start = Instant::now();
+    while count_pending() > 0 {
+        assert!(
+            start.elapsed() < Duration::from_secs(60),
+            "Timed out waiting for the pending payloads to drain"
+        );
+        sleep_ms(500);
+    }
+
+    test_observer::clear();
+}
+
+/// An observer subscribed to one contract must only see that contract's print events and the
+/// transactions involving it, even when another contract in the same blocks prints too.
+#[test]
+#[ignore]
+fn contract_event_observer_integration_test() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let spender_sk = StacksPrivateKey::from_hex(SK_1).unwrap();
+    let spender_addr = to_addr(&spender_sk);
+
+    let (mut conf, _) = neon_integration_test_conf();
+    conf.initial_balances.push(InitialBalance {
+        address: spender_addr.clone().into(),
+        amount: 10_000_000,
+    });
+
+    let watched_contract =
+        QualifiedContractIdentifier::new(spender_addr.clone().into(), "watched".into());
+    test_observer::spawn();
+    conf.events_observers.push(EventObserverConfig {
+        endpoint: format!("localhost:{}", test_observer::EVENT_OBSERVER_PORT),
+        events_keys: vec![EventKeyType::ContractEvent(watched_contract.clone())],
+    });
+
+    let mut btcd_controller = BitcoinCoreController::new(conf.clone());
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(conf.clone(), None);
+    let http_origin = format!("http://{}", &conf.node.rpc_bind);
+
+    btc_regtest_controller.bootstrap_chain(201);
+
+    eprintln!("Chain bootstrapped...");
+
+    let mut run_loop = neon::RunLoop::new(conf.clone());
+    let blocks_processed = run_loop.get_blocks_processed_arc();
+
+    let channel = run_loop.get_coordinator_channel().unwrap();
+
+    thread::spawn(move || run_loop.start(None, 0));
+
+    // give the run loop some time to start up!
+    wait_for_runloop(&blocks_processed);
+
+    // first block wakes up the run loop
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // first block will hold our VRF registration
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // second block will be the first mined Stacks block
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // both contracts print when they are deployed and when `emit` is called
+    let contract = "
+        (print \"deployed\")
+        (define-public (emit) (begin (print \"emitted\") (ok true)))";
+
+    let watched_txids = vec![
+        submit_tx(
+            &http_origin,
+            &make_contract_publish(&spender_sk, 0, 1000, "watched", contract),
+        ),
+        submit_tx(
+            &http_origin,
+            &make_contract_call(&spender_sk, 2, 1000, &spender_addr, "watched", "emit", &[]),
+        ),
+    ];
+    let unwatched_txids = vec![
+        submit_tx(
+            &http_origin,
+            &make_contract_publish(&spender_sk, 1, 1000, "unwatched", contract),
+        ),
+        submit_tx(
+            &http_origin,
+            &make_contract_call(
+                &spender_sk,
+                3,
+                1000,
+                &spender_addr,
+                "unwatched",
+                "emit",
+                &[],
+            ),
+        ),
+    ];
+
+    for _ in 0..4 {
+        next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+    }
+
+    let account = get_account(&http_origin, &spender_addr);
+    assert_eq!(account.nonce, 4);
+
+    let mut watched_events = 0;
+    let mut seen_txids = HashSet::new();
+    for block in test_observer::get_blocks().iter() {
+        for event in block.get("events").unwrap().as_array().unwrap() {
+            assert_eq!(
+                event.get("type").unwrap().as_str().unwrap(),
+                "contract_event"
+            );
+            assert_eq!(
+                event["contract_event"]["contract_identifier"]
+                    .as_str()
+                    .unwrap(),
+                watched_contract.to_string()
+            );
+            watched_events += 1;
+        }
+        for tx in block.get("transactions").unwrap().as_array().unwrap() {
+            let txid = tx.get("txid").unwrap().as_str().unwrap().to_string();
+            assert!(
+                !unwatched_txids
+                    .iter()
+                    .any(|unwatched| txid == format!("0x{}", unwatched)),
+                "Observer received unrelated transaction {}",
+                &txid
+            );
+            seen_txids.insert(txid);
+        }
+    }
+
+    assert_eq!(watched_events, 2);
+    for watched in watched_txids.iter() {
+        assert!(seen_txids.contains(&format!("0x{}", watched)));
+    }
+    assert_eq!(seen_txids.len(), watched_txids.len());
+
+    test_observer::clear();
+    channel.stop_chains_coordinator();
+}
+
+/// A block-commit that is stuck in bitcoind's mempool must be replaced by fee when the miner
+/// submits a new one: the replacement spends the same inputs, so bitcoind only accepts it if it
+/// pays a higher fee, and only the replacement can confirm.  bitcoind's relay fee cannot be
+/// changed over RPC, so the first commit is kept unconfirmed by simply not mining a block.
+#[test]
+#[ignore]
+fn block_commit_rbf_replaces_stuck_commit() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let (mut conf, _) = neon_integration_test_conf();
+    // only the block-commits sent below may spend the miner's UTXOs
+    conf.node.miner = false;
+    conf.burnchain.max_rbf = 1000000;
+
+    let mut btcd_controller = BitcoinCoreController::new(conf.clone());
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(conf.clone(), None);
+
+    btc_regtest_controller.bootstrap_chain(201);
+
+    eprintln!("Chain bootstrapped...");
+
+    let mut run_loop = neon::RunLoop::new(conf.clone());
+    let blocks_processed = run_loop.get_blocks_processed_arc();
+
+    let channel = run_loop.get_coordinator_channel().unwrap();
+
+    thread::spawn(move || run_loop.start(None, 0));
+
+    // give the run loop some time to start up!
+    wait_for_runloop(&blocks_processed);
+
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    let burnchain = Burnchain::regtest(&conf.get_burn_db_path());
+    let keychain = Keychain::default(conf.node.seed.clone());
+    let mut bitcoin_controller = BitcoinRegtestController::new_dummy(conf.clone());
+
+    let make_commit = |block_header_hash: BlockHeaderHash| {
+        let tip_info = get_chain_info(&conf);
+        let commit_outs = if tip_info.burn_block_height + 1 < burnchain.pox_constants.sunset_end
+            && !burnchain.is_in_prepare_phase(tip_info.burn_block_height + 1)
+        {
+            vec![
+                PoxAddress::standard_burn_address(conf.is_mainnet()),
+                PoxAddress::standard_burn_address(conf.is_mainnet()),
+            ]
+        } else {
+            vec![PoxAddress::standard_burn_address(conf.is_mainnet())]
+        };
+
+        BlockstackOperationType::LeaderBlockCommit(LeaderBlockCommitOp {
+            sunset_burn: 0,
+            block_header_hash,
+            burn_fee: 20_000,
+            input: (Txid([0; 32]), 0),
+            apparent_sender: keychain.get_burnchain_signer(),
+            key_block_ptr: 0,
+            key_vtxindex: 0,
+            memo: vec![0],
+            new_seed: VRFSeed([0x11; 32]),
+            parent_block_ptr: 0,
+            parent_vtxindex: 0,
+            // to be filled in
+            vtxindex: 0,
+            txid: Txid([0u8; 32]),
+            block_height: 0,
+            burn_header_hash: BurnchainHeaderHash::zero(),
+            burn_parent_modulus: (tip_info.burn_block_height % BURN_BLOCK_MINED_AT_MODULUS) as u8,
+            commit_outs,
+        })
+    };
+
+    let first_txid = bitcoin_controller
+        .submit_operation(
+            StacksEpochId::Epoch20,
+            make_commit(BlockHeaderHash([0x01; 32])),
+            &mut keychain.generate_op_signer(),
+            1,
+        )
+        .expect("Failed to submit block-commit");
+    let ongoing = bitcoin_controller.get_ongoing_commit().unwrap();
+    assert_eq!(ongoing.txids(), &vec![first_txid.clone()]);
+
+    // no block was mined, so the first commit is still in the mempool. bitcoind only accepts a
+    // conflicting spend of its inputs if it pays more, so acceptance means it was replaced by fee.
+    let second_txid = bitcoin_controller
+        .submit_operation(
+            StacksEpochId::Epoch20,
+            make_commit(BlockHeaderHash([0x02; 32])),
+            &mut keychain.generate_op_signer(),
+            2,
+        )
+        .expect("Failed to replace block-commit by fee");
+    assert_ne!(first_txid, second_txid);
+    let ongoing = bitcoin_controller.get_ongoing_commit().unwrap();
+    assert_eq!(
+        ongoing.txids(),
+        &vec![first_txid.clone(), second_txid.clone()]
+    );
+
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    let burnchain_db = BurnchainDB::open(&burnchain.get_burnchaindb_path(), false).unwrap();
+    let indexer = make_bitcoin_indexer(&conf);
+    assert!(burnchain_db
+        .find_burnchain_op(&indexer, &second_txid)
+        .is_some());
+    assert!(burnchain_db
+        .find_burnchain_op(&indexer, &first_txid)
+        .is_none());
+
+    // the replacement confirmed, so the next commit must not try to RBF it
+    let third_txid = bitcoin_controller
+        .submit_operation(
+            StacksEpochId::Epoch20,
+            make_commit(BlockHeaderHash([0x03; 32])),
+            &mut keychain.generate_op_signer(),
+            3,
+        )
+        .expect("Failed to submit block-commit");
+    let ongoing = bitcoin_controller.get_ongoing_commit().unwrap();
+    assert_eq!(ongoing.txids(), &vec![third_txid]);
+
+    channel.stop_chains_coordinator();
+}
+
+/// With VRF key rotation on, a miner registers one VRF key per reward cycle. A miner that is
+/// restarted in the middle of a reward cycle must keep winning sortitions with the key it already
+/// registered for that cycle, and must register a new key once the next cycle starts.
+#[test]
+#[ignore]
+fn vrf_key_rotation_restart_integration_test() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let (mut conf, _) = neon_integration_test_conf();
+    conf.miner.rotate_vrf_keys = true;
+
+    let mut btcd_controller = BitcoinCoreController::new(conf.clone());
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    // reward cycles are long enough that the restart below happens mid-cycle
+    let mut burnchain_config = Burnchain::regtest(&conf.get_burn_db_path());
+    let reward_cycle_len = 20;
+    let prepare_phase_len = 2;
+    let pox_constants = PoxConstants::new(
+        reward_cycle_len,
+        prepare_phase_len,
+        2,
+        5,
+        15,
+        (16 * reward_cycle_len - 1).into(),
+        (17 * reward_cycle_len).into(),
+        u32::MAX,
+        u32::MAX,
+        u32::MAX,
+    );
+    burnchain_config.pox_constants = pox_constants;
+
+    let mut btc_regtest_controller = BitcoinRegtestController::with_burnchain(
+        conf.clone(),
+        None,
+        Some(burnchain_config.clone()),
+        None,
+    );
+
+    btc_regtest_controller.bootstrap_chain(201);
+
+    eprintln!("Chain bootstrapped...");
+
+    let registered_keys = |reward_cycle: u64| {
+        let conn = open_vrf_keys_db(&conf.get_vrf_keys_db_path()).unwrap();
+        get_registered_vrf_keys(&conn, reward_cycle).unwrap()
+    };
+    let reward_cycle_of = |burn_height: u64| {
+        burnchain_config
+            .block_height_to_reward_cycle(burn_height)
+            .unwrap()
+    };
+
+    let mut run_loop = neon::RunLoop::new(conf.clone());
+    let blocks_processed = run_loop.get_blocks_processed_arc();
+    let channel = run_loop.get_coordinator_channel().unwrap();
+    let termination_switch = run_loop.get_termination_switch();
+
+    let run_loop_burnchain = burnchain_config.clone();
+    let run_loop_thread = thread::spawn(move || run_loop.start(Some(run_loop_burnchain), 0));
+
+    wait_for_runloop(&blocks_processed);
+
+    // first block wakes up the run loop, the second holds our VRF key registration, and the
+    // rest are mined with it
+    for _ in 0..5 {
+        next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+    }
+
+    let tip_info = get_chain_info(&conf);
+    let reward_cycle = reward_cycle_of(tip_info.burn_block_height);
+    assert!(tip_info.stacks_tip_height >= 2);
+    let keys = registered_keys(reward_cycle);
+    assert_eq!(keys.len(), 1);
+    let first_key = keys[0].vrf_public_key.clone();
+
+    // restart the node mid-cycle
+    assert!(
+        reward_cycle_of(tip_info.burn_block_height + 5) == reward_cycle,
+        "restart must happen mid-cycle"
+    );
+    channel.stop_chains_coordinator();
+    termination_switch.store(false, Ordering::SeqCst);
+    run_loop_thread.join().unwrap();
+
+    eprintln!("Restarting node...");
+
+    let mut run_loop = neon::RunLoop::new(conf.clone());
+    let blocks_processed = run_loop.get_blocks_processed_arc();
+    let channel = run_loop.get_coordinator_channel().unwrap();
+
+    let run_loop_burnchain = burnchain_config.clone();
+    thread::spawn(move || run_loop.start(Some(run_loop_burnchain), 0));
+
+    wait_for_runloop(&blocks_processed);
+
+    // the restarted miner wins sortitions right away, with the key it already registered
+    let stacks_height_before = get_chain_info(&conf).stacks_tip_height;
+    for _ in 0..3 {
+        next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+    }
+    let tip_info = get_chain_info(&conf);
+    assert_eq!(reward_cycle_of(tip_info.burn_block_height), reward_cycle);
+    assert!(tip_info.stacks_tip_height >= stacks_height_before + 2);
+    let keys = registered_keys(reward_cycle);
+    assert_eq!(keys.len(), 1);
+    assert_eq!(keys[0].vrf_public_key, first_key);
+
+    // once the next reward cycle starts, the miner registers a new key and keeps mining
+    while reward_cycle_of(get_chain_info(&conf).burn_block_height) == reward_cycle {
+        next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+    }
+    let stacks_height_before = get_chain_info(&conf).stacks_tip_height;
+    for _ in 0..4 {
+        next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+    }
+    assert!(get_chain_info(&conf).stacks_tip_height >= stacks_height_before + 2);
+
+    let keys = registered_keys(reward_cycle + 1);
+    assert_eq!(keys.len(), 1);
+    assert_ne!(keys[0].vrf_public_key, first_key);
+
+    channel.stop_chains_coordinator();
+}
+
+/// The block-commit endpoints must report the block-commits the miner submitted for the blocks it
+/// mined, whether they are looked up by burnchain height or by Stacks block, and must report
+/// burnchain blocks without a winning block-commit as such.
+#[test]
+#[ignore]
+fn block_commit_info_integration_test() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let (mut conf, _) = neon_integration_test_conf();
+    test_observer::spawn();
+    conf.events_observers.push(EventObserverConfig {
+        endpoint: format!("localhost:{}", test_observer::EVENT_OBSERVER_PORT),
+        events_keys: vec![EventKeyType::AnyEvent],
+    });
+
+    let mut btcd_controller = BitcoinCoreController::new(conf.clone());
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(conf.clone(), None);
+    let http_origin = format!("http://{}", &conf.node.rpc_bind);
+
+    btc_regtest_controller.bootstrap_chain(201);
+
+    eprintln!("Chain bootstrapped...");
+
+    let mut run_loop = neon::RunLoop::new(conf.clone());
+    let blocks_processed = run_loop.get_blocks_processed_arc();
+
+    let channel = run_loop.get_coordinator_channel().unwrap();
+
+    thread::spawn(move || run_loop.start(None, 0));
+
+    wait_for_runloop(&blocks_processed);
+
+    // first block wakes up the run loop
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // first block will hold our VRF registration
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+    let key_block_ptr = get_chain_info(&conf).burn_block_height as u32;
+
+    // the rest are mined by us
+    for _ in 0..5 {
+        next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+    }
+
+    // nobody mined during bootstrapping
+    let bootstrap_info = get_block_commit_info(&http_origin, 150);
+    assert_eq!(bootstrap_info.burn_block_height, 150);
+    assert!(bootstrap_info.winning_block_commit.is_none());
+
+    let blocks = test_observer::get_blocks();
+    assert!(blocks.len() >= 4);
+
+    let mut last_burn_height = None;
+    for block in blocks.iter() {
+        let burn_height = block.get("burn_block_height").unwrap().as_u64().unwrap();
+        let index_block_hash = block.get("index_block_hash").unwrap().as_str().unwrap();
+        let miner_txid = block.get("miner_txid").unwrap().as_str().unwrap();
+
+        let commit_info = get_block_commit_info(&http_origin, burn_height);
+        assert_eq!(
+            commit_info,
+            get_block_commit_info_by_stacks_block(&http_origin, &index_block_hash[2..])
+        );
+        assert_eq!(commit_info.burn_block_height, burn_height);
+
+        // this is the block-commit our miner submitted, for the full spend amount
+        let commit = commit_info.winning_block_commit.unwrap();
+        assert_eq!(format!("0x{}", commit.txid), miner_txid);
+        assert_eq!(commit.burn_fee, conf.burnchain.burn_fee_cap);
+        assert_eq!(commit.sunset_burn, 0);
+        assert_eq!(commit.key_block_ptr, key_block_ptr);
+        assert!(!commit.commit_outs.is_empty());
+        if let Some(last_burn_height) = last_burn_height {
+            assert_eq!(commit.parent_block_ptr as u64, last_burn_height);
+        }
+        last_burn_height = Some(burn_height);
+    }
+
+    test_observer::clear();
+    channel.stop_chains_coordinator();
+}
+
+/// Mine a few blocks with a miner configured by `configure`, and return the miner's config along
+/// with the burn fees of the winning block-commits for the blocks it mined, in order.
+fn run_burn_fee_strategy_test<F: FnOnce(&mut Config)>(configure: F) -> (Config, Vec<u64>) {
+    let (mut conf, _) = neon_integration_test_conf();
+    configure(&mut conf);
+
+    test_observer::spawn();
+    conf.events_observers.push(EventObserverConfig {
+        endpoint: format!("localhost:{}", test_observer::EVENT_OBSERVER_PORT),
+        events_keys: vec![EventKeyType::AnyEvent],
+    });
+
+    let mut btcd_controller = BitcoinCoreController::new(conf.clone());
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(conf.clone(), None);
+    let http_origin = format!("http://{}", &conf.node.rpc_bind);
+
+    btc_regtest_controller.bootstrap_chain(201);
+
+    eprintln!("Chain bootstrapped...");
+
+    let mut run_loop = neon::RunLoop::new(conf.clone());
+    let blocks_processed = run_loop.get_blocks_processed_arc();
+
+    let channel = run_loop.get_coordinator_channel().unwrap();
+
+    thread::spawn(move || run_loop.start(None, 0));
+
+    wait_for_runloop(&blocks_processed);
+
+    // first block wakes up the run loop
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // first block will hold our VRF registration
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // the rest are mined by us
+    for _ in 0..6 {
+        next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+    }
+
+    let blocks = test_observer::get_blocks();
+    assert!(blocks.len() >= 4);
+
+    // every block was mined with a block-commit that the burnchain accepted
+    let burn_fees = blocks
+        .iter()
+        .map(|block| {
+            let burn_height = block.get("burn_block_height").unwrap().as_u64().unwrap();
+            let miner_txid = block.get("miner_txid").unwrap().as_str().unwrap();
+            let commit = get_block_commit_info(&http_origin, burn_height)
+                .winning_block_commit
+                .unwrap();
+            assert_eq!(format!("0x{}", commit.txid), miner_txid);
+            commit.burn_fee
+        })
+        .collect();
+
+    test_observer::clear();
+    channel.stop_chains_coordinator();
+
+    (conf, burn_fees)
+}
+
+#[test]
+#[ignore]
+fn burn_fee_strategy_fixed_integration_test() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let (conf, burn_fees) = run_burn_fee_strategy_test(|conf| {
+        conf.miner.burn_fee_strategy = BurnFeeStrategy::Fixed;
+    });
+    for burn_fee in burn_fees {
+        assert_eq!(burn_fee, conf.burnchain.burn_fee_cap);
+    }
+}
+
+#[test]
+#[ignore]
+fn burn_fee_strategy_balance_percent_integration_test() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    // the miner holds thousands of BTC after bootstrapping, so 1% of its balance is well above
+    // the default cap, but well below this one.
+    let default_burn_fee_cap = neon_integration_test_conf().0.burnchain.burn_fee_cap;
+    let (conf, burn_fees) = run_burn_fee_strategy_test(|conf| {
+        conf.burnchain.burn_fee_cap = 1_000 * 100_000_000;
+        conf.miner.burn_fee_strategy = BurnFeeStrategy::BalancePercent;
+        conf.miner.burn_fee_balance_pct = 1;
+    });
+    for burn_fee in burn_fees {
+        assert!(burn_fee > default_burn_fee_cap);
+        assert!(burn_fee < conf.burnchain.burn_fee_cap);
+    }
+}
+
+#[test]
+#[ignore]
+fn burn_fee_strategy_follow_winners_integration_test() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    // with a window of one sortition, each block-commit bids half of the last one that won
+    let (conf, burn_fees) = run_burn_fee_strategy_test(|conf| {
+        conf.miner.burn_fee_strategy = BurnFeeStrategy::FollowWinners;
+        conf.miner.burn_fee_follow_window = 1;
+        conf.miner.burn_fee_follow_pct = 50;
+    });
+    let burn_fee_cap = conf.burnchain.burn_fee_cap;
+
+    // nobody won the sortition before the first block, so it bids the cap
+    assert_eq!(burn_fees[0], burn_fee_cap);
+    assert!(burn_fees.iter().any(|burn_fee| *burn_fee < burn_fee_cap));
+    for burn_fee in burn_fees.iter() {
+        assert!((0..32).any(|halvings| *burn_fee == (burn_fee_cap >> halvings).max(1)));
+    }
+}
+
+/// Two miners compete for sortitions.  Miner 0 adapts its burn to its win rate, and miner 1
+/// outbids it by far.  Miner 0 raises its burn while it keeps losing, and lowers it again once
+/// miner 1 stops mining and it starts winning.
+#[test]
+#[ignore]
+fn burn_fee_strategy_win_rate_integration_test() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let num_miners = 2;
+
+    let mut confs = vec![];
+    let mut blocks_processed = vec![];
+    let mut miner_status = vec![];
+
+    for _i in 0..num_miners {
+        let seed = StacksPrivateKey::new().to_bytes();
+        let (mut conf, _) = neon_integration_test_conf_with_seed(seed);
+
+        conf.miner.first_attempt_time_ms = 2_000;
+        conf.miner.subsequent_attempt_time_ms = 5_000;
+        conf.node.wait_time_for_blocks = 1_000;
+
+        // multiple nodes so they must download from each other
+        conf.miner.wait_for_block_download = true;
+
+        confs.push(conf);
+    }
+
+    let burn_fee_cap = confs[0].burnchain.burn_fee_cap;
+    let min_burn_fee = burn_fee_cap / 10;
+    confs[0].miner.burn_fee_strategy = BurnFeeStrategy::WinRate;
+    confs[0].miner.min_burn_fee = min_burn_fee;
+    confs[0].miner.burn_fee_win_rate_window = 6;
+    confs[0].miner.burn_fee_loss_increase_pct = 10;
+    confs[0].miner.burn_fee_win_decrease_pct = 10;
+    confs[1].miner.burn_fee_strategy = BurnFeeStrategy::Fixed;
+    confs[1].burnchain.burn_fee_cap = 100 * burn_fee_cap;
+
+    let node_privkey_1 =
+        StacksNode::make_node_private_key_from_seed(&confs[0].node.local_peer_seed);
+    for i in 1..num_miners {
+        let chain_id = confs[0].burnchain.chain_id;
+        let peer_version = confs[0].burnchain.peer_version;
+        let p2p_bind = confs[0].node.p2p_bind.clone();
+
+        confs[i].node.set_bootstrap_nodes(
+            format!(
+                "{}@{}",
+                &StacksPublicKey::from_private(&node_privkey_1).to_hex(),
+                p2p_bind
+            ),
+            chain_id,
+            peer_version,
+        );
+    }
+
+    let mut btcd_controller = BitcoinCoreController::new(confs[0].clone());
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(confs[0].clone(), None);
+
+    btc_regtest_controller.bootstrap_chain(1);
+
+    // make sure all miners have BTC
+    for i in 1..num_miners {
+        let old_mining_pubkey = btc_regtest_controller.get_mining_pubkey().unwrap();
+        btc_regtest_controller
+            .set_mining_pubkey(confs[i].burnchain.local_mining_public_key.clone().unwrap());
+        btc_regtest_controller.bootstrap_chain(1);
+        btc_regtest_controller.set_mining_pubkey(old_mining_pubkey);
+    }
+
+    btc_regtest_controller.bootstrap_chain((199 - num_miners) as u64);
+
+    eprintln!("Chain bootstrapped...");
+
+    for i in 0..num_miners {
+        let mut run_loop = neon::RunLoop::new(confs[i].clone());
+        blocks_processed.push(run_loop.get_blocks_processed_arc());
+        miner_status.push(run_loop.get_miner_status());
+        thread::spawn(move || run_loop.start(None, 0));
+    }
+
+    // give the run loops some time to start up!
+    for i in 0..num_miners {
+        wait_for_runloop(&blocks_processed[i as usize]);
+    }
+
+    // activate miners
+    for i in 0..num_miners {
+        loop {
+            let tip_info_opt = get_chain_info_opt(&confs[i]);
+            if let Some(tip_info) = tip_info_opt {
+                if tip_info.stacks_tip_height > 0 {
+                    break;
+                }
+            } else {
+                eprintln!("\n\nWaiting for miner {}...\n\n", i);
+            }
+            next_block_and_iterate(
+                &mut btc_regtest_controller,
+                &blocks_processed[i as usize],
+                5_000,
+            );
+        }
+    }
+
+    // with no history, miner 0 would burn halfway between its floor and its cap
+    let starting_burn_fee = min_burn_fee + (burn_fee_cap - min_burn_fee) / 2;
+
+    // miner 0 loses (nearly) every sortition, so it raises its burn
+    for _i in 0..10 {
+        next_block_and_iterate(&mut btc_regtest_controller, &blocks_processed[0], 5_000);
+    }
+    let losing_info = get_chain_info(&confs[0]).miner.unwrap();
+    eprintln!("Miner 0 while losing: {:?}", &losing_info);
+    assert!(losing_info.recent_sortitions_entered.unwrap() > 0);
+    assert!(
+        losing_info.recent_sortitions_won.unwrap() < losing_info.recent_sortitions_entered.unwrap()
+    );
+    assert!(losing_info.last_burn_fee > starting_burn_fee);
+    assert!(losing_info.last_burn_fee <= burn_fee_cap);
+
+    // miner 1 doesn't report a win rate, since its strategy doesn't track it
+    let fixed_info = get_chain_info(&confs[1]).miner.unwrap();
+    assert_eq!(fixed_info.last_burn_fee, 100 * burn_fee_cap);
+    assert!(fixed_info.recent_sortitions_entered.is_none());
+
+    // miner 1 stops mining, so miner 0 wins every sortition and lowers its burn
+    miner_status[1].lock().unwrap().add_blocked();
+    for _i in 0..10 {
+        next_block_and_iterate(&mut btc_regtest_controller, &blocks_processed[0], 5_000);
+    }
+    let winning_info = get_chain_info(&confs[0]).miner.unwrap();
+    eprintln!("Miner 0 while winning: {:?}", &winning_info);
+    assert!(winning_info.recent_sortitions_entered.unwrap() > 0);
+    assert_eq!(
+        winning_info.recent_sortitions_won,
+        winning_info.recent_sortitions_entered
+    );
+    assert!(winning_info.last_burn_fee < losing_info.last_burn_fee);
+    assert!(winning_info.last_burn_fee >= min_burn_fee);
+    assert!(winning_info.last_burn_fee_block_height > losing_info.last_burn_fee_block_height);
+}
+
+/// A pending transaction must be reported as in the mempool until it is mined into a microblock,
+/// as in a microblock until an anchored block confirms it, and as not found afterwards.
+#[test]
+#[ignore]
+fn unconfirmed_tx_status_integration_test() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let spender_sk = StacksPrivateKey::new();
+    let spender_addr: PrincipalData = to_addr(&spender_sk).into();
+    let recipient: PrincipalData = to_addr(&StacksPrivateKey::new()).into();
+
+    let (mut conf, _) = neon_integration_test_conf();
+
+    conf.miner.wait_for_block_download = false;
+    conf.initial_balances.push(InitialBalance {
+        address: spender_addr.clone(),
+        amount: 100_000,
+    });
+
+    conf.node.mine_microblocks = true;
+    conf.node.microblock_frequency = 1_000;
+    conf.miner.microblock_attempt_time_ms = 1_000;
+    conf.node.wait_time_for_microblocks = 0;
+
+    let mut btcd_controller = BitcoinCoreController::new(conf.clone());
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(conf.clone(), None);
+    let http_origin = format!("http://{}", &conf.node.rpc_bind);
+
+    btc_regtest_controller.bootstrap_chain(201);
+
+    eprintln!("Chain bootstrapped...");
+
+    let mut run_loop = neon::RunLoop::new(conf.clone());
+    let blocks_processed = run_loop.get_blocks_processed_arc();
+    let channel = run_loop.get_coordinator_channel().unwrap();
+
+    thread::spawn(move || run_loop.start(None, 0));
+
+    // give the run loop some time to start up!
+    wait_for_runloop(&blocks_processed);
+
+    // first block wakes up the run loop
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // first block will hold our VRF registration
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // there is no Stacks block to build microblocks on yet, so a microblock-only transaction
+    // stays in the mempool
+    let tx = make_stacks_transfer_mblock_only(&spender_sk, 0, 1000, &recipient, 1000);
+    let txid = Txid::from_hex(&submit_tx(&http_origin, &tx)).unwrap();
+
+    let status = get_unconfirmed_tx_status(&http_origin, &txid).unwrap();
+    assert_eq!(status.status, UnconfirmedTransactionStatus::Mempool);
+    assert_eq!(status.tx, to_hex(&tx));
+    assert_eq!(status.fee, 1000);
+    assert_eq!(status.nonce, 0);
+    let receipt_time = status.receipt_time.unwrap();
+
+    // once the first Stacks block is mined, the transaction goes into a microblock on top of it
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    let start = Instant::now();
+    let status = loop {
+        let status = get_unconfirmed_tx_status(&http_origin, &txid).unwrap();
+        if status.status != UnconfirmedTransactionStatus::Mempool {
+            break status;
+        }
+        assert!(
+            start.elapsed() < Duration::from_secs(60),
+            "Timed out waiting for the transaction to be mined into a microblock"
+        );
+        sleep_ms(1_000);
+    };
+    match status.status {
+        UnconfirmedTransactionStatus::Microblock { seq, .. } => assert_eq!(seq, 0),
+        UnconfirmedTransactionStatus::Mempool => unreachable!(),
+    }
+    assert_eq!(status.fee, 1000);
+    assert_eq!(status.nonce, 0);
+    assert_eq!(status.receipt_time, Some(receipt_time));
+
+    // once the next anchored block confirms the microblock, the transaction is no longer pending
+    for _ in 0..5 {
+        next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+        if get_unconfirmed_tx_status(&http_origin, &txid).is_none() {
+            break;
+        }
+    }
+    assert!(get_unconfirmed_tx_status(&http_origin, &txid).is_none());
+    assert_eq!(get_account(&http_origin, &spender_addr).nonce, 1);
+
+    channel.stop_chains_coordinator();
+}
+
+/// A dry-run contract-call must report the same execution cost that the event observer reports
+/// once the same transaction is mined, and must not keep any of the call's writes.
+#[test]
+#[ignore]
+fn dry_run_execution_cost_integration_test() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let contract_src = "
+    (define-data-var counter int 0)
+    (define-public (bump (amount uint))
+        (begin
+            (var-set counter (+ (var-get counter) 1))
+            (try! (stx-burn? amount tx-sender))
+            (ok (var-get counter))))
+    ";
+
+    let spender_sk = StacksPrivateKey::new();
+    let spender_addr = to_addr(&spender_sk);
+    let spender_princ: PrincipalData = spender_addr.into();
+
+    let (mut conf, _) = neon_integration_test_conf();
+
+    test_observer::spawn();
+    conf.events_observers.push(EventObserverConfig {
+        endpoint: format!("localhost:{}", test_observer::EVENT_OBSERVER_PORT),
+        events_keys: vec![EventKeyType::AnyEvent],
+    });
+
+    conf.initial_balances.push(InitialBalance {
+        address: spender_princ.clone(),
+        amount: 10_000_000,
+    });
+
+    let mut btcd_controller = BitcoinCoreController::new(conf.clone());
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(conf.clone(), None);
+    let http_origin = format!("http://{}", &conf.node.rpc_bind);
+
+    btc_regtest_controller.bootstrap_chain(201);
+
+    eprintln!("Chain bootstrapped...");
+
+    let mut run_loop = neon::RunLoop::new(conf.clone());
+    let blocks_processed = run_loop.get_blocks_processed_arc();
+    let channel = run_loop.get_coordinator_channel().unwrap();
+
+    thread::spawn(move || run_loop.start(None, 0));
+
+    // give the run loop some time to start up!
+    wait_for_runloop(&blocks_processed);
+
+    // first block wakes up the run loop
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // first block will hold our VRF registration
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // second block will be the first mined Stacks block
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    let publish = make_contract_publish(&spender_sk, 0, 10_000, "counter", contract_src);
+    submit_tx(&http_origin, &publish);
+
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    let account = get_account(&http_origin, &spender_addr);
+    assert_eq!(account.nonce, 1);
+    let balance_before = account.balance;
+
+    let call = make_contract_call(
+        &spender_sk,
+        1,
+        1_000,
+        &spender_addr,
+        "counter",
+        "bump",
+        &[Value::UInt(1_000)],
+    );
+    let call_txid = StacksTransaction::consensus_deserialize(&mut &call[..])
+        .unwrap()
+        .txid();
+
+    let dry_run = dry_run_tx(&http_origin, &call);
+    assert!(dry_run.okay);
+    assert_eq!(
+        Value::try_deserialize_hex_untyped(&dry_run.result.clone().unwrap()[2..]).unwrap(),
+        Value::okay(Value::Int(1)).unwrap()
+    );
+    assert!(dry_run.execution_cost.runtime > 0);
+    assert_eq!(dry_run.events.len(), 1);
+
+    // the dry-run's burn and counter increment were discarded, so running it again gives the
+    // same answer
+    assert_eq!(
+        get_account(&http_origin, &spender_addr).balance,
+        balance_before
+    );
+    assert_eq!(dry_run_tx(&http_origin, &call), dry_run);
+
+    // now mine the very same transaction
+    test_observer::clear();
+    submit_tx(&http_origin, &call);
+
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    let account = get_account(&http_origin, &spender_addr);
+    assert_eq!(account.nonce, 2);
+    assert_eq!(account.balance, balance_before - 1_000 - 1_000);
+
+    let mined_cost: ExecutionCost = test_observer::get_blocks()
+        .iter()
+        .flat_map(|block| {
+            block
+                .get("transactions")
+                .unwrap()
+                .as_array()
+                .unwrap()
+                .clone()
+        })
+        .find(|tx| tx.get("txid").unwrap().as_str().unwrap() == format!("0x{}", call_txid))
+        .map(|tx| serde_json::from_value(tx.get("execution_cost").cloned().unwrap()).unwrap())
+        .expect("Contract-call was not mined");
+
+    assert_eq!(dry_run.execution_cost.runtime, mined_cost.runtime);
+
+    test_observer::clear();
+    channel.stop_chains_coordinator();
+}
+
+/// Every transaction in a `/new_block` payload reports all five dimensions of the execution cost
+/// it was charged, not just its runtime.
+#[test]
+#[ignore]
+fn event_observer_execution_cost_integration_test() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let contract_src = "
+    (define-data-var counter int 0)
+    (define-public (bump)
+        (begin
+            (var-set counter (+ (var-get counter) 1))
+            (ok (var-get counter))))
+    (define-public (peek)
+        (ok (var-get counter)))
+    ";
+
+    let spender_sk = StacksPrivateKey::new();
+    let spender_addr = to_addr(&spender_sk);
+    let spender_princ: PrincipalData = spender_addr.into();
+
+    let (mut conf, _) = neon_integration_test_conf();
+
+    test_observer::spawn();
+    conf.events_observers.push(EventObserverConfig {
+        endpoint: format!("localhost:{}", test_observer::EVENT_OBSERVER_PORT),
+        events_keys: vec![EventKeyType::AnyEvent],
+    });
+
+    conf.initial_balances.push(InitialBalance {
+        address: spender_princ.clone(),
+        amount: 10_000_000,
+    });
+
+    let mut btcd_controller = BitcoinCoreController::new(conf.clone());
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(conf.clone(), None);
+    let http_origin = format!("http://{}", &conf.node.rpc_bind);
+
+    btc_regtest_controller.bootstrap_chain(201);
+
+    eprintln!("Chain bootstrapped...");
+
+    let mut run_loop = neon::RunLoop::new(conf.clone());
+    let blocks_processed = run_loop.get_blocks_processed_arc();
+    let channel = run_loop.get_coordinator_channel().unwrap();
+
+    thread::spawn(move || run_loop.start(None, 0));
+
+    // give the run loop some time to start up!
+    wait_for_runloop(&blocks_processed);
+
+    // first block wakes up the run loop
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // first block will hold our VRF registration
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // second block will be the first mined Stacks block
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    let publish = make_contract_publish(&spender_sk, 0, 10_000, "counter", contract_src);
+    submit_tx(&http_origin, &publish);
+
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    assert_eq!(get_account(&http_origin, &spender_addr).nonce, 1);
+
+    let mut txids = HashMap::new();
+    for (nonce, function_name) in [(1, "bump"), (2, "peek")] {
+        let call = make_contract_call(
+            &spender_sk,
+            nonce,
+            1_000,
+            &spender_addr,
+            "counter",
+            function_name,
+            &[],
+        );
+        let txid = StacksTransaction::consensus_deserialize(&mut &call[..])
+            .unwrap()
+            .txid();
+        txids.insert(format!("0x{}", txid), function_name);
+        submit_tx(&http_origin, &call);
+    }
+
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    assert_eq!(get_account(&http_origin, &spender_addr).nonce, 3);
+
+    let mut costs = HashMap::new();
+    for block in test_observer::get_blocks() {
+        for tx in block.get("transactions").unwrap().as_array().unwrap() {
+            let execution_cost = tx.get("execution_cost").unwrap();
+            let dimensions = [
+                "runtime",
+                "read_count",
+                "read_length",
+                "write_count",
+                "write_length",
+            ];
+            for dimension in dimensions.iter() {
+                assert!(
+                    execution_cost.get(dimension).unwrap().is_u64(),
+                    "execution_cost.{} is not a number: {}",
+                    dimension,
+                    execution_cost
+                );
+            }
+
+            let txid = tx.get("txid").unwrap().as_str().unwrap();
+            if let Some(function_name) = txids.get(txid) {
+                let cost: ExecutionCost = serde_json::from_value(execution_cost.clone()).unwrap();
+                costs.insert(*function_name, cost);
+            }
+        }
+    }
+
+    // `bump` reads and writes the data var
+    let bump_cost = costs.get("bump").expect("`bump` was not mined");
+    assert!(bump_cost.runtime > 0);
+    assert!(bump_cost.read_count > 0);
+    assert!(bump_cost.read_length > 0);
+    assert!(bump_cost.write_count > 0);
+    assert!(bump_cost.write_length > 0);
+
+    // `peek` only reads it
+    let peek_cost = costs.get("peek").expect("`peek` was not mined");
+    assert!(peek_cost.runtime > 0);
+    assert!(peek_cost.read_count > 0);
+    assert!(peek_cost.read_length > 0);
+    assert_eq!(peek_cost.write_count, 0);
+    assert_eq!(peek_cost.write_length, 0);
+    assert!(peek_cost.read_count <= bump_cost.read_count);
+    assert!(bump_cost.runtime > peek_cost.runtime);
+
+    test_observer::clear();
+    channel.stop_chains_coordinator();
+}
+
+#[test]
+#[ignore]
+fn post_transaction_batch_integration_test() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let spender_sk = StacksPrivateKey::new();
+    let spender_addr = to_addr(&spender_sk);
+    let spender_princ: PrincipalData = spender_addr.into();
+    let recipient: PrincipalData = to_addr(&StacksPrivateKey::new()).into();
+
+    let (mut conf, _) = neon_integration_test_conf();
+
+    test_observer::spawn();
+    conf.events_observers.push(EventObserverConfig {
+        endpoint: format!("localhost:{}", test_observer::EVENT_OBSERVER_PORT),
+        events_keys: vec![EventKeyType::AnyEvent],
+    });
+
+    conf.initial_balances.push(InitialBalance {
+        address: spender_princ.clone(),
+        amount: 10_000_000,
+    });
+
+    let mut btcd_controller = BitcoinCoreController::new(conf.clone());
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(conf.clone(), None);
+    let http_origin = format!("http://{}", &conf.node.rpc_bind);
+
+    btc_regtest_controller.bootstrap_chain(201);
+
+    eprintln!("Chain bootstrapped...");
+
+    let mut run_loop = neon::RunLoop::new(conf.clone());
+    let blocks_processed = run_loop.get_blocks_processed_arc();
+    let channel = run_loop.get_coordinator_channel().unwrap();
+
+    thread::spawn(move || run_loop.start(None, 0));
+
+    // give the run loop some time to start up!
+    wait_for_runloop(&blocks_processed);
+
+    // first block wakes up the run loop
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // first block will hold our VRF registration
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // second block will be the first mined Stacks block
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    let transfers: Vec<_> = (0..20)
+        .map(|nonce| make_stacks_transfer(&spender_sk, nonce, 1_000, &recipient, 1_000))
+        .collect();
+    let mut txids = HashSet::new();
+    for transfer in transfers.iter() {
+        let txid = StacksTransaction::consensus_deserialize(&mut &transfer[..])
+            .unwrap()
+            .txid();
+        txids.insert(txid);
+    }
+
+    // a malformed transaction and a reused nonce in the same batch don't stop the transfers
+    // from being accepted
+    let mut batch = transfers.clone();
+    batch.push(vec![0x00, 0x01, 0x02]);
+    batch.push(make_stacks_transfer(
+        &spender_sk,
+        0,
+        1_000,
+        &recipient,
+        2_000,
+    ));
+
+    let results = submit_tx_batch(&http_origin, &batch);
+    assert_eq!(results.len(), 22);
+    for result in results[0..20].iter() {
+        assert!(result.accepted, "Transfer rejected: {:?}", result);
+        assert!(result.rejection.is_none());
+        assert!(txids.contains(&result.txid));
+    }
+    for (result, reason) in results[20..]
+        .iter()
+        .zip(["Deserialization", "ConflictingNonceInMempool"].iter())
+    {
+        assert!(!result.accepted);
+        let rejection = result.rejection.as_ref().unwrap();
+        assert_eq!(&rejection.error, *reason);
+    }
+
+    // resubmitting an already-accepted transaction is not an error
+    let results = submit_tx_batch(&http_origin, &transfers[0..1]);
+    assert_eq!(results.len(), 1);
+    assert!(results[0].accepted);
+
+    for _i in 0..10 {
+        next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+        if get_account(&http_origin, &spender_addr).nonce == 20 {
+            break;
+        }
+    }
+
+    let account = get_account(&http_origin, &spender_addr);
+    assert_eq!(account.nonce, 20);
+    assert_eq!(account.balance, 10_000_000 - 20 * (1_000 + 1_000));
+
+    let mined_txids: HashSet<_> = test_observer::get_blocks()
+        .iter()
+        .flat_map(|block| {
+            block
+                .get("transactions")
+                .unwrap()
+                .as_array()
+                .unwrap()
+                .clone()
+        })
+        .map(|tx| tx.get("txid").unwrap().as_str().unwrap().to_string())
+        .collect();
+    for txid in txids.iter() {
+        assert!(
+            mined_txids.contains(&format!("0x{}", txid)),
+            "Transfer {} was not mined",
+            txid
+        );
+    }
+
+    test_observer::clear();
+    channel.stop_chains_coordinator();
+}
+
+/// The read-only Clarity REPL sees the same state as the node it was pointed at, and cannot
+/// change it.
+#[test]
+#[ignore]
+fn clarity_repl_integration_test() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let spender_sk = StacksPrivateKey::new();
+    let spender_addr = to_addr(&spender_sk);
+    let spender_princ: PrincipalData = spender_addr.into();
+    let recipient: PrincipalData = to_addr(&StacksPrivateKey::new()).into();
+
+    let (mut conf, _) = neon_integration_test_conf();
+
+    conf.initial_balances.push(InitialBalance {
+        address: spender_princ.clone(),
+        amount: 100_000,
+    });
+
+    let mut btcd_controller = BitcoinCoreController::new(conf.clone());
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(conf.clone(), None);
+    let http_origin = format!("http://{}", &conf.node.rpc_bind);
+
+    btc_regtest_controller.bootstrap_chain(201);
+
+    eprintln!("Chain bootstrapped...");
+
+    let mut run_loop = neon::RunLoop::new(conf.clone());
+    let blocks_processed = run_loop.get_blocks_processed_arc();
+    let channel = run_loop.get_coordinator_channel().unwrap();
+
+    thread::spawn(move || run_loop.start(None, 0));
+
+    // give the run loop some time to start up!
+    wait_for_runloop(&blocks_processed);
+
+    // first block wakes up the run loop
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // first block will hold our VRF registration
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // second block will be the first mined Stacks block
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    let transfer = make_stacks_transfer(&spender_sk, 0, 1_000, &recipient, 1_234);
+    submit_tx(&http_origin, &transfer);
+
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    assert_eq!(get_account(&http_origin, &spender_addr).nonce, 1);
+
+    channel.stop_chains_coordinator();
+
+    let mut repl = ClarityRepl::open(&conf.get_chainstate_path_str(), None, None, None).unwrap();
+
+    let (value, cost) = repl
+        .eval(&format!("(stx-get-balance '{})", &recipient))
+        .unwrap();
+    assert_eq!(value, Value::UInt(1_234));
+    assert!(cost.runtime > 0);
+    assert!(cost.read_count > 0);
+    assert_eq!(cost.write_count, 0);
+
+    let (value, _) = repl
+        .eval(&format!("(stx-get-balance '{})", &spender_princ))
+        .unwrap();
+    assert_eq!(
+        value,
+        Value::UInt(get_balance(&http_origin, &spender_princ))
+    );
+
+    let mut output = vec![];
+    repl.run(&b"(+ 1 2)\n\n(unknown-function)\n"[..], &mut output)
+        .unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("> 3\ncost: "), "{}", &output);
+    assert!(output.contains("error: "), "{}", &output);
+
+    // evaluating in a contract's context can read its data, but not write it
+    let mut repl = ClarityRepl::open(
+        &conf.get_chainstate_path_str(),
+        None,
+        Some(boot_code_id("pox", false)),
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        repl.eval("(var-get configured)").unwrap().0,
+        Value::Bool(true)
+    );
+    assert!(repl.eval("(var-set configured false)").is_err());
+    assert_eq!(
+        repl.eval("(var-get configured)").unwrap().0,
+        Value::Bool(true)
+    );
+}
+
+/// With a mempool far larger than can be walked in the miner's time budget, each attempt still
+/// commits the partial block it assembled, and later attempts in the same tenure resume the
+/// mempool walk rather than starting it over.
+#[test]
+#[ignore]
+fn block_assembly_time_budget_integration_test() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let num_senders = 250;
+    let txs_per_sender = 20;
+    let sender_sks: Vec<_> = (0..num_senders).map(|_| StacksPrivateKey::new()).collect();
+    let recipient: PrincipalData = to_addr(&StacksPrivateKey::new()).into();
+
+    let (mut conf, _) = neon_integration_test_conf();
+
+    test_observer::spawn();
+    conf.events_observers.push(EventObserverConfig {
+        endpoint: format!("localhost:{}", test_observer::EVENT_OBSERVER_PORT),
+        events_keys: vec![EventKeyType::AnyEvent],
+    });
+
+    for sender_sk in sender_sks.iter() {
+        conf.initial_balances.push(InitialBalance {
+            address: to_addr(sender_sk).into(),
+            amount: 10_000_000,
+        });
+    }
+
+    conf.node.mine_microblocks = false;
+    conf.miner.first_attempt_time_ms = 100;
+    conf.miner.subsequent_attempt_time_ms = 100;
+
+    let mut btcd_controller = BitcoinCoreController::new(conf.clone());
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(conf.clone(), None);
+    let http_origin = format!("http://{}", &conf.node.rpc_bind);
+
+    btc_regtest_controller.bootstrap_chain(201);
+
+    eprintln!("Chain bootstrapped...");
+
+    let mut run_loop = neon::RunLoop::new(conf.clone());
+    let blocks_processed = run_loop.get_blocks_processed_arc();
+    let channel = run_loop.get_coordinator_channel().unwrap();
+
+    thread::spawn(move || run_loop.start(None, 0));
+
+    // give the run loop some time to start up!
+    wait_for_runloop(&blocks_processed);
+
+    // first block wakes up the run loop
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // first block will hold our VRF registration
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // second block will be the first mined Stacks block
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // 5,000 transfers, chained no deeper than the mempool allows
+    let transfers: Vec<_> = (0..txs_per_sender)
+        .flat_map(|nonce| {
+            let recipient = &recipient;
+            sender_sks
+                .iter()
+                .map(move |sender_sk| make_stacks_transfer(sender_sk, nonce, 1_000, recipient, 1))
+        })
+        .collect();
+    assert_eq!(transfers.len(), 5_000);
+
+    for batch in transfers.chunks(MAX_TRANSACTION_BATCH_LEN) {
+        for result in submit_tx_batch(&http_origin, batch) {
+            assert!(result.accepted, "Transfer rejected: {:?}", result);
+        }
+    }
+
+    for _i in 0..5 {
+        next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+    }
+
+    // the miner never had time to walk the whole mempool, but it still mined transfers
+    let blocks = test_observer::get_blocks();
+    let max_block_tx_count = blocks
+        .iter()
+        .map(|block| block.get("transactions").unwrap().as_array().unwrap().len())
+        .max()
+        .unwrap();
+    assert!(
+        max_block_tx_count > 1,
+        "Only mined coinbase-only blocks under the time budget"
+    );
+
+    let mined_nonces: u64 = sender_sks
+        .iter()
+        .map(|sender_sk| get_account(&http_origin, &to_addr(sender_sk)).nonce)
+        .sum();
+    assert!(mined_nonces > 0);
+
+    channel.stop_chains_coordinator();
+}
+
+/// Account state can be read as of past blocks on the canonical fork, by height or by index block
+/// hash, and blocks beyond the tip are reported as not found.
+#[test]
+#[ignore]
+fn get_account_until_block_integration_test() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let spender_sk = StacksPrivateKey::new();
+    let spender_addr: PrincipalData = to_addr(&spender_sk).into();
+    let recipient: PrincipalData = to_addr(&StacksPrivateKey::new()).into();
+
+    let (mut conf, _) = neon_integration_test_conf();
+    conf.initial_balances.push(InitialBalance {
+        address: spender_addr.clone(),
+        amount: 10_000_000,
+    });
+
+    let mut btcd_controller = BitcoinCoreController::new(conf.clone());
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(conf.clone(), None);
+    let http_origin = format!("http://{}", &conf.node.rpc_bind);
+
+    btc_regtest_controller.bootstrap_chain(201);
+
+    eprintln!("Chain bootstrapped...");
+
+    let mut run_loop = neon::RunLoop::new(conf.clone());
+    let blocks_processed = run_loop.get_blocks_processed_arc();
+    let channel = run_loop.get_coordinator_channel().unwrap();
+
+    thread::spawn(move || run_loop.start(None, 0));
+
+    // give the run loop some time to start up!
+    wait_for_runloop(&blocks_processed);
+
+    // first block wakes up the run loop
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // first block will hold our VRF registration
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // second block will be the first mined Stacks block
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    let before_info = get_chain_info(&conf);
+    let before_block_id = StacksBlockId::new(
+        &before_info.stacks_tip_consensus_hash,
+        &before_info.stacks_tip,
+    );
+
+    let transfer = make_stacks_transfer(&spender_sk, 0, 1_000, &recipient, 1_000_000);
+    submit_tx(&http_origin, &transfer);
+
+    for _i in 0..10 {
+        next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+        if get_account(&http_origin, &spender_addr).nonce == 1 {
+            break;
+        }
+    }
+    let after_info = get_chain_info(&conf);
+    let after_block_id = StacksBlockId::new(
+        &after_info.stacks_tip_consensus_hash,
+        &after_info.stacks_tip,
+    );
+    assert!(after_info.stacks_tip_height > before_info.stacks_tip_height);
+
+    // the spender's state before and after the transfer, by height and by block
+    for until_block in [
+        before_info.stacks_tip_height.to_string(),
+        before_block_id.to_string(),
+    ]
+    .iter()
+    {
+        let account = get_account_until_block(&http_origin, &spender_addr, until_block).unwrap();
+        assert_eq!(account.nonce, 0);
+        assert_eq!(account.balance, 10_000_000);
+
+        // the recipient had never been sent anything
+        let account = get_account_until_block(&http_origin, &recipient, until_block).unwrap();
+        assert_eq!(account.nonce, 0);
+        assert_eq!(account.balance, 0);
+    }
+    for until_block in [
+        after_info.stacks_tip_height.to_string(),
+        after_block_id.to_string(),
+    ]
+    .iter()
+    {
+        let account = get_account_until_block(&http_origin, &spender_addr, until_block).unwrap();
+        assert_eq!(account.nonce, 1);
+        assert_eq!(account.balance, 10_000_000 - 1_000_000 - 1_000);
+
+        let account = get_account_until_block(&http_origin, &recipient, until_block).unwrap();
+        assert_eq!(account.balance, 1_000_000);
+    }
+
+    // there is no block beyond the tip
+    assert!(get_account_until_block(
+        &http_origin,
+        &spender_addr,
+        &(after_info.stacks_tip_height + 100).to_string()
+    )
+    .is_none());
+
+    channel.stop_chains_coordinator();
+}
+
+/// Blocks fetched by height on the canonical fork are the same blocks that are fetched by their
+/// index block hashes, and heights beyond the tip are reported as not found.
+#[test]
+#[ignore]
+fn get_block_by_height_integration_test() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let (conf, _) = neon_integration_test_conf();
+
+    let mut btcd_controller = BitcoinCoreController::new(conf.clone());
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(conf.clone(), None);
+    let http_origin = format!("http://{}", &conf.node.rpc_bind);
+
+    btc_regtest_controller.bootstrap_chain(201);
+
+    eprintln!("Chain bootstrapped...");
+
+    let mut run_loop = neon::RunLoop::new(conf.clone());
+    let blocks_processed = run_loop.get_blocks_processed_arc();
+    let channel = run_loop.get_coordinator_channel().unwrap();
+
+    thread::spawn(move || run_loop.start(None, 0));
+
+    // give the run loop some time to start up!
+    wait_for_runloop(&blocks_processed);
+
+    // first block wakes up the run loop
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // first block will hold our VRF registration
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // mine a few Stacks blocks, and remember which block is at which height
+    let mut block_ids = HashMap::new();
+    for _i in 0..4 {
+        next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+        let info = get_chain_info(&conf);
+        block_ids.insert(
+            info.stacks_tip_height,
+            StacksBlockId::new(&info.stacks_tip_consensus_hash, &info.stacks_tip),
+        );
+    }
+    let tip_info = get_chain_info(&conf);
+    assert!(tip_info.stacks_tip_height >= 3);
+
+    for (height, block_id) in block_ids.iter() {
+        let block = get_block(&http_origin, block_id).unwrap();
+
+        let (block_by_height, microblocks_opt) =
+            get_block_by_height(&http_origin, *height, false).unwrap();
+        assert_eq!(block_by_height, block);
+        assert!(microblocks_opt.is_none());
+
+        // no microblocks are mined, so the block confirmed none
+        let (block_by_height, microblocks_opt) =
+            get_block_by_height(&http_origin, *height, true).unwrap();
+        assert_eq!(block_by_height, block);
+        assert_eq!(microblocks_opt, Some(vec![]));
+    }
+
+    // there is no block beyond the tip
+    assert!(get_block_by_height(&http_origin, tip_info.stacks_tip_height + 1, false).is_none());
+    assert!(get_block_by_height(&http_origin, tip_info.stacks_tip_height + 100, true).is_none());
+
+    channel.stop_chains_coordinator();
+}
+
+/// Get the (index block hash, parent index block hash, height) of each Stacks block the test
+/// observer has seen, in the order they were processed.
+fn get_observed_block_ids() -> Vec<(StacksBlockId, StacksBlockId, u64)> {
+    let parse_block_id = |block: &serde_json::Value, key: &str| {
+        let hex = block.get(key).unwrap().as_str().unwrap();
+        StacksBlockId::from_hex(&hex[2..]).unwrap()
+    };
+    test_observer::get_blocks()
+        .iter()
+        .map(|block| {
+            (
+                parse_block_id(block, "index_block_hash"),
+                parse_block_id(block, "parent_index_block_hash"),
+                block.get("block_height").unwrap().as_u64().unwrap(),
+            )
+        })
+        .collect()
+}
+
+/// Get the index block hashes of the observed children of `parent`, in the order they were
+/// processed.
+fn get_observed_children(parent: &StacksBlockId) -> Vec<StacksBlockId> {
+    get_observed_block_ids()
+        .into_iter()
+        .filter(|(_, parent_id, _)| parent_id == parent)
+        .map(|(block_id, _, _)| block_id)
+        .collect()
+}
+
+/// Tell the miner to build on a given parent, forcing two blocks off the same parent, and check
+/// that the canonical Stacks tip follows the fork with the most work.
+#[test]
+#[ignore]
+fn miner_parent_override_integration_test() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let (mut conf, _) = neon_integration_test_conf();
+
+    test_observer::spawn();
+    conf.events_observers.push(EventObserverConfig {
+        endpoint: format!("localhost:{}", test_observer::EVENT_OBSERVER_PORT),
+        events_keys: vec![EventKeyType::AnyEvent],
+    });
+
+    let mut btcd_controller = BitcoinCoreController::new(conf.clone());
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(conf.clone(), None);
+
+    btc_regtest_controller.bootstrap_chain(201);
+
+    eprintln!("Chain bootstrapped...");
+
+    let mut run_loop = neon::RunLoop::new(conf.clone());
+    let blocks_processed = run_loop.get_blocks_processed_arc();
+    let channel = run_loop.get_coordinator_channel().unwrap();
+    let parent_override = run_loop.get_miner_parent_override();
+
+    thread::spawn(move || run_loop.start(None, 0));
+
+    // give the run loop some time to start up!
+    wait_for_runloop(&blocks_processed);
+
+    // first block wakes up the run loop
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // first block will hold our VRF registration
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // a parent that isn't a processed block is ignored, and the miner keeps extending the tip
+    *parent_override.lock().unwrap() = Some(StacksBlockId([0x11; 32]));
+    for _i in 0..3 {
+        next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+    }
+    let tip_info = get_chain_info(&conf);
+    assert!(tip_info.stacks_tip_height >= 2);
+
+    // build every block on the current tip until it has two children
+    let fork_point = StacksBlockId::new(&tip_info.stacks_tip_consensus_hash, &tip_info.stacks_tip);
+    let fork_height = tip_info.stacks_tip_height;
+    *parent_override.lock().unwrap() = Some(fork_point.clone());
+
+    let mut siblings = vec![];
+    for _i in 0..10 {
+        next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+        siblings = get_observed_children(&fork_point);
+        if siblings.len() >= 2 {
+            break;
+        }
+    }
+    assert!(
+        siblings.len() >= 2,
+        "Expected competing blocks off of the fork point"
+    );
+
+    // the siblings have the same work score, so the first one to arrive stays canonical
+    let tip_info = get_chain_info(&conf);
+    assert_eq!(tip_info.stacks_tip_height, fork_height + 1);
+    let canonical_sibling =
+        StacksBlockId::new(&tip_info.stacks_tip_consensus_hash, &tip_info.stacks_tip);
+    assert_eq!(canonical_sibling, siblings[0]);
+
+    // extend a non-canonical sibling, so its fork has the most work
+    let other_sibling = siblings[1].clone();
+    *parent_override.lock().unwrap() = Some(other_sibling.clone());
+
+    let mut children = vec![];
+    for _i in 0..10 {
+        next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+        children = get_observed_children(&other_sibling);
+        if children.len() > 0 {
+            break;
+        }
+    }
+    assert!(
+        children.len() > 0,
+        "Expected a block off of the other sibling"
+    );
+
+    // the coordinator switched to the fork with the most work
+    let tip_info = get_chain_info(&conf);
+    assert_eq!(tip_info.stacks_tip_height, fork_height + 2);
+    let tip = StacksBlockId::new(&tip_info.stacks_tip_consensus_hash, &tip_info.stacks_tip);
+    assert!(children.contains(&tip));
+
+    // without the override, the miner goes back to extending the canonical tip
+    *parent_override.lock().unwrap() = None;
+    for _i in 0..3 {
+        next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+    }
+    let tip_info = get_chain_info(&conf);
+    assert!(tip_info.stacks_tip_height > fork_height + 2);
+
+    // the canonical fork goes through the sibling we extended
+    let parents: HashMap<_, _> = get_observed_block_ids()
+        .into_iter()
+        .map(|(block_id, parent_id, height)| (block_id, (parent_id, height)))
+        .collect();
+    let mut cursor = StacksBlockId::new(&tip_info.stacks_tip_consensus_hash, &tip_info.stacks_tip);
+    loop {
+        let (parent_id, height) = parents.get(&cursor).unwrap().clone();
+        if height == fork_height + 1 {
+            break;
+        }
+        cursor = parent_id;
+    }
+    assert_eq!(cursor, other_sibling);
+
+    channel.stop_chains_coordinator();
+}
+
+/// A contract's data vars and map entries can be read back over RPC after a contract-call sets
+/// them, and unknown data vars are reported as not found.
+#[test]
+#[ignore]
+fn read_contract_data_integration_test() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let contract_src = "
+(define-data-var db1 uint u0)
+(define-map db2 uint uint)
+(define-public (store (value uint))
+  (begin
+    (var-set db1 value)
+    (map-set db2 u1 value)
+    (ok value)))
+";
+
+    let spender_sk = StacksPrivateKey::new();
+    let spender_addr = to_addr(&spender_sk);
+    let spender_princ: PrincipalData = spender_addr.clone().into();
+
+    let (mut conf, _) = neon_integration_test_conf();
+    conf.initial_balances.push(InitialBalance {
+        address: spender_princ.clone(),
+        amount: 10_000_000,
+    });
+
+    let mut btcd_controller = BitcoinCoreController::new(conf.clone());
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(conf.clone(), None);
+    let http_origin = format!("http://{}", &conf.node.rpc_bind);
+
+    btc_regtest_controller.bootstrap_chain(201);
+
+    eprintln!("Chain bootstrapped...");
+
+    let mut run_loop = neon::RunLoop::new(conf.clone());
+    let blocks_processed = run_loop.get_blocks_processed_arc();
+    let channel = run_loop.get_coordinator_channel().unwrap();
+
+    thread::spawn(move || run_loop.start(None, 0));
+
+    // give the run loop some time to start up!
+    wait_for_runloop(&blocks_processed);
+
+    // first block wakes up the run loop
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // first block will hold our VRF registration
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // second block will be the first mined Stacks block
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    let publish = make_contract_publish(&spender_sk, 0, 10_000, "store", contract_src);
+    submit_tx(&http_origin, &publish);
+
+    for _i in 0..10 {
+        next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+        if get_account(&http_origin, &spender_princ).nonce == 1 {
+            break;
+        }
+    }
+
+    // freshly deployed, the data var holds its initial value and the map is empty
+    assert_eq!(
+        get_data_var(&http_origin, &spender_addr, "store", "db1"),
+        Some(Value::UInt(0))
+    );
+    assert_eq!(
+        get_map_entry(&http_origin, &spender_addr, "store", "db2", &Value::UInt(1)),
+        Value::none()
+    );
+
+    let call = make_contract_call(
+        &spender_sk,
+        1,
+        1_000,
+        &spender_addr,
+        "store",
+        "store",
+        &[Value::UInt(42)],
+    );
+    submit_tx(&http_origin, &call);
+
+    for _i in 0..10 {
+        next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+        if get_account(&http_origin, &spender_princ).nonce == 2 {
+            break;
+        }
+    }
+
+    assert_eq!(
+        get_data_var(&http_origin, &spender_addr, "store", "db1"),
+        Some(Value::UInt(42))
+    );
+    assert_eq!(
+        get_map_entry(&http_origin, &spender_addr, "store", "db2", &Value::UInt(1)),
+        Value::some(Value::UInt(42)).unwrap()
+    );
+
+    // neither an undefined var nor an undeployed contract can be read
+    assert!(get_data_var(&http_origin, &spender_addr, "store", "db3").is_none());
+    assert!(get_data_var(&http_origin, &spender_addr, "no-such-contract", "db1").is_none());
+
+    channel.stop_chains_coordinator();
+}
+
+/// A wallet can chain transactions using `possible_next_nonce` from `/v2/accounts`, which accounts
+/// for the sender's transactions in the mempool and reports the first gap in their nonces.
+#[test]
+#[ignore]
+fn account_possible_next_nonce_integration_test() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let spender_sk = StacksPrivateKey::new();
+    let spender_addr: PrincipalData = to_addr(&spender_sk).into();
+    let recipient: PrincipalData = to_addr(&StacksPrivateKey::new()).into();
+
+    let (mut conf, _) = neon_integration_test_conf();
+    conf.initial_balances.push(InitialBalance {
+        address: spender_addr.clone(),
+        amount: 10_000_000,
+    });
+
+    let mut btcd_controller = BitcoinCoreController::new(conf.clone());
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(conf.clone(), None);
+    let http_origin = format!("http://{}", &conf.node.rpc_bind);
+
+    btc_regtest_controller.bootstrap_chain(201);
+
+    eprintln!("Chain bootstrapped...");
+
+    let mut run_loop = neon::RunLoop::new(conf.clone());
+    let blocks_processed = run_loop.get_blocks_processed_arc();
+    let channel = run_loop.get_coordinator_channel().unwrap();
+
+    thread::spawn(move || run_loop.start(None, 0));
+
+    // give the run loop some time to start up!
+    wait_for_runloop(&blocks_processed);
+
+    // first block wakes up the run loop
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // first block will hold our VRF registration
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // second block will be the first mined Stacks block
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    let account = get_account(&http_origin, &spender_addr);
+    assert_eq!(account.nonce, 0);
+    assert_eq!(get_possible_next_nonce(&http_origin, &spender_addr), 0);
+
+    // chain three transfers before any of them is mined, each using the nonce the node reports
+    for i in 0..3 {
+        let nonce = get_possible_next_nonce(&http_origin, &spender_addr);
+        assert_eq!(nonce, i);
+        let transfer = make_stacks_transfer(&spender_sk, nonce, 1_000, &recipient, 1_000);
+        submit_tx(&http_origin, &transfer);
+        assert_eq!(get_possible_next_nonce(&http_origin, &spender_addr), i + 1);
+    }
+
+    // skipping a nonce leaves a gap, which is reported instead of the highest nonce plus one
+    let transfer = make_stacks_transfer(&spender_sk, 4, 1_000, &recipient, 1_000);
+    submit_tx(&http_origin, &transfer);
+    assert_eq!(get_possible_next_nonce(&http_origin, &spender_addr), 3);
+
+    // once the chained transfers are mined, the gap is still the next possible nonce
+    for _i in 0..10 {
+        next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+        if get_account(&http_origin, &spender_addr).nonce == 3 {
+            break;
+        }
+    }
+    assert_eq!(get_account(&http_origin, &spender_addr).nonce, 3);
+    assert_eq!(get_possible_next_nonce(&http_origin, &spender_addr), 3);
+
+    channel.stop_chains_coordinator();
+}
+
+/// Rejected transaction submissions report a stable reason code and the details a wallet needs to
+/// fix the transaction, so that a nonce problem can be told apart from a fee problem.
+#[test]
+#[ignore]
+fn submit_tx_rejection_reasons_integration_test() {
+    if env::var("BITCOIND_TEST") != Ok("1".into()) {
+        return;
+    }
+
+    let spender_sk = StacksPrivateKey::new();
+    let spender_addr: PrincipalData = to_addr(&spender_sk).into();
+    let recipient: PrincipalData = to_addr(&StacksPrivateKey::new()).into();
+
+    let (mut conf, _) = neon_integration_test_conf();
+    conf.initial_balances.push(InitialBalance {
+        address: spender_addr.clone(),
+        amount: 10_000_000,
+    });
+
+    let mut btcd_controller = BitcoinCoreController::new(conf.clone());
+    btcd_controller
+        .start_bitcoind()
+        .map_err(|_e| ())
+        .expect("Failed starting bitcoind");
+
+    let mut btc_regtest_controller = BitcoinRegtestController::new(conf.clone(), None);
+    let http_origin = format!("http://{}", &conf.node.rpc_bind);
+
+    btc_regtest_controller.bootstrap_chain(201);
+
+    eprintln!("Chain bootstrapped...");
+
+    let mut run_loop = neon::RunLoop::new(conf.clone());
+    let blocks_processed = run_loop.get_blocks_processed_arc();
+    let channel = run_loop.get_coordinator_channel().unwrap();
+
+    thread::spawn(move || run_loop.start(None, 0));
+
+    // give the run loop some time to start up!
+    wait_for_runloop(&blocks_processed);
+
+    // first block wakes up the run loop
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // first block will hold our VRF registration
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // second block will be the first mined Stacks block
+    next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+
+    // a transaction that pays no fee is rejected for its fee
+    let transfer = make_stacks_transfer(&spender_sk, 0, 0, &recipient, 1000);
+    let error = submit_tx_fallible(&http_origin, &transfer).unwrap_err();
+    assert_eq!(error.error, "FeeTooLow");
+    assert!(!error.reason.is_empty());
+    let reason_data = error.reason_data.unwrap();
+    assert_eq!(reason_data.get("actual").unwrap().as_u64().unwrap(), 0);
+    assert!(reason_data.get("expected").unwrap().as_u64().unwrap() > 0);
+
+    let transfer = make_stacks_transfer(&spender_sk, 0, 1000, &recipient, 1000);
+    submit_tx(&http_origin, &transfer);
+
+    for _i in 0..10 {
+        next_block_and_wait(&mut btc_regtest_controller, &blocks_processed);
+        if get_account(&http_origin, &spender_addr).nonce == 1 {
+            break;
+        }
+    }
+    assert_eq!(get_account(&http_origin, &spender_addr).nonce, 1);
+
+    // reusing the spent nonce is rejected for its nonce, and the node says which nonce to use
+    let transfer = make_stacks_transfer(&spender_sk, 0, 2000, &recipient, 1000);
+    let txid = StacksTransaction::consensus_deserialize(&mut &transfer[..])
+        .unwrap()
+        .txid();
+    let error = submit_tx_fallible(&http_origin, &transfer).unwrap_err();
+    assert_eq!(error.txid, Some(txid));
+    assert_eq!(error.error, "BadNonce");
+    assert!(!error.reason.is_empty());
+    let reason_data = error.reason_data.unwrap();
+    assert_eq!(reason_data.get("expected").unwrap().as_u64().unwrap(), 1);
+    assert_eq!(reason_data.get("actual").unwrap().as_u64().unwrap(), 0);
+    assert_eq!(
+        reason_data.get("principal").unwrap().as_str().unwrap(),
+        &spender_addr.to_string()
+    );
+    assert!(reason_data.get("is_origin").unwrap().as_bool().unwrap());
+
+    channel.stop_chains_coordinator();
+}